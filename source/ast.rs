@@ -31,8 +31,15 @@
 
 //! Structures that will constitute the Abstract Syntax Tree.
 
+use std::borrow::Cow;
+use std::fmt;
+use std::ops::BitOr;
+use std::str;
+use super::span::Spanned;
+
 /// A term.
 #[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Term {
     /// The term value.
     pub t: Literal
@@ -40,6 +47,7 @@ pub struct Term {
 
 /// An addition of two terms.
 #[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Addition {
     /// The left-hand side of the addition.
     pub a: Term,
@@ -49,6 +57,7 @@ pub struct Addition {
 
 /// A literal represents a fixed value, aka an atom.
 #[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Literal {
     /// A boolean, either `true` or `false`.
     ///
@@ -88,6 +97,40 @@ pub enum Literal {
     /// ```
     Integer(i64),
 
+    /// A nowdoc, i.e. a string delimited by a label where nothing is
+    /// escaped, like `<<<'EOT'` and `EOT`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tagua_parser;
+    /// use tagua_parser::Result;
+    /// use tagua_parser::ast::Literal;
+    /// use tagua_parser::rules::literals::literal;
+    ///
+    /// # fn main () {
+    /// assert_eq!(
+    ///     literal(b"<<<'EOT'\n    foo\\n\n      bar\n    EOT"),
+    ///     Result::Done(
+    ///         &b""[..],
+    ///         Literal::Nowdoc {
+    ///             label      : b"EOT".to_vec(),
+    ///             indentation: b"    ".to_vec(),
+    ///             value      : b"foo\\n\n  bar".to_vec()
+    ///         }
+    ///     )
+    /// );
+    /// # }
+    /// ```
+    Nowdoc {
+        /// The label delimiting the nowdoc.
+        label: Vec<u8>,
+        /// The indentation of the closing label, removed from each line.
+        indentation: Vec<u8>,
+        /// The content, without the indentation.
+        value: Vec<u8>
+    },
+
     /// A null value.
     ///
     /// # Examples
@@ -144,30 +187,132 @@ pub enum Literal {
     String(Vec<u8>)
 }
 
+impl Literal {
+    /// View a string literal or a nowdoc as a string, if it is valid UTF-8.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tagua_parser::ast::Literal;
+    ///
+    /// assert_eq!(Literal::String(b"foo".to_vec()).to_str(), Some("foo"));
+    /// assert_eq!(Literal::String(b"\xff".to_vec()).to_str(), None);
+    /// assert_eq!(Literal::Integer(42).to_str(), None);
+    /// ```
+    pub fn to_str(&self) -> Option<&str> {
+        match *self {
+            Literal::String(ref string) |
+            Literal::Nowdoc { value: ref string, .. } => str::from_utf8(string).ok(),
+            _ => None
+        }
+    }
+}
 
-/// A variable.
-///
-/// # Examples
-///
-/// ```
-/// # extern crate tagua_parser;
-/// use tagua_parser::Result;
-/// use tagua_parser::ast::Variable;
-/// use tagua_parser::rules::tokens::variable;
-///
-/// # fn main () {
-/// assert_eq!(
-///     variable(b"$foo"),
-///     Result::Done(&b""[..], Variable(&b"foo"[..]))
-/// );
-/// # }
-/// ```
-/// Note that the `$` is not present.
+impl<'a> From<&'a str> for Literal {
+    /// Create a string literal.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tagua_parser::ast::Literal;
+    ///
+    /// assert_eq!(Literal::from("foo"), Literal::String(b"foo".to_vec()));
+    /// ```
+    fn from(string: &'a str) -> Literal {
+        Literal::String(string.as_bytes().to_vec())
+    }
+}
+
+
+/// A variable. Note that the `$` is not present.
 #[derive(Debug, PartialEq)]
-pub struct Variable<'a>(pub &'a [u8]);
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Variable<'a> {
+    /// A variable with a fixed name, like `$foo`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tagua_parser;
+    /// use tagua_parser::Result;
+    /// use tagua_parser::ast::Variable;
+    /// use tagua_parser::rules::tokens::variable;
+    ///
+    /// # fn main () {
+    /// assert_eq!(
+    ///     variable(b"$foo"),
+    ///     Result::Done(&b""[..], Variable::Name(&b"foo"[..]))
+    /// );
+    /// # }
+    /// ```
+    Name(&'a [u8]),
+
+    /// A variable variable, i.e. a variable whose name is the value of an
+    /// expression, like `$$foo` or `${'foo' . $bar}`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tagua_parser;
+    /// use tagua_parser::Result;
+    /// use tagua_parser::ast::{Expression, Variable};
+    /// use tagua_parser::rules::expressions::primaries::dynamic_variable;
+    /// use tagua_parser::span::Spanned;
+    ///
+    /// # fn main () {
+    /// assert_eq!(
+    ///     dynamic_variable(b"$$foo"),
+    ///     Result::Done(
+    ///         &b""[..],
+    ///         Variable::Dynamic(
+    ///             Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..]))))
+    ///         )
+    ///     )
+    /// );
+    /// # }
+    /// ```
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    Dynamic(Box<Spanned<Expression<'a>>>)
+}
+
+impl<'a> Variable<'a> {
+    /// View the name of a variable as a string, if it has a fixed name
+    /// that is valid UTF-8.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tagua_parser::ast::Variable;
+    ///
+    /// assert_eq!(Variable::Name(&b"foo"[..]).to_str(), Some("foo"));
+    /// assert_eq!(Variable::Name(&b"caf\xe9"[..]).to_str(), None);
+    /// ```
+    pub fn to_str(&self) -> Option<&'a str> {
+        match *self {
+            Variable::Name(name) => str::from_utf8(name).ok(),
+            _ => None
+        }
+    }
+}
+
+impl<'a> From<&'a str> for Variable<'a> {
+    /// Create a variable with a fixed name, without the `$`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tagua_parser::ast::Variable;
+    ///
+    /// assert_eq!(Variable::from("foo"), Variable::Name(&b"foo"[..]));
+    /// ```
+    fn from(name: &'a str) -> Variable<'a> {
+        Variable::Name(name.as_bytes())
+    }
+}
 
 /// A name represents an entity name.
 #[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Name<'a> {
     /// An unqualified name, i.e. a name without a namespace, like `Bar`.
     ///
@@ -206,6 +351,7 @@ pub enum Name<'a> {
     /// );
     /// # }
     /// ```
+    #[cfg_attr(feature = "serde", serde(borrow))]
     Qualified(Vec<&'a [u8]>),
 
     /// A relative qualified name, i.e. a name in a relative namespace
@@ -227,6 +373,7 @@ pub enum Name<'a> {
     /// # }
     /// ```
     /// Note that the `namespace` part is not present.
+    #[cfg_attr(feature = "serde", serde(borrow))]
     RelativeQualified(Vec<&'a [u8]>),
 
     /// A fully qualified name, i.e. a name in an absolute namespace, like
@@ -248,12 +395,373 @@ pub enum Name<'a> {
     /// # }
     /// ```
     /// Note that the leading `\` part is not present.
+    #[cfg_attr(feature = "serde", serde(borrow))]
     FullyQualified(Vec<&'a [u8]>)
 }
 
+impl<'a> Name<'a> {
+    /// View the name as in the source, if it is valid UTF-8. Only an
+    /// unqualified name is borrowed, the other names are joined.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tagua_parser::ast::Name;
+    ///
+    /// assert_eq!(Name::Unqualified(&b"Foo"[..]).to_str().unwrap(), "Foo");
+    /// assert_eq!(Name::Qualified(vec![&b"Foo"[..], &b"Bar"[..]]).to_str().unwrap(), "Foo\\Bar");
+    /// assert_eq!(Name::RelativeQualified(vec![&b"Foo"[..]]).to_str().unwrap(), "namespace\\Foo");
+    /// assert_eq!(Name::FullyQualified(vec![&b"Foo"[..]]).to_str().unwrap(), "\\Foo");
+    /// assert_eq!(Name::Unqualified(&b"\xff"[..]).to_str(), None);
+    /// ```
+    pub fn to_str(&self) -> Option<Cow<'a, str>> {
+        let (prefix, parts) = match *self {
+            Name::Unqualified(name) => return str::from_utf8(name).ok().map(Cow::Borrowed),
+            Name::Qualified(ref parts) => ("", parts),
+            Name::RelativeQualified(ref parts) => ("namespace\\", parts),
+            Name::FullyQualified(ref parts) => ("\\", parts)
+        };
+        let mut output = prefix.to_string();
+
+        for (index, part) in parts.iter().enumerate() {
+            if index > 0 {
+                output.push('\\');
+            }
+
+            match str::from_utf8(part) {
+                Ok(part) => output.push_str(part),
+                Err(_) => return None
+            }
+        }
+
+        Some(Cow::Owned(output))
+    }
+}
+
+impl<'a> From<&'a str> for Name<'a> {
+    /// Create a name written as in the source, like `Foo\Bar`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tagua_parser::ast::Name;
+    ///
+    /// assert_eq!(Name::from("Foo"), Name::Unqualified(&b"Foo"[..]));
+    /// assert_eq!(Name::from("Foo\\Bar"), Name::Qualified(vec![&b"Foo"[..], &b"Bar"[..]]));
+    /// assert_eq!(Name::from("namespace\\Foo"), Name::RelativeQualified(vec![&b"Foo"[..]]));
+    /// assert_eq!(Name::from("\\Foo"), Name::FullyQualified(vec![&b"Foo"[..]]));
+    /// ```
+    fn from(name: &'a str) -> Name<'a> {
+        let name = name.as_bytes();
+
+        if name.starts_with(b"\\") {
+            Name::FullyQualified(name[1..].split(|byte| *byte == b'\\').collect())
+        } else if name.len() > 10 && name[..10].eq_ignore_ascii_case(b"namespace\\") {
+            Name::RelativeQualified(name[10..].split(|byte| *byte == b'\\').collect())
+        } else if name.contains(&b'\\') {
+            Name::Qualified(name.split(|byte| *byte == b'\\').collect())
+        } else {
+            Name::Unqualified(name)
+        }
+    }
+}
+
+/// A cast type, i.e. the targeted type of a cast. See `Expression::Cast`.
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum CastType {
+    /// An array, from `(array)`.
+    Array,
+    /// A boolean, from `(bool)` or `(boolean)`.
+    Boolean,
+    /// An integer, from `(int)` or `(integer)`.
+    Integer,
+    /// An object, from `(object)`.
+    Object,
+    /// A real, from `(float)`, `(double)` or `(real)`.
+    Real,
+    /// A string, from `(string)`.
+    String,
+    /// A null value, from `(unset)`.
+    Unset
+}
+
+/// The kind of a file inclusion. See `Expression::Include`.
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum IncludeKind {
+    /// Include a file, or emit a warning if it does not exist, i.e.
+    /// `include`.
+    Include,
+    /// Include a file only if it has not been included yet, i.e.
+    /// `include_once`.
+    IncludeOnce,
+    /// Include a file, or raise a fatal error if it does not exist, i.e.
+    /// `require`.
+    Require,
+    /// Include a file only if it has not been included yet, or raise a
+    /// fatal error if it does not exist, i.e. `require_once`.
+    RequireOnce
+}
+
+/// A relative scope, i.e. a reference to a class relatively to the current
+/// class.
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum RelativeScope {
+    /// The current class, i.e. `self`.
+    Current,
+    /// The parent class, i.e. `parent`.
+    Parent,
+    /// The class that was called at runtime (late static binding), i.e.
+    /// `static`.
+    Static
+}
+
+/// A magic constant, i.e. a constant whose value depends on where it is
+/// used.
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum MagicConstant {
+    /// The name of the current class, i.e. `__CLASS__`.
+    Class,
+    /// The directory of the current file, i.e. `__DIR__`.
+    Directory,
+    /// The full path of the current file, i.e. `__FILE__`.
+    File,
+    /// The name of the current function, i.e. `__FUNCTION__`.
+    Function,
+    /// The current line number, i.e. `__LINE__`.
+    Line,
+    /// The name of the current method, i.e. `__METHOD__`.
+    Method,
+    /// The name of the current namespace, i.e. `__NAMESPACE__`.
+    Namespace,
+    /// The name of the current trait, i.e. `__TRAIT__`.
+    Trait
+}
+
+/// A type declaration, e.g. the type of a parameter or the return type of
+/// a function.
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Type<'a> {
+    /// The array type, i.e. `array`.
+    Array,
+    /// The boolean type, i.e. `bool`.
+    Bool,
+    /// The callable type, i.e. `callable`.
+    Callable,
+    /// The current class, i.e. `self`.
+    Current,
+    /// The `false` type.
+    False,
+    /// The real type, i.e. `float`.
+    Float,
+    /// The integer type, i.e. `int`.
+    Int,
+    /// A type accepting values of all its types, e.g. `Countable&Traversable`.
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    Intersection(Vec<Spanned<Type<'a>>>),
+    /// The iterable type, i.e. `iterable`.
+    Iterable,
+    /// Any type, i.e. `mixed`.
+    Mixed,
+    /// A named type, i.e. a class or an interface, e.g. `Foo\Bar`.
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    Name(Name<'a>),
+    /// The type of a function never returning, i.e. `never`.
+    Never,
+    /// The `null` type.
+    Null,
+    /// A type also accepting `null`, e.g. `?int`.
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    Nullable(Box<Spanned<Type<'a>>>),
+    /// The object type, i.e. `object`.
+    Object,
+    /// The parent class, i.e. `parent`.
+    Parent,
+    /// The class that was called at runtime (late static binding), i.e.
+    /// `static`.
+    Static,
+    /// The string type, i.e. `string`.
+    String,
+    /// The `true` type.
+    True,
+    /// A type accepting any of its types, e.g. `int|string`.
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    Union(Vec<Spanned<Type<'a>>>),
+    /// The type of a function returning nothing, i.e. `void`.
+    Void
+}
+
+/// A constant expression, i.e. an expression that can be evaluated at
+/// compile time, e.g. the default value of a parameter. It is restricted to
+/// literals, arrays, constants, class constants and operators applied on
+/// them.
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ConstantExpression<'a>(
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub Spanned<Expression<'a>>
+);
+
+/// A parameter of a function, e.g. `int &$x = 42`.
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Parameter<'a> {
+    /// The attributes.
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub attributes: Vec<Attribute<'a>>,
+    /// The visibility of the property the parameter is promoted to, if
+    /// any, e.g. `private int $x` in a constructor.
+    pub visibility: Option<Visibility>,
+    /// The write visibility of the property the parameter is promoted to,
+    /// if it differs from its visibility, e.g. `private(set) int $x` in a
+    /// constructor.
+    pub set_visibility: Option<Visibility>,
+    /// Whether the property the parameter is promoted to is readonly, e.g.
+    /// `readonly int $x` in a constructor.
+    pub is_readonly: bool,
+    /// The type of the parameter, if declared.
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub ty: Option<Spanned<Type<'a>>>,
+    /// Whether the argument is passed by reference, i.e. `&$x`.
+    pub is_reference: bool,
+    /// Whether the parameter collects all remaining arguments, i.e. `...$x`.
+    pub is_variadic: bool,
+    /// The name of the parameter.
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub name: Variable<'a>,
+    /// The default value, if any.
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub value: Option<ConstantExpression<'a>>,
+    /// The hooks of the property the parameter is promoted to, e.g.
+    /// `public int $x { get => 42; }` in a constructor.
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub hooks: Vec<PropertyHook<'a>>
+}
+
+/// An argument given to a call, e.g. `42`, `foo: 42` when named, or
+/// `...$foo` when unpacked.
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Argument<'a> {
+    /// The name of the parameter to bind the argument to, if named.
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub name: Option<&'a [u8]>,
+    /// Whether the argument is unpacked into many arguments, e.g.
+    /// `...$foo`.
+    pub is_unpacked: bool,
+    /// The value of the argument.
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub value: Spanned<Expression<'a>>
+}
+
+/// An attribute, i.e. structured metadata attached to a declaration, e.g.
+/// `#[Foo(42, bar: 'baz')]`.
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Attribute<'a> {
+    /// The name of the attribute class.
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub name: Spanned<Name<'a>>,
+    /// The arguments given to the constructor of the attribute class.
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub arguments: Vec<Spanned<Argument<'a>>>
+}
+
+/// An arm of a match expression, e.g. `1, 2 => 'foo'`.
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MatchArm<'a> {
+    /// The conditions to compare the subject with, or `None` for the
+    /// `default` arm.
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub conditions: Option<Vec<Spanned<Expression<'a>>>>,
+    /// The expression to evaluate when a condition is identical to the
+    /// subject.
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub body: Spanned<Expression<'a>>
+}
+
+/// A part of an interpolated string.
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum StringPart<'a> {
+    /// A sequence of bytes, where escape sequences have been evaluated.
+    Bytes(Vec<u8>),
+    /// An embedded expression, e.g. `$foo`, `$foo[42]` or `$foo->bar`.
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    Expression(Spanned<Expression<'a>>)
+}
+
 /// An expression.
 #[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Expression<'a> {
+    /// Anonymous class.
+    /// Create a new instance of a class declared inline, with no name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tagua_parser;
+    /// use tagua_parser::Result;
+    /// use tagua_parser::ast::{Argument, ClassMember, ConstantExpression, Expression, Literal, Name, Variable, Visibility};
+    /// use tagua_parser::rules::expressions::expression;
+    /// use tagua_parser::span::Spanned;
+    ///
+    /// # fn main () {
+    /// assert_eq!(
+    ///     expression(b"new class ($foo) extends Bar implements Baz { const QUX = 42; }"),
+    ///     Result::Done(
+    ///         &b""[..],
+    ///         Expression::AnonymousClass {
+    ///             arguments : vec![
+    ///                 Spanned::from(Argument {
+    ///                     name       : None,
+    ///                     is_unpacked: false,
+    ///                     value      : Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..])))
+    ///                 })
+    ///             ],
+    ///             extends   : Some(Spanned::from(Name::Unqualified(&b"Bar"[..]))),
+    ///             implements: vec![Spanned::from(Name::Unqualified(&b"Baz"[..]))],
+    ///             members   : vec![
+    ///                 Spanned::from(ClassMember::Constant {
+    ///                     doc_comment: None,
+    ///                     attributes : vec![],
+    ///                     visibility : Visibility::Public,
+    ///                     is_final   : false,
+    ///                     ty         : None,
+    ///                     constants  : vec![
+    ///                         (
+    ///                             &b"QUX"[..],
+    ///                             ConstantExpression(Spanned::from(Expression::Literal(Literal::Integer(42))))
+    ///                         )
+    ///                     ]
+    ///                 })
+    ///             ]
+    ///         }
+    ///     )
+    /// );
+    /// # }
+    /// ```
+    AnonymousClass {
+        /// The arguments given to the constructor.
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        arguments: Vec<Spanned<Argument<'a>>>,
+        /// The parent class.
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        extends: Option<Spanned<Name<'a>>>,
+        /// The implemented interfaces.
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        implements: Vec<Spanned<Name<'a>>>,
+        /// The members, like for a class declaration.
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        members: Vec<Spanned<ClassMember<'a>>>
+    },
+
     /// Array.
     /// A collection of heterogeneous pairs (key, value). The key is
     /// optional.
@@ -265,6 +773,7 @@ pub enum Expression<'a> {
     /// use tagua_parser::Result;
     /// use tagua_parser::ast::{Expression, Literal, Variable};
     /// use tagua_parser::rules::expressions::expression;
+    /// use tagua_parser::span::Spanned;
     ///
     /// # fn main () {
     /// assert_eq!(
@@ -275,15 +784,15 @@ pub enum Expression<'a> {
     ///             vec![
     ///                 (
     ///                     None,
-    ///                     Expression::Literal(Literal::String(b"foo".to_vec()))
+    ///                     Spanned::from(Expression::Literal(Literal::String(b"foo".to_vec())))
     ///                 ),
     ///                 (
-    ///                     Some(Expression::Literal(Literal::Integer(42i64))),
-    ///                     Expression::Literal(Literal::String(b"bar".to_vec()))
+    ///                     Some(Spanned::from(Expression::Literal(Literal::Integer(42i64)))),
+    ///                     Spanned::from(Expression::Literal(Literal::String(b"bar".to_vec())))
     ///                 ),
     ///                 (
-    ///                     Some(Expression::Literal(Literal::String(b"baz".to_vec()))),
-    ///                     Expression::Variable(Variable(&b"qux"[..]))
+    ///                     Some(Spanned::from(Expression::Literal(Literal::String(b"baz".to_vec())))),
+    ///                     Spanned::from(Expression::Variable(Variable::Name(&b"qux"[..])))
     ///                 )
     ///             ]
     ///         )
@@ -291,12 +800,12 @@ pub enum Expression<'a> {
     /// );
     /// # }
     /// ```
-    Array(Vec<(Option<Expression<'a>>, Expression<'a>)>),
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    Array(Vec<(Option<Spanned<Expression<'a>>>, Spanned<Expression<'a>>)>),
 
-    /// An echo.
-    /// Echo converts each of its expression's values into strings,
-    /// concatenates them in order given, and writes the result to the
-    /// output stream.
+    /// Array access.
+    /// Access an element of an array, or push a new element into it when
+    /// the index is absent, e.g. `$foo[] = 42`.
     ///
     /// # Examples
     ///
@@ -305,316 +814,674 @@ pub enum Expression<'a> {
     /// use tagua_parser::Result;
     /// use tagua_parser::ast::{Expression, Literal, Variable};
     /// use tagua_parser::rules::expressions::expression;
+    /// use tagua_parser::span::Spanned;
     ///
     /// # fn main () {
     /// assert_eq!(
-    ///     expression(b"echo 'foobar', $bazqux, 42"),
+    ///     expression(b"$foo[42]"),
     ///     Result::Done(
     ///         &b""[..],
-    ///         Expression::Echo(
-    ///             vec![
-    ///                 Expression::Literal(Literal::String(b"foobar".to_vec())),
-    ///                 Expression::Variable(Variable(&b"bazqux"[..])),
-    ///                 Expression::Literal(Literal::Integer(42i64))
-    ///             ]
-    ///         )
+    ///         Expression::ArrayAccess {
+    ///             array: Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..])))),
+    ///             index: Some(Box::new(Spanned::from(Expression::Literal(Literal::Integer(42i64)))))
+    ///         }
     ///     )
     /// );
     /// # }
     /// ```
-    Echo(Vec<Expression<'a>>),
+    ArrayAccess {
+        /// The array to access.
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        array: Box<Spanned<Expression<'a>>>,
+        /// The index of the element, if any.
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        index: Option<Box<Spanned<Expression<'a>>>>
+    },
 
-    /// Empty.
-    /// Returns `TRUE` if the variable or value designated by the
-    /// expression is empty.
+    /// Arrow function.
+    /// Create an anonymous function whose body is a single expression. All
+    /// the variables of the enclosing scope are captured by value.
     ///
     /// # Examples
     ///
     /// ```
     /// # extern crate tagua_parser;
     /// use tagua_parser::Result;
-    /// use tagua_parser::ast::{Expression, Literal};
+    /// use tagua_parser::ast::{Expression, Parameter, Variable};
     /// use tagua_parser::rules::expressions::expression;
+    /// use tagua_parser::span::Spanned;
     ///
     /// # fn main () {
     /// assert_eq!(
-    ///     expression(b"empty('')"),
+    ///     expression(b"fn ($foo) => $foo"),
     ///     Result::Done(
     ///         &b""[..],
-    ///         Expression::Empty(
-    ///             Box::new(
-    ///                 Expression::Literal(
-    ///                     Literal::String(b"".to_vec())
-    ///                 )
-    ///             )
-    ///         )
+    ///         Expression::ArrowFunction {
+    ///             is_static        : false,
+    ///             returns_reference: false,
+    ///             parameters       : vec![
+    ///                 Spanned::from(Parameter {
+    ///                     attributes    : vec![],
+    ///                     visibility    : None,
+    ///                     set_visibility: None,
+    ///                     is_readonly   : false,
+    ///                     ty            : None,
+    ///                     is_reference  : false,
+    ///                     is_variadic   : false,
+    ///                     name          : Variable::Name(&b"foo"[..]),
+    ///                     value         : None,
+    ///                     hooks         : vec![]
+    ///                 })
+    ///             ],
+    ///             return_type      : None,
+    ///             body             : Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..]))))
+    ///         }
     ///     )
     /// );
     /// # }
     /// ```
-    Empty(Box<Expression<'a>>),
+    ArrowFunction {
+        /// Whether the arrow function is static, i.e. not bound to `$this`.
+        is_static: bool,
+        /// Whether the arrow function returns a reference, i.e. `fn &() =>`.
+        returns_reference: bool,
+        /// The parameters.
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        parameters: Vec<Spanned<Parameter<'a>>>,
+        /// The return type, if declared.
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        return_type: Option<Spanned<Type<'a>>>,
+        /// The expression to evaluate.
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        body: Box<Spanned<Expression<'a>>>
+    },
 
-    /// Eval.
-    /// Late evaluation of a PHP program represented as a string.
+    /// Assignment.
+    /// Bind a value to a variable, e.g. `$x = 42`. When the value is an
+    /// `Expression::Reference`, the variable becomes an alias of the
+    /// referenced one, e.g. `$x = &$y`, instead of holding a copy of its
+    /// value. A list or an array can be assigned to destructure the value.
     ///
     /// # Examples
     ///
     /// ```
     /// # extern crate tagua_parser;
     /// use tagua_parser::Result;
-    /// use tagua_parser::ast::{Expression, Literal};
+    /// use tagua_parser::ast::{Expression, Variable};
     /// use tagua_parser::rules::expressions::expression;
+    /// use tagua_parser::span::Spanned;
     ///
     /// # fn main () {
     /// assert_eq!(
-    ///     expression(b"eval('1 + 2;')"),
+    ///     expression(b"$x = &$y"),
     ///     Result::Done(
     ///         &b""[..],
-    ///         Expression::Eval(
-    ///             Box::new(
-    ///                 Expression::Literal(
-    ///                     Literal::String(b"1 + 2;".to_vec())
-    ///                 )
+    ///         Expression::Assignment {
+    ///             variable: Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"x"[..])))),
+    ///             value   : Box::new(
+    ///                 Spanned::from(Expression::Reference(
+    ///                     Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"y"[..]))))
+    ///                 ))
     ///             )
-    ///         )
+    ///         }
     ///     )
     /// );
     /// # }
     /// ```
-    Eval(Box<Expression<'a>>),
+    Assignment {
+        /// The variable to bind the value to.
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        variable: Box<Spanned<Expression<'a>>>,
+        /// The value to bind.
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        value: Box<Spanned<Expression<'a>>>
+    },
 
-    /// Exit.
-    /// Terminate the current script.
+    /// Call.
+    /// Call a function, or any callable value, with a list of arguments. A
+    /// parenthesized property, e.g. `($foo->bar)()`, is a callable value,
+    /// while a method is called with `MethodCall`, `NullsafeMethodCall` or
+    /// `StaticMethodCall`.
     ///
     /// # Examples
     ///
     /// ```
     /// # extern crate tagua_parser;
     /// use tagua_parser::Result;
-    /// use tagua_parser::ast::{Expression, Literal};
+    /// use tagua_parser::ast::{Argument, Expression, Literal, Name, Variable};
     /// use tagua_parser::rules::expressions::expression;
+    /// use tagua_parser::span::Spanned;
     ///
     /// # fn main () {
     /// assert_eq!(
-    ///     expression(b"exit(42)"),
+    ///     expression(b"($foo->bar)(42)"),
     ///     Result::Done(
     ///         &b""[..],
-    ///         Expression::Exit(
-    ///             Some(
-    ///                 Box::new(
-    ///                     Expression::Literal(
-    ///                         Literal::Integer(42i64)
-    ///                     )
-    ///                 )
-    ///             )
-    ///         )
+    ///         Expression::Call {
+    ///             callee   : Box::new(
+    ///                 Spanned::from(Expression::PropertyFetch {
+    ///                     object  : Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..])))),
+    ///                     property: Box::new(Spanned::from(Expression::Name(Name::Unqualified(&b"bar"[..]))))
+    ///                 })
+    ///             ),
+    ///             arguments: vec![
+    ///                 Spanned::from(Argument {
+    ///                     name       : None,
+    ///                     is_unpacked: false,
+    ///                     value      : Spanned::from(Expression::Literal(Literal::Integer(42i64)))
+    ///                 })
+    ///             ]
+    ///         }
     ///     )
     /// );
     /// # }
     /// ```
-    Exit(Option<Box<Expression<'a>>>),
+    Call {
+        /// The callable to call.
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        callee: Box<Spanned<Expression<'a>>>,
+        /// The arguments.
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        arguments: Vec<Spanned<Argument<'a>>>
+    },
 
-    /// Isset.
-    /// Return `TRUE` if all expressions set and their values are not
-    /// `NULL`. Otherwise, it returns `FALSE`.
+    /// Callable creation.
+    /// Create a closure from a callable, i.e. a function, a method or a
+    /// static method, with the `(...)` syntax.
     ///
     /// # Examples
     ///
     /// ```
     /// # extern crate tagua_parser;
     /// use tagua_parser::Result;
-    /// use tagua_parser::ast::{Expression, Variable};
+    /// use tagua_parser::ast::{Expression, Name};
     /// use tagua_parser::rules::expressions::expression;
+    /// use tagua_parser::span::Spanned;
     ///
     /// # fn main () {
     /// assert_eq!(
-    ///     expression(b"isset($foo, $bar)"),
+    ///     expression(b"strlen(...)"),
     ///     Result::Done(
     ///         &b""[..],
-    ///         Expression::Isset(
-    ///             vec![
-    ///                 Expression::Variable(Variable(&b"foo"[..])),
-    ///                 Expression::Variable(Variable(&b"bar"[..]))
-    ///             ]
+    ///         Expression::CallableCreation(
+    ///             Box::new(Spanned::from(Expression::Name(Name::Unqualified(&b"strlen"[..]))))
     ///         )
     ///     )
     /// );
     /// # }
     /// ```
-    Isset(Vec<Expression<'a>>),
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    CallableCreation(Box<Spanned<Expression<'a>>>),
 
-    /// List.
-    /// Match and assign one or more elements of the source array to
-    /// the target variables.
+    /// Cast.
+    /// Convert the value of the operand into another type.
     ///
     /// # Examples
     ///
-    /// A keyed list:
-    ///
     /// ```
     /// # extern crate tagua_parser;
     /// use tagua_parser::Result;
-    /// use tagua_parser::ast::{Expression, Literal, Variable};
+    /// use tagua_parser::ast::{CastType, Expression, Variable};
     /// use tagua_parser::rules::expressions::expression;
+    /// use tagua_parser::span::Spanned;
     ///
     /// # fn main () {
     /// assert_eq!(
-    ///     expression(b"list('foo' => $foo, 'bar' => $bar, 'baz' => $baz)"),
+    ///     expression(b"(int) $foo"),
     ///     Result::Done(
     ///         &b""[..],
-    ///         Expression::List(vec![
-    ///             Some((
-    ///                 Some(Expression::Literal(Literal::String(b"foo".to_vec()))),
-    ///                 Expression::Variable(Variable(&b"foo"[..]))
-    ///             )),
-    ///             Some((
-    ///                 Some(Expression::Literal(Literal::String(b"bar".to_vec()))),
-    ///                 Expression::Variable(Variable(&b"bar"[..]))
-    ///             )),
-    ///             Some((
-    ///                 Some(Expression::Literal(Literal::String(b"baz".to_vec()))),
-    ///                 Expression::Variable(Variable(&b"baz"[..]))
-    ///             ))
-    ///         ])
+    ///         Expression::Cast {
+    ///             ty     : CastType::Integer,
+    ///             operand: Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..]))))
+    ///         }
     ///     )
     /// );
     /// # }
     /// ```
-    /// An unkeyed list:
+    Cast {
+        /// The type to cast into.
+        ty: CastType,
+        /// The expression to cast.
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        operand: Box<Spanned<Expression<'a>>>
+    },
+
+    /// Class name constant.
+    /// Get the fully qualified name of a class, e.g. `Foo::class`. The
+    /// class is either a name, which can be resolved at compile time, a
+    /// relative scope or an object, e.g. `$foo::class`.
+    ///
+    /// # Examples
     ///
     /// ```
     /// # extern crate tagua_parser;
     /// use tagua_parser::Result;
-    /// use tagua_parser::ast::{Expression, Literal, Variable};
+    /// use tagua_parser::ast::{Expression, Name};
     /// use tagua_parser::rules::expressions::expression;
+    /// use tagua_parser::span::Spanned;
     ///
     /// # fn main () {
     /// assert_eq!(
-    ///     expression(b"list($foo, , , $bar, $baz)"),
+    ///     expression(b"Foo\\Bar::class"),
     ///     Result::Done(
     ///         &b""[..],
-    ///         Expression::List(vec![
-    ///             Some((
-    ///                 None,
-    ///                 Expression::Variable(Variable(&b"foo"[..]))
-    ///             )),
-    ///             None,
-    ///             None,
-    ///             Some((
-    ///                 None,
-    ///                 Expression::Variable(Variable(&b"bar"[..]))
-    ///             )),
-    ///             Some((
-    ///                 None,
-    ///                 Expression::Variable(Variable(&b"baz"[..]))
-    ///             ))
-    ///         ])
+    ///         Expression::ClassNameConstant(
+    ///             Box::new(Spanned::from(Expression::Name(Name::Qualified(vec![&b"Foo"[..], &b"Bar"[..]]))))
+    ///         )
     ///     )
     /// );
     /// # }
     /// ```
-    List(Vec<Option<(Option<Expression<'a>>, Expression<'a>)>>),
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    ClassNameConstant(Box<Spanned<Expression<'a>>>),
 
-    /// A literal. See `Literal`.
+    /// Clone.
+    /// Create a shallow copy of an object.
     ///
     /// # Examples
     ///
     /// ```
     /// # extern crate tagua_parser;
     /// use tagua_parser::Result;
-    /// use tagua_parser::ast::{Expression, Literal};
+    /// use tagua_parser::ast::{Expression, Variable};
     /// use tagua_parser::rules::expressions::expression;
+    /// use tagua_parser::span::Spanned;
     ///
     /// # fn main () {
     /// assert_eq!(
-    ///     expression(b"'Hello, World!'"),
-    ///     Result::Done(&b""[..], Expression::Literal(Literal::String(b"Hello, World!".to_vec())))
+    ///     expression(b"clone $foo"),
+    ///     Result::Done(
+    ///         &b""[..],
+    ///         Expression::Clone(
+    ///             Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..]))))
+    ///         )
+    ///     )
     /// );
     /// # }
     /// ```
-    Literal(Literal),
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    Clone(Box<Spanned<Expression<'a>>>),
 
-    /// A name. See `Name`.
+    /// Closure.
+    /// Create an anonymous function, that can capture variables from the
+    /// enclosing scope by value or by reference (`&$x`).
     ///
     /// # Examples
     ///
     /// ```
     /// # extern crate tagua_parser;
     /// use tagua_parser::Result;
-    /// use tagua_parser::ast::{Expression, Name};
+    /// use tagua_parser::ast::{Expression, Parameter, Statement, Type, Variable};
     /// use tagua_parser::rules::expressions::expression;
+    /// use tagua_parser::span::Spanned;
     ///
     /// # fn main () {
     /// assert_eq!(
-    ///     expression(b"Foo\\Bar"),
-    ///     Result::Done(&b""[..], Expression::Name(Name::Qualified(vec![&b"Foo"[..], &b"Bar"[..]])))
+    ///     expression(b"function ($foo) use (&$bar): int { return $foo; }"),
+    ///     Result::Done(
+    ///         &b""[..],
+    ///         Expression::Closure {
+    ///             is_static        : false,
+    ///             returns_reference: false,
+    ///             parameters       : vec![
+    ///                 Spanned::from(Parameter {
+    ///                     attributes    : vec![],
+    ///                     visibility    : None,
+    ///                     set_visibility: None,
+    ///                     is_readonly   : false,
+    ///                     ty            : None,
+    ///                     is_reference  : false,
+    ///                     is_variadic   : false,
+    ///                     name          : Variable::Name(&b"foo"[..]),
+    ///                     value         : None,
+    ///                     hooks         : vec![]
+    ///                 })
+    ///             ],
+    ///             captures         : vec![
+    ///                 Spanned::from(Expression::Reference(
+    ///                     Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"bar"[..]))))
+    ///                 ))
+    ///             ],
+    ///             return_type      : Some(Spanned::from(Type::Int)),
+    ///             body             : vec![
+    ///                 Spanned::from(Statement::Return(
+    ///                     Some(Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..]))))
+    ///                 ))
+    ///             ]
+    ///         }
+    ///     )
     /// );
     /// # }
     /// ```
-    Name(Name<'a>),
+    Closure {
+        /// Whether the closure is static, i.e. not bound to `$this`.
+        is_static: bool,
+        /// Whether the closure returns a reference, i.e. `function &()`.
+        returns_reference: bool,
+        /// The parameters.
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        parameters: Vec<Spanned<Parameter<'a>>>,
+        /// The variables captured from the enclosing scope, either as
+        /// `Expression::Variable` or as `Expression::Reference`.
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        captures: Vec<Spanned<Expression<'a>>>,
+        /// The return type, if declared.
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        return_type: Option<Spanned<Type<'a>>>,
+        /// The body.
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        body: Vec<Spanned<Statement<'a>>>
+    },
 
-    /// Print.
-    /// Unlike `echo`, `print` can be used in any context allowing an
-    /// expression. It always returns the value `1`.
+    /// Dynamic class constant.
+    /// Access a constant of a class whose name is computed at runtime,
+    /// e.g. `Foo::{$name}`. When called, e.g. `Foo::{$name}()`, the member
+    /// is a static method instead, see `Expression::StaticFetch`.
     ///
     /// # Examples
     ///
     /// ```
     /// # extern crate tagua_parser;
     /// use tagua_parser::Result;
-    /// use tagua_parser::ast::{Expression, Variable};
+    /// use tagua_parser::ast::{Expression, Name, Variable};
     /// use tagua_parser::rules::expressions::expression;
+    /// use tagua_parser::span::Spanned;
     ///
     /// # fn main () {
     /// assert_eq!(
-    ///     expression(b"print $foo"),
+    ///     expression(b"Foo::{$name}"),
     ///     Result::Done(
     ///         &b""[..],
-    ///         Expression::Print(
+    ///         Expression::DynamicClassConstant {
+    ///             class: Box::new(Spanned::from(Expression::Name(Name::Unqualified(&b"Foo"[..])))),
+    ///             name : Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"name"[..]))))
+    ///         }
+    ///     )
+    /// );
+    /// # }
+    /// ```
+    DynamicClassConstant {
+        /// The class to access.
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        class: Box<Spanned<Expression<'a>>>,
+        /// The expression computing the name of the constant.
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        name: Box<Spanned<Expression<'a>>>
+    },
+
+    /// An echo.
+    /// Echo converts each of its expression's values into strings,
+    /// concatenates them in order given, and writes the result to the
+    /// output stream.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tagua_parser;
+    /// use tagua_parser::Result;
+    /// use tagua_parser::ast::{Expression, Literal, Variable};
+    /// use tagua_parser::rules::expressions::expression;
+    /// use tagua_parser::span::Spanned;
+    ///
+    /// # fn main () {
+    /// assert_eq!(
+    ///     expression(b"echo 'foobar', $bazqux, 42"),
+    ///     Result::Done(
+    ///         &b""[..],
+    ///         Expression::Echo(
+    ///             vec![
+    ///                 Spanned::from(Expression::Literal(Literal::String(b"foobar".to_vec()))),
+    ///                 Spanned::from(Expression::Variable(Variable::Name(&b"bazqux"[..]))),
+    ///                 Spanned::from(Expression::Literal(Literal::Integer(42i64)))
+    ///             ]
+    ///         )
+    ///     )
+    /// );
+    /// # }
+    /// ```
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    Echo(Vec<Spanned<Expression<'a>>>),
+
+    /// Empty.
+    /// Returns `TRUE` if the variable or value designated by the
+    /// expression is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tagua_parser;
+    /// use tagua_parser::Result;
+    /// use tagua_parser::ast::{Expression, Literal};
+    /// use tagua_parser::rules::expressions::expression;
+    /// use tagua_parser::span::Spanned;
+    ///
+    /// # fn main () {
+    /// assert_eq!(
+    ///     expression(b"empty('')"),
+    ///     Result::Done(
+    ///         &b""[..],
+    ///         Expression::Empty(
     ///             Box::new(
-    ///                 Expression::Variable(Variable(&b"foo"[..])),
+    ///                 Spanned::from(Expression::Literal(
+    ///                     Literal::String(b"".to_vec())
+    ///                 ))
     ///             )
     ///         )
     ///     )
     /// );
     /// # }
     /// ```
-    Print(Box<Expression<'a>>),
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    Empty(Box<Spanned<Expression<'a>>>),
 
-    /// Reference.
-    /// Describe an expression assignment by reference.
+    /// Eval.
+    /// Late evaluation of a PHP program represented as a string.
     ///
     /// # Examples
     ///
     /// ```
     /// # extern crate tagua_parser;
     /// use tagua_parser::Result;
-    /// use tagua_parser::ast::{Expression, Literal, Variable};
+    /// use tagua_parser::ast::{Expression, Literal};
     /// use tagua_parser::rules::expressions::expression;
+    /// use tagua_parser::span::Spanned;
     ///
     /// # fn main () {
     /// assert_eq!(
-    ///     expression(b"[7 => &$foo]"),
+    ///     expression(b"eval('1 + 2;')"),
     ///     Result::Done(
     ///         &b""[..],
-    ///         Expression::Array(
-    ///             vec![
-    ///                 (
-    ///                     Some(Expression::Literal(Literal::Integer(7i64))),
-    ///                     Expression::Reference(
-    ///                         Box::new(Expression::Variable(Variable(&b"foo"[..])))
-    ///                     )
+    ///         Expression::Eval(
+    ///             Box::new(
+    ///                 Spanned::from(Expression::Literal(
+    ///                     Literal::String(b"1 + 2;".to_vec())
+    ///                 ))
+    ///             )
+    ///         )
+    ///     )
+    /// );
+    /// # }
+    /// ```
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    Eval(Box<Spanned<Expression<'a>>>),
+
+    /// Exit.
+    /// Terminate the current script.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tagua_parser;
+    /// use tagua_parser::Result;
+    /// use tagua_parser::ast::{Expression, Literal};
+    /// use tagua_parser::rules::expressions::expression;
+    /// use tagua_parser::span::Spanned;
+    ///
+    /// # fn main () {
+    /// assert_eq!(
+    ///     expression(b"exit(42)"),
+    ///     Result::Done(
+    ///         &b""[..],
+    ///         Expression::Exit(
+    ///             Some(
+    ///                 Box::new(
+    ///                     Spanned::from(Expression::Literal(
+    ///                         Literal::Integer(42i64)
+    ///                     ))
     ///                 )
+    ///             )
+    ///         )
+    ///     )
+    /// );
+    /// # }
+    /// ```
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    Exit(Option<Box<Spanned<Expression<'a>>>>),
+
+    /// Heredoc.
+    /// A string delimited by a label, like `<<<EOT` and `EOT`, where
+    /// expressions are embedded like in an interpolated string.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tagua_parser;
+    /// use tagua_parser::Result;
+    /// use tagua_parser::ast::{Expression, StringPart, Variable};
+    /// use tagua_parser::rules::expressions::expression;
+    /// use tagua_parser::span::Spanned;
+    ///
+    /// # fn main () {
+    /// assert_eq!(
+    ///     expression(b"<<<EOT\n  Hello $name!\n  EOT"),
+    ///     Result::Done(
+    ///         &b""[..],
+    ///         Expression::Heredoc {
+    ///             label      : &b"EOT"[..],
+    ///             indentation: &b"  "[..],
+    ///             parts      : vec![
+    ///                 StringPart::Bytes(b"Hello ".to_vec()),
+    ///                 StringPart::Expression(Spanned::from(Expression::Variable(Variable::Name(&b"name"[..])))),
+    ///                 StringPart::Bytes(b"!".to_vec())
+    ///             ]
+    ///         }
+    ///     )
+    /// );
+    /// # }
+    /// ```
+    Heredoc {
+        /// The label delimiting the heredoc.
+        label: &'a [u8],
+        /// The indentation of the closing label, removed from each line.
+        indentation: &'a [u8],
+        /// The parts of the content, without the indentation.
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        parts: Vec<StringPart<'a>>
+    },
+
+    /// Instance of.
+    /// Check whether an object is an instance of a class or of one of its
+    /// subclasses. The class can be a name, a relative scope or a variable.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tagua_parser;
+    /// use tagua_parser::Result;
+    /// use tagua_parser::ast::{Expression, Name, Variable};
+    /// use tagua_parser::rules::expressions::expression;
+    /// use tagua_parser::span::Spanned;
+    ///
+    /// # fn main () {
+    /// assert_eq!(
+    ///     expression(b"$foo instanceof Bar"),
+    ///     Result::Done(
+    ///         &b""[..],
+    ///         Expression::InstanceOf {
+    ///             subject: Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..])))),
+    ///             class  : Box::new(Spanned::from(Expression::Name(Name::Unqualified(&b"Bar"[..]))))
+    ///         }
+    ///     )
+    /// );
+    /// # }
+    /// ```
+    InstanceOf {
+        /// The object to check.
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        subject: Box<Spanned<Expression<'a>>>,
+        /// The class to check against.
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        class: Box<Spanned<Expression<'a>>>
+    },
+
+    /// Interpolated string.
+    /// A string where expressions are embedded, e.g. `"Hello $name!"`. A
+    /// string without any embedded expression is a `Literal::String`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tagua_parser;
+    /// use tagua_parser::Result;
+    /// use tagua_parser::ast::{Expression, StringPart, Variable};
+    /// use tagua_parser::rules::expressions::expression;
+    /// use tagua_parser::span::Spanned;
+    ///
+    /// # fn main () {
+    /// assert_eq!(
+    ///     expression(b"\"Hello $name!\""),
+    ///     Result::Done(
+    ///         &b""[..],
+    ///         Expression::InterpolatedString(
+    ///             vec![
+    ///                 StringPart::Bytes(b"Hello ".to_vec()),
+    ///                 StringPart::Expression(Spanned::from(Expression::Variable(Variable::Name(&b"name"[..])))),
+    ///                 StringPart::Bytes(b"!".to_vec())
     ///             ]
     ///         )
     ///     )
     /// );
     /// # }
     /// ```
-    Reference(Box<Expression<'a>>),
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    InterpolatedString(Vec<StringPart<'a>>),
 
-    /// Unset.
-    /// Unset the variables designated by each expression.
+    /// Include.
+    /// Include and evaluate a file, and return its return value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tagua_parser;
+    /// use tagua_parser::Result;
+    /// use tagua_parser::ast::{Expression, IncludeKind, Literal};
+    /// use tagua_parser::rules::expressions::expression;
+    /// use tagua_parser::span::Spanned;
+    ///
+    /// # fn main () {
+    /// assert_eq!(
+    ///     expression(b"require_once 'config.php'"),
+    ///     Result::Done(
+    ///         &b""[..],
+    ///         Expression::Include {
+    ///             kind: IncludeKind::RequireOnce,
+    ///             path: Box::new(Spanned::from(Expression::Literal(Literal::String(b"config.php".to_vec()))))
+    ///         }
+    ///     )
+    /// );
+    /// # }
+    /// ```
+    Include {
+        /// The kind of inclusion.
+        kind: IncludeKind,
+        /// The path of the file to include.
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        path: Box<Spanned<Expression<'a>>>
+    },
+
+    /// Isset.
+    /// Return `TRUE` if all expressions set and their values are not
+    /// `NULL`. Otherwise, it returns `FALSE`.
     ///
     /// # Examples
     ///
@@ -623,40 +1490,2463 @@ pub enum Expression<'a> {
     /// use tagua_parser::Result;
     /// use tagua_parser::ast::{Expression, Variable};
     /// use tagua_parser::rules::expressions::expression;
+    /// use tagua_parser::span::Spanned;
     ///
     /// # fn main () {
     /// assert_eq!(
-    ///     expression(b"unset($foo, $bar)"),
+    ///     expression(b"isset($foo, $bar)"),
     ///     Result::Done(
     ///         &b""[..],
-    ///         Expression::Unset(
+    ///         Expression::Isset(
     ///             vec![
-    ///                 Expression::Variable(Variable(&b"foo"[..])),
-    ///                 Expression::Variable(Variable(&b"bar"[..]))
+    ///                 Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..]))),
+    ///                 Spanned::from(Expression::Variable(Variable::Name(&b"bar"[..])))
     ///             ]
     ///         )
     ///     )
     /// );
     /// # }
     /// ```
-    Unset(Vec<Expression<'a>>),
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    Isset(Vec<Spanned<Expression<'a>>>),
 
-    /// A variable. See `Variable`.
+    /// List.
+    /// Match and assign one or more elements of the source array to
+    /// the target variables.
     ///
     /// # Examples
     ///
+    /// A keyed list:
+    ///
     /// ```
     /// # extern crate tagua_parser;
     /// use tagua_parser::Result;
-    /// use tagua_parser::ast::{Expression, Variable};
+    /// use tagua_parser::ast::{Expression, Literal, Variable};
     /// use tagua_parser::rules::expressions::expression;
+    /// use tagua_parser::span::Spanned;
     ///
     /// # fn main () {
     /// assert_eq!(
-    ///     expression(b"$foo"),
-    ///     Result::Done(&b""[..], Expression::Variable(Variable(&b"foo"[..])))
+    ///     expression(b"list('foo' => $foo, 'bar' => $bar, 'baz' => $baz)"),
+    ///     Result::Done(
+    ///         &b""[..],
+    ///         Expression::List(vec![
+    ///             Some((
+    ///                 Some(Spanned::from(Expression::Literal(Literal::String(b"foo".to_vec())))),
+    ///                 Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..])))
+    ///             )),
+    ///             Some((
+    ///                 Some(Spanned::from(Expression::Literal(Literal::String(b"bar".to_vec())))),
+    ///                 Spanned::from(Expression::Variable(Variable::Name(&b"bar"[..])))
+    ///             )),
+    ///             Some((
+    ///                 Some(Spanned::from(Expression::Literal(Literal::String(b"baz".to_vec())))),
+    ///                 Spanned::from(Expression::Variable(Variable::Name(&b"baz"[..])))
+    ///             ))
+    ///         ])
+    ///     )
+    /// );
+    /// # }
+    /// ```
+    /// An unkeyed list:
+    ///
+    /// ```
+    /// # extern crate tagua_parser;
+    /// use tagua_parser::Result;
+    /// use tagua_parser::ast::{Expression, Literal, Variable};
+    /// use tagua_parser::rules::expressions::expression;
+    /// use tagua_parser::span::Spanned;
+    ///
+    /// # fn main () {
+    /// assert_eq!(
+    ///     expression(b"list($foo, , , $bar, $baz)"),
+    ///     Result::Done(
+    ///         &b""[..],
+    ///         Expression::List(vec![
+    ///             Some((
+    ///                 None,
+    ///                 Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..])))
+    ///             )),
+    ///             None,
+    ///             None,
+    ///             Some((
+    ///                 None,
+    ///                 Spanned::from(Expression::Variable(Variable::Name(&b"bar"[..])))
+    ///             )),
+    ///             Some((
+    ///                 None,
+    ///                 Spanned::from(Expression::Variable(Variable::Name(&b"baz"[..])))
+    ///             ))
+    ///         ])
+    ///     )
+    /// );
+    /// # }
+    /// ```
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    List(Vec<Option<(Option<Spanned<Expression<'a>>>, Spanned<Expression<'a>>)>>),
+
+    /// A literal. See `Literal`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tagua_parser;
+    /// use tagua_parser::Result;
+    /// use tagua_parser::ast::{Expression, Literal};
+    /// use tagua_parser::rules::expressions::expression;
+    ///
+    /// # fn main () {
+    /// assert_eq!(
+    ///     expression(b"'Hello, World!'"),
+    ///     Result::Done(&b""[..], Expression::Literal(Literal::String(b"Hello, World!".to_vec())))
+    /// );
+    /// # }
+    /// ```
+    Literal(Literal),
+
+    /// A magic constant. See `MagicConstant`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tagua_parser;
+    /// use tagua_parser::Result;
+    /// use tagua_parser::ast::{Expression, MagicConstant};
+    /// use tagua_parser::rules::expressions::expression;
+    ///
+    /// # fn main () {
+    /// assert_eq!(
+    ///     expression(b"__LINE__"),
+    ///     Result::Done(&b""[..], Expression::MagicConstant(MagicConstant::Line))
+    /// );
+    /// # }
+    /// ```
+    MagicConstant(MagicConstant),
+
+    /// Match.
+    /// Compare the subject with the conditions of each arm, using a strict
+    /// comparison, and evaluate the body of the first matching arm.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tagua_parser;
+    /// use tagua_parser::Result;
+    /// use tagua_parser::ast::{Expression, Literal, MatchArm, Variable};
+    /// use tagua_parser::rules::expressions::expression;
+    /// use tagua_parser::span::Spanned;
+    ///
+    /// # fn main () {
+    /// assert_eq!(
+    ///     expression(b"match ($x) { 1, 2 => 'a', default => 'b' }"),
+    ///     Result::Done(
+    ///         &b""[..],
+    ///         Expression::Match {
+    ///             subject: Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"x"[..])))),
+    ///             arms   : vec![
+    ///                 MatchArm {
+    ///                     conditions: Some(vec![
+    ///                         Spanned::from(Expression::Literal(Literal::Integer(1))),
+    ///                         Spanned::from(Expression::Literal(Literal::Integer(2)))
+    ///                     ]),
+    ///                     body      : Spanned::from(Expression::Literal(Literal::String(b"a".to_vec())))
+    ///                 },
+    ///                 MatchArm {
+    ///                     conditions: None,
+    ///                     body      : Spanned::from(Expression::Literal(Literal::String(b"b".to_vec())))
+    ///                 }
+    ///             ]
+    ///         }
+    ///     )
+    /// );
+    /// # }
+    /// ```
+    Match {
+        /// The expression to compare.
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        subject: Box<Spanned<Expression<'a>>>,
+        /// The arms, in order.
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        arms: Vec<MatchArm<'a>>
+    },
+
+    /// Method call.
+    /// Call a method of an object with a list of arguments.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tagua_parser;
+    /// use tagua_parser::Result;
+    /// use tagua_parser::ast::{Argument, Expression, Literal, Name, Variable};
+    /// use tagua_parser::rules::expressions::expression;
+    /// use tagua_parser::span::Spanned;
+    ///
+    /// # fn main () {
+    /// assert_eq!(
+    ///     expression(b"$foo->bar(42)"),
+    ///     Result::Done(
+    ///         &b""[..],
+    ///         Expression::MethodCall {
+    ///             object   : Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..])))),
+    ///             method   : Box::new(Spanned::from(Expression::Name(Name::Unqualified(&b"bar"[..])))),
+    ///             arguments: vec![
+    ///                 Spanned::from(Argument {
+    ///                     name       : None,
+    ///                     is_unpacked: false,
+    ///                     value      : Spanned::from(Expression::Literal(Literal::Integer(42i64)))
+    ///                 })
+    ///             ]
+    ///         }
+    ///     )
+    /// );
+    /// # }
+    /// ```
+    MethodCall {
+        /// The object to call the method of.
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        object: Box<Spanned<Expression<'a>>>,
+        /// The method to call, like a property name.
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        method: Box<Spanned<Expression<'a>>>,
+        /// The arguments.
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        arguments: Vec<Spanned<Argument<'a>>>
+    },
+
+    /// A name. See `Name`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tagua_parser;
+    /// use tagua_parser::Result;
+    /// use tagua_parser::ast::{Expression, Name};
+    /// use tagua_parser::rules::expressions::expression;
+    ///
+    /// # fn main () {
+    /// assert_eq!(
+    ///     expression(b"Foo\\Bar"),
+    ///     Result::Done(&b""[..], Expression::Name(Name::Qualified(vec![&b"Foo"[..], &b"Bar"[..]])))
+    /// );
+    /// # }
+    /// ```
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    Name(Name<'a>),
+
+    /// Negation.
+    /// Arithmetically negate the operand, e.g. `-$x`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tagua_parser;
+    /// use tagua_parser::Result;
+    /// use tagua_parser::ast::{Expression, Variable};
+    /// use tagua_parser::rules::expressions::expression;
+    /// use tagua_parser::span::Spanned;
+    ///
+    /// # fn main () {
+    /// assert_eq!(
+    ///     expression(b"-$foo"),
+    ///     Result::Done(
+    ///         &b""[..],
+    ///         Expression::Negation(
+    ///             Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..]))))
+    ///         )
+    ///     )
+    /// );
+    /// # }
+    /// ```
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    Negation(Box<Spanned<Expression<'a>>>),
+
+    /// New.
+    /// Create a new instance of a class.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tagua_parser;
+    /// use tagua_parser::Result;
+    /// use tagua_parser::ast::{Argument, Expression, Literal, Name, Variable};
+    /// use tagua_parser::rules::expressions::expression;
+    /// use tagua_parser::span::Spanned;
+    ///
+    /// # fn main () {
+    /// assert_eq!(
+    ///     expression(b"new Foo($bar, 42)"),
+    ///     Result::Done(
+    ///         &b""[..],
+    ///         Expression::New {
+    ///             class    : Box::new(Spanned::from(Expression::Name(Name::Unqualified(&b"Foo"[..])))),
+    ///             arguments: vec![
+    ///                 Spanned::from(Argument {
+    ///                     name       : None,
+    ///                     is_unpacked: false,
+    ///                     value      : Spanned::from(Expression::Variable(Variable::Name(&b"bar"[..])))
+    ///                 }),
+    ///                 Spanned::from(Argument {
+    ///                     name       : None,
+    ///                     is_unpacked: false,
+    ///                     value      : Spanned::from(Expression::Literal(Literal::Integer(42i64)))
+    ///                 })
+    ///             ]
+    ///         }
+    ///     )
+    /// );
+    /// # }
+    /// ```
+    New {
+        /// The class to instanciate.
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        class: Box<Spanned<Expression<'a>>>,
+        /// The arguments given to the constructor.
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        arguments: Vec<Spanned<Argument<'a>>>
+    },
+
+    /// Nullsafe method call.
+    /// Call a method of an object, or evaluate to `null` (and skip the rest
+    /// of the chain) if the object is `null`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tagua_parser;
+    /// use tagua_parser::Result;
+    /// use tagua_parser::ast::{Expression, Name, Variable};
+    /// use tagua_parser::rules::expressions::expression;
+    /// use tagua_parser::span::Spanned;
+    ///
+    /// # fn main () {
+    /// assert_eq!(
+    ///     expression(b"$foo?->bar()"),
+    ///     Result::Done(
+    ///         &b""[..],
+    ///         Expression::NullsafeMethodCall {
+    ///             object   : Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..])))),
+    ///             method   : Box::new(Spanned::from(Expression::Name(Name::Unqualified(&b"bar"[..])))),
+    ///             arguments: vec![]
+    ///         }
+    ///     )
+    /// );
+    /// # }
+    /// ```
+    NullsafeMethodCall {
+        /// The object to call the method of.
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        object: Box<Spanned<Expression<'a>>>,
+        /// The method to call, like a property name.
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        method: Box<Spanned<Expression<'a>>>,
+        /// The arguments.
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        arguments: Vec<Spanned<Argument<'a>>>
+    },
+
+    /// Nullsafe property fetch.
+    /// Access a property of an object, or evaluate to `null` (and skip the
+    /// rest of the chain) if the object is `null`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tagua_parser;
+    /// use tagua_parser::Result;
+    /// use tagua_parser::ast::{Expression, Name, Variable};
+    /// use tagua_parser::rules::expressions::expression;
+    /// use tagua_parser::span::Spanned;
+    ///
+    /// # fn main () {
+    /// assert_eq!(
+    ///     expression(b"$foo?->bar"),
+    ///     Result::Done(
+    ///         &b""[..],
+    ///         Expression::NullsafePropertyFetch {
+    ///             object  : Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..])))),
+    ///             property: Box::new(Spanned::from(Expression::Name(Name::Unqualified(&b"bar"[..]))))
+    ///         }
+    ///     )
+    /// );
+    /// # }
+    /// ```
+    NullsafePropertyFetch {
+        /// The object to access.
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        object: Box<Spanned<Expression<'a>>>,
+        /// The property to access.
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        property: Box<Spanned<Expression<'a>>>
+    },
+
+    /// Postfix decrement.
+    /// Decrement the variable by one and return its previous value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tagua_parser;
+    /// use tagua_parser::Result;
+    /// use tagua_parser::ast::{Expression, Variable};
+    /// use tagua_parser::rules::expressions::expression;
+    /// use tagua_parser::span::Spanned;
+    ///
+    /// # fn main () {
+    /// assert_eq!(
+    ///     expression(b"$foo--"),
+    ///     Result::Done(
+    ///         &b""[..],
+    ///         Expression::PostDecrement(
+    ///             Box::new(
+    ///                 Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..])))
+    ///             )
+    ///         )
+    ///     )
+    /// );
+    /// # }
+    /// ```
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    PostDecrement(Box<Spanned<Expression<'a>>>),
+
+    /// Postfix increment.
+    /// Increment the variable by one and return its previous value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tagua_parser;
+    /// use tagua_parser::Result;
+    /// use tagua_parser::ast::{Expression, Variable};
+    /// use tagua_parser::rules::expressions::expression;
+    /// use tagua_parser::span::Spanned;
+    ///
+    /// # fn main () {
+    /// assert_eq!(
+    ///     expression(b"$foo++"),
+    ///     Result::Done(
+    ///         &b""[..],
+    ///         Expression::PostIncrement(
+    ///             Box::new(
+    ///                 Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..])))
+    ///             )
+    ///         )
+    ///     )
+    /// );
+    /// # }
+    /// ```
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    PostIncrement(Box<Spanned<Expression<'a>>>),
+
+    /// Power.
+    /// Raise the base to the power of the exponent. The operator is right
+    /// associative, and has a higher precedence than the unary operators,
+    /// e.g. `-2 ** 2` is `-(2 ** 2)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tagua_parser;
+    /// use tagua_parser::Result;
+    /// use tagua_parser::ast::{Expression, Literal};
+    /// use tagua_parser::rules::expressions::expression;
+    /// use tagua_parser::span::Spanned;
+    ///
+    /// # fn main () {
+    /// assert_eq!(
+    ///     expression(b"2 ** 3 ** 2"),
+    ///     Result::Done(
+    ///         &b""[..],
+    ///         Expression::Power {
+    ///             base    : Box::new(Spanned::from(Expression::Literal(Literal::Integer(2)))),
+    ///             exponent: Box::new(
+    ///                 Spanned::from(Expression::Power {
+    ///                     base    : Box::new(Spanned::from(Expression::Literal(Literal::Integer(3)))),
+    ///                     exponent: Box::new(Spanned::from(Expression::Literal(Literal::Integer(2))))
+    ///                 })
+    ///             )
+    ///         }
+    ///     )
+    /// );
+    /// # }
+    /// ```
+    Power {
+        /// The base.
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        base: Box<Spanned<Expression<'a>>>,
+        /// The exponent.
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        exponent: Box<Spanned<Expression<'a>>>
+    },
+
+    /// Prefix decrement.
+    /// Decrement the variable by one and return its new value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tagua_parser;
+    /// use tagua_parser::Result;
+    /// use tagua_parser::ast::{Expression, Variable};
+    /// use tagua_parser::rules::expressions::expression;
+    /// use tagua_parser::span::Spanned;
+    ///
+    /// # fn main () {
+    /// assert_eq!(
+    ///     expression(b"--$foo"),
+    ///     Result::Done(
+    ///         &b""[..],
+    ///         Expression::PreDecrement(
+    ///             Box::new(
+    ///                 Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..])))
+    ///             )
+    ///         )
+    ///     )
+    /// );
+    /// # }
+    /// ```
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    PreDecrement(Box<Spanned<Expression<'a>>>),
+
+    /// Prefix increment.
+    /// Increment the variable by one and return its new value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tagua_parser;
+    /// use tagua_parser::Result;
+    /// use tagua_parser::ast::{Expression, Variable};
+    /// use tagua_parser::rules::expressions::expression;
+    /// use tagua_parser::span::Spanned;
+    ///
+    /// # fn main () {
+    /// assert_eq!(
+    ///     expression(b"++$foo"),
+    ///     Result::Done(
+    ///         &b""[..],
+    ///         Expression::PreIncrement(
+    ///             Box::new(
+    ///                 Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..])))
+    ///             )
+    ///         )
+    ///     )
+    /// );
+    /// # }
+    /// ```
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    PreIncrement(Box<Spanned<Expression<'a>>>),
+
+    /// Print.
+    /// Unlike `echo`, `print` can be used in any context allowing an
+    /// expression. It always returns the value `1`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tagua_parser;
+    /// use tagua_parser::Result;
+    /// use tagua_parser::ast::{Expression, Variable};
+    /// use tagua_parser::rules::expressions::expression;
+    /// use tagua_parser::span::Spanned;
+    ///
+    /// # fn main () {
+    /// assert_eq!(
+    ///     expression(b"print $foo"),
+    ///     Result::Done(
+    ///         &b""[..],
+    ///         Expression::Print(
+    ///             Box::new(
+    ///                 Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..]))),
+    ///             )
+    ///         )
+    ///     )
+    /// );
+    /// # }
+    /// ```
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    Print(Box<Spanned<Expression<'a>>>),
+
+    /// Property fetch.
+    /// Access a property of an object. The property is either a name, a
+    /// variable, or an expression (e.g. `$foo->{'bar'}`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tagua_parser;
+    /// use tagua_parser::Result;
+    /// use tagua_parser::ast::{Expression, Name, Variable};
+    /// use tagua_parser::rules::expressions::expression;
+    /// use tagua_parser::span::Spanned;
+    ///
+    /// # fn main () {
+    /// assert_eq!(
+    ///     expression(b"$foo->bar"),
+    ///     Result::Done(
+    ///         &b""[..],
+    ///         Expression::PropertyFetch {
+    ///             object  : Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..])))),
+    ///             property: Box::new(Spanned::from(Expression::Name(Name::Unqualified(&b"bar"[..]))))
+    ///         }
+    ///     )
+    /// );
+    /// # }
+    /// ```
+    PropertyFetch {
+        /// The object to access.
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        object: Box<Spanned<Expression<'a>>>,
+        /// The property to access.
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        property: Box<Spanned<Expression<'a>>>
+    },
+
+    /// Reference.
+    /// Describe an expression assignment by reference.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tagua_parser;
+    /// use tagua_parser::Result;
+    /// use tagua_parser::ast::{Expression, Literal, Variable};
+    /// use tagua_parser::rules::expressions::expression;
+    /// use tagua_parser::span::Spanned;
+    ///
+    /// # fn main () {
+    /// assert_eq!(
+    ///     expression(b"[7 => &$foo]"),
+    ///     Result::Done(
+    ///         &b""[..],
+    ///         Expression::Array(
+    ///             vec![
+    ///                 (
+    ///                     Some(Spanned::from(Expression::Literal(Literal::Integer(7i64)))),
+    ///                     Spanned::from(Expression::Reference(
+    ///                         Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..]))))
+    ///                     ))
+    ///                 )
+    ///             ]
+    ///         )
+    ///     )
+    /// );
+    /// # }
+    /// ```
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    Reference(Box<Spanned<Expression<'a>>>),
+
+    /// A relative scope. See `RelativeScope`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tagua_parser;
+    /// use tagua_parser::Result;
+    /// use tagua_parser::ast::{Expression, RelativeScope, Variable};
+    /// use tagua_parser::rules::expressions::expression;
+    /// use tagua_parser::span::Spanned;
+    ///
+    /// # fn main () {
+    /// assert_eq!(
+    ///     expression(b"$foo instanceof static"),
+    ///     Result::Done(
+    ///         &b""[..],
+    ///         Expression::InstanceOf {
+    ///             subject: Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..])))),
+    ///             class  : Box::new(Spanned::from(Expression::RelativeScope(RelativeScope::Static)))
+    ///         }
+    ///     )
+    /// );
+    /// # }
+    /// ```
+    RelativeScope(RelativeScope),
+
+    /// Shell execution.
+    /// Execute the string between backticks as a shell command and return
+    /// its output. The string is interpolated the same way a double-quoted
+    /// string is.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tagua_parser;
+    /// use tagua_parser::Result;
+    /// use tagua_parser::ast::{Expression, StringPart, Variable};
+    /// use tagua_parser::rules::expressions::expression;
+    /// use tagua_parser::span::Spanned;
+    ///
+    /// # fn main () {
+    /// assert_eq!(
+    ///     expression(b"`ls -la $dir`"),
+    ///     Result::Done(
+    ///         &b""[..],
+    ///         Expression::ShellExec(
+    ///             vec![
+    ///                 StringPart::Bytes(b"ls -la ".to_vec()),
+    ///                 StringPart::Expression(Spanned::from(Expression::Variable(Variable::Name(&b"dir"[..]))))
+    ///             ]
+    ///         )
+    ///     )
+    /// );
+    /// # }
+    /// ```
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    ShellExec(Vec<StringPart<'a>>),
+
+    /// A short list, i.e. an array used as a destructuring pattern, on the
+    /// left of an assignment or as the value of a `foreach`, with or
+    /// without skipped elements. A `None` item represents a skipped
+    /// element, as for `List`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tagua_parser;
+    /// use tagua_parser::Result;
+    /// use tagua_parser::ast::{Expression, Variable};
+    /// use tagua_parser::rules::expressions::expression;
+    /// use tagua_parser::span::Spanned;
+    ///
+    /// # fn main () {
+    /// assert_eq!(
+    ///     expression(b"[, $foo] = $bar"),
+    ///     Result::Done(
+    ///         &b""[..],
+    ///         Expression::Assignment {
+    ///             variable: Box::new(
+    ///                 Spanned::from(Expression::ShortList(vec![
+    ///                     None,
+    ///                     Some((
+    ///                         None,
+    ///                         Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..])))
+    ///                     ))
+    ///                 ]))
+    ///             ),
+    ///             value   : Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"bar"[..]))))
+    ///         }
+    ///     )
+    /// );
+    /// # }
+    /// ```
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    ShortList(Vec<Option<(Option<Spanned<Expression<'a>>>, Spanned<Expression<'a>>)>>),
+
+    /// Static fetch.
+    /// Access a static member of a class, i.e. a constant (e.g.
+    /// `Foo::BAR`) or a static property (e.g. `Foo::$bar`). The class is
+    /// either a name, a relative scope or an expression.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tagua_parser;
+    /// use tagua_parser::Result;
+    /// use tagua_parser::ast::{Expression, Name, RelativeScope, Variable};
+    /// use tagua_parser::rules::expressions::expression;
+    /// use tagua_parser::span::Spanned;
+    ///
+    /// # fn main () {
+    /// assert_eq!(
+    ///     expression(b"self::$foo"),
+    ///     Result::Done(
+    ///         &b""[..],
+    ///         Expression::StaticFetch {
+    ///             class : Box::new(Spanned::from(Expression::RelativeScope(RelativeScope::Current))),
+    ///             member: Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..]))))
+    ///         }
+    ///     )
+    /// );
+    /// # }
+    /// ```
+    StaticFetch {
+        /// The class to access.
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        class: Box<Spanned<Expression<'a>>>,
+        /// The member to access.
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        member: Box<Spanned<Expression<'a>>>
+    },
+
+    /// Static method call.
+    /// Call a static method of a class with a list of arguments, e.g.
+    /// `Foo::bar()`, or `Foo::$bar()` where the variable holds the name of
+    /// the method, unlike `(Foo::$bar)()` which calls the value of a static
+    /// property.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tagua_parser;
+    /// use tagua_parser::Result;
+    /// use tagua_parser::ast::{Expression, Name, RelativeScope};
+    /// use tagua_parser::rules::expressions::expression;
+    /// use tagua_parser::span::Spanned;
+    ///
+    /// # fn main () {
+    /// assert_eq!(
+    ///     expression(b"parent::foo()"),
+    ///     Result::Done(
+    ///         &b""[..],
+    ///         Expression::StaticMethodCall {
+    ///             class    : Box::new(Spanned::from(Expression::RelativeScope(RelativeScope::Parent))),
+    ///             method   : Box::new(Spanned::from(Expression::Name(Name::Unqualified(&b"foo"[..])))),
+    ///             arguments: vec![]
+    ///         }
+    ///     )
+    /// );
+    /// # }
+    /// ```
+    StaticMethodCall {
+        /// The class to call the method of.
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        class: Box<Spanned<Expression<'a>>>,
+        /// The method to call, like a static member name.
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        method: Box<Spanned<Expression<'a>>>,
+        /// The arguments.
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        arguments: Vec<Spanned<Argument<'a>>>
+    },
+
+    /// Throw.
+    /// Throw an exception. Since PHP 8.0, it can be used in any context
+    /// allowing an expression.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tagua_parser;
+    /// use tagua_parser::Result;
+    /// use tagua_parser::ast::{Expression, Variable};
+    /// use tagua_parser::rules::expressions::expression;
+    /// use tagua_parser::span::Spanned;
+    ///
+    /// # fn main () {
+    /// assert_eq!(
+    ///     expression(b"throw $e"),
+    ///     Result::Done(
+    ///         &b""[..],
+    ///         Expression::Throw(
+    ///             Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"e"[..]))))
+    ///         )
+    ///     )
+    /// );
+    /// # }
+    /// ```
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    Throw(Box<Spanned<Expression<'a>>>),
+
+    /// Unset.
+    /// Unset the variables designated by each expression.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tagua_parser;
+    /// use tagua_parser::Result;
+    /// use tagua_parser::ast::{Expression, Variable};
+    /// use tagua_parser::rules::expressions::expression;
+    /// use tagua_parser::span::Spanned;
+    ///
+    /// # fn main () {
+    /// assert_eq!(
+    ///     expression(b"unset($foo, $bar)"),
+    ///     Result::Done(
+    ///         &b""[..],
+    ///         Expression::Unset(
+    ///             vec![
+    ///                 Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..]))),
+    ///                 Spanned::from(Expression::Variable(Variable::Name(&b"bar"[..])))
+    ///             ]
+    ///         )
+    ///     )
+    /// );
+    /// # }
+    /// ```
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    Unset(Vec<Spanned<Expression<'a>>>),
+
+    /// A variable. See `Variable`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tagua_parser;
+    /// use tagua_parser::Result;
+    /// use tagua_parser::ast::{Expression, Variable};
+    /// use tagua_parser::rules::expressions::expression;
+    ///
+    /// # fn main () {
+    /// assert_eq!(
+    ///     expression(b"$foo"),
+    ///     Result::Done(&b""[..], Expression::Variable(Variable::Name(&b"foo"[..])))
+    /// );
+    /// # }
+    /// ```
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    Variable(Variable<'a>),
+
+    /// Yield.
+    /// Suspend a generator and provide a value, optionally with a key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tagua_parser;
+    /// use tagua_parser::Result;
+    /// use tagua_parser::ast::{Expression, Variable};
+    /// use tagua_parser::rules::expressions::expression;
+    /// use tagua_parser::span::Spanned;
+    ///
+    /// # fn main () {
+    /// assert_eq!(
+    ///     expression(b"yield $k => $v"),
+    ///     Result::Done(
+    ///         &b""[..],
+    ///         Expression::Yield {
+    ///             key  : Some(Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"k"[..]))))),
+    ///             value: Some(Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"v"[..])))))
+    ///         }
+    ///     )
+    /// );
+    /// # }
+    /// ```
+    Yield {
+        /// The key, if any.
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        key: Option<Box<Spanned<Expression<'a>>>>,
+        /// The value, if any; `NULL` is yielded otherwise.
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        value: Option<Box<Spanned<Expression<'a>>>>
+    },
+
+    /// Yield from.
+    /// Delegate a generator to another generator, an array or a
+    /// `Traversable` object.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tagua_parser;
+    /// use tagua_parser::Result;
+    /// use tagua_parser::ast::{Expression, Variable};
+    /// use tagua_parser::rules::expressions::expression;
+    /// use tagua_parser::span::Spanned;
+    ///
+    /// # fn main () {
+    /// assert_eq!(
+    ///     expression(b"yield from $iterator"),
+    ///     Result::Done(
+    ///         &b""[..],
+    ///         Expression::YieldFrom(
+    ///             Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"iterator"[..]))))
+    ///         )
+    ///     )
+    /// );
+    /// # }
+    /// ```
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    YieldFrom(Box<Spanned<Expression<'a>>>)
+}
+
+/// A catch block of a try statement, e.g. `catch (A | B $e) { … }`.
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Catch<'a> {
+    /// The types of the exceptions to catch.
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub types: Vec<Spanned<Name<'a>>>,
+    /// The variable receiving the exception, if any.
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub variable: Option<Variable<'a>>,
+    /// The statements to execute when an exception is caught.
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub body: Vec<Spanned<Statement<'a>>>
+}
+
+/// A member of a class, e.g. a constant, a property or a method.
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ClassMember<'a> {
+    /// A case of an enumeration, e.g. `case Hearts = 'H';`.
+    Case {
+        /// The attributes.
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        attributes: Vec<Attribute<'a>>,
+        /// The name of the case.
+        name: &'a [u8],
+        /// The value of the case, only for backed enumerations.
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        value: Option<ConstantExpression<'a>>
+    },
+    /// Constants, e.g. `const FOO = 42, BAR = 'x';`.
+    Constant {
+        /// The documentation comment, e.g. `/** … */`, if any.
+        doc_comment: Option<Vec<u8>>,
+        /// The attributes.
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        attributes: Vec<Attribute<'a>>,
+        /// The visibility of the constants.
+        visibility: Visibility,
+        /// Whether the constants cannot be overridden by a child class.
+        is_final: bool,
+        /// The type of the constants, if declared.
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        ty: Option<Spanned<Type<'a>>>,
+        /// The names and values of the constants.
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        constants: Vec<(&'a [u8], ConstantExpression<'a>)>
+    },
+    /// A method, e.g. `public function f() { … }`.
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    Method(Method<'a>),
+    /// Properties, e.g. `public static $foo = 42, $bar;`.
+    Property {
+        /// The documentation comment, e.g. `/** … */`, if any.
+        doc_comment: Option<Vec<u8>>,
+        /// The attributes.
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        attributes: Vec<Attribute<'a>>,
+        /// The visibility of the properties.
+        visibility: Visibility,
+        /// The write visibility of the properties, if it differs from their
+        /// visibility, e.g. `private(set)`.
+        set_visibility: Option<Visibility>,
+        /// Whether the properties belong to the class instead of its
+        /// instances.
+        is_static: bool,
+        /// Whether the properties can only be initialized once, from the
+        /// scope of the class.
+        is_readonly: bool,
+        /// The type of the properties, if declared.
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        ty: Option<Spanned<Type<'a>>>,
+        /// The names and default values of the properties.
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        properties: Vec<(Variable<'a>, Option<ConstantExpression<'a>>)>,
+        /// The hooks of the property, e.g. `{ get => 42; }`. A property
+        /// with hooks is declared alone.
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        hooks: Vec<PropertyHook<'a>>
+    },
+    /// Traits whose members are copied into the class, e.g. `use A, B;`
+    /// or `use A, B { A::f insteadof B; }`.
+    TraitUse {
+        /// The names of the used traits.
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        traits: Vec<Spanned<Name<'a>>>,
+        /// The rules resolving conflicts between methods of the traits, or
+        /// changing their names and visibilities.
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        adaptations: Vec<TraitAdaptation<'a>>
+    }
+}
+
+/// A directive of a declare statement, e.g. `strict_types=1`.
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum DeclareDirective {
+    /// The encoding of the script, e.g. `encoding='UTF-8'`.
+    Encoding(Vec<u8>),
+    /// Whether scalar types are strictly checked, e.g. `strict_types=1`.
+    StrictTypes(bool),
+    /// The number of low-level statements executed between two tick
+    /// events, e.g. `ticks=1`.
+    Ticks(i64)
+}
+
+/// A method of a class, e.g. `public function f(): int { … }`.
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Method<'a> {
+    /// The documentation comment, e.g. `/** … */`, if any.
+    pub doc_comment: Option<Vec<u8>>,
+    /// The attributes.
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub attributes: Vec<Attribute<'a>>,
+    /// The modifiers, always including a visibility.
+    pub modifiers: MethodModifiers,
+    /// Whether the method returns a reference, i.e. `function &f()`.
+    pub returns_reference: bool,
+    /// The name of the method.
+    pub name: &'a [u8],
+    /// The parameters.
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub parameters: Vec<Spanned<Parameter<'a>>>,
+    /// The return type, if declared.
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub return_type: Option<Spanned<Type<'a>>>,
+    /// The statements of the method, or `None` if abstract.
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub body: Option<Vec<Spanned<Statement<'a>>>>
+}
+
+/// A set of modifiers of a method, e.g. `abstract protected static`.
+///
+/// Modifiers are combined with the `|` operator:
+///
+/// ```
+/// # extern crate tagua_parser;
+/// use tagua_parser::ast::{METHOD_PROTECTED, METHOD_STATIC, Visibility};
+///
+/// # fn main () {
+/// let modifiers = METHOD_PROTECTED | METHOD_STATIC;
+///
+/// assert_eq!(modifiers.visibility(), Visibility::Protected);
+/// assert!(modifiers.is_static());
+/// assert!(!modifiers.is_abstract());
+/// # }
+/// ```
+#[derive(Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MethodModifiers {
+    bits: u8
+}
+
+/// The method is accessible from everywhere, i.e. `public`.
+pub const METHOD_PUBLIC: MethodModifiers = MethodModifiers { bits: 0b000001 };
+/// The method is accessible from the class, its parents and its children,
+/// i.e. `protected`.
+pub const METHOD_PROTECTED: MethodModifiers = MethodModifiers { bits: 0b000010 };
+/// The method is accessible from the class only, i.e. `private`.
+pub const METHOD_PRIVATE: MethodModifiers = MethodModifiers { bits: 0b000100 };
+/// The method belongs to the class instead of its instances, i.e.
+/// `static`.
+pub const METHOD_STATIC: MethodModifiers = MethodModifiers { bits: 0b001000 };
+/// The method has no body and must be implemented by a child class, i.e.
+/// `abstract`.
+pub const METHOD_ABSTRACT: MethodModifiers = MethodModifiers { bits: 0b010000 };
+/// The method cannot be overridden by a child class, i.e. `final`.
+pub const METHOD_FINAL: MethodModifiers = MethodModifiers { bits: 0b100000 };
+
+impl MethodModifiers {
+    /// An empty set of modifiers.
+    pub fn empty() -> MethodModifiers {
+        MethodModifiers { bits: 0 }
+    }
+
+    /// The raw representation of the set.
+    pub fn bits(&self) -> u8 {
+        self.bits
+    }
+
+    /// Check whether all the modifiers of `other` are in the set.
+    pub fn contains(&self, other: MethodModifiers) -> bool {
+        self.bits & other.bits == other.bits
+    }
+
+    /// Add all the modifiers of `other` to the set.
+    pub fn insert(&mut self, other: MethodModifiers) {
+        self.bits |= other.bits;
+    }
+
+    /// The visibility. A set without visibility is public.
+    pub fn visibility(&self) -> Visibility {
+        if self.contains(METHOD_PRIVATE) {
+            Visibility::Private
+        } else if self.contains(METHOD_PROTECTED) {
+            Visibility::Protected
+        } else {
+            Visibility::Public
+        }
+    }
+
+    /// Whether the method belongs to the class instead of its instances.
+    pub fn is_static(&self) -> bool {
+        self.contains(METHOD_STATIC)
+    }
+
+    /// Whether the method has no body and must be implemented by a child
+    /// class.
+    pub fn is_abstract(&self) -> bool {
+        self.contains(METHOD_ABSTRACT)
+    }
+
+    /// Whether the method cannot be overridden by a child class.
+    pub fn is_final(&self) -> bool {
+        self.contains(METHOD_FINAL)
+    }
+}
+
+impl BitOr for MethodModifiers {
+    type Output = MethodModifiers;
+
+    fn bitor(self, other: MethodModifiers) -> MethodModifiers {
+        MethodModifiers { bits: self.bits | other.bits }
+    }
+}
+
+impl fmt::Debug for MethodModifiers {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        let flags = [
+            (METHOD_PUBLIC, "METHOD_PUBLIC"),
+            (METHOD_PROTECTED, "METHOD_PROTECTED"),
+            (METHOD_PRIVATE, "METHOD_PRIVATE"),
+            (METHOD_STATIC, "METHOD_STATIC"),
+            (METHOD_ABSTRACT, "METHOD_ABSTRACT"),
+            (METHOD_FINAL, "METHOD_FINAL")
+        ];
+        let names: Vec<&str> = flags.iter().filter(|&&(flag, _)| self.contains(flag)).map(|&(_, name)| name).collect();
+
+        if names.is_empty() {
+            formatter.write_str("(empty)")
+        } else {
+            formatter.write_str(&names.join(" | "))
+        }
+    }
+}
+
+/// A hook of a property, intercepting its reads or its writes, e.g.
+/// `get => $this->x;` or `set ($value) { … }`.
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PropertyHook<'a> {
+    /// The attributes.
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub attributes: Vec<Attribute<'a>>,
+    /// Whether the hook cannot be overridden by a child class.
+    pub is_final: bool,
+    /// Whether the hook returns a reference, i.e. `&get`.
+    pub returns_reference: bool,
+    /// The kind of the hook.
+    pub kind: PropertyHookKind,
+    /// The parameters, declared by a `set` hook only.
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub parameters: Vec<Spanned<Parameter<'a>>>,
+    /// The body of the hook.
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub body: PropertyHookBody<'a>
+}
+
+/// The kind of a property hook.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum PropertyHookKind {
+    /// A hook intercepting the reads, i.e. `get`.
+    Get,
+    /// A hook intercepting the writes, i.e. `set`.
+    Set
+}
+
+/// The body of a property hook.
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum PropertyHookBody<'a> {
+    /// A list of statements, e.g. `{ return $this->x; }`.
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    Block(Vec<Spanned<Statement<'a>>>),
+    /// A single expression, e.g. `=> $this->x;`. The value of the
+    /// expression is returned by a `get` hook, and assigned to the property
+    /// by a `set` hook.
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    Expression(Spanned<Expression<'a>>)
+}
+
+/// A case of a switch statement, e.g. `case 42: …`.
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SwitchCase<'a> {
+    /// The expression to compare the subject with, or `None` for the
+    /// `default` case.
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub condition: Option<Spanned<Expression<'a>>>,
+    /// The statements to execute when the expression is equal to the
+    /// subject. Execution falls through the next cases unless it is
+    /// interrupted.
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub body: Vec<Spanned<Statement<'a>>>
+}
+
+/// An adaptation of the methods of used traits, e.g. `A::f insteadof B;`
+/// or `f as protected g;`.
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum TraitAdaptation<'a> {
+    /// Copy a method under another name or with another visibility, e.g.
+    /// `A::f as protected g;`.
+    Alias {
+        /// The trait declaring the method, if specified.
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        trait_name: Option<Spanned<Name<'a>>>,
+        /// The name of the method.
+        method: &'a [u8],
+        /// The new visibility, if any.
+        visibility: Option<Visibility>,
+        /// The new name, if any.
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        alias: Option<&'a [u8]>
+    },
+    /// Use the method of a trait instead of the ones of other traits, e.g.
+    /// `A::f insteadof B, C;`.
+    Insteadof {
+        /// The trait declaring the method to use.
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        trait_name: Spanned<Name<'a>>,
+        /// The name of the method.
+        method: &'a [u8],
+        /// The traits whose method is excluded.
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        excluded: Vec<Spanned<Name<'a>>>
+    }
+}
+
+/// A name imported by a use statement, e.g. `Foo\Bar as Baz`.
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Use<'a> {
+    /// The kind of the imported name.
+    pub kind: UseKind,
+    /// The imported name.
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub name: Spanned<Name<'a>>,
+    /// The alias of the name, if any.
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub alias: Option<&'a [u8]>
+}
+
+/// The kind of a name imported by a use statement.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum UseKind {
+    /// A class, an interface, a trait, an enumeration or a namespace, e.g.
+    /// `use Foo\Bar;`.
+    Class,
+    /// A function, e.g. `use function Foo\bar;`.
+    Function,
+    /// A constant, e.g. `use const Foo\BAR;`.
+    Constant
+}
+
+/// The visibility of a class member.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Visibility {
+    /// Accessible from everywhere, i.e. `public`.
+    Public,
+    /// Accessible from the class, its parents and its children, i.e.
+    /// `protected`.
+    Protected,
+    /// Accessible from the class only, i.e. `private`.
+    Private
+}
+
+/// A statement.
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+// The control structures hold their spanned expressions inline, like the
+// other variants, rather than boxing them.
+#[allow(clippy::large_enum_variant)]
+pub enum Statement<'a> {
+    /// Block.
+    /// A sequence of statements surrounded by curly brackets, aka a
+    /// compound statement.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tagua_parser;
+    /// use tagua_parser::Result;
+    /// use tagua_parser::ast::Statement;
+    /// use tagua_parser::rules::statements::statement;
+    /// use tagua_parser::span::Spanned;
+    ///
+    /// # fn main () {
+    /// assert_eq!(
+    ///     statement(b"{ {} }"),
+    ///     Result::Done(
+    ///         &b""[..],
+    ///         Statement::Block(vec![Spanned::from(Statement::Block(vec![]))])
+    ///     )
+    /// );
+    /// # }
+    /// ```
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    Block(Vec<Spanned<Statement<'a>>>),
+
+    /// Break.
+    /// End the execution of the current loop or `switch` statement, or of
+    /// the given number of enclosing ones.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tagua_parser;
+    /// use tagua_parser::Result;
+    /// use tagua_parser::ast::Statement;
+    /// use tagua_parser::rules::statements::statement;
+    ///
+    /// # fn main () {
+    /// assert_eq!(statement(b"break;"), Result::Done(&b""[..], Statement::Break(None)));
+    /// assert_eq!(statement(b"break 2;"), Result::Done(&b""[..], Statement::Break(Some(2))));
+    /// # }
+    /// ```
+    Break(Option<u64>),
+
+    /// Class.
+    /// Declare a class, i.e. a set of constants, properties and methods.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tagua_parser;
+    /// use tagua_parser::Result;
+    /// use tagua_parser::ast::{ClassMember, Name, Statement, Variable, Visibility};
+    /// use tagua_parser::rules::statements::statement;
+    /// use tagua_parser::span::Spanned;
+    ///
+    /// # fn main () {
+    /// assert_eq!(
+    ///     statement(b"final class Foo extends Bar implements Baz { public $qux; }"),
+    ///     Result::Done(
+    ///         &b""[..],
+    ///         Statement::Class {
+    ///             doc_comment: None,
+    ///             attributes : vec![],
+    ///             is_abstract: false,
+    ///             is_final   : true,
+    ///             is_readonly: false,
+    ///             name       : &b"Foo"[..],
+    ///             extends    : Some(Spanned::from(Name::Unqualified(&b"Bar"[..]))),
+    ///             implements : vec![Spanned::from(Name::Unqualified(&b"Baz"[..]))],
+    ///             members    : vec![
+    ///                 Spanned::from(ClassMember::Property {
+    ///                     doc_comment   : None,
+    ///                     attributes    : vec![],
+    ///                     visibility    : Visibility::Public,
+    ///                     set_visibility: None,
+    ///                     is_static     : false,
+    ///                     is_readonly   : false,
+    ///                     ty            : None,
+    ///                     properties    : vec![(Variable::Name(&b"qux"[..]), None)],
+    ///                     hooks         : vec![]
+    ///                 })
+    ///             ]
+    ///         }
+    ///     )
+    /// );
+    /// # }
+    /// ```
+    Class {
+        /// The documentation comment, e.g. `/** … */`, if any.
+        doc_comment: Option<Vec<u8>>,
+        /// The attributes.
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        attributes: Vec<Attribute<'a>>,
+        /// Whether the class cannot be instanciated, and may declare
+        /// abstract methods.
+        is_abstract: bool,
+        /// Whether the class cannot be extended.
+        is_final: bool,
+        /// Whether all the properties of the class are readonly. Its
+        /// properties, and the promoted parameters of its constructor, are
+        /// then marked as readonly.
+        is_readonly: bool,
+        /// The name of the class.
+        name: &'a [u8],
+        /// The parent class, if any.
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        extends: Option<Spanned<Name<'a>>>,
+        /// The implemented interfaces.
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        implements: Vec<Spanned<Name<'a>>>,
+        /// The members of the class.
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        members: Vec<Spanned<ClassMember<'a>>>
+    },
+
+    /// Constant.
+    /// Declare constants in the current namespace.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tagua_parser;
+    /// use tagua_parser::Result;
+    /// use tagua_parser::ast::{ConstantExpression, Expression, Literal, Statement};
+    /// use tagua_parser::rules::statements::statement;
+    /// use tagua_parser::span::Spanned;
+    ///
+    /// # fn main () {
+    /// assert_eq!(
+    ///     statement(b"const FOO = 42, BAR = 'x';"),
+    ///     Result::Done(
+    ///         &b""[..],
+    ///         Statement::Constant(
+    ///             vec![
+    ///                 (&b"FOO"[..], ConstantExpression(Spanned::from(Expression::Literal(Literal::Integer(42))))),
+    ///                 (&b"BAR"[..], ConstantExpression(Spanned::from(Expression::Literal(Literal::String(b"x".to_vec())))))
+    ///             ]
+    ///         )
+    ///     )
+    /// );
+    /// # }
+    /// ```
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    Constant(Vec<(&'a [u8], ConstantExpression<'a>)>),
+
+    /// Continue.
+    /// Skip the rest of the current iteration of the current loop, or of
+    /// the given number of enclosing ones.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tagua_parser;
+    /// use tagua_parser::Result;
+    /// use tagua_parser::ast::Statement;
+    /// use tagua_parser::rules::statements::statement;
+    ///
+    /// # fn main () {
+    /// assert_eq!(statement(b"continue;"), Result::Done(&b""[..], Statement::Continue(None)));
+    /// assert_eq!(statement(b"continue 2;"), Result::Done(&b""[..], Statement::Continue(Some(2))));
+    /// # }
+    /// ```
+    Continue(Option<u64>),
+
+    /// Declare.
+    /// Set execution directives for a statement, or for the rest of the
+    /// script if there is no statement.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tagua_parser;
+    /// use tagua_parser::Result;
+    /// use tagua_parser::ast::{DeclareDirective, Statement};
+    /// use tagua_parser::rules::statements::statement;
+    ///
+    /// # fn main () {
+    /// assert_eq!(
+    ///     statement(b"declare(strict_types=1);"),
+    ///     Result::Done(
+    ///         &b""[..],
+    ///         Statement::Declare {
+    ///             directives: vec![DeclareDirective::StrictTypes(true)],
+    ///             body      : None
+    ///         }
+    ///     )
+    /// );
+    /// # }
+    /// ```
+    Declare {
+        /// The directives.
+        directives: Vec<DeclareDirective>,
+        /// The statement the directives apply to, if any.
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        body: Option<Box<Spanned<Statement<'a>>>>
+    },
+
+    /// Do while.
+    /// Execute a statement, then execute it again as long as a condition
+    /// is true.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tagua_parser;
+    /// use tagua_parser::Result;
+    /// use tagua_parser::ast::{Expression, Statement, Variable};
+    /// use tagua_parser::rules::statements::statement;
+    /// use tagua_parser::span::Spanned;
+    ///
+    /// # fn main () {
+    /// assert_eq!(
+    ///     statement(b"do {} while ($foo);"),
+    ///     Result::Done(
+    ///         &b""[..],
+    ///         Statement::DoWhile {
+    ///             body     : Box::new(Spanned::from(Statement::Block(vec![]))),
+    ///             condition: Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..])))
+    ///         }
+    ///     )
+    /// );
+    /// # }
+    /// ```
+    DoWhile {
+        /// The statement to execute.
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        body: Box<Spanned<Statement<'a>>>,
+        /// The condition to execute the statement again.
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        condition: Spanned<Expression<'a>>
+    },
+
+    /// Echo.
+    /// Output one or more expressions, with the `echo` statement or the
+    /// `<?=` opening tag.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tagua_parser;
+    /// use tagua_parser::Result;
+    /// use tagua_parser::ast::{Expression, Statement, Variable};
+    /// use tagua_parser::rules::script::script;
+    /// use tagua_parser::rules::statements::statement;
+    /// use tagua_parser::span::Spanned;
+    ///
+    /// # fn main () {
+    /// let output = || {
+    ///     Statement::Echo(vec![
+    ///         Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..]))),
+    ///         Spanned::from(Expression::Variable(Variable::Name(&b"bar"[..])))
+    ///     ])
+    /// };
+    ///
+    /// assert_eq!(statement(b"echo $foo, $bar;"), Result::Done(&b""[..], output()));
+    /// assert_eq!(script(b"<?= $foo, $bar ?>"), Result::Done(&b""[..], vec![Spanned::from(output())]));
+    /// # }
+    /// ```
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    Echo(Vec<Spanned<Expression<'a>>>),
+
+    /// Empty.
+    /// A statement doing nothing, i.e. a lone semicolon.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tagua_parser;
+    /// use tagua_parser::Result;
+    /// use tagua_parser::ast::Statement;
+    /// use tagua_parser::rules::statements::statement;
+    ///
+    /// # fn main () {
+    /// assert_eq!(statement(b";"), Result::Done(&b""[..], Statement::Empty));
+    /// # }
+    /// ```
+    Empty,
+
+    /// Enumeration.
+    /// Declare an enumeration, i.e. a closed set of cases, optionally
+    /// backed by integers or strings.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tagua_parser;
+    /// use tagua_parser::Result;
+    /// use tagua_parser::ast::{ClassMember, ConstantExpression, Expression, Literal, Statement, Type};
+    /// use tagua_parser::rules::statements::statement;
+    /// use tagua_parser::span::Spanned;
+    ///
+    /// # fn main () {
+    /// assert_eq!(
+    ///     statement(b"enum Suit: string { case Hearts = 'H'; }"),
+    ///     Result::Done(
+    ///         &b""[..],
+    ///         Statement::Enum {
+    ///             doc_comment : None,
+    ///             attributes  : vec![],
+    ///             name        : &b"Suit"[..],
+    ///             backing_type: Some(Spanned::from(Type::String)),
+    ///             implements  : vec![],
+    ///             members     : vec![
+    ///                 Spanned::from(ClassMember::Case {
+    ///                     attributes: vec![],
+    ///                     name      : &b"Hearts"[..],
+    ///                     value     : Some(ConstantExpression(Spanned::from(Expression::Literal(Literal::String(b"H".to_vec())))))
+    ///                 })
+    ///             ]
+    ///         }
+    ///     )
+    /// );
+    /// # }
+    /// ```
+    Enum {
+        /// The documentation comment, e.g. `/** … */`, if any.
+        doc_comment: Option<Vec<u8>>,
+        /// The attributes.
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        attributes: Vec<Attribute<'a>>,
+        /// The name of the enumeration.
+        name: &'a [u8],
+        /// The type of the values of the cases, i.e. `int` or `string`,
+        /// if the enumeration is backed.
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        backing_type: Option<Spanned<Type<'a>>>,
+        /// The implemented interfaces.
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        implements: Vec<Spanned<Name<'a>>>,
+        /// The members of the enumeration, i.e. cases, constants, methods
+        /// and trait uses.
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        members: Vec<Spanned<ClassMember<'a>>>
+    },
+
+    /// Error.
+    /// A placeholder for an invalid statement, skipped by the error
+    /// recovery up to a synchronization point. It holds the skipped bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tagua_parser;
+    /// use tagua_parser::ast::Statement;
+    /// use tagua_parser::rules::script::recovering_script;
+    /// use tagua_parser::span::Spanned;
+    ///
+    /// # fn main () {
+    /// let (statements, _diagnostics) = recovering_script(b"<?php $a = ; {}");
+    ///
+    /// assert_eq!(
+    ///     statements,
+    ///     vec![
+    ///         Spanned::from(Statement::Error(&b"$a = ;"[..])),
+    ///         Spanned::from(Statement::Block(vec![]))
+    ///     ]
+    /// );
+    /// # }
+    /// ```
+    Error(&'a [u8]),
+
+    /// Expression.
+    /// Evaluate an expression for its side-effects, and discard its
+    /// value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tagua_parser;
+    /// use tagua_parser::Result;
+    /// use tagua_parser::ast::{Expression, Literal, Statement, Variable};
+    /// use tagua_parser::rules::statements::statement;
+    /// use tagua_parser::span::Spanned;
+    ///
+    /// # fn main () {
+    /// assert_eq!(
+    ///     statement(b"$foo = 42;"),
+    ///     Result::Done(
+    ///         &b""[..],
+    ///         Statement::Expression(
+    ///             Spanned::from(Expression::Assignment {
+    ///                 variable: Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..])))),
+    ///                 value   : Box::new(Spanned::from(Expression::Literal(Literal::Integer(42))))
+    ///             })
+    ///         )
+    ///     )
+    /// );
+    /// # }
+    /// ```
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    Expression(Spanned<Expression<'a>>),
+
+    /// For.
+    /// Evaluate the initializing expressions once, then execute a
+    /// statement and evaluate the step expressions as long as the
+    /// condition is true, i.e. as long as the last condition expression is
+    /// true.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tagua_parser;
+    /// use tagua_parser::Result;
+    /// use tagua_parser::ast::{Expression, Literal, Statement, Variable};
+    /// use tagua_parser::rules::statements::statement;
+    /// use tagua_parser::span::Spanned;
+    ///
+    /// # fn main () {
+    /// assert_eq!(
+    ///     statement(b"for ($i = 0; $i; $i--) {}"),
+    ///     Result::Done(
+    ///         &b""[..],
+    ///         Statement::For {
+    ///             init     : vec![
+    ///                 Spanned::from(Expression::Assignment {
+    ///                     variable: Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"i"[..])))),
+    ///                     value   : Box::new(Spanned::from(Expression::Literal(Literal::Integer(0))))
+    ///                 })
+    ///             ],
+    ///             condition: vec![Spanned::from(Expression::Variable(Variable::Name(&b"i"[..])))],
+    ///             step     : vec![
+    ///                 Spanned::from(Expression::PostDecrement(
+    ///                     Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"i"[..]))))
+    ///                 ))
+    ///             ],
+    ///             body     : Box::new(Spanned::from(Statement::Block(vec![])))
+    ///         }
+    ///     )
+    /// );
+    /// # }
+    /// ```
+    For {
+        /// The expressions evaluated once, before the first iteration.
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        init: Vec<Spanned<Expression<'a>>>,
+        /// The expressions evaluated before each iteration. The result of
+        /// the last one is the condition. The loop is infinite if empty.
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        condition: Vec<Spanned<Expression<'a>>>,
+        /// The expressions evaluated after each iteration.
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        step: Vec<Spanned<Expression<'a>>>,
+        /// The statement to execute.
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        body: Box<Spanned<Statement<'a>>>
+    },
+
+    /// Foreach.
+    /// Execute a statement for each element of an array or a
+    /// `Traversable` object, binding the value, and the key if any, of the
+    /// element to some variables.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tagua_parser;
+    /// use tagua_parser::Result;
+    /// use tagua_parser::ast::{Expression, Statement, Variable};
+    /// use tagua_parser::rules::statements::statement;
+    /// use tagua_parser::span::Spanned;
+    ///
+    /// # fn main () {
+    /// assert_eq!(
+    ///     statement(b"foreach ($foo as $key => &$value) {}"),
+    ///     Result::Done(
+    ///         &b""[..],
+    ///         Statement::Foreach {
+    ///             subject     : Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..]))),
+    ///             key         : Some(Spanned::from(Expression::Variable(Variable::Name(&b"key"[..])))),
+    ///             value       : Spanned::from(Expression::Variable(Variable::Name(&b"value"[..]))),
+    ///             is_reference: true,
+    ///             body        : Box::new(Spanned::from(Statement::Block(vec![])))
+    ///         }
+    ///     )
+    /// );
+    /// # }
+    /// ```
+    Foreach {
+        /// The array or object to iterate over.
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        subject: Spanned<Expression<'a>>,
+        /// The variable receiving the key, if any.
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        key: Option<Spanned<Expression<'a>>>,
+        /// The variable receiving the value, or a list of variables, e.g.
+        /// `[$a, $b]`, to destructure the value into.
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        value: Spanned<Expression<'a>>,
+        /// Whether the value is bound by reference, e.g. `&$value`.
+        is_reference: bool,
+        /// The statement to execute.
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        body: Box<Spanned<Statement<'a>>>
+    },
+
+    /// Function.
+    /// Declare a named function.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tagua_parser;
+    /// use tagua_parser::Result;
+    /// use tagua_parser::ast::{Parameter, Statement, Type, Variable};
+    /// use tagua_parser::rules::statements::statement;
+    /// use tagua_parser::span::Spanned;
+    ///
+    /// # fn main () {
+    /// assert_eq!(
+    ///     statement(b"function foo(int $a): int {}"),
+    ///     Result::Done(
+    ///         &b""[..],
+    ///         Statement::Function {
+    ///             doc_comment      : None,
+    ///             attributes       : vec![],
+    ///             returns_reference: false,
+    ///             name             : &b"foo"[..],
+    ///             parameters       : vec![
+    ///                 Spanned::from(Parameter {
+    ///                     attributes    : vec![],
+    ///                     visibility    : None,
+    ///                     set_visibility: None,
+    ///                     is_readonly   : false,
+    ///                     ty            : Some(Spanned::from(Type::Int)),
+    ///                     is_reference  : false,
+    ///                     is_variadic   : false,
+    ///                     name          : Variable::Name(&b"a"[..]),
+    ///                     value         : None,
+    ///                     hooks         : vec![]
+    ///                 })
+    ///             ],
+    ///             return_type      : Some(Spanned::from(Type::Int)),
+    ///             body             : vec![]
+    ///         }
+    ///     )
+    /// );
+    /// # }
+    /// ```
+    Function {
+        /// The documentation comment, e.g. `/** … */`, if any.
+        doc_comment: Option<Vec<u8>>,
+        /// The attributes.
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        attributes: Vec<Attribute<'a>>,
+        /// Whether the function returns a reference, i.e. `function &f()`.
+        returns_reference: bool,
+        /// The name of the function.
+        name: &'a [u8],
+        /// The parameters.
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        parameters: Vec<Spanned<Parameter<'a>>>,
+        /// The return type, if declared.
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        return_type: Option<Spanned<Type<'a>>>,
+        /// The statements of the function.
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        body: Vec<Spanned<Statement<'a>>>
+    },
+
+    /// Global.
+    /// Bind local variables to the global variables of the same names.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tagua_parser;
+    /// use tagua_parser::Result;
+    /// use tagua_parser::ast::{Statement, Variable};
+    /// use tagua_parser::rules::statements::statement;
+    ///
+    /// # fn main () {
+    /// assert_eq!(
+    ///     statement(b"global $foo, $bar;"),
+    ///     Result::Done(
+    ///         &b""[..],
+    ///         Statement::Global(vec![
+    ///             Variable::Name(&b"foo"[..]),
+    ///             Variable::Name(&b"bar"[..])
+    ///         ])
+    ///     )
+    /// );
+    /// # }
+    /// ```
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    Global(Vec<Variable<'a>>),
+
+    /// Goto.
+    /// Jump to a label in the same function or script.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tagua_parser;
+    /// use tagua_parser::Result;
+    /// use tagua_parser::ast::Statement;
+    /// use tagua_parser::rules::statements::statement;
+    ///
+    /// # fn main () {
+    /// assert_eq!(statement(b"goto foo;"), Result::Done(&b""[..], Statement::Goto(&b"foo"[..])));
+    /// # }
+    /// ```
+    Goto(&'a [u8]),
+
+    /// Halt compiler.
+    /// Stop the compilation of the script. The rest of the file is raw
+    /// data, starting at the given byte offset in the script, e.g. the
+    /// archive of a PHAR stub.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tagua_parser;
+    /// use tagua_parser::Result;
+    /// use tagua_parser::ast::Statement;
+    /// use tagua_parser::rules::script::script;
+    /// use tagua_parser::span::Spanned;
+    ///
+    /// # fn main () {
+    /// assert_eq!(
+    ///     script(b"<?php __halt_compiler(); foo"),
+    ///     Result::Done(
+    ///         &b""[..],
+    ///         vec![
+    ///             Spanned::from(Statement::HaltCompiler {
+    ///                 offset: 24,
+    ///                 data  : &b" foo"[..]
+    ///             })
+    ///         ]
+    ///     )
+    /// );
+    /// # }
+    /// ```
+    HaltCompiler {
+        /// The offset of the data in the script.
+        offset: usize,
+        /// The data following the statement.
+        data: &'a [u8]
+    },
+
+    /// If.
+    /// Execute a statement if a condition is true, else try the `elseif`
+    /// clauses in order, else execute the `else` clause if any.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tagua_parser;
+    /// use tagua_parser::Result;
+    /// use tagua_parser::ast::{Expression, Statement, Variable};
+    /// use tagua_parser::rules::statements::statement;
+    /// use tagua_parser::span::Spanned;
+    ///
+    /// # fn main () {
+    /// assert_eq!(
+    ///     statement(b"if ($foo) {} elseif ($bar) {} else {}"),
+    ///     Result::Done(
+    ///         &b""[..],
+    ///         Statement::If {
+    ///             condition: Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..]))),
+    ///             then     : Box::new(Spanned::from(Statement::Block(vec![]))),
+    ///             elseifs  : vec![
+    ///                 (
+    ///                     Spanned::from(Expression::Variable(Variable::Name(&b"bar"[..]))),
+    ///                     Spanned::from(Statement::Block(vec![]))
+    ///                 )
+    ///             ],
+    ///             otherwise: Some(Box::new(Spanned::from(Statement::Block(vec![]))))
+    ///         }
+    ///     )
+    /// );
+    /// # }
+    /// ```
+    If {
+        /// The condition.
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        condition: Spanned<Expression<'a>>,
+        /// The statement to execute if the condition is true.
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        then: Box<Spanned<Statement<'a>>>,
+        /// The `elseif` clauses, i.e. pairs of a condition and a statement.
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        elseifs: Vec<(Spanned<Expression<'a>>, Spanned<Statement<'a>>)>,
+        /// The statement of the `else` clause, if any.
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        otherwise: Option<Box<Spanned<Statement<'a>>>>
+    },
+
+    /// Inline HTML.
+    /// Text outside of the PHP tags, which is output as is.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tagua_parser;
+    /// use tagua_parser::Result;
+    /// use tagua_parser::ast::Statement;
+    /// use tagua_parser::rules::script::script;
+    /// use tagua_parser::span::Spanned;
+    ///
+    /// # fn main () {
+    /// assert_eq!(
+    ///     script(b"<p><?php {} ?></p>"),
+    ///     Result::Done(
+    ///         &b""[..],
+    ///         vec![
+    ///             Spanned::from(Statement::InlineHtml(&b"<p>"[..])),
+    ///             Spanned::from(Statement::Block(vec![])),
+    ///             Spanned::from(Statement::InlineHtml(&b"</p>"[..]))
+    ///         ]
+    ///     )
+    /// );
+    /// # }
+    /// ```
+    InlineHtml(&'a [u8]),
+
+    /// Interface.
+    /// Declare an interface, i.e. a set of constants and method signatures
+    /// a class must implement.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tagua_parser;
+    /// use tagua_parser::Result;
+    /// use tagua_parser::ast::{ClassMember, METHOD_PUBLIC, Method, Name, Statement, Type};
+    /// use tagua_parser::rules::statements::statement;
+    /// use tagua_parser::span::Spanned;
+    ///
+    /// # fn main () {
+    /// assert_eq!(
+    ///     statement(b"interface Foo extends Bar { public function baz(): void; }"),
+    ///     Result::Done(
+    ///         &b""[..],
+    ///         Statement::Interface {
+    ///             doc_comment: None,
+    ///             attributes : vec![],
+    ///             name       : &b"Foo"[..],
+    ///             extends    : vec![Spanned::from(Name::Unqualified(&b"Bar"[..]))],
+    ///             members    : vec![
+    ///                 Spanned::from(ClassMember::Method(
+    ///                     Method {
+    ///                         doc_comment      : None,
+    ///                         attributes       : vec![],
+    ///                         modifiers        : METHOD_PUBLIC,
+    ///                         returns_reference: false,
+    ///                         name             : &b"baz"[..],
+    ///                         parameters       : vec![],
+    ///                         return_type      : Some(Spanned::from(Type::Void)),
+    ///                         body             : None
+    ///                     }
+    ///                 ))
+    ///             ]
+    ///         }
+    ///     )
+    /// );
+    /// # }
+    /// ```
+    Interface {
+        /// The documentation comment, e.g. `/** … */`, if any.
+        doc_comment: Option<Vec<u8>>,
+        /// The attributes.
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        attributes: Vec<Attribute<'a>>,
+        /// The name of the interface.
+        name: &'a [u8],
+        /// The parent interfaces.
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        extends: Vec<Spanned<Name<'a>>>,
+        /// The members of the interface, i.e. only constants and methods
+        /// without a body.
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        members: Vec<Spanned<ClassMember<'a>>>
+    },
+
+    /// Label.
+    /// Declare a label, i.e. a target for the `goto` statement.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tagua_parser;
+    /// use tagua_parser::Result;
+    /// use tagua_parser::ast::Statement;
+    /// use tagua_parser::rules::statements::statement;
+    ///
+    /// # fn main () {
+    /// assert_eq!(statement(b"foo:"), Result::Done(&b""[..], Statement::Label(&b"foo"[..])));
+    /// # }
+    /// ```
+    Label(&'a [u8]),
+
+    /// Namespace.
+    /// Declare a namespace, i.e. a scope for the names of the classes,
+    /// interfaces, traits, functions and constants declared in it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tagua_parser;
+    /// use tagua_parser::Result;
+    /// use tagua_parser::ast::{Name, Statement};
+    /// use tagua_parser::rules::statements::statement;
+    /// use tagua_parser::span::Spanned;
+    ///
+    /// # fn main () {
+    /// assert_eq!(
+    ///     statement(b"namespace Foo\\Bar;"),
+    ///     Result::Done(
+    ///         &b""[..],
+    ///         Statement::Namespace {
+    ///             name: Some(Spanned::from(Name::Qualified(vec![&b"Foo"[..], &b"Bar"[..]]))),
+    ///             body: None
+    ///         }
+    ///     )
+    /// );
+    /// assert_eq!(
+    ///     statement(b"namespace { }"),
+    ///     Result::Done(
+    ///         &b""[..],
+    ///         Statement::Namespace {
+    ///             name: None,
+    ///             body: Some(vec![])
+    ///         }
+    ///     )
+    /// );
+    /// # }
+    /// ```
+    Namespace {
+        /// The name of the namespace, or `None` for the global namespace.
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        name: Option<Spanned<Name<'a>>>,
+        /// The statements of the namespace, or `None` if the namespace
+        /// applies to the rest of the file, i.e. until the next namespace
+        /// declaration.
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        body: Option<Vec<Spanned<Statement<'a>>>>
+    },
+
+    /// Return.
+    /// End the execution of the current function, or script, and return
+    /// the value of an expression, if any.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tagua_parser;
+    /// use tagua_parser::Result;
+    /// use tagua_parser::ast::{Expression, Statement, Variable};
+    /// use tagua_parser::rules::statements::statement;
+    /// use tagua_parser::span::Spanned;
+    ///
+    /// # fn main () {
+    /// assert_eq!(
+    ///     statement(b"return $foo;"),
+    ///     Result::Done(
+    ///         &b""[..],
+    ///         Statement::Return(Some(Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..])))))
+    ///     )
+    /// );
+    /// # }
+    /// ```
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    Return(Option<Spanned<Expression<'a>>>),
+
+    /// Static variables.
+    /// Declare variables keeping their values between the calls of the
+    /// enclosing function, with optional constant initial values.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tagua_parser;
+    /// use tagua_parser::Result;
+    /// use tagua_parser::ast::{Expression, Literal, Statement, Variable};
+    /// use tagua_parser::rules::statements::statement;
+    /// use tagua_parser::span::Spanned;
+    ///
+    /// # fn main () {
+    /// assert_eq!(
+    ///     statement(b"static $count = 0, $cache;"),
+    ///     Result::Done(
+    ///         &b""[..],
+    ///         Statement::StaticVariables(vec![
+    ///             (Variable::Name(&b"count"[..]), Some(Spanned::from(Expression::Literal(Literal::Integer(0))))),
+    ///             (Variable::Name(&b"cache"[..]), None)
+    ///         ])
+    ///     )
+    /// );
+    /// # }
+    /// ```
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    StaticVariables(Vec<(Variable<'a>, Option<Spanned<Expression<'a>>>)>),
+
+    /// Switch.
+    /// Compare a subject to the conditions of cases in order, and execute
+    /// the statements from the first matching case, or from the `default`
+    /// case if none matches.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tagua_parser;
+    /// use tagua_parser::Result;
+    /// use tagua_parser::ast::{Expression, Literal, Statement, SwitchCase, Variable};
+    /// use tagua_parser::rules::statements::statement;
+    /// use tagua_parser::span::Spanned;
+    ///
+    /// # fn main () {
+    /// assert_eq!(
+    ///     statement(b"switch ($foo) { case 42: {} default: }"),
+    ///     Result::Done(
+    ///         &b""[..],
+    ///         Statement::Switch {
+    ///             subject: Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..]))),
+    ///             cases  : vec![
+    ///                 SwitchCase {
+    ///                     condition: Some(Spanned::from(Expression::Literal(Literal::Integer(42)))),
+    ///                     body     : vec![Spanned::from(Statement::Block(vec![]))]
+    ///                 },
+    ///                 SwitchCase {
+    ///                     condition: None,
+    ///                     body     : vec![]
+    ///                 }
+    ///             ]
+    ///         }
+    ///     )
+    /// );
+    /// # }
+    /// ```
+    Switch {
+        /// The subject to compare.
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        subject: Spanned<Expression<'a>>,
+        /// The cases.
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        cases: Vec<SwitchCase<'a>>
+    },
+
+    /// Trait.
+    /// Declare a trait, i.e. a set of members to be copied into classes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tagua_parser;
+    /// use tagua_parser::Result;
+    /// use tagua_parser::ast::{ClassMember, Statement, Variable, Visibility};
+    /// use tagua_parser::rules::statements::statement;
+    /// use tagua_parser::span::Spanned;
+    ///
+    /// # fn main () {
+    /// assert_eq!(
+    ///     statement(b"trait Foo { protected $bar; }"),
+    ///     Result::Done(
+    ///         &b""[..],
+    ///         Statement::Trait {
+    ///             doc_comment: None,
+    ///             attributes : vec![],
+    ///             name       : &b"Foo"[..],
+    ///             members    : vec![
+    ///                 Spanned::from(ClassMember::Property {
+    ///                     doc_comment   : None,
+    ///                     attributes    : vec![],
+    ///                     visibility    : Visibility::Protected,
+    ///                     set_visibility: None,
+    ///                     is_static     : false,
+    ///                     is_readonly   : false,
+    ///                     ty            : None,
+    ///                     properties    : vec![(Variable::Name(&b"bar"[..]), None)],
+    ///                     hooks         : vec![]
+    ///                 })
+    ///             ]
+    ///         }
+    ///     )
+    /// );
+    /// # }
+    /// ```
+    Trait {
+        /// The documentation comment, e.g. `/** … */`, if any.
+        doc_comment: Option<Vec<u8>>,
+        /// The attributes.
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        attributes: Vec<Attribute<'a>>,
+        /// The name of the trait.
+        name: &'a [u8],
+        /// The members of the trait.
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        members: Vec<Spanned<ClassMember<'a>>>
+    },
+
+    /// Try.
+    /// Execute statements, and catch the exceptions they throw with the
+    /// first catch block matching the type of the exception. The finally
+    /// block, if any, is always executed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tagua_parser;
+    /// use tagua_parser::Result;
+    /// use tagua_parser::ast::{Catch, Name, Statement, Variable};
+    /// use tagua_parser::rules::statements::statement;
+    /// use tagua_parser::span::Spanned;
+    ///
+    /// # fn main () {
+    /// assert_eq!(
+    ///     statement(b"try {} catch (A | B $e) {} finally {}"),
+    ///     Result::Done(
+    ///         &b""[..],
+    ///         Statement::Try {
+    ///             body   : vec![],
+    ///             catches: vec![
+    ///                 Catch {
+    ///                     types   : vec![Spanned::from(Name::Unqualified(&b"A"[..])), Spanned::from(Name::Unqualified(&b"B"[..]))],
+    ///                     variable: Some(Variable::Name(&b"e"[..])),
+    ///                     body    : vec![]
+    ///                 }
+    ///             ],
+    ///             finally: Some(vec![])
+    ///         }
+    ///     )
+    /// );
+    /// # }
+    /// ```
+    Try {
+        /// The statements to execute.
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        body: Vec<Spanned<Statement<'a>>>,
+        /// The catch blocks.
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        catches: Vec<Catch<'a>>,
+        /// The statements of the finally block, if any.
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        finally: Option<Vec<Spanned<Statement<'a>>>>
+    },
+
+    /// Use.
+    /// Import names from other namespaces, optionally under aliases.
+    /// Names imported by a group use, e.g. `use Foo\{Bar, Baz};`, are
+    /// prefixed by the name of the group.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tagua_parser;
+    /// use tagua_parser::Result;
+    /// use tagua_parser::ast::{Name, Statement, Use, UseKind};
+    /// use tagua_parser::rules::statements::statement;
+    /// use tagua_parser::span::Spanned;
+    ///
+    /// # fn main () {
+    /// assert_eq!(
+    ///     statement(b"use Foo\\{Bar as Baz, function qux};"),
+    ///     Result::Done(
+    ///         &b""[..],
+    ///         Statement::Use(
+    ///             vec![
+    ///                 Use {
+    ///                     kind : UseKind::Class,
+    ///                     name : Spanned::from(Name::Qualified(vec![&b"Foo"[..], &b"Bar"[..]])),
+    ///                     alias: Some(&b"Baz"[..])
+    ///                 },
+    ///                 Use {
+    ///                     kind : UseKind::Function,
+    ///                     name : Spanned::from(Name::Qualified(vec![&b"Foo"[..], &b"qux"[..]])),
+    ///                     alias: None
+    ///                 }
+    ///             ]
+    ///         )
+    ///     )
+    /// );
+    /// # }
+    /// ```
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    Use(Vec<Use<'a>>),
+
+    /// While.
+    /// Execute a statement as long as a condition is true.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tagua_parser;
+    /// use tagua_parser::Result;
+    /// use tagua_parser::ast::{Expression, Statement, Variable};
+    /// use tagua_parser::rules::statements::statement;
+    /// use tagua_parser::span::Spanned;
+    ///
+    /// # fn main () {
+    /// assert_eq!(
+    ///     statement(b"while ($foo) {}"),
+    ///     Result::Done(
+    ///         &b""[..],
+    ///         Statement::While {
+    ///             condition: Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..]))),
+    ///             body     : Box::new(Spanned::from(Statement::Block(vec![])))
+    ///         }
+    ///     )
     /// );
     /// # }
     /// ```
-    Variable(Variable<'a>)
+    While {
+        /// The condition to execute the statement.
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        condition: Spanned<Expression<'a>>,
+        /// The statement to execute.
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        body: Box<Spanned<Statement<'a>>>
+    }
 }
@@ -0,0 +1,356 @@
+// Tagua VM
+//
+//
+// New BSD License
+//
+// Copyright © 2016-2016, Ivan Enderlin.
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//     * Redistributions of source code must retain the above copyright
+//       notice, this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright
+//       notice, this list of conditions and the following disclaimer in the
+//       documentation and/or other materials provided with the distribution.
+//     * Neither the name of the Hoa nor the names of its contributors may be
+//       used to endorse or promote products derived from this software without
+//       specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE
+// ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDERS AND CONTRIBUTORS BE
+// LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR
+// CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF
+// SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS
+// INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN
+// CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+
+//! Diagnostics reported while parsing.
+//!
+//! Some constructions are accepted by the parser but deserve the attention
+//! of the user, like a syntax deprecated by the targeted PHP version, or a
+//! syntax error skipped by the error recovery. They do not stop the
+//! parser, so they cannot be returned by the rules: they are reported on
+//! the current thread instead, and can be collected while parsing a datum.
+//!
+//! # Examples
+//!
+//! ```
+//! use tagua_parser::diagnostics::{self, Diagnostic, DiagnosticKind};
+//! use tagua_parser::rules::expressions::expression;
+//! use tagua_parser::version::{self, Version};
+//!
+//! version::set_target(Version::Php74);
+//!
+//! let input                  = b"$foo{0}";
+//! let (_result, diagnostics) = diagnostics::collect(&input[..], expression);
+//!
+//! assert_eq!(
+//!     diagnostics,
+//!     vec![
+//!         Diagnostic {
+//!             kind: DiagnosticKind::CurlyBracketOffset,
+//!             span: 4..7
+//!         }
+//!     ]
+//! );
+//! ```
+
+use std::cell::{
+    Cell,
+    RefCell
+};
+use std::mem;
+use std::ops::Range;
+use super::internal::Result;
+
+/// The kind of a diagnostic.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum DiagnosticKind {
+    /// An array or string offset accessed with curly brackets, e.g.
+    /// `$foo{0}`, deprecated since PHP 7.4 and removed in PHP 8.0.
+    CurlyBracketOffset,
+
+    /// An invalid `\u{…}` escape sequence in a string, e.g. `"\u{}"` or
+    /// `"\u{110000}"`, kept verbatim. It is an error, unless the syntax
+    /// errors are recovered.
+    InvalidUnicodeEscape,
+
+    /// A syntax error, skipped by the error recovery.
+    SyntaxError
+}
+
+/// A diagnostic about the parsed datum.
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Diagnostic {
+    /// The kind of the diagnostic.
+    pub kind: DiagnosticKind,
+
+    /// The byte range of the concerned construction in the parsed datum.
+    pub span: Range<usize>
+}
+
+thread_local!(
+    static IS_COLLECTING: Cell<bool> = const { Cell::new(false) }
+);
+
+thread_local!(
+    static DIAGNOSTICS: RefCell<Vec<(usize, usize, DiagnosticKind)>> = const { RefCell::new(Vec::new()) }
+);
+
+/// Run a parser on a datum and collect all the diagnostics it has
+/// reported, sorted by position.
+///
+/// Because the parser backtracks, a diagnostic can be reported several
+/// times; it is collected once.
+pub fn collect<'a, F, T>(input: &'a [u8], parser: F) -> (T, Vec<Diagnostic>)
+    where F: FnOnce(&'a [u8]) -> T
+{
+    let collection  = CollectionGuard::new();
+    let output      = parser(input);
+    let mut records = collection.records();
+    let start       = input.as_ptr() as usize;
+    let end         = start + input.len();
+
+    records.sort();
+    records.dedup();
+
+    let diagnostics = records
+        .into_iter()
+        .filter(|&(diagnostic_start, diagnostic_end, _)| diagnostic_start >= start && diagnostic_end <= end)
+        .map(
+            |(diagnostic_start, diagnostic_end, kind)| {
+                Diagnostic {
+                    kind: kind,
+                    span: diagnostic_start - start..diagnostic_end - start
+                }
+            }
+        )
+        .collect();
+
+    (output, diagnostics)
+}
+
+/// Collect the diagnostics on the current thread, and stop collecting them
+/// when dropped, even if the parser panics.
+struct CollectionGuard;
+
+impl CollectionGuard {
+    fn new() -> CollectionGuard {
+        DIAGNOSTICS.with(|diagnostics| diagnostics.borrow_mut().clear());
+        IS_COLLECTING.with(|is_collecting| is_collecting.set(true));
+
+        CollectionGuard
+    }
+
+    /// Stop collecting, and return the records collected so far.
+    fn records(self) -> Vec<(usize, usize, DiagnosticKind)> {
+        DIAGNOSTICS.with(|diagnostics| mem::take(&mut *diagnostics.borrow_mut()))
+    }
+}
+
+impl Drop for CollectionGuard {
+    fn drop(&mut self) {
+        IS_COLLECTING.with(|is_collecting| is_collecting.set(false));
+        DIAGNOSTICS.with(|diagnostics| diagnostics.borrow_mut().clear());
+    }
+}
+
+/// Run a parser, and discard the diagnostics it has reported if it fails:
+/// the parser backtracks, so the concerned constructions are not part of
+/// the result.
+pub fn attempt<'a, F, O>(input: &'a [u8], parser: F) -> Result<&'a [u8], O>
+    where F: FnOnce(&'a [u8]) -> Result<&'a [u8], O>
+{
+    let length = DIAGNOSTICS.with(|diagnostics| diagnostics.borrow().len());
+    let output = parser(input);
+
+    if let Result::Done(_, _) = output {
+        return output;
+    }
+
+    DIAGNOSTICS.with(|diagnostics| diagnostics.borrow_mut().truncate(length));
+
+    output
+}
+
+/// Report a diagnostic if the diagnostics are being collected on the
+/// current thread. The concerned construction spans from `input` to
+/// `rest`.
+pub fn report(input: &[u8], rest: &[u8], kind: DiagnosticKind) {
+    if !IS_COLLECTING.with(|is_collecting| is_collecting.get()) {
+        return;
+    }
+
+    DIAGNOSTICS.with(
+        |diagnostics| {
+            diagnostics.borrow_mut().push((
+                input.as_ptr() as usize,
+                rest.as_ptr() as usize,
+                kind
+            ))
+        }
+    );
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::panic;
+    use super::{
+        DIAGNOSTICS,
+        Diagnostic,
+        DiagnosticKind,
+        IS_COLLECTING,
+        attempt,
+        collect,
+        report
+    };
+    use super::super::internal::{
+        Error,
+        ErrorKind,
+        Result
+    };
+
+    #[test]
+    fn case_collect_nothing() {
+        let input = b"foo";
+        let (output, diagnostics) = collect(&input[..], |input| input.len());
+
+        assert_eq!(output, 3);
+        assert_eq!(diagnostics, vec![]);
+    }
+
+    #[test]
+    fn case_collect_sorted_and_once() {
+        let input = b"foobar";
+        let (_, diagnostics) = collect(
+            &input[..],
+            |input| {
+                report(&input[3..], &input[6..], DiagnosticKind::CurlyBracketOffset);
+                report(&input[0..], &input[3..], DiagnosticKind::CurlyBracketOffset);
+                report(&input[3..], &input[6..], DiagnosticKind::CurlyBracketOffset);
+            }
+        );
+
+        assert_eq!(
+            diagnostics,
+            vec![
+                Diagnostic {
+                    kind: DiagnosticKind::CurlyBracketOffset,
+                    span: 0..3
+                },
+                Diagnostic {
+                    kind: DiagnosticKind::CurlyBracketOffset,
+                    span: 3..6
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn case_collect_ignores_other_data() {
+        let input = b"foo";
+        let other = b"bar";
+        let (_, diagnostics) = collect(
+            &input[..],
+            |_| report(&other[..], &other[3..], DiagnosticKind::CurlyBracketOffset)
+        );
+
+        assert_eq!(diagnostics, vec![]);
+    }
+
+    #[test]
+    fn case_attempt_keeps_the_diagnostics_of_a_success() {
+        let input = b"foobar";
+        let (_, diagnostics) = collect(
+            &input[..],
+            |input| {
+                attempt(
+                    input,
+                    |input| {
+                        report(&input[0..], &input[3..], DiagnosticKind::CurlyBracketOffset);
+
+                        Result::Done(&input[3..], ())
+                    }
+                )
+            }
+        );
+
+        assert_eq!(
+            diagnostics,
+            vec![
+                Diagnostic {
+                    kind: DiagnosticKind::CurlyBracketOffset,
+                    span: 0..3
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn case_attempt_discards_the_diagnostics_of_a_failure() {
+        let input = b"foobar";
+        let (_, diagnostics) = collect(
+            &input[..],
+            |input| {
+                report(&input[0..], &input[3..], DiagnosticKind::CurlyBracketOffset);
+
+                attempt(
+                    input,
+                    |input| -> Result<&[u8], ()> {
+                        report(&input[3..], &input[6..], DiagnosticKind::CurlyBracketOffset);
+
+                        Result::Error(Error::Position(ErrorKind::Tag, input))
+                    }
+                )
+            }
+        );
+
+        assert_eq!(
+            diagnostics,
+            vec![
+                Diagnostic {
+                    kind: DiagnosticKind::CurlyBracketOffset,
+                    span: 0..3
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn case_diagnostics_are_not_reported_outside_collect() {
+        let input = b"foo";
+
+        report(&input[..], &input[3..], DiagnosticKind::CurlyBracketOffset);
+
+        assert!(DIAGNOSTICS.with(|diagnostics| diagnostics.borrow().is_empty()));
+    }
+
+    #[test]
+    fn case_collect_stops_collecting_on_panic() {
+        let input  = b"foo";
+        let result = panic::catch_unwind(
+            || {
+                collect(
+                    &input[..],
+                    |input| {
+                        report(&input[0..], &input[3..], DiagnosticKind::CurlyBracketOffset);
+
+                        panic!("The parser panics.");
+                    }
+                )
+            }
+        );
+
+        assert!(result.is_err());
+        assert!(!IS_COLLECTING.with(|is_collecting| is_collecting.get()));
+        assert!(DIAGNOSTICS.with(|diagnostics| diagnostics.borrow().is_empty()));
+    }
+}
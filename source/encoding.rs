@@ -0,0 +1,197 @@
+// Tagua VM
+//
+//
+// New BSD License
+//
+// Copyright © 2016-2016, Ivan Enderlin.
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//     * Redistributions of source code must retain the above copyright
+//       notice, this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright
+//       notice, this list of conditions and the following disclaimer in the
+//       documentation and/or other materials provided with the distribution.
+//     * Neither the name of the Hoa nor the names of its contributors may be
+//       used to endorse or promote products derived from this software without
+//       specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE
+// ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDERS AND CONTRIBUTORS BE
+// LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR
+// CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF
+// SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS
+// INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN
+// CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+
+//! Encodings of the input.
+//!
+//! PHP source is byte-oriented: the parser never validates UTF-8, and bytes
+//! of another encoding, like Latin-1 or Windows-1252, are kept as is in the
+//! literals and the comments. A UTF-8 byte order mark starting a script is
+//! skipped. When the AST must be UTF-8, a datum can be transcoded before
+//! being parsed.
+//!
+//! # Examples
+//!
+//! ```
+//! use tagua_parser::encoding::{self, Encoding};
+//!
+//! let input = b"'caf\xe9'";
+//!
+//! assert_eq!(
+//!     encoding::transcode(&input[..], Encoding::Latin1).into_owned(),
+//!     "'café'".as_bytes().to_vec()
+//! );
+//! ```
+
+use std::borrow::Cow;
+
+/// The byte order mark of UTF-8.
+pub const UTF8_BOM: &[u8] = b"\xef\xbb\xbf";
+
+/// The encoding of a datum.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Encoding {
+    /// UTF-8, with or without a byte order mark.
+    Utf8,
+
+    /// ISO-8859-1, where each byte is a codepoint.
+    Latin1,
+
+    /// Windows-1252, a superset of Latin-1 where the bytes from `0x80` to
+    /// `0x9f` are printable characters, like `€`.
+    Windows1252
+}
+
+/// Windows-1252 codepoints of the bytes from `0x80` to `0x9f`. Undefined
+/// bytes are mapped to their Latin-1 control character.
+const WINDOWS_1252: [u32; 32] = [
+    0x20ac, 0x0081, 0x201a, 0x0192, 0x201e, 0x2026, 0x2020, 0x2021,
+    0x02c6, 0x2030, 0x0160, 0x2039, 0x0152, 0x008d, 0x017d, 0x008f,
+    0x0090, 0x2018, 0x2019, 0x201c, 0x201d, 0x2022, 0x2013, 0x2014,
+    0x02dc, 0x2122, 0x0161, 0x203a, 0x0153, 0x009d, 0x017e, 0x0178
+];
+
+/// Skip the UTF-8 byte order mark starting the datum, if any.
+pub fn skip_bom(input: &[u8]) -> &[u8] {
+    if input.starts_with(UTF8_BOM) {
+        &input[UTF8_BOM.len()..]
+    } else {
+        input
+    }
+}
+
+/// Transcode a datum from the given encoding to UTF-8, without its byte
+/// order mark. The datum is borrowed when no transcoding is needed.
+pub fn transcode(input: &[u8], encoding: Encoding) -> Cow<[u8]> {
+    if encoding == Encoding::Utf8 {
+        return Cow::Borrowed(skip_bom(input));
+    }
+
+    if input.iter().all(|byte| *byte < 0x80) {
+        return Cow::Borrowed(input);
+    }
+
+    let mut output = Vec::with_capacity(input.len() * 2);
+
+    for &byte in input {
+        let codepoint = match encoding {
+            Encoding::Windows1252 if (0x80..0xa0).contains(&byte) => WINDOWS_1252[(byte - 0x80) as usize],
+            _ => byte as u32
+        };
+
+        encode_codepoint(codepoint, &mut output);
+    }
+
+    Cow::Owned(output)
+}
+
+/// Encode a codepoint in UTF-8 into the buffer. Like PHP, surrogates are
+/// encoded as any other codepoint.
+pub fn encode_codepoint(codepoint: u32, buffer: &mut Vec<u8>) {
+    if codepoint < 0x80 {
+        buffer.push(codepoint as u8);
+    } else if codepoint < 0x800 {
+        buffer.push((0xc0 | (codepoint >> 6)) as u8);
+        buffer.push((0x80 | (codepoint & 0x3f)) as u8);
+    } else if codepoint < 0x10000 {
+        buffer.push((0xe0 | (codepoint >> 12)) as u8);
+        buffer.push((0x80 | ((codepoint >> 6) & 0x3f)) as u8);
+        buffer.push((0x80 | (codepoint & 0x3f)) as u8);
+    } else {
+        buffer.push((0xf0 | (codepoint >> 18)) as u8);
+        buffer.push((0x80 | ((codepoint >> 12) & 0x3f)) as u8);
+        buffer.push((0x80 | ((codepoint >> 6) & 0x3f)) as u8);
+        buffer.push((0x80 | (codepoint & 0x3f)) as u8);
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+    use super::{
+        Encoding,
+        encode_codepoint,
+        skip_bom,
+        transcode
+    };
+
+    #[test]
+    fn case_skip_bom() {
+        assert_eq!(skip_bom(b"\xef\xbb\xbf<?php"), &b"<?php"[..]);
+    }
+
+    #[test]
+    fn case_skip_no_bom() {
+        assert_eq!(skip_bom(b"<?php \xef\xbb\xbf"), &b"<?php \xef\xbb\xbf"[..]);
+    }
+
+    #[test]
+    fn case_transcode_utf8_is_borrowed() {
+        let input = "\u{feff}'café'".as_bytes();
+
+        assert_eq!(transcode(input, Encoding::Utf8), Cow::Borrowed("'café'".as_bytes()));
+    }
+
+    #[test]
+    fn case_transcode_ascii_is_borrowed() {
+        let input = b"'cafe'";
+
+        assert_eq!(transcode(input, Encoding::Latin1), Cow::Borrowed(&input[..]));
+    }
+
+    #[test]
+    fn case_transcode_latin1() {
+        let input  = b"'\x80\xe9\xff'";
+        let output = "'\u{80}é\u{ff}'".as_bytes();
+
+        assert_eq!(transcode(input, Encoding::Latin1).into_owned(), output.to_vec());
+    }
+
+    #[test]
+    fn case_transcode_windows1252() {
+        let input  = b"'\x80\x81\x99\x9f\xe9'";
+        let output = "'€\u{81}™Ÿé'".as_bytes();
+
+        assert_eq!(transcode(input, Encoding::Windows1252).into_owned(), output.to_vec());
+    }
+
+    #[test]
+    fn case_encode_codepoint() {
+        let mut buffer = vec![];
+
+        encode_codepoint(0x41, &mut buffer);
+        encode_codepoint(0xe9, &mut buffer);
+        encode_codepoint(0x20ac, &mut buffer);
+        encode_codepoint(0x1f418, &mut buffer);
+
+        assert_eq!(buffer, "Aé€🐘".as_bytes().to_vec());
+    }
+}
@@ -0,0 +1,531 @@
+// Tagua VM
+//
+//
+// New BSD License
+//
+// Copyright © 2016-2016, Ivan Enderlin.
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//     * Redistributions of source code must retain the above copyright
+//       notice, this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright
+//       notice, this list of conditions and the following disclaimer in the
+//       documentation and/or other materials provided with the distribution.
+//     * Neither the name of the Hoa nor the names of its contributors may be
+//       used to endorse or promote products derived from this software without
+//       specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE
+// ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDERS AND CONTRIBUTORS BE
+// LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR
+// CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF
+// SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS
+// INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN
+// CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+
+//! Errors of the parser.
+//!
+//! The rules return the errors of nom, whose positions are slices of the
+//! datum. A `ParseError` turns them into a located and human-readable
+//! error.
+//!
+//! Because the rules backtrack, the error of nom is often located where
+//! the failing statement starts. While running a rule with `track`, the
+//! rules report the furthest position where they have failed, with the
+//! tokens they expected there, see `expect`; the error is located there
+//! instead.
+//!
+//! The custom error kinds have distinct codes: the ones of the macros
+//! start at 0, the ones of the rules at 100, 200, and so on.
+//!
+//! # Examples
+//!
+//! ```
+//! use tagua_parser::error;
+//! use tagua_parser::rules::script::script;
+//!
+//! let error = error::track(&b"<?php\nreturn 1 2;"[..], "script", script).unwrap_err();
+//!
+//! assert_eq!(
+//!     error.to_string(),
+//!     "Syntax error at line 2, column 10, in the `return_statement` rule: unexpected `2;` (Tag), expected `;`."
+//! );
+//! ```
+
+use std::cell::{
+    Cell,
+    RefCell
+};
+use std::error;
+use std::fmt;
+use std::result::Result as StdResult;
+use super::internal::{
+    Error,
+    ErrorKind,
+    Needed,
+    Result
+};
+use super::macros::ErrorKindCustom;
+use super::rules::expressions::primaries::IntrinsicError;
+use super::rules::literals::StringError;
+use super::rules::script::ScriptError;
+use super::span::{
+    Location,
+    locate
+};
+
+/// A located error of the parser.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParseError {
+    /// The location of the failure in the datum.
+    pub location: Location,
+
+    /// The name of the rule that has failed.
+    pub rule: &'static str,
+
+    /// The kind of the failure, from the deepest error.
+    pub kind: ErrorKind,
+
+    /// The bytes found at the location, up to the end of the line.
+    pub found: Vec<u8>,
+
+    /// The tokens that were expected at the location, if known.
+    pub expected: Vec<&'static [u8]>
+}
+
+impl ParseError {
+    /// Locate an error returned by a rule. The location is the one of the
+    /// deepest position of the error, or the start of the datum if it has
+    /// no position.
+    pub fn new(datum: &[u8], rule: &'static str, error: Error<&[u8]>) -> ParseError {
+        let (kind, position) = deepest(error, None);
+        let offset           = match position {
+            Some(position) => position.as_ptr() as usize - datum.as_ptr() as usize,
+            None => 0
+        };
+
+        ParseError {
+            location: locate(datum, offset),
+            rule    : rule,
+            kind    : kind,
+            found   : found(datum, offset),
+            expected: vec![]
+        }
+    }
+
+    /// Create an error for a rule that needs more bytes than the datum
+    /// contains.
+    pub fn incomplete(datum: &[u8], rule: &'static str, _needed: Needed) -> ParseError {
+        ParseError {
+            location: locate(datum, datum.len()),
+            rule    : rule,
+            kind    : ErrorKind::Complete,
+            found   : vec![],
+            expected: vec![]
+        }
+    }
+
+    /// Set the tokens that were expected at the location.
+    pub fn expecting(mut self, expected: Vec<&'static [u8]>) -> ParseError {
+        self.expected = expected;
+
+        self
+    }
+}
+
+/// Find the kind and the position of the deepest error.
+fn deepest<'a>(error: Error<&'a [u8]>, position: Option<&'a [u8]>) -> (ErrorKind, Option<&'a [u8]>) {
+    match error {
+        Error::Code(kind) => (kind, position),
+        Error::Node(_, next) => deepest(*next, position),
+        Error::Position(kind, position) => (kind, Some(position)),
+        Error::NodePosition(_, position, next) => deepest(*next, Some(position))
+    }
+}
+
+/// Collect the bytes found at an offset of the datum, up to the end of the
+/// line.
+fn found(datum: &[u8], offset: usize) -> Vec<u8> {
+    datum[offset..]
+        .iter()
+        .take_while(|&&byte| byte != b'\n' && byte != b'\r')
+        .cloned()
+        .collect()
+}
+
+/// The furthest failure reported while tracking: its position, the rule
+/// that has failed, the kind of the failure, and the expected tokens.
+type Failure = (usize, &'static str, ErrorKind, Vec<&'static [u8]>);
+
+thread_local!(
+    static IS_TRACKING: Cell<bool> = const { Cell::new(false) }
+);
+
+thread_local!(
+    static FAILURE: RefCell<Option<Failure>> = const { RefCell::new(None) }
+);
+
+/// Run a rule on a datum, and locate its error, if any. When the rules
+/// have reported a failure further than the error of nom, see `expect`,
+/// the error is the furthest failure, with the name of the rule that has
+/// failed and the expected tokens. Otherwise, the error is the one of
+/// nom, with the given rule name.
+pub fn track<'a, F, O>(datum: &'a [u8], rule: &'static str, parser: F) -> StdResult<O, ParseError>
+    where F: FnOnce(&'a [u8]) -> Result<&'a [u8], O>
+{
+    let tracking = TrackingGuard::new();
+    let output   = parser(datum);
+    let failure  = tracking.failure();
+    let error    = match output {
+        Result::Done(_, output) => return Ok(output),
+        Result::Error(error) => ParseError::new(datum, rule, error),
+        Result::Incomplete(needed) => ParseError::incomplete(datum, rule, needed)
+    };
+    let start = datum.as_ptr() as usize;
+
+    match failure {
+        Some((position, rule, kind, expected)) if position >= start + error.location.offset && position <= start + datum.len() => {
+            let offset = position - start;
+
+            Err(
+                ParseError {
+                    location: locate(datum, offset),
+                    rule    : rule,
+                    kind    : kind,
+                    found   : found(datum, offset),
+                    expected: expected
+                }
+            )
+        },
+
+        _ => Err(error)
+    }
+}
+
+/// Track the failures on the current thread, and stop tracking them when
+/// dropped, even if the parser panics.
+struct TrackingGuard;
+
+impl TrackingGuard {
+    fn new() -> TrackingGuard {
+        FAILURE.with(|failure| *failure.borrow_mut() = None);
+        IS_TRACKING.with(|is_tracking| is_tracking.set(true));
+
+        TrackingGuard
+    }
+
+    /// Stop tracking, and return the furthest failure.
+    fn failure(self) -> Option<Failure> {
+        FAILURE.with(|failure| failure.borrow_mut().take())
+    }
+}
+
+impl Drop for TrackingGuard {
+    fn drop(&mut self) {
+        IS_TRACKING.with(|is_tracking| is_tracking.set(false));
+        FAILURE.with(|failure| *failure.borrow_mut() = None);
+    }
+}
+
+/// Report the result of a rule, run on `input`, if the failures are being
+/// tracked on the current thread. If it has failed, the rule was expecting
+/// the given tokens, if any; the failure is kept if it is the furthest one.
+/// The tokens expected by several rules at the same position are merged.
+pub fn expect<O>(input: &[u8], rule: &'static str, expected: &[&'static [u8]], result: &Result<&[u8], O>) {
+    if !IS_TRACKING.with(|is_tracking| is_tracking.get()) {
+        return;
+    }
+
+    let kind = match *result {
+        Result::Done(_, _) => return,
+        Result::Error(_) if !expected.is_empty() => ErrorKind::Tag,
+        Result::Error(ref error) => top_kind(error),
+        Result::Incomplete(_) => ErrorKind::Complete
+    };
+    let position = input.as_ptr() as usize;
+
+    FAILURE.with(
+        |failure| {
+            let mut failure = failure.borrow_mut();
+
+            if let Some((furthest_position, _, _, ref mut furthest_expected)) = *failure {
+                if position < furthest_position {
+                    return;
+                }
+
+                if position == furthest_position {
+                    for token in expected {
+                        if !furthest_expected.contains(token) {
+                            furthest_expected.push(*token);
+                        }
+                    }
+
+                    return;
+                }
+            }
+
+            *failure = Some((position, rule, kind, expected.to_vec()));
+        }
+    );
+}
+
+/// Find the kind of the outermost error.
+fn top_kind<I>(error: &Error<I>) -> ErrorKind {
+    match *error {
+        Error::Code(ref kind) |
+        Error::Node(ref kind, _) |
+        Error::Position(ref kind, _) |
+        Error::NodePosition(ref kind, _, _) => kind.clone()
+    }
+}
+
+/// Describe a custom error kind of the macros or of the rules.
+fn describe(code: u32) -> Option<&'static str> {
+    CUSTOM_DESCRIPTIONS
+        .iter()
+        .find(|&&(custom_code, _)| custom_code == code)
+        .map(|&(_, description)| description)
+}
+
+const CUSTOM_DESCRIPTIONS: [(u32, &str); 16] = [
+    (ErrorKindCustom::Exclude as u32, "excluded construction"),
+    (ErrorKindCustom::ITag as u32, "case-insensitive tag"),
+    (ErrorKindCustom::Keyword as u32, "keyword followed by a name"),
+    (IntrinsicError::ReservedExitCode as u32, "reserved exit code"),
+    (IntrinsicError::OutOfRangeExitCode as u32, "exit code out of range"),
+    (IntrinsicError::ListIsEmpty as u32, "empty list"),
+    (ScriptError::MixedNamespaceDeclarations as u32, "namespaces declared with and without a body"),
+    (ScriptError::StatementOutsideNamespace as u32, "statement outside the namespaces"),
+    (ScriptError::StatementBeforeNamespace as u32, "statement before the first namespace"),
+    (StringError::TooShort as u32, "string too short"),
+    (StringError::InvalidOpeningCharacter as u32, "invalid opening character of a string"),
+    (StringError::InvalidClosingCharacter as u32, "invalid closing character of a string"),
+    (StringError::InvalidEncoding as u32, "invalid encoding of a string"),
+    (StringError::InvalidDelimiterIdentifier as u32, "invalid delimiter identifier of a string"),
+    (StringError::InvalidIndentation as u32, "invalid indentation of a string"),
+    (StringError::InvalidUnicodeEscape as u32, "invalid unicode escape sequence")
+];
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        let mut message = format!(
+            "Syntax error at line {}, column {}, in the `{}` rule: ",
+            self.location.line,
+            self.location.column,
+            self.rule
+        );
+
+        if self.found.is_empty() {
+            message.push_str("unexpected end of the datum");
+        } else {
+            message.push_str(&format!("unexpected `{}`", String::from_utf8_lossy(&self.found)));
+        }
+
+        match self.kind {
+            ErrorKind::Custom(code) => {
+                match describe(code) {
+                    Some(description) => message.push_str(&format!(" ({})", description)),
+                    None => message.push_str(&format!(" (error {})", code))
+                }
+            },
+
+            ref kind => message.push_str(&format!(" ({})", kind.description()))
+        }
+
+        if !self.expected.is_empty() {
+            let expected: Vec<String> = self.expected
+                .iter()
+                .map(|token| format!("`{}`", String::from_utf8_lossy(token)))
+                .collect();
+
+            message.push_str(&format!(", expected {}", expected.join(" or ")));
+        }
+
+        message.push('.');
+
+        formatter.write_str(&message)
+    }
+}
+
+impl error::Error for ParseError {
+    fn description(&self) -> &str {
+        "syntax error"
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::panic;
+    use super::{
+        FAILURE,
+        IS_TRACKING,
+        ParseError,
+        expect,
+        track
+    };
+    use super::super::internal::{
+        Error,
+        ErrorKind,
+        Needed,
+        Result
+    };
+    use super::super::rules::literals::StringError;
+    use super::super::span::Location;
+
+    #[test]
+    fn case_error_with_a_position() {
+        let datum  = b"foo\nbar baz\nqux";
+        let error  = ParseError::new(&datum[..], "test", Error::Position(ErrorKind::Tag, &datum[8..]));
+        let output = ParseError {
+            location: Location { offset: 8, line: 2, column: 5 },
+            rule    : "test",
+            kind    : ErrorKind::Tag,
+            found   : b"baz".to_vec(),
+            expected: vec![]
+        };
+
+        assert_eq!(error, output);
+        assert_eq!(error.to_string(), "Syntax error at line 2, column 5, in the `test` rule: unexpected `baz` (Tag).");
+    }
+
+    #[test]
+    fn case_error_with_the_deepest_position() {
+        let datum = b"foo bar";
+        let error = ParseError::new(
+            &datum[..],
+            "test",
+            Error::NodePosition(
+                ErrorKind::Alt,
+                &datum[..],
+                Box::new(Error::Node(ErrorKind::Many0, Box::new(Error::Position(ErrorKind::Custom(42), &datum[4..]))))
+            )
+        );
+
+        assert_eq!(error.location, Location { offset: 4, line: 1, column: 5 });
+        assert_eq!(error.kind, ErrorKind::Custom(42));
+        assert_eq!(error.to_string(), "Syntax error at line 1, column 5, in the `test` rule: unexpected `bar` (error 42).");
+    }
+
+    #[test]
+    fn case_error_without_a_position() {
+        let datum = b"foo";
+        let error = ParseError::new(&datum[..], "test", Error::Node(ErrorKind::Alt, Box::new(Error::Code(ErrorKind::Tag))));
+
+        assert_eq!(error.location, Location { offset: 0, line: 1, column: 1 });
+        assert_eq!(error.kind, ErrorKind::Tag);
+    }
+
+    #[test]
+    fn case_error_incomplete() {
+        let datum = b"foo\n";
+        let error = ParseError::incomplete(&datum[..], "test", Needed::Size(5));
+
+        assert_eq!(error.location, Location { offset: 4, line: 2, column: 1 });
+        assert_eq!(error.to_string(), "Syntax error at line 2, column 1, in the `test` rule: unexpected end of the datum (Complete).");
+    }
+
+    #[test]
+    fn case_error_expecting() {
+        let datum = b"foo";
+        let error = ParseError::new(&datum[..], "test", Error::Position(ErrorKind::Tag, &datum[3..])).expecting(vec![b")", b","]);
+
+        assert_eq!(error.to_string(), "Syntax error at line 1, column 4, in the `test` rule: unexpected end of the datum (Tag), expected `)` or `,`.");
+    }
+
+    #[test]
+    fn case_error_with_a_custom_kind() {
+        let datum = b"'";
+        let error = ParseError::new(&datum[..], "test", Error::Position(ErrorKind::Custom(StringError::TooShort as u32), &datum[..]));
+
+        assert_eq!(error.to_string(), "Syntax error at line 1, column 1, in the `test` rule: unexpected `'` (string too short).");
+    }
+
+    named!(
+        alternatives,
+        preceded!(
+            tag!("foo"),
+            alt!(
+                expect!("bar", &b"bar"[..])
+              | expect!("baz", &b"baz"[..])
+            )
+        )
+    );
+
+    named!(
+        near_and_far,
+        alt!(
+            expect!("near", &b"qux"[..])
+          | preceded!(tag!("foo"), expect!("far", &b"bar"[..]))
+        )
+    );
+
+    #[test]
+    fn case_track_without_error() {
+        assert_eq!(track(&b"foobar"[..], "test", alternatives), Ok(&b"bar"[..]));
+    }
+
+    #[test]
+    fn case_track_merges_the_expected_tokens() {
+        let error = track(&b"foo qux"[..], "test", alternatives).unwrap_err();
+
+        assert_eq!(error.location, Location { offset: 3, line: 1, column: 4 });
+        assert_eq!(error.rule, "bar");
+        assert_eq!(error.kind, ErrorKind::Tag);
+        assert_eq!(error.expected, vec![&b"bar"[..], &b"baz"[..]]);
+    }
+
+    #[test]
+    fn case_track_keeps_the_furthest_failure() {
+        let error = track(&b"foobaz"[..], "test", near_and_far).unwrap_err();
+
+        assert_eq!(error.location, Location { offset: 3, line: 1, column: 4 });
+        assert_eq!(error.rule, "far");
+        assert_eq!(error.expected, vec![&b"bar"[..]]);
+    }
+
+    #[test]
+    fn case_track_without_a_reported_failure() {
+        let error = track(&b"foo"[..], "test", |input: &[u8]| -> Result<&[u8], ()> { Result::Error(Error::Position(ErrorKind::Tag, &input[1..])) }).unwrap_err();
+
+        assert_eq!(error.location, Location { offset: 1, line: 1, column: 2 });
+        assert_eq!(error.rule, "test");
+        assert_eq!(error.expected, Vec::<&[u8]>::new());
+    }
+
+    #[test]
+    fn case_failures_are_not_reported_outside_track() {
+        let input = b"foo";
+
+        expect(&input[..], "test", &[b"bar"], &Result::Error::<&[u8], ()>(Error::Position(ErrorKind::Tag, &input[..])));
+
+        assert!(FAILURE.with(|failure| failure.borrow().is_none()));
+    }
+
+    #[test]
+    fn case_track_stops_tracking_on_panic() {
+        let result = panic::catch_unwind(
+            || {
+                track(
+                    &b"foo"[..],
+                    "test",
+                    |input: &[u8]| -> Result<&[u8], ()> {
+                        expect(input, "test", &[b"bar"], &Result::Error::<&[u8], ()>(Error::Position(ErrorKind::Tag, input)));
+
+                        panic!("The parser panics.");
+                    }
+                )
+            }
+        );
+
+        assert!(result.is_err());
+        assert!(!IS_TRACKING.with(|is_tracking| is_tracking.get()));
+        assert!(FAILURE.with(|failure| failure.borrow().is_none()));
+    }
+}
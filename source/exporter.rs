@@ -0,0 +1,1813 @@
+// Tagua VM
+//
+//
+// New BSD License
+//
+// Copyright © 2016-2016, Ivan Enderlin.
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//     * Redistributions of source code must retain the above copyright
+//       notice, this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright
+//       notice, this list of conditions and the following disclaimer in the
+//       documentation and/or other materials provided with the distribution.
+//     * Neither the name of the Hoa nor the names of its contributors may be
+//       used to endorse or promote products derived from this software without
+//       specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE
+// ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDERS AND CONTRIBUTORS BE
+// LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR
+// CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF
+// SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS
+// INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN
+// CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+
+//! Export of the AST to the JSON format of nikic/php-parser.
+//!
+//! [nikic/php-parser](https://github.com/nikic/PHP-Parser) is the de facto
+//! PHP parser written in PHP, and many tools consume the JSON dump of its
+//! nodes. The `export` function maps the AST onto the same node names and
+//! sub-nodes (version 5 of nikic/php-parser), so that these tools can use
+//! this parser without being rewritten.
+//!
+//! Every node is an object with a `nodeType` key, its sub-nodes, and an
+//! `attributes` key. The AST does not locate its nodes, so the only
+//! attributes are the documentation comment of a declaration, in
+//! `comments`, and the kind and the label of a heredoc or of a nowdoc, in
+//! `kind` and `docLabel`.
+//! Byte strings that are not valid UTF-8 are converted lossily, like
+//! `json_encode` would refuse them.
+//!
+//! Some constructions are normalized by the parser, hence they are
+//! exported in their normalized form: The alternative syntax becomes
+//! blocks, group uses become flat uses, and the default visibility is
+//! exported as `public`.
+//!
+//! # Examples
+//!
+//! ```
+//! use tagua_parser::parse;
+//! use tagua_parser::exporter::export;
+//!
+//! let program = parse(b"<?php echo $x;").unwrap();
+//!
+//! assert_eq!(
+//!     export(&program.statements),
+//!     "[{\"nodeType\":\"Stmt_Echo\",\"exprs\":[{\"nodeType\":\"Expr_Variable\",\"name\":\"x\",\"attributes\":[]}],\"attributes\":[]}]"
+//! );
+//! ```
+
+use super::ast::{
+    Argument,
+    Attribute,
+    CastType,
+    Catch,
+    ClassMember,
+    ConstantExpression,
+    DeclareDirective,
+    Expression,
+    IncludeKind,
+    Literal,
+    MagicConstant,
+    MatchArm,
+    Method,
+    Name,
+    Parameter,
+    PropertyHook,
+    PropertyHookBody,
+    PropertyHookKind,
+    RelativeScope,
+    Statement,
+    StringPart,
+    SwitchCase,
+    TraitAdaptation,
+    Type,
+    Use,
+    UseKind,
+    Variable,
+    Visibility
+};
+use super::span::Spanned;
+
+/// The `public` modifier flag.
+const MODIFIER_PUBLIC: i64 = 1;
+/// The `protected` modifier flag.
+const MODIFIER_PROTECTED: i64 = 2;
+/// The `private` modifier flag.
+const MODIFIER_PRIVATE: i64 = 4;
+/// The `static` modifier flag.
+const MODIFIER_STATIC: i64 = 8;
+/// The `abstract` modifier flag.
+const MODIFIER_ABSTRACT: i64 = 16;
+/// The `final` modifier flag.
+const MODIFIER_FINAL: i64 = 32;
+/// The `readonly` modifier flag.
+const MODIFIER_READONLY: i64 = 64;
+/// The `public(set)` modifier flag.
+const MODIFIER_PUBLIC_SET: i64 = 128;
+/// The `protected(set)` modifier flag.
+const MODIFIER_PROTECTED_SET: i64 = 256;
+/// The `private(set)` modifier flag.
+const MODIFIER_PRIVATE_SET: i64 = 512;
+
+/// The `kind` attribute of a list.
+const LIST_KIND_LIST: i64 = 1;
+/// The `kind` attribute of a short list.
+const LIST_KIND_ARRAY: i64 = 2;
+
+/// The `kind` attribute of a heredoc.
+const STRING_KIND_HEREDOC: i64 = 3;
+/// The `kind` attribute of a nowdoc.
+const STRING_KIND_NOWDOC: i64 = 4;
+
+/// A JSON value.
+enum Value {
+    Null,
+    Boolean(bool),
+    Integer(i64),
+    Real(f64),
+    String(String),
+    Array(Vec<Value>),
+    Object(Vec<(&'static str, Value)>)
+}
+
+/// Export statements, e.g. the statements of a `Program`, to the JSON
+/// format of nikic/php-parser.
+pub fn export(statements: &[Spanned<Statement>]) -> String {
+    let mut output = String::new();
+
+    write(&mut output, &script(statements));
+
+    output
+}
+
+/// Export top-level statements. A namespace declared without curly
+/// brackets contains the statements following it, up to the next
+/// namespace.
+fn script(statements: &[Spanned<Statement>]) -> Value {
+    let mut values    = Vec::new();
+    let mut namespace = None;
+
+    for statement in statements {
+        match statement.node {
+            Statement::Namespace { ref name, body: None } => {
+                if let Some((name, stmts)) = namespace.take() {
+                    values.push(namespace_node(name, stmts));
+                }
+
+                namespace = Some((name.as_ref().map(|name| self::name(name)).unwrap_or(Value::Null), Vec::new()));
+            },
+
+            ref statement => {
+                match namespace {
+                    Some((_, ref mut stmts)) => stmts.push(self::statement(statement)),
+                    None => values.push(self::statement(statement))
+                }
+            }
+        }
+    }
+
+    if let Some((name, stmts)) = namespace {
+        values.push(namespace_node(name, stmts));
+    }
+
+    Value::Array(values)
+}
+
+fn namespace_node(name: Value, stmts: Vec<Value>) -> Value {
+    node(
+        "Stmt_Namespace",
+        vec![
+            ("name", name),
+            ("stmts", Value::Array(stmts))
+        ]
+    )
+}
+
+/// Build a node, with no attribute.
+fn node(node_type: &'static str, sub_nodes: Vec<(&'static str, Value)>) -> Value {
+    documented_node(node_type, sub_nodes, &None)
+}
+
+/// Build a node, with its documentation comment as attribute.
+fn documented_node(node_type: &'static str, sub_nodes: Vec<(&'static str, Value)>, doc_comment: &Option<Vec<u8>>) -> Value {
+    let attributes = match *doc_comment {
+        Some(ref doc_comment) => {
+            vec![
+                (
+                    "comments",
+                    Value::Array(
+                        vec![
+                            Value::Object(
+                                vec![
+                                    ("nodeType", Value::String("Comment_Doc".to_string())),
+                                    ("text", string(doc_comment))
+                                ]
+                            )
+                        ]
+                    )
+                )
+            ]
+        },
+
+        None => {
+            vec![]
+        }
+    };
+
+    attributed_node(node_type, sub_nodes, attributes)
+}
+
+/// Build a node, with attributes. No attribute is an empty array, like
+/// `json_encode` encodes an empty PHP array.
+fn attributed_node(node_type: &'static str, sub_nodes: Vec<(&'static str, Value)>, attributes: Vec<(&'static str, Value)>) -> Value {
+    let mut fields = Vec::with_capacity(sub_nodes.len() + 2);
+
+    fields.push(("nodeType", Value::String(node_type.to_string())));
+    fields.extend(sub_nodes);
+
+    if attributes.is_empty() {
+        fields.push(("attributes", Value::Array(vec![])));
+    } else {
+        fields.push(("attributes", Value::Object(attributes)));
+    }
+
+    Value::Object(fields)
+}
+
+/// Build the attributes of a heredoc or of a nowdoc: its kind and its
+/// label.
+fn document_attributes(kind: i64, label: &[u8]) -> Vec<(&'static str, Value)> {
+    vec![
+        ("kind", Value::Integer(kind)),
+        ("docLabel", string(label))
+    ]
+}
+
+fn string(bytes: &[u8]) -> Value {
+    Value::String(String::from_utf8_lossy(bytes).into_owned())
+}
+
+fn optional<T, F>(item: &Option<T>, map: F) -> Value
+    where F: Fn(&T) -> Value
+{
+    match *item {
+        Some(ref item) => map(item),
+        None => Value::Null
+    }
+}
+
+fn array<T, F>(items: &[T], map: F) -> Value
+    where F: Fn(&T) -> Value
+{
+    Value::Array(items.iter().map(map).collect())
+}
+
+fn identifier(name: &[u8]) -> Value {
+    node("Identifier", vec![("name", string(name))])
+}
+
+fn name(name: &Name) -> Value {
+    let (node_type, parts) = match *name {
+        Name::Unqualified(name) => ("Name", vec![name]),
+        Name::Qualified(ref parts) => ("Name", parts.clone()),
+        Name::RelativeQualified(ref parts) => ("Name_Relative", parts.clone()),
+        Name::FullyQualified(ref parts) => ("Name_FullyQualified", parts.clone())
+    };
+
+    node(node_type, vec![("name", string(&parts.join(&b"\\"[..])))])
+}
+
+fn special_name(name: &str) -> Value {
+    node("Name", vec![("name", Value::String(name.to_string()))])
+}
+
+fn relative_scope(scope: &RelativeScope) -> Value {
+    special_name(
+        match *scope {
+            RelativeScope::Current => "self",
+            RelativeScope::Parent  => "parent",
+            RelativeScope::Static  => "static"
+        }
+    )
+}
+
+fn variable(variable: &Variable) -> Value {
+    let name = match *variable {
+        Variable::Name(name) => string(name),
+        Variable::Dynamic(ref expression) => self::expression(expression)
+    };
+
+    node("Expr_Variable", vec![("name", name)])
+}
+
+/// Export a class reference, e.g. the class of `new` or of `::`.
+fn class(class: &Expression) -> Value {
+    match *class {
+        Expression::Name(ref class_name) => name(class_name),
+        Expression::RelativeScope(ref scope) => relative_scope(scope),
+        ref class => expression(class)
+    }
+}
+
+/// Export the name of a member, i.e. of a property or a method.
+fn member(member: &Expression) -> Value {
+    match *member {
+        Expression::Name(Name::Unqualified(name)) => identifier(name),
+        ref member => expression(member)
+    }
+}
+
+fn visibility(visibility: Visibility) -> i64 {
+    match visibility {
+        Visibility::Public    => MODIFIER_PUBLIC,
+        Visibility::Protected => MODIFIER_PROTECTED,
+        Visibility::Private   => MODIFIER_PRIVATE
+    }
+}
+
+fn set_visibility(visibility: Option<Visibility>) -> i64 {
+    match visibility {
+        Some(Visibility::Public)    => MODIFIER_PUBLIC_SET,
+        Some(Visibility::Protected) => MODIFIER_PROTECTED_SET,
+        Some(Visibility::Private)   => MODIFIER_PRIVATE_SET,
+        None                        => 0
+    }
+}
+
+fn flag(is_set: bool, flag: i64) -> i64 {
+    if is_set { flag } else { 0 }
+}
+
+fn ty(ty: &Type) -> Value {
+    let builtin = match *ty {
+        Type::Array    => "array",
+        Type::Bool     => "bool",
+        Type::Callable => "callable",
+        Type::False    => "false",
+        Type::Float    => "float",
+        Type::Int      => "int",
+        Type::Iterable => "iterable",
+        Type::Mixed    => "mixed",
+        Type::Never    => "never",
+        Type::Null     => "null",
+        Type::Object   => "object",
+        Type::Static   => "static",
+        Type::String   => "string",
+        Type::True     => "true",
+        Type::Void     => "void",
+
+        Type::Current => {
+            return special_name("self");
+        },
+
+        Type::Parent => {
+            return special_name("parent");
+        },
+
+        Type::Intersection(ref types) => {
+            return node("IntersectionType", vec![("types", array(types, |item| self::ty(item)))]);
+        },
+
+        Type::Name(ref type_name) => {
+            return name(type_name);
+        },
+
+        Type::Nullable(ref nullable_type) => {
+            return node("NullableType", vec![("type", self::ty(nullable_type))]);
+        },
+
+        Type::Union(ref types) => {
+            return node("UnionType", vec![("types", array(types, |item| self::ty(item)))]);
+        }
+    };
+
+    identifier(builtin.as_bytes())
+}
+
+fn optional_type(optional_type: &Option<Spanned<Type>>) -> Value {
+    optional(optional_type, |item| ty(item))
+}
+
+fn literal(literal: &Literal) -> Value {
+    match *literal {
+        Literal::Boolean(true) => {
+            node("Expr_ConstFetch", vec![("name", special_name("true"))])
+        },
+
+        Literal::Boolean(false) => {
+            node("Expr_ConstFetch", vec![("name", special_name("false"))])
+        },
+
+        Literal::Integer(value) => {
+            node("Scalar_Int", vec![("value", Value::Integer(value))])
+        },
+
+        Literal::Nowdoc { ref label, ref value, .. } => {
+            attributed_node(
+                "Scalar_String",
+                vec![("value", string(value))],
+                document_attributes(STRING_KIND_NOWDOC, label)
+            )
+        },
+
+        Literal::Null => {
+            node("Expr_ConstFetch", vec![("name", special_name("null"))])
+        },
+
+        Literal::Real(value) => {
+            node("Scalar_Float", vec![("value", Value::Real(value))])
+        },
+
+        Literal::String(ref value) => {
+            node("Scalar_String", vec![("value", string(value))])
+        }
+    }
+}
+
+fn constant_expression(constant_expression: &ConstantExpression) -> Value {
+    expression(&constant_expression.0)
+}
+
+fn attribute_groups(attributes: &[Attribute]) -> Value {
+    array(
+        attributes,
+        |attribute| {
+            node(
+                "AttributeGroup",
+                vec![
+                    (
+                        "attrs",
+                        Value::Array(
+                            vec![
+                                node(
+                                    "Attribute",
+                                    vec![
+                                        ("name", name(&attribute.name)),
+                                        ("args", arguments(&attribute.arguments))
+                                    ]
+                                )
+                            ]
+                        )
+                    )
+                ]
+            )
+        }
+    )
+}
+
+fn argument(argument: &Argument) -> Value {
+    node(
+        "Arg",
+        vec![
+            ("name", optional(&argument.name, |name| identifier(name))),
+            ("value", expression(&argument.value)),
+            ("byRef", Value::Boolean(false)),
+            ("unpack", Value::Boolean(argument.is_unpacked))
+        ]
+    )
+}
+
+fn arguments(arguments: &[Spanned<Argument>]) -> Value {
+    array(arguments, |item| argument(item))
+}
+
+fn parameter(parameter: &Parameter) -> Value {
+    let flags =
+        parameter.visibility.map(visibility).unwrap_or(0) |
+        set_visibility(parameter.set_visibility) |
+        flag(parameter.is_readonly, MODIFIER_READONLY);
+
+    node(
+        "Param",
+        vec![
+            ("attrGroups", attribute_groups(&parameter.attributes)),
+            ("flags", Value::Integer(flags)),
+            ("type", optional_type(&parameter.ty)),
+            ("byRef", Value::Boolean(parameter.is_reference)),
+            ("variadic", Value::Boolean(parameter.is_variadic)),
+            ("var", variable(&parameter.name)),
+            ("default", optional(&parameter.value, |value| constant_expression(value))),
+            ("hooks", array(&parameter.hooks, |hook| property_hook(hook)))
+        ]
+    )
+}
+
+fn parameters(parameters: &[Spanned<Parameter>]) -> Value {
+    array(parameters, |item| parameter(item))
+}
+
+/// Export an item of an array or of a list.
+fn array_item(key: &Option<Spanned<Expression>>, value: &Expression) -> Value {
+    let (value, is_reference) = match *value {
+        Expression::Reference(ref value) => (&value.node, true),
+        ref value => (value, false)
+    };
+
+    node(
+        "ArrayItem",
+        vec![
+            ("key", optional(key, |key| expression(key))),
+            ("value", expression(value)),
+            ("byRef", Value::Boolean(is_reference)),
+            ("unpack", Value::Boolean(false))
+        ]
+    )
+}
+
+/// Export the items of a list or of a short list, where a skipped element
+/// is `null`.
+fn list_items(items: &[Option<(Option<Spanned<Expression>>, Spanned<Expression>)>]) -> Value {
+    array(
+        items,
+        |item| {
+            match *item {
+                Some((ref key, ref value)) => array_item(key, value),
+                None => Value::Null
+            }
+        }
+    )
+}
+
+fn string_parts(parts: &[StringPart]) -> Value {
+    array(
+        parts,
+        |part| {
+            match *part {
+                StringPart::Bytes(ref bytes) => {
+                    node("InterpolatedStringPart", vec![("value", string(bytes))])
+                },
+
+                StringPart::Expression(ref part_expression) => {
+                    expression(part_expression)
+                }
+            }
+        }
+    )
+}
+
+fn match_arm(arm: &MatchArm) -> Value {
+    node(
+        "MatchArm",
+        vec![
+            ("conds", optional(&arm.conditions, |conditions| array(conditions, |condition| expression(condition)))),
+            ("body", expression(&arm.body))
+        ]
+    )
+}
+
+fn expressions(expressions: &[Spanned<Expression>]) -> Value {
+    array(expressions, |item| expression(item))
+}
+
+fn function_call(callee: &Expression, arguments: Value) -> Value {
+    let name = match *callee {
+        Expression::Name(ref function_name) => name(function_name),
+        ref callee => expression(callee)
+    };
+
+    node("Expr_FuncCall", vec![("name", name), ("args", arguments)])
+}
+
+fn method_call(node_type: &'static str, object: &Expression, method: &Expression, arguments: Value) -> Value {
+    node(
+        node_type,
+        vec![
+            ("var", expression(object)),
+            ("name", member(method)),
+            ("args", arguments)
+        ]
+    )
+}
+
+fn static_call(static_class: &Expression, method: &Expression, arguments: Value) -> Value {
+    node(
+        "Expr_StaticCall",
+        vec![
+            ("class", class(static_class)),
+            ("name", member(method)),
+            ("args", arguments)
+        ]
+    )
+}
+
+/// Export a callable creation, e.g. `$foo->bar(...)`, as a call with a
+/// variadic placeholder.
+fn callable_creation(callee: &Expression) -> Value {
+    let arguments = Value::Array(vec![node("VariadicPlaceholder", vec![])]);
+
+    match *callee {
+        Expression::NullsafePropertyFetch { ref object, ref property } => {
+            method_call("Expr_NullsafeMethodCall", object, property, arguments)
+        },
+
+        Expression::PropertyFetch { ref object, ref property } => {
+            method_call("Expr_MethodCall", object, property, arguments)
+        },
+
+        Expression::StaticFetch { class: ref static_class, member: ref static_member } => {
+            static_call(static_class, static_member, arguments)
+        },
+
+        ref callee => {
+            function_call(callee, arguments)
+        }
+    }
+}
+
+fn expression(expression: &Expression) -> Value {
+    match *expression {
+        Expression::AnonymousClass { ref arguments, ref extends, ref implements, ref members } => {
+            let class = node(
+                "Stmt_Class",
+                vec![
+                    ("attrGroups", Value::Array(vec![])),
+                    ("flags", Value::Integer(0)),
+                    ("name", Value::Null),
+                    ("extends", optional(extends, |extends| name(extends))),
+                    ("implements", array(implements, |item| name(item))),
+                    ("stmts", class_members(members))
+                ]
+            );
+
+            node(
+                "Expr_New",
+                vec![
+                    ("class", class),
+                    ("args", self::arguments(arguments))
+                ]
+            )
+        },
+
+        Expression::Array(ref pairs) => {
+            node("Expr_Array", vec![("items", array(pairs, |(key, value)| array_item(key, value)))])
+        },
+
+        Expression::ArrayAccess { ref array, ref index } => {
+            node(
+                "Expr_ArrayDimFetch",
+                vec![
+                    ("var", self::expression(array)),
+                    ("dim", optional(index, |index| self::expression(index)))
+                ]
+            )
+        },
+
+        Expression::ArrowFunction { is_static, returns_reference, ref parameters, ref return_type, ref body } => {
+            node(
+                "Expr_ArrowFunction",
+                vec![
+                    ("attrGroups", Value::Array(vec![])),
+                    ("static", Value::Boolean(is_static)),
+                    ("byRef", Value::Boolean(returns_reference)),
+                    ("params", self::parameters(parameters)),
+                    ("returnType", optional_type(return_type)),
+                    ("expr", self::expression(body))
+                ]
+            )
+        },
+
+        Expression::Assignment { ref variable, ref value } => {
+            match value.node {
+                Expression::Reference(ref value) => {
+                    node(
+                        "Expr_AssignRef",
+                        vec![
+                            ("var", self::expression(variable)),
+                            ("expr", self::expression(value))
+                        ]
+                    )
+                },
+
+                ref value => {
+                    node(
+                        "Expr_Assign",
+                        vec![
+                            ("var", self::expression(variable)),
+                            ("expr", self::expression(value))
+                        ]
+                    )
+                }
+            }
+        },
+
+        Expression::Call { ref callee, ref arguments } => {
+            function_call(callee, self::arguments(arguments))
+        },
+
+        Expression::CallableCreation(ref callee) => {
+            callable_creation(callee)
+        },
+
+        Expression::Cast { ref ty, ref operand } => {
+            let node_type = match *ty {
+                CastType::Array   => "Expr_Cast_Array",
+                CastType::Boolean => "Expr_Cast_Bool",
+                CastType::Integer => "Expr_Cast_Int",
+                CastType::Object  => "Expr_Cast_Object",
+                CastType::Real    => "Expr_Cast_Double",
+                CastType::String  => "Expr_Cast_String",
+                CastType::Unset   => "Expr_Cast_Unset"
+            };
+
+            node(node_type, vec![("expr", self::expression(operand))])
+        },
+
+        Expression::ClassNameConstant(ref constant_class) => {
+            node(
+                "Expr_ClassConstFetch",
+                vec![
+                    ("class", class(constant_class)),
+                    ("name", identifier(b"class"))
+                ]
+            )
+        },
+
+        Expression::Clone(ref operand) => {
+            node("Expr_Clone", vec![("expr", self::expression(operand))])
+        },
+
+        Expression::Closure { is_static, returns_reference, ref parameters, ref captures, ref return_type, ref body } => {
+            let uses = array(
+                captures,
+                |capture| {
+                    let (capture, is_reference) = match capture.node {
+                        Expression::Reference(ref capture) => (&capture.node, true),
+                        ref capture => (capture, false)
+                    };
+
+                    node(
+                        "ClosureUse",
+                        vec![
+                            ("var", self::expression(capture)),
+                            ("byRef", Value::Boolean(is_reference))
+                        ]
+                    )
+                }
+            );
+
+            node(
+                "Expr_Closure",
+                vec![
+                    ("attrGroups", Value::Array(vec![])),
+                    ("static", Value::Boolean(is_static)),
+                    ("byRef", Value::Boolean(returns_reference)),
+                    ("params", self::parameters(parameters)),
+                    ("uses", uses),
+                    ("returnType", optional_type(return_type)),
+                    ("stmts", statements(body))
+                ]
+            )
+        },
+
+        Expression::DynamicClassConstant { class: ref constant_class, ref name } => {
+            node(
+                "Expr_ClassConstFetch",
+                vec![
+                    ("class", class(constant_class)),
+                    ("name", self::expression(name))
+                ]
+            )
+        },
+
+        Expression::Echo(ref echoed) => {
+            node("Stmt_Echo", vec![("exprs", expressions(echoed))])
+        },
+
+        Expression::Empty(ref operand) => {
+            node("Expr_Empty", vec![("expr", self::expression(operand))])
+        },
+
+        Expression::Eval(ref operand) => {
+            node("Expr_Eval", vec![("expr", self::expression(operand))])
+        },
+
+        Expression::Exit(ref operand) => {
+            node("Expr_Exit", vec![("expr", optional(operand, |operand| self::expression(operand)))])
+        },
+
+        Expression::Heredoc { label, ref parts, .. } => {
+            let attributes = document_attributes(STRING_KIND_HEREDOC, label);
+
+            match *parts.as_slice() {
+                [] => {
+                    attributed_node("Scalar_String", vec![("value", string(b""))], attributes)
+                },
+
+                [StringPart::Bytes(ref bytes)] => {
+                    attributed_node("Scalar_String", vec![("value", string(bytes))], attributes)
+                },
+
+                _ => {
+                    attributed_node("Scalar_InterpolatedString", vec![("parts", string_parts(parts))], attributes)
+                }
+            }
+        },
+
+        Expression::InstanceOf { ref subject, class: ref instance_class } => {
+            node(
+                "Expr_Instanceof",
+                vec![
+                    ("expr", self::expression(subject)),
+                    ("class", class(instance_class))
+                ]
+            )
+        },
+
+        Expression::InterpolatedString(ref parts) => {
+            node("Scalar_InterpolatedString", vec![("parts", string_parts(parts))])
+        },
+
+        Expression::Include { ref kind, ref path } => {
+            let include_type = match *kind {
+                IncludeKind::Include     => 1,
+                IncludeKind::IncludeOnce => 2,
+                IncludeKind::Require     => 3,
+                IncludeKind::RequireOnce => 4
+            };
+
+            node(
+                "Expr_Include",
+                vec![
+                    ("expr", self::expression(path)),
+                    ("type", Value::Integer(include_type))
+                ]
+            )
+        },
+
+        Expression::Isset(ref variables) => {
+            node("Expr_Isset", vec![("vars", expressions(variables))])
+        },
+
+        Expression::List(ref items) => {
+            attributed_node("Expr_List", vec![("items", list_items(items))], vec![("kind", Value::Integer(LIST_KIND_LIST))])
+        },
+
+        Expression::Literal(ref value) => {
+            literal(value)
+        },
+
+        Expression::MagicConstant(ref constant) => {
+            let node_type = match *constant {
+                MagicConstant::Class     => "Scalar_MagicConst_Class",
+                MagicConstant::Directory => "Scalar_MagicConst_Dir",
+                MagicConstant::File      => "Scalar_MagicConst_File",
+                MagicConstant::Function  => "Scalar_MagicConst_Function",
+                MagicConstant::Line      => "Scalar_MagicConst_Line",
+                MagicConstant::Method    => "Scalar_MagicConst_Method",
+                MagicConstant::Namespace => "Scalar_MagicConst_Namespace",
+                MagicConstant::Trait     => "Scalar_MagicConst_Trait"
+            };
+
+            node(node_type, vec![])
+        },
+
+        Expression::Match { ref subject, ref arms } => {
+            node(
+                "Expr_Match",
+                vec![
+                    ("cond", self::expression(subject)),
+                    ("arms", array(arms, |arm| match_arm(arm)))
+                ]
+            )
+        },
+
+        Expression::MethodCall { ref object, ref method, ref arguments } => {
+            method_call("Expr_MethodCall", object, method, self::arguments(arguments))
+        },
+
+        Expression::Name(ref constant_name) => {
+            node("Expr_ConstFetch", vec![("name", name(constant_name))])
+        },
+
+        Expression::Negation(ref operand) => {
+            node("Expr_UnaryMinus", vec![("expr", self::expression(operand))])
+        },
+
+        Expression::New { class: ref new_class, ref arguments } => {
+            node(
+                "Expr_New",
+                vec![
+                    ("class", class(new_class)),
+                    ("args", self::arguments(arguments))
+                ]
+            )
+        },
+
+        Expression::NullsafeMethodCall { ref object, ref method, ref arguments } => {
+            method_call("Expr_NullsafeMethodCall", object, method, self::arguments(arguments))
+        },
+
+        Expression::NullsafePropertyFetch { ref object, ref property } => {
+            node(
+                "Expr_NullsafePropertyFetch",
+                vec![
+                    ("var", self::expression(object)),
+                    ("name", member(property))
+                ]
+            )
+        },
+
+        Expression::PostDecrement(ref operand) => {
+            node("Expr_PostDec", vec![("var", self::expression(operand))])
+        },
+
+        Expression::PostIncrement(ref operand) => {
+            node("Expr_PostInc", vec![("var", self::expression(operand))])
+        },
+
+        Expression::Power { ref base, ref exponent } => {
+            node(
+                "Expr_BinaryOp_Pow",
+                vec![
+                    ("left", self::expression(base)),
+                    ("right", self::expression(exponent))
+                ]
+            )
+        },
+
+        Expression::PreDecrement(ref operand) => {
+            node("Expr_PreDec", vec![("var", self::expression(operand))])
+        },
+
+        Expression::PreIncrement(ref operand) => {
+            node("Expr_PreInc", vec![("var", self::expression(operand))])
+        },
+
+        Expression::Print(ref operand) => {
+            node("Expr_Print", vec![("expr", self::expression(operand))])
+        },
+
+        Expression::PropertyFetch { ref object, ref property } => {
+            node(
+                "Expr_PropertyFetch",
+                vec![
+                    ("var", self::expression(object)),
+                    ("name", member(property))
+                ]
+            )
+        },
+
+        // A reference is exported by its parent, e.g. `Expr_AssignRef`.
+        Expression::Reference(ref operand) => {
+            self::expression(operand)
+        },
+
+        Expression::RelativeScope(ref scope) => {
+            relative_scope(scope)
+        },
+
+        Expression::ShellExec(ref parts) => {
+            node("Expr_ShellExec", vec![("parts", string_parts(parts))])
+        },
+
+        Expression::ShortList(ref items) => {
+            attributed_node("Expr_List", vec![("items", list_items(items))], vec![("kind", Value::Integer(LIST_KIND_ARRAY))])
+        },
+
+        Expression::StaticFetch { class: ref static_class, member: ref static_member } => {
+            match static_member.node {
+                Expression::Variable(Variable::Name(name)) => {
+                    node(
+                        "Expr_StaticPropertyFetch",
+                        vec![
+                            ("class", class(static_class)),
+                            ("name", node("VarLikeIdentifier", vec![("name", string(name))]))
+                        ]
+                    )
+                },
+
+                Expression::Variable(Variable::Dynamic(ref name)) => {
+                    node(
+                        "Expr_StaticPropertyFetch",
+                        vec![
+                            ("class", class(static_class)),
+                            ("name", self::expression(name))
+                        ]
+                    )
+                },
+
+                ref static_member => {
+                    node(
+                        "Expr_ClassConstFetch",
+                        vec![
+                            ("class", class(static_class)),
+                            ("name", member(static_member))
+                        ]
+                    )
+                }
+            }
+        },
+
+        Expression::StaticMethodCall { class: ref static_class, ref method, ref arguments } => {
+            static_call(static_class, method, self::arguments(arguments))
+        },
+
+        Expression::Throw(ref operand) => {
+            node("Expr_Throw", vec![("expr", self::expression(operand))])
+        },
+
+        Expression::Unset(ref variables) => {
+            node("Stmt_Unset", vec![("vars", expressions(variables))])
+        },
+
+        Expression::Variable(ref value) => {
+            variable(value)
+        },
+
+        Expression::Yield { ref key, ref value } => {
+            node(
+                "Expr_Yield",
+                vec![
+                    ("key", optional(key, |key| self::expression(key))),
+                    ("value", optional(value, |value| self::expression(value)))
+                ]
+            )
+        },
+
+        Expression::YieldFrom(ref operand) => {
+            node("Expr_YieldFrom", vec![("expr", self::expression(operand))])
+        }
+    }
+}
+
+fn statements(statements: &[Spanned<Statement>]) -> Value {
+    array(statements, |item| statement(item))
+}
+
+/// Export the body of a control structure as a list of statements.
+fn body(body: &Statement) -> Value {
+    match *body {
+        Statement::Block(ref block) => statements(block),
+        Statement::Empty => Value::Array(vec![]),
+        ref body => Value::Array(vec![statement(body)])
+    }
+}
+
+fn constants(constants: &[(&[u8], ConstantExpression)]) -> Value {
+    array(
+        constants,
+        |&(name, ref value)| {
+            node(
+                "Const",
+                vec![
+                    ("name", identifier(name)),
+                    ("value", constant_expression(value))
+                ]
+            )
+        }
+    )
+}
+
+fn catch(catch: &Catch) -> Value {
+    node(
+        "Stmt_Catch",
+        vec![
+            ("types", array(&catch.types, |item| name(item))),
+            ("var", optional(&catch.variable, |item| variable(item))),
+            ("stmts", statements(&catch.body))
+        ]
+    )
+}
+
+fn switch_case(case: &SwitchCase) -> Value {
+    node(
+        "Stmt_Case",
+        vec![
+            ("cond", optional(&case.condition, |condition| expression(condition))),
+            ("stmts", statements(&case.body))
+        ]
+    )
+}
+
+fn declare_item(directive: &DeclareDirective) -> Value {
+    let (key, value) = match *directive {
+        DeclareDirective::Encoding(ref encoding) => {
+            ("encoding", literal(&Literal::String(encoding.clone())))
+        },
+
+        DeclareDirective::StrictTypes(is_strict) => {
+            ("strict_types", literal(&Literal::Integer(flag(is_strict, 1))))
+        },
+
+        DeclareDirective::Ticks(ticks) => {
+            ("ticks", literal(&Literal::Integer(ticks)))
+        }
+    };
+
+    node(
+        "DeclareItem",
+        vec![
+            ("key", identifier(key.as_bytes())),
+            ("value", value)
+        ]
+    )
+}
+
+fn use_type(kind: UseKind) -> i64 {
+    match kind {
+        UseKind::Class    => 1,
+        UseKind::Function => 2,
+        UseKind::Constant => 3
+    }
+}
+
+/// Export use items. Uses of different kinds come from a group use; the
+/// statement has then the unknown type, and each item has its own type.
+fn uses(uses: &[Use]) -> Value {
+    let kind         = uses[0].kind;
+    let is_same_kind = uses.iter().all(|item| item.kind == kind);
+    let items        = array(
+        uses,
+        |item| {
+            node(
+                "UseItem",
+                vec![
+                    ("type", Value::Integer(if is_same_kind { 0 } else { use_type(item.kind) })),
+                    ("name", name(&item.name)),
+                    ("alias", optional(&item.alias, |alias| identifier(alias)))
+                ]
+            )
+        }
+    );
+
+    node(
+        "Stmt_Use",
+        vec![
+            ("type", Value::Integer(if is_same_kind { use_type(kind) } else { 0 })),
+            ("uses", items)
+        ]
+    )
+}
+
+fn trait_adaptation(adaptation: &TraitAdaptation) -> Value {
+    match *adaptation {
+        TraitAdaptation::Alias { ref trait_name, method, visibility: alias_visibility, alias } => {
+            node(
+                "Stmt_TraitUseAdaptation_Alias",
+                vec![
+                    ("trait", optional(trait_name, |item| name(item))),
+                    ("method", identifier(method)),
+                    ("newModifier", optional(&alias_visibility, |item| Value::Integer(visibility(*item)))),
+                    ("newName", optional(&alias, |item| identifier(item)))
+                ]
+            )
+        },
+
+        TraitAdaptation::Insteadof { ref trait_name, method, ref excluded } => {
+            node(
+                "Stmt_TraitUseAdaptation_Precedence",
+                vec![
+                    ("trait", name(trait_name)),
+                    ("method", identifier(method)),
+                    ("insteadof", array(excluded, |item| name(item)))
+                ]
+            )
+        }
+    }
+}
+
+fn property_hook(hook: &PropertyHook) -> Value {
+    let name = match hook.kind {
+        PropertyHookKind::Get => "get",
+        PropertyHookKind::Set => "set"
+    };
+    let body = match hook.body {
+        PropertyHookBody::Block(ref block) => statements(block),
+        PropertyHookBody::Expression(ref body) => expression(body)
+    };
+
+    node(
+        "PropertyHook",
+        vec![
+            ("attrGroups", attribute_groups(&hook.attributes)),
+            ("flags", Value::Integer(flag(hook.is_final, MODIFIER_FINAL))),
+            ("byRef", Value::Boolean(hook.returns_reference)),
+            ("name", identifier(name.as_bytes())),
+            ("params", parameters(&hook.parameters)),
+            ("body", body)
+        ]
+    )
+}
+
+fn method(method: &Method) -> Value {
+    let flags =
+        visibility(method.modifiers.visibility()) |
+        flag(method.modifiers.is_static(), MODIFIER_STATIC) |
+        flag(method.modifiers.is_abstract(), MODIFIER_ABSTRACT) |
+        flag(method.modifiers.is_final(), MODIFIER_FINAL);
+
+    documented_node(
+        "Stmt_ClassMethod",
+        vec![
+            ("attrGroups", attribute_groups(&method.attributes)),
+            ("flags", Value::Integer(flags)),
+            ("byRef", Value::Boolean(method.returns_reference)),
+            ("name", identifier(method.name)),
+            ("params", parameters(&method.parameters)),
+            ("returnType", optional_type(&method.return_type)),
+            ("stmts", optional(&method.body, |body| statements(body)))
+        ],
+        &method.doc_comment
+    )
+}
+
+fn class_member(member: &ClassMember) -> Value {
+    match *member {
+        ClassMember::Case { ref attributes, name, ref value } => {
+            node(
+                "Stmt_EnumCase",
+                vec![
+                    ("attrGroups", attribute_groups(attributes)),
+                    ("name", identifier(name)),
+                    ("expr", optional(value, |value| constant_expression(value)))
+                ]
+            )
+        },
+
+        ClassMember::Constant { ref doc_comment, ref attributes, visibility: constant_visibility, is_final, ref ty, constants: ref class_constants } => {
+            let flags =
+                visibility(constant_visibility) |
+                flag(is_final, MODIFIER_FINAL);
+
+            documented_node(
+                "Stmt_ClassConst",
+                vec![
+                    ("attrGroups", attribute_groups(attributes)),
+                    ("flags", Value::Integer(flags)),
+                    ("type", optional_type(ty)),
+                    ("consts", constants(class_constants))
+                ],
+                doc_comment
+            )
+        },
+
+        ClassMember::Method(ref class_method) => {
+            method(class_method)
+        },
+
+        ClassMember::Property { ref doc_comment, ref attributes, visibility: property_visibility, set_visibility: property_set_visibility, is_static, is_readonly, ref ty, ref properties, ref hooks } => {
+            let flags =
+                visibility(property_visibility) |
+                set_visibility(property_set_visibility) |
+                flag(is_static, MODIFIER_STATIC) |
+                flag(is_readonly, MODIFIER_READONLY);
+            let properties = array(
+                properties,
+                |(name, value)| {
+                    let name = match *name {
+                        Variable::Name(name) => string(name),
+                        ref name => variable(name)
+                    };
+
+                    node(
+                        "PropertyItem",
+                        vec![
+                            ("name", node("VarLikeIdentifier", vec![("name", name)])),
+                            ("default", optional(value, |value| constant_expression(value)))
+                        ]
+                    )
+                }
+            );
+
+            documented_node(
+                "Stmt_Property",
+                vec![
+                    ("attrGroups", attribute_groups(attributes)),
+                    ("flags", Value::Integer(flags)),
+                    ("type", optional_type(ty)),
+                    ("props", properties),
+                    ("hooks", array(hooks, |hook| property_hook(hook)))
+                ],
+                doc_comment
+            )
+        },
+
+        ClassMember::TraitUse { ref traits, ref adaptations } => {
+            node(
+                "Stmt_TraitUse",
+                vec![
+                    ("traits", array(traits, |item| name(item))),
+                    ("adaptations", array(adaptations, |item| trait_adaptation(item)))
+                ]
+            )
+        }
+    }
+}
+
+fn class_members(members: &[Spanned<ClassMember>]) -> Value {
+    array(members, |item| class_member(item))
+}
+
+fn statement(statement: &Statement) -> Value {
+    match *statement {
+        Statement::Block(ref block) => {
+            node("Stmt_Block", vec![("stmts", statements(block))])
+        },
+
+        Statement::Break(level) => {
+            node("Stmt_Break", vec![("num", optional(&level, |level| literal(&Literal::Integer(*level as i64))))])
+        },
+
+        Statement::Class { ref doc_comment, ref attributes, is_abstract, is_final, is_readonly, name, ref extends, ref implements, ref members } => {
+            let flags =
+                flag(is_abstract, MODIFIER_ABSTRACT) |
+                flag(is_final, MODIFIER_FINAL) |
+                flag(is_readonly, MODIFIER_READONLY);
+
+            documented_node(
+                "Stmt_Class",
+                vec![
+                    ("attrGroups", attribute_groups(attributes)),
+                    ("flags", Value::Integer(flags)),
+                    ("name", identifier(name)),
+                    ("extends", optional(extends, |item| self::name(item))),
+                    ("implements", array(implements, |item| self::name(item))),
+                    ("stmts", class_members(members))
+                ],
+                doc_comment
+            )
+        },
+
+        Statement::Constant(ref statement_constants) => {
+            node("Stmt_Const", vec![("consts", constants(statement_constants))])
+        },
+
+        Statement::Continue(level) => {
+            node("Stmt_Continue", vec![("num", optional(&level, |level| literal(&Literal::Integer(*level as i64))))])
+        },
+
+        Statement::Declare { ref directives, body: ref declare_body } => {
+            node(
+                "Stmt_Declare",
+                vec![
+                    ("declares", array(directives, declare_item)),
+                    ("stmts", optional(declare_body, |declare_body| body(declare_body)))
+                ]
+            )
+        },
+
+        Statement::DoWhile { body: ref do_body, ref condition } => {
+            node(
+                "Stmt_Do",
+                vec![
+                    ("stmts", body(do_body)),
+                    ("cond", expression(condition))
+                ]
+            )
+        },
+
+        Statement::Echo(ref echoed) => {
+            node("Stmt_Echo", vec![("exprs", expressions(echoed))])
+        },
+
+        Statement::Empty => {
+            node("Stmt_Nop", vec![])
+        },
+
+        Statement::Enum { ref doc_comment, ref attributes, name, ref backing_type, ref implements, ref members } => {
+            documented_node(
+                "Stmt_Enum",
+                vec![
+                    ("attrGroups", attribute_groups(attributes)),
+                    ("name", identifier(name)),
+                    ("scalarType", optional_type(backing_type)),
+                    ("implements", array(implements, |item| self::name(item))),
+                    ("stmts", class_members(members))
+                ],
+                doc_comment
+            )
+        },
+
+        // nikic/php-parser represents the recovered errors with
+        // `Expr_Error`, which has no sub-node.
+        Statement::Error(_) => {
+            node("Stmt_Expression", vec![("expr", node("Expr_Error", vec![]))])
+        },
+
+        // `echo` and `unset` are statements in nikic/php-parser.
+        Statement::Expression(Spanned { node: Expression::Echo(ref echoed), .. }) => {
+            node("Stmt_Echo", vec![("exprs", expressions(echoed))])
+        },
+
+        Statement::Expression(Spanned { node: Expression::Unset(ref variables), .. }) => {
+            node("Stmt_Unset", vec![("vars", expressions(variables))])
+        },
+
+        Statement::Expression(ref statement_expression) => {
+            node("Stmt_Expression", vec![("expr", expression(statement_expression))])
+        },
+
+        Statement::For { ref init, ref condition, ref step, body: ref for_body } => {
+            node(
+                "Stmt_For",
+                vec![
+                    ("init", expressions(init)),
+                    ("cond", expressions(condition)),
+                    ("loop", expressions(step)),
+                    ("stmts", body(for_body))
+                ]
+            )
+        },
+
+        Statement::Foreach { ref subject, ref key, ref value, is_reference, body: ref foreach_body } => {
+            node(
+                "Stmt_Foreach",
+                vec![
+                    ("expr", expression(subject)),
+                    ("keyVar", optional(key, |key| expression(key))),
+                    ("byRef", Value::Boolean(is_reference)),
+                    ("valueVar", expression(value)),
+                    ("stmts", body(foreach_body))
+                ]
+            )
+        },
+
+        Statement::Function { ref doc_comment, ref attributes, returns_reference, name, ref parameters, ref return_type, body: ref function_body } => {
+            documented_node(
+                "Stmt_Function",
+                vec![
+                    ("attrGroups", attribute_groups(attributes)),
+                    ("byRef", Value::Boolean(returns_reference)),
+                    ("name", identifier(name)),
+                    ("params", self::parameters(parameters)),
+                    ("returnType", optional_type(return_type)),
+                    ("stmts", statements(function_body))
+                ],
+                doc_comment
+            )
+        },
+
+        Statement::Global(ref variables) => {
+            node("Stmt_Global", vec![("vars", array(variables, |item| variable(item)))])
+        },
+
+        Statement::Goto(label) => {
+            node("Stmt_Goto", vec![("name", identifier(label))])
+        },
+
+        Statement::HaltCompiler { data, .. } => {
+            node("Stmt_HaltCompiler", vec![("remaining", string(data))])
+        },
+
+        Statement::If { ref condition, ref then, ref elseifs, ref otherwise } => {
+            let elseifs = array(
+                elseifs,
+                |(elseif_condition, elseif_body)| {
+                    node(
+                        "Stmt_ElseIf",
+                        vec![
+                            ("cond", expression(elseif_condition)),
+                            ("stmts", body(elseif_body))
+                        ]
+                    )
+                }
+            );
+
+            node(
+                "Stmt_If",
+                vec![
+                    ("cond", expression(condition)),
+                    ("stmts", body(then)),
+                    ("elseifs", elseifs),
+                    ("else", optional(otherwise, |otherwise| node("Stmt_Else", vec![("stmts", body(otherwise))])))
+                ]
+            )
+        },
+
+        Statement::InlineHtml(html) => {
+            node("Stmt_InlineHTML", vec![("value", string(html))])
+        },
+
+        Statement::Interface { ref doc_comment, ref attributes, name, ref extends, ref members } => {
+            documented_node(
+                "Stmt_Interface",
+                vec![
+                    ("attrGroups", attribute_groups(attributes)),
+                    ("name", identifier(name)),
+                    ("extends", array(extends, |item| self::name(item))),
+                    ("stmts", class_members(members))
+                ],
+                doc_comment
+            )
+        },
+
+        Statement::Label(label) => {
+            node("Stmt_Label", vec![("name", identifier(label))])
+        },
+
+        Statement::Namespace { ref name, body: ref namespace_body } => {
+            namespace_node(
+                optional(name, |item| self::name(item)),
+                match *namespace_body {
+                    Some(ref namespace_body) => namespace_body.iter().map(|statement| self::statement(statement)).collect(),
+                    None => Vec::new()
+                }
+            )
+        },
+
+        Statement::Return(ref value) => {
+            node("Stmt_Return", vec![("expr", optional(value, |value| expression(value)))])
+        },
+
+        Statement::StaticVariables(ref variables) => {
+            let variables = array(
+                variables,
+                |(name, value)| {
+                    node(
+                        "StaticVar",
+                        vec![
+                            ("var", variable(name)),
+                            ("default", optional(value, |value| expression(value)))
+                        ]
+                    )
+                }
+            );
+
+            node("Stmt_Static", vec![("vars", variables)])
+        },
+
+        Statement::Switch { ref subject, ref cases } => {
+            node(
+                "Stmt_Switch",
+                vec![
+                    ("cond", expression(subject)),
+                    ("cases", array(cases, |case| switch_case(case)))
+                ]
+            )
+        },
+
+        Statement::Trait { ref doc_comment, ref attributes, name, ref members } => {
+            documented_node(
+                "Stmt_Trait",
+                vec![
+                    ("attrGroups", attribute_groups(attributes)),
+                    ("name", identifier(name)),
+                    ("stmts", class_members(members))
+                ],
+                doc_comment
+            )
+        },
+
+        Statement::Try { body: ref try_body, ref catches, ref finally } => {
+            node(
+                "Stmt_TryCatch",
+                vec![
+                    ("stmts", statements(try_body)),
+                    ("catches", array(catches, |item| catch(item))),
+                    ("finally", optional(finally, |finally| node("Stmt_Finally", vec![("stmts", statements(finally))])))
+                ]
+            )
+        },
+
+        Statement::Use(ref statement_uses) => {
+            uses(statement_uses)
+        },
+
+        Statement::While { ref condition, body: ref while_body } => {
+            node(
+                "Stmt_While",
+                vec![
+                    ("cond", expression(condition)),
+                    ("stmts", body(while_body))
+                ]
+            )
+        }
+    }
+}
+
+/// Serialize a JSON value.
+fn write(output: &mut String, value: &Value) {
+    match *value {
+        Value::Null => {
+            output.push_str("null");
+        },
+
+        Value::Boolean(boolean) => {
+            output.push_str(if boolean { "true" } else { "false" });
+        },
+
+        Value::Integer(integer) => {
+            output.push_str(&integer.to_string());
+        },
+
+        Value::Real(real) => {
+            if real.is_infinite() {
+                // JSON has no infinity, but decoding `1e1000` gives `INF`.
+                output.push_str(if real > 0f64 { "1e1000" } else { "-1e1000" });
+            } else {
+                output.push_str(&format!("{:?}", real));
+            }
+        },
+
+        Value::String(ref string) => {
+            write_string(output, string);
+        },
+
+        Value::Array(ref items) => {
+            output.push('[');
+
+            for (index, item) in items.iter().enumerate() {
+                if index > 0 {
+                    output.push(',');
+                }
+
+                write(output, item);
+            }
+
+            output.push(']');
+        },
+
+        Value::Object(ref fields) => {
+            output.push('{');
+
+            for (index, &(key, ref value)) in fields.iter().enumerate() {
+                if index > 0 {
+                    output.push(',');
+                }
+
+                write_string(output, key);
+                output.push(':');
+                write(output, value);
+            }
+
+            output.push('}');
+        }
+    }
+}
+
+fn write_string(output: &mut String, string: &str) {
+    output.push('"');
+
+    for character in string.chars() {
+        match character {
+            '"'  => output.push_str("\\\""),
+            '\\' => output.push_str("\\\\"),
+            '\n' => output.push_str("\\n"),
+            '\r' => output.push_str("\\r"),
+            '\t' => output.push_str("\\t"),
+
+            character if (character as u32) < 0x20 => {
+                output.push_str(&format!("\\u{:04x}", character as u32));
+            },
+
+            character => {
+                output.push(character);
+            }
+        }
+    }
+
+    output.push('"');
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::export;
+    use super::super::ast::Statement;
+    use super::super::internal::Result;
+    use super::super::rules::script::script;
+    use super::super::span::Spanned;
+
+    fn statements(input: &[u8]) -> Vec<Spanned<Statement>> {
+        match script(input) {
+            Result::Done(_, statements) => statements,
+            _ => panic!("The script is invalid.")
+        }
+    }
+
+    #[test]
+    fn case_export_method_call_and_assignment() {
+        let output = export(&statements(b"<?php $a = &$b->c(1);"));
+
+        assert_eq!(
+            output,
+            "[{\"nodeType\":\"Stmt_Expression\",\"expr\":{\"nodeType\":\"Expr_AssignRef\",\
+             \"var\":{\"nodeType\":\"Expr_Variable\",\"name\":\"a\",\"attributes\":[]},\
+             \"expr\":{\"nodeType\":\"Expr_MethodCall\",\
+             \"var\":{\"nodeType\":\"Expr_Variable\",\"name\":\"b\",\"attributes\":[]},\
+             \"name\":{\"nodeType\":\"Identifier\",\"name\":\"c\",\"attributes\":[]},\
+             \"args\":[{\"nodeType\":\"Arg\",\"name\":null,\
+             \"value\":{\"nodeType\":\"Scalar_Int\",\"value\":1,\"attributes\":[]},\
+             \"byRef\":false,\"unpack\":false,\"attributes\":[]}],\"attributes\":[]},\
+             \"attributes\":[]},\"attributes\":[]}]"
+        );
+    }
+
+    #[test]
+    fn case_export_call_of_a_fetched_property() {
+        let output = export(&statements(b"<?php ($o->m)();"));
+
+        assert_eq!(
+            output,
+            "[{\"nodeType\":\"Stmt_Expression\",\"expr\":{\"nodeType\":\"Expr_FuncCall\",\
+             \"name\":{\"nodeType\":\"Expr_PropertyFetch\",\
+             \"var\":{\"nodeType\":\"Expr_Variable\",\"name\":\"o\",\"attributes\":[]},\
+             \"name\":{\"nodeType\":\"Identifier\",\"name\":\"m\",\"attributes\":[]},\"attributes\":[]},\
+             \"args\":[],\"attributes\":[]},\"attributes\":[]}]"
+        );
+    }
+
+    #[test]
+    fn case_export_namespace_without_curly_brackets() {
+        let output = export(&statements(b"<?php namespace A\\B; echo 1; namespace C; unset($x);"));
+
+        assert_eq!(
+            output,
+            "[{\"nodeType\":\"Stmt_Namespace\",\
+             \"name\":{\"nodeType\":\"Name\",\"name\":\"A\\\\B\",\"attributes\":[]},\
+             \"stmts\":[{\"nodeType\":\"Stmt_Echo\",\
+             \"exprs\":[{\"nodeType\":\"Scalar_Int\",\"value\":1,\"attributes\":[]}],\"attributes\":[]}],\
+             \"attributes\":[]},\
+             {\"nodeType\":\"Stmt_Namespace\",\
+             \"name\":{\"nodeType\":\"Name\",\"name\":\"C\",\"attributes\":[]},\
+             \"stmts\":[{\"nodeType\":\"Stmt_Unset\",\
+             \"vars\":[{\"nodeType\":\"Expr_Variable\",\"name\":\"x\",\"attributes\":[]}],\"attributes\":[]}],\
+             \"attributes\":[]}]"
+        );
+    }
+
+    #[test]
+    fn case_export_class_with_modifiers_and_doc_comment() {
+        let output = export(&statements(b"<?php /** D. */ final class C { private static ?int $p = null; abstract protected function m(); }"));
+
+        assert!(output.starts_with("[{\"nodeType\":\"Stmt_Class\",\"attrGroups\":[],\"flags\":32,"));
+        assert!(output.contains("\"nodeType\":\"Stmt_Property\",\"attrGroups\":[],\"flags\":12,\"type\":{\"nodeType\":\"NullableType\""));
+        assert!(output.contains("\"nodeType\":\"Stmt_ClassMethod\",\"attrGroups\":[],\"flags\":18,\"byRef\":false,"));
+        assert!(output.contains("\"stmts\":null,"));
+        assert!(output.ends_with("\"attributes\":{\"comments\":[{\"nodeType\":\"Comment_Doc\",\"text\":\"/** D. */\"}]}}]"));
+    }
+
+    #[test]
+    fn case_export_escaped_strings() {
+        let output = export(&statements(b"<?php \"a\\n\\\"$b\";"));
+
+        assert_eq!(
+            output,
+            "[{\"nodeType\":\"Stmt_Expression\",\"expr\":{\"nodeType\":\"Scalar_InterpolatedString\",\
+             \"parts\":[{\"nodeType\":\"InterpolatedStringPart\",\"value\":\"a\\n\\\"\",\"attributes\":[]},\
+             {\"nodeType\":\"Expr_Variable\",\"name\":\"b\",\"attributes\":[]}],\"attributes\":[]},\
+             \"attributes\":[]}]"
+        );
+    }
+
+    #[test]
+    fn case_export_heredoc_and_nowdoc() {
+        let output = export(&statements(b"<?php echo <<<EOT\n  a\n  EOT, <<<EOT\n$b\nEOT, <<<'EOT'\nc\nEOT;"));
+
+        assert_eq!(
+            output,
+            "[{\"nodeType\":\"Stmt_Echo\",\"exprs\":[\
+             {\"nodeType\":\"Scalar_String\",\"value\":\"a\",\"attributes\":{\"kind\":3,\"docLabel\":\"EOT\"}},\
+             {\"nodeType\":\"Scalar_InterpolatedString\",\
+             \"parts\":[{\"nodeType\":\"Expr_Variable\",\"name\":\"b\",\"attributes\":[]}],\
+             \"attributes\":{\"kind\":3,\"docLabel\":\"EOT\"}},\
+             {\"nodeType\":\"Scalar_String\",\"value\":\"c\",\"attributes\":{\"kind\":4,\"docLabel\":\"EOT\"}}],\
+             \"attributes\":[]}]"
+        );
+    }
+
+    #[test]
+    fn case_export_destructuring() {
+        let output = export(&statements(b"<?php [$a] = list($b) = $c;"));
+
+        assert_eq!(
+            output,
+            "[{\"nodeType\":\"Stmt_Expression\",\"expr\":{\"nodeType\":\"Expr_Assign\",\
+             \"var\":{\"nodeType\":\"Expr_List\",\"items\":[{\"nodeType\":\"ArrayItem\",\"key\":null,\
+             \"value\":{\"nodeType\":\"Expr_Variable\",\"name\":\"a\",\"attributes\":[]},\
+             \"byRef\":false,\"unpack\":false,\"attributes\":[]}],\"attributes\":{\"kind\":2}},\
+             \"expr\":{\"nodeType\":\"Expr_Assign\",\
+             \"var\":{\"nodeType\":\"Expr_List\",\"items\":[{\"nodeType\":\"ArrayItem\",\"key\":null,\
+             \"value\":{\"nodeType\":\"Expr_Variable\",\"name\":\"b\",\"attributes\":[]},\
+             \"byRef\":false,\"unpack\":false,\"attributes\":[]}],\"attributes\":{\"kind\":1}},\
+             \"expr\":{\"nodeType\":\"Expr_Variable\",\"name\":\"c\",\"attributes\":[]},\
+             \"attributes\":[]},\"attributes\":[]},\"attributes\":[]}]"
+        );
+    }
+
+    #[test]
+    fn case_export_closure_body() {
+        let output = export(&statements(b"<?php function () use (&$a) { return $a; };"));
+
+        assert!(output.contains("\"uses\":[{\"nodeType\":\"ClosureUse\",\"var\":{\"nodeType\":\"Expr_Variable\",\"name\":\"a\",\"attributes\":[]},\"byRef\":true,\"attributes\":[]}]"));
+        assert!(output.contains("\"stmts\":[{\"nodeType\":\"Stmt_Return\",\"expr\":{\"nodeType\":\"Expr_Variable\",\"name\":\"a\""));
+    }
+
+    #[test]
+    fn case_export_static_members() {
+        let output = export(&statements(b"<?php A::$b; A::C; static::d(); self::class;"));
+
+        assert!(output.contains("\"nodeType\":\"Expr_StaticPropertyFetch\",\"class\":{\"nodeType\":\"Name\",\"name\":\"A\",\"attributes\":[]},\"name\":{\"nodeType\":\"VarLikeIdentifier\",\"name\":\"b\""));
+        assert!(output.contains("\"nodeType\":\"Expr_ClassConstFetch\",\"class\":{\"nodeType\":\"Name\",\"name\":\"A\",\"attributes\":[]},\"name\":{\"nodeType\":\"Identifier\",\"name\":\"C\""));
+        assert!(output.contains("\"nodeType\":\"Expr_StaticCall\",\"class\":{\"nodeType\":\"Name\",\"name\":\"static\""));
+        assert!(output.contains("\"class\":{\"nodeType\":\"Name\",\"name\":\"self\",\"attributes\":[]},\"name\":{\"nodeType\":\"Identifier\",\"name\":\"class\""));
+    }
+}
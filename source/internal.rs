@@ -36,6 +36,8 @@ pub use nom::ErrorKind;
 pub use nom::IResult as Result;
 pub use nom::Needed;
 
+use std::result::Result as StdResult;
+
 /// Fold an item into a vector.
 /// This is useful when combined with the `fold_many0!` macro for instance.
 ///
@@ -67,3 +69,34 @@ pub fn fold_into_vector<T>(mut accumulator: Vec<T>, item: T) -> Vec<T> {
 
     accumulator
 }
+
+/// Wrap an item into a vector.
+/// This is useful when combined with the `map_res!` macro, to parse a single
+/// item where a list is expected.
+///
+/// # Examples
+///
+/// ```
+/// # #[macro_use]
+/// # extern crate nom;
+/// # #[macro_use]
+/// # extern crate tagua_parser;
+/// use tagua_parser::Result;
+/// use tagua_parser::internal::into_vector_mapper;
+///
+/// # fn main() {
+/// named!(
+///     test< &[u8], Vec<&[u8]> >,
+///     map_res!(
+///         tag!("abc"),
+///         into_vector_mapper
+///     )
+/// );
+///
+/// assert_eq!(test(&b"abc"[..]), Result::Done(&b""[..], vec![&b"abc"[..]]));
+/// # }
+/// ```
+#[inline(always)]
+pub fn into_vector_mapper<T>(item: T) -> StdResult<Vec<T>, ()> {
+    Ok(vec![item])
+}
@@ -44,23 +44,57 @@
 //! and syntax analyzers form a monolithic algorithm. The organization is the
 //! following:
 //!
+//!   * The `parser` module is the entry point, parsing a whole script,
 //!   * The `tokens` module declares all the lexemes,
 //!   * The `rules` module declares the grammar as a set of rules,
-//!   * The `ast` module contains the structure that will constitute the AST.
+//!   * The `ast` module contains the structure that will constitute the AST,
+//!   * The `visit` module traverses the AST,
+//!   * The `printer` module prints the AST back to PHP,
+//!   * The `exporter` module exports the AST to the JSON format of
+//!     nikic/php-parser,
+//!   * The `span` module locates the nodes in the datum,
+//!   * The `trivia` module collects the comments, absent from the AST,
+//!   * The `error` module describes the errors of the parser,
+//!   * The `diagnostics` module collects the diagnostics, like deprecations,
+//!   * The `encoding` module handles the encoding of the datum.
 //!
 //! The parser is based on [nom](https://github.com/Geal/nom). nom is a parser
 //! combinator library with a focus on safe parsing, streaming patterns, and as
 //! much as possible zero copy. We try to enforce the zero copy property to
 //! hold.
+//!
+//! With the `serde` feature, the AST and the parsed program implement the
+//! `Serialize` and `Deserialize` traits of [Serde](https://serde.rs/). The
+//! AST borrows byte slices from the datum; they are serialized as sequences
+//! of bytes, and deserializing borrows them back from the serialized input,
+//! which works with formats encoding both the same way, like bincode.
 
 // Increase the macro recursion limit.
 #![recursion_limit="128"]
+// The rules are written with the `chain!` macro of nom 2, deprecated in favor
+// of `do_parse!`.
+#![allow(deprecated)]
+// Struct literals spell out `field: field`, and rules declared with `named!`
+// return AST nodes without naming the lifetime of the datum.
+#![allow(clippy::redundant_field_names)]
+#![allow(mismatched_lifetime_syntaxes)]
+// Mappers given to `map_res!` fail with `()`, and receive the tuples of
+// parsed values as they are produced by `chain!`.
+#![allow(clippy::result_unit_err)]
+#![allow(clippy::type_complexity)]
 
 #[macro_use]
 extern crate lazy_static;
 #[macro_use]
 extern crate nom;
 extern crate regex;
+#[cfg(feature = "serde")]
+extern crate serde;
+#[cfg(feature = "serde")]
+#[macro_use]
+extern crate serde_derive;
+#[cfg(all(test, feature = "serde"))]
+extern crate bincode;
 #[cfg(test)]
 #[macro_use]
 extern crate quickcheck;
@@ -69,25 +103,61 @@ pub mod internal;
 #[macro_use]
 pub mod macros;
 pub mod ast;
+pub mod diagnostics;
+pub mod encoding;
+pub mod error;
+pub mod exporter;
+pub mod parser;
+pub mod phpdoc;
+pub mod printer;
 pub mod rules;
+pub mod span;
 pub mod tokens;
+pub mod trivia;
+pub mod version;
+pub mod visit;
 
+use std::result::Result as StdResult;
+
+pub use self::error::ParseError;
 pub use self::internal::*;
+pub use self::parser::{
+    Parser,
+    Program
+};
 
 /// Complete parsing of a datum starting by the sentence symbol of the grammar.
 ///
 /// The grammar is a set of rules. By definition, it has a sentence symbol,
-/// also called the root rule. The `parse` function will lex, parse and produce
-/// the associated AST of the `input` datum.
+/// also called the root rule, here a script. The `parse` function will lex,
+/// parse and produce the associated AST of the `input` datum, with the
+/// default `Parser`.
+///
+/// # Examples
+///
+/// ```
+/// use tagua_parser as parser;
+///
+/// let script = b"<?php echo 'Hello, World!';";
+///
+/// assert!(parser::parse(&script[..]).is_ok());
+/// assert!(parser::parse(&b"<?php echo ;"[..]).is_err());
+/// ```
+pub fn parse(input: &[u8]) -> StdResult<Program, ParseError> {
+    Parser::new().parse(input)
+}
+
+/// Complete parsing of a datum given as a string, see `parse`.
 ///
 /// # Examples
 ///
 /// ```
 /// use tagua_parser as parser;
 ///
-/// let expression = b"1+2";
-/// parser::parse(&expression[..]);
+/// let program = parser::parse_str("<?php $café;").unwrap();
+///
+/// assert_eq!(program.statements.len(), 1);
 /// ```
-pub fn parse(input: &[u8]) -> ast::Expression {
-    rules::root(input)
+pub fn parse_str(input: &str) -> StdResult<Program, ParseError> {
+    Parser::new().parse_str(input)
 }
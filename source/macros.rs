@@ -31,12 +31,16 @@
 
 //! Extra macros helping to write parsers.
 
-/// Custom values for `ErrorKind::Custom`.
+/// Custom values for `ErrorKind::Custom`, from 0. The custom errors of the
+/// rules start at 100, see the `error` module.
 pub enum ErrorKindCustom {
     /// Represent errors from the `exclude` macro.
-    Exclude,
+    Exclude = 0,
     /// Represent errors from the `itag` macro.
-    ITag
+    ITag,
+    /// Represent errors from the `keyword` macro when the keyword is
+    /// followed by a name byte, e.g. `returnValue`.
+    Keyword
 }
 
 /// `exclude!(I -> Result<I, O>, I -> Result<I, P>) => I -> Result<I, 0>`
@@ -103,15 +107,15 @@ macro_rules! exclude(
     );
 
     ($input:expr, $submacro1:ident!($($arguments1:tt)*), $g:expr) => (
-        exclude!($input, $submacro1!($($arguments1)*), call!($g));
+        exclude!($input, $submacro1!($($arguments1)*), call!($g))
     );
 
     ($input:expr, $f:expr, $submacro2:ident!($($arguments2:tt)*)) => (
-        exclude!($input, call!($f), $submacro2!($($arguments2)*));
+        exclude!($input, call!($f), $submacro2!($($arguments2)*))
     );
 
     ($input:expr, $f:expr, $g:expr) => (
-        exclude!($input, call!($f), call!($g));
+        exclude!($input, call!($f), call!($g))
     );
 );
 
@@ -150,7 +154,7 @@ macro_rules! first(
     );
 
     ($input:expr, $f:expr) => (
-        first!($input, call!($f));
+        first!($input, call!($f))
     );
 );
 
@@ -188,8 +192,6 @@ macro_rules! first(
 macro_rules! itag(
     ($input:expr, $string:expr) => (
         {
-            use std::ascii::AsciiExt;
-
             let input = $input as &[u8];
 
             #[inline(always)]
@@ -220,13 +222,15 @@ macro_rules! itag(
 );
 
 /// `keyword!(&[T]: nom::AsBytes) => &[T] -> Result<&[T], &[T]>`
-/// is an alias to the `itag` macro.
+/// recognizes a keyword as a whole word.
 ///
-/// The goal of this alias is twofold:
+/// It is based on the `itag` macro, and the goal is threefold:
 ///
 ///   1. It avoids confusion and errors (a PHP keyword is always
 ///      case-insensitive),
-///   2. It ensures a better readability of parsers.
+///   2. It rejects a keyword followed by a name byte, e.g. `return` is
+///      not recognized in `returnValue`,
+///   3. It ensures a better readability of parsers.
 ///
 /// # Examples
 ///
@@ -250,17 +254,147 @@ macro_rules! itag(
 ///
 /// assert_eq!(test(&b"class"[..]), output);
 /// assert_eq!(test(&b"ClAsS"[..]), output);
+/// assert!(test(&b"classes"[..]).is_err());
 /// # }
 /// ```
 #[macro_export]
 macro_rules! keyword(
     ($input:expr, $keyword:expr) => (
         {
-            itag!($input, $keyword)
+            #[inline(always)]
+            fn as_bytes<T: ::nom::AsBytes>(datum: &T) -> &[u8] {
+                datum.as_bytes()
+            }
+
+            let input    = $input as &[u8];
+            let expected = $keyword;
+
+            match itag!(input, expected) {
+                $crate::Result::Done(rest, output) => {
+                    let ends_with_name_byte = match as_bytes(&expected).last() {
+                        Some(&byte) => $crate::macros::is_name_byte(byte),
+                        None        => false
+                    };
+
+                    match rest.first() {
+                        Some(&byte) if ends_with_name_byte && $crate::macros::is_name_byte(byte) =>
+                            $crate::Result::Error($crate::Error::Position($crate::ErrorKind::Custom($crate::macros::ErrorKindCustom::Keyword as u32), input)),
+
+                        _ =>
+                            $crate::Result::Done(rest, output)
+                    }
+                },
+
+                $crate::Result::Incomplete(needed) =>
+                    $crate::Result::Incomplete(needed),
+
+                $crate::Result::Error(error) =>
+                    $crate::Result::Error(error)
+            }
         }
     );
 );
 
+/// `expect!(&'static str, &[&'static [u8]], I -> Result<I, O>) => I -> Result<I, O>`
+/// runs a parser, and reports its failure as the one of the given rule
+/// expecting the given tokens, see `error::expect`.
+///
+/// With a single token instead of a parser, it recognizes this token like
+/// the nom `tag!` macro, and reports it as expected if it is missing.
+///
+/// # Examples
+///
+/// ```
+/// # #[macro_use]
+/// # extern crate nom;
+/// # #[macro_use]
+/// # extern crate tagua_parser;
+/// use tagua_parser::error;
+///
+/// # fn main() {
+/// named!(
+///     test,
+///     preceded!(
+///         tag!("foo"),
+///         expect!("test", &b"bar"[..])
+///     )
+/// );
+///
+/// let error = error::track(&b"foobaz"[..], "script", test).unwrap_err();
+///
+/// assert_eq!(error.location.offset, 3);
+/// assert_eq!(error.rule, "test");
+/// assert_eq!(error.expected, vec![&b"bar"[..]]);
+/// # }
+/// ```
+#[macro_export]
+macro_rules! expect(
+    ($input:expr, $rule:expr, $expected:expr, $submacro:ident!($($arguments:tt)*)) => (
+        {
+            let input  = $input;
+            let result = $submacro!(input, $($arguments)*);
+
+            $crate::error::expect(input, $rule, $expected, &result);
+
+            result
+        }
+    );
+
+    ($input:expr, $rule:expr, $expected:expr, $f:expr) => (
+        expect!($input, $rule, $expected, call!($f))
+    );
+
+    ($input:expr, $rule:expr, $token:expr) => (
+        expect!($input, $rule, &[$token], tag!($token))
+    );
+);
+
+/// `spanned!(I -> Result<I, O>) => I -> Result<I, Spanned<O>>`
+/// wraps the output of the parser with the span of what it has consumed,
+/// see the `span` module.
+///
+/// # Examples
+///
+/// ```
+/// # #[macro_use]
+/// # extern crate nom;
+/// # #[macro_use]
+/// # extern crate tagua_parser;
+/// use tagua_parser::Result;
+/// use tagua_parser::span::{self, Span, Spanned};
+///
+/// # fn main() {
+/// named!(
+///     test<Spanned<&[u8]>>,
+///     preceded!(
+///         tag!("foo"),
+///         spanned!(tag!("bar"))
+///     )
+/// );
+///
+/// if let Result::Done(_, bar) = span::within(&b"foobar"[..], test) {
+///     assert_eq!(bar.span, Span { start: 3, end: 6 });
+///     assert_eq!(bar.node, &b"bar"[..]);
+/// }
+/// # }
+/// ```
+#[macro_export]
+macro_rules! spanned(
+    ($input:expr, $submacro:ident!($($arguments:tt)*)) => (
+        $crate::span::spanned($input, |input| $submacro!(input, $($arguments)*))
+    );
+
+    ($input:expr, $f:expr) => (
+        spanned!($input, call!($f))
+    );
+);
+
+/// Check whether a byte can continue a name, i.e. `[a-zA-Z0-9_\x80-\xff]`.
+#[doc(hidden)]
+#[inline(always)]
+pub fn is_name_byte(byte: u8) -> bool {
+    matches!(byte, b'a' ..= b'z' | b'A' ..= b'Z' | b'0' ..= b'9' | b'_' | 0x80 ..= 0xff)
+}
 
 #[cfg(test)]
 mod tests {
@@ -423,10 +557,35 @@ mod tests {
         named!(test1<&str>, keyword!("foobar"));
         named!(test2<&str>, keyword!("fOoBaR"));
 
-        let input = &b"FoObArBaZQuX"[..];
+        let input = &b"FoObAr BaZQuX"[..];
 
-        assert_eq!(test1(input), Result::Done(&b"BaZQuX"[..], "foobar"));
-        assert_eq!(test2(input), Result::Done(&b"BaZQuX"[..], "fOoBaR"));
+        assert_eq!(test1(input), Result::Done(&b" BaZQuX"[..], "foobar"));
+        assert_eq!(test2(input), Result::Done(&b" BaZQuX"[..], "fOoBaR"));
+    }
+
+    #[test]
+    fn case_keyword_followed_by_a_non_name_byte() {
+        named!(test<&str>, keyword!("foobar"));
+
+        assert_eq!(test(&b"foobar(42)"[..]), Result::Done(&b"(42)"[..], "foobar"));
+        assert_eq!(test(&b"foobar\\baz"[..]), Result::Done(&b"\\baz"[..], "foobar"));
+    }
+
+    #[test]
+    fn case_keyword_followed_by_a_non_name_byte_if_it_ends_with_one() {
+        named!(test<&str>, keyword!("foo(bar)"));
+
+        assert_eq!(test(&b"foo(bar)baz"[..]), Result::Done(&b"baz"[..], "foo(bar)"));
+    }
+
+    #[test]
+    fn case_invalid_keyword_followed_by_a_name_byte() {
+        named!(test<&str>, keyword!("foobar"));
+
+        assert_eq!(test(&b"foobarBaz"[..]), Result::Error(Error::Position(ErrorKind::Custom(ErrorKindCustom::Keyword as u32), &b"foobarBaz"[..])));
+        assert_eq!(test(&b"foobar_"[..]), Result::Error(Error::Position(ErrorKind::Custom(ErrorKindCustom::Keyword as u32), &b"foobar_"[..])));
+        assert_eq!(test(&b"foobar42"[..]), Result::Error(Error::Position(ErrorKind::Custom(ErrorKindCustom::Keyword as u32), &b"foobar42"[..])));
+        assert_eq!(test(&b"foobar\xe9"[..]), Result::Error(Error::Position(ErrorKind::Custom(ErrorKindCustom::Keyword as u32), &b"foobar\xe9"[..])));
     }
 
     #[test]
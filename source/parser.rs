@@ -0,0 +1,580 @@
+// Tagua VM
+//
+//
+// New BSD License
+//
+// Copyright © 2016-2016, Ivan Enderlin.
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//     * Redistributions of source code must retain the above copyright
+//       notice, this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright
+//       notice, this list of conditions and the following disclaimer in the
+//       documentation and/or other materials provided with the distribution.
+//     * Neither the name of the Hoa nor the names of its contributors may be
+//       used to endorse or promote products derived from this software without
+//       specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE
+// ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDERS AND CONTRIBUTORS BE
+// LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR
+// CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF
+// SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS
+// INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN
+// CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+
+//! The entry point of the parser.
+//!
+//! A `Parser` parses a whole script, and hides the rules and their results.
+//! It is configured with the targeted PHP version, and whether the
+//! comments are collected, and whether the syntax errors are recovered.
+//!
+//! # Examples
+//!
+//! ```
+//! use tagua_parser::Parser;
+//! use tagua_parser::ast::Statement;
+//! use tagua_parser::diagnostics::{Diagnostic, DiagnosticKind};
+//! use tagua_parser::version::Version;
+//!
+//! let program = Parser::new()
+//!     .php_version(Version::Php74)
+//!     .with_comments(true)
+//!     .parse(b"<?php /** Hello. */ $foo{0};")
+//!     .unwrap();
+//!
+//! assert_eq!(program.statements.len(), 1);
+//! assert_eq!(program.statements[0].span.start..program.statements[0].span.end, 20..28);
+//! assert_eq!(program.comments[0].content, &b"* Hello. "[..]);
+//! assert_eq!(
+//!     program.diagnostics,
+//!     vec![
+//!         Diagnostic {
+//!             kind: DiagnosticKind::CurlyBracketOffset,
+//!             span: 24..27
+//!         }
+//!     ]
+//! );
+//! ```
+
+use std::result::Result as StdResult;
+use super::ast::Statement;
+use super::diagnostics::{
+    self,
+    Diagnostic,
+    DiagnosticKind
+};
+use super::encoding::skip_bom;
+use super::error::{
+    self,
+    ParseError
+};
+use super::internal::{
+    Error,
+    ErrorKind
+};
+use super::rules::literals::StringError;
+use super::rules::script::{
+    self,
+    recovering_script
+};
+use super::span::Spanned;
+use super::trivia::{
+    self,
+    Comment
+};
+use super::version::{
+    self,
+    Version
+};
+
+/// A parsed script.
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Program<'a> {
+    /// The shebang line starting the script, if any, without `#!`, e.g.
+    /// `/usr/bin/env php`.
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub shebang: Option<&'a [u8]>,
+
+    /// The statements.
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub statements: Vec<Spanned<Statement<'a>>>,
+
+    /// The comments, if collected.
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub comments: Vec<Comment<'a>>,
+
+    /// The diagnostics, like deprecations or recovered syntax errors.
+    pub diagnostics: Vec<Diagnostic>
+}
+
+/// A configurable parser of scripts.
+#[derive(Clone, Copy, Debug)]
+pub struct Parser {
+    version      : Version,
+    with_comments: bool,
+    with_recovery: bool
+}
+
+impl Parser {
+    /// Create a parser targeting the latest PHP version, without collecting
+    /// the comments, and without recovering the syntax errors.
+    pub fn new() -> Parser {
+        Parser {
+            version      : version::LATEST,
+            with_comments: false,
+            with_recovery: false
+        }
+    }
+
+    /// Set the targeted PHP version.
+    pub fn php_version(mut self, version: Version) -> Parser {
+        self.version = version;
+
+        self
+    }
+
+    /// Set whether the comments are collected.
+    pub fn with_comments(mut self, with_comments: bool) -> Parser {
+        self.with_comments = with_comments;
+
+        self
+    }
+
+    /// Set whether the syntax errors are recovered, see
+    /// `rules::script::recovering_script`. A recovering parser never fails:
+    /// an invalid escape sequence in a string is only a diagnostic.
+    pub fn with_recovery(mut self, with_recovery: bool) -> Parser {
+        self.with_recovery = with_recovery;
+
+        self
+    }
+
+    /// Parse a script.
+    pub fn parse<'a>(&self, input: &'a [u8]) -> StdResult<Program<'a>, ParseError> {
+        let _target = TargetGuard::new(self.version);
+
+        let (output, comments) = if self.with_comments {
+            trivia::collect(input, |input| self.parse_statements(input))
+        } else {
+            (self.parse_statements(input), vec![])
+        };
+
+        output.map(
+            |(statements, diagnostics)| {
+                Program {
+                    shebang    : script::shebang(skip_bom(input)),
+                    statements : statements,
+                    comments   : comments,
+                    diagnostics: diagnostics
+                }
+            }
+        )
+    }
+
+    /// Parse a script given as a string.
+    pub fn parse_str<'a>(&self, input: &'a str) -> StdResult<Program<'a>, ParseError> {
+        self.parse(input.as_bytes())
+    }
+
+    fn parse_statements<'a>(&self, input: &'a [u8]) -> StdResult<(Vec<Spanned<Statement<'a>>>, Vec<Diagnostic>), ParseError> {
+        if self.with_recovery {
+            return Ok(recovering_script(input));
+        }
+
+        match diagnostics::collect(input, |input| error::track(input, "script", script::script)) {
+            (Ok(statements), diagnostics) => {
+                let invalid_escape = diagnostics
+                    .iter()
+                    .find(|diagnostic| diagnostic.kind == DiagnosticKind::InvalidUnicodeEscape);
+
+                match invalid_escape {
+                    Some(diagnostic) => Err(
+                        ParseError::new(
+                            input,
+                            "string",
+                            Error::Position(ErrorKind::Custom(StringError::InvalidUnicodeEscape as u32), &input[diagnostic.span.start..])
+                        )
+                    ),
+
+                    None => Ok((statements, diagnostics))
+                }
+            },
+
+            (Err(error), _) => Err(error)
+        }
+    }
+}
+
+impl Default for Parser {
+    /// Create a parser with the same configuration as `Parser::new`.
+    fn default() -> Parser {
+        Parser::new()
+    }
+}
+
+/// Target a PHP version on the current thread, and restore the previously
+/// targeted version when dropped, even if the parser panics.
+struct TargetGuard {
+    previous_version: Version
+}
+
+impl TargetGuard {
+    fn new(version: Version) -> TargetGuard {
+        let previous_version = version::target();
+
+        version::set_target(version);
+
+        TargetGuard {
+            previous_version: previous_version
+        }
+    }
+}
+
+impl Drop for TargetGuard {
+    fn drop(&mut self) {
+        version::set_target(self.previous_version);
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "serde")]
+    use bincode;
+    use std::panic;
+    use super::{
+        Parser,
+        Program,
+        TargetGuard
+    };
+    use super::super::ast::{
+        Expression,
+        Literal,
+        Statement,
+        Variable
+    };
+    use super::super::diagnostics::{
+        Diagnostic,
+        DiagnosticKind
+    };
+    use super::super::internal::ErrorKind;
+    use super::super::rules::literals::StringError;
+    use super::super::span::{
+        Location,
+        Span,
+        Spanned
+    };
+    use super::super::trivia::{
+        Comment,
+        CommentKind
+    };
+    use super::super::version::{
+        self,
+        Version
+    };
+
+    #[test]
+    fn case_parse() {
+        let input  = b"<?php // foo\n$foo;";
+        let output = Ok(
+            Program {
+                shebang    : None,
+                statements : vec![Spanned::from(Statement::Expression(Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..])))))],
+                comments   : vec![],
+                diagnostics: vec![]
+            }
+        );
+
+        assert_eq!(Parser::new().parse(input), output);
+    }
+
+    #[test]
+    fn case_default_parser() {
+        let input = b"<?php // foo\n$foo;";
+
+        assert_eq!(Parser::default().parse(input), Parser::new().parse(input));
+    }
+
+    #[test]
+    fn case_parse_with_comments() {
+        let input  = b"<?php // foo\n$foo;";
+        let output = Ok(
+            Program {
+                shebang    : None,
+                statements : vec![Spanned::from(Statement::Expression(Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..])))))],
+                comments   : vec![
+                    Comment {
+                        kind   : CommentKind::SingleLine,
+                        content: &b" foo\n"[..],
+                        span   : 6..13
+                    }
+                ],
+                diagnostics: vec![]
+            }
+        );
+
+        assert_eq!(Parser::new().with_comments(true).parse(input), output);
+    }
+
+    #[test]
+    fn case_parse_with_a_shebang() {
+        let input   = b"\xef\xbb\xbf#!/usr/bin/env php\n<?php $foo;";
+        let program = Parser::new().parse(input).unwrap();
+
+        assert_eq!(program.shebang, Some(&b"/usr/bin/env php"[..]));
+        assert_eq!(Parser::new().with_comments(true).parse(input).unwrap().shebang, program.shebang);
+        assert_eq!(Parser::new().with_recovery(true).parse(input).unwrap().shebang, program.shebang);
+    }
+
+    #[test]
+    fn case_parse_spans_nested_statements() {
+        let input   = b"<?php\nwhile ($a) {\n    if ($b): ?>b<?= $c ?><?php endif;\n}";
+        let program = Parser::new().parse(input).unwrap();
+        let source  = |span: Span| &input[span.start..span.end];
+
+        assert_eq!(source(program.statements[0].span), &b"while ($a) {\n    if ($b): ?>b<?= $c ?><?php endif;\n}"[..]);
+
+        match program.statements[0].node {
+            Statement::While { ref condition, ref body } => {
+                assert_eq!(source(condition.span), &b"$a"[..]);
+                assert_eq!(source(body.span), &b"{\n    if ($b): ?>b<?= $c ?><?php endif;\n}"[..]);
+
+                match body.node {
+                    Statement::Block(ref statements) => {
+                        assert_eq!(source(statements[0].span), &b"if ($b): ?>b<?= $c ?><?php endif;"[..]);
+
+                        match statements[0].node {
+                            Statement::If { ref then, .. } => {
+                                assert_eq!(source(then.span), &b": ?>b<?= $c ?><?php "[..]);
+
+                                match then.node {
+                                    Statement::Block(ref statements) => {
+                                        assert_eq!(source(statements[0].span), &b"b"[..]);
+                                        assert_eq!(source(statements[1].span), &b"<?= $c "[..]);
+                                    },
+
+                                    _ => panic!("A block was expected.")
+                                }
+                            },
+
+                            _ => panic!("An if was expected.")
+                        }
+                    },
+
+                    _ => panic!("A block was expected.")
+                }
+            },
+
+            _ => panic!("A while was expected.")
+        }
+    }
+
+    #[test]
+    fn case_parse_spans_nested_expressions() {
+        let input   = b"<?php\n$a = $b->c(f(1), ...$d)[2];";
+        let program = Parser::new().parse(input).unwrap();
+        let source  = |span: Span| &input[span.start..span.end];
+
+        assert_eq!(program.statements[0].span, Span { start: 6, end: 33 });
+
+        match program.statements[0].node {
+            Statement::Expression(Spanned { node: Expression::Assignment { ref variable, ref value }, span }) => {
+                assert_eq!(source(span), &b"$a = $b->c(f(1), ...$d)[2]"[..]);
+                assert_eq!(source(variable.span), &b"$a"[..]);
+                assert_eq!(source(value.span), &b"$b->c(f(1), ...$d)[2]"[..]);
+
+                match value.node {
+                    Expression::ArrayAccess { ref array, index: Some(ref index) } => {
+                        assert_eq!(source(array.span), &b"$b->c(f(1), ...$d)"[..]);
+                        assert_eq!(source(index.span), &b"2"[..]);
+
+                        match array.node {
+                            Expression::MethodCall { ref object, ref method, ref arguments } => {
+                                assert_eq!(source(object.span), &b"$b"[..]);
+                                assert_eq!(source(method.span), &b"c"[..]);
+                                assert_eq!(source(arguments[0].span), &b"f(1)"[..]);
+                                assert_eq!(source(arguments[1].span), &b"...$d"[..]);
+                                assert_eq!(source(arguments[1].value.span), &b"$d"[..]);
+                            },
+
+                            _ => panic!("A method call was expected.")
+                        }
+                    },
+
+                    _ => panic!("An array access was expected.")
+                }
+            },
+
+            _ => panic!("An assignment was expected.")
+        }
+    }
+
+    #[test]
+    fn case_parse_with_a_php_version() {
+        let input = b"<?php $foo{0};";
+
+        assert!(Parser::new().parse(input).is_err());
+        assert_eq!(Parser::new().php_version(Version::Php74).parse(input).unwrap().diagnostics.len(), 1);
+        assert_eq!(version::target(), version::LATEST);
+    }
+
+    #[test]
+    fn case_target_guard_restores_the_version_on_panic() {
+        let result = panic::catch_unwind(
+            || {
+                let _target = TargetGuard::new(Version::Php74);
+
+                assert_eq!(version::target(), Version::Php74);
+
+                panic!("The parser panics.");
+            }
+        );
+
+        assert!(result.is_err());
+        assert_eq!(version::target(), version::LATEST);
+    }
+
+    #[test]
+    fn case_parse_with_recovery() {
+        let input  = b"<?php $foo = ; $bar;";
+        let output = Ok(
+            Program {
+                shebang    : None,
+                statements : vec![
+                    Spanned::from(Statement::Error(&b"$foo = ;"[..])),
+                    Spanned::from(Statement::Expression(Spanned::from(Expression::Variable(Variable::Name(&b"bar"[..])))))
+                ],
+                comments   : vec![],
+                diagnostics: vec![
+                    Diagnostic {
+                        kind: DiagnosticKind::SyntaxError,
+                        span: 6..14
+                    }
+                ]
+            }
+        );
+
+        assert_eq!(Parser::new().with_recovery(true).parse(input), output);
+    }
+
+    #[test]
+    fn case_parse_with_recovery_discards_the_diagnostics_of_an_invalid_statement() {
+        let input  = b"<?php $foo{0} = ; $bar{1};";
+        let output = Ok(
+            Program {
+                shebang    : None,
+                statements : vec![
+                    Spanned::from(Statement::Error(&b"$foo{0} = ;"[..])),
+                    Spanned::from(Statement::Expression(
+                        Spanned::from(Expression::ArrayAccess {
+                            array: Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"bar"[..])))),
+                            index: Some(Box::new(Spanned::from(Expression::Literal(Literal::Integer(1)))))
+                        })
+                    ))
+                ],
+                comments   : vec![],
+                diagnostics: vec![
+                    Diagnostic {
+                        kind: DiagnosticKind::SyntaxError,
+                        span: 6..17
+                    },
+                    Diagnostic {
+                        kind: DiagnosticKind::CurlyBracketOffset,
+                        span: 22..25
+                    }
+                ]
+            }
+        );
+
+        assert_eq!(Parser::new().php_version(Version::Php74).with_recovery(true).parse(input), output);
+    }
+
+    #[test]
+    fn case_invalid_parse_unicode_escaped_sequence() {
+        let input = b"<?php\n$foo = \"\\u{110000}\";";
+        let error = Parser::new().parse(input).unwrap_err();
+
+        assert_eq!(error.location, Location { offset: 14, line: 2, column: 9 });
+        assert_eq!(error.kind, ErrorKind::Custom(StringError::InvalidUnicodeEscape as u32));
+        assert_eq!(
+            Parser::new().with_recovery(true).parse(input).unwrap().diagnostics,
+            vec![
+                Diagnostic {
+                    kind: DiagnosticKind::InvalidUnicodeEscape,
+                    span: 14..24
+                }
+            ]
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn case_serialize_and_deserialize() {
+        let input   = b"<?php /* foo */ $foo{0} = \"bar $baz\";";
+        let program = Parser::new().php_version(Version::Php74).with_comments(true).parse(input).unwrap();
+        let datum   = bincode::serialize(&program).unwrap();
+        let output  = bincode::deserialize::<Program>(&datum).unwrap();
+
+        assert_eq!(output, program);
+        assert_eq!(output.comments[0].content, &b" foo "[..]);
+        assert_eq!(output.diagnostics.len(), 1);
+    }
+
+    #[test]
+    fn case_parse_str() {
+        let input  = "<?php $café;";
+        let output = Ok(
+            Program {
+                shebang    : None,
+                statements : vec![Spanned::from(Statement::Expression(Spanned::from(Expression::Variable(Variable::Name("café".as_bytes())))))],
+                comments   : vec![],
+                diagnostics: vec![]
+            }
+        );
+
+        assert_eq!(Parser::new().parse_str(input), output);
+    }
+
+    #[test]
+    fn case_invalid_parse() {
+        let error = Parser::new().parse(b"<?php {}\n  $foo = ;").unwrap_err();
+
+        assert_eq!(error.location, Location { offset: 18, line: 2, column: 10 });
+        assert_eq!(error.rule, "assignment_expression");
+        assert_eq!(error.kind, ErrorKind::Alt);
+        assert_eq!(error.expected, Vec::<&[u8]>::new());
+    }
+
+    #[test]
+    fn case_invalid_parse_expecting_a_token() {
+        let error = Parser::new().parse(b"<?php\nreturn f(1 2);").unwrap_err();
+
+        assert_eq!(error.location, Location { offset: 17, line: 2, column: 12 });
+        assert_eq!(error.rule, "arguments");
+        assert_eq!(error.kind, ErrorKind::Tag);
+        assert_eq!(error.expected, vec![&b")"[..]]);
+        assert_eq!(error.to_string(), "Syntax error at line 2, column 12, in the `arguments` rule: unexpected `2);` (Tag), expected `)`.");
+    }
+
+    #[test]
+    fn case_invalid_parse_string_document_indentation() {
+        let error = Parser::new().parse(b"<?php\n$a = <<<FOO\n  a\n b\n  FOO;\n").unwrap_err();
+
+        assert_eq!(error.location, Location { offset: 11, line: 2, column: 6 });
+        assert_eq!(error.rule, "string_heredoc");
+        assert_eq!(error.kind, ErrorKind::Custom(StringError::InvalidIndentation as u32));
+        assert_eq!(error.to_string(), "Syntax error at line 2, column 6, in the `string_heredoc` rule: unexpected `<<<FOO` (invalid indentation of a string).");
+
+        let error = Parser::new().parse(b"<?php\n$a = <<<'FOO'\n  a\n b\n  FOO;\n").unwrap_err();
+
+        assert_eq!(error.to_string(), "Syntax error at line 2, column 6, in the `string_nowdoc` rule: unexpected `<<<'FOO'` (invalid indentation of a string).");
+    }
+}
@@ -0,0 +1,322 @@
+// Tagua VM
+//
+//
+// New BSD License
+//
+// Copyright © 2016-2016, Ivan Enderlin.
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//     * Redistributions of source code must retain the above copyright
+//       notice, this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright
+//       notice, this list of conditions and the following disclaimer in the
+//       documentation and/or other materials provided with the distribution.
+//     * Neither the name of the Hoa nor the names of its contributors may be
+//       used to endorse or promote products derived from this software without
+//       specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE
+// ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDERS AND CONTRIBUTORS BE
+// LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR
+// CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF
+// SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS
+// INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN
+// CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+
+//! PHPDoc, i.e. the tags of the documentation comments.
+//!
+//! The documentation comment of a declaration is kept as is in the AST, see
+//! the `doc_comment` fields. For static analysis, it can be parsed on demand
+//! to extract the `@param`, `@return` and `@var` tags with their types. The
+//! types are kept as is, since PHPDoc types, e.g. `array<int, string>`, are
+//! richer than the PHP types.
+//!
+//! # Examples
+//!
+//! ```
+//! use tagua_parser::phpdoc::{self, Tag};
+//!
+//! let doc_comment = b"/**\n * Sum.\n *\n * @param int[] $xs The numbers.\n * @return int\n */";
+//!
+//! assert_eq!(
+//!     phpdoc::tags(&doc_comment[..]),
+//!     vec![
+//!         Tag::Param {
+//!             ty         : Some(&b"int[]"[..]),
+//!             name       : &b"xs"[..],
+//!             description: &b"The numbers."[..]
+//!         },
+//!         Tag::Return {
+//!             ty         : &b"int"[..],
+//!             description: &b""[..]
+//!         }
+//!     ]
+//! );
+//! ```
+
+/// A PHPDoc tag.
+#[derive(Debug, PartialEq)]
+pub enum Tag<'a> {
+    /// A parameter, e.g. `@param string $name The name.`. The name has no
+    /// `$`, and no `&` or `...` prefix.
+    Param {
+        /// The type, if any.
+        ty: Option<&'a [u8]>,
+        /// The name of the parameter.
+        name: &'a [u8],
+        /// The description, on the same line.
+        description: &'a [u8]
+    },
+
+    /// A return type, e.g. `@return int The sum.`.
+    Return {
+        /// The type.
+        ty: &'a [u8],
+        /// The description, on the same line.
+        description: &'a [u8]
+    },
+
+    /// A variable or a property type, e.g. `@var int $count The count.`.
+    /// The name has no `$`.
+    Var {
+        /// The type.
+        ty: &'a [u8],
+        /// The name of the variable, if any.
+        name: Option<&'a [u8]>,
+        /// The description, on the same line.
+        description: &'a [u8]
+    }
+}
+
+/// Extract the `@param`, `@return` and `@var` tags of a documentation
+/// comment, in order. Other tags, and malformed tags, are ignored.
+pub fn tags(doc_comment: &[u8]) -> Vec<Tag> {
+    let mut content = doc_comment;
+
+    if content.starts_with(b"/**") {
+        content = &content[3..];
+    }
+
+    if content.ends_with(b"*/") {
+        content = &content[..content.len() - 2];
+    }
+
+    content
+        .split(|byte| *byte == b'\n')
+        .filter_map(|line| tag(line_content(line)))
+        .collect()
+}
+
+/// Remove the leading `*` of a line, and the surrounding whitespaces.
+fn line_content(line: &[u8]) -> &[u8] {
+    let line = trim(line);
+
+    if line.starts_with(b"*") {
+        trim(&line[1..])
+    } else {
+        line
+    }
+}
+
+/// Parse a tag from the content of a line.
+fn tag(line: &[u8]) -> Option<Tag> {
+    let (name, rest) = token(line);
+
+    match name {
+        b"@param" => {
+            let (first, rest) = token(rest);
+            let (ty, name, description) = if is_variable(first) {
+                (None, first, rest)
+            } else {
+                let (name, description) = token(rest);
+
+                (Some(first), name, description)
+            };
+
+            if ty == Some(b"") || !is_variable(name) {
+                return None;
+            }
+
+            let name = if name.starts_with(b"&") { &name[1..] } else { name };
+            let name = if name.starts_with(b"...") { &name[3..] } else { name };
+
+            Some(
+                Tag::Param {
+                    ty         : ty,
+                    name       : &name[1..],
+                    description: description
+                }
+            )
+        },
+
+        b"@return" => {
+            let (ty, description) = token(rest);
+
+            if ty.is_empty() {
+                return None;
+            }
+
+            Some(
+                Tag::Return {
+                    ty         : ty,
+                    description: description
+                }
+            )
+        },
+
+        b"@var" => {
+            let (ty, rest) = token(rest);
+
+            if ty.is_empty() || is_variable(ty) {
+                return None;
+            }
+
+            let (name, description) = token(rest);
+
+            if name.starts_with(b"$") {
+                Some(
+                    Tag::Var {
+                        ty         : ty,
+                        name       : Some(&name[1..]),
+                        description: description
+                    }
+                )
+            } else {
+                Some(
+                    Tag::Var {
+                        ty         : ty,
+                        name       : None,
+                        description: rest
+                    }
+                )
+            }
+        },
+
+        _ => {
+            None
+        }
+    }
+}
+
+/// Split the first token, up to a whitespace outside of brackets so that
+/// `array<int, string>` is one token, from the rest of the line.
+fn token(line: &[u8]) -> (&[u8], &[u8]) {
+    let mut depth = 0usize;
+
+    for (index, byte) in line.iter().enumerate() {
+        match *byte {
+            b'<' | b'(' | b'[' | b'{' => {
+                depth += 1;
+            },
+
+            b'>' | b')' | b']' | b'}' => {
+                depth = depth.saturating_sub(1);
+            },
+
+            b' ' | b'\t' if depth == 0 => {
+                return (&line[..index], trim(&line[index..]));
+            },
+
+            _ => {}
+        }
+    }
+
+    (line, &line[line.len()..])
+}
+
+/// Check whether a token is a variable, possibly by reference or variadic.
+fn is_variable(token: &[u8]) -> bool {
+    token.starts_with(b"$") || token.starts_with(b"&$") || token.starts_with(b"...$") || token.starts_with(b"&...$")
+}
+
+/// Remove the whitespaces around bytes.
+fn trim(bytes: &[u8]) -> &[u8] {
+    let is_whitespace = |byte: &u8| *byte == b' ' || *byte == b'\t' || *byte == b'\r';
+    let start         = bytes.iter().position(|byte| !is_whitespace(byte)).unwrap_or(bytes.len());
+    let end           = bytes.iter().rposition(|byte| !is_whitespace(byte)).map(|index| index + 1).unwrap_or(start);
+
+    &bytes[start..end]
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        Tag,
+        tags
+    };
+
+    #[test]
+    fn case_tags_none() {
+        assert_eq!(tags(b"/** Nothing to see. */"), vec![]);
+    }
+
+    #[test]
+    fn case_tags_param() {
+        let input  = b"/**\n * @param string $name The name.\n * @param $untyped\n * @param int &...$rest\n */";
+        let output = vec![
+            Tag::Param {
+                ty         : Some(&b"string"[..]),
+                name       : &b"name"[..],
+                description: &b"The name."[..]
+            },
+            Tag::Param {
+                ty         : None,
+                name       : &b"untyped"[..],
+                description: &b""[..]
+            },
+            Tag::Param {
+                ty         : Some(&b"int"[..]),
+                name       : &b"rest"[..],
+                description: &b""[..]
+            }
+        ];
+
+        assert_eq!(tags(input), output);
+    }
+
+    #[test]
+    fn case_tags_generic_type() {
+        let input  = b"/** @return array<int, list<string>> The map. */";
+        let output = vec![
+            Tag::Return {
+                ty         : &b"array<int, list<string>>"[..],
+                description: &b"The map."[..]
+            }
+        ];
+
+        assert_eq!(tags(input), output);
+    }
+
+    #[test]
+    fn case_tags_var() {
+        let input  = b"/**\r\n * @var ?Foo $foo The foo.\r\n * @var int|null The count.\r\n */";
+        let output = vec![
+            Tag::Var {
+                ty         : &b"?Foo"[..],
+                name       : Some(&b"foo"[..]),
+                description: &b"The foo."[..]
+            },
+            Tag::Var {
+                ty         : &b"int|null"[..],
+                name       : None,
+                description: &b"The count."[..]
+            }
+        ];
+
+        assert_eq!(tags(input), output);
+    }
+
+    #[test]
+    fn case_tags_malformed_or_unknown_are_ignored() {
+        let input = b"/**\n * @param\n * @param int\n * @return\n * @var $foo\n * @throws Exception\n */";
+
+        assert_eq!(tags(input), vec![]);
+    }
+}
@@ -0,0 +1,2458 @@
+// Tagua VM
+//
+//
+// New BSD License
+//
+// Copyright © 2016-2016, Ivan Enderlin.
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//     * Redistributions of source code must retain the above copyright
+//       notice, this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright
+//       notice, this list of conditions and the following disclaimer in the
+//       documentation and/or other materials provided with the distribution.
+//     * Neither the name of the Hoa nor the names of its contributors may be
+//       used to endorse or promote products derived from this software without
+//       specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE
+// ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDERS AND CONTRIBUTORS BE
+// LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR
+// CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF
+// SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS
+// INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN
+// CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+
+//! Pretty-printer of the AST.
+//!
+//! A `Printer` renders any node of the AST back to PHP source code, see the
+//! `Print` trait. The printed code is valid PHP: parentheses are added where
+//! the precedence of the expressions requires them, so that parsing the
+//! printed code gives the same AST. Comments are not part of the AST, hence
+//! they are not printed, except the documentation comments of the
+//! declarations.
+//!
+//! The indentation and the line width are configurable. A list, like the
+//! arguments of a call or the items of an array, is split over several
+//! lines when it does not fit in the line width.
+//!
+//! # Examples
+//!
+//! ```
+//! use tagua_parser::parse;
+//! use tagua_parser::printer::{Indentation, Printer};
+//!
+//! let program = parse(b"<?php if ($x) { $y = [1, 2]; }").unwrap();
+//! let output  = Printer::new()
+//!     .indentation(Indentation::Spaces(2))
+//!     .print_script(&program.statements);
+//!
+//! assert_eq!(output, b"<?php\nif ($x) {\n  $y = [1, 2];\n}\n".to_vec());
+//! ```
+
+use super::ast::{
+    Argument,
+    Attribute,
+    CastType,
+    Catch,
+    ClassMember,
+    ConstantExpression,
+    DeclareDirective,
+    Expression,
+    IncludeKind,
+    Literal,
+    MagicConstant,
+    MatchArm,
+    Method,
+    Name,
+    Parameter,
+    PropertyHook,
+    PropertyHookBody,
+    PropertyHookKind,
+    RelativeScope,
+    Statement,
+    StringPart,
+    SwitchCase,
+    TraitAdaptation,
+    Type,
+    Use,
+    UseKind,
+    Variable,
+    Visibility
+};
+use super::span::Spanned;
+use super::tokens;
+
+/// The indentation of the printed code.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Indentation {
+    /// Indent each level with a number of spaces.
+    Spaces(usize),
+
+    /// Indent each level with a tabulation.
+    Tabs
+}
+
+/// A configurable printer of the AST.
+#[derive(Clone, Copy, Debug)]
+pub struct Printer {
+    indentation: Indentation,
+    line_width : usize
+}
+
+impl Printer {
+    /// Create a printer indenting with 4 spaces, within 80 columns.
+    pub fn new() -> Printer {
+        Printer {
+            indentation: Indentation::Spaces(4),
+            line_width : 80
+        }
+    }
+
+    /// Set the indentation.
+    pub fn indentation(mut self, indentation: Indentation) -> Printer {
+        self.indentation = indentation;
+
+        self
+    }
+
+    /// Set the line width, beyond which the lists are split over several
+    /// lines. A single item longer than the line width is not split.
+    pub fn line_width(mut self, line_width: usize) -> Printer {
+        self.line_width = line_width;
+
+        self
+    }
+
+    /// Print a node.
+    pub fn print<T: Print>(&self, node: &T) -> Vec<u8> {
+        let mut output = Output::new(self);
+
+        node.print(&mut output);
+
+        output.buffer
+    }
+
+    /// Print a whole script, i.e. statements opened by `<?php`. Inline
+    /// HTML is printed outside of the PHP sections.
+    pub fn print_script(&self, statements: &[Spanned<Statement>]) -> Vec<u8> {
+        let mut output = Output::new(self);
+        let mut is_php = false;
+
+        for statement in statements {
+            match statement.node {
+                Statement::InlineHtml(html) => {
+                    if is_php {
+                        output.write(tokens::CLOSE_TAG);
+
+                        // The newline directly following a closing tag is
+                        // eaten, see the `script` rule.
+                        if html.starts_with(b"\n") || html.starts_with(b"\r") {
+                            output.write(b"\n");
+                        }
+                    }
+
+                    output.write(html);
+                    is_php = false;
+                },
+
+                ref statement => {
+                    if !is_php {
+                        output.write(tokens::OPEN_TAG);
+                        is_php = true;
+                    }
+
+                    output.newline();
+                    statement.print(&mut output);
+                }
+            }
+        }
+
+        // The data following `__halt_compiler();` is printed verbatim, up
+        // to the end of the script.
+        let is_halted = matches!(statements.last().map(|statement| &statement.node), Some(&Statement::HaltCompiler { .. }));
+
+        if is_php && !is_halted {
+            output.newline();
+        }
+
+        output.buffer
+    }
+}
+
+impl Default for Printer {
+    /// Create a printer with the same configuration as `Printer::new`.
+    fn default() -> Printer {
+        Printer::new()
+    }
+}
+
+/// A node of the AST that can be printed, see `Printer::print`.
+pub trait Print {
+    /// Print the node into the output.
+    fn print(&self, output: &mut Output);
+}
+
+impl<T: Print> Print for Spanned<T> {
+    fn print(&self, output: &mut Output) {
+        self.node.print(output);
+    }
+}
+
+/// The code being printed, with its current indentation level.
+pub struct Output<'p> {
+    printer: &'p Printer,
+    buffer : Vec<u8>,
+    level  : usize
+}
+
+impl<'p> Output<'p> {
+    fn new(printer: &'p Printer) -> Output<'p> {
+        Output {
+            printer: printer,
+            buffer : Vec::new(),
+            level  : 0
+        }
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        self.buffer.extend_from_slice(bytes);
+    }
+
+    /// Start a new line, indented to the current level.
+    fn newline(&mut self) {
+        self.buffer.push(b'\n');
+
+        match self.printer.indentation {
+            Indentation::Spaces(width) => {
+                for _ in 0..self.level * width {
+                    self.buffer.push(b' ');
+                }
+            },
+
+            Indentation::Tabs => {
+                for _ in 0..self.level {
+                    self.buffer.push(b'\t');
+                }
+            }
+        }
+    }
+
+    /// The column of the end of the output.
+    fn column(&self) -> usize {
+        match self.buffer.iter().rposition(|&byte| byte == b'\n') {
+            Some(position) => self.buffer.len() - position - 1,
+            None => self.buffer.len()
+        }
+    }
+
+    /// Print into a new output, at the same level.
+    fn render<F>(&self, print: F) -> Vec<u8>
+        where F: FnOnce(&mut Output)
+    {
+        let mut output = Output {
+            printer: self.printer,
+            buffer : Vec::new(),
+            level  : self.level
+        };
+
+        print(&mut output);
+
+        output.buffer
+    }
+}
+
+/// The precedence of an expression. An operand with a lower precedence
+/// than the one expected by its operator is parenthesized. The levels
+/// follow the expression rules, see the `rules::expressions` module:
+/// `print`, `yield`, arrow functions etc. are the lowest because they
+/// consume the whole expression on their right.
+type Precedence = u8;
+
+const OPEN         : Precedence = 0;
+const ASSIGNMENT   : Precedence = 1;
+const INSTANCEOF   : Precedence = 2;
+const UNARY        : Precedence = 3;
+const POWER_OPERAND: Precedence = 4;
+const PRIMARY      : Precedence = 5;
+const POSTFIX      : Precedence = 6;
+
+fn precedence(expression: &Expression) -> Precedence {
+    match *expression {
+        Expression::Array(_) |
+        Expression::ArrayAccess { .. } |
+        Expression::Call { .. } |
+        Expression::CallableCreation(_) |
+        Expression::ClassNameConstant(_) |
+        Expression::DynamicClassConstant { .. } |
+        Expression::InterpolatedString(_) |
+        Expression::MagicConstant(_) |
+        Expression::MethodCall { .. } |
+        Expression::Name(_) |
+        Expression::NullsafeMethodCall { .. } |
+        Expression::NullsafePropertyFetch { .. } |
+        Expression::PropertyFetch { .. } |
+        Expression::RelativeScope(_) |
+        Expression::StaticFetch { .. } |
+        Expression::StaticMethodCall { .. } |
+        Expression::Variable(_) => {
+            POSTFIX
+        },
+
+        Expression::Literal(Literal::Integer(_)) |
+        Expression::Literal(Literal::Nowdoc { .. }) |
+        Expression::Literal(Literal::Real(_)) => {
+            PRIMARY
+        },
+
+        Expression::Literal(_) => {
+            POSTFIX
+        },
+
+        Expression::AnonymousClass { .. } |
+        Expression::Closure { .. } |
+        Expression::Empty(_) |
+        Expression::Eval(_) |
+        Expression::Exit(_) |
+        Expression::Heredoc { .. } |
+        Expression::Isset(_) |
+        Expression::List(_) |
+        Expression::Match { .. } |
+        Expression::New { .. } |
+        Expression::ShellExec(_) |
+        Expression::ShortList(_) |
+        Expression::Unset(_) => {
+            PRIMARY
+        },
+
+        Expression::Clone(_) |
+        Expression::PostDecrement(_) |
+        Expression::PostIncrement(_) |
+        Expression::PreDecrement(_) |
+        Expression::PreIncrement(_) => {
+            POWER_OPERAND
+        },
+
+        Expression::Cast { .. } |
+        Expression::Negation(_) |
+        Expression::Power { .. } => {
+            UNARY
+        },
+
+        Expression::InstanceOf { .. } => {
+            INSTANCEOF
+        },
+
+        Expression::Assignment { .. } |
+        Expression::Reference(_) => {
+            ASSIGNMENT
+        },
+
+        Expression::ArrowFunction { .. } |
+        Expression::Echo(_) |
+        Expression::Include { .. } |
+        Expression::Print(_) |
+        Expression::Throw(_) |
+        Expression::Yield { .. } |
+        Expression::YieldFrom(_) => {
+            OPEN
+        }
+    }
+}
+
+/// Print an operand, parenthesized if its precedence is lower than the
+/// expected one.
+fn operand(output: &mut Output, expression: &Expression, expected: Precedence) {
+    if precedence(expression) < expected {
+        output.write(b"(");
+        expression.print(output);
+        output.write(b")");
+    } else {
+        expression.print(output);
+    }
+}
+
+/// Print a comma-separated list of items, between `open` and `close`. The
+/// list is split with an item per line when it does not fit in the line
+/// width, or when an item spans several lines.
+fn list<T, F>(output: &mut Output, open: &[u8], items: &[T], close: &[u8], trailing_comma: bool, print: F)
+    where F: Fn(&mut Output, &T)
+{
+    output.write(open);
+
+    if items.is_empty() {
+        output.write(close);
+
+        return;
+    }
+
+    let flat = output.render(
+        |output| {
+            for (index, item) in items.iter().enumerate() {
+                if index > 0 {
+                    output.write(b", ");
+                }
+
+                print(output, item);
+            }
+        }
+    );
+
+    let fits =
+        !flat.contains(&(b'\n')) &&
+        output.column() + flat.len() + close.len() <= output.printer.line_width;
+
+    if fits {
+        output.write(&flat);
+    } else {
+        output.level += 1;
+
+        for (index, item) in items.iter().enumerate() {
+            output.newline();
+            print(output, item);
+
+            if index + 1 < items.len() || trailing_comma {
+                output.write(b",");
+            }
+        }
+
+        output.level -= 1;
+        output.newline();
+    }
+
+    output.write(close);
+}
+
+/// Print the items of a list or a short list, where `None` is a skipped
+/// element.
+fn list_items(output: &mut Output, open: &[u8], items: &[Option<(Option<Spanned<Expression>>, Spanned<Expression>)>], close: &[u8]) {
+    list(
+        output,
+        open,
+        items,
+        close,
+        false,
+        |output, item| {
+            if let Some((ref key, ref value)) = *item {
+                if let Some(ref key) = *key {
+                    operand(output, key, ASSIGNMENT);
+                    output.write(b" => ");
+                }
+
+                operand(output, value, OPEN);
+            }
+        }
+    );
+}
+
+/// Print items separated by `separator`, on a single line.
+fn join<T, F>(output: &mut Output, items: &[T], separator: &[u8], print: F)
+    where F: Fn(&mut Output, &T)
+{
+    for (index, item) in items.iter().enumerate() {
+        if index > 0 {
+            output.write(separator);
+        }
+
+        print(output, item);
+    }
+}
+
+/// Print bytes of an interpolated string, of a shell execution or of a
+/// heredoc, escaped for the given delimiter, if any.
+fn escape(output: &mut Output, bytes: &[u8], delimiter: Option<u8>) {
+    for &byte in bytes {
+        match byte {
+            b'\\' => output.write(b"\\\\"),
+            b'$'  => output.write(b"\\$"),
+            b'\n' => output.write(b"\\n"),
+            b'\t' => output.write(b"\\t"),
+            b'\r' => output.write(b"\\r"),
+            0x0b  => output.write(b"\\v"),
+            0x1b  => output.write(b"\\e"),
+            0x0c  => output.write(b"\\f"),
+
+            byte if Some(byte) == delimiter => {
+                output.write(&[b'\\', byte]);
+            },
+
+            byte if byte < 0x20 || byte == 0x7f => {
+                output.write(format!("\\x{:02x}", byte).as_bytes());
+            },
+
+            byte => {
+                output.buffer.push(byte);
+            }
+        }
+    }
+}
+
+/// Print the parts of an interpolated string or of a shell execution. The
+/// expressions are always printed with the `{$…}` syntax.
+fn string_parts(output: &mut Output, parts: &[StringPart], delimiter: u8) {
+    output.write(&[delimiter]);
+
+    for part in parts {
+        match *part {
+            StringPart::Bytes(ref bytes) => {
+                escape(output, bytes, Some(delimiter));
+            },
+
+            StringPart::Expression(ref expression) => {
+                output.write(b"{");
+                operand(output, expression, OPEN);
+                output.write(b"}");
+            }
+        }
+    }
+
+    output.write(&[delimiter]);
+}
+
+/// Print the bytes of a heredoc or of a nowdoc, where each line is
+/// indented by the indentation of the closing label, except the empty
+/// lines. The bytes of a heredoc are escaped, except the newlines. Return
+/// whether the printed bytes end a line.
+fn document_bytes(output: &mut Output, bytes: &[u8], indentation: &[u8], is_escaped: bool, is_line_start: bool) -> bool {
+    let mut is_line_start = is_line_start;
+
+    for &byte in bytes {
+        if byte == b'\n' {
+            output.write(b"\n");
+            is_line_start = true;
+
+            continue;
+        }
+
+        if is_line_start {
+            output.write(indentation);
+            is_line_start = false;
+        }
+
+        if is_escaped {
+            escape(output, &[byte], None);
+        } else {
+            output.buffer.push(byte);
+        }
+    }
+
+    is_line_start
+}
+
+/// Print the closing label of a heredoc or of a nowdoc, on its own line.
+fn document_closing(output: &mut Output, label: &[u8], indentation: &[u8]) {
+    output.write(b"\n");
+    output.write(indentation);
+    output.write(label);
+}
+
+/// Print the name of a member, i.e. a property or a static member.
+fn member(output: &mut Output, member: &Expression) {
+    match *member {
+        Expression::Name(Name::Unqualified(name)) => {
+            output.write(name);
+        },
+
+        Expression::Variable(ref variable) => {
+            variable.print(output);
+        },
+
+        ref member => {
+            output.write(b"{");
+            operand(output, member, OPEN);
+            output.write(b"}");
+        }
+    }
+}
+
+fn arguments(output: &mut Output, arguments: &[Spanned<Argument>]) {
+    list(output, b"(", arguments, b")", false, |output, argument| argument.print(output));
+}
+
+fn parameters(output: &mut Output, parameters: &[Spanned<Parameter>]) {
+    list(output, b"(", parameters, b")", false, |output, parameter| parameter.print(output));
+}
+
+fn return_type(output: &mut Output, ty: &Option<Spanned<Type>>) {
+    if let Some(ref ty) = *ty {
+        output.write(b": ");
+        ty.print(output);
+    }
+}
+
+fn names(output: &mut Output, names: &[Spanned<Name>]) {
+    join(output, names, b", ", |output, name| name.print(output));
+}
+
+fn visibility(visibility: Visibility) -> &'static [u8] {
+    match visibility {
+        Visibility::Public    => tokens::PUBLIC,
+        Visibility::Protected => tokens::PROTECTED,
+        Visibility::Private   => tokens::PRIVATE
+    }
+}
+
+fn set_visibility(visibility: Visibility) -> &'static [u8] {
+    match visibility {
+        Visibility::Public    => tokens::PUBLIC_SET,
+        Visibility::Protected => tokens::PROTECTED_SET,
+        Visibility::Private   => tokens::PRIVATE_SET
+    }
+}
+
+/// Print the attributes, each in its own group, followed by a space when
+/// inlined, or by a newline otherwise.
+fn attributes(output: &mut Output, attributes: &[Attribute], inline: bool) {
+    for attribute in attributes {
+        output.write(tokens::ATTRIBUTE);
+        attribute.print(output);
+        output.write(tokens::RIGHT_SQUARE_BRACKET);
+
+        if inline {
+            output.write(b" ");
+        } else {
+            output.newline();
+        }
+    }
+}
+
+/// Print the documentation comment and the attributes of a declaration.
+fn declaration_header(output: &mut Output, doc_comment: &Option<Vec<u8>>, declaration_attributes: &[Attribute]) {
+    if let Some(ref doc_comment) = *doc_comment {
+        output.write(doc_comment);
+        output.newline();
+    }
+
+    attributes(output, declaration_attributes, false);
+}
+
+/// Print statements between curly brackets, one per line.
+fn block(output: &mut Output, statements: &[Spanned<Statement>]) {
+    output.write(tokens::LEFT_CURLY_BRACKET);
+    output.level += 1;
+
+    for statement in statements {
+        output.newline();
+        statement.print(output);
+    }
+
+    output.level -= 1;
+    output.newline();
+    output.write(tokens::RIGHT_CURLY_BRACKET);
+}
+
+/// Print the body of a control structure: a block on the same line, any
+/// other statement on the next line, indented.
+fn body(output: &mut Output, statement: &Statement) {
+    match *statement {
+        Statement::Block(ref statements) => {
+            output.write(b" ");
+            block(output, statements);
+        },
+
+        Statement::Empty => {
+            output.write(tokens::SEMICOLON);
+        },
+
+        ref statement => {
+            output.level += 1;
+            output.newline();
+            statement.print(output);
+            output.level -= 1;
+        }
+    }
+}
+
+/// Print the separator between a body and the next clause, e.g. `else`.
+fn clause_separator(output: &mut Output, previous: &Statement) {
+    match *previous {
+        Statement::Block(_) => output.write(b" "),
+        _ => output.newline()
+    }
+}
+
+fn class_body(output: &mut Output, members: &[Spanned<ClassMember>]) {
+    output.newline();
+    class_members(output, members);
+}
+
+/// Print class members between curly brackets. A blank line separates
+/// the methods from their neighbours.
+fn class_members(output: &mut Output, members: &[Spanned<ClassMember>]) {
+    output.write(tokens::LEFT_CURLY_BRACKET);
+    output.level += 1;
+
+    let mut previous_is_method = false;
+
+    for (index, member) in members.iter().enumerate() {
+        let is_method = matches!(member.node, ClassMember::Method(_));
+
+        if index > 0 && (is_method || previous_is_method) {
+            output.write(b"\n");
+        }
+
+        output.newline();
+        member.print(output);
+        previous_is_method = is_method;
+    }
+
+    output.level -= 1;
+    output.newline();
+    output.write(tokens::RIGHT_CURLY_BRACKET);
+}
+
+fn constants(output: &mut Output, constants: &[(&[u8], ConstantExpression)]) {
+    join(
+        output,
+        constants,
+        b", ",
+        |output, &(name, ref value)| {
+            output.write(name);
+            output.write(b" = ");
+            value.print(output);
+        }
+    );
+}
+
+fn jump(output: &mut Output, keyword: &[u8], level: Option<u64>) {
+    output.write(keyword);
+
+    if let Some(level) = level {
+        output.write(format!(" {}", level).as_bytes());
+    }
+
+    output.write(tokens::SEMICOLON);
+}
+
+fn use_kind(kind: UseKind) -> &'static [u8] {
+    match kind {
+        UseKind::Class    => b"",
+        UseKind::Function => b"function ",
+        UseKind::Constant => b"const "
+    }
+}
+
+fn name_parts<'a>(name: &Name<'a>) -> Vec<&'a [u8]> {
+    match *name {
+        Name::Unqualified(name) => vec![name],
+        Name::Qualified(ref parts) |
+        Name::RelativeQualified(ref parts) |
+        Name::FullyQualified(ref parts) => parts.clone()
+    }
+}
+
+/// Print a use statement. Uses of different kinds come from a group use,
+/// e.g. `use A\{function b, const C}`, and are grouped back under their
+/// common prefix.
+fn uses(output: &mut Output, uses: &[Use]) {
+    let kind = uses[0].kind;
+
+    output.write(tokens::USE);
+    output.write(b" ");
+
+    if uses.iter().all(|item| item.kind == kind) {
+        output.write(use_kind(kind));
+        join(output, uses, b", ", |output, item| item.print(output));
+        output.write(tokens::SEMICOLON);
+
+        return;
+    }
+
+    let parts: Vec<Vec<&[u8]>> = uses.iter().map(|item| name_parts(&item.name)).collect();
+    let shortest               = parts.iter().map(|parts| parts.len()).min().unwrap_or(0);
+    let prefix_length          = (0..shortest.saturating_sub(1))
+        .take_while(|&index| parts.iter().all(|item_parts| item_parts[index] == parts[0][index]))
+        .count();
+
+    if let Name::FullyQualified(_) = uses[0].name.node {
+        output.write(tokens::NAMESPACE_SEPARATOR);
+    }
+
+    output.write(&parts[0][..prefix_length].join(tokens::NAMESPACE_SEPARATOR));
+    output.write(tokens::NAMESPACE_SEPARATOR);
+    output.write(tokens::LEFT_CURLY_BRACKET);
+
+    for (index, item) in uses.iter().enumerate() {
+        if index > 0 {
+            output.write(b", ");
+        }
+
+        output.write(use_kind(item.kind));
+        output.write(&parts[index][prefix_length..].join(tokens::NAMESPACE_SEPARATOR));
+
+        if let Some(alias) = item.alias {
+            output.write(b" as ");
+            output.write(alias);
+        }
+    }
+
+    output.write(tokens::RIGHT_CURLY_BRACKET);
+    output.write(tokens::SEMICOLON);
+}
+
+fn real(real: f64) -> String {
+    if real.is_infinite() {
+        // `INF` has no literal, but overflowing reals become `INF`.
+        "1e1000".to_string()
+    } else {
+        format!("{:?}", real)
+    }
+}
+
+impl Print for Literal {
+    fn print(&self, output: &mut Output) {
+        match *self {
+            Literal::Boolean(true) => {
+                output.write(tokens::TRUE);
+            },
+
+            Literal::Boolean(false) => {
+                output.write(tokens::FALSE);
+            },
+
+            Literal::Integer(integer) => {
+                output.write(integer.to_string().as_bytes());
+            },
+
+            Literal::Nowdoc { ref label, ref indentation, ref value } => {
+                output.write(b"<<<'");
+                output.write(label);
+                output.write(b"'\n");
+                document_bytes(output, value, indentation, false, true);
+                document_closing(output, label, indentation);
+            },
+
+            Literal::Null => {
+                output.write(tokens::NULL);
+            },
+
+            Literal::Real(value) => {
+                output.write(real(value).as_bytes());
+            },
+
+            Literal::String(ref string) => {
+                output.write(b"'");
+
+                for &byte in string {
+                    if byte == b'\\' || byte == b'\'' {
+                        output.buffer.push(b'\\');
+                    }
+
+                    output.buffer.push(byte);
+                }
+
+                output.write(b"'");
+            }
+        }
+    }
+}
+
+impl<'a> Print for Variable<'a> {
+    fn print(&self, output: &mut Output) {
+        output.write(tokens::VARIABLE);
+
+        match *self {
+            Variable::Name(name) => {
+                output.write(name);
+            },
+
+            Variable::Dynamic(ref expression) => {
+                match expression.node {
+                    Expression::Variable(ref variable) => {
+                        variable.print(output);
+                    },
+
+                    ref expression => {
+                        output.write(tokens::LEFT_CURLY_BRACKET);
+                        operand(output, expression, OPEN);
+                        output.write(tokens::RIGHT_CURLY_BRACKET);
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl<'a> Print for Name<'a> {
+    fn print(&self, output: &mut Output) {
+        let parts = match *self {
+            Name::Unqualified(name) => {
+                output.write(name);
+
+                return;
+            },
+
+            Name::Qualified(ref parts) => {
+                parts
+            },
+
+            Name::RelativeQualified(ref parts) => {
+                output.write(tokens::NAMESPACE);
+                output.write(tokens::NAMESPACE_SEPARATOR);
+
+                parts
+            },
+
+            Name::FullyQualified(ref parts) => {
+                output.write(tokens::NAMESPACE_SEPARATOR);
+
+                parts
+            }
+        };
+
+        output.write(&parts.join(tokens::NAMESPACE_SEPARATOR));
+    }
+}
+
+impl<'a> Print for Type<'a> {
+    fn print(&self, output: &mut Output) {
+        let keyword: &[u8] = match *self {
+            Type::Array    => tokens::ARRAY,
+            Type::Bool     => tokens::BOOL,
+            Type::Callable => tokens::CALLABLE,
+            Type::Current  => tokens::SELF,
+            Type::False    => tokens::FALSE,
+            Type::Float    => tokens::FLOAT,
+            Type::Int      => tokens::INT,
+            Type::Iterable => tokens::ITERABLE,
+            Type::Mixed    => tokens::MIXED,
+            Type::Never    => tokens::NEVER,
+            Type::Null     => tokens::NULL,
+            Type::Object   => tokens::OBJECT,
+            Type::Parent   => tokens::PARENT,
+            Type::Static   => tokens::STATIC,
+            Type::String   => tokens::STRING,
+            Type::True     => tokens::TRUE,
+            Type::Void     => tokens::VOID,
+
+            Type::Intersection(ref types) => {
+                join(output, types, tokens::BITWISE_AND, |output, ty| ty.print(output));
+
+                return;
+            },
+
+            Type::Name(ref name) => {
+                name.print(output);
+
+                return;
+            },
+
+            Type::Nullable(ref ty) => {
+                output.write(tokens::NULLABLE);
+                ty.print(output);
+
+                return;
+            },
+
+            Type::Union(ref types) => {
+                join(
+                    output,
+                    types,
+                    tokens::BITWISE_OR,
+                    |output, ty| {
+                        match ty.node {
+                            Type::Intersection(_) => {
+                                output.write(tokens::LEFT_PARENTHESIS);
+                                ty.print(output);
+                                output.write(tokens::RIGHT_PARENTHESIS);
+                            },
+
+                            _ => {
+                                ty.print(output);
+                            }
+                        }
+                    }
+                );
+
+                return;
+            }
+        };
+
+        output.write(keyword);
+    }
+}
+
+impl<'a> Print for ConstantExpression<'a> {
+    fn print(&self, output: &mut Output) {
+        operand(output, &self.0, OPEN);
+    }
+}
+
+impl<'a> Print for Parameter<'a> {
+    fn print(&self, output: &mut Output) {
+        attributes(output, &self.attributes, true);
+
+        if let Some(parameter_visibility) = self.visibility {
+            output.write(visibility(parameter_visibility));
+            output.write(b" ");
+        }
+
+        if let Some(parameter_set_visibility) = self.set_visibility {
+            output.write(set_visibility(parameter_set_visibility));
+            output.write(b" ");
+        }
+
+        if self.is_readonly {
+            output.write(tokens::READONLY);
+            output.write(b" ");
+        }
+
+        if let Some(ref ty) = self.ty {
+            ty.print(output);
+            output.write(b" ");
+        }
+
+        if self.is_reference {
+            output.write(tokens::REFERENCE);
+        }
+
+        if self.is_variadic {
+            output.write(tokens::ELLIPSIS);
+        }
+
+        self.name.print(output);
+
+        if let Some(ref value) = self.value {
+            output.write(b" = ");
+            value.print(output);
+        }
+
+        if !self.hooks.is_empty() {
+            output.write(b" ");
+            property_hooks(output, &self.hooks);
+        }
+    }
+}
+
+impl<'a> Print for Argument<'a> {
+    fn print(&self, output: &mut Output) {
+        if let Some(name) = self.name {
+            output.write(name);
+            output.write(b": ");
+        }
+
+        if self.is_unpacked {
+            output.write(tokens::ELLIPSIS);
+        }
+
+        operand(output, &self.value, OPEN);
+    }
+}
+
+impl<'a> Print for Attribute<'a> {
+    fn print(&self, output: &mut Output) {
+        self.name.print(output);
+
+        if !self.arguments.is_empty() {
+            arguments(output, &self.arguments);
+        }
+    }
+}
+
+impl<'a> Print for MatchArm<'a> {
+    fn print(&self, output: &mut Output) {
+        match self.conditions {
+            Some(ref conditions) => {
+                join(output, conditions, b", ", |output, condition| operand(output, condition, ASSIGNMENT));
+            },
+
+            None => {
+                output.write(tokens::DEFAULT);
+            }
+        }
+
+        output.write(b" => ");
+        operand(output, &self.body, OPEN);
+    }
+}
+
+impl<'a> Print for Expression<'a> {
+    fn print(&self, output: &mut Output) {
+        match *self {
+            Expression::AnonymousClass { arguments: ref class_arguments, ref extends, ref implements, ref members } => {
+                output.write(tokens::NEW);
+                output.write(b" ");
+                output.write(tokens::CLASS);
+
+                if !class_arguments.is_empty() {
+                    arguments(output, class_arguments);
+                }
+
+                if let Some(ref extends) = *extends {
+                    output.write(b" extends ");
+                    extends.print(output);
+                }
+
+                if !implements.is_empty() {
+                    output.write(b" implements ");
+                    names(output, implements);
+                }
+
+                output.write(b" ");
+                class_members(output, members);
+            },
+
+            Expression::Array(ref pairs) => {
+                list(
+                    output,
+                    tokens::LEFT_SQUARE_BRACKET,
+                    pairs,
+                    tokens::RIGHT_SQUARE_BRACKET,
+                    true,
+                    |output, (key, value)| {
+                        if let Some(ref key) = *key {
+                            operand(output, key, ASSIGNMENT);
+                            output.write(b" => ");
+                        }
+
+                        operand(output, value, OPEN);
+                    }
+                );
+            },
+
+            Expression::ArrayAccess { ref array, ref index } => {
+                operand(output, array, POSTFIX);
+                output.write(tokens::LEFT_SQUARE_BRACKET);
+
+                if let Some(ref index) = *index {
+                    operand(output, index, OPEN);
+                }
+
+                output.write(tokens::RIGHT_SQUARE_BRACKET);
+            },
+
+            Expression::ArrowFunction { is_static, returns_reference, parameters: ref function_parameters, return_type: ref function_return_type, ref body } => {
+                if is_static {
+                    output.write(tokens::STATIC);
+                    output.write(b" ");
+                }
+
+                output.write(tokens::FN);
+
+                if returns_reference {
+                    output.write(tokens::REFERENCE);
+                }
+
+                parameters(output, function_parameters);
+                return_type(output, function_return_type);
+                output.write(b" => ");
+                operand(output, body, OPEN);
+            },
+
+            Expression::Assignment { ref variable, ref value } => {
+                operand(output, variable, INSTANCEOF);
+                output.write(b" = ");
+                operand(output, value, ASSIGNMENT);
+            },
+
+            Expression::Call { ref callee, arguments: ref call_arguments } => {
+                match callee.node {
+                    // Without parenthesis, the call of a fetched member is
+                    // a method call.
+                    Expression::NullsafePropertyFetch { .. } |
+                    Expression::PropertyFetch { .. } |
+                    Expression::StaticFetch { .. } => {
+                        output.write(b"(");
+                        operand(output, callee, OPEN);
+                        output.write(b")");
+                    },
+
+                    _ => {
+                        operand(output, callee, POSTFIX);
+                    }
+                }
+
+                arguments(output, call_arguments);
+            },
+
+            Expression::CallableCreation(ref callee) => {
+                operand(output, callee, POSTFIX);
+                output.write(b"(...)");
+            },
+
+            Expression::Cast { ref ty, operand: ref cast_operand } => {
+                let cast_type: &[u8] = match *ty {
+                    CastType::Array   => tokens::ARRAY,
+                    CastType::Boolean => tokens::BOOL,
+                    CastType::Integer => tokens::INT,
+                    CastType::Object  => tokens::OBJECT,
+                    CastType::Real    => tokens::FLOAT,
+                    CastType::String  => tokens::STRING,
+                    CastType::Unset   => tokens::UNSET
+                };
+
+                output.write(tokens::LEFT_PARENTHESIS);
+                output.write(cast_type);
+                output.write(tokens::RIGHT_PARENTHESIS);
+                output.write(b" ");
+                operand(output, cast_operand, UNARY);
+            },
+
+            Expression::ClassNameConstant(ref class) => {
+                operand(output, class, POSTFIX);
+                output.write(tokens::STATIC_CALL);
+                output.write(tokens::CLASS);
+            },
+
+            Expression::Clone(ref clone_operand) => {
+                output.write(tokens::CLONE);
+                output.write(b" ");
+
+                match clone_operand.node {
+                    Expression::Clone(_) => clone_operand.print(output),
+                    _ => operand(output, clone_operand, PRIMARY)
+                }
+            },
+
+            Expression::Closure { is_static, returns_reference, parameters: ref function_parameters, ref captures, return_type: ref function_return_type, body: ref function_body } => {
+                if is_static {
+                    output.write(tokens::STATIC);
+                    output.write(b" ");
+                }
+
+                output.write(tokens::FUNCTION);
+                output.write(b" ");
+
+                if returns_reference {
+                    output.write(tokens::REFERENCE);
+                }
+
+                parameters(output, function_parameters);
+
+                if !captures.is_empty() {
+                    output.write(b" ");
+                    output.write(tokens::USE);
+                    output.write(b" ");
+                    list(output, b"(", captures, b")", false, |output, capture| operand(output, capture, OPEN));
+                }
+
+                return_type(output, function_return_type);
+                output.write(b" ");
+                block(output, function_body);
+            },
+
+            Expression::DynamicClassConstant { ref class, ref name } => {
+                operand(output, class, POSTFIX);
+                output.write(tokens::STATIC_CALL);
+                output.write(tokens::LEFT_CURLY_BRACKET);
+                operand(output, name, OPEN);
+                output.write(tokens::RIGHT_CURLY_BRACKET);
+            },
+
+            Expression::Echo(ref expressions) => {
+                output.write(tokens::ECHO);
+                output.write(b" ");
+                join(output, expressions, b", ", |output, expression| operand(output, expression, OPEN));
+            },
+
+            Expression::Empty(ref expression) => {
+                output.write(tokens::EMPTY);
+                output.write(tokens::LEFT_PARENTHESIS);
+                operand(output, expression, OPEN);
+                output.write(tokens::RIGHT_PARENTHESIS);
+            },
+
+            Expression::Eval(ref expression) => {
+                output.write(tokens::EVAL);
+                output.write(tokens::LEFT_PARENTHESIS);
+                operand(output, expression, OPEN);
+                output.write(tokens::RIGHT_PARENTHESIS);
+            },
+
+            Expression::Exit(ref expression) => {
+                output.write(tokens::EXIT);
+
+                if let Some(ref expression) = *expression {
+                    output.write(tokens::LEFT_PARENTHESIS);
+                    operand(output, expression, OPEN);
+                    output.write(tokens::RIGHT_PARENTHESIS);
+                }
+            },
+
+            Expression::Heredoc { label, indentation, ref parts } => {
+                let mut is_line_start = true;
+
+                output.write(b"<<<");
+                output.write(label);
+                output.write(b"\n");
+
+                for part in parts {
+                    match *part {
+                        StringPart::Bytes(ref bytes) => {
+                            is_line_start = document_bytes(output, bytes, indentation, true, is_line_start);
+                        },
+
+                        StringPart::Expression(ref expression) => {
+                            if is_line_start {
+                                output.write(indentation);
+                                is_line_start = false;
+                            }
+
+                            output.write(b"{");
+                            operand(output, expression, OPEN);
+                            output.write(b"}");
+                        }
+                    }
+                }
+
+                document_closing(output, label, indentation);
+            },
+
+            Expression::InstanceOf { ref subject, ref class } => {
+                operand(output, subject, UNARY);
+                output.write(b" ");
+                output.write(tokens::INSTANCEOF);
+                output.write(b" ");
+                operand(output, class, POSTFIX);
+            },
+
+            Expression::InterpolatedString(ref parts) => {
+                string_parts(output, parts, b'"');
+            },
+
+            Expression::Include { ref kind, ref path } => {
+                output.write(
+                    match *kind {
+                        IncludeKind::Include     => tokens::INCLUDE,
+                        IncludeKind::IncludeOnce => tokens::INCLUDE_ONCE,
+                        IncludeKind::Require     => tokens::REQUIRE,
+                        IncludeKind::RequireOnce => tokens::REQUIRE_ONCE
+                    }
+                );
+                output.write(b" ");
+                operand(output, path, OPEN);
+            },
+
+            Expression::Isset(ref expressions) => {
+                list(output, b"isset(", expressions, b")", false, |output, expression| operand(output, expression, OPEN));
+            },
+
+            Expression::List(ref items) => {
+                list_items(output, b"list(", items, b")");
+            },
+
+            Expression::Literal(ref literal) => {
+                literal.print(output);
+            },
+
+            Expression::MagicConstant(ref constant) => {
+                output.write(
+                    match *constant {
+                        MagicConstant::Class     => tokens::MAGIC_CLASS,
+                        MagicConstant::Directory => tokens::MAGIC_DIRECTORY,
+                        MagicConstant::File      => tokens::MAGIC_FILE,
+                        MagicConstant::Function  => tokens::MAGIC_FUNCTION,
+                        MagicConstant::Line      => tokens::MAGIC_LINE,
+                        MagicConstant::Method    => tokens::MAGIC_METHOD,
+                        MagicConstant::Namespace => tokens::MAGIC_NAMESPACE,
+                        MagicConstant::Trait     => tokens::MAGIC_TRAIT
+                    }
+                );
+            },
+
+            Expression::Match { ref subject, ref arms } => {
+                output.write(tokens::MATCH);
+                output.write(b" (");
+                operand(output, subject, OPEN);
+                output.write(b") {");
+                output.level += 1;
+
+                for arm in arms {
+                    output.newline();
+                    arm.print(output);
+                    output.write(tokens::COMMA);
+                }
+
+                output.level -= 1;
+                output.newline();
+                output.write(tokens::RIGHT_CURLY_BRACKET);
+            },
+
+            Expression::MethodCall { ref object, ref method, arguments: ref call_arguments } => {
+                operand(output, object, POSTFIX);
+                output.write(tokens::DYNAMIC_CALL);
+                member(output, method);
+                arguments(output, call_arguments);
+            },
+
+            Expression::Name(ref name) => {
+                name.print(output);
+            },
+
+            Expression::Negation(ref negation_operand) => {
+                let rendered = output.render(|output| operand(output, negation_operand, UNARY));
+
+                output.write(tokens::SUBTRACT);
+
+                // `--` is the decrement operator.
+                if rendered.starts_with(tokens::SUBTRACT) {
+                    output.write(b" ");
+                }
+
+                output.write(&rendered);
+            },
+
+            Expression::New { ref class, arguments: ref new_arguments } => {
+                output.write(tokens::NEW);
+                output.write(b" ");
+                operand(output, class, POSTFIX);
+                arguments(output, new_arguments);
+            },
+
+            Expression::NullsafeMethodCall { ref object, ref method, arguments: ref call_arguments } => {
+                operand(output, object, POSTFIX);
+                output.write(tokens::NULLSAFE_DYNAMIC_CALL);
+                member(output, method);
+                arguments(output, call_arguments);
+            },
+
+            Expression::NullsafePropertyFetch { ref object, ref property } => {
+                operand(output, object, POSTFIX);
+                output.write(tokens::NULLSAFE_DYNAMIC_CALL);
+                member(output, property);
+            },
+
+            Expression::PostDecrement(ref expression) => {
+                operand(output, expression, POSTFIX);
+                output.write(tokens::DECREMENT);
+            },
+
+            Expression::PostIncrement(ref expression) => {
+                operand(output, expression, POSTFIX);
+                output.write(tokens::INCREMENT);
+            },
+
+            Expression::Power { ref base, ref exponent } => {
+                operand(output, base, POWER_OPERAND);
+                output.write(b" ");
+                output.write(tokens::POW);
+                output.write(b" ");
+                operand(output, exponent, UNARY);
+            },
+
+            Expression::PreDecrement(ref expression) => {
+                output.write(tokens::DECREMENT);
+                operand(output, expression, POSTFIX);
+            },
+
+            Expression::PreIncrement(ref expression) => {
+                output.write(tokens::INCREMENT);
+                operand(output, expression, POSTFIX);
+            },
+
+            Expression::Print(ref expression) => {
+                output.write(tokens::PRINT);
+                output.write(b" ");
+                operand(output, expression, OPEN);
+            },
+
+            Expression::PropertyFetch { ref object, ref property } => {
+                operand(output, object, POSTFIX);
+                output.write(tokens::DYNAMIC_CALL);
+                member(output, property);
+            },
+
+            Expression::Reference(ref expression) => {
+                output.write(tokens::REFERENCE);
+                operand(output, expression, POSTFIX);
+            },
+
+            Expression::RelativeScope(ref scope) => {
+                output.write(
+                    match *scope {
+                        RelativeScope::Current => tokens::SELF,
+                        RelativeScope::Parent  => tokens::PARENT,
+                        RelativeScope::Static  => tokens::STATIC
+                    }
+                );
+            },
+
+            Expression::ShellExec(ref parts) => {
+                string_parts(output, parts, b'`');
+            },
+
+            Expression::ShortList(ref items) => {
+                list_items(output, tokens::LEFT_SQUARE_BRACKET, items, tokens::RIGHT_SQUARE_BRACKET);
+            },
+
+            Expression::StaticFetch { ref class, member: ref class_member } => {
+                operand(output, class, POSTFIX);
+                output.write(tokens::STATIC_CALL);
+                member(output, class_member);
+            },
+
+            Expression::StaticMethodCall { ref class, ref method, arguments: ref call_arguments } => {
+                operand(output, class, POSTFIX);
+                output.write(tokens::STATIC_CALL);
+                member(output, method);
+                arguments(output, call_arguments);
+            },
+
+            Expression::Throw(ref expression) => {
+                output.write(tokens::THROW);
+                output.write(b" ");
+                operand(output, expression, OPEN);
+            },
+
+            Expression::Unset(ref expressions) => {
+                list(output, b"unset(", expressions, b")", false, |output, expression| operand(output, expression, OPEN));
+            },
+
+            Expression::Variable(ref variable) => {
+                variable.print(output);
+            },
+
+            Expression::Yield { ref key, ref value } => {
+                output.write(tokens::YIELD);
+
+                if let Some(ref key) = *key {
+                    output.write(b" ");
+                    operand(output, key, ASSIGNMENT);
+                    output.write(b" =>");
+                }
+
+                if let Some(ref value) = *value {
+                    output.write(b" ");
+                    operand(output, value, OPEN);
+                }
+            },
+
+            Expression::YieldFrom(ref expression) => {
+                output.write(tokens::YIELD);
+                output.write(b" from ");
+                operand(output, expression, OPEN);
+            }
+        }
+    }
+}
+
+impl<'a> Print for Catch<'a> {
+    fn print(&self, output: &mut Output) {
+        output.write(tokens::CATCH);
+        output.write(b" (");
+        join(output, &self.types, tokens::BITWISE_OR, |output, name| name.print(output));
+
+        if let Some(ref variable) = self.variable {
+            output.write(b" ");
+            variable.print(output);
+        }
+
+        output.write(b") ");
+        block(output, &self.body);
+    }
+}
+
+impl<'a> Print for ClassMember<'a> {
+    fn print(&self, output: &mut Output) {
+        match *self {
+            ClassMember::Case { attributes: ref case_attributes, name, ref value } => {
+                attributes(output, case_attributes, false);
+                output.write(tokens::CASE);
+                output.write(b" ");
+                output.write(name);
+
+                if let Some(ref value) = *value {
+                    output.write(b" = ");
+                    value.print(output);
+                }
+
+                output.write(tokens::SEMICOLON);
+            },
+
+            ClassMember::Constant { ref doc_comment, attributes: ref constant_attributes, visibility: constant_visibility, is_final, ref ty, constants: ref class_constants } => {
+                declaration_header(output, doc_comment, constant_attributes);
+
+                if is_final {
+                    output.write(tokens::FINAL);
+                    output.write(b" ");
+                }
+
+                output.write(visibility(constant_visibility));
+                output.write(b" ");
+                output.write(tokens::CONST);
+                output.write(b" ");
+
+                if let Some(ref ty) = *ty {
+                    ty.print(output);
+                    output.write(b" ");
+                }
+
+                constants(output, class_constants);
+                output.write(tokens::SEMICOLON);
+            },
+
+            ClassMember::Method(ref method) => {
+                method.print(output);
+            },
+
+            ClassMember::Property { ref doc_comment, attributes: ref property_attributes, visibility: property_visibility, set_visibility: property_set_visibility, is_static, is_readonly, ref ty, ref properties, ref hooks } => {
+                declaration_header(output, doc_comment, property_attributes);
+                output.write(visibility(property_visibility));
+
+                if let Some(property_set_visibility) = property_set_visibility {
+                    output.write(b" ");
+                    output.write(set_visibility(property_set_visibility));
+                }
+
+                if is_static {
+                    output.write(b" ");
+                    output.write(tokens::STATIC);
+                }
+
+                if is_readonly {
+                    output.write(b" ");
+                    output.write(tokens::READONLY);
+                }
+
+                if let Some(ref ty) = *ty {
+                    output.write(b" ");
+                    ty.print(output);
+                }
+
+                output.write(b" ");
+                join(
+                    output,
+                    properties,
+                    b", ",
+                    |output, (name, value)| {
+                        name.print(output);
+
+                        if let Some(ref value) = *value {
+                            output.write(b" = ");
+                            value.print(output);
+                        }
+                    }
+                );
+
+                if hooks.is_empty() {
+                    output.write(tokens::SEMICOLON);
+                } else {
+                    output.write(b" ");
+                    property_hooks(output, hooks);
+                }
+            },
+
+            ClassMember::TraitUse { ref traits, ref adaptations } => {
+                output.write(tokens::USE);
+                output.write(b" ");
+                names(output, traits);
+
+                if adaptations.is_empty() {
+                    output.write(tokens::SEMICOLON);
+                } else {
+                    output.write(b" {");
+                    output.level += 1;
+
+                    for adaptation in adaptations {
+                        output.newline();
+                        adaptation.print(output);
+                    }
+
+                    output.level -= 1;
+                    output.newline();
+                    output.write(tokens::RIGHT_CURLY_BRACKET);
+                }
+            }
+        }
+    }
+}
+
+impl Print for DeclareDirective {
+    fn print(&self, output: &mut Output) {
+        match *self {
+            DeclareDirective::Encoding(ref encoding) => {
+                output.write(tokens::ENCODING);
+                output.write(tokens::ASSIGN);
+                Literal::String(encoding.clone()).print(output);
+            },
+
+            DeclareDirective::StrictTypes(is_strict) => {
+                output.write(tokens::STRICT_TYPES);
+                output.write(tokens::ASSIGN);
+                output.write(if is_strict { b"1" } else { b"0" });
+            },
+
+            DeclareDirective::Ticks(ticks) => {
+                output.write(tokens::TICKS);
+                output.write(tokens::ASSIGN);
+                output.write(ticks.to_string().as_bytes());
+            }
+        }
+    }
+}
+
+impl<'a> Print for Method<'a> {
+    fn print(&self, output: &mut Output) {
+        declaration_header(output, &self.doc_comment, &self.attributes);
+
+        if self.modifiers.is_final() {
+            output.write(tokens::FINAL);
+            output.write(b" ");
+        }
+
+        if self.modifiers.is_abstract() {
+            output.write(tokens::ABSTRACT);
+            output.write(b" ");
+        }
+
+        output.write(visibility(self.modifiers.visibility()));
+        output.write(b" ");
+
+        if self.modifiers.is_static() {
+            output.write(tokens::STATIC);
+            output.write(b" ");
+        }
+
+        output.write(tokens::FUNCTION);
+        output.write(b" ");
+
+        if self.returns_reference {
+            output.write(tokens::REFERENCE);
+        }
+
+        output.write(self.name);
+        parameters(output, &self.parameters);
+        return_type(output, &self.return_type);
+
+        match self.body {
+            Some(ref body) => {
+                output.newline();
+                block(output, body);
+            },
+
+            None => {
+                output.write(tokens::SEMICOLON);
+            }
+        }
+    }
+}
+
+/// Print property hooks, one per line, between curly brackets.
+fn property_hooks(output: &mut Output, hooks: &[PropertyHook]) {
+    output.write(tokens::LEFT_CURLY_BRACKET);
+    output.level += 1;
+
+    for hook in hooks {
+        output.newline();
+        hook.print(output);
+    }
+
+    output.level -= 1;
+    output.newline();
+    output.write(tokens::RIGHT_CURLY_BRACKET);
+}
+
+impl<'a> Print for PropertyHook<'a> {
+    fn print(&self, output: &mut Output) {
+        attributes(output, &self.attributes, true);
+
+        if self.is_final {
+            output.write(tokens::FINAL);
+            output.write(b" ");
+        }
+
+        if self.returns_reference {
+            output.write(tokens::REFERENCE);
+        }
+
+        output.write(
+            match self.kind {
+                PropertyHookKind::Get => tokens::GET,
+                PropertyHookKind::Set => tokens::SET
+            }
+        );
+
+        if !self.parameters.is_empty() {
+            parameters(output, &self.parameters);
+        }
+
+        match self.body {
+            PropertyHookBody::Block(ref statements) => {
+                output.write(b" ");
+                block(output, statements);
+            },
+
+            PropertyHookBody::Expression(ref expression) => {
+                output.write(b" => ");
+                operand(output, expression, OPEN);
+                output.write(tokens::SEMICOLON);
+            }
+        }
+    }
+}
+
+impl<'a> Print for SwitchCase<'a> {
+    fn print(&self, output: &mut Output) {
+        match self.condition {
+            Some(ref condition) => {
+                output.write(tokens::CASE);
+                output.write(b" ");
+                operand(output, condition, OPEN);
+            },
+
+            None => {
+                output.write(tokens::DEFAULT);
+            }
+        }
+
+        output.write(tokens::CASE_SEPARATOR);
+        output.level += 1;
+
+        for statement in &self.body {
+            output.newline();
+            statement.print(output);
+        }
+
+        output.level -= 1;
+    }
+}
+
+impl<'a> Print for TraitAdaptation<'a> {
+    fn print(&self, output: &mut Output) {
+        match *self {
+            TraitAdaptation::Alias { ref trait_name, method, visibility: alias_visibility, alias } => {
+                if let Some(ref trait_name) = *trait_name {
+                    trait_name.print(output);
+                    output.write(tokens::STATIC_CALL);
+                }
+
+                output.write(method);
+                output.write(b" ");
+                output.write(tokens::AS);
+
+                if let Some(alias_visibility) = alias_visibility {
+                    output.write(b" ");
+                    output.write(visibility(alias_visibility));
+                }
+
+                if let Some(alias) = alias {
+                    output.write(b" ");
+                    output.write(alias);
+                }
+            },
+
+            TraitAdaptation::Insteadof { ref trait_name, method, ref excluded } => {
+                trait_name.print(output);
+                output.write(tokens::STATIC_CALL);
+                output.write(method);
+                output.write(b" ");
+                output.write(tokens::INSTEADOF);
+                output.write(b" ");
+                names(output, excluded);
+            }
+        }
+
+        output.write(tokens::SEMICOLON);
+    }
+}
+
+impl<'a> Print for Use<'a> {
+    fn print(&self, output: &mut Output) {
+        self.name.print(output);
+
+        if let Some(alias) = self.alias {
+            output.write(b" ");
+            output.write(tokens::AS);
+            output.write(b" ");
+            output.write(alias);
+        }
+    }
+}
+
+impl<'a> Print for Statement<'a> {
+    fn print(&self, output: &mut Output) {
+        match *self {
+            Statement::Block(ref statements) => {
+                block(output, statements);
+            },
+
+            Statement::Break(level) => {
+                jump(output, tokens::BREAK, level);
+            },
+
+            Statement::Class { ref doc_comment, attributes: ref class_attributes, is_abstract, is_final, is_readonly, name, ref extends, ref implements, ref members } => {
+                declaration_header(output, doc_comment, class_attributes);
+
+                if is_abstract {
+                    output.write(tokens::ABSTRACT);
+                    output.write(b" ");
+                }
+
+                if is_final {
+                    output.write(tokens::FINAL);
+                    output.write(b" ");
+                }
+
+                if is_readonly {
+                    output.write(tokens::READONLY);
+                    output.write(b" ");
+                }
+
+                output.write(tokens::CLASS);
+                output.write(b" ");
+                output.write(name);
+
+                if let Some(ref extends) = *extends {
+                    output.write(b" ");
+                    output.write(tokens::EXTENDS);
+                    output.write(b" ");
+                    extends.print(output);
+                }
+
+                if !implements.is_empty() {
+                    output.write(b" ");
+                    output.write(tokens::IMPLEMENTS);
+                    output.write(b" ");
+                    names(output, implements);
+                }
+
+                class_body(output, members);
+            },
+
+            Statement::Constant(ref statement_constants) => {
+                output.write(tokens::CONST);
+                output.write(b" ");
+                constants(output, statement_constants);
+                output.write(tokens::SEMICOLON);
+            },
+
+            Statement::Continue(level) => {
+                jump(output, tokens::CONTINUE, level);
+            },
+
+            Statement::Declare { ref directives, body: ref declare_body } => {
+                output.write(tokens::DECLARE);
+                output.write(tokens::LEFT_PARENTHESIS);
+                join(output, directives, b", ", |output, directive| directive.print(output));
+                output.write(tokens::RIGHT_PARENTHESIS);
+
+                match *declare_body {
+                    Some(ref declare_body) => body(output, declare_body),
+                    None => output.write(tokens::SEMICOLON)
+                }
+            },
+
+            Statement::DoWhile { body: ref do_body, ref condition } => {
+                output.write(tokens::DO);
+                body(output, do_body);
+                clause_separator(output, do_body);
+                output.write(tokens::WHILE);
+                output.write(b" (");
+                operand(output, condition, OPEN);
+                output.write(b");");
+            },
+
+            Statement::Echo(ref expressions) => {
+                output.write(tokens::ECHO);
+                output.write(b" ");
+                join(output, expressions, b", ", |output, expression| operand(output, expression, OPEN));
+                output.write(tokens::SEMICOLON);
+            },
+
+            Statement::Empty => {
+                output.write(tokens::SEMICOLON);
+            },
+
+            Statement::Enum { ref doc_comment, attributes: ref enum_attributes, name, ref backing_type, ref implements, ref members } => {
+                declaration_header(output, doc_comment, enum_attributes);
+                output.write(tokens::ENUM);
+                output.write(b" ");
+                output.write(name);
+
+                if let Some(ref backing_type) = *backing_type {
+                    output.write(tokens::ENUM_BACKING_TYPE);
+                    output.write(b" ");
+                    backing_type.print(output);
+                }
+
+                if !implements.is_empty() {
+                    output.write(b" ");
+                    output.write(tokens::IMPLEMENTS);
+                    output.write(b" ");
+                    names(output, implements);
+                }
+
+                class_body(output, members);
+            },
+
+            Statement::Error(bytes) => {
+                output.write(bytes);
+            },
+
+            Statement::Expression(ref expression) => {
+                operand(output, expression, OPEN);
+                output.write(tokens::SEMICOLON);
+            },
+
+            Statement::For { ref init, ref condition, ref step, body: ref for_body } => {
+                let expressions = |output: &mut Output, expressions: &[Spanned<Expression>]| {
+                    join(output, expressions, b", ", |output, expression| operand(output, expression, OPEN));
+                };
+
+                output.write(tokens::FOR);
+                output.write(b" (");
+                expressions(output, init);
+                output.write(tokens::SEMICOLON);
+
+                if !condition.is_empty() {
+                    output.write(b" ");
+                    expressions(output, condition);
+                }
+
+                output.write(tokens::SEMICOLON);
+
+                if !step.is_empty() {
+                    output.write(b" ");
+                    expressions(output, step);
+                }
+
+                output.write(tokens::RIGHT_PARENTHESIS);
+                body(output, for_body);
+            },
+
+            Statement::Foreach { ref subject, ref key, ref value, is_reference, body: ref foreach_body } => {
+                output.write(tokens::FOREACH);
+                output.write(b" (");
+                operand(output, subject, OPEN);
+                output.write(b" ");
+                output.write(tokens::AS);
+                output.write(b" ");
+
+                if let Some(ref key) = *key {
+                    operand(output, key, PRIMARY);
+                    output.write(b" => ");
+                }
+
+                if is_reference {
+                    output.write(tokens::REFERENCE);
+                }
+
+                operand(output, value, PRIMARY);
+                output.write(tokens::RIGHT_PARENTHESIS);
+                body(output, foreach_body);
+            },
+
+            Statement::Function { ref doc_comment, attributes: ref function_attributes, returns_reference, name, parameters: ref function_parameters, return_type: ref function_return_type, body: ref function_body } => {
+                declaration_header(output, doc_comment, function_attributes);
+                output.write(tokens::FUNCTION);
+                output.write(b" ");
+
+                if returns_reference {
+                    output.write(tokens::REFERENCE);
+                }
+
+                output.write(name);
+                parameters(output, function_parameters);
+                return_type(output, function_return_type);
+                output.newline();
+                block(output, function_body);
+            },
+
+            Statement::Global(ref variables) => {
+                output.write(tokens::GLOBAL);
+                output.write(b" ");
+                join(output, variables, b", ", |output, variable| variable.print(output));
+                output.write(tokens::SEMICOLON);
+            },
+
+            Statement::Goto(label) => {
+                output.write(tokens::GOTO);
+                output.write(b" ");
+                output.write(label);
+                output.write(tokens::SEMICOLON);
+            },
+
+            Statement::HaltCompiler { data, .. } => {
+                output.write(tokens::HALT_COMPILER);
+                output.write(b"();");
+                output.write(data);
+            },
+
+            Statement::If { ref condition, ref then, ref elseifs, ref otherwise } => {
+                output.write(tokens::IF);
+                output.write(b" (");
+                operand(output, condition, OPEN);
+                output.write(tokens::RIGHT_PARENTHESIS);
+                body(output, then);
+
+                let mut previous: &Statement = then;
+
+                for (elseif_condition, elseif_body) in elseifs {
+                    clause_separator(output, previous);
+                    output.write(tokens::ELSEIF);
+                    output.write(b" (");
+                    operand(output, elseif_condition, OPEN);
+                    output.write(tokens::RIGHT_PARENTHESIS);
+                    body(output, elseif_body);
+                    previous = elseif_body;
+                }
+
+                if let Some(ref otherwise) = *otherwise {
+                    clause_separator(output, previous);
+                    output.write(tokens::ELSE);
+                    body(output, otherwise);
+                }
+            },
+
+            Statement::InlineHtml(html) => {
+                output.write(tokens::CLOSE_TAG);
+
+                if html.starts_with(b"\n") || html.starts_with(b"\r") {
+                    output.write(b"\n");
+                }
+
+                output.write(html);
+                output.write(tokens::OPEN_TAG);
+            },
+
+            Statement::Interface { ref doc_comment, attributes: ref interface_attributes, name, ref extends, ref members } => {
+                declaration_header(output, doc_comment, interface_attributes);
+                output.write(tokens::INTERFACE);
+                output.write(b" ");
+                output.write(name);
+
+                if !extends.is_empty() {
+                    output.write(b" ");
+                    output.write(tokens::EXTENDS);
+                    output.write(b" ");
+                    names(output, extends);
+                }
+
+                class_body(output, members);
+            },
+
+            Statement::Label(label) => {
+                output.write(label);
+                output.write(tokens::LABEL_SEPARATOR);
+            },
+
+            Statement::Namespace { ref name, body: ref namespace_body } => {
+                output.write(tokens::NAMESPACE);
+
+                if let Some(ref name) = *name {
+                    output.write(b" ");
+                    name.print(output);
+                }
+
+                match *namespace_body {
+                    Some(ref statements) => {
+                        output.write(b" ");
+                        block(output, statements);
+                    },
+
+                    None => {
+                        output.write(tokens::SEMICOLON);
+                    }
+                }
+            },
+
+            Statement::Return(ref value) => {
+                output.write(tokens::RETURN);
+
+                if let Some(ref value) = *value {
+                    output.write(b" ");
+                    operand(output, value, OPEN);
+                }
+
+                output.write(tokens::SEMICOLON);
+            },
+
+            Statement::StaticVariables(ref variables) => {
+                output.write(tokens::STATIC);
+                output.write(b" ");
+                join(
+                    output,
+                    variables,
+                    b", ",
+                    |output, (variable, value)| {
+                        variable.print(output);
+
+                        if let Some(ref value) = *value {
+                            output.write(b" = ");
+                            operand(output, value, OPEN);
+                        }
+                    }
+                );
+                output.write(tokens::SEMICOLON);
+            },
+
+            Statement::Switch { ref subject, ref cases } => {
+                output.write(tokens::SWITCH);
+                output.write(b" (");
+                operand(output, subject, OPEN);
+                output.write(b") {");
+                output.level += 1;
+
+                for case in cases {
+                    output.newline();
+                    case.print(output);
+                }
+
+                output.level -= 1;
+                output.newline();
+                output.write(tokens::RIGHT_CURLY_BRACKET);
+            },
+
+            Statement::Trait { ref doc_comment, attributes: ref trait_attributes, name, ref members } => {
+                declaration_header(output, doc_comment, trait_attributes);
+                output.write(tokens::TRAIT);
+                output.write(b" ");
+                output.write(name);
+                class_body(output, members);
+            },
+
+            Statement::Try { body: ref try_body, ref catches, ref finally } => {
+                output.write(tokens::TRY);
+                output.write(b" ");
+                block(output, try_body);
+
+                for catch in catches {
+                    output.write(b" ");
+                    catch.print(output);
+                }
+
+                if let Some(ref finally) = *finally {
+                    output.write(b" ");
+                    output.write(tokens::FINALLY);
+                    output.write(b" ");
+                    block(output, finally);
+                }
+            },
+
+            Statement::Use(ref statement_uses) => {
+                uses(output, statement_uses);
+            },
+
+            Statement::While { ref condition, body: ref while_body } => {
+                output.write(tokens::WHILE);
+                output.write(b" (");
+                operand(output, condition, OPEN);
+                output.write(tokens::RIGHT_PARENTHESIS);
+                body(output, while_body);
+            }
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        Indentation,
+        Printer
+    };
+    use super::super::ast::{
+        Expression,
+        Literal,
+        Statement,
+        Variable
+    };
+    use super::super::internal::Result;
+    use super::super::rules::script::script;
+    use super::super::span::Spanned;
+
+    fn statements(input: &[u8]) -> Vec<Spanned<Statement>> {
+        match script(input) {
+            Result::Done(_, statements) => statements,
+            _ => panic!("The script is invalid.")
+        }
+    }
+
+    fn round_trip(input: &[u8]) -> Vec<u8> {
+        let ast    = statements(input);
+        let output = Printer::new().print_script(&ast);
+
+        assert_eq!(statements(&output), ast);
+
+        output
+    }
+
+    #[test]
+    fn case_print_script() {
+        let input  = b"<?php namespace N; use A\\B as C, D; if ($a) { echo 1, 2; } elseif ($b) print 'c'; else { while (true) break 2; }";
+        let output = b"<?php\nnamespace N;\nuse A\\B as C, D;\nif ($a) {\n    echo 1, 2;\n} elseif ($b)\n    print 'c';\nelse {\n    while (true)\n        break 2;\n}\n";
+
+        assert_eq!(round_trip(input), output.to_vec());
+    }
+
+    #[test]
+    fn case_default_printer() {
+        let input = b"<?php if ($a) { echo 1; }";
+        let ast   = statements(input);
+
+        assert_eq!(Printer::default().print_script(&ast), Printer::new().print_script(&ast));
+    }
+
+    #[test]
+    fn case_print_declarations() {
+        let input = b"<?php
+use \\A\\B\\{function c, const D, E as F};
+/** Doc. */
+#[A(1, b: 2)]
+final class C extends P implements I, J {
+    use T, U { T::m insteadof U; m as protected n; }
+    const X = 1, Y = 'y';
+    public static ?int $p = 2, $q;
+    private(set) string $r { get => $this->r; set(string $v) { $this->r = $v; } }
+    abstract protected function &m(int|Foo $a, ...$b): static;
+    public function __construct(public readonly array $c = [1, 2]) { parent::__construct(); }
+}
+interface I extends J, K { function m(); }
+trait T { public $x; }
+enum E: string implements I { case A = 'a'; case B = 'b'; }
+function f(&$x, ?\\N\\T $y = null): never { throw new \\Exception(); }";
+
+        round_trip(input);
+    }
+
+    #[test]
+    fn case_print_control_structures() {
+        let input = b"<?php
+for ($i = 0, $j = 1; $i; ++$i) { continue; }
+for (;;);
+foreach ($a as $k => &$v) { unset($v); }
+do { $i--; } while ($i);
+switch ($x) { case 1: case 2: f(); break; default: g(); }
+try { f(); } catch (A|B $e) { g(); } catch (C) { } finally { h(); }
+declare(strict_types=1);
+declare(ticks=1) { f(); }
+static $s = 1, $t;
+global $g, $h;
+goto end;
+end:
+const Z = 1;
+return;";
+
+        round_trip(input);
+    }
+
+    #[test]
+    fn case_print_expressions() {
+        let input = b"<?php
+$a = $b = [1, 'k' => [2], $c];
+list($x, , $y) = $z;
+[, $x, [$y, , $w]] = $z;
+$f = static fn &(int $x): int => $x ** 2;
+$g = function ($x) use ($y, &$z): void { $z = f($x, $y); return; };
+$gg = array_map(fn ($x) => $x, [function () { yield 1; }]);
+$h = match (true) { 1, 2 => 'a', default => 'b' };
+$i = new class(1) extends A implements B { const C = 2; public $d; public function e() { return $this->d; } };
+$j = \"a $b {$c->d} \\n \\\" \\\\\";
+$k = `ls $dir`;
+$l = $m?->n->{$o}::$p::{'q'}::r(...);
+$s = (int) -$t instanceof U;
+$v = clone clone $w;
+$x = -(-1);
+$y = [isset($a, $b[0]), empty($c), eval('1'), exit(1)];
+$z = include 'a.php';
+$$a = ${'b'};
+yield $k => $v;
+yield from f();
+echo __LINE__, self::class, static::X, 1.5, 1e1000;
+f(a: 1, ...$b);";
+
+        round_trip(input);
+    }
+
+    #[test]
+    fn case_print_heredoc_and_nowdoc() {
+        let input = b"<?php\n$a = <<<EOT\n    a \\$ \\\\ {$b}\\t\n\n      $c[0]\n    EOT;\n$d = <<<'EOT'\n\ta $b \\\n\n\t\tc\n\tEOT;\n$e = [<<<EOT\nEOT, <<<'EOT'\nEOT];\n";
+        let output = b"<?php\n$a = <<<EOT\n    a \\$ \\\\ {$b}\\t\n\n      {$c[0]}\n    EOT;\n$d = <<<'EOT'\n\ta $b \\\n\n\t\tc\n\tEOT;\n$e = [\n    <<<EOT\n\nEOT,\n    <<<'EOT'\n\nEOT,\n];\n";
+
+        assert_eq!(round_trip(input), output.to_vec());
+    }
+
+    #[test]
+    fn case_print_new_with_a_fetch_chain() {
+        let input  = b"<?php new $this->cls; new static::$cls['k']($a);";
+        let output = b"<?php\nnew $this->cls();\nnew static::$cls['k']($a);\n";
+
+        assert_eq!(round_trip(input), output.to_vec());
+    }
+
+    #[test]
+    fn case_print_call_of_a_fetched_member() {
+        let input  = b"<?php $o->m(); ($o->m)(); $o?->m(); ($o?->m)(); Foo::$bar(); (Foo::$bar)();";
+        let output = b"<?php\n$o->m();\n($o->m)();\n$o?->m();\n($o?->m)();\nFoo::$bar();\n(Foo::$bar)();\n";
+
+        assert_eq!(round_trip(input), output.to_vec());
+    }
+
+    #[test]
+    fn case_print_halt_compiler_data_verbatim() {
+        let input = b"<?php\nf();\n__halt_compiler();foo\nbar";
+
+        assert_eq!(round_trip(input), input.to_vec());
+    }
+
+    #[test]
+    fn case_print_halt_compiler_without_data() {
+        let input = b"<?php\n__halt_compiler();";
+
+        assert_eq!(round_trip(input), input.to_vec());
+    }
+
+    #[test]
+    fn case_print_inline_html() {
+        let input  = b"<p><?php echo 1; ?>\n</p><?php f();";
+        let output = b"<p><?php\necho 1;?></p><?php\nf();\n";
+
+        assert_eq!(round_trip(input), output.to_vec());
+    }
+
+    #[test]
+    fn case_print_echo() {
+        let input  = b"<?= $a, $b ?><?php if ($c) { echo 1, 2; }";
+        let output = b"<?php\necho $a, $b;\nif ($c) {\n    echo 1, 2;\n}\n";
+
+        assert_eq!(round_trip(input), output.to_vec());
+    }
+
+    #[test]
+    fn case_print_nested_inline_html() {
+        let input  = b"<?php foreach ($a as $b): ?>\n<li><?= $b ?></li><?php endforeach;";
+        let output = b"<?php\nforeach ($a as $b) {\n    ?><li><?php\n    echo $b;\n    ?></li><?php\n}\n";
+
+        assert_eq!(round_trip(input), output.to_vec());
+    }
+
+    #[test]
+    fn case_print_parenthesized_operands() {
+        let power = Expression::Power {
+            base    : Box::new(Spanned::from(Expression::Negation(Box::new(Spanned::from(Expression::Literal(Literal::Integer(1))))))),
+            exponent: Box::new(Spanned::from(Expression::Literal(Literal::Integer(2))))
+        };
+
+        assert_eq!(Printer::new().print(&power), b"(-1) ** 2".to_vec());
+
+        let fetch = Expression::ArrayAccess {
+            array: Box::new(
+                Spanned::from(Expression::Assignment {
+                    variable: Box::new(Spanned::from(Expression::Variable(Variable::Name(b"a")))),
+                    value   : Box::new(Spanned::from(Expression::Array(vec![])))
+                })
+            ),
+            index: Some(Box::new(Spanned::from(Expression::Literal(Literal::Integer(0)))))
+        };
+
+        assert_eq!(Printer::new().print(&fetch), b"($a = [])[0]".to_vec());
+    }
+
+    #[test]
+    fn case_print_with_indentation_and_line_width() {
+        let ast     = statements(b"<?php if ($a) { f($bbbbbbbb, $cccccccc); }");
+        let printer = Printer::new()
+            .indentation(Indentation::Tabs)
+            .line_width(20);
+        let output  = printer.print_script(&ast);
+
+        assert_eq!(output, b"<?php\nif ($a) {\n\tf(\n\t\t$bbbbbbbb,\n\t\t$cccccccc\n\t);\n}\n".to_vec());
+        assert_eq!(statements(&output), ast);
+    }
+}
@@ -0,0 +1,237 @@
+// Tagua VM
+//
+//
+// New BSD License
+//
+// Copyright © 2016-2016, Ivan Enderlin.
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//     * Redistributions of source code must retain the above copyright
+//       notice, this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright
+//       notice, this list of conditions and the following disclaimer in the
+//       documentation and/or other materials provided with the distribution.
+//     * Neither the name of the Hoa nor the names of its contributors may be
+//       used to endorse or promote products derived from this software without
+//       specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE
+// ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDERS AND CONTRIBUTORS BE
+// LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR
+// CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF
+// SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS
+// INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN
+// CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+
+//! Group of attribute rules.
+//!
+//! Attributes are introduced by PHP 8.0 and are not part of the PHP
+//! Language Specification yet. They can be attached to classes, interfaces,
+//! traits, enumerations, functions, methods, properties, parameters, class
+//! constants and enumeration cases.
+
+use super::expressions::constants::constant_arguments;
+use super::tokens::qualified_name;
+use super::super::ast::Attribute;
+use super::super::internal::{
+    fold_into_vector,
+    into_vector_mapper
+};
+use super::super::tokens;
+use super::super::version::{
+    self,
+    Version
+};
+
+named!(
+    pub attributes< Vec<Attribute> >,
+    fold_many0!(
+        first!(attribute_group),
+        Vec::new(),
+        fold_into_attributes
+    )
+);
+
+#[inline(always)]
+fn fold_into_attributes<'a>(mut accumulator: Vec<Attribute<'a>>, mut group: Vec<Attribute<'a>>) -> Vec<Attribute<'a>> {
+    accumulator.append(&mut group);
+
+    accumulator
+}
+
+named!(
+    pub attribute_group< Vec<Attribute> >,
+    cond_reduce!(
+        version::is_targeting(Version::Php80),
+        chain!(
+            tag!(tokens::ATTRIBUTE) ~
+            accumulator: map_res!(
+                first!(attribute),
+                into_vector_mapper
+            ) ~
+            result: fold_many0!(
+                preceded!(
+                    first!(tag!(tokens::COMMA)),
+                    first!(attribute)
+                ),
+                accumulator,
+                fold_into_vector
+            ) ~
+            opt!(first!(tag!(tokens::COMMA))) ~
+            first!(expect!("attribute_group", tokens::RIGHT_SQUARE_BRACKET)),
+            || { result }
+        )
+    )
+);
+
+named!(
+    attribute<Attribute>,
+    chain!(
+        name: spanned!(qualified_name) ~
+        arguments: opt!(first!(constant_arguments)),
+        || {
+            Attribute {
+                name     : name,
+                arguments: arguments.unwrap_or(vec![])
+            }
+        }
+    )
+);
+
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        attribute_group,
+        attributes
+    };
+    use super::super::super::ast::{
+        Argument,
+        Attribute,
+        Expression,
+        Literal,
+        Name
+    };
+    use super::super::super::internal::{
+        Error,
+        ErrorKind,
+        Result
+    };
+    use super::super::super::span::Spanned;
+    use super::super::super::version::{
+        self,
+        Version
+    };
+
+    #[test]
+    fn case_attribute_group_one_attribute() {
+        let input  = b"#[Foo]";
+        let output = Result::Done(
+            &b""[..],
+            vec![
+                Attribute {
+                    name     : Spanned::from(Name::Unqualified(&b"Foo"[..])),
+                    arguments: vec![]
+                }
+            ]
+        );
+
+        assert_eq!(attribute_group(input), output);
+        assert_eq!(attributes(input), output);
+    }
+
+    #[test]
+    fn case_attribute_group_many_attributes_with_arguments() {
+        let input  = b"#[ \\Foo\\Bar , Baz(1, qux: 2), ]";
+        let output = Result::Done(
+            &b""[..],
+            vec![
+                Attribute {
+                    name     : Spanned::from(Name::FullyQualified(vec![&b"Foo"[..], &b"Bar"[..]])),
+                    arguments: vec![]
+                },
+                Attribute {
+                    name     : Spanned::from(Name::Unqualified(&b"Baz"[..])),
+                    arguments: vec![
+                        Spanned::from(Argument {
+                            name       : None,
+                            is_unpacked: false,
+                            value      : Spanned::from(Expression::Literal(Literal::Integer(1)))
+                        }),
+                        Spanned::from(Argument {
+                            name       : Some(&b"qux"[..]),
+                            is_unpacked: false,
+                            value      : Spanned::from(Expression::Literal(Literal::Integer(2)))
+                        })
+                    ]
+                }
+            ]
+        );
+
+        assert_eq!(attribute_group(input), output);
+        assert_eq!(attributes(input), output);
+    }
+
+    #[test]
+    fn case_attributes_many_groups() {
+        let input  = b"#[Foo] /* bar */ #[Baz]";
+        let output = Result::Done(
+            &b""[..],
+            vec![
+                Attribute {
+                    name     : Spanned::from(Name::Unqualified(&b"Foo"[..])),
+                    arguments: vec![]
+                },
+                Attribute {
+                    name     : Spanned::from(Name::Unqualified(&b"Baz"[..])),
+                    arguments: vec![]
+                }
+            ]
+        );
+
+        assert_eq!(attributes(input), output);
+    }
+
+    #[test]
+    fn case_attributes_none() {
+        assert_eq!(attributes(b"class"), Result::Done(&b"class"[..], vec![]));
+    }
+
+    #[test]
+    fn case_invalid_attribute_group_empty() {
+        let input = b"#[]";
+
+        assert_eq!(attribute_group(input), Result::Error(Error::Code(ErrorKind::RegexpFind)));
+    }
+
+    #[test]
+    fn case_invalid_attribute_group_argument_is_not_constant() {
+        let input = b"#[Foo($bar)]";
+
+        assert_eq!(attribute_group(input), Result::Error(Error::Position(ErrorKind::Tag, &b"($bar)]"[..])));
+    }
+
+    #[test]
+    fn case_invalid_attribute_group_argument_is_unpacked() {
+        let input = b"#[Foo(...[1])]";
+
+        assert_eq!(attribute_group(input), Result::Error(Error::Position(ErrorKind::Tag, &b"(...[1])]"[..])));
+    }
+
+    #[test]
+    fn case_invalid_attribute_group_before_php_80() {
+        let input = b"#[Foo]";
+
+        version::set_target(Version::Php74);
+        let output = attribute_group(input);
+        version::set_target(version::LATEST);
+
+        assert_eq!(output, Result::Error(Error::Position(ErrorKind::CondReduce, &input[..])));
+    }
+}
@@ -35,8 +35,42 @@
 //! the [Grammar chapter, Comments
 //! section](https://github.com/php/php-langspec/blob/master/spec/19-grammar.md#comments).
 
+use std::cell::RefCell;
+use super::super::internal::{
+    Error,
+    ErrorKind,
+    Result
+};
+use super::super::tokens;
+use super::super::trivia::{
+    self,
+    CommentKind
+};
+use super::super::version::{
+    self,
+    Version
+};
+
+/// Parse a comment, and record it if the comments are being collected, see
+/// the `trivia` module.
+pub fn comment(input: &[u8]) -> Result<&[u8], &[u8]> {
+    let output = any_comment(input);
+
+    if let Result::Done(rest, content) = output {
+        let kind = if input.starts_with(b"/*") {
+            CommentKind::Delimited
+        } else {
+            CommentKind::SingleLine
+        };
+
+        trivia::record(input, content, rest, kind);
+    }
+
+    output
+}
+
 named!(
-    pub comment,
+    any_comment,
     alt!(
         comment_single_line
       | comment_delimited
@@ -46,11 +80,47 @@ named!(
 named!(
     comment_single_line,
     preceded!(
-        alt!(tag!("//") | tag!("#")),
-        re_bytes_find_static!(r"^.*?(\r\n|\r|\n|$)")
+        alt!(tag!("//") | comment_single_line_hash),
+        comment_single_line_content
     )
 );
 
+/// Since PHP 8.0, `#[` opens an attribute group instead of a comment.
+fn comment_single_line_hash(input: &[u8]) -> Result<&[u8], &[u8]> {
+    if version::is_targeting(Version::Php80) && input.starts_with(tokens::ATTRIBUTE) {
+        return Result::Error(Error::Position(ErrorKind::Tag, input));
+    }
+
+    tag!(input, "#")
+}
+
+/// The content of a single line comment runs until the end of the line,
+/// included, or until a closing tag, excluded.
+fn comment_single_line_content(input: &[u8]) -> Result<&[u8], &[u8]> {
+    let input_length = input.len();
+    let mut index    = 0;
+
+    while index < input_length {
+        let item = input[index];
+
+        if item == b'\n' {
+            return Result::Done(&input[index + 1..], &input[..index + 1]);
+        } else if item == b'\r' {
+            if index + 1 < input_length && input[index + 1] == b'\n' {
+                index += 1;
+            }
+
+            return Result::Done(&input[index + 1..], &input[..index + 1]);
+        } else if input[index..].starts_with(tokens::CLOSE_TAG) {
+            return Result::Done(&input[index..], &input[..index]);
+        }
+
+        index += 1;
+    }
+
+    Result::Done(&input[input_length..], input)
+}
+
 named!(
     comment_delimited,
     preceded!(
@@ -59,6 +129,62 @@ named!(
     )
 );
 
+thread_local!(
+    static DOC_COMMENT: RefCell<(usize, usize, Option<Vec<u8>>)> = const { RefCell::new((0, 0, None)) }
+);
+
+/// Remember the last documentation comment, i.e. `/** … */`, skipped from
+/// `input` until `rest`, so that the declaration starting at `rest` can be
+/// documented by it, see the `doc_comment` rule.
+pub fn remember_doc_comment(input: &[u8], skipped: &[&[u8]], rest: &[u8]) {
+    if skipped.is_empty() {
+        return;
+    }
+
+    let start = input.as_ptr() as usize;
+    let doc_comment = skipped
+        .iter()
+        .rev()
+        .filter_map(
+            |content| {
+                let offset = content.as_ptr() as usize - start;
+
+                if
+                    offset >= 2 &&
+                    &input[offset - 2..offset] == b"/*" &&
+                    content.len() >= 2 &&
+                    content[0] == b'*' &&
+                    (content[1] == b' ' || content[1] == b'\t' || content[1] == b'\n' || content[1] == b'\r')
+                {
+                    Some(input[offset - 2..offset + content.len() + 2].to_vec())
+                } else {
+                    None
+                }
+            }
+        )
+        .next();
+
+    DOC_COMMENT.with(|state| *state.borrow_mut() = (rest.as_ptr() as usize, rest.len(), doc_comment));
+}
+
+/// Get the documentation comment immediately preceding the input, if any.
+/// It consumes nothing.
+pub fn doc_comment(input: &[u8]) -> Result<&[u8], Option<Vec<u8>>> {
+    let doc_comment = DOC_COMMENT.with(
+        |state| {
+            let (position, length, ref doc_comment) = *state.borrow();
+
+            if position == input.as_ptr() as usize && length == input.len() {
+                doc_comment.clone()
+            } else {
+                None
+            }
+        }
+    );
+
+    Result::Done(input, doc_comment)
+}
+
 
 #[cfg(test)]
 mod tests {
@@ -72,6 +198,10 @@ mod tests {
         ErrorKind,
         Result
     };
+    use super::super::super::version::{
+        self,
+        Version
+    };
 
     #[test]
     fn case_comment_single_line_double_slash_empty() {
@@ -163,6 +293,34 @@ mod tests {
         assert_eq!(comment(input), output);
     }
 
+    #[test]
+    fn case_comment_single_line_hash_before_an_attribute() {
+        let input  = b"# [foobar]\nbazqux";
+        let output = Result::Done(&b"bazqux"[..], &b" [foobar]\n"[..]);
+
+        assert_eq!(comment_single_line(input), output);
+        assert_eq!(comment(input), output);
+    }
+
+    #[test]
+    fn case_invalid_comment_single_line_hash_is_an_attribute() {
+        let input = b"#[foobar]\nbazqux";
+
+        assert_eq!(comment_single_line(input), Result::Error(Error::Position(ErrorKind::Alt, &input[..])));
+    }
+
+    #[test]
+    fn case_comment_single_line_hash_is_an_attribute_before_php_80() {
+        let input  = b"#[foobar]\nbazqux";
+        let output = Result::Done(&b"bazqux"[..], &b"[foobar]\n"[..]);
+
+        version::set_target(Version::Php74);
+        let result = comment_single_line(input);
+        version::set_target(version::LATEST);
+
+        assert_eq!(result, output);
+    }
+
     #[test]
     fn case_comment_single_line_hash_without_line_ending() {
         let input  = b"# foobar";
@@ -181,6 +339,24 @@ mod tests {
         assert_eq!(comment(input), output);
     }
 
+    #[test]
+    fn case_comment_single_line_stops_before_a_closing_tag() {
+        let input  = b"// foo ?> bar";
+        let output = Result::Done(&b"?> bar"[..], &b" foo "[..]);
+
+        assert_eq!(comment_single_line(input), output);
+        assert_eq!(comment(input), output);
+    }
+
+    #[test]
+    fn case_comment_single_line_question_mark() {
+        let input  = b"# foo? bar>\nbaz";
+        let output = Result::Done(&b"baz"[..], &b" foo? bar>\n"[..]);
+
+        assert_eq!(comment_single_line(input), output);
+        assert_eq!(comment(input), output);
+    }
+
     #[test]
     fn case_comment_delimited_empty() {
         let input  = b"/**/xyz";
@@ -0,0 +1,834 @@
+// Tagua VM
+//
+//
+// New BSD License
+//
+// Copyright © 2016-2016, Ivan Enderlin.
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//     * Redistributions of source code must retain the above copyright
+//       notice, this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright
+//       notice, this list of conditions and the following disclaimer in the
+//       documentation and/or other materials provided with the distribution.
+//     * Neither the name of the Hoa nor the names of its contributors may be
+//       used to endorse or promote products derived from this software without
+//       specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE
+// ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDERS AND CONTRIBUTORS BE
+// LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR
+// CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF
+// SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS
+// INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN
+// CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+
+//! Group of assignment expression rules.
+//!
+//! The list of all assignment expressions is provided by the PHP Language
+//! Specification in the [Grammar chapter, Expressions
+//! section](https://github.com/php/php-langspec/blob/master/spec/19-grammar.md#assignment-operators).
+
+use std::result::Result as StdResult;
+use super::expression;
+use super::binaries::instanceof;
+use super::postfixes::postfix;
+use super::primaries::short_list;
+use super::unaries::{
+    is_modifiable,
+    modifiable_operand_mapper
+};
+use super::super::whitespaces::whitespace;
+use super::super::super::ast::Expression;
+use super::super::super::internal::Result;
+use super::super::super::span::Spanned;
+use super::super::super::tokens;
+
+/// Parse an assignment, or a `yield` expression which has the same
+/// precedence. If both fail, the error of the assignment is returned.
+pub fn assignment(input: &[u8]) -> Result<&[u8], Expression> {
+    let output = assignment_expression(input);
+
+    if let Result::Error(_) = output {
+        if let Result::Done(rest, expression) = yield_expressions(input) {
+            return Result::Done(rest, expression);
+        }
+    }
+
+    output
+}
+
+named!(
+    assignment_expression<Expression>,
+    map_res!(
+        chain!(
+            variable: spanned!(
+                alt!(
+                    instanceof
+                  | terminated!(
+                        short_list,
+                        complete!(peek!(first!(tag!(tokens::ASSIGN))))
+                    )
+                )
+            ) ~
+            value: opt!(
+                complete!(
+                    preceded!(
+                        first!(tag!(tokens::ASSIGN)),
+                        first!(
+                            expect!(
+                                "assignment_expression",
+                                &[],
+                                spanned!(
+                                    alt!(
+                                        map_res!(
+                                            preceded!(
+                                                tag!(tokens::REFERENCE),
+                                                first!(spanned!(postfix))
+                                            ),
+                                            reference_mapper
+                                        )
+                                      | assignment
+                                    )
+                                )
+                            )
+                        )
+                    )
+                )
+            ),
+            || { (variable, value) }
+        ),
+        assignment_mapper
+    )
+);
+
+named!(
+    yield_expressions<Expression>,
+    alt!(
+        yield_from_expression
+      | yield_expression
+    )
+);
+
+/// Only a modifiable storage, or the result of a call, can be assigned by
+/// reference, e.g. `$x = &$y` or `$x = &f()`, but not `$x = &new C`.
+#[inline(always)]
+fn reference_mapper<'a>(expression: Spanned<Expression<'a>>) -> StdResult<Expression<'a>, ()> {
+    match expression.node {
+        Expression::Call { .. } |
+        Expression::MethodCall { .. } |
+        Expression::NullsafeMethodCall { .. } |
+        Expression::StaticMethodCall { .. } => Ok(Expression::Reference(Box::new(expression))),
+        _ if is_modifiable(&expression) => Ok(Expression::Reference(Box::new(expression))),
+        _ => Err(())
+    }
+}
+
+#[inline(always)]
+fn assignment_mapper<'a>((variable, value): (Spanned<Expression<'a>>, Option<Spanned<Expression<'a>>>)) -> StdResult<Expression<'a>, ()> {
+    let span = variable.span;
+
+    match (variable.node, value) {
+        (Expression::ShortList(_), None) => {
+            Err(())
+        },
+
+        (variable, None) => {
+            Ok(variable)
+        },
+
+        (variable @ Expression::Array(_), Some(value)) |
+        (variable @ Expression::List(_), Some(value)) |
+        (variable @ Expression::ShortList(_), Some(value)) => {
+            destructuring_mapper(variable).map(
+                |variable| {
+                    Expression::Assignment {
+                        variable: Box::new(Spanned::new(variable, span)),
+                        value   : Box::new(value)
+                    }
+                }
+            )
+        },
+
+        (variable, Some(value)) => {
+            modifiable_operand_mapper(variable).map(
+                |variable| {
+                    Expression::Assignment {
+                        variable: Box::new(Spanned::new(variable, span)),
+                        value   : Box::new(value)
+                    }
+                }
+            )
+        }
+    }
+}
+
+named!(
+    pub yield_expression<Expression>,
+    chain!(
+        keyword!(tokens::YIELD) ~
+        pair: opt!(
+            complete!(
+                chain!(
+                    first: first!(spanned!(expression)) ~
+                    second: opt!(
+                        complete!(
+                            preceded!(
+                                first!(tag!(tokens::MAP)),
+                                first!(spanned!(expression))
+                            )
+                        )
+                    ),
+                    || { (first, second) }
+                )
+            )
+        ),
+        || { into_yield(pair) }
+    )
+);
+
+#[inline(always)]
+fn into_yield<'a>(pair: Option<(Spanned<Expression<'a>>, Option<Spanned<Expression<'a>>>)>) -> Expression<'a> {
+    match pair {
+        Some((key, Some(value))) => {
+            Expression::Yield {
+                key  : Some(Box::new(key)),
+                value: Some(Box::new(value))
+            }
+        },
+
+        Some((value, None)) => {
+            Expression::Yield {
+                key  : None,
+                value: Some(Box::new(value))
+            }
+        },
+
+        None => {
+            Expression::Yield {
+                key  : None,
+                value: None
+            }
+        }
+    }
+}
+
+named!(
+    pub yield_from_expression<Expression>,
+    map!(
+        preceded!(
+            complete!(
+                chain!(
+                    keyword!(tokens::YIELD) ~
+                    whitespace ~
+                    keyword!(tokens::FROM),
+                    || {}
+                )
+            ),
+            first!(spanned!(expression))
+        ),
+        yield_from_mapper
+    )
+);
+
+#[inline(always)]
+fn yield_from_mapper<'a>(expression: Spanned<Expression<'a>>) -> Expression<'a> {
+    Expression::YieldFrom(Box::new(expression))
+}
+
+/// Check that an array, a short list or a list can be destructured into,
+/// e.g. `[$a, [$b, &$c]]`, `[, $a]` or `['x' => $x, 'y' => $y]`. An array
+/// pattern becomes a short list, so that a pattern has a single
+/// representation whether it skips elements or not.
+#[inline(always)]
+pub fn destructuring_mapper<'a>(pattern: Expression<'a>) -> StdResult<Expression<'a>, ()> {
+    if is_destructuring(&pattern) {
+        Ok(into_pattern(pattern))
+    } else {
+        Err(())
+    }
+}
+
+/// Turn the arrays of a pattern, possibly nested, into short lists.
+fn into_pattern<'a>(pattern: Expression<'a>) -> Expression<'a> {
+    let items = match pattern {
+        Expression::Array(pairs) => pairs.into_iter().map(Some).collect(),
+        Expression::ShortList(items) => items,
+        pattern => return pattern
+    };
+
+    Expression::ShortList(
+        items
+            .into_iter()
+            .map(|item| item.map(|(key, value)| (key, value.map(into_pattern))))
+            .collect()
+    )
+}
+
+/// A pattern has at least one element. Each value is a modifiable operand,
+/// possibly by reference, or a nested pattern of the same kind: arrays and
+/// short lists can be mixed, but not with lists.
+fn is_destructuring(pattern: &Expression) -> bool {
+    let pairs: Vec<&(Option<Spanned<Expression>>, Spanned<Expression>)> = match *pattern {
+        Expression::Array(ref pairs) => pairs.iter().collect(),
+        Expression::List(ref items) |
+        Expression::ShortList(ref items) => items.iter().filter_map(|item| item.as_ref()).collect(),
+        _ => return false
+    };
+
+    if pairs.is_empty() {
+        return false;
+    }
+
+    pairs.iter().all(
+        |&(_, value)| {
+            match (pattern, &value.node) {
+                (_, Expression::Reference(operand)) => is_modifiable(operand),
+                (&Expression::Array(_), &Expression::Array(_)) |
+                (&Expression::Array(_), &Expression::ShortList(_)) |
+                (&Expression::ShortList(_), &Expression::Array(_)) |
+                (&Expression::ShortList(_), &Expression::ShortList(_)) |
+                (&Expression::List(_), &Expression::List(_)) => is_destructuring(value),
+                _ => is_modifiable(value)
+            }
+        }
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        assignment,
+        yield_expression,
+        yield_from_expression
+    };
+    use super::super::expression;
+    use super::super::super::super::ast::{
+        Expression,
+        Literal,
+        Name,
+        Variable
+    };
+    use super::super::super::super::internal::{
+        Error,
+        ErrorKind,
+        Result
+    };
+    use super::super::super::super::macros::ErrorKindCustom;
+    use super::super::super::super::span::Spanned;
+
+    #[test]
+    fn case_assignment() {
+        let input  = b"$foo = 42";
+        let output = Result::Done(
+            &b""[..],
+            Expression::Assignment {
+                variable: Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..])))),
+                value   : Box::new(Spanned::from(Expression::Literal(Literal::Integer(42i64))))
+            }
+        );
+
+        assert_eq!(assignment(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_assignment_is_right_associative() {
+        let input  = b"$foo = /* bar */ $bar = 42";
+        let output = Result::Done(
+            &b""[..],
+            Expression::Assignment {
+                variable: Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..])))),
+                value   : Box::new(
+                    Spanned::from(Expression::Assignment {
+                        variable: Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"bar"[..])))),
+                        value   : Box::new(Spanned::from(Expression::Literal(Literal::Integer(42i64))))
+                    })
+                )
+            }
+        );
+
+        assert_eq!(assignment(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_assignment_by_reference() {
+        let input  = b"$foo = & $bar";
+        let output = Result::Done(
+            &b""[..],
+            Expression::Assignment {
+                variable: Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..])))),
+                value   : Box::new(
+                    Spanned::from(Expression::Reference(
+                        Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"bar"[..]))))
+                    ))
+                )
+            }
+        );
+
+        assert_eq!(assignment(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_assignment_by_reference_of_a_call() {
+        let input  = b"$foo->bar =& baz()";
+        let output = Result::Done(
+            &b""[..],
+            Expression::Assignment {
+                variable: Box::new(
+                    Spanned::from(Expression::PropertyFetch {
+                        object  : Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..])))),
+                        property: Box::new(Spanned::from(Expression::Name(Name::Unqualified(&b"bar"[..]))))
+                    })
+                ),
+                value   : Box::new(
+                    Spanned::from(Expression::Reference(
+                        Box::new(
+                            Spanned::from(Expression::Call {
+                                callee   : Box::new(Spanned::from(Expression::Name(Name::Unqualified(&b"baz"[..])))),
+                                arguments: vec![]
+                            })
+                        )
+                    ))
+                )
+            }
+        );
+
+        assert_eq!(assignment(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_assignment_to_an_array_element() {
+        let input  = b"$foo[] = 42";
+        let output = Result::Done(
+            &b""[..],
+            Expression::Assignment {
+                variable: Box::new(
+                    Spanned::from(Expression::ArrayAccess {
+                        array: Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..])))),
+                        index: None
+                    })
+                ),
+                value   : Box::new(Spanned::from(Expression::Literal(Literal::Integer(42i64))))
+            }
+        );
+
+        assert_eq!(assignment(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_assignment_destructuring_an_array() {
+        let input  = b"[$foo, &$bar] = $baz";
+        let output = Result::Done(
+            &b""[..],
+            Expression::Assignment {
+                variable: Box::new(
+                    Spanned::from(Expression::ShortList(vec![
+                        Some((None, Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..]))))),
+                        Some((None, Spanned::from(Expression::Reference(Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"bar"[..]))))))))
+                    ]))
+                ),
+                value   : Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"baz"[..]))))
+            }
+        );
+
+        assert_eq!(assignment(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_assignment_destructuring_a_list() {
+        let input  = b"list($foo) = $bar";
+        let output = Result::Done(
+            &b""[..],
+            Expression::Assignment {
+                variable: Box::new(
+                    Spanned::from(Expression::List(vec![
+                        Some((None, Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..])))))
+                    ]))
+                ),
+                value   : Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"bar"[..]))))
+            }
+        );
+
+        assert_eq!(assignment(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_assignment_destructuring_a_nested_array() {
+        let input  = b"[$a, [$b, $c]] = $arr";
+        let output = Result::Done(
+            &b""[..],
+            Expression::Assignment {
+                variable: Box::new(
+                    Spanned::from(Expression::ShortList(vec![
+                        Some((None, Spanned::from(Expression::Variable(Variable::Name(&b"a"[..]))))),
+                        Some((
+                            None,
+                            Spanned::from(Expression::ShortList(vec![
+                                Some((None, Spanned::from(Expression::Variable(Variable::Name(&b"b"[..]))))),
+                                Some((None, Spanned::from(Expression::Variable(Variable::Name(&b"c"[..])))))
+                            ]))
+                        ))
+                    ]))
+                ),
+                value   : Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"arr"[..]))))
+            }
+        );
+
+        assert_eq!(assignment(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_assignment_destructuring_a_keyed_array() {
+        let input  = b"['id' => $id, 'name' => &$name] = $row";
+        let output = Result::Done(
+            &b""[..],
+            Expression::Assignment {
+                variable: Box::new(
+                    Spanned::from(Expression::ShortList(vec![
+                        Some((
+                            Some(Spanned::from(Expression::Literal(Literal::String(b"id".to_vec())))),
+                            Spanned::from(Expression::Variable(Variable::Name(&b"id"[..])))
+                        )),
+                        Some((
+                            Some(Spanned::from(Expression::Literal(Literal::String(b"name".to_vec())))),
+                            Spanned::from(Expression::Reference(Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"name"[..]))))))
+                        ))
+                    ]))
+                ),
+                value   : Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"row"[..]))))
+            }
+        );
+
+        assert_eq!(assignment(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_assignment_destructuring_a_nested_list() {
+        let input  = b"list('x' => &$x, 'y' => list($y, , $z)) = $point";
+        let output = Result::Done(
+            &b""[..],
+            Expression::Assignment {
+                variable: Box::new(
+                    Spanned::from(Expression::List(vec![
+                        Some((
+                            Some(Spanned::from(Expression::Literal(Literal::String(b"x".to_vec())))),
+                            Spanned::from(Expression::Reference(Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"x"[..]))))))
+                        )),
+                        Some((
+                            Some(Spanned::from(Expression::Literal(Literal::String(b"y".to_vec())))),
+                            Spanned::from(Expression::List(vec![
+                                Some((None, Spanned::from(Expression::Variable(Variable::Name(&b"y"[..]))))),
+                                None,
+                                Some((None, Spanned::from(Expression::Variable(Variable::Name(&b"z"[..])))))
+                            ]))
+                        ))
+                    ]))
+                ),
+                value   : Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"point"[..]))))
+            }
+        );
+
+        assert_eq!(assignment(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_assignment_destructuring_an_array_with_skipped_elements() {
+        let input  = b"[$foo, , $bar] = $baz";
+        let output = Result::Done(
+            &b""[..],
+            Expression::Assignment {
+                variable: Box::new(
+                    Spanned::from(Expression::ShortList(vec![
+                        Some((None, Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..]))))),
+                        None,
+                        Some((None, Spanned::from(Expression::Variable(Variable::Name(&b"bar"[..])))))
+                    ]))
+                ),
+                value   : Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"baz"[..]))))
+            }
+        );
+
+        assert_eq!(assignment(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_assignment_destructuring_an_array_with_a_leading_skipped_element() {
+        let input  = b"[, $foo] = $bar";
+        let output = Result::Done(
+            &b""[..],
+            Expression::Assignment {
+                variable: Box::new(
+                    Spanned::from(Expression::ShortList(vec![
+                        None,
+                        Some((None, Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..])))))
+                    ]))
+                ),
+                value   : Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"bar"[..]))))
+            }
+        );
+
+        assert_eq!(assignment(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_assignment_destructuring_a_nested_array_with_skipped_elements() {
+        let input  = b"[[, $foo], [$bar]] = $baz";
+        let output = Result::Done(
+            &b""[..],
+            Expression::Assignment {
+                variable: Box::new(
+                    Spanned::from(Expression::ShortList(vec![
+                        Some((
+                            None,
+                            Spanned::from(Expression::ShortList(vec![
+                                None,
+                                Some((None, Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..])))))
+                            ]))
+                        )),
+                        Some((
+                            None,
+                            Spanned::from(Expression::ShortList(vec![
+                                Some((None, Spanned::from(Expression::Variable(Variable::Name(&b"bar"[..])))))
+                            ]))
+                        ))
+                    ]))
+                ),
+                value   : Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"baz"[..]))))
+            }
+        );
+
+        assert_eq!(assignment(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_invalid_array_with_skipped_elements_is_not_a_value() {
+        let input = b"$foo = [, $bar]";
+
+        assert_eq!(expression(input), Result::Done(&b" = [, $bar]"[..], Expression::Variable(Variable::Name(&b"foo"[..]))));
+    }
+
+    #[test]
+    fn case_invalid_assignment_destructuring_only_skipped_elements() {
+        let input  = b"[, ,] = $foo";
+        let output = Result::Error(Error::Position(ErrorKind::MapRes, &input[..]));
+
+        assert_eq!(assignment(input), output);
+    }
+
+    #[test]
+    fn case_invalid_assignment_destructuring_into_a_literal() {
+        let input  = b"[$foo, [42]] = $bar";
+        let output = Result::Error(Error::Position(ErrorKind::MapRes, &input[..]));
+
+        assert_eq!(assignment(input), output);
+    }
+
+    #[test]
+    fn case_invalid_assignment_destructuring_an_empty_array() {
+        let input  = b"[] = $foo";
+        let output = Result::Error(Error::Position(ErrorKind::MapRes, &input[..]));
+
+        assert_eq!(assignment(input), output);
+    }
+
+    #[test]
+    fn case_invalid_assignment_destructuring_a_list_inside_an_array() {
+        let input  = b"[$foo, list($bar)] = $baz";
+        let output = Result::Error(Error::Position(ErrorKind::MapRes, &input[..]));
+
+        assert_eq!(assignment(input), output);
+    }
+
+    #[test]
+    fn case_assignment_without_value() {
+        let input  = b"$foo";
+        let output = Result::Done(&b""[..], Expression::Variable(Variable::Name(&b"foo"[..])));
+
+        assert_eq!(assignment(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_assignment_value_missing() {
+        let input  = b"$foo = ;";
+        let output = Result::Done(&b" = ;"[..], Expression::Variable(Variable::Name(&b"foo"[..])));
+
+        assert_eq!(assignment(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_invalid_assignment_by_reference_of_a_new() {
+        let input  = b"$foo = &new Bar";
+        let output = Result::Done(&b" = &new Bar"[..], Expression::Variable(Variable::Name(&b"foo"[..])));
+
+        assert_eq!(assignment(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_invalid_assignment_by_reference_of_a_literal() {
+        let input  = b"$foo = &42";
+        let output = Result::Done(&b" = &42"[..], Expression::Variable(Variable::Name(&b"foo"[..])));
+
+        assert_eq!(assignment(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_invalid_assignment_to_a_literal() {
+        let input  = b"42 = $foo";
+        let output = Result::Error(Error::Position(ErrorKind::MapRes, &input[..]));
+
+        assert_eq!(assignment(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_invalid_assignment_to_a_class_constant() {
+        let input  = b"Foo::BAR = 42";
+        let output = Result::Error(Error::Position(ErrorKind::MapRes, &input[..]));
+
+        assert_eq!(assignment(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_yield() {
+        let input  = b"yield";
+        let output = Result::Done(
+            &b""[..],
+            Expression::Yield {
+                key  : None,
+                value: None
+            }
+        );
+
+        assert_eq!(yield_expression(input), output);
+        assert_eq!(assignment(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_yield_value() {
+        let input  = b"yield /* foo */ 42";
+        let output = Result::Done(
+            &b""[..],
+            Expression::Yield {
+                key  : None,
+                value: Some(Box::new(Spanned::from(Expression::Literal(Literal::Integer(42i64)))))
+            }
+        );
+
+        assert_eq!(yield_expression(input), output);
+        assert_eq!(assignment(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_yield_key_and_value() {
+        let input  = b"YiElD 'foo' => $bar";
+        let output = Result::Done(
+            &b""[..],
+            Expression::Yield {
+                key  : Some(Box::new(Spanned::from(Expression::Literal(Literal::String(b"foo".to_vec()))))),
+                value: Some(Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"bar"[..])))))
+            }
+        );
+
+        assert_eq!(yield_expression(input), output);
+        assert_eq!(assignment(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_yield_without_value_followed_by_a_delimiter() {
+        let input  = b"yield;";
+        let output = Result::Done(
+            &b";"[..],
+            Expression::Yield {
+                key  : None,
+                value: None
+            }
+        );
+
+        assert_eq!(yield_expression(input), output);
+        assert_eq!(assignment(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_yield_from() {
+        let input  = b"yield \n\t from foo()";
+        let output = Result::Done(
+            &b""[..],
+            Expression::YieldFrom(
+                Box::new(
+                    Spanned::from(Expression::Call {
+                        callee   : Box::new(Spanned::from(Expression::Name(Name::Unqualified(&b"foo"[..])))),
+                        arguments: vec![]
+                    })
+                )
+            )
+        );
+
+        assert_eq!(yield_from_expression(input), output);
+        assert_eq!(assignment(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_yield_a_variable_named_like_from() {
+        let input  = b"yield $from";
+        let output = Result::Done(
+            &b""[..],
+            Expression::Yield {
+                key  : None,
+                value: Some(Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"from"[..])))))
+            }
+        );
+
+        assert_eq!(assignment(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_yield_a_constant_starting_with_from() {
+        let input  = b"yield fromage";
+        let output = Result::Done(
+            &b""[..],
+            Expression::Yield {
+                key  : None,
+                value: Some(Box::new(Spanned::from(Expression::Name(Name::Unqualified(&b"fromage"[..])))))
+            }
+        );
+
+        assert_eq!(yield_from_expression(input), Result::Error(Error::Position(ErrorKind::Custom(ErrorKindCustom::Keyword as u32), &b"fromage"[..])));
+        assert_eq!(assignment(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_invalid_yield_from_missing_expression() {
+        assert_eq!(yield_from_expression(b"yield from;"), Result::Error(Error::Position(ErrorKind::Alt, &b";"[..])));
+    }
+}
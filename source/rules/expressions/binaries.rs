@@ -0,0 +1,423 @@
+// Tagua VM
+//
+//
+// New BSD License
+//
+// Copyright © 2016-2016, Ivan Enderlin.
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//     * Redistributions of source code must retain the above copyright
+//       notice, this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright
+//       notice, this list of conditions and the following disclaimer in the
+//       documentation and/or other materials provided with the distribution.
+//     * Neither the name of the Hoa nor the names of its contributors may be
+//       used to endorse or promote products derived from this software without
+//       specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE
+// ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDERS AND CONTRIBUTORS BE
+// LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR
+// CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF
+// SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS
+// INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN
+// CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+
+//! Group of binary expression rules.
+//!
+//! The list of all binary expressions is provided by the PHP Language
+//! Specification in the [Grammar chapter, Expressions
+//! section](https://github.com/php/php-langspec/blob/master/spec/19-grammar.md#instanceof-operator).
+
+use super::postfixes::postfix;
+use super::primaries::class_type_designator;
+use super::unaries::{
+    clone,
+    postfix_decrement,
+    postfix_increment,
+    prefix_decrement,
+    prefix_increment,
+    unary
+};
+use super::super::super::ast::Expression;
+use super::super::super::tokens;
+
+named!(
+    pub instanceof<Expression>,
+    chain!(
+        subject: spanned!(unary) ~
+        class: opt!(
+            complete!(
+                preceded!(
+                    first!(keyword!(tokens::INSTANCEOF)),
+                    first!(spanned!(class_type_designator))
+                )
+            )
+        ),
+        || {
+            match class {
+                Some(class) => {
+                    Expression::InstanceOf {
+                        subject: Box::new(subject),
+                        class  : Box::new(class)
+                    }
+                },
+
+                None => {
+                    subject.node
+                }
+            }
+        }
+    )
+);
+
+named!(
+    pub power<Expression>,
+    chain!(
+        base: spanned!(power_operand) ~
+        exponent: opt!(
+            complete!(
+                preceded!(
+                    first!(tag!(tokens::POW)),
+                    first!(spanned!(unary))
+                )
+            )
+        ),
+        || {
+            match exponent {
+                Some(exponent) => {
+                    Expression::Power {
+                        base    : Box::new(base),
+                        exponent: Box::new(exponent)
+                    }
+                },
+
+                None => {
+                    base.node
+                }
+            }
+        }
+    )
+);
+
+named!(
+    power_operand<Expression>,
+    alt!(
+        prefix_increment
+      | prefix_decrement
+      | postfix_increment
+      | postfix_decrement
+      | postfix
+      | clone
+    )
+);
+
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        instanceof,
+        power
+    };
+    use super::super::expression;
+    use super::super::unaries::unary;
+    use super::super::super::super::ast::{
+        CastType,
+        Expression,
+        Literal,
+        Name,
+        RelativeScope,
+        Variable
+    };
+    use super::super::super::super::internal::Result;
+    use super::super::super::super::span::Spanned;
+
+    #[test]
+    fn case_instanceof_qualified_name() {
+        let input  = b"$foo instanceof Bar\\Baz";
+        let output = Result::Done(
+            &b""[..],
+            Expression::InstanceOf {
+                subject: Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..])))),
+                class  : Box::new(Spanned::from(Expression::Name(Name::Qualified(vec![&b"Bar"[..], &b"Baz"[..]]))))
+            }
+        );
+
+        assert_eq!(instanceof(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_instanceof_fully_qualified_name() {
+        let input  = b"$foo instanceof \\Bar";
+        let output = Result::Done(
+            &b""[..],
+            Expression::InstanceOf {
+                subject: Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..])))),
+                class  : Box::new(Spanned::from(Expression::Name(Name::FullyQualified(vec![&b"Bar"[..]]))))
+            }
+        );
+
+        assert_eq!(instanceof(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_instanceof_variable() {
+        let input  = b"$foo instanceof $class";
+        let output = Result::Done(
+            &b""[..],
+            Expression::InstanceOf {
+                subject: Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..])))),
+                class  : Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"class"[..]))))
+            }
+        );
+
+        assert_eq!(instanceof(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_instanceof_property_fetch() {
+        let input  = b"$foo instanceof $this->bar";
+        let output = Result::Done(
+            &b""[..],
+            Expression::InstanceOf {
+                subject: Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..])))),
+                class  : Box::new(
+                    Spanned::from(Expression::PropertyFetch {
+                        object  : Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"this"[..])))),
+                        property: Box::new(Spanned::from(Expression::Name(Name::Unqualified(&b"bar"[..]))))
+                    })
+                )
+            }
+        );
+
+        assert_eq!(instanceof(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_instanceof_self() {
+        let input  = b"$foo instanceof self";
+        let output = Result::Done(
+            &b""[..],
+            Expression::InstanceOf {
+                subject: Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..])))),
+                class  : Box::new(Spanned::from(Expression::RelativeScope(RelativeScope::Current)))
+            }
+        );
+
+        assert_eq!(instanceof(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_instanceof_parent() {
+        let input  = b"$foo instanceof parent";
+        let output = Result::Done(
+            &b""[..],
+            Expression::InstanceOf {
+                subject: Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..])))),
+                class  : Box::new(Spanned::from(Expression::RelativeScope(RelativeScope::Parent)))
+            }
+        );
+
+        assert_eq!(instanceof(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_instanceof_static() {
+        let input  = b"$foo InstanceOf /* bar */ StAtIc";
+        let output = Result::Done(
+            &b""[..],
+            Expression::InstanceOf {
+                subject: Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..])))),
+                class  : Box::new(Spanned::from(Expression::RelativeScope(RelativeScope::Static)))
+            }
+        );
+
+        assert_eq!(instanceof(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_instanceof_name_starting_like_a_relative_scope() {
+        let input  = b"$foo instanceof Selfish";
+        let output = Result::Done(
+            &b""[..],
+            Expression::InstanceOf {
+                subject: Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..])))),
+                class  : Box::new(Spanned::from(Expression::Name(Name::Unqualified(&b"Selfish"[..]))))
+            }
+        );
+
+        assert_eq!(instanceof(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_instanceof_subject_is_a_unary_expression() {
+        let input  = b"$foo++ instanceof Bar";
+        let output = Result::Done(
+            &b""[..],
+            Expression::InstanceOf {
+                subject: Box::new(
+                    Spanned::from(Expression::PostIncrement(
+                        Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..]))))
+                    ))
+                ),
+                class: Box::new(Spanned::from(Expression::Name(Name::Unqualified(&b"Bar"[..]))))
+            }
+        );
+
+        assert_eq!(instanceof(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_invalid_instanceof_class_missing() {
+        let input  = b"$foo instanceof 42";
+        let output = Result::Done(&b" instanceof 42"[..], Expression::Variable(Variable::Name(&b"foo"[..])));
+
+        assert_eq!(instanceof(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_power() {
+        let input  = b"$foo ** 2";
+        let output = Result::Done(
+            &b""[..],
+            Expression::Power {
+                base    : Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..])))),
+                exponent: Box::new(Spanned::from(Expression::Literal(Literal::Integer(2i64))))
+            }
+        );
+
+        assert_eq!(power(input), output);
+        assert_eq!(unary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_power_is_right_associative() {
+        let input  = b"2 ** /* foo */ 3 ** 2";
+        let output = Result::Done(
+            &b""[..],
+            Expression::Power {
+                base    : Box::new(Spanned::from(Expression::Literal(Literal::Integer(2i64)))),
+                exponent: Box::new(
+                    Spanned::from(Expression::Power {
+                        base    : Box::new(Spanned::from(Expression::Literal(Literal::Integer(3i64)))),
+                        exponent: Box::new(Spanned::from(Expression::Literal(Literal::Integer(2i64))))
+                    })
+                )
+            }
+        );
+
+        assert_eq!(power(input), output);
+        assert_eq!(unary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_power_has_a_higher_precedence_than_negation() {
+        let input  = b"-2 ** 2";
+        let output = Result::Done(
+            &b""[..],
+            Expression::Negation(
+                Box::new(
+                    Spanned::from(Expression::Power {
+                        base    : Box::new(Spanned::from(Expression::Literal(Literal::Integer(2i64)))),
+                        exponent: Box::new(Spanned::from(Expression::Literal(Literal::Integer(2i64))))
+                    })
+                )
+            )
+        );
+
+        assert_eq!(unary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_power_with_a_negative_exponent() {
+        let input  = b"2 ** -3 ** 2";
+        let output = Result::Done(
+            &b""[..],
+            Expression::Power {
+                base    : Box::new(Spanned::from(Expression::Literal(Literal::Integer(2i64)))),
+                exponent: Box::new(
+                    Spanned::from(Expression::Negation(
+                        Box::new(
+                            Spanned::from(Expression::Power {
+                                base    : Box::new(Spanned::from(Expression::Literal(Literal::Integer(3i64)))),
+                                exponent: Box::new(Spanned::from(Expression::Literal(Literal::Integer(2i64))))
+                            })
+                        )
+                    ))
+                )
+            }
+        );
+
+        assert_eq!(power(input), output);
+        assert_eq!(unary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_power_has_a_higher_precedence_than_cast() {
+        let input  = b"(int) $foo ** 2";
+        let output = Result::Done(
+            &b""[..],
+            Expression::Cast {
+                ty     : CastType::Integer,
+                operand: Box::new(
+                    Spanned::from(Expression::Power {
+                        base    : Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..])))),
+                        exponent: Box::new(Spanned::from(Expression::Literal(Literal::Integer(2i64))))
+                    })
+                )
+            }
+        );
+
+        assert_eq!(unary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_power_of_a_postfix_increment() {
+        let input  = b"$foo++ ** 2";
+        let output = Result::Done(
+            &b""[..],
+            Expression::Power {
+                base    : Box::new(
+                    Spanned::from(Expression::PostIncrement(
+                        Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..]))))
+                    ))
+                ),
+                exponent: Box::new(Spanned::from(Expression::Literal(Literal::Integer(2i64))))
+            }
+        );
+
+        assert_eq!(power(input), output);
+        assert_eq!(unary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_power_exponent_missing() {
+        let input  = b"$foo ** ;";
+        let output = Result::Done(&b" ** ;"[..], Expression::Variable(Variable::Name(&b"foo"[..])));
+
+        assert_eq!(power(input), output);
+        assert_eq!(expression(input), output);
+    }
+}
@@ -0,0 +1,449 @@
+// Tagua VM
+//
+//
+// New BSD License
+//
+// Copyright © 2016-2016, Ivan Enderlin.
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//     * Redistributions of source code must retain the above copyright
+//       notice, this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright
+//       notice, this list of conditions and the following disclaimer in the
+//       documentation and/or other materials provided with the distribution.
+//     * Neither the name of the Hoa nor the names of its contributors may be
+//       used to endorse or promote products derived from this software without
+//       specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE
+// ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDERS AND CONTRIBUTORS BE
+// LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR
+// CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF
+// SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS
+// INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN
+// CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+
+//! Group of constant expression rules.
+//!
+//! The list of all constant expressions is provided by the PHP Language
+//! Specification in the [Grammar chapter, Constant Expressions
+//! section](https://github.com/php/php-langspec/blob/master/spec/19-grammar.md#constant-expressions).
+
+use std::result::Result as StdResult;
+use super::expression;
+use super::primaries::arguments;
+use super::super::super::ast::{
+    Argument,
+    ConstantExpression,
+    Expression,
+    RelativeScope,
+    StringPart
+};
+use super::super::super::span::Spanned;
+use super::super::super::version::{
+    self,
+    Version
+};
+
+named!(
+    pub constant_expression<ConstantExpression>,
+    map_res!(
+        spanned!(expression),
+        constant_expression_mapper
+    )
+);
+
+#[inline(always)]
+fn constant_expression_mapper<'a>(expression: Spanned<Expression<'a>>) -> StdResult<ConstantExpression<'a>, ()> {
+    if is_constant(&expression, false) {
+        Ok(ConstantExpression(expression))
+    } else {
+        Err(())
+    }
+}
+
+named!(
+    pub initializer_expression<ConstantExpression>,
+    map_res!(
+        spanned!(expression),
+        initializer_expression_mapper
+    )
+);
+
+#[inline(always)]
+fn initializer_expression_mapper<'a>(expression: Spanned<Expression<'a>>) -> StdResult<ConstantExpression<'a>, ()> {
+    if is_constant(&expression, version::is_targeting(Version::Php81)) {
+        Ok(ConstantExpression(expression))
+    } else {
+        Err(())
+    }
+}
+
+named!(
+    pub constant_arguments< Vec<Spanned<Argument>> >,
+    map_res!(
+        arguments,
+        constant_arguments_mapper
+    )
+);
+
+/// Constant arguments, e.g. of an attribute, cannot be unpacked. Since PHP
+/// 8.1, they can be instanciations.
+#[inline(always)]
+fn constant_arguments_mapper<'a>(arguments: Vec<Spanned<Argument<'a>>>) -> StdResult<Vec<Spanned<Argument<'a>>>, ()> {
+    let allow_new = version::is_targeting(Version::Php81);
+
+    if arguments.iter().all(|argument| !argument.is_unpacked && is_constant(&argument.value, allow_new)) {
+        Ok(arguments)
+    } else {
+        Err(())
+    }
+}
+
+/// Check whether an expression can be evaluated at compile time. An
+/// instanciation is accepted if `allow_new` is set, as long as the class
+/// and the arguments are constant.
+fn is_constant(expression: &Expression, allow_new: bool) -> bool {
+    let is_constant_operand = |operand: &Expression| is_constant(operand, allow_new);
+
+    match *expression {
+        Expression::Literal(_) |
+        Expression::MagicConstant(_) |
+        Expression::Name(_) => {
+            true
+        },
+
+        Expression::Array(ref pairs) => {
+            pairs.iter().all(
+                |(key, value)| {
+                    key.as_ref().is_none_or(|key| is_constant_operand(key)) && is_constant_operand(value)
+                }
+            )
+        },
+
+        Expression::ClassNameConstant(ref class) => {
+            is_constant_class(class)
+        },
+
+        Expression::DynamicClassConstant { ref class, ref name } => {
+            is_constant_class(class) && is_constant_operand(name)
+        },
+
+        Expression::Heredoc { ref parts, .. } => {
+            parts.iter().all(|part| matches!(*part, StringPart::Bytes(_)))
+        },
+
+        Expression::StaticFetch { ref class, ref member } => {
+            let is_constant_member = matches!(member.node, Expression::Name(_));
+
+            is_constant_member && is_constant_class(class)
+        },
+
+        Expression::Negation(ref operand) => {
+            is_constant_operand(operand)
+        },
+
+        Expression::New { ref class, ref arguments } => {
+            let is_named_class = matches!(class.node, Expression::Name(_));
+
+            allow_new &&
+            is_named_class &&
+            arguments.iter().all(
+                |argument| {
+                    !argument.is_unpacked && is_constant_operand(&argument.value)
+                }
+            )
+        },
+
+        Expression::Power { ref base, ref exponent } => {
+            is_constant_operand(base) && is_constant_operand(exponent)
+        },
+
+        _ => {
+            false
+        }
+    }
+}
+
+/// Check whether a class can be resolved at compile time. Late static
+/// binding, i.e. `static`, is resolved at runtime.
+fn is_constant_class(class: &Expression) -> bool {
+    matches!(
+        *class,
+        Expression::Name(_) |
+        Expression::RelativeScope(RelativeScope::Current) |
+        Expression::RelativeScope(RelativeScope::Parent)
+    )
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        constant_expression,
+        initializer_expression
+    };
+    use super::super::super::super::ast::{
+        Argument,
+        ConstantExpression,
+        Expression,
+        Literal,
+        MagicConstant,
+        Name,
+        RelativeScope,
+        StringPart
+    };
+    use super::super::super::super::internal::{
+        Error,
+        ErrorKind,
+        Result
+    };
+    use super::super::super::super::span::Spanned;
+    use super::super::super::super::version::{
+        self,
+        Version
+    };
+
+    #[test]
+    fn case_constant_expression_literal() {
+        let input  = b"42";
+        let output = Result::Done(&b""[..], ConstantExpression(Spanned::from(Expression::Literal(Literal::Integer(42i64)))));
+
+        assert_eq!(constant_expression(input), output);
+    }
+
+    #[test]
+    fn case_constant_expression_binary_strings() {
+        let input  = b"[b'foo', B\"bar\"]";
+        let output = Result::Done(
+            &b""[..],
+            ConstantExpression(
+                Spanned::from(Expression::Array(vec![
+                    (None, Spanned::from(Expression::Literal(Literal::String(b"foo".to_vec())))),
+                    (None, Spanned::from(Expression::Literal(Literal::String(b"bar".to_vec()))))
+                ]))
+            )
+        );
+
+        assert_eq!(constant_expression(input), output);
+    }
+
+    #[test]
+    fn case_constant_expression_heredoc() {
+        let input  = b"<<<FOO\n  bar\n  FOO";
+        let output = Result::Done(
+            &b""[..],
+            ConstantExpression(
+                Spanned::from(Expression::Heredoc {
+                    label      : &b"FOO"[..],
+                    indentation: &b"  "[..],
+                    parts      : vec![StringPart::Bytes(b"bar".to_vec())]
+                })
+            )
+        );
+
+        assert_eq!(constant_expression(input), output);
+    }
+
+    #[test]
+    fn case_constant_expression_constant_named_b() {
+        let input  = b"b";
+        let output = Result::Done(&b""[..], ConstantExpression(Spanned::from(Expression::Name(Name::Unqualified(&b"b"[..])))));
+
+        assert_eq!(constant_expression(input), output);
+    }
+
+    #[test]
+    fn case_constant_expression_constant() {
+        let input  = b"Foo\\BAR";
+        let output = Result::Done(&b""[..], ConstantExpression(Spanned::from(Expression::Name(Name::Qualified(vec![&b"Foo"[..], &b"BAR"[..]])))));
+
+        assert_eq!(constant_expression(input), output);
+    }
+
+    #[test]
+    fn case_constant_expression_magic_constant() {
+        let input  = b"__DIR__";
+        let output = Result::Done(&b""[..], ConstantExpression(Spanned::from(Expression::MagicConstant(MagicConstant::Directory))));
+
+        assert_eq!(constant_expression(input), output);
+    }
+
+    #[test]
+    fn case_constant_expression_class_constant() {
+        let input  = b"self::FOO";
+        let output = Result::Done(
+            &b""[..],
+            ConstantExpression(
+                Spanned::from(Expression::StaticFetch {
+                    class : Box::new(Spanned::from(Expression::RelativeScope(RelativeScope::Current))),
+                    member: Box::new(Spanned::from(Expression::Name(Name::Unqualified(&b"FOO"[..]))))
+                })
+            )
+        );
+
+        assert_eq!(constant_expression(input), output);
+    }
+
+    #[test]
+    fn case_constant_expression_dynamic_class_constant() {
+        let input  = b"self::{'FOO'}";
+        let output = Result::Done(
+            &b""[..],
+            ConstantExpression(
+                Spanned::from(Expression::DynamicClassConstant {
+                    class: Box::new(Spanned::from(Expression::RelativeScope(RelativeScope::Current))),
+                    name : Box::new(Spanned::from(Expression::Literal(Literal::String(b"FOO".to_vec()))))
+                })
+            )
+        );
+
+        assert_eq!(constant_expression(input), output);
+    }
+
+    #[test]
+    fn case_constant_expression_class_name_constant() {
+        let input  = b"Foo::class";
+        let output = Result::Done(
+            &b""[..],
+            ConstantExpression(
+                Spanned::from(Expression::ClassNameConstant(
+                    Box::new(Spanned::from(Expression::Name(Name::Unqualified(&b"Foo"[..]))))
+                ))
+            )
+        );
+
+        assert_eq!(constant_expression(input), output);
+    }
+
+    #[test]
+    fn case_constant_expression_array() {
+        let input  = b"['foo' => [-1, 2 ** 3], Foo::BAR]";
+        let output = Result::Done(
+            &b""[..],
+            ConstantExpression(
+                Spanned::from(Expression::Array(vec![
+                    (
+                        Some(Spanned::from(Expression::Literal(Literal::String(b"foo".to_vec())))),
+                        Spanned::from(Expression::Array(vec![
+                            (
+                                None,
+                                Spanned::from(Expression::Negation(Box::new(Spanned::from(Expression::Literal(Literal::Integer(1i64))))))
+                            ),
+                            (
+                                None,
+                                Spanned::from(Expression::Power {
+                                    base    : Box::new(Spanned::from(Expression::Literal(Literal::Integer(2i64)))),
+                                    exponent: Box::new(Spanned::from(Expression::Literal(Literal::Integer(3i64))))
+                                })
+                            )
+                        ]))
+                    ),
+                    (
+                        None,
+                        Spanned::from(Expression::StaticFetch {
+                            class : Box::new(Spanned::from(Expression::Name(Name::Unqualified(&b"Foo"[..])))),
+                            member: Box::new(Spanned::from(Expression::Name(Name::Unqualified(&b"BAR"[..]))))
+                        })
+                    )
+                ]))
+            )
+        );
+
+        assert_eq!(constant_expression(input), output);
+    }
+
+    macro_rules! test_invalid_constant_expression {
+        ($test_case_name:ident: $input:expr) => (
+            #[test]
+            fn $test_case_name() {
+                assert_eq!(constant_expression($input), Result::Error(Error::Position(ErrorKind::MapRes, &$input[..])));
+            }
+        )
+    }
+
+    test_invalid_constant_expression!(case_invalid_constant_expression_variable:              b"$foo");
+    test_invalid_constant_expression!(case_invalid_constant_expression_call:                  b"foo()");
+    test_invalid_constant_expression!(case_invalid_constant_expression_new:                   b"new Foo()");
+    test_invalid_constant_expression!(case_invalid_constant_expression_static_property:       b"Foo::$bar");
+    test_invalid_constant_expression!(case_invalid_constant_expression_late_static_binding:   b"static::FOO");
+    test_invalid_constant_expression!(case_invalid_constant_expression_array_with_a_variable: b"[1, $foo]");
+    test_invalid_constant_expression!(case_invalid_constant_expression_array_by_reference:    b"[&FOO]");
+    test_invalid_constant_expression!(case_invalid_constant_expression_interpolated_string:   b"\"$foo\"");
+    test_invalid_constant_expression!(case_invalid_constant_expression_interpolated_heredoc:  b"<<<FOO\n$foo\nFOO");
+
+    #[test]
+    fn case_initializer_expression_literal() {
+        let input  = b"42";
+        let output = Result::Done(&b""[..], ConstantExpression(Spanned::from(Expression::Literal(Literal::Integer(42i64)))));
+
+        assert_eq!(initializer_expression(input), output);
+    }
+
+    #[test]
+    fn case_initializer_expression_new() {
+        let input  = b"[new Foo(42, bar: new Bar)]";
+        let output = Result::Done(
+            &b""[..],
+            ConstantExpression(
+                Spanned::from(Expression::Array(vec![
+                    (
+                        None,
+                        Spanned::from(Expression::New {
+                            class    : Box::new(Spanned::from(Expression::Name(Name::Unqualified(&b"Foo"[..])))),
+                            arguments: vec![
+                                Spanned::from(Argument {
+                                    name       : None,
+                                    is_unpacked: false,
+                                    value      : Spanned::from(Expression::Literal(Literal::Integer(42i64)))
+                                }),
+                                Spanned::from(Argument {
+                                    name       : Some(&b"bar"[..]),
+                                    is_unpacked: false,
+                                    value      : Spanned::from(Expression::New {
+                                        class    : Box::new(Spanned::from(Expression::Name(Name::Unqualified(&b"Bar"[..])))),
+                                        arguments: vec![]
+                                    })
+                                })
+                            ]
+                        })
+                    )
+                ]))
+            )
+        );
+
+        assert_eq!(initializer_expression(input), output);
+    }
+
+    #[test]
+    fn case_invalid_initializer_expression_new_before_php_81() {
+        let input = b"new Foo";
+
+        version::set_target(Version::Php80);
+        let output = initializer_expression(input);
+        version::set_target(version::LATEST);
+
+        assert_eq!(output, Result::Error(Error::Position(ErrorKind::MapRes, &input[..])));
+    }
+
+    macro_rules! test_invalid_initializer_expression {
+        ($test_case_name:ident: $input:expr) => (
+            #[test]
+            fn $test_case_name() {
+                assert_eq!(initializer_expression($input), Result::Error(Error::Position(ErrorKind::MapRes, &$input[..])));
+            }
+        )
+    }
+
+    test_invalid_initializer_expression!(case_invalid_initializer_expression_variable:                  b"$foo");
+    test_invalid_initializer_expression!(case_invalid_initializer_expression_new_with_a_variable:       b"new Foo($bar)");
+    test_invalid_initializer_expression!(case_invalid_initializer_expression_new_with_unpacking:        b"new Foo(...BAR)");
+    test_invalid_initializer_expression!(case_invalid_initializer_expression_new_with_a_dynamic_class:  b"new $foo");
+    test_invalid_initializer_expression!(case_invalid_initializer_expression_new_with_an_anonymous_class: b"new class {}");
+}
@@ -35,11 +35,23 @@
 //! in the [Grammar chapter, Expressions
 //! section](https://github.com/php/php-langspec/blob/master/spec/19-grammar.md#expressions).
 
+pub mod assignments;
+pub mod binaries;
+pub mod constants;
+pub mod postfixes;
 pub mod primaries;
+pub mod strings;
+pub mod unaries;
 
 use super::super::ast::Expression;
+use super::super::diagnostics;
+use super::super::internal::Result;
+use super::super::span;
 
-named!(
-    pub expression<Expression>,
-    call!(primaries::primary)
-);
+/// Parse an expression. The diagnostics reported while parsing a
+/// backtracked expression are discarded, see `diagnostics::attempt`. The
+/// spans of the nested nodes are relative to the input, unless a datum has
+/// already been set, see `span::within`.
+pub fn expression(input: &[u8]) -> Result<&[u8], Expression> {
+    span::within(input, |input| diagnostics::attempt(input, assignments::assignment))
+}
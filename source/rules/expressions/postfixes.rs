@@ -0,0 +1,1706 @@
+// Tagua VM
+//
+//
+// New BSD License
+//
+// Copyright © 2016-2016, Ivan Enderlin.
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//     * Redistributions of source code must retain the above copyright
+//       notice, this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright
+//       notice, this list of conditions and the following disclaimer in the
+//       documentation and/or other materials provided with the distribution.
+//     * Neither the name of the Hoa nor the names of its contributors may be
+//       used to endorse or promote products derived from this software without
+//       specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE
+// ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDERS AND CONTRIBUTORS BE
+// LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR
+// CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF
+// SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS
+// INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN
+// CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+
+//! Group of postfix expression rules.
+//!
+//! The list of all postfix expressions is provided by the PHP Language
+//! Specification in the [Grammar chapter, Expressions
+//! section](https://github.com/php/php-langspec/blob/master/spec/19-grammar.md#postfix-operators).
+
+use std::result::Result as StdResult;
+use super::expression;
+use super::primaries::{
+    arguments,
+    dereferencable,
+    dynamic_variable,
+    primary
+};
+use super::super::tokens::{
+    member_name,
+    qualified_name,
+    relative_scope,
+    variable
+};
+use super::super::super::ast::{
+    Argument,
+    Expression,
+    Name,
+    RelativeScope,
+    Variable
+};
+use super::super::super::diagnostics::{
+    self,
+    DiagnosticKind
+};
+use super::super::super::internal::Result;
+use super::super::super::span::Spanned;
+use super::super::super::tokens;
+use super::super::super::version::{
+    self,
+    Version
+};
+
+/// A postfix operator, i.e. what follows an operand in a postfix
+/// expression.
+enum Postfix<'a> {
+    /// Access an array element, e.g. `[42]`, or push into an array, e.g. `[]`.
+    ArrayAccess(Option<Spanned<Expression<'a>>>),
+    /// Call a callable with arguments, e.g. `($foo, 42)`.
+    Call(Vec<Spanned<Argument<'a>>>),
+    /// Create a closure from a callable, e.g. `(...)`.
+    CallableCreation,
+    /// Get the fully qualified name of a class, i.e. `::class`.
+    ClassNameConstant,
+    /// Access a constant of a class by a computed name, e.g. `::{$foo}`.
+    DynamicClassConstant(Spanned<Expression<'a>>),
+    /// Call a method of an object, e.g. `->foo(42)`.
+    MethodCall(Spanned<Expression<'a>>, Vec<Spanned<Argument<'a>>>),
+    /// Call a method of an object if not null, e.g. `?->foo(42)`.
+    NullsafeMethodCall(Spanned<Expression<'a>>, Vec<Spanned<Argument<'a>>>),
+    /// Access a property of an object if not null, e.g. `?->foo`.
+    NullsafePropertyFetch(Spanned<Expression<'a>>),
+    /// Access a property of an object, e.g. `->foo`.
+    PropertyFetch(Spanned<Expression<'a>>),
+    /// Access a static member of a class, e.g. `::FOO` or `::$foo`.
+    StaticFetch(Spanned<Expression<'a>>),
+    /// Call a static method of a class, e.g. `::foo(42)` or `::$foo(42)`.
+    StaticMethodCall(Spanned<Expression<'a>>, Vec<Spanned<Argument<'a>>>)
+}
+
+named!(
+    pub postfix<Expression>,
+    alt!(
+        chain!(
+            operand: spanned!(postfix_operand) ~
+            result: fold_many0!(
+                complete!(first!(spanned!(postfix_operator))),
+                operand,
+                postfix_mapper
+            ),
+            || { result.node }
+        )
+      | primary
+    )
+);
+
+named!(
+    postfix_operand<Expression>,
+    alt!(
+        terminated!(
+            relative_scope,
+            peek!(complete!(first!(tag!(tokens::STATIC_CALL))))
+        ) => { relative_scope_mapper }
+      | dereferencable
+    )
+);
+
+#[inline(always)]
+fn relative_scope_mapper<'a>(scope: RelativeScope) -> Expression<'a> {
+    Expression::RelativeScope(scope)
+}
+
+named!(
+    postfix_operator<Postfix>,
+    alt!(
+        array_access
+      | curly_bracket_array_access
+      | callable_creation
+      | call
+      | method_call
+      | property_fetch
+      | nullsafe_method_call
+      | nullsafe_property_fetch
+      | static_method_call
+      | static_fetch
+    )
+);
+
+// A variable designating the class of a `new` expression, e.g.
+// `$this->cls`, `$a['k']` or `static::$cls`. Calls are excluded, since
+// the arguments belong to the `new` expression.
+named!(
+    pub new_variable<Expression>,
+    chain!(
+        operand: spanned!(new_variable_operand) ~
+        result: fold_many0!(
+            complete!(first!(spanned!(new_variable_operator))),
+            operand,
+            postfix_mapper
+        ),
+        || { result.node }
+    )
+);
+
+named!(
+    new_variable_operand<Expression>,
+    alt!(
+        variable => { variable_mapper }
+      | chain!(
+            class: spanned!(
+                alt!(
+                    relative_scope => { relative_scope_mapper }
+                  | qualified_name => { qualified_name_mapper }
+                )
+            ) ~
+            member: complete!(first!(spanned!(static_property_fetch))),
+            || { postfix_mapper(class, member).node }
+        )
+    )
+);
+
+#[inline(always)]
+fn qualified_name_mapper<'a>(name: Name<'a>) -> Expression<'a> {
+    Expression::Name(name)
+}
+
+named!(
+    new_variable_operator<Postfix>,
+    alt!(
+        array_access
+      | curly_bracket_array_access
+      | property_fetch
+      | nullsafe_property_fetch
+      | static_property_fetch
+    )
+);
+
+named!(
+    array_access<Postfix>,
+    chain!(
+        tag!(tokens::LEFT_SQUARE_BRACKET) ~
+        index: opt!(first!(spanned!(expression))) ~
+        first!(expect!("array_access", tokens::RIGHT_SQUARE_BRACKET)),
+        || { Postfix::ArrayAccess(index) }
+    )
+);
+
+/// Before PHP 8.0, an array or string offset can be accessed with curly
+/// brackets, e.g. `$foo{42}`. This syntax is deprecated since PHP 7.4, and
+/// is reported as such, see the `diagnostics` module. The report is
+/// discarded if the enclosing expression or statement backtracks.
+fn curly_bracket_array_access(input: &[u8]) -> Result<&[u8], Postfix> {
+    let output = curly_bracket_offset(input);
+
+    if let Result::Done(rest, _) = output {
+        diagnostics::report(input, rest, DiagnosticKind::CurlyBracketOffset);
+    }
+
+    output
+}
+
+named!(
+    curly_bracket_offset<Postfix>,
+    cond_reduce!(
+        !version::is_targeting(Version::Php80),
+        chain!(
+            tag!(tokens::LEFT_CURLY_BRACKET) ~
+            index: first!(spanned!(expression)) ~
+            first!(expect!("curly_bracket_offset", tokens::RIGHT_CURLY_BRACKET)),
+            || { Postfix::ArrayAccess(Some(index)) }
+        )
+    )
+);
+
+named!(
+    call<Postfix>,
+    map!(
+        arguments,
+        call_mapper
+    )
+);
+
+#[inline(always)]
+fn call_mapper<'a>(arguments: Vec<Spanned<Argument<'a>>>) -> Postfix<'a> {
+    Postfix::Call(arguments)
+}
+
+named!(
+    callable_creation<Postfix>,
+    chain!(
+        tag!(tokens::LEFT_PARENTHESIS) ~
+        first!(tag!(tokens::ELLIPSIS)) ~
+        first!(expect!("callable_creation", tokens::RIGHT_PARENTHESIS)),
+        || { Postfix::CallableCreation }
+    )
+);
+
+// A method call is a single postfix operator, so that calling a fetched
+// property, e.g. `($foo->bar)()`, remains distinct from calling a method,
+// e.g. `$foo->bar()`.
+named!(
+    method_call<Postfix>,
+    chain!(
+        tag!(tokens::DYNAMIC_CALL) ~
+        method: first!(spanned!(property_name)) ~
+        arguments: complete!(first!(arguments)),
+        || { Postfix::MethodCall(method, arguments) }
+    )
+);
+
+named!(
+    nullsafe_method_call<Postfix>,
+    chain!(
+        tag!(tokens::NULLSAFE_DYNAMIC_CALL) ~
+        method: first!(spanned!(property_name)) ~
+        arguments: complete!(first!(arguments)),
+        || { Postfix::NullsafeMethodCall(method, arguments) }
+    )
+);
+
+named!(
+    static_method_call<Postfix>,
+    chain!(
+        tag!(tokens::STATIC_CALL) ~
+        method: first!(spanned!(property_name)) ~
+        arguments: complete!(first!(arguments)),
+        || { Postfix::StaticMethodCall(method, arguments) }
+    )
+);
+
+named!(
+    property_fetch<Postfix>,
+    chain!(
+        tag!(tokens::DYNAMIC_CALL) ~
+        property: first!(spanned!(property_name)),
+        || { Postfix::PropertyFetch(property) }
+    )
+);
+
+named!(
+    nullsafe_property_fetch<Postfix>,
+    chain!(
+        tag!(tokens::NULLSAFE_DYNAMIC_CALL) ~
+        property: first!(spanned!(property_name)),
+        || { Postfix::NullsafePropertyFetch(property) }
+    )
+);
+
+named!(
+    static_fetch<Postfix>,
+    map_res!(
+        chain!(
+            tag!(tokens::STATIC_CALL) ~
+            member: first!(
+                alt!(
+                    spanned!(computed_member_name) => { |member| (member, true) }
+                  | spanned!(property_name)        => { |member| (member, false) }
+                )
+            ) ~
+            is_call: opt!(peek!(complete!(first!(tag!(tokens::LEFT_PARENTHESIS))))),
+            || { (member, is_call.is_some()) }
+        ),
+        static_fetch_mapper
+    )
+);
+
+named!(
+    static_property_fetch<Postfix>,
+    chain!(
+        tag!(tokens::STATIC_CALL) ~
+        property: first!(
+            spanned!(
+                alt!(
+                    variable         => { variable_mapper }
+                  | dynamic_variable => { variable_mapper }
+                )
+            )
+        ),
+        || { Postfix::StaticFetch(property) }
+    )
+);
+
+named!(
+    computed_member_name<Expression>,
+    preceded!(
+        tag!(tokens::LEFT_CURLY_BRACKET),
+        terminated!(
+            first!(expression),
+            first!(expect!("computed_member_name", tokens::RIGHT_CURLY_BRACKET))
+        )
+    )
+);
+
+named!(
+    property_name<Expression>,
+    alt!(
+        member_name      => { name_mapper }
+      | variable         => { variable_mapper }
+      | dynamic_variable => { variable_mapper }
+      | computed_member_name
+    )
+);
+
+#[inline(always)]
+fn name_mapper<'a>(name: &'a [u8]) -> Expression<'a> {
+    Expression::Name(Name::Unqualified(name))
+}
+
+#[inline(always)]
+fn variable_mapper<'a>(variable: Variable<'a>) -> Expression<'a> {
+    Expression::Variable(variable)
+}
+
+/// A computed member name, e.g. `::{$foo}`, designates a static method
+/// when called, or a constant since PHP 8.3.
+#[inline(always)]
+fn static_fetch_mapper<'a>(((member, is_computed), is_call): ((Spanned<Expression<'a>>, bool), bool)) -> StdResult<Postfix<'a>, ()> {
+    if is_computed && !is_call {
+        return if version::is_targeting(Version::Php83) {
+            Ok(Postfix::DynamicClassConstant(member))
+        } else {
+            Err(())
+        };
+    }
+
+    let is_class = !is_call && match member.node {
+        Expression::Name(Name::Unqualified(name)) => name.eq_ignore_ascii_case(tokens::CLASS),
+        _ => false
+    };
+
+    if is_class {
+        Ok(Postfix::ClassNameConstant)
+    } else {
+        Ok(Postfix::StaticFetch(member))
+    }
+}
+
+/// The span of a postfix expression goes from its operand to its
+/// operator.
+#[inline(always)]
+fn postfix_mapper<'a>(operand: Spanned<Expression<'a>>, operator: Spanned<Postfix<'a>>) -> Spanned<Expression<'a>> {
+    let span       = operand.span.to(operator.span);
+    let expression = match operator.node {
+        Postfix::ArrayAccess(index) => {
+            Expression::ArrayAccess {
+                array: Box::new(operand),
+                index: index.map(Box::new)
+            }
+        },
+
+        Postfix::Call(arguments) => {
+            Expression::Call {
+                callee   : Box::new(operand),
+                arguments: arguments
+            }
+        },
+
+        Postfix::CallableCreation => {
+            Expression::CallableCreation(Box::new(operand))
+        },
+
+        Postfix::ClassNameConstant => {
+            Expression::ClassNameConstant(Box::new(operand))
+        },
+
+        Postfix::DynamicClassConstant(name) => {
+            Expression::DynamicClassConstant {
+                class: Box::new(operand),
+                name : Box::new(name)
+            }
+        },
+
+        Postfix::MethodCall(method, arguments) => {
+            Expression::MethodCall {
+                object   : Box::new(operand),
+                method   : Box::new(method),
+                arguments: arguments
+            }
+        },
+
+        Postfix::NullsafeMethodCall(method, arguments) => {
+            Expression::NullsafeMethodCall {
+                object   : Box::new(operand),
+                method   : Box::new(method),
+                arguments: arguments
+            }
+        },
+
+        Postfix::NullsafePropertyFetch(property) => {
+            Expression::NullsafePropertyFetch {
+                object  : Box::new(operand),
+                property: Box::new(property)
+            }
+        },
+
+        Postfix::PropertyFetch(property) => {
+            Expression::PropertyFetch {
+                object  : Box::new(operand),
+                property: Box::new(property)
+            }
+        },
+
+        Postfix::StaticFetch(member) => {
+            Expression::StaticFetch {
+                class : Box::new(operand),
+                member: Box::new(member)
+            }
+        },
+
+        Postfix::StaticMethodCall(method, arguments) => {
+            Expression::StaticMethodCall {
+                class    : Box::new(operand),
+                method   : Box::new(method),
+                arguments: arguments
+            }
+        }
+    };
+
+    Spanned::new(expression, span)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::postfix;
+    use super::super::expression;
+    use super::super::super::super::ast::{
+        Argument,
+        Expression,
+        Literal,
+        Name,
+        RelativeScope,
+        StringPart,
+        Variable
+    };
+    use super::super::super::super::diagnostics::{
+        self,
+        Diagnostic,
+        DiagnosticKind
+    };
+    use super::super::super::super::internal::Result;
+    use super::super::super::super::span::{
+        Span,
+        Spanned
+    };
+    use super::super::super::super::version::{
+        self,
+        Version
+    };
+
+    #[test]
+    fn case_array_access() {
+        let input  = b"$foo[42]";
+        let output = Result::Done(
+            &b""[..],
+            Expression::ArrayAccess {
+                array: Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..])))),
+                index: Some(Box::new(Spanned::from(Expression::Literal(Literal::Integer(42i64)))))
+            }
+        );
+
+        assert_eq!(postfix(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_array_access_with_a_string_index_and_comments() {
+        let input  = b"$foo /* a */ [ /* b */ 'bar' /* c */ ]";
+        let output = Result::Done(
+            &b""[..],
+            Expression::ArrayAccess {
+                array: Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..])))),
+                index: Some(Box::new(Spanned::from(Expression::Literal(Literal::String(b"bar".to_vec())))))
+            }
+        );
+
+        assert_eq!(postfix(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_array_access_push() {
+        let input  = b"$foo[]";
+        let output = Result::Done(
+            &b""[..],
+            Expression::ArrayAccess {
+                array: Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..])))),
+                index: None
+            }
+        );
+
+        assert_eq!(postfix(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_array_access_chained() {
+        let input  = b"$foo[0][$bar]";
+        let output = Result::Done(
+            &b""[..],
+            Expression::ArrayAccess {
+                array: Box::new(
+                    Spanned::from(Expression::ArrayAccess {
+                        array: Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..])))),
+                        index: Some(Box::new(Spanned::from(Expression::Literal(Literal::Integer(0i64)))))
+                    })
+                ),
+                index: Some(Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"bar"[..])))))
+            }
+        );
+
+        assert_eq!(postfix(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_array_access_on_an_array() {
+        let input  = b"[1, 2][1]";
+        let output = Result::Done(
+            &b""[..],
+            Expression::ArrayAccess {
+                array: Box::new(
+                    Spanned::from(Expression::Array(
+                        vec![
+                            (None, Spanned::from(Expression::Literal(Literal::Integer(1i64)))),
+                            (None, Spanned::from(Expression::Literal(Literal::Integer(2i64))))
+                        ]
+                    ))
+                ),
+                index: Some(Box::new(Spanned::from(Expression::Literal(Literal::Integer(1i64)))))
+            }
+        );
+
+        assert_eq!(postfix(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_array_access_with_curly_brackets() {
+        let input  = b"$foo{0}[1]";
+        let output = Result::Done(
+            &b""[..],
+            Expression::ArrayAccess {
+                array: Box::new(
+                    Spanned::from(Expression::ArrayAccess {
+                        array: Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..])))),
+                        index: Some(Box::new(Spanned::from(Expression::Literal(Literal::Integer(0i64)))))
+                    })
+                ),
+                index: Some(Box::new(Spanned::from(Expression::Literal(Literal::Integer(1i64)))))
+            }
+        );
+
+        version::set_target(Version::Php74);
+
+        let postfix_output    = postfix(input);
+        let expression_output = expression(input);
+
+        version::set_target(version::LATEST);
+
+        assert_eq!(postfix_output, output);
+        assert_eq!(expression_output, output);
+    }
+
+    #[test]
+    fn case_array_access_with_curly_brackets_is_reported() {
+        let input = b"['foo' { 1 }, $bar{$baz}]";
+
+        version::set_target(Version::Php70);
+
+        let (_, diagnostics) = diagnostics::collect(&input[..], expression);
+
+        version::set_target(version::LATEST);
+
+        assert_eq!(
+            diagnostics,
+            vec![
+                Diagnostic {
+                    kind: DiagnosticKind::CurlyBracketOffset,
+                    span: 7..12
+                },
+                Diagnostic {
+                    kind: DiagnosticKind::CurlyBracketOffset,
+                    span: 18..24
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn case_invalid_array_access_with_curly_brackets_and_no_index() {
+        let input  = b"$foo{}";
+        let output = Result::Done(&b"{}"[..], Expression::Variable(Variable::Name(&b"foo"[..])));
+
+        version::set_target(Version::Php74);
+
+        let postfix_output = postfix(input);
+
+        version::set_target(version::LATEST);
+
+        assert_eq!(postfix_output, output);
+    }
+
+    #[test]
+    fn case_invalid_array_access_with_curly_brackets_since_php80() {
+        let input  = b"$foo{0}";
+        let output = Result::Done(&b"{0}"[..], Expression::Variable(Variable::Name(&b"foo"[..])));
+
+        assert_eq!(postfix(input), output);
+    }
+
+    #[test]
+    fn case_array_access_then_callable_creation() {
+        let input  = b"$foo['bar'](...)";
+        let output = Result::Done(
+            &b""[..],
+            Expression::CallableCreation(
+                Box::new(
+                    Spanned::from(Expression::ArrayAccess {
+                        array: Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..])))),
+                        index: Some(Box::new(Spanned::from(Expression::Literal(Literal::String(b"bar".to_vec())))))
+                    })
+                )
+            )
+        );
+
+        assert_eq!(postfix(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_invalid_array_access_not_closed() {
+        assert_eq!(postfix(b"$foo[42"), Result::Done(&b"[42"[..], Expression::Variable(Variable::Name(&b"foo"[..]))));
+    }
+
+    #[test]
+    fn case_callable_creation_from_a_name() {
+        let input  = b"strlen(...)";
+        let output = Result::Done(
+            &b""[..],
+            Expression::CallableCreation(
+                Box::new(Spanned::from(Expression::Name(Name::Unqualified(&b"strlen"[..]))))
+            )
+        );
+
+        assert_eq!(postfix(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_callable_creation_from_a_variable() {
+        let input  = b"$foo ( /* bar */ ... )";
+        let output = Result::Done(
+            &b""[..],
+            Expression::CallableCreation(
+                Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..]))))
+            )
+        );
+
+        assert_eq!(postfix(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_callable_creation_chained() {
+        let input  = b"$foo(...)(...)";
+        let output = Result::Done(
+            &b""[..],
+            Expression::CallableCreation(
+                Box::new(
+                    Spanned::from(Expression::CallableCreation(
+                        Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..]))))
+                    ))
+                )
+            )
+        );
+
+        assert_eq!(postfix(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_postfix_without_operator() {
+        let input  = b"$foo";
+        let output = Result::Done(&b""[..], Expression::Variable(Variable::Name(&b"foo"[..])));
+
+        assert_eq!(postfix(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_postfix_spans() {
+        let input  = b"$a->b [ 0 ] ()";
+        let source = |span: Span| &input[span.start..span.end];
+
+        match expression(input) {
+            Result::Done(_, Expression::Call { ref callee, ref arguments }) => {
+                assert!(arguments.is_empty());
+                assert_eq!(source(callee.span), &b"$a->b [ 0 ]"[..]);
+
+                match callee.node {
+                    Expression::ArrayAccess { ref array, index: Some(ref index) } => {
+                        assert_eq!(source(array.span), &b"$a->b"[..]);
+                        assert_eq!(source(index.span), &b"0"[..]);
+
+                        match array.node {
+                            Expression::PropertyFetch { ref object, ref property } => {
+                                assert_eq!(source(object.span), &b"$a"[..]);
+                                assert_eq!(source(property.span), &b"b"[..]);
+                            },
+
+                            _ => panic!("A property fetch was expected.")
+                        }
+                    },
+
+                    _ => panic!("An array access was expected.")
+                }
+            },
+
+            _ => panic!("A call was expected.")
+        }
+    }
+
+    #[test]
+    fn case_invalid_callable_creation_not_an_ellipsis() {
+        assert_eq!(postfix(b"strlen(..)"), Result::Done(&b"(..)"[..], Expression::Name(Name::Unqualified(&b"strlen"[..]))));
+    }
+
+    #[test]
+    fn case_property_fetch() {
+        let input  = b"$foo->bar";
+        let output = Result::Done(
+            &b""[..],
+            Expression::PropertyFetch {
+                object  : Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..])))),
+                property: Box::new(Spanned::from(Expression::Name(Name::Unqualified(&b"bar"[..]))))
+            }
+        );
+
+        assert_eq!(postfix(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_property_fetch_keyword_as_a_name() {
+        let input  = b"$foo->class";
+        let output = Result::Done(
+            &b""[..],
+            Expression::PropertyFetch {
+                object  : Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..])))),
+                property: Box::new(Spanned::from(Expression::Name(Name::Unqualified(&b"class"[..]))))
+            }
+        );
+
+        assert_eq!(postfix(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_property_fetch_variable() {
+        let input  = b"$foo /* a */ -> /* b */ $bar";
+        let output = Result::Done(
+            &b""[..],
+            Expression::PropertyFetch {
+                object  : Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..])))),
+                property: Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"bar"[..]))))
+            }
+        );
+
+        assert_eq!(postfix(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_property_fetch_expression() {
+        let input  = b"$foo->{'bar'}";
+        let output = Result::Done(
+            &b""[..],
+            Expression::PropertyFetch {
+                object  : Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..])))),
+                property: Box::new(Spanned::from(Expression::Literal(Literal::String(b"bar".to_vec()))))
+            }
+        );
+
+        assert_eq!(postfix(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_property_fetch_dynamic_variable() {
+        let input  = b"$foo->$$bar";
+        let output = Result::Done(
+            &b""[..],
+            Expression::PropertyFetch {
+                object  : Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..])))),
+                property: Box::new(
+                    Spanned::from(Expression::Variable(
+                        Variable::Dynamic(
+                            Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"bar"[..]))))
+                        )
+                    ))
+                )
+            }
+        );
+
+        assert_eq!(postfix(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_property_fetch_variable_method_call() {
+        let input  = b"$foo->$bar($baz)";
+        let output = Result::Done(
+            &b""[..],
+            Expression::MethodCall {
+                object   : Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..])))),
+                method   : Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"bar"[..])))),
+                arguments: vec![
+                    Spanned::from(Argument {
+                        name       : None,
+                        is_unpacked: false,
+                        value      : Spanned::from(Expression::Variable(Variable::Name(&b"baz"[..])))
+                    })
+                ]
+            }
+        );
+
+        assert_eq!(postfix(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_property_fetch_chained() {
+        let input  = b"$a->b->c[0]";
+        let output = Result::Done(
+            &b""[..],
+            Expression::ArrayAccess {
+                array: Box::new(
+                    Spanned::from(Expression::PropertyFetch {
+                        object  : Box::new(
+                            Spanned::from(Expression::PropertyFetch {
+                                object  : Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"a"[..])))),
+                                property: Box::new(Spanned::from(Expression::Name(Name::Unqualified(&b"b"[..]))))
+                            })
+                        ),
+                        property: Box::new(Spanned::from(Expression::Name(Name::Unqualified(&b"c"[..]))))
+                    })
+                ),
+                index: Some(Box::new(Spanned::from(Expression::Literal(Literal::Integer(0i64)))))
+            }
+        );
+
+        assert_eq!(postfix(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_invalid_property_fetch_missing_property() {
+        assert_eq!(postfix(b"$foo->;"), Result::Done(&b"->;"[..], Expression::Variable(Variable::Name(&b"foo"[..]))));
+    }
+
+    #[test]
+    fn case_nullsafe_property_fetch() {
+        let input  = b"$foo?->bar";
+        let output = Result::Done(
+            &b""[..],
+            Expression::NullsafePropertyFetch {
+                object  : Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..])))),
+                property: Box::new(Spanned::from(Expression::Name(Name::Unqualified(&b"bar"[..]))))
+            }
+        );
+
+        assert_eq!(postfix(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_nullsafe_property_fetch_mixed_with_property_fetch() {
+        let input  = b"$a?->b->c /* d */ ?-> /* e */ $f";
+        let output = Result::Done(
+            &b""[..],
+            Expression::NullsafePropertyFetch {
+                object  : Box::new(
+                    Spanned::from(Expression::PropertyFetch {
+                        object  : Box::new(
+                            Spanned::from(Expression::NullsafePropertyFetch {
+                                object  : Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"a"[..])))),
+                                property: Box::new(Spanned::from(Expression::Name(Name::Unqualified(&b"b"[..]))))
+                            })
+                        ),
+                        property: Box::new(Spanned::from(Expression::Name(Name::Unqualified(&b"c"[..]))))
+                    })
+                ),
+                property: Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"f"[..]))))
+            }
+        );
+
+        assert_eq!(postfix(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_invalid_nullsafe_property_fetch_incomplete_operator() {
+        assert_eq!(postfix(b"$foo?-bar"), Result::Done(&b"?-bar"[..], Expression::Variable(Variable::Name(&b"foo"[..]))));
+    }
+
+    #[test]
+    fn case_static_fetch_constant() {
+        let input  = b"Foo\\Bar::BAZ";
+        let output = Result::Done(
+            &b""[..],
+            Expression::StaticFetch {
+                class : Box::new(Spanned::from(Expression::Name(Name::Qualified(vec![&b"Foo"[..], &b"Bar"[..]])))),
+                member: Box::new(Spanned::from(Expression::Name(Name::Unqualified(&b"BAZ"[..]))))
+            }
+        );
+
+        assert_eq!(postfix(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_static_fetch_property() {
+        let input  = b"Foo /* a */ :: /* b */ $bar";
+        let output = Result::Done(
+            &b""[..],
+            Expression::StaticFetch {
+                class : Box::new(Spanned::from(Expression::Name(Name::Unqualified(&b"Foo"[..])))),
+                member: Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"bar"[..]))))
+            }
+        );
+
+        assert_eq!(postfix(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_static_fetch_on_a_variable() {
+        let input  = b"$foo::BAR";
+        let output = Result::Done(
+            &b""[..],
+            Expression::StaticFetch {
+                class : Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..])))),
+                member: Box::new(Spanned::from(Expression::Name(Name::Unqualified(&b"BAR"[..]))))
+            }
+        );
+
+        assert_eq!(postfix(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    macro_rules! test_static_fetch_relative_scope {
+        ($test_case_name:ident: ($input:expr, $scope:expr)) => (
+            #[test]
+            fn $test_case_name() {
+                let output = Result::Done(
+                    &b""[..],
+                    Expression::StaticFetch {
+                        class : Box::new(Spanned::from(Expression::RelativeScope($scope))),
+                        member: Box::new(Spanned::from(Expression::Name(Name::Unqualified(&b"foo"[..]))))
+                    }
+                );
+
+                assert_eq!(postfix($input), output);
+                assert_eq!(expression($input), output);
+            }
+        );
+    }
+
+    test_static_fetch_relative_scope!(case_static_fetch_self:   (b"self::foo", RelativeScope::Current));
+    test_static_fetch_relative_scope!(case_static_fetch_parent: (b"parent::foo", RelativeScope::Parent));
+    test_static_fetch_relative_scope!(case_static_fetch_static: (b"static::foo", RelativeScope::Static));
+    test_static_fetch_relative_scope!(case_static_fetch_static_case_insensitive: (b"StAtIc :: foo", RelativeScope::Static));
+
+    #[test]
+    fn case_self_without_static_fetch_is_a_name() {
+        let input  = b"self";
+        let output = Result::Done(&b""[..], Expression::Name(Name::Unqualified(&b"self"[..])));
+
+        assert_eq!(postfix(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_static_fetch_then_property_fetch() {
+        let input  = b"parent::$foo->bar";
+        let output = Result::Done(
+            &b""[..],
+            Expression::PropertyFetch {
+                object  : Box::new(
+                    Spanned::from(Expression::StaticFetch {
+                        class : Box::new(Spanned::from(Expression::RelativeScope(RelativeScope::Parent))),
+                        member: Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..]))))
+                    })
+                ),
+                property: Box::new(Spanned::from(Expression::Name(Name::Unqualified(&b"bar"[..]))))
+            }
+        );
+
+        assert_eq!(postfix(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_class_name_constant() {
+        let input  = b"Foo\\Bar::class";
+        let output = Result::Done(
+            &b""[..],
+            Expression::ClassNameConstant(
+                Box::new(Spanned::from(Expression::Name(Name::Qualified(vec![&b"Foo"[..], &b"Bar"[..]]))))
+            )
+        );
+
+        assert_eq!(postfix(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_class_name_constant_on_a_variable() {
+        let input  = b"$foo /* bar */ :: CLASS";
+        let output = Result::Done(
+            &b""[..],
+            Expression::ClassNameConstant(
+                Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..]))))
+            )
+        );
+
+        assert_eq!(postfix(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_class_name_constant_on_a_relative_scope() {
+        let input  = b"static::class";
+        let output = Result::Done(
+            &b""[..],
+            Expression::ClassNameConstant(
+                Box::new(Spanned::from(Expression::RelativeScope(RelativeScope::Static)))
+            )
+        );
+
+        assert_eq!(postfix(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_static_fetch_constant_starting_like_class() {
+        let input  = b"Foo::classes";
+        let output = Result::Done(
+            &b""[..],
+            Expression::StaticFetch {
+                class : Box::new(Spanned::from(Expression::Name(Name::Unqualified(&b"Foo"[..])))),
+                member: Box::new(Spanned::from(Expression::Name(Name::Unqualified(&b"classes"[..]))))
+            }
+        );
+
+        assert_eq!(postfix(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_dynamic_class_constant() {
+        let input  = b"Foo::{$bar}";
+        let output = Result::Done(
+            &b""[..],
+            Expression::DynamicClassConstant {
+                class: Box::new(Spanned::from(Expression::Name(Name::Unqualified(&b"Foo"[..])))),
+                name : Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"bar"[..]))))
+            }
+        );
+
+        assert_eq!(postfix(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_dynamic_class_constant_is_not_a_class_name_constant() {
+        let input  = b"Foo::{'class'}";
+        let output = Result::Done(
+            &b""[..],
+            Expression::DynamicClassConstant {
+                class: Box::new(Spanned::from(Expression::Name(Name::Unqualified(&b"Foo"[..])))),
+                name : Box::new(Spanned::from(Expression::Literal(Literal::String(b"class".to_vec()))))
+            }
+        );
+
+        assert_eq!(postfix(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_invalid_dynamic_class_constant_before_php_83() {
+        let input = b"Foo::{$bar}";
+
+        version::set_target(Version::Php82);
+        let output = postfix(input);
+        version::set_target(version::LATEST);
+
+        assert_eq!(output, Result::Done(&b"::{$bar}"[..], Expression::Name(Name::Unqualified(&b"Foo"[..]))));
+    }
+
+    #[test]
+    fn case_static_fetch_expression_called() {
+        let input  = b"Foo::{$bar}()";
+        let output = Result::Done(
+            &b""[..],
+            Expression::StaticMethodCall {
+                class    : Box::new(Spanned::from(Expression::Name(Name::Unqualified(&b"Foo"[..])))),
+                method   : Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"bar"[..])))),
+                arguments: vec![]
+            }
+        );
+
+        assert_eq!(postfix(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_static_fetch_dynamic_variable() {
+        let input  = b"Foo::$$bar";
+        let output = Result::Done(
+            &b""[..],
+            Expression::StaticFetch {
+                class : Box::new(Spanned::from(Expression::Name(Name::Unqualified(&b"Foo"[..])))),
+                member: Box::new(
+                    Spanned::from(Expression::Variable(
+                        Variable::Dynamic(
+                            Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"bar"[..]))))
+                        )
+                    ))
+                )
+            }
+        );
+
+        assert_eq!(postfix(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_invalid_static_fetch_missing_member() {
+        assert_eq!(postfix(b"Foo::;"), Result::Done(&b"::;"[..], Expression::Name(Name::Unqualified(&b"Foo"[..]))));
+    }
+
+    #[test]
+    fn case_call_without_arguments() {
+        let input  = b"foo()";
+        let output = Result::Done(
+            &b""[..],
+            Expression::Call {
+                callee   : Box::new(Spanned::from(Expression::Name(Name::Unqualified(&b"foo"[..])))),
+                arguments: vec![]
+            }
+        );
+
+        assert_eq!(postfix(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_call_variable() {
+        let input  = b"$foo($bar)";
+        let output = Result::Done(
+            &b""[..],
+            Expression::Call {
+                callee   : Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..])))),
+                arguments: vec![
+                    Spanned::from(Argument {
+                        name       : None,
+                        is_unpacked: false,
+                        value      : Spanned::from(Expression::Variable(Variable::Name(&b"bar"[..])))
+                    })
+                ]
+            }
+        );
+
+        assert_eq!(postfix(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_call_with_arguments() {
+        let input  = b"\\foo /* a */ ( /* b */ $a, /* c */ $b, )";
+        let output = Result::Done(
+            &b""[..],
+            Expression::Call {
+                callee   : Box::new(Spanned::from(Expression::Name(Name::FullyQualified(vec![&b"foo"[..]])))),
+                arguments: vec![
+                    Spanned::from(Argument {
+                        name       : None,
+                        is_unpacked: false,
+                        value      : Spanned::from(Expression::Variable(Variable::Name(&b"a"[..])))
+                    }),
+                    Spanned::from(Argument {
+                        name       : None,
+                        is_unpacked: false,
+                        value      : Spanned::from(Expression::Variable(Variable::Name(&b"b"[..])))
+                    })
+                ]
+            }
+        );
+
+        assert_eq!(postfix(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_call_method_chained() {
+        let input  = b"$obj->m(1)(2)";
+        let output = Result::Done(
+            &b""[..],
+            Expression::Call {
+                callee   : Box::new(
+                    Spanned::from(Expression::MethodCall {
+                        object   : Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"obj"[..])))),
+                        method   : Box::new(Spanned::from(Expression::Name(Name::Unqualified(&b"m"[..])))),
+                        arguments: vec![
+                            Spanned::from(Argument {
+                                name       : None,
+                                is_unpacked: false,
+                                value      : Spanned::from(Expression::Literal(Literal::Integer(1i64)))
+                            })
+                        ]
+                    })
+                ),
+                arguments: vec![
+                    Spanned::from(Argument {
+                        name       : None,
+                        is_unpacked: false,
+                        value      : Spanned::from(Expression::Literal(Literal::Integer(2i64)))
+                    })
+                ]
+            }
+        );
+
+        assert_eq!(postfix(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_call_method_is_not_a_call_of_a_property() {
+        let method_call = Expression::MethodCall {
+            object   : Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"o"[..])))),
+            method   : Box::new(Spanned::from(Expression::Name(Name::Unqualified(&b"m"[..])))),
+            arguments: vec![]
+        };
+        let property_call = Expression::Call {
+            callee   : Box::new(
+                Spanned::from(Expression::PropertyFetch {
+                    object  : Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"o"[..])))),
+                    property: Box::new(Spanned::from(Expression::Name(Name::Unqualified(&b"m"[..]))))
+                })
+            ),
+            arguments: vec![]
+        };
+
+        assert_eq!(postfix(b"$o->m()"), Result::Done(&b""[..], method_call));
+        assert_eq!(postfix(b"($o->m)()"), Result::Done(&b""[..], property_call));
+    }
+
+    #[test]
+    fn case_call_nullsafe_method_is_not_a_call_of_a_property() {
+        let method_call = Expression::NullsafeMethodCall {
+            object   : Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"o"[..])))),
+            method   : Box::new(Spanned::from(Expression::Name(Name::Unqualified(&b"m"[..])))),
+            arguments: vec![]
+        };
+        let property_call = Expression::Call {
+            callee   : Box::new(
+                Spanned::from(Expression::NullsafePropertyFetch {
+                    object  : Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"o"[..])))),
+                    property: Box::new(Spanned::from(Expression::Name(Name::Unqualified(&b"m"[..]))))
+                })
+            ),
+            arguments: vec![]
+        };
+
+        assert_eq!(postfix(b"$o?->m()"), Result::Done(&b""[..], method_call));
+        assert_eq!(postfix(b"($o?->m)()"), Result::Done(&b""[..], property_call));
+    }
+
+    #[test]
+    fn case_call_static_method_is_not_a_call_of_a_static_property() {
+        let method_call = Expression::StaticMethodCall {
+            class    : Box::new(Spanned::from(Expression::Name(Name::Unqualified(&b"Foo"[..])))),
+            method   : Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"bar"[..])))),
+            arguments: vec![]
+        };
+        let property_call = Expression::Call {
+            callee   : Box::new(
+                Spanned::from(Expression::StaticFetch {
+                    class : Box::new(Spanned::from(Expression::Name(Name::Unqualified(&b"Foo"[..])))),
+                    member: Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"bar"[..]))))
+                })
+            ),
+            arguments: vec![]
+        };
+
+        assert_eq!(postfix(b"Foo::$bar()"), Result::Done(&b""[..], method_call));
+        assert_eq!(postfix(b"(Foo::$bar)()"), Result::Done(&b""[..], property_call));
+    }
+
+    #[test]
+    fn case_call_static_method() {
+        let input  = b"parent::__construct($foo)";
+        let output = Result::Done(
+            &b""[..],
+            Expression::StaticMethodCall {
+                class    : Box::new(Spanned::from(Expression::RelativeScope(RelativeScope::Parent))),
+                method   : Box::new(Spanned::from(Expression::Name(Name::Unqualified(&b"__construct"[..])))),
+                arguments: vec![
+                    Spanned::from(Argument {
+                        name       : None,
+                        is_unpacked: false,
+                        value      : Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..])))
+                    })
+                ]
+            }
+        );
+
+        assert_eq!(postfix(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_call_then_array_access() {
+        let input  = b"foo($bar)[0]";
+        let output = Result::Done(
+            &b""[..],
+            Expression::ArrayAccess {
+                array: Box::new(
+                    Spanned::from(Expression::Call {
+                        callee   : Box::new(Spanned::from(Expression::Name(Name::Unqualified(&b"foo"[..])))),
+                        arguments: vec![
+                            Spanned::from(Argument {
+                                name       : None,
+                                is_unpacked: false,
+                                value      : Spanned::from(Expression::Variable(Variable::Name(&b"bar"[..])))
+                            })
+                        ]
+                    })
+                ),
+                index: Some(Box::new(Spanned::from(Expression::Literal(Literal::Integer(0i64)))))
+            }
+        );
+
+        assert_eq!(postfix(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_callable_creation_from_a_method() {
+        let input  = b"$obj->method(...)";
+        let output = Result::Done(
+            &b""[..],
+            Expression::CallableCreation(
+                Box::new(
+                    Spanned::from(Expression::PropertyFetch {
+                        object  : Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"obj"[..])))),
+                        property: Box::new(Spanned::from(Expression::Name(Name::Unqualified(&b"method"[..]))))
+                    })
+                )
+            )
+        );
+
+        assert_eq!(postfix(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_callable_creation_from_a_static_method() {
+        let input  = b"Foo::bar(...)";
+        let output = Result::Done(
+            &b""[..],
+            Expression::CallableCreation(
+                Box::new(
+                    Spanned::from(Expression::StaticFetch {
+                        class : Box::new(Spanned::from(Expression::Name(Name::Unqualified(&b"Foo"[..])))),
+                        member: Box::new(Spanned::from(Expression::Name(Name::Unqualified(&b"bar"[..]))))
+                    })
+                )
+            )
+        );
+
+        assert_eq!(postfix(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_invalid_call_not_closed() {
+        assert_eq!(postfix(b"foo($a;"), Result::Done(&b"($a;"[..], Expression::Name(Name::Unqualified(&b"foo"[..]))));
+    }
+
+    #[test]
+    fn case_call_with_unpacked_and_named_arguments() {
+        let input  = b"foo(...$bar, baz: 42)";
+        let output = Result::Done(
+            &b""[..],
+            Expression::Call {
+                callee   : Box::new(Spanned::from(Expression::Name(Name::Unqualified(&b"foo"[..])))),
+                arguments: vec![
+                    Spanned::from(Argument {
+                        name       : None,
+                        is_unpacked: true,
+                        value      : Spanned::from(Expression::Variable(Variable::Name(&b"bar"[..])))
+                    }),
+                    Spanned::from(Argument {
+                        name       : Some(&b"baz"[..]),
+                        is_unpacked: false,
+                        value      : Spanned::from(Expression::Literal(Literal::Integer(42i64)))
+                    })
+                ]
+            }
+        );
+
+        assert_eq!(postfix(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_dereferencable_parenthesized_new() {
+        let input  = b"(new Foo($x))->method()[0]";
+        let output = Result::Done(
+            &b""[..],
+            Expression::ArrayAccess {
+                array: Box::new(
+                    Spanned::from(Expression::MethodCall {
+                        object   : Box::new(
+                            Spanned::from(Expression::New {
+                                class    : Box::new(Spanned::from(Expression::Name(Name::Unqualified(&b"Foo"[..])))),
+                                arguments: vec![
+                                    Spanned::from(Argument {
+                                        name       : None,
+                                        is_unpacked: false,
+                                        value      : Spanned::from(Expression::Variable(Variable::Name(&b"x"[..])))
+                                    })
+                                ]
+                            })
+                        ),
+                        method   : Box::new(Spanned::from(Expression::Name(Name::Unqualified(&b"method"[..])))),
+                        arguments: vec![]
+                    })
+                ),
+                index: Some(Box::new(Spanned::from(Expression::Literal(Literal::Integer(0i64)))))
+            }
+        );
+
+        assert_eq!(postfix(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_new_with_arguments_is_not_dereferencable() {
+        let input  = b"new Foo()->bar";
+        let output = Result::Done(
+            &b"->bar"[..],
+            Expression::New {
+                class    : Box::new(Spanned::from(Expression::Name(Name::Unqualified(&b"Foo"[..])))),
+                arguments: vec![]
+            }
+        );
+
+        assert_eq!(postfix(input), output);
+    }
+
+    #[test]
+    fn case_new_without_arguments_is_not_dereferencable() {
+        let input  = b"new Foo->bar";
+        let output = Result::Done(
+            &b"->bar"[..],
+            Expression::New {
+                class    : Box::new(Spanned::from(Expression::Name(Name::Unqualified(&b"Foo"[..])))),
+                arguments: vec![]
+            }
+        );
+
+        assert_eq!(postfix(input), output);
+    }
+
+    #[test]
+    fn case_anonymous_function_is_not_dereferencable() {
+        let input  = b"function () {}()";
+        let output = Result::Done(
+            &b"()"[..],
+            Expression::Closure {
+                is_static        : false,
+                returns_reference: false,
+                parameters       : vec![],
+                captures         : vec![],
+                return_type      : None,
+                body             : vec![]
+            }
+        );
+
+        assert_eq!(postfix(input), output);
+    }
+
+    #[test]
+    fn case_array_access_on_an_array_constructor() {
+        let input  = b"array('a')[0]";
+        let output = Result::Done(
+            &b""[..],
+            Expression::ArrayAccess {
+                array: Box::new(Spanned::from(Expression::Array(vec![(None, Spanned::from(Expression::Literal(Literal::String(b"a".to_vec()))))]))),
+                index: Some(Box::new(Spanned::from(Expression::Literal(Literal::Integer(0i64)))))
+            }
+        );
+
+        assert_eq!(postfix(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_array_access_on_a_string() {
+        let input  = b"'hello'[1]";
+        let output = Result::Done(
+            &b""[..],
+            Expression::ArrayAccess {
+                array: Box::new(Spanned::from(Expression::Literal(Literal::String(b"hello".to_vec())))),
+                index: Some(Box::new(Spanned::from(Expression::Literal(Literal::Integer(1i64)))))
+            }
+        );
+
+        assert_eq!(postfix(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_call_on_a_string() {
+        let input  = b"'strlen'($foo)";
+        let output = Result::Done(
+            &b""[..],
+            Expression::Call {
+                callee   : Box::new(Spanned::from(Expression::Literal(Literal::String(b"strlen".to_vec())))),
+                arguments: vec![
+                    Spanned::from(Argument {
+                        name       : None,
+                        is_unpacked: false,
+                        value      : Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..])))
+                    })
+                ]
+            }
+        );
+
+        assert_eq!(postfix(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_array_access_on_an_interpolated_string() {
+        let input  = b"\"foo$bar\"[0]";
+        let output = Result::Done(
+            &b""[..],
+            Expression::ArrayAccess {
+                array: Box::new(
+                    Spanned::from(Expression::InterpolatedString(vec![
+                        StringPart::Bytes(b"foo".to_vec()),
+                        StringPart::Expression(Spanned::from(Expression::Variable(Variable::Name(&b"bar"[..]))))
+                    ]))
+                ),
+                index: Some(Box::new(Spanned::from(Expression::Literal(Literal::Integer(0i64)))))
+            }
+        );
+
+        assert_eq!(postfix(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_number_is_not_dereferencable() {
+        assert_eq!(postfix(b"42[0]"), Result::Done(&b"[0]"[..], Expression::Literal(Literal::Integer(42i64))));
+    }
+
+    #[test]
+    fn case_nowdoc_is_not_dereferencable() {
+        let input  = b"<<<'FOO'\nbar\nFOO\n[0]";
+        let output = Result::Done(
+            &b"\n[0]"[..],
+            Expression::Literal(
+                Literal::Nowdoc {
+                    label      : b"FOO".to_vec(),
+                    indentation: Vec::new(),
+                    value      : b"bar".to_vec()
+                }
+            )
+        );
+
+        assert_eq!(postfix(input), output);
+    }
+
+    #[test]
+    fn case_call_method_named_after_a_semi_reserved_keyword() {
+        let input  = b"$foo->empty()->unset()";
+        let output = Result::Done(
+            &b""[..],
+            Expression::MethodCall {
+                object   : Box::new(
+                    Spanned::from(Expression::MethodCall {
+                        object   : Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..])))),
+                        method   : Box::new(Spanned::from(Expression::Name(Name::Unqualified(&b"empty"[..])))),
+                        arguments: vec![]
+                    })
+                ),
+                method   : Box::new(Spanned::from(Expression::Name(Name::Unqualified(&b"unset"[..])))),
+                arguments: vec![]
+            }
+        );
+
+        assert_eq!(postfix(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_call_static_method_named_after_a_semi_reserved_keyword() {
+        let input  = b"Foo::print($bar)";
+        let output = Result::Done(
+            &b""[..],
+            Expression::StaticMethodCall {
+                class    : Box::new(Spanned::from(Expression::Name(Name::Unqualified(&b"Foo"[..])))),
+                method   : Box::new(Spanned::from(Expression::Name(Name::Unqualified(&b"print"[..])))),
+                arguments: vec![
+                    Spanned::from(Argument {
+                        name       : None,
+                        is_unpacked: false,
+                        value      : Spanned::from(Expression::Variable(Variable::Name(&b"bar"[..])))
+                    })
+                ]
+            }
+        );
+
+        assert_eq!(postfix(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_call_static_method_named_class() {
+        let input  = b"Foo::class ()";
+        let output = Result::Done(
+            &b""[..],
+            Expression::StaticMethodCall {
+                class    : Box::new(Spanned::from(Expression::Name(Name::Unqualified(&b"Foo"[..])))),
+                method   : Box::new(Spanned::from(Expression::Name(Name::Unqualified(&b"class"[..])))),
+                arguments: vec![]
+            }
+        );
+
+        assert_eq!(postfix(input), output);
+        assert_eq!(expression(input), output);
+    }
+}
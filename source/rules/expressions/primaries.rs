@@ -37,28 +37,70 @@
 
 use std::result::Result as StdResult;
 use super::expression;
-use super::super::literals::literal;
-use super::super::super::internal::fold_into_vector;
+use super::constants::initializer_expression;
+use super::postfixes::new_variable;
+use super::strings::{
+    shell_exec,
+    string_double_quoted,
+    string_heredoc
+};
+use super::super::attributes::attributes;
+use super::super::hooks::property_hooks;
+use super::super::literals::{
+    boolean,
+    literal,
+    null,
+    string,
+    string_single_quoted
+};
+use super::super::super::internal::{
+    fold_into_vector,
+    into_vector_mapper
+};
+use super::super::statements::class::class_body;
+use super::super::statements::compound::block;
 use super::super::tokens::{
+    name,
     qualified_name,
+    relative_scope,
     variable
 };
+use super::super::types::{
+    return_type,
+    type_declaration
+};
 use super::super::super::ast::{
+    Argument,
+    Attribute,
+    ConstantExpression,
     Expression,
+    IncludeKind,
     Literal,
+    MagicConstant,
+    MatchArm,
     Name,
-    Variable
+    Parameter,
+    PropertyHook,
+    RelativeScope,
+    Type,
+    Variable,
+    Visibility
 };
 use super::super::super::internal::{
     Error,
     ErrorKind
 };
+use super::super::super::span::Spanned;
 use super::super::super::tokens;
+use super::super::super::version::{
+    self,
+    Version
+};
 
-/// Intrinsic errors.
+/// Intrinsic errors, from 100.
 pub enum IntrinsicError {
     /// The exit code is reserved (only 255 is reserved to PHP).
-    ReservedExitCode,
+    ReservedExitCode = 100,
     /// The exit code is out of range if greater than 255.
     OutOfRangeExitCode,
     /// The list constructor must contain at least one item.
@@ -68,26 +110,96 @@ pub enum IntrinsicError {
 named!(
     pub primary<Expression>,
     alt!(
-        variable       => { variable_mapper }
-      | qualified_name => { qualified_name_mapper }
-      | literal        => { literal_mapper }
+        dereferencable
+      | non_dereferencable
+    )
+);
+
+named!(
+    pub dereferencable<Expression>,
+    alt!(
+        variable               => { variable_mapper }
+      | dynamic_variable       => { variable_mapper }
+      | string_double_quoted
+      | dereferencable_literal => { literal_mapper }
+      | preceded!(
+            not!(
+                alt!(
+                    string_heredoc
+                  | string => { literal_mapper }
+                )
+            ),
+            qualified_name
+        )                      => { qualified_name_mapper }
+      | magic_constant
       | array
-      | intrinsic
       | preceded!(
             tag!(tokens::LEFT_PARENTHESIS),
             terminated!(
                 first!(expression),
-                first!(tag!(tokens::RIGHT_PARENTHESIS))
+                first!(expect!("dereferencable", tokens::RIGHT_PARENTHESIS))
             )
         )
     )
 );
 
+named!(
+    non_dereferencable<Expression>,
+    alt!(
+        string_heredoc
+      | shell_exec
+      | literal => { literal_mapper }
+      | intrinsic
+      | new
+      | anonymous_function
+      | arrow_function
+      | match_expression
+      | throw
+    )
+);
+
+named!(
+    dereferencable_literal<Literal>,
+    alt!(
+        null
+      | boolean
+      | string_single_quoted
+    )
+);
+
 #[inline(always)]
 fn variable_mapper<'a>(variable: Variable<'a>) -> Expression<'a> {
     Expression::Variable(variable)
 }
 
+named!(
+    pub dynamic_variable<Variable>,
+    preceded!(
+        tag!(tokens::VARIABLE),
+        alt!(
+            spanned!(variable)         => { variable_variable_mapper }
+          | spanned!(dynamic_variable) => { variable_variable_mapper }
+          | preceded!(
+                tag!(tokens::LEFT_CURLY_BRACKET),
+                terminated!(
+                    first!(spanned!(expression)),
+                    first!(expect!("dynamic_variable", tokens::RIGHT_CURLY_BRACKET))
+                )
+            ) => { dynamic_variable_mapper }
+        )
+    )
+);
+
+#[inline(always)]
+fn variable_variable_mapper<'a>(variable: Spanned<Variable<'a>>) -> Variable<'a> {
+    Variable::Dynamic(Box::new(variable.map(Expression::Variable)))
+}
+
+#[inline(always)]
+fn dynamic_variable_mapper<'a>(expression: Spanned<Expression<'a>>) -> Variable<'a> {
+    Variable::Dynamic(Box::new(expression))
+}
+
 #[inline(always)]
 fn qualified_name_mapper<'a>(name: Name<'a>) -> Expression<'a> {
     Expression::Name(name)
@@ -98,6 +210,33 @@ fn literal_mapper<'a>(literal: Literal) -> Expression<'a> {
     Expression::Literal(literal)
 }
 
+#[inline(always)]
+fn relative_scope_mapper<'a>(scope: RelativeScope) -> Expression<'a> {
+    Expression::RelativeScope(scope)
+}
+
+named!(
+    pub magic_constant<Expression>,
+    map!(
+        alt!(
+            keyword!(tokens::MAGIC_CLASS)     => { |_| MagicConstant::Class }
+          | keyword!(tokens::MAGIC_DIRECTORY) => { |_| MagicConstant::Directory }
+          | keyword!(tokens::MAGIC_FILE)      => { |_| MagicConstant::File }
+          | keyword!(tokens::MAGIC_FUNCTION)  => { |_| MagicConstant::Function }
+          | keyword!(tokens::MAGIC_LINE)      => { |_| MagicConstant::Line }
+          | keyword!(tokens::MAGIC_METHOD)    => { |_| MagicConstant::Method }
+          | keyword!(tokens::MAGIC_NAMESPACE) => { |_| MagicConstant::Namespace }
+          | keyword!(tokens::MAGIC_TRAIT)     => { |_| MagicConstant::Trait }
+        ),
+        magic_constant_mapper
+    )
+);
+
+#[inline(always)]
+fn magic_constant_mapper<'a>(constant: MagicConstant) -> Expression<'a> {
+    Expression::MagicConstant(constant)
+}
+
 named!(
     pub array<Expression>,
     alt!(
@@ -105,12 +244,12 @@ named!(
             tag!(tokens::LEFT_SQUARE_BRACKET),
             alt!(
                 map_res!(
-                    first!(tag!(tokens::RIGHT_SQUARE_BRACKET)),
+                    first!(expect!("array", tokens::RIGHT_SQUARE_BRACKET)),
                     empty_array_mapper
                 )
               | terminated!(
                     array_pairs,
-                    first!(tag!(tokens::RIGHT_SQUARE_BRACKET))
+                    first!(expect!("array", tokens::RIGHT_SQUARE_BRACKET))
                 )
             )
         )
@@ -121,12 +260,12 @@ named!(
             ),
             alt!(
                 map_res!(
-                    first!(tag!(tokens::RIGHT_PARENTHESIS)),
+                    first!(expect!("array", tokens::RIGHT_PARENTHESIS)),
                     empty_array_mapper
                 )
               | terminated!(
                     array_pairs,
-                    first!(tag!(tokens::RIGHT_PARENTHESIS))
+                    first!(expect!("array", tokens::RIGHT_PARENTHESIS))
                 )
             )
         )
@@ -154,23 +293,27 @@ named!(
 );
 
 named!(
-    array_pair<(Option<Expression>, Expression)>,
+    array_pair<(Option<Spanned<Expression>>, Spanned<Expression>)>,
     chain!(
         key: opt!(
             terminated!(
-                expression,
+                spanned!(expression),
                 first!(tag!(tokens::MAP))
             )
         ) ~
-        value: alt!(
-            map_res!(
-                preceded!(
-                    first!(tag!(tokens::REFERENCE)),
-                    first!(expression)
-                ),
-                value_by_reference_array_mapper
+        value: first!(
+            spanned!(
+                alt!(
+                    map_res!(
+                        preceded!(
+                            tag!(tokens::REFERENCE),
+                            first!(spanned!(expression))
+                        ),
+                        value_by_reference_array_mapper
+                    )
+                  | expression
+                )
             )
-          | first!(expression)
         ),
         || { (key, value) }
     )
@@ -182,12 +325,12 @@ fn empty_array_mapper<'a>(_: &[u8]) -> StdResult<Expression<'a>, ()> {
 }
 
 #[inline(always)]
-fn value_by_reference_array_mapper<'a>(expression: Expression<'a>) -> StdResult<Expression<'a>, ()> {
+fn value_by_reference_array_mapper<'a>(expression: Spanned<Expression<'a>>) -> StdResult<Expression<'a>, ()> {
     Ok(Expression::Reference(Box::new(expression)))
 }
 
 #[inline(always)]
-fn into_array<'a>(expressions: Vec<(Option<Expression<'a>>, Expression<'a>)>) -> Expression<'a> {
+fn into_array<'a>(expressions: Vec<(Option<Spanned<Expression<'a>>>, Spanned<Expression<'a>>)>) -> Expression<'a> {
     Expression::Array(expressions)
 }
 
@@ -214,6 +357,7 @@ named!(
         intrinsic_empty
       | intrinsic_eval
       | intrinsic_exit
+      | intrinsic_include
       | intrinsic_isset
       | intrinsic_print
     )
@@ -225,14 +369,14 @@ named!(
         accumulator: map_res!(
             preceded!(
                 keyword!(tokens::ECHO),
-                first!(expression)
+                first!(spanned!(expression))
             ),
             into_vector_mapper
         ) ~
         result: fold_many0!(
             preceded!(
                 first!(tag!(tokens::COMMA)),
-                first!(expression)
+                first!(spanned!(expression))
             ),
             accumulator,
             fold_into_vector
@@ -242,12 +386,7 @@ named!(
 );
 
 #[inline(always)]
-fn into_vector_mapper<T>(item: T) -> StdResult<Vec<T>, ()> {
-    Ok(vec![item])
-}
-
-#[inline(always)]
-fn into_echo<'a>(expressions: Vec<Expression<'a>>) -> Expression<'a> {
+fn into_echo<'a>(expressions: Vec<Spanned<Expression<'a>>>) -> Expression<'a> {
     Expression::Echo(expressions)
 }
 
@@ -264,7 +403,7 @@ named!(
                     intrinsic_keyed_list
                   | intrinsic_unkeyed_list
                 ),
-                first!(tag!(tokens::RIGHT_PARENTHESIS))
+                first!(expect!("intrinsic_list", tokens::RIGHT_PARENTHESIS))
             )
         ),
         intrinsic_list_mapper
@@ -311,35 +450,95 @@ named!(
 );
 
 named!(
-    intrinsic_keyed_list_item< Option<(Option<Expression>, Expression)> >,
+    intrinsic_keyed_list_item< Option<(Option<Spanned<Expression>>, Spanned<Expression>)> >,
     chain!(
         key: terminated!(
-            expression,
+            spanned!(expression),
             first!(tag!(tokens::MAP))
         ) ~
-        value: first!(expression),
+        value: first!(spanned!(list_item_value)),
         || { Some((Some(key), value)) }
     )
 );
 
 named!(
-    intrinsic_unkeyed_list_item<(Option<Expression>, Expression)>,
+    intrinsic_unkeyed_list_item<(Option<Spanned<Expression>>, Spanned<Expression>)>,
     chain!(
-        value: expression,
+        value: spanned!(list_item_value),
         || { (None, value) }
     )
 );
 
+named!(
+    list_item_value<Expression>,
+    alt!(
+        map_res!(
+            preceded!(
+                tag!(tokens::REFERENCE),
+                first!(spanned!(expression))
+            ),
+            value_by_reference_array_mapper
+        )
+      | short_list
+      | expression
+    )
+);
+
+named!(
+    pub short_list<Expression>,
+    map_res!(
+        preceded!(
+            tag!(tokens::LEFT_SQUARE_BRACKET),
+            terminated!(
+                alt!(
+                    intrinsic_keyed_list
+                  | intrinsic_unkeyed_list
+                ),
+                first!(expect!("short_list", tokens::RIGHT_SQUARE_BRACKET))
+            )
+        ),
+        short_list_mapper
+    )
+);
+
 #[inline(always)]
-fn into_list<'a>(expressions: Vec<Option<(Option<Expression<'a>>, Expression<'a>)>>) -> Expression<'a> {
+fn into_list<'a>(expressions: Vec<Option<(Option<Spanned<Expression<'a>>>, Spanned<Expression<'a>>)>>) -> Expression<'a> {
     Expression::List(expressions)
 }
 
+/// A short list has a skipped element, or a short list as an element,
+/// otherwise it is an array.
+#[inline(always)]
+fn short_list_mapper<'a>(expression: Expression<'a>) -> StdResult<Expression<'a>, ()> {
+    match expression {
+        Expression::List(items) => {
+            let has_skipped_elements = items.iter().any(
+                |item| {
+                    match *item {
+                        Some((_, ref value)) => matches!(value.node, Expression::ShortList(_)),
+                        None                 => true
+                    }
+                }
+            );
+
+            if has_skipped_elements {
+                Ok(Expression::ShortList(items))
+            } else {
+                Err(())
+            }
+        },
+
+        _ => {
+            Err(())
+        }
+    }
+}
+
 #[inline(always)]
 fn intrinsic_list_mapper<'a>(expression: Expression<'a>) -> StdResult<Expression<'a>, Error<ErrorKind>> {
     match expression {
         Expression::List(items) => {
-            if items.iter().any(|ref item| item.is_some()) {
+            if items.iter().any(|item| item.is_some()) {
                 Ok(Expression::List(items))
             } else {
                 Err(Error::Code(ErrorKind::Custom(IntrinsicError::ListIsEmpty as u32)))
@@ -360,7 +559,7 @@ named!(
                 keyword!(tokens::UNSET),
                 preceded!(
                     first!(tag!(tokens::LEFT_PARENTHESIS)),
-                    first!(expression)
+                    first!(spanned!(expression))
                 )
             ),
             into_vector_mapper
@@ -369,19 +568,22 @@ named!(
             fold_many0!(
                 preceded!(
                     first!(tag!(tokens::COMMA)),
-                    first!(expression)
+                    first!(spanned!(expression))
                 ),
                 accumulator,
                 fold_into_vector
             ),
-            first!(tag!(tokens::RIGHT_PARENTHESIS))
+            terminated!(
+                opt!(first!(tag!(tokens::COMMA))),
+                first!(expect!("intrinsic_unset", tokens::RIGHT_PARENTHESIS))
+            )
         ),
         || { into_unset(result) }
     )
 );
 
 #[inline(always)]
-fn into_unset<'a>(expressions: Vec<Expression<'a>>) -> Expression<'a> {
+fn into_unset<'a>(expressions: Vec<Spanned<Expression<'a>>>) -> Expression<'a> {
     Expression::Unset(expressions)
 }
 
@@ -393,8 +595,8 @@ named!(
             preceded!(
                 first!(tag!(tokens::LEFT_PARENTHESIS)),
                 terminated!(
-                    first!(expression),
-                    first!(tag!(tokens::RIGHT_PARENTHESIS))
+                    first!(spanned!(expression)),
+                    first!(expect!("intrinsic_empty", tokens::RIGHT_PARENTHESIS))
                 )
             )
         ),
@@ -403,7 +605,7 @@ named!(
 );
 
 #[inline(always)]
-fn empty_mapper<'a>(expression: Expression<'a>) -> StdResult<Expression<'a>, ()> {
+fn empty_mapper<'a>(expression: Spanned<Expression<'a>>) -> StdResult<Expression<'a>, ()> {
     Ok(Expression::Empty(Box::new(expression)))
 }
 
@@ -415,8 +617,8 @@ named!(
             preceded!(
                 first!(tag!(tokens::LEFT_PARENTHESIS)),
                 terminated!(
-                    first!(expression),
-                    first!(tag!(tokens::RIGHT_PARENTHESIS))
+                    first!(spanned!(expression)),
+                    first!(expect!("intrinsic_eval", tokens::RIGHT_PARENTHESIS))
                 )
             )
         ),
@@ -425,7 +627,7 @@ named!(
 );
 
 #[inline(always)]
-fn eval_mapper<'a>(expression: Expression<'a>) -> StdResult<Expression<'a>, ()> {
+fn eval_mapper<'a>(expression: Spanned<Expression<'a>>) -> StdResult<Expression<'a>, ()> {
     Ok(Expression::Eval(Box::new(expression)))
 }
 
@@ -441,8 +643,8 @@ named!(
                 preceded!(
                     first!(tag!(tokens::LEFT_PARENTHESIS)),
                     terminated!(
-                        first!(expression),
-                        first!(tag!(tokens::RIGHT_PARENTHESIS))
+                        first!(spanned!(expression)),
+                        first!(expect!("intrinsic_exit", tokens::RIGHT_PARENTHESIS))
                     )
                 )
             )
@@ -452,10 +654,10 @@ named!(
 );
 
 #[inline(always)]
-fn exit_mapper<'a>(expression: Option<Expression<'a>>) -> StdResult<Expression<'a>, Error<ErrorKind>> {
+fn exit_mapper<'a>(expression: Option<Spanned<Expression<'a>>>) -> StdResult<Expression<'a>, Error<ErrorKind>> {
     match expression {
         Some(expression) => {
-            if let Expression::Literal(Literal::Integer(code)) = expression {
+            if let Expression::Literal(Literal::Integer(code)) = expression.node {
                 if code == 255 {
                     return Err(Error::Code(ErrorKind::Custom(IntrinsicError::ReservedExitCode as u32)));
                 } else if code > 255 {
@@ -472,6 +674,25 @@ fn exit_mapper<'a>(expression: Option<Expression<'a>>) -> StdResult<Expression<'
     }
 }
 
+named!(
+    intrinsic_include<Expression>,
+    chain!(
+        kind: alt!(
+            keyword!(tokens::INCLUDE_ONCE) => { |_| IncludeKind::IncludeOnce }
+          | keyword!(tokens::INCLUDE)      => { |_| IncludeKind::Include }
+          | keyword!(tokens::REQUIRE_ONCE) => { |_| IncludeKind::RequireOnce }
+          | keyword!(tokens::REQUIRE)      => { |_| IncludeKind::Require }
+        ) ~
+        path: first!(spanned!(expression)),
+        || {
+            Expression::Include {
+                kind: kind,
+                path: Box::new(path)
+            }
+        }
+    )
+);
+
 named!(
     intrinsic_isset<Expression>,
     chain!(
@@ -480,7 +701,7 @@ named!(
                 keyword!(tokens::ISSET),
                 preceded!(
                     first!(tag!(tokens::LEFT_PARENTHESIS)),
-                    first!(expression)
+                    first!(spanned!(expression))
                 )
             ),
             into_vector_mapper
@@ -489,19 +710,22 @@ named!(
             fold_many0!(
                 preceded!(
                     first!(tag!(tokens::COMMA)),
-                    first!(expression)
+                    first!(spanned!(expression))
                 ),
                 accumulator,
                 fold_into_vector
             ),
-            first!(tag!(tokens::RIGHT_PARENTHESIS))
+            terminated!(
+                opt!(first!(tag!(tokens::COMMA))),
+                first!(expect!("intrinsic_isset", tokens::RIGHT_PARENTHESIS))
+            )
         ),
         || { into_isset(result) }
     )
 );
 
 #[inline(always)]
-fn into_isset<'a>(expressions: Vec<Expression<'a>>) -> Expression<'a> {
+fn into_isset<'a>(expressions: Vec<Spanned<Expression<'a>>>) -> Expression<'a> {
     Expression::Isset(expressions)
 }
 
@@ -510,233 +734,608 @@ named!(
     map_res!(
         preceded!(
             keyword!(tokens::PRINT),
-            first!(expression)
+            first!(spanned!(expression))
         ),
         print_mapper
     )
 );
 
 #[inline(always)]
-fn print_mapper<'a>(expression: Expression<'a>) -> StdResult<Expression<'a>, ()> {
+fn print_mapper<'a>(expression: Spanned<Expression<'a>>) -> StdResult<Expression<'a>, ()> {
     Ok(Expression::Print(Box::new(expression)))
 }
 
 
-#[cfg(test)]
-mod tests {
-    use super::{
-        array,
-        intrinsic,
-        intrinsic_construct,
-        intrinsic_echo,
-        intrinsic_empty,
-        intrinsic_eval,
-        intrinsic_exit,
-        intrinsic_isset,
-        intrinsic_list,
-        intrinsic_operator,
-        intrinsic_print,
-        intrinsic_unset,
-        primary
-    };
-    use super::super::expression;
-    use super::super::super::super::ast::{
-        Expression,
-        Literal,
-        Name,
-        Variable
-    };
-    use super::super::super::super::internal::{
-        Error,
-        ErrorKind,
-        Result
-    };
+named!(
+    pub new<Expression>,
+    preceded!(
+        keyword!(tokens::NEW),
+        first!(
+            alt!(
+                anonymous_class
+              | chain!(
+                    class: spanned!(class_type_designator) ~
+                    arguments: opt!(complete!(first!(arguments))),
+                    || {
+                        Expression::New {
+                            class    : Box::new(class),
+                            arguments: arguments.unwrap_or(vec![])
+                        }
+                    }
+                )
+            )
+        )
+    )
+);
 
-    #[test]
-    fn case_array_empty() {
-        let input  = b"[ /* foo */ ]";
-        let output = Result::Done(&b""[..], Expression::Array(vec![]));
+named!(
+    anonymous_class<Expression>,
+    chain!(
+        keyword!(tokens::CLASS) ~
+        arguments: opt!(first!(arguments)) ~
+        extends: opt!(
+            preceded!(
+                first!(keyword!(tokens::EXTENDS)),
+                first!(spanned!(qualified_name))
+            )
+        ) ~
+        implements: opt!(
+            preceded!(
+                first!(keyword!(tokens::IMPLEMENTS)),
+                first!(qualified_names)
+            )
+        ) ~
+        members: first!(class_body),
+        || {
+            Expression::AnonymousClass {
+                arguments : arguments.unwrap_or(vec![]),
+                extends   : extends,
+                implements: implements.unwrap_or(vec![]),
+                members   : members
+            }
+        }
+    )
+);
 
-        assert_eq!(array(input), output);
-        assert_eq!(primary(input), output);
-        assert_eq!(expression(input), output);
-    }
+named!(
+    pub qualified_names< Vec<Spanned<Name>> >,
+    chain!(
+        accumulator: map_res!(
+            spanned!(qualified_name),
+            into_vector_mapper
+        ) ~
+        result: fold_many0!(
+            preceded!(
+                first!(tag!(tokens::COMMA)),
+                first!(spanned!(qualified_name))
+            ),
+            accumulator,
+            fold_into_vector
+        ),
+        || { result }
+    )
+);
 
-    #[test]
-    fn case_array_one_value() {
-        let input  = b"['foo']";
-        let output = Result::Done(
-            &b""[..],
-            Expression::Array(vec![
-                (
-                    None,
-                    Expression::Literal(Literal::String(b"foo".to_vec()))
-                )
-            ])
-        );
+named!(
+    pub class_type_designator<Expression>,
+    alt!(
+        new_variable
+      | relative_scope => { relative_scope_mapper }
+      | qualified_name => { qualified_name_mapper }
+    )
+);
 
-        assert_eq!(array(input), output);
-        assert_eq!(primary(input), output);
-        assert_eq!(expression(input), output);
-    }
+named!(
+    pub arguments< Vec<Spanned<Argument>> >,
+    chain!(
+        tag!(tokens::LEFT_PARENTHESIS) ~
+        result: opt!(
+            chain!(
+                accumulator: map_res!(
+                    first!(spanned!(argument)),
+                    into_vector_mapper
+                ) ~
+                result: fold_many0!(
+                    preceded!(
+                        first!(tag!(tokens::COMMA)),
+                        first!(spanned!(argument))
+                    ),
+                    accumulator,
+                    fold_into_vector
+                ) ~
+                opt!(first!(tag!(tokens::COMMA))),
+                || { result }
+            )
+        ) ~
+        first!(expect!("arguments", tokens::RIGHT_PARENTHESIS)),
+        || { result.unwrap_or(vec![]) }
+    )
+);
 
-    #[test]
-    fn case_array_one_pair() {
-        let input  = b"[42 => 'foo']";
-        let output = Result::Done(
-            &b""[..],
-            Expression::Array(vec![
-                (
-                    Some(Expression::Literal(Literal::Integer(42i64))),
-                    Expression::Literal(Literal::String(b"foo".to_vec()))
-                )
-            ])
-        );
+named!(
+    pub argument<Argument>,
+    alt!(
+        chain!(
+            name: name ~
+            first!(tag!(tokens::NAMED_ARGUMENT)) ~
+            not!(tag!(tokens::NAMED_ARGUMENT)) ~
+            value: first!(spanned!(expression)),
+            || {
+                Argument {
+                    name       : Some(name),
+                    is_unpacked: false,
+                    value      : value
+                }
+            }
+        )
+      | preceded!(
+            tag!(tokens::ELLIPSIS),
+            first!(spanned!(expression))
+        ) => { unpacked_argument_mapper }
+      | spanned!(expression) => { positional_argument_mapper }
+    )
+);
 
-        assert_eq!(array(input), output);
-        assert_eq!(primary(input), output);
-        assert_eq!(expression(input), output);
+#[inline(always)]
+fn unpacked_argument_mapper<'a>(expression: Spanned<Expression<'a>>) -> Argument<'a> {
+    Argument {
+        name       : None,
+        is_unpacked: true,
+        value      : expression
     }
+}
 
-    #[test]
-    fn case_array_many_pairs() {
-        let input  = b"['foo', 42 => 'bar', 'baz' => $qux]";
-        let output = Result::Done(
-            &b""[..],
-            Expression::Array(vec![
-                (
-                    None,
-                    Expression::Literal(Literal::String(b"foo".to_vec()))
-                ),
-                (
-                    Some(Expression::Literal(Literal::Integer(42i64))),
-                    Expression::Literal(Literal::String(b"bar".to_vec()))
-                ),
-                (
-                    Some(Expression::Literal(Literal::String(b"baz".to_vec()))),
-                    Expression::Variable(Variable(&b"qux"[..]))
-                )
-            ])
-        );
-
-        assert_eq!(array(input), output);
-        assert_eq!(primary(input), output);
-        assert_eq!(expression(input), output);
+#[inline(always)]
+fn positional_argument_mapper<'a>(expression: Spanned<Expression<'a>>) -> Argument<'a> {
+    Argument {
+        name       : None,
+        is_unpacked: false,
+        value      : expression
     }
+}
 
-    #[test]
-    fn case_array_trailing_comma() {
-        let input  = b"[1, 2, 3, /* foo */]";
-        let output = Result::Done(
-            &b""[..],
-            Expression::Array(vec![
-                (
-                    None,
-                    Expression::Literal(Literal::Integer(1i64))
-                ),
-                (
-                    None,
-                    Expression::Literal(Literal::Integer(2i64))
-                ),
-                (
-                    None,
-                    Expression::Literal(Literal::Integer(3i64))
-                )
-            ])
-        );
+named!(
+    pub anonymous_function<Expression>,
+    chain!(
+        is_static: opt!(keyword!(tokens::STATIC)) ~
+        first!(keyword!(tokens::FUNCTION)) ~
+        returns_reference: opt!(first!(tag!(tokens::REFERENCE))) ~
+        parameters: first!(parameters) ~
+        captures: opt!(first!(anonymous_function_use)) ~
+        return_type: opt!(first!(spanned!(return_type))) ~
+        body: first!(block),
+        || {
+            Expression::Closure {
+                is_static        : is_static.is_some(),
+                returns_reference: returns_reference.is_some(),
+                parameters       : parameters,
+                captures         : captures.unwrap_or(vec![]),
+                return_type      : return_type,
+                body             : body
+            }
+        }
+    )
+);
 
-        assert_eq!(array(input), output);
-        assert_eq!(primary(input), output);
-        assert_eq!(expression(input), output);
+named!(
+    pub arrow_function<Expression>,
+    chain!(
+        is_static: opt!(keyword!(tokens::STATIC)) ~
+        first!(keyword!(tokens::FN)) ~
+        returns_reference: opt!(first!(tag!(tokens::REFERENCE))) ~
+        parameters: first!(parameters) ~
+        return_type: opt!(first!(spanned!(return_type))) ~
+        first!(tag!(tokens::MAP)) ~
+        body: first!(spanned!(expression)),
+        || {
+            Expression::ArrowFunction {
+                is_static        : is_static.is_some(),
+                returns_reference: returns_reference.is_some(),
+                parameters       : parameters,
+                return_type      : return_type,
+                body             : Box::new(body)
+            }
+        }
+    )
+);
+
+named!(
+    pub match_expression<Expression>,
+    chain!(
+        keyword!(tokens::MATCH) ~
+        first!(tag!(tokens::LEFT_PARENTHESIS)) ~
+        subject: first!(spanned!(expression)) ~
+        first!(expect!("match_expression", tokens::RIGHT_PARENTHESIS)) ~
+        first!(tag!(tokens::LEFT_CURLY_BRACKET)) ~
+        accumulator: map_res!(
+            first!(match_arm),
+            into_vector_mapper
+        ) ~
+        result: fold_many0!(
+            preceded!(
+                first!(tag!(tokens::COMMA)),
+                first!(match_arm)
+            ),
+            accumulator,
+            fold_into_vector
+        ) ~
+        opt!(first!(tag!(tokens::COMMA))) ~
+        first!(expect!("match_expression", tokens::RIGHT_CURLY_BRACKET)),
+        || {
+            Expression::Match {
+                subject: Box::new(subject),
+                arms   : result
+            }
+        }
+    )
+);
+
+named!(
+    match_arm<MatchArm>,
+    alt!(
+        chain!(
+            keyword!(tokens::DEFAULT) ~
+            opt!(first!(tag!(tokens::COMMA))) ~
+            first!(tag!(tokens::MAP)) ~
+            body: first!(spanned!(expression)),
+            || {
+                MatchArm {
+                    conditions: None,
+                    body      : body
+                }
+            }
+        )
+      | chain!(
+            conditions: match_conditions ~
+            first!(tag!(tokens::MAP)) ~
+            body: first!(spanned!(expression)),
+            || {
+                MatchArm {
+                    conditions: Some(conditions),
+                    body      : body
+                }
+            }
+        )
+    )
+);
+
+named!(
+    match_conditions< Vec<Spanned<Expression>> >,
+    chain!(
+        accumulator: map_res!(
+            spanned!(expression),
+            into_vector_mapper
+        ) ~
+        result: fold_many0!(
+            preceded!(
+                first!(tag!(tokens::COMMA)),
+                first!(spanned!(expression))
+            ),
+            accumulator,
+            fold_into_vector
+        ) ~
+        opt!(first!(tag!(tokens::COMMA))),
+        || { result }
+    )
+);
+
+named!(
+    pub throw<Expression>,
+    map!(
+        preceded!(
+            keyword!(tokens::THROW),
+            first!(spanned!(expression))
+        ),
+        throw_mapper
+    )
+);
+
+#[inline(always)]
+fn throw_mapper<'a>(expression: Spanned<Expression<'a>>) -> Expression<'a> {
+    Expression::Throw(Box::new(expression))
+}
+
+named!(
+    anonymous_function_use< Vec<Spanned<Expression>> >,
+    chain!(
+        keyword!(tokens::USE) ~
+        first!(tag!(tokens::LEFT_PARENTHESIS)) ~
+        accumulator: map_res!(
+            first!(spanned!(anonymous_function_use_variable)),
+            into_vector_mapper
+        ) ~
+        result: fold_many0!(
+            preceded!(
+                first!(tag!(tokens::COMMA)),
+                first!(spanned!(anonymous_function_use_variable))
+            ),
+            accumulator,
+            fold_into_vector
+        ) ~
+        opt!(first!(tag!(tokens::COMMA))) ~
+        first!(expect!("anonymous_function_use", tokens::RIGHT_PARENTHESIS)),
+        || { result }
+    )
+);
+
+named!(
+    anonymous_function_use_variable<Expression>,
+    alt!(
+        variable => { variable_mapper }
+      | preceded!(
+            tag!(tokens::REFERENCE),
+            first!(spanned!(variable))
+        ) => { variable_by_reference_mapper }
+    )
+);
+
+#[inline(always)]
+fn variable_by_reference_mapper<'a>(variable: Spanned<Variable<'a>>) -> Expression<'a> {
+    Expression::Reference(Box::new(variable.map(Expression::Variable)))
+}
+
+named!(
+    pub parameters< Vec<Spanned<Parameter>> >,
+    map_res!(
+        parameter_list,
+        parameters_mapper
+    )
+);
+
+/// Only the parameters of a constructor can be promoted to properties.
+#[inline(always)]
+fn parameters_mapper(parameters: Vec<Spanned<Parameter>>) -> StdResult<Vec<Spanned<Parameter>>, ()> {
+    if parameters.iter().any(|parameter| is_promoted(parameter)) {
+        Err(())
+    } else {
+        Ok(parameters)
     }
+}
 
-    #[test]
-    fn case_array_recursive() {
-        let input  = b"['foo', 42 => [3 => 5, 7 => [11 => '13']], 'baz' => $qux]";
-        let output = Result::Done(
-            &b""[..],
-            Expression::Array(vec![
-                (
-                    None,
-                    Expression::Literal(Literal::String(b"foo".to_vec()))
-                ),
-                (
-                    Some(Expression::Literal(Literal::Integer(42i64))),
-                    Expression::Array(vec![
-                        (
-                            Some(Expression::Literal(Literal::Integer(3i64))),
-                            Expression::Literal(Literal::Integer(5i64))
-                        ),
-                        (
-                            Some(Expression::Literal(Literal::Integer(7i64))),
-                            Expression::Array(vec![
-                                (
-                                    Some(Expression::Literal(Literal::Integer(11i64))),
-                                    Expression::Literal(Literal::String(b"13".to_vec()))
-                                )
-                            ])
-                        )
-                    ])
-                ),
-                (
-                    Some(Expression::Literal(Literal::String(b"baz".to_vec()))),
-                    Expression::Variable(Variable(&b"qux"[..]))
-                )
-            ])
-        );
+/// Whether the parameter is promoted to a property.
+pub fn is_promoted(parameter: &Parameter) -> bool {
+    parameter.visibility.is_some() || parameter.set_visibility.is_some() || parameter.is_readonly
+}
 
-        assert_eq!(array(input), output);
-        assert_eq!(primary(input), output);
-        assert_eq!(expression(input), output);
+/// Whether a property can be written with the given visibility while being
+/// read with the other one: the write visibility cannot be wider.
+pub fn is_set_visibility_compatible(visibility: &Visibility, set_visibility: &Visibility) -> bool {
+    fn rank(visibility: &Visibility) -> u8 {
+        match *visibility {
+            Visibility::Private   => 0,
+            Visibility::Protected => 1,
+            Visibility::Public    => 2
+        }
     }
 
-    #[test]
-    fn case_array_value_by_reference() {
-        let input  = b"[7 => &$foo, 42 => $bar]";
-        let output = Result::Done(
-            &b""[..],
-            Expression::Array(vec![
-                (
-                    Some(Expression::Literal(Literal::Integer(7i64))),
-                    Expression::Reference(
-                        Box::new(Expression::Variable(Variable(&b"foo"[..])))
+    rank(set_visibility) <= rank(visibility)
+}
+
+named!(
+    pub method_parameters< Vec<Spanned<Parameter>> >,
+    map_res!(
+        parameter_list,
+        method_parameters_mapper
+    )
+);
+
+/// A variadic parameter cannot be promoted to a property.
+#[inline(always)]
+fn method_parameters_mapper(parameters: Vec<Spanned<Parameter>>) -> StdResult<Vec<Spanned<Parameter>>, ()> {
+    if parameters.iter().any(|parameter| is_promoted(parameter) && parameter.is_variadic) {
+        Err(())
+    } else {
+        Ok(parameters)
+    }
+}
+
+named!(
+    parameter_list< Vec<Spanned<Parameter>> >,
+    chain!(
+        tag!(tokens::LEFT_PARENTHESIS) ~
+        result: opt!(
+            chain!(
+                accumulator: map_res!(
+                    first!(spanned!(parameter)),
+                    into_vector_mapper
+                ) ~
+                result: fold_many0!(
+                    preceded!(
+                        first!(tag!(tokens::COMMA)),
+                        first!(spanned!(parameter))
+                    ),
+                    accumulator,
+                    fold_into_vector
+                ) ~
+                opt!(first!(tag!(tokens::COMMA))),
+                || { result }
+            )
+        ) ~
+        first!(expect!("parameter_list", tokens::RIGHT_PARENTHESIS)),
+        || { result.unwrap_or(vec![]) }
+    )
+);
+
+named!(
+    pub parameter<Parameter>,
+    map_res!(
+        chain!(
+            attributes: attributes ~
+            modifiers: fold_many0!(
+                first!(parameter_modifier),
+                Vec::new(),
+                fold_into_vector
+            ) ~
+            ty: opt!(first!(spanned!(type_declaration))) ~
+            is_reference: opt!(first!(tag!(tokens::REFERENCE))) ~
+            is_variadic: opt!(first!(tag!(tokens::ELLIPSIS))) ~
+            name: first!(variable) ~
+            value: opt!(
+                complete!(
+                    preceded!(
+                        first!(tag!(tokens::ASSIGN)),
+                        first!(initializer_expression)
                     )
-                ),
-                (
-                    Some(Expression::Literal(Literal::Integer(42i64))),
-                    Expression::Variable(Variable(&b"bar"[..]))
                 )
-            ])
-        );
+            ) ~
+            hooks: opt!(complete!(first!(property_hooks))),
+            || { (attributes, modifiers, ty, is_reference.is_some(), is_variadic.is_some(), name, value, hooks) }
+        ),
+        parameter_mapper
+    )
+);
 
-        assert_eq!(array(input), output);
-        assert_eq!(primary(input), output);
-        assert_eq!(expression(input), output);
+/// A parameter is promoted to a property by a visibility, a write
+/// visibility, a `readonly` modifier, or some of them. Each can be declared
+/// once. Only a typed property can have a write visibility, and only a
+/// property that is not readonly can be hooked.
+#[inline(always)]
+fn parameter_mapper<'a>(
+    (attributes, modifiers, ty, is_reference, is_variadic, name, value, hooks): (Vec<Attribute<'a>>, Vec<Modifier>, Option<Spanned<Type<'a>>>, bool, bool, Variable<'a>, Option<ConstantExpression<'a>>, Option<Vec<PropertyHook<'a>>>)
+) -> StdResult<Parameter<'a>, ()> {
+    let mut visibility     = None;
+    let mut set_visibility = None;
+    let mut is_readonly    = false;
+
+    for modifier in modifiers {
+        match modifier {
+            Modifier::Visibility(_) if visibility.is_some() => return Err(()),
+            Modifier::Visibility(modifier) => visibility = Some(modifier),
+            Modifier::SetVisibility(_) if set_visibility.is_some() || !version::is_targeting(Version::Php84) => return Err(()),
+            Modifier::SetVisibility(modifier) => set_visibility = Some(modifier),
+            Modifier::Readonly if is_readonly || !version::is_targeting(Version::Php81) => return Err(()),
+            Modifier::Readonly => is_readonly = true
+        }
     }
 
-    #[test]
-    fn case_invalid_array_trailing_commas() {
-        let input  = b"[1, 2, 3,,]";
-        let output = Result::Error(Error::Position(ErrorKind::Alt, &b"[1, 2, 3,,]"[..]));
+    if let Some(ref set_visibility) = set_visibility {
+        if ty.is_none() || !is_set_visibility_compatible(visibility.as_ref().unwrap_or(&Visibility::Public), set_visibility) {
+            return Err(());
+        }
+    }
 
-        assert_eq!(array(input), Result::Error(Error::Position(ErrorKind::Alt, &b"[1, 2, 3,,]"[..])));
-        assert_eq!(primary(input), output);
-        assert_eq!(expression(input), output);
+    if hooks.is_some() && ((visibility.is_none() && set_visibility.is_none()) || is_readonly) {
+        return Err(());
     }
 
-    #[test]
-    fn case_invalid_array_empty_trailing_comma() {
-        let input  = b"[,]";
-        let output = Result::Error(Error::Position(ErrorKind::Alt, &b"[,]"[..]));
+    Ok(
+        Parameter {
+            attributes    : attributes,
+            visibility    : visibility,
+            set_visibility: set_visibility,
+            is_readonly   : is_readonly,
+            ty            : ty,
+            is_reference  : is_reference,
+            is_variadic   : is_variadic,
+            name          : name,
+            value         : value,
+            hooks         : hooks.unwrap_or(vec![])
+        }
+    )
+}
 
-        assert_eq!(array(input), Result::Error(Error::Position(ErrorKind::Alt, &b"[,]"[..])));
-        assert_eq!(primary(input), output);
-        assert_eq!(expression(input), output);
+/// A modifier of a promoted parameter.
+enum Modifier {
+    Visibility(Visibility),
+    SetVisibility(Visibility),
+    Readonly
+}
+
+named!(
+    parameter_modifier<Modifier>,
+    alt!(
+        keyword!(tokens::PUBLIC_SET) => { |_| Modifier::SetVisibility(Visibility::Public) }
+      | keyword!(tokens::PROTECTED_SET) => { |_| Modifier::SetVisibility(Visibility::Protected) }
+      | keyword!(tokens::PRIVATE_SET) => { |_| Modifier::SetVisibility(Visibility::Private) }
+      | map_res!(
+            name,
+            parameter_modifier_mapper
+        )
+    )
+);
+
+/// The modifier is recognized as a whole name so that a type like
+/// `PublicKey` is not split.
+#[inline(always)]
+fn parameter_modifier_mapper(modifier: &[u8]) -> StdResult<Modifier, ()> {
+    if modifier.eq_ignore_ascii_case(tokens::PUBLIC) {
+        Ok(Modifier::Visibility(Visibility::Public))
+    } else if modifier.eq_ignore_ascii_case(tokens::PROTECTED) {
+        Ok(Modifier::Visibility(Visibility::Protected))
+    } else if modifier.eq_ignore_ascii_case(tokens::PRIVATE) {
+        Ok(Modifier::Visibility(Visibility::Private))
+    } else if modifier.eq_ignore_ascii_case(tokens::READONLY) {
+        Ok(Modifier::Readonly)
+    } else {
+        Err(())
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        anonymous_function,
+        argument,
+        arguments,
+        array,
+        arrow_function,
+        dynamic_variable,
+        intrinsic,
+        intrinsic_construct,
+        intrinsic_echo,
+        intrinsic_empty,
+        intrinsic_eval,
+        intrinsic_exit,
+        intrinsic_include,
+        intrinsic_isset,
+        intrinsic_list,
+        intrinsic_operator,
+        intrinsic_print,
+        intrinsic_unset,
+        magic_constant,
+        match_expression,
+        new,
+        method_parameters,
+        parameter,
+        parameters,
+        primary,
+        throw
+    };
+    use super::super::expression;
+    use super::super::super::super::ast::{
+        Argument,
+        ClassMember,
+        ConstantExpression,
+        Expression,
+        IncludeKind,
+        Literal,
+        MagicConstant,
+        MatchArm,
+        METHOD_PUBLIC,
+        Method,
+        Name,
+        Parameter,
+        RelativeScope,
+        Statement,
+        Type,
+        Variable,
+        Visibility
+    };
+    use super::super::super::super::internal::{
+        Error,
+        ErrorKind,
+        Needed,
+        Result
+    };
+    use super::super::super::super::span::Spanned;
+    use super::super::super::super::version::{
+        self,
+        Version
+    };
 
     #[test]
-    fn case_array_long_syntax_empty() {
-        let input  = b"array ( /* foo */ )";
+    fn case_array_empty() {
+        let input  = b"[ /* foo */ ]";
         let output = Result::Done(&b""[..], Expression::Array(vec![]));
 
         assert_eq!(array(input), output);
@@ -745,14 +1344,14 @@ mod tests {
     }
 
     #[test]
-    fn case_array_long_syntax_one_value() {
-        let input  = b"array('foo')";
+    fn case_array_one_value() {
+        let input  = b"['foo']";
         let output = Result::Done(
             &b""[..],
             Expression::Array(vec![
                 (
                     None,
-                    Expression::Literal(Literal::String(b"foo".to_vec()))
+                    Spanned::from(Expression::Literal(Literal::String(b"foo".to_vec())))
                 )
             ])
         );
@@ -763,14 +1362,14 @@ mod tests {
     }
 
     #[test]
-    fn case_array_long_syntax_one_pair() {
-        let input  = b"array(42 => 'foo')";
+    fn case_array_one_pair() {
+        let input  = b"[42 => 'foo']";
         let output = Result::Done(
             &b""[..],
             Expression::Array(vec![
                 (
-                    Some(Expression::Literal(Literal::Integer(42i64))),
-                    Expression::Literal(Literal::String(b"foo".to_vec()))
+                    Some(Spanned::from(Expression::Literal(Literal::Integer(42i64)))),
+                    Spanned::from(Expression::Literal(Literal::String(b"foo".to_vec())))
                 )
             ])
         );
@@ -781,22 +1380,22 @@ mod tests {
     }
 
     #[test]
-    fn case_array_long_syntax_many_pairs() {
-        let input  = b"array('foo', 42 => 'bar', 'baz' => $qux)";
+    fn case_array_many_pairs() {
+        let input  = b"['foo', 42 => 'bar', 'baz' => $qux]";
         let output = Result::Done(
             &b""[..],
             Expression::Array(vec![
                 (
                     None,
-                    Expression::Literal(Literal::String(b"foo".to_vec()))
+                    Spanned::from(Expression::Literal(Literal::String(b"foo".to_vec())))
                 ),
                 (
-                    Some(Expression::Literal(Literal::Integer(42i64))),
-                    Expression::Literal(Literal::String(b"bar".to_vec()))
+                    Some(Spanned::from(Expression::Literal(Literal::Integer(42i64)))),
+                    Spanned::from(Expression::Literal(Literal::String(b"bar".to_vec())))
                 ),
                 (
-                    Some(Expression::Literal(Literal::String(b"baz".to_vec()))),
-                    Expression::Variable(Variable(&b"qux"[..]))
+                    Some(Spanned::from(Expression::Literal(Literal::String(b"baz".to_vec())))),
+                    Spanned::from(Expression::Variable(Variable::Name(&b"qux"[..])))
                 )
             ])
         );
@@ -807,22 +1406,22 @@ mod tests {
     }
 
     #[test]
-    fn case_array_long_syntax_trailing_comma() {
-        let input  = b"array(1, 2, 3, /* foo */)";
+    fn case_array_trailing_comma() {
+        let input  = b"[1, 2, 3, /* foo */]";
         let output = Result::Done(
             &b""[..],
             Expression::Array(vec![
                 (
                     None,
-                    Expression::Literal(Literal::Integer(1i64))
+                    Spanned::from(Expression::Literal(Literal::Integer(1i64)))
                 ),
                 (
                     None,
-                    Expression::Literal(Literal::Integer(2i64))
+                    Spanned::from(Expression::Literal(Literal::Integer(2i64)))
                 ),
                 (
                     None,
-                    Expression::Literal(Literal::Integer(3i64))
+                    Spanned::from(Expression::Literal(Literal::Integer(3i64)))
                 )
             ])
         );
@@ -833,36 +1432,218 @@ mod tests {
     }
 
     #[test]
-    fn case_array_long_syntax_recursive() {
-        let input  = b"array('foo', 42 => array(3 => 5, 7 => array(11 => '13')), 'baz' => $qux)";
+    fn case_array_recursive() {
+        let input  = b"['foo', 42 => [3 => 5, 7 => [11 => '13']], 'baz' => $qux]";
         let output = Result::Done(
             &b""[..],
             Expression::Array(vec![
                 (
                     None,
-                    Expression::Literal(Literal::String(b"foo".to_vec()))
+                    Spanned::from(Expression::Literal(Literal::String(b"foo".to_vec())))
                 ),
                 (
-                    Some(Expression::Literal(Literal::Integer(42i64))),
-                    Expression::Array(vec![
+                    Some(Spanned::from(Expression::Literal(Literal::Integer(42i64)))),
+                    Spanned::from(Expression::Array(vec![
                         (
-                            Some(Expression::Literal(Literal::Integer(3i64))),
-                            Expression::Literal(Literal::Integer(5i64))
+                            Some(Spanned::from(Expression::Literal(Literal::Integer(3i64)))),
+                            Spanned::from(Expression::Literal(Literal::Integer(5i64)))
                         ),
                         (
-                            Some(Expression::Literal(Literal::Integer(7i64))),
-                            Expression::Array(vec![
+                            Some(Spanned::from(Expression::Literal(Literal::Integer(7i64)))),
+                            Spanned::from(Expression::Array(vec![
                                 (
-                                    Some(Expression::Literal(Literal::Integer(11i64))),
-                                    Expression::Literal(Literal::String(b"13".to_vec()))
+                                    Some(Spanned::from(Expression::Literal(Literal::Integer(11i64)))),
+                                    Spanned::from(Expression::Literal(Literal::String(b"13".to_vec())))
                                 )
-                            ])
+                            ]))
                         )
-                    ])
+                    ]))
                 ),
                 (
-                    Some(Expression::Literal(Literal::String(b"baz".to_vec()))),
-                    Expression::Variable(Variable(&b"qux"[..]))
+                    Some(Spanned::from(Expression::Literal(Literal::String(b"baz".to_vec())))),
+                    Spanned::from(Expression::Variable(Variable::Name(&b"qux"[..])))
+                )
+            ])
+        );
+
+        assert_eq!(array(input), output);
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_array_value_by_reference() {
+        let input  = b"[7 => &$foo, 42 => $bar]";
+        let output = Result::Done(
+            &b""[..],
+            Expression::Array(vec![
+                (
+                    Some(Spanned::from(Expression::Literal(Literal::Integer(7i64)))),
+                    Spanned::from(Expression::Reference(
+                        Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..]))))
+                    ))
+                ),
+                (
+                    Some(Spanned::from(Expression::Literal(Literal::Integer(42i64)))),
+                    Spanned::from(Expression::Variable(Variable::Name(&b"bar"[..])))
+                )
+            ])
+        );
+
+        assert_eq!(array(input), output);
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_invalid_array_trailing_commas() {
+        let input  = b"[1, 2, 3,,]";
+        let output = Result::Error(Error::Position(ErrorKind::Alt, &b"[1, 2, 3,,]"[..]));
+
+        assert_eq!(array(input), Result::Error(Error::Position(ErrorKind::Alt, &b"[1, 2, 3,,]"[..])));
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_invalid_array_empty_trailing_comma() {
+        let input  = b"[,]";
+        let output = Result::Error(Error::Position(ErrorKind::Alt, &b"[,]"[..]));
+
+        assert_eq!(array(input), Result::Error(Error::Position(ErrorKind::Alt, &b"[,]"[..])));
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_array_long_syntax_empty() {
+        let input  = b"array ( /* foo */ )";
+        let output = Result::Done(&b""[..], Expression::Array(vec![]));
+
+        assert_eq!(array(input), output);
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_array_long_syntax_one_value() {
+        let input  = b"array('foo')";
+        let output = Result::Done(
+            &b""[..],
+            Expression::Array(vec![
+                (
+                    None,
+                    Spanned::from(Expression::Literal(Literal::String(b"foo".to_vec())))
+                )
+            ])
+        );
+
+        assert_eq!(array(input), output);
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_array_long_syntax_one_pair() {
+        let input  = b"array(42 => 'foo')";
+        let output = Result::Done(
+            &b""[..],
+            Expression::Array(vec![
+                (
+                    Some(Spanned::from(Expression::Literal(Literal::Integer(42i64)))),
+                    Spanned::from(Expression::Literal(Literal::String(b"foo".to_vec())))
+                )
+            ])
+        );
+
+        assert_eq!(array(input), output);
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_array_long_syntax_many_pairs() {
+        let input  = b"array('foo', 42 => 'bar', 'baz' => $qux)";
+        let output = Result::Done(
+            &b""[..],
+            Expression::Array(vec![
+                (
+                    None,
+                    Spanned::from(Expression::Literal(Literal::String(b"foo".to_vec())))
+                ),
+                (
+                    Some(Spanned::from(Expression::Literal(Literal::Integer(42i64)))),
+                    Spanned::from(Expression::Literal(Literal::String(b"bar".to_vec())))
+                ),
+                (
+                    Some(Spanned::from(Expression::Literal(Literal::String(b"baz".to_vec())))),
+                    Spanned::from(Expression::Variable(Variable::Name(&b"qux"[..])))
+                )
+            ])
+        );
+
+        assert_eq!(array(input), output);
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_array_long_syntax_trailing_comma() {
+        let input  = b"array(1, 2, 3, /* foo */)";
+        let output = Result::Done(
+            &b""[..],
+            Expression::Array(vec![
+                (
+                    None,
+                    Spanned::from(Expression::Literal(Literal::Integer(1i64)))
+                ),
+                (
+                    None,
+                    Spanned::from(Expression::Literal(Literal::Integer(2i64)))
+                ),
+                (
+                    None,
+                    Spanned::from(Expression::Literal(Literal::Integer(3i64)))
+                )
+            ])
+        );
+
+        assert_eq!(array(input), output);
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_array_long_syntax_recursive() {
+        let input  = b"array('foo', 42 => array(3 => 5, 7 => array(11 => '13')), 'baz' => $qux)";
+        let output = Result::Done(
+            &b""[..],
+            Expression::Array(vec![
+                (
+                    None,
+                    Spanned::from(Expression::Literal(Literal::String(b"foo".to_vec())))
+                ),
+                (
+                    Some(Spanned::from(Expression::Literal(Literal::Integer(42i64)))),
+                    Spanned::from(Expression::Array(vec![
+                        (
+                            Some(Spanned::from(Expression::Literal(Literal::Integer(3i64)))),
+                            Spanned::from(Expression::Literal(Literal::Integer(5i64)))
+                        ),
+                        (
+                            Some(Spanned::from(Expression::Literal(Literal::Integer(7i64)))),
+                            Spanned::from(Expression::Array(vec![
+                                (
+                                    Some(Spanned::from(Expression::Literal(Literal::Integer(11i64)))),
+                                    Spanned::from(Expression::Literal(Literal::String(b"13".to_vec())))
+                                )
+                            ]))
+                        )
+                    ]))
+                ),
+                (
+                    Some(Spanned::from(Expression::Literal(Literal::String(b"baz".to_vec())))),
+                    Spanned::from(Expression::Variable(Variable::Name(&b"qux"[..])))
                 )
             ])
         );
@@ -879,14 +1660,14 @@ mod tests {
             &b""[..],
             Expression::Array(vec![
                 (
-                    Some(Expression::Literal(Literal::Integer(7i64))),
-                    Expression::Reference(
-                        Box::new(Expression::Variable(Variable(&b"foo"[..])))
-                    )
+                    Some(Spanned::from(Expression::Literal(Literal::Integer(7i64)))),
+                    Spanned::from(Expression::Reference(
+                        Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..]))))
+                    ))
                 ),
                 (
-                    Some(Expression::Literal(Literal::Integer(42i64))),
-                    Expression::Variable(Variable(&b"bar"[..]))
+                    Some(Spanned::from(Expression::Literal(Literal::Integer(42i64)))),
+                    Spanned::from(Expression::Variable(Variable::Name(&b"bar"[..])))
                 )
             ])
         );
@@ -899,12 +1680,103 @@ mod tests {
     #[test]
     fn case_variable() {
         let input  = b"$foo";
-        let output = Result::Done(&b""[..], Expression::Variable(Variable(&b"foo"[..])));
+        let output = Result::Done(&b""[..], Expression::Variable(Variable::Name(&b"foo"[..])));
+
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_dynamic_variable() {
+        let input  = b"$$foo";
+        let output = Result::Done(
+            &b""[..],
+            Variable::Dynamic(
+                Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..]))))
+            )
+        );
+
+        assert_eq!(dynamic_variable(input), output);
+    }
+
+    #[test]
+    fn case_dynamic_variable_is_a_primary() {
+        let input  = b"$$foo";
+        let output = Result::Done(
+            &b""[..],
+            Expression::Variable(
+                Variable::Dynamic(
+                    Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..]))))
+                )
+            )
+        );
 
         assert_eq!(primary(input), output);
         assert_eq!(expression(input), output);
     }
 
+    #[test]
+    fn case_dynamic_variable_recursive() {
+        let input  = b"$$$foo";
+        let output = Result::Done(
+            &b""[..],
+            Variable::Dynamic(
+                Box::new(
+                    Spanned::from(Expression::Variable(
+                        Variable::Dynamic(
+                            Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..]))))
+                        )
+                    ))
+                )
+            )
+        );
+
+        assert_eq!(dynamic_variable(input), output);
+    }
+
+    #[test]
+    fn case_dynamic_variable_with_an_expression() {
+        let input  = b"${ /* foo */ 'bar' }";
+        let output = Result::Done(
+            &b""[..],
+            Variable::Dynamic(
+                Box::new(Spanned::from(Expression::Literal(Literal::String(b"bar".to_vec()))))
+            )
+        );
+
+        assert_eq!(dynamic_variable(input), output);
+    }
+
+    #[test]
+    fn case_dynamic_variable_is_modifiable() {
+        let input  = b"${$foo} = 42";
+        let output = Result::Done(
+            &b""[..],
+            Expression::Assignment {
+                variable: Box::new(
+                    Spanned::from(Expression::Variable(
+                        Variable::Dynamic(
+                            Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..]))))
+                        )
+                    ))
+                ),
+                value   : Box::new(Spanned::from(Expression::Literal(Literal::Integer(42i64))))
+            }
+        );
+
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_invalid_dynamic_variable_with_whitespace() {
+        assert_eq!(dynamic_variable(b"$ $foo"), Result::Error(Error::Position(ErrorKind::Alt, &b" $foo"[..])));
+    }
+
+    #[test]
+    fn case_invalid_dynamic_variable_not_closed() {
+        assert_eq!(dynamic_variable(b"${$foo;"), Result::Error(Error::Position(ErrorKind::Alt, &b"{$foo;"[..])));
+    }
+
     #[test]
     fn case_qualified_name() {
         let input  = b"Foo\\Bar";
@@ -923,6 +1795,89 @@ mod tests {
         assert_eq!(expression(input), output);
     }
 
+    #[test]
+    fn case_literal_keywords_case_insensitive() {
+        assert_eq!(expression(b"TRUE"),  Result::Done(&b""[..], Expression::Literal(Literal::Boolean(true))));
+        assert_eq!(expression(b"False"), Result::Done(&b""[..], Expression::Literal(Literal::Boolean(false))));
+        assert_eq!(expression(b"NULL"),  Result::Done(&b""[..], Expression::Literal(Literal::Null)));
+    }
+
+    #[test]
+    fn case_literal_keywords_are_not_name_prefixes() {
+        assert_eq!(expression(b"nullable"),   Result::Done(&b""[..], Expression::Name(Name::Unqualified(&b"nullable"[..]))));
+        assert_eq!(expression(b"true_value"), Result::Done(&b""[..], Expression::Name(Name::Unqualified(&b"true_value"[..]))));
+    }
+
+    #[test]
+    fn case_literal_keywords_are_not_namespaces() {
+        assert_eq!(expression(b"true\\Foo"), Result::Done(&b""[..], Expression::Name(Name::Qualified(vec![&b"true"[..], &b"Foo"[..]]))));
+        assert_eq!(expression(b"null\\Foo"), Result::Done(&b""[..], Expression::Name(Name::Qualified(vec![&b"null"[..], &b"Foo"[..]]))));
+    }
+
+    #[test]
+    fn case_fully_qualified_literal_keywords() {
+        assert_eq!(expression(b"\\true"),  Result::Done(&b""[..], Expression::Name(Name::FullyQualified(vec![&b"true"[..]]))));
+        assert_eq!(expression(b"\\FALSE"), Result::Done(&b""[..], Expression::Name(Name::FullyQualified(vec![&b"FALSE"[..]]))));
+    }
+
+    #[test]
+    fn case_literal_binary_string() {
+        let input  = b"b'Hello, World!'";
+        let output = Result::Done(&b""[..], Expression::Literal(Literal::String(b"Hello, World!".to_vec())));
+
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_literal_binary_nowdoc() {
+        let input  = b"B<<<'FOO'\nHello\nFOO\n";
+        let output = Result::Done(
+            &b"\n"[..],
+            Expression::Literal(
+                Literal::Nowdoc {
+                    label      : b"FOO".to_vec(),
+                    indentation: Vec::new(),
+                    value      : b"Hello".to_vec()
+                }
+            )
+        );
+
+        assert_eq!(primary(input), output);
+    }
+
+    macro_rules! test_magic_constant {
+        ($test_case_name:ident: ($string:expr, $expect:expr)) => (
+            #[test]
+            fn $test_case_name() {
+                let output = Result::Done(&b""[..], Expression::MagicConstant($expect));
+
+                assert_eq!(magic_constant($string), output);
+                assert_eq!(primary($string), output);
+                assert_eq!(expression($string), output);
+            }
+        )
+    }
+
+    test_magic_constant!(case_magic_constant_class:     (b"__CLASS__", MagicConstant::Class));
+    test_magic_constant!(case_magic_constant_directory: (b"__DIR__", MagicConstant::Directory));
+    test_magic_constant!(case_magic_constant_file:      (b"__FILE__", MagicConstant::File));
+    test_magic_constant!(case_magic_constant_function:  (b"__FUNCTION__", MagicConstant::Function));
+    test_magic_constant!(case_magic_constant_line:      (b"__LINE__", MagicConstant::Line));
+    test_magic_constant!(case_magic_constant_method:    (b"__METHOD__", MagicConstant::Method));
+    test_magic_constant!(case_magic_constant_namespace: (b"__NAMESPACE__", MagicConstant::Namespace));
+    test_magic_constant!(case_magic_constant_trait:     (b"__TRAIT__", MagicConstant::Trait));
+    test_magic_constant!(case_magic_constant_case:      (b"__line__", MagicConstant::Line));
+
+    #[test]
+    fn case_magic_constant_prefix_is_a_name() {
+        let input  = b"__LINE__S";
+        let output = Result::Done(&b""[..], Expression::Name(Name::Unqualified(&b"__LINE__S"[..])));
+
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
     #[test]
     fn case_intrinsic_echo_one_expression() {
         let input  = b"echo /* baz */ 'foobar'";
@@ -930,7 +1885,7 @@ mod tests {
             &b""[..],
             Expression::Echo(
                 vec![
-                    Expression::Literal(Literal::String(b"foobar".to_vec()))
+                    Spanned::from(Expression::Literal(Literal::String(b"foobar".to_vec())))
                 ]
             )
         );
@@ -948,9 +1903,9 @@ mod tests {
             &b""[..],
             Expression::Echo(
                 vec![
-                    Expression::Literal(Literal::String(b"foobar".to_vec())),
-                    Expression::Variable(Variable(&b"bazqux"[..])),
-                    Expression::Literal(Literal::Integer(42i64))
+                    Spanned::from(Expression::Literal(Literal::String(b"foobar".to_vec()))),
+                    Spanned::from(Expression::Variable(Variable::Name(&b"bazqux"[..]))),
+                    Spanned::from(Expression::Literal(Literal::Integer(42i64)))
                 ]
             )
         );
@@ -979,8 +1934,8 @@ mod tests {
             &b""[..],
             Expression::List(vec![
                 Some((
-                    Some(Expression::Literal(Literal::String(b"foo".to_vec()))),
-                    Expression::Variable(Variable(&b"foo"[..]))
+                    Some(Spanned::from(Expression::Literal(Literal::String(b"foo".to_vec())))),
+                    Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..])))
                 ))
             ])
         );
@@ -998,16 +1953,16 @@ mod tests {
             &b""[..],
             Expression::List(vec![
                 Some((
-                    Some(Expression::Literal(Literal::String(b"foo".to_vec()))),
-                    Expression::Variable(Variable(&b"foo"[..]))
+                    Some(Spanned::from(Expression::Literal(Literal::String(b"foo".to_vec())))),
+                    Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..])))
                 )),
                 Some((
-                    Some(Expression::Literal(Literal::String(b"bar".to_vec()))),
-                    Expression::Variable(Variable(&b"bar"[..]))
+                    Some(Spanned::from(Expression::Literal(Literal::String(b"bar".to_vec())))),
+                    Spanned::from(Expression::Variable(Variable::Name(&b"bar"[..])))
                 )),
                 Some((
-                    Some(Expression::Literal(Literal::String(b"baz".to_vec()))),
-                    Expression::Variable(Variable(&b"baz"[..]))
+                    Some(Spanned::from(Expression::Literal(Literal::String(b"baz".to_vec())))),
+                    Spanned::from(Expression::Variable(Variable::Name(&b"baz"[..])))
                 ))
             ])
         );
@@ -1025,12 +1980,12 @@ mod tests {
             &b""[..],
             Expression::List(vec![
                 Some((
-                    Some(Expression::Literal(Literal::String(b"foo".to_vec()))),
-                    Expression::Variable(Variable(&b"foo"[..]))
+                    Some(Spanned::from(Expression::Literal(Literal::String(b"foo".to_vec())))),
+                    Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..])))
                 )),
                 Some((
-                    Some(Expression::Literal(Literal::String(b"bar".to_vec()))),
-                    Expression::Variable(Variable(&b"bar"[..]))
+                    Some(Spanned::from(Expression::Literal(Literal::String(b"bar".to_vec())))),
+                    Spanned::from(Expression::Variable(Variable::Name(&b"bar"[..])))
                 ))
             ])
         );
@@ -1048,23 +2003,23 @@ mod tests {
             &b""[..],
             Expression::List(vec![
                 Some((
-                    Some(Expression::Literal(Literal::String(b"foo".to_vec()))),
-                    Expression::List(vec![
+                    Some(Spanned::from(Expression::Literal(Literal::String(b"foo".to_vec())))),
+                    Spanned::from(Expression::List(vec![
                         Some((
-                            Some(Expression::Literal(Literal::String(b"bar".to_vec()))),
-                            Expression::Variable(Variable(&b"bar"[..]))
+                            Some(Spanned::from(Expression::Literal(Literal::String(b"bar".to_vec())))),
+                            Spanned::from(Expression::Variable(Variable::Name(&b"bar"[..])))
                         ))
-                    ])
+                    ]))
                 )),
                 Some((
-                    Some(Expression::Literal(Literal::String(b"baz".to_vec()))),
-                    Expression::List(vec![
+                    Some(Spanned::from(Expression::Literal(Literal::String(b"baz".to_vec())))),
+                    Spanned::from(Expression::List(vec![
                         None,
                         Some((
                             None,
-                            Expression::Variable(Variable(&b"qux"[..]))
+                            Spanned::from(Expression::Variable(Variable::Name(&b"qux"[..])))
                         ))
-                    ])
+                    ]))
                 ))
             ])
         );
@@ -1083,7 +2038,7 @@ mod tests {
             Expression::List(vec![
                 Some((
                     None,
-                    Expression::Variable(Variable(&b"foo"[..]))
+                    Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..])))
                 ))
             ])
         );
@@ -1102,15 +2057,15 @@ mod tests {
             Expression::List(vec![
                 Some((
                     None,
-                    Expression::Variable(Variable(&b"foo"[..]))
+                    Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..])))
                 )),
                 Some((
                     None,
-                    Expression::Variable(Variable(&b"bar"[..]))
+                    Spanned::from(Expression::Variable(Variable::Name(&b"bar"[..])))
                 )),
                 Some((
                     None,
-                    Expression::Variable(Variable(&b"baz"[..]))
+                    Spanned::from(Expression::Variable(Variable::Name(&b"baz"[..])))
                 ))
             ])
         );
@@ -1129,18 +2084,18 @@ mod tests {
             Expression::List(vec![
                 Some((
                     None,
-                    Expression::Variable(Variable(&b"foo"[..]))
+                    Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..])))
                 )),
                 None,
                 Some((
                     None,
-                    Expression::Variable(Variable(&b"bar"[..]))
+                    Spanned::from(Expression::Variable(Variable::Name(&b"bar"[..])))
                 )),
                 None,
                 None,
                 Some((
                     None,
-                    Expression::Variable(Variable(&b"baz"[..]))
+                    Spanned::from(Expression::Variable(Variable::Name(&b"baz"[..])))
                 )),
                 None
             ])
@@ -1160,25 +2115,25 @@ mod tests {
             Expression::List(vec![
                 Some((
                     None,
-                    Expression::Variable(Variable(&b"foo"[..]))
+                    Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..])))
                 )),
                 Some((
                     None,
-                    Expression::List(vec![
+                    Spanned::from(Expression::List(vec![
                         Some((
                             None,
-                            Expression::Variable(Variable(&b"bar"[..]))
+                            Spanned::from(Expression::Variable(Variable::Name(&b"bar"[..])))
                         ))
-                    ])
+                    ]))
                 )),
                 Some((
                     None,
-                    Expression::List(vec![
+                    Spanned::from(Expression::List(vec![
                         Some((
-                            Some(Expression::Literal(Literal::String(b"baz".to_vec()))),
-                            Expression::Variable(Variable(&b"baz"[..]))
+                            Some(Spanned::from(Expression::Literal(Literal::String(b"baz".to_vec())))),
+                            Spanned::from(Expression::Variable(Variable::Name(&b"baz"[..])))
                         ))
-                    ])
+                    ]))
                 ))
             ])
         );
@@ -1229,7 +2184,7 @@ mod tests {
             &b""[..],
             Expression::Unset(
                 vec![
-                    Expression::Variable(Variable(&b"foo"[..]))
+                    Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..])))
                 ]
             )
         );
@@ -1247,8 +2202,8 @@ mod tests {
             &b""[..],
             Expression::Unset(
                 vec![
-                    Expression::Variable(Variable(&b"foo"[..])),
-                    Expression::Variable(Variable(&b"bar"[..]))
+                    Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..]))),
+                    Spanned::from(Expression::Variable(Variable::Name(&b"bar"[..])))
                 ]
             )
         );
@@ -1260,14 +2215,40 @@ mod tests {
     }
 
     #[test]
-    fn case_invalid_intrinsic_unset_zero_variable() {
-        let input  = b"unset()";
-        let output = Result::Error(Error::Position(ErrorKind::Alt, &b"unset()"[..]));
-
-        assert_eq!(intrinsic_unset(input), Result::Error(Error::Position(ErrorKind::Alt, &b")"[..])));
-        assert_eq!(intrinsic_construct(input), output);
-        assert_eq!(intrinsic(input), output);
-        assert_eq!(expression(input), output);
+    fn case_intrinsic_unset_trailing_comma() {
+        let input  = b"unset($foo, $bar, )";
+        let output = Result::Done(
+            &b""[..],
+            Expression::Unset(
+                vec![
+                    Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..]))),
+                    Spanned::from(Expression::Variable(Variable::Name(&b"bar"[..])))
+                ]
+            )
+        );
+
+        assert_eq!(intrinsic_unset(input), output);
+        assert_eq!(intrinsic_construct(input), output);
+        assert_eq!(intrinsic(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_invalid_intrinsic_unset_only_a_comma() {
+        let input = b"unset(,)";
+
+        assert_eq!(intrinsic_unset(input), Result::Error(Error::Position(ErrorKind::Alt, &b",)"[..])));
+    }
+
+    #[test]
+    fn case_invalid_intrinsic_unset_zero_variable() {
+        let input  = b"unset()";
+        let output = Result::Error(Error::Position(ErrorKind::Alt, &b"unset()"[..]));
+
+        assert_eq!(intrinsic_unset(input), Result::Error(Error::Position(ErrorKind::Alt, &b")"[..])));
+        assert_eq!(intrinsic_construct(input), output);
+        assert_eq!(intrinsic(input), output);
+        assert_eq!(expression(input), output);
     }
 
     #[test]
@@ -1277,9 +2258,9 @@ mod tests {
             &b""[..],
             Expression::Empty(
                 Box::new(
-                    Expression::Literal(
+                    Spanned::from(Expression::Literal(
                         Literal::String(b"foo".to_vec())
-                    )
+                    ))
                 )
             )
         );
@@ -1297,9 +2278,9 @@ mod tests {
             &b""[..],
             Expression::Empty(
                 Box::new(
-                    Expression::Literal(
+                    Spanned::from(Expression::Literal(
                         Literal::Integer(42i64)
-                    )
+                    ))
                 )
             )
         );
@@ -1328,9 +2309,9 @@ mod tests {
             &b""[..],
             Expression::Eval(
                 Box::new(
-                    Expression::Literal(
+                    Spanned::from(Expression::Literal(
                         Literal::String(b"1 + 2;".to_vec())
-                    )
+                    ))
                 )
             )
         );
@@ -1360,9 +2341,9 @@ mod tests {
             Expression::Exit(
                 Some(
                     Box::new(
-                        Expression::Literal(
+                        Spanned::from(Expression::Literal(
                             Literal::Integer(42i64)
-                        )
+                        ))
                     )
                 )
             )
@@ -1393,9 +2374,9 @@ mod tests {
             Expression::Exit(
                 Some(
                     Box::new(
-                        Expression::Variable(
-                            Variable(&b"foo"[..])
-                        )
+                        Spanned::from(Expression::Variable(
+                            Variable::Name(&b"foo"[..])
+                        ))
                     )
                 )
             )
@@ -1437,9 +2418,9 @@ mod tests {
             Expression::Exit(
                 Some(
                     Box::new(
-                        Expression::Literal(
+                        Spanned::from(Expression::Literal(
                             Literal::Integer(42i64)
-                        )
+                        ))
                     )
                 )
             )
@@ -1470,9 +2451,9 @@ mod tests {
             Expression::Exit(
                 Some(
                     Box::new(
-                        Expression::Variable(
-                            Variable(&b"foo"[..])
-                        )
+                        Spanned::from(Expression::Variable(
+                            Variable::Name(&b"foo"[..])
+                        ))
                     )
                 )
             )
@@ -1506,6 +2487,56 @@ mod tests {
         assert_eq!(expression(input), output);
     }
 
+    macro_rules! test_intrinsic_include {
+        ($test_case_name:ident: ($string:expr, $kind:expr)) => (
+            #[test]
+            fn $test_case_name() {
+                let output = Result::Done(
+                    &b""[..],
+                    Expression::Include {
+                        kind: $kind,
+                        path: Box::new(Spanned::from(Expression::Literal(Literal::String(b"foo.php".to_vec()))))
+                    }
+                );
+
+                assert_eq!(intrinsic_include($string), output);
+                assert_eq!(intrinsic_operator($string), output);
+                assert_eq!(intrinsic($string), output);
+                assert_eq!(expression($string), output);
+            }
+        )
+    }
+
+    test_intrinsic_include!(case_intrinsic_include:      (b"include 'foo.php'", IncludeKind::Include));
+    test_intrinsic_include!(case_intrinsic_include_once: (b"include_once 'foo.php'", IncludeKind::IncludeOnce));
+    test_intrinsic_include!(case_intrinsic_require:      (b"require 'foo.php'", IncludeKind::Require));
+    test_intrinsic_include!(case_intrinsic_require_once: (b"require_once 'foo.php'", IncludeKind::RequireOnce));
+    test_intrinsic_include!(case_intrinsic_include_case_insensitive_with_parenthesis: (b"ReQuIrE /* bar */ ('foo.php')", IncludeKind::Require));
+
+    #[test]
+    fn case_intrinsic_include_in_an_assignment() {
+        let input  = b"$config = require $path";
+        let output = Result::Done(
+            &b""[..],
+            Expression::Assignment {
+                variable: Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"config"[..])))),
+                value   : Box::new(
+                    Spanned::from(Expression::Include {
+                        kind: IncludeKind::Require,
+                        path: Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"path"[..]))))
+                    })
+                )
+            }
+        );
+
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_invalid_intrinsic_include_missing_path() {
+        assert_eq!(intrinsic_include(b"include;"), Result::Error(Error::Position(ErrorKind::Alt, &b";"[..])));
+    }
+
     #[test]
     fn case_intrinsic_isset_one_variable() {
         let input  = b"isset($foo)";
@@ -1513,7 +2544,7 @@ mod tests {
             &b""[..],
             Expression::Isset(
                 vec![
-                    Expression::Variable(Variable(&b"foo"[..]))
+                    Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..])))
                 ]
             )
         );
@@ -1531,8 +2562,26 @@ mod tests {
             &b""[..],
             Expression::Isset(
                 vec![
-                    Expression::Variable(Variable(&b"foo"[..])),
-                    Expression::Variable(Variable(&b"bar"[..]))
+                    Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..]))),
+                    Spanned::from(Expression::Variable(Variable::Name(&b"bar"[..])))
+                ]
+            )
+        );
+
+        assert_eq!(intrinsic_isset(input), output);
+        assert_eq!(intrinsic_operator(input), output);
+        assert_eq!(intrinsic(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_intrinsic_isset_trailing_comma() {
+        let input  = b"isset($foo,)";
+        let output = Result::Done(
+            &b""[..],
+            Expression::Isset(
+                vec![
+                    Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..])))
                 ]
             )
         );
@@ -1561,7 +2610,7 @@ mod tests {
             &b""[..],
             Expression::Print(
                 Box::new(
-                    Expression::Literal(Literal::String(b"foobar".to_vec()))
+                    Spanned::from(Expression::Literal(Literal::String(b"foobar".to_vec())))
                 )
             )
         );
@@ -1583,6 +2632,1688 @@ mod tests {
         assert_eq!(expression(input), output);
     }
 
+    #[test]
+    fn case_new_without_arguments() {
+        let input  = b"new Foo";
+        let output = Result::Done(
+            &b""[..],
+            Expression::New {
+                class    : Box::new(Spanned::from(Expression::Name(Name::Unqualified(&b"Foo"[..])))),
+                arguments: vec![]
+            }
+        );
+
+        assert_eq!(new(input), output);
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_new_with_empty_arguments() {
+        let input  = b"new Foo\\Bar ( /* baz */ )";
+        let output = Result::Done(
+            &b""[..],
+            Expression::New {
+                class    : Box::new(Spanned::from(Expression::Name(Name::Qualified(vec![&b"Foo"[..], &b"Bar"[..]])))),
+                arguments: vec![]
+            }
+        );
+
+        assert_eq!(new(input), output);
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_new_with_arguments() {
+        let input  = b"new Foo($a, 42, 'bar')";
+        let output = Result::Done(
+            &b""[..],
+            Expression::New {
+                class    : Box::new(Spanned::from(Expression::Name(Name::Unqualified(&b"Foo"[..])))),
+                arguments: vec![
+                    Spanned::from(Argument {
+                        name       : None,
+                        is_unpacked: false,
+                        value      : Spanned::from(Expression::Variable(Variable::Name(&b"a"[..])))
+                    }),
+                    Spanned::from(Argument {
+                        name       : None,
+                        is_unpacked: false,
+                        value      : Spanned::from(Expression::Literal(Literal::Integer(42i64)))
+                    }),
+                    Spanned::from(Argument {
+                        name       : None,
+                        is_unpacked: false,
+                        value      : Spanned::from(Expression::Literal(Literal::String(b"bar".to_vec())))
+                    })
+                ]
+            }
+        );
+
+        assert_eq!(new(input), output);
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_new_variable() {
+        let input  = b"new $className($a)";
+        let output = Result::Done(
+            &b""[..],
+            Expression::New {
+                class    : Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"className"[..])))),
+                arguments: vec![
+                    Spanned::from(Argument {
+                        name       : None,
+                        is_unpacked: false,
+                        value      : Spanned::from(Expression::Variable(Variable::Name(&b"a"[..])))
+                    })
+                ]
+            }
+        );
+
+        assert_eq!(new(input), output);
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_new_property_fetch() {
+        let input  = b"new $this->cls()";
+        let output = Result::Done(
+            &b""[..],
+            Expression::New {
+                class    : Box::new(
+                    Spanned::from(Expression::PropertyFetch {
+                        object  : Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"this"[..])))),
+                        property: Box::new(Spanned::from(Expression::Name(Name::Unqualified(&b"cls"[..]))))
+                    })
+                ),
+                arguments: vec![]
+            }
+        );
+
+        assert_eq!(new(input), output);
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_new_array_access() {
+        let input  = b"new $a['k']";
+        let output = Result::Done(
+            &b""[..],
+            Expression::New {
+                class    : Box::new(
+                    Spanned::from(Expression::ArrayAccess {
+                        array: Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"a"[..])))),
+                        index: Some(Box::new(Spanned::from(Expression::Literal(Literal::String(b"k".to_vec())))))
+                    })
+                ),
+                arguments: vec![]
+            }
+        );
+
+        assert_eq!(new(input), output);
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_new_static_property_fetch() {
+        let input  = b"new static::$cls";
+        let output = Result::Done(
+            &b""[..],
+            Expression::New {
+                class    : Box::new(
+                    Spanned::from(Expression::StaticFetch {
+                        class : Box::new(Spanned::from(Expression::RelativeScope(RelativeScope::Static))),
+                        member: Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"cls"[..]))))
+                    })
+                ),
+                arguments: vec![]
+            }
+        );
+
+        assert_eq!(new(input), output);
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_new_fetch_chain() {
+        let input  = b"new Foo::$a->b['c']($d)";
+        let output = Result::Done(
+            &b""[..],
+            Expression::New {
+                class    : Box::new(
+                    Spanned::from(Expression::ArrayAccess {
+                        array: Box::new(
+                            Spanned::from(Expression::PropertyFetch {
+                                object  : Box::new(
+                                    Spanned::from(Expression::StaticFetch {
+                                        class : Box::new(Spanned::from(Expression::Name(Name::Unqualified(&b"Foo"[..])))),
+                                        member: Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"a"[..]))))
+                                    })
+                                ),
+                                property: Box::new(Spanned::from(Expression::Name(Name::Unqualified(&b"b"[..]))))
+                            })
+                        ),
+                        index: Some(Box::new(Spanned::from(Expression::Literal(Literal::String(b"c".to_vec())))))
+                    })
+                ),
+                arguments: vec![
+                    Spanned::from(Argument {
+                        name       : None,
+                        is_unpacked: false,
+                        value      : Spanned::from(Expression::Variable(Variable::Name(&b"d"[..])))
+                    })
+                ]
+            }
+        );
+
+        assert_eq!(new(input), output);
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_new_class_constant_is_not_a_class_designator() {
+        let input  = b"new Foo::BAR";
+        let output = Result::Done(
+            &b"::BAR"[..],
+            Expression::New {
+                class    : Box::new(Spanned::from(Expression::Name(Name::Unqualified(&b"Foo"[..])))),
+                arguments: vec![]
+            }
+        );
+
+        assert_eq!(new(input), output);
+    }
+
+    #[test]
+    fn case_new_static() {
+        let input  = b"NeW static()";
+        let output = Result::Done(
+            &b""[..],
+            Expression::New {
+                class    : Box::new(Spanned::from(Expression::RelativeScope(RelativeScope::Static))),
+                arguments: vec![]
+            }
+        );
+
+        assert_eq!(new(input), output);
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_new_recursive() {
+        let input  = b"new Foo(new Bar)";
+        let output = Result::Done(
+            &b""[..],
+            Expression::New {
+                class    : Box::new(Spanned::from(Expression::Name(Name::Unqualified(&b"Foo"[..])))),
+                arguments: vec![
+                    Spanned::from(Argument {
+                        name       : None,
+                        is_unpacked: false,
+                        value      : Spanned::from(Expression::New {
+                            class    : Box::new(Spanned::from(Expression::Name(Name::Unqualified(&b"Bar"[..])))),
+                            arguments: vec![]
+                        })
+                    })
+                ]
+            }
+        );
+
+        assert_eq!(new(input), output);
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_new_anonymous_class() {
+        let input  = b"new class {}";
+        let output = Result::Done(
+            &b""[..],
+            Expression::AnonymousClass {
+                arguments : vec![],
+                extends   : None,
+                implements: vec![],
+                members   : vec![]
+            }
+        );
+
+        assert_eq!(new(input), output);
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_new_anonymous_class_with_members() {
+        let input  = b"new class { const A = 1; private $b = 2; public function c() { return $this->b; } }";
+        let output = Result::Done(
+            &b""[..],
+            Expression::AnonymousClass {
+                arguments : vec![],
+                extends   : None,
+                implements: vec![],
+                members   : vec![
+                    Spanned::from(ClassMember::Constant {
+                        doc_comment: None,
+                        attributes : vec![],
+                        visibility : Visibility::Public,
+                        is_final   : false,
+                        ty         : None,
+                        constants  : vec![
+                            (&b"A"[..], ConstantExpression(Spanned::from(Expression::Literal(Literal::Integer(1)))))
+                        ]
+                    }),
+                    Spanned::from(ClassMember::Property {
+                        doc_comment   : None,
+                        attributes    : vec![],
+                        visibility    : Visibility::Private,
+                        set_visibility: None,
+                        is_static     : false,
+                        is_readonly   : false,
+                        ty            : None,
+                        properties    : vec![
+                            (
+                                Variable::Name(&b"b"[..]),
+                                Some(ConstantExpression(Spanned::from(Expression::Literal(Literal::Integer(2)))))
+                            )
+                        ],
+                        hooks         : vec![]
+                    }),
+                    Spanned::from(ClassMember::Method(
+                        Method {
+                            doc_comment      : None,
+                            attributes       : vec![],
+                            modifiers        : METHOD_PUBLIC,
+                            returns_reference: false,
+                            name             : &b"c"[..],
+                            parameters       : vec![],
+                            return_type      : None,
+                            body             : Some(
+                                vec![
+                                    Spanned::from(Statement::Return(
+                                        Some(
+                                            Spanned::from(Expression::PropertyFetch {
+                                                object  : Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"this"[..])))),
+                                                property: Box::new(Spanned::from(Expression::Name(Name::Unqualified(&b"b"[..]))))
+                                            })
+                                        )
+                                    ))
+                                ]
+                            )
+                        }
+                    ))
+                ]
+            }
+        );
+
+        assert_eq!(new(input), output);
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_invalid_new_anonymous_class_with_a_method_without_body() {
+        let input  = b"new class { function f(); }";
+        let output = Result::Error(Error::Position(ErrorKind::Alt, &b"class { function f(); }"[..]));
+
+        assert_eq!(new(input), output);
+    }
+
+    #[test]
+    fn case_new_anonymous_class_with_arguments() {
+        let input  = b"new class ($a, 42) { /* foo */ }";
+        let output = Result::Done(
+            &b""[..],
+            Expression::AnonymousClass {
+                arguments : vec![
+                    Spanned::from(Argument {
+                        name       : None,
+                        is_unpacked: false,
+                        value      : Spanned::from(Expression::Variable(Variable::Name(&b"a"[..])))
+                    }),
+                    Spanned::from(Argument {
+                        name       : None,
+                        is_unpacked: false,
+                        value      : Spanned::from(Expression::Literal(Literal::Integer(42i64)))
+                    })
+                ],
+                extends   : None,
+                implements: vec![],
+                members   : vec![]
+            }
+        );
+
+        assert_eq!(new(input), output);
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_new_anonymous_class_with_extends_and_implements() {
+        let input  = b"new class($a) extends Foo\\Bar implements Baz, \\Qux {}";
+        let output = Result::Done(
+            &b""[..],
+            Expression::AnonymousClass {
+                arguments : vec![
+                    Spanned::from(Argument {
+                        name       : None,
+                        is_unpacked: false,
+                        value      : Spanned::from(Expression::Variable(Variable::Name(&b"a"[..])))
+                    })
+                ],
+                extends   : Some(Spanned::from(Name::Qualified(vec![&b"Foo"[..], &b"Bar"[..]]))),
+                implements: vec![
+                    Spanned::from(Name::Unqualified(&b"Baz"[..])),
+                    Spanned::from(Name::FullyQualified(vec![&b"Qux"[..]]))
+                ],
+                members   : vec![]
+            }
+        );
+
+        assert_eq!(new(input), output);
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_new_anonymous_class_only_implements() {
+        let input  = b"NEW CLASS IMPLEMENTS Foo {}";
+        let output = Result::Done(
+            &b""[..],
+            Expression::AnonymousClass {
+                arguments : vec![],
+                extends   : None,
+                implements: vec![Spanned::from(Name::Unqualified(&b"Foo"[..]))],
+                members   : vec![]
+            }
+        );
+
+        assert_eq!(new(input), output);
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_new_class_name_starting_with_class() {
+        let input  = b"new Classy";
+        let output = Result::Done(
+            &b""[..],
+            Expression::New {
+                class    : Box::new(Spanned::from(Expression::Name(Name::Unqualified(&b"Classy"[..])))),
+                arguments: vec![]
+            }
+        );
+
+        assert_eq!(new(input), output);
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_invalid_new_anonymous_class_body_missing() {
+        let input = b"new class extends Foo;";
+
+        assert_eq!(new(input), Result::Error(Error::Position(ErrorKind::Alt, &b"class extends Foo;"[..])));
+    }
+
+    #[test]
+    fn case_name_starting_with_new_is_not_a_new() {
+        let input  = b"newFoo";
+        let output = Result::Done(&b""[..], Expression::Name(Name::Unqualified(&b"newFoo"[..])));
+
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_invalid_new_class_missing() {
+        let input = b"new 42";
+
+        assert_eq!(new(input), Result::Error(Error::Position(ErrorKind::Alt, &b"42"[..])));
+        assert_eq!(primary(input), Result::Error(Error::Position(ErrorKind::Alt, &b"new 42"[..])));
+    }
+
+    #[test]
+    fn case_arguments_empty() {
+        assert_eq!(arguments(b"()"), Result::Done(&b""[..], vec![]));
+    }
+
+    #[test]
+    fn case_arguments_many() {
+        assert_eq!(
+            arguments(b"( $a , /* b */ 42 )"),
+            Result::Done(
+                &b""[..],
+                vec![
+                    Spanned::from(Argument {
+                        name       : None,
+                        is_unpacked: false,
+                        value      : Spanned::from(Expression::Variable(Variable::Name(&b"a"[..])))
+                    }),
+                    Spanned::from(Argument {
+                        name       : None,
+                        is_unpacked: false,
+                        value      : Spanned::from(Expression::Literal(Literal::Integer(42i64)))
+                    })
+                ]
+            )
+        );
+    }
+
+    #[test]
+    fn case_arguments_with_a_trailing_comma() {
+        assert_eq!(
+            arguments(b"($a, 42, )"),
+            Result::Done(
+                &b""[..],
+                vec![
+                    Spanned::from(Argument {
+                        name       : None,
+                        is_unpacked: false,
+                        value      : Spanned::from(Expression::Variable(Variable::Name(&b"a"[..])))
+                    }),
+                    Spanned::from(Argument {
+                        name       : None,
+                        is_unpacked: false,
+                        value      : Spanned::from(Expression::Literal(Literal::Integer(42i64)))
+                    })
+                ]
+            )
+        );
+    }
+
+    #[test]
+    fn case_argument_named() {
+        assert_eq!(
+            argument(b"limit /* a */ : /* b */ 10"),
+            Result::Done(
+                &b""[..],
+                Argument {
+                    name       : Some(&b"limit"[..]),
+                    is_unpacked: false,
+                    value      : Spanned::from(Expression::Literal(Literal::Integer(10i64)))
+                }
+            )
+        );
+    }
+
+    #[test]
+    fn case_argument_named_with_a_keyword() {
+        assert_eq!(
+            argument(b"array: $foo"),
+            Result::Done(
+                &b""[..],
+                Argument {
+                    name       : Some(&b"array"[..]),
+                    is_unpacked: false,
+                    value      : Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..])))
+                }
+            )
+        );
+    }
+
+    #[test]
+    fn case_argument_static_fetch_is_not_named() {
+        assert_eq!(
+            argument(b"Foo::BAR"),
+            Result::Done(
+                &b""[..],
+                Argument {
+                    name       : None,
+                    is_unpacked: false,
+                    value      : Spanned::from(Expression::StaticFetch {
+                        class : Box::new(Spanned::from(Expression::Name(Name::Unqualified(&b"Foo"[..])))),
+                        member: Box::new(Spanned::from(Expression::Name(Name::Unqualified(&b"BAR"[..]))))
+                    })
+                }
+            )
+        );
+    }
+
+    #[test]
+    fn case_arguments_named_and_positional() {
+        assert_eq!(
+            arguments(b"($x, limit: 10, offset: $y)"),
+            Result::Done(
+                &b""[..],
+                vec![
+                    Spanned::from(Argument {
+                        name       : None,
+                        is_unpacked: false,
+                        value      : Spanned::from(Expression::Variable(Variable::Name(&b"x"[..])))
+                    }),
+                    Spanned::from(Argument {
+                        name       : Some(&b"limit"[..]),
+                        is_unpacked: false,
+                        value      : Spanned::from(Expression::Literal(Literal::Integer(10i64)))
+                    }),
+                    Spanned::from(Argument {
+                        name       : Some(&b"offset"[..]),
+                        is_unpacked: false,
+                        value      : Spanned::from(Expression::Variable(Variable::Name(&b"y"[..])))
+                    })
+                ]
+            )
+        );
+    }
+
+    #[test]
+    fn case_arguments_unpacked() {
+        assert_eq!(
+            arguments(b"(...$parts, ... /* a */ getMore())"),
+            Result::Done(
+                &b""[..],
+                vec![
+                    Spanned::from(Argument {
+                        name       : None,
+                        is_unpacked: true,
+                        value      : Spanned::from(Expression::Variable(Variable::Name(&b"parts"[..])))
+                    }),
+                    Spanned::from(Argument {
+                        name       : None,
+                        is_unpacked: true,
+                        value      : Spanned::from(Expression::Call {
+                            callee   : Box::new(Spanned::from(Expression::Name(Name::Unqualified(&b"getMore"[..])))),
+                            arguments: vec![]
+                        })
+                    })
+                ]
+            )
+        );
+    }
+
+    #[test]
+    fn case_invalid_argument_unpacked_missing_expression() {
+        assert_eq!(argument(b"...)"), Result::Error(Error::Position(ErrorKind::Alt, &b"...)"[..])));
+    }
+
+    #[test]
+    fn case_invalid_arguments_only_a_comma() {
+        assert_eq!(arguments(b"(,)"), Result::Error(Error::Position(ErrorKind::Tag, &b",)"[..])));
+    }
+
+    #[test]
+    fn case_invalid_arguments_many_trailing_commas() {
+        assert_eq!(arguments(b"($a,,)"), Result::Error(Error::Position(ErrorKind::Tag, &b",)"[..])));
+    }
+
+    #[test]
+    fn case_invalid_arguments_not_closed() {
+        assert_eq!(arguments(b"($a, 42;"), Result::Error(Error::Position(ErrorKind::Tag, &b";"[..])));
+    }
+
+    #[test]
+    fn case_anonymous_function() {
+        let input  = b"function () {}";
+        let output = Result::Done(
+            &b""[..],
+            Expression::Closure {
+                is_static        : false,
+                returns_reference: false,
+                parameters       : vec![],
+                captures         : vec![],
+                return_type      : None,
+                body             : vec![]
+            }
+        );
+
+        assert_eq!(anonymous_function(input), output);
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_anonymous_function_with_a_body() {
+        let input  = b"function ($x) { $y = $x; return $y; }";
+        let output = Result::Done(
+            &b""[..],
+            Expression::Closure {
+                is_static        : false,
+                returns_reference: false,
+                parameters       : vec![
+                    Spanned::from(Parameter {
+                        attributes    : vec![],
+                        visibility    : None,
+                        set_visibility: None,
+                        is_readonly   : false,
+                        ty            : None,
+                        is_reference  : false,
+                        is_variadic   : false,
+                        name          : Variable::Name(&b"x"[..]),
+                        value         : None,
+                        hooks         : vec![]
+                    })
+                ],
+                captures         : vec![],
+                return_type      : None,
+                body             : vec![
+                    Spanned::from(Statement::Expression(
+                        Spanned::from(Expression::Assignment {
+                            variable: Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"y"[..])))),
+                            value   : Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"x"[..]))))
+                        })
+                    )),
+                    Spanned::from(Statement::Return(Some(Spanned::from(Expression::Variable(Variable::Name(&b"y"[..]))))))
+                ]
+            }
+        );
+
+        assert_eq!(anonymous_function(input), output);
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_anonymous_function_with_a_nested_closure() {
+        let input  = b"static function () use ($a) { return function () { return 1; }; }";
+        let output = Result::Done(
+            &b""[..],
+            Expression::Closure {
+                is_static        : true,
+                returns_reference: false,
+                parameters       : vec![],
+                captures         : vec![Spanned::from(Expression::Variable(Variable::Name(&b"a"[..])))],
+                return_type      : None,
+                body             : vec![
+                    Spanned::from(Statement::Return(
+                        Some(
+                            Spanned::from(Expression::Closure {
+                                is_static        : false,
+                                returns_reference: false,
+                                parameters       : vec![],
+                                captures         : vec![],
+                                return_type      : None,
+                                body             : vec![
+                                    Spanned::from(Statement::Return(Some(Spanned::from(Expression::Literal(Literal::Integer(1))))))
+                                ]
+                            })
+                        )
+                    ))
+                ]
+            }
+        );
+
+        assert_eq!(anonymous_function(input), output);
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_anonymous_function_with_an_unterminated_body() {
+        let input = b"function () { return 1; ";
+
+        assert_eq!(anonymous_function(input), Result::Incomplete(Needed::Size(25)));
+    }
+
+    #[test]
+    fn case_anonymous_function_returning_a_reference() {
+        let input  = b"function &() use ($x) {}";
+        let output = Result::Done(
+            &b""[..],
+            Expression::Closure {
+                is_static        : false,
+                returns_reference: true,
+                parameters       : vec![],
+                captures         : vec![Spanned::from(Expression::Variable(Variable::Name(&b"x"[..])))],
+                return_type      : None,
+                body             : vec![]
+            }
+        );
+
+        assert_eq!(anonymous_function(input), output);
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_anonymous_function_static() {
+        let input  = b"static function() {}";
+        let output = Result::Done(
+            &b""[..],
+            Expression::Closure {
+                is_static        : true,
+                returns_reference: false,
+                parameters       : vec![],
+                captures         : vec![],
+                return_type      : None,
+                body             : vec![]
+            }
+        );
+
+        assert_eq!(anonymous_function(input), output);
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_anonymous_function_with_static_return_type() {
+        let input  = b"function(): static {}";
+        let output = Result::Done(
+            &b""[..],
+            Expression::Closure {
+                is_static        : false,
+                returns_reference: false,
+                parameters       : vec![],
+                captures         : vec![],
+                return_type      : Some(Spanned::from(Type::Static)),
+                body             : vec![]
+            }
+        );
+
+        assert_eq!(anonymous_function(input), output);
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_anonymous_function_captures_trailing_comma() {
+        let input  = b"function () use ($a, &$b,) {}";
+        let output = Result::Done(
+            &b""[..],
+            Expression::Closure {
+                is_static        : false,
+                returns_reference: false,
+                parameters       : vec![],
+                captures         : vec![
+                    Spanned::from(Expression::Variable(Variable::Name(&b"a"[..]))),
+                    Spanned::from(Expression::Reference(
+                        Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"b"[..]))))
+                    ))
+                ],
+                return_type      : None,
+                body             : vec![]
+            }
+        );
+
+        assert_eq!(anonymous_function(input), output);
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_anonymous_function_with_parameters_captures_and_return_type() {
+        let input  = b"function ($a, $b) use ($c, &$d): int {}";
+        let output = Result::Done(
+            &b""[..],
+            Expression::Closure {
+                is_static        : false,
+                returns_reference: false,
+                parameters       : vec![
+                    Spanned::from(Parameter {
+                        attributes    : vec![],
+                        visibility    : None,
+                        set_visibility: None,
+                        is_readonly   : false,
+                        ty            : None,
+                        is_reference  : false,
+                        is_variadic   : false,
+                        name          : Variable::Name(&b"a"[..]),
+                        value         : None,
+                        hooks         : vec![]
+                    }),
+                    Spanned::from(Parameter {
+                        attributes    : vec![],
+                        visibility    : None,
+                        set_visibility: None,
+                        is_readonly   : false,
+                        ty            : None,
+                        is_reference  : false,
+                        is_variadic   : false,
+                        name          : Variable::Name(&b"b"[..]),
+                        value         : None,
+                        hooks         : vec![]
+                    })
+                ],
+                captures         : vec![
+                    Spanned::from(Expression::Variable(Variable::Name(&b"c"[..]))),
+                    Spanned::from(Expression::Reference(Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"d"[..]))))))
+                ],
+                return_type      : Some(Spanned::from(Type::Int)),
+                body             : vec![]
+            }
+        );
+
+        assert_eq!(anonymous_function(input), output);
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_anonymous_function_with_comments() {
+        let input  = b"function /* a */ ( /* b */ $a /* c */ ) /* d */ use /* e */ ( /* f */ $b /* g */ ) /* h */ : /* i */ \\Foo /* j */ { /* k */ }";
+        let output = Result::Done(
+            &b""[..],
+            Expression::Closure {
+                is_static        : false,
+                returns_reference: false,
+                parameters       : vec![
+                    Spanned::from(Parameter {
+                        attributes    : vec![],
+                        visibility    : None,
+                        set_visibility: None,
+                        is_readonly   : false,
+                        ty            : None,
+                        is_reference  : false,
+                        is_variadic   : false,
+                        name          : Variable::Name(&b"a"[..]),
+                        value         : None,
+                        hooks         : vec![]
+                    })
+                ],
+                captures         : vec![Spanned::from(Expression::Variable(Variable::Name(&b"b"[..])))],
+                return_type      : Some(Spanned::from(Type::Name(Name::FullyQualified(vec![&b"Foo"[..]])))),
+                body             : vec![]
+            }
+        );
+
+        assert_eq!(anonymous_function(input), output);
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_invalid_anonymous_function_empty_use() {
+        assert_eq!(anonymous_function(b"function () use () {}"), Result::Error(Error::Position(ErrorKind::Tag, &b"use () {}"[..])));
+    }
+
+    #[test]
+    fn case_invalid_anonymous_function_missing_body() {
+        assert_eq!(anonymous_function(b"function ($a);"), Result::Error(Error::Position(ErrorKind::Tag, &b";"[..])));
+    }
+
+    #[test]
+    fn case_function_name_is_not_an_anonymous_function() {
+        let input  = b"functions";
+        let output = Result::Done(&b""[..], Expression::Name(Name::Unqualified(&b"functions"[..])));
+
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_arrow_function() {
+        let input  = b"fn($x) => $x";
+        let output = Result::Done(
+            &b""[..],
+            Expression::ArrowFunction {
+                is_static        : false,
+                returns_reference: false,
+                parameters       : vec![
+                    Spanned::from(Parameter {
+                        attributes    : vec![],
+                        visibility    : None,
+                        set_visibility: None,
+                        is_readonly   : false,
+                        ty            : None,
+                        is_reference  : false,
+                        is_variadic   : false,
+                        name          : Variable::Name(&b"x"[..]),
+                        value         : None,
+                        hooks         : vec![]
+                    })
+                ],
+                return_type      : None,
+                body             : Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"x"[..]))))
+            }
+        );
+
+        assert_eq!(arrow_function(input), output);
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_arrow_function_returning_a_reference() {
+        let input  = b"static fn &() => $x";
+        let output = Result::Done(
+            &b""[..],
+            Expression::ArrowFunction {
+                is_static        : true,
+                returns_reference: true,
+                parameters       : vec![],
+                return_type      : None,
+                body             : Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"x"[..]))))
+            }
+        );
+
+        assert_eq!(arrow_function(input), output);
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_arrow_function_static_with_return_type() {
+        let input  = b"static fn (): int => 42";
+        let output = Result::Done(
+            &b""[..],
+            Expression::ArrowFunction {
+                is_static        : true,
+                returns_reference: false,
+                parameters       : vec![],
+                return_type      : Some(Spanned::from(Type::Int)),
+                body             : Box::new(Spanned::from(Expression::Literal(Literal::Integer(42i64))))
+            }
+        );
+
+        assert_eq!(arrow_function(input), output);
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_arrow_function_with_nullable_static_return_type() {
+        let input  = b"fn(): ?static => $this";
+        let output = Result::Done(
+            &b""[..],
+            Expression::ArrowFunction {
+                is_static        : false,
+                returns_reference: false,
+                parameters       : vec![],
+                return_type      : Some(Spanned::from(Type::Nullable(Box::new(Spanned::from(Type::Static))))),
+                body             : Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"this"[..]))))
+            }
+        );
+
+        assert_eq!(arrow_function(input), output);
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_arrow_function_nested() {
+        let input  = b"fn () => fn () => 42";
+        let output = Result::Done(
+            &b""[..],
+            Expression::ArrowFunction {
+                is_static        : false,
+                returns_reference: false,
+                parameters       : vec![],
+                return_type      : None,
+                body             : Box::new(
+                    Spanned::from(Expression::ArrowFunction {
+                        is_static        : false,
+                        returns_reference: false,
+                        parameters       : vec![],
+                        return_type      : None,
+                        body             : Box::new(Spanned::from(Expression::Literal(Literal::Integer(42i64))))
+                    })
+                )
+            }
+        );
+
+        assert_eq!(arrow_function(input), output);
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_invalid_arrow_function_missing_body() {
+        assert_eq!(arrow_function(b"fn ($x) => ;"), Result::Error(Error::Position(ErrorKind::Alt, &b";"[..])));
+    }
+
+    #[test]
+    fn case_invalid_arrow_function_with_block() {
+        assert_eq!(arrow_function(b"fn ($x) {}"), Result::Error(Error::Position(ErrorKind::Tag, &b"{}"[..])));
+    }
+
+    #[test]
+    fn case_match() {
+        let input  = b"match ($x) { 1, 2 => 'a', default => 'b' }";
+        let output = Result::Done(
+            &b""[..],
+            Expression::Match {
+                subject: Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"x"[..])))),
+                arms   : vec![
+                    MatchArm {
+                        conditions: Some(vec![
+                            Spanned::from(Expression::Literal(Literal::Integer(1i64))),
+                            Spanned::from(Expression::Literal(Literal::Integer(2i64)))
+                        ]),
+                        body      : Spanned::from(Expression::Literal(Literal::String(b"a".to_vec())))
+                    },
+                    MatchArm {
+                        conditions: None,
+                        body      : Spanned::from(Expression::Literal(Literal::String(b"b".to_vec())))
+                    }
+                ]
+            }
+        );
+
+        assert_eq!(match_expression(input), output);
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_match_with_trailing_commas() {
+        let input  = b"MaTcH (/* foo */ $x) { $y, => $z, DEFAULT, => 42, }";
+        let output = Result::Done(
+            &b""[..],
+            Expression::Match {
+                subject: Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"x"[..])))),
+                arms   : vec![
+                    MatchArm {
+                        conditions: Some(vec![Spanned::from(Expression::Variable(Variable::Name(&b"y"[..])))]),
+                        body      : Spanned::from(Expression::Variable(Variable::Name(&b"z"[..])))
+                    },
+                    MatchArm {
+                        conditions: None,
+                        body      : Spanned::from(Expression::Literal(Literal::Integer(42i64)))
+                    }
+                ]
+            }
+        );
+
+        assert_eq!(match_expression(input), output);
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_match_condition_starting_like_default() {
+        let input  = b"match ($x) { defaults => 42 }";
+        let output = Result::Done(
+            &b""[..],
+            Expression::Match {
+                subject: Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"x"[..])))),
+                arms   : vec![
+                    MatchArm {
+                        conditions: Some(vec![Spanned::from(Expression::Name(Name::Unqualified(&b"defaults"[..])))]),
+                        body      : Spanned::from(Expression::Literal(Literal::Integer(42i64)))
+                    }
+                ]
+            }
+        );
+
+        assert_eq!(match_expression(input), output);
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_invalid_match_without_arms() {
+        assert_eq!(match_expression(b"match ($x) {}"), Result::Error(Error::Position(ErrorKind::Alt, &b"}"[..])));
+    }
+
+    #[test]
+    fn case_invalid_match_arm_without_conditions() {
+        assert_eq!(match_expression(b"match ($x) { => 42 }"), Result::Error(Error::Position(ErrorKind::Alt, &b"=> 42 }"[..])));
+    }
+
+    #[test]
+    fn case_invalid_match_arm_missing_body() {
+        assert_eq!(match_expression(b"match ($x) { 1 => }"), Result::Error(Error::Position(ErrorKind::Alt, &b"1 => }"[..])));
+    }
+
+    #[test]
+    fn case_invalid_match_not_closed() {
+        assert_eq!(match_expression(b"match ($x) { 1 => 2 3"), Result::Error(Error::Position(ErrorKind::Tag, &b"3"[..])));
+    }
+
+    #[test]
+    fn case_throw() {
+        let input  = b"throw new Exception()";
+        let output = Result::Done(
+            &b""[..],
+            Expression::Throw(
+                Box::new(
+                    Spanned::from(Expression::New {
+                        class    : Box::new(Spanned::from(Expression::Name(Name::Unqualified(&b"Exception"[..])))),
+                        arguments: vec![]
+                    })
+                )
+            )
+        );
+
+        assert_eq!(throw(input), output);
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_throw_in_an_arrow_function() {
+        let input  = b"fn () => THROW /* foo */ $e";
+        let output = Result::Done(
+            &b""[..],
+            Expression::ArrowFunction {
+                is_static        : false,
+                returns_reference: false,
+                parameters       : vec![],
+                return_type      : None,
+                body             : Box::new(
+                    Spanned::from(Expression::Throw(
+                        Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"e"[..]))))
+                    ))
+                )
+            }
+        );
+
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_throw_prefix_is_a_name() {
+        let input  = b"throwable";
+        let output = Result::Done(&b""[..], Expression::Name(Name::Unqualified(&b"throwable"[..])));
+
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_invalid_throw_missing_expression() {
+        assert_eq!(throw(b"throw;"), Result::Error(Error::Position(ErrorKind::Alt, &b";"[..])));
+    }
+
+    #[test]
+    fn case_parameters_empty() {
+        assert_eq!(parameters(b"( )"), Result::Done(&b""[..], vec![]));
+    }
+
+    #[test]
+    fn case_parameters_trailing_comma() {
+        assert_eq!(
+            parameters(b"($foo , )"),
+            Result::Done(
+                &b""[..],
+                vec![
+                    Spanned::from(Parameter {
+                        attributes    : vec![],
+                        visibility    : None,
+                        set_visibility: None,
+                        is_readonly   : false,
+                        ty            : None,
+                        is_reference  : false,
+                        is_variadic   : false,
+                        name          : Variable::Name(&b"foo"[..]),
+                        value         : None,
+                        hooks         : vec![]
+                    })
+                ]
+            )
+        );
+    }
+
+    #[test]
+    fn case_invalid_parameters_only_a_comma() {
+        assert_eq!(parameters(b"(,)"), Result::Error(Error::Position(ErrorKind::Tag, &b",)"[..])));
+    }
+
+    #[test]
+    fn case_parameter() {
+        assert_eq!(
+            parameter(b"$foo"),
+            Result::Done(
+                &b""[..],
+                Parameter {
+                    attributes    : vec![],
+                    visibility    : None,
+                    set_visibility: None,
+                    is_readonly   : false,
+                    ty            : None,
+                    is_reference  : false,
+                    is_variadic   : false,
+                    name          : Variable::Name(&b"foo"[..]),
+                    value         : None,
+                    hooks         : vec![]
+                }
+            )
+        );
+    }
+
+    #[test]
+    fn case_parameter_typed_by_reference_with_default_value() {
+        assert_eq!(
+            parameter(b"Foo\\Bar &$foo = 42"),
+            Result::Done(
+                &b""[..],
+                Parameter {
+                    attributes    : vec![],
+                    visibility    : None,
+                    set_visibility: None,
+                    is_readonly   : false,
+                    ty            : Some(Spanned::from(Type::Name(Name::Qualified(vec![&b"Foo"[..], &b"Bar"[..]])))),
+                    is_reference  : true,
+                    is_variadic   : false,
+                    name          : Variable::Name(&b"foo"[..]),
+                    value         : Some(ConstantExpression(Spanned::from(Expression::Literal(Literal::Integer(42i64))))),
+                    hooks         : vec![]
+                }
+            )
+        );
+    }
+
+    #[test]
+    fn case_invalid_parameter_default_value_is_not_constant() {
+        assert_eq!(
+            parameter(b"$foo = $bar"),
+            Result::Done(
+                &b" = $bar"[..],
+                Parameter {
+                    attributes    : vec![],
+                    visibility    : None,
+                    set_visibility: None,
+                    is_readonly   : false,
+                    ty            : None,
+                    is_reference  : false,
+                    is_variadic   : false,
+                    name          : Variable::Name(&b"foo"[..]),
+                    value         : None,
+                    hooks         : vec![]
+                }
+            )
+        );
+        assert_eq!(parameters(b"($foo = $bar)"), Result::Error(Error::Position(ErrorKind::Tag, &b"= $bar)"[..])));
+    }
+
+    #[test]
+    fn case_parameter_default_value_is_a_new() {
+        assert_eq!(
+            parameter(b"Foo $foo = new Foo"),
+            Result::Done(
+                &b""[..],
+                Parameter {
+                    attributes    : vec![],
+                    visibility    : None,
+                    set_visibility: None,
+                    is_readonly   : false,
+                    ty            : Some(Spanned::from(Type::Name(Name::Unqualified(&b"Foo"[..])))),
+                    is_reference  : false,
+                    is_variadic   : false,
+                    name          : Variable::Name(&b"foo"[..]),
+                    value         : Some(
+                        ConstantExpression(
+                            Spanned::from(Expression::New {
+                                class    : Box::new(Spanned::from(Expression::Name(Name::Unqualified(&b"Foo"[..])))),
+                                arguments: vec![]
+                            })
+                        )
+                    ),
+                    hooks         : vec![]
+                }
+            )
+        );
+    }
+
+    #[test]
+    fn case_parameter_nullable() {
+        assert_eq!(
+            parameter(b"?Foo $foo = 42"),
+            Result::Done(
+                &b""[..],
+                Parameter {
+                    attributes    : vec![],
+                    visibility    : None,
+                    set_visibility: None,
+                    is_readonly   : false,
+                    ty            : Some(Spanned::from(Type::Nullable(Box::new(Spanned::from(Type::Name(Name::Unqualified(&b"Foo"[..]))))))),
+                    is_reference  : false,
+                    is_variadic   : false,
+                    name          : Variable::Name(&b"foo"[..]),
+                    value         : Some(ConstantExpression(Spanned::from(Expression::Literal(Literal::Integer(42))))),
+                    hooks         : vec![]
+                }
+            )
+        );
+    }
+
+    #[test]
+    fn case_parameter_union_type() {
+        assert_eq!(
+            parameter(b"int|string $foo"),
+            Result::Done(
+                &b""[..],
+                Parameter {
+                    attributes    : vec![],
+                    visibility    : None,
+                    set_visibility: None,
+                    is_readonly   : false,
+                    ty            : Some(
+                        Spanned::from(Type::Union(vec![
+                            Spanned::from(Type::Int),
+                            Spanned::from(Type::String)
+                        ]))
+                    ),
+                    is_reference  : false,
+                    is_variadic   : false,
+                    name          : Variable::Name(&b"foo"[..]),
+                    value         : None,
+                    hooks         : vec![]
+                }
+            )
+        );
+    }
+
+    #[test]
+    fn case_parameter_intersection_type_by_reference() {
+        assert_eq!(
+            parameter(b"A&B &$foo"),
+            Result::Done(
+                &b""[..],
+                Parameter {
+                    attributes    : vec![],
+                    visibility    : None,
+                    set_visibility: None,
+                    is_readonly   : false,
+                    ty            : Some(
+                        Spanned::from(Type::Intersection(vec![
+                            Spanned::from(Type::Name(Name::Unqualified(&b"A"[..]))),
+                            Spanned::from(Type::Name(Name::Unqualified(&b"B"[..])))
+                        ]))
+                    ),
+                    is_reference  : true,
+                    is_variadic   : false,
+                    name          : Variable::Name(&b"foo"[..]),
+                    value         : None,
+                    hooks         : vec![]
+                }
+            )
+        );
+    }
+
+    #[test]
+    fn case_parameter_variadic() {
+        assert_eq!(
+            parameter(b"int ...$foo"),
+            Result::Done(
+                &b""[..],
+                Parameter {
+                    attributes    : vec![],
+                    visibility    : None,
+                    set_visibility: None,
+                    is_readonly   : false,
+                    ty            : Some(Spanned::from(Type::Int)),
+                    is_reference  : false,
+                    is_variadic   : true,
+                    name          : Variable::Name(&b"foo"[..]),
+                    value         : None,
+                    hooks         : vec![]
+                }
+            )
+        );
+    }
+
+    #[test]
+    fn case_parameters_many() {
+        assert_eq!(
+            parameters(b"($a, &...$b)"),
+            Result::Done(
+                &b""[..],
+                vec![
+                    Spanned::from(Parameter {
+                        attributes    : vec![],
+                        visibility    : None,
+                        set_visibility: None,
+                        is_readonly   : false,
+                        ty            : None,
+                        is_reference  : false,
+                        is_variadic   : false,
+                        name          : Variable::Name(&b"a"[..]),
+                        value         : None,
+                        hooks         : vec![]
+                    }),
+                    Spanned::from(Parameter {
+                        attributes    : vec![],
+                        visibility    : None,
+                        set_visibility: None,
+                        is_readonly   : false,
+                        ty            : None,
+                        is_reference  : true,
+                        is_variadic   : true,
+                        name          : Variable::Name(&b"b"[..]),
+                        value         : None,
+                        hooks         : vec![]
+                    })
+                ]
+            )
+        );
+    }
+
+    #[test]
+    fn case_invalid_parameter_missing_name() {
+        assert_eq!(parameter(b"int = 42"), Result::Error(Error::Position(ErrorKind::Tag, &b"= 42"[..])));
+    }
+
+    #[test]
+    fn case_parameter_promoted() {
+        assert_eq!(
+            parameter(b"PRIVATE int $foo = 42"),
+            Result::Done(
+                &b""[..],
+                Parameter {
+                    attributes    : vec![],
+                    visibility    : Some(Visibility::Private),
+                    set_visibility: None,
+                    is_readonly   : false,
+                    ty            : Some(Spanned::from(Type::Int)),
+                    is_reference  : false,
+                    is_variadic   : false,
+                    name          : Variable::Name(&b"foo"[..]),
+                    value         : Some(ConstantExpression(Spanned::from(Expression::Literal(Literal::Integer(42))))),
+                    hooks         : vec![]
+                }
+            )
+        );
+    }
+
+    #[test]
+    fn case_parameter_promoted_readonly() {
+        assert_eq!(
+            parameter(b"readonly private int $foo"),
+            Result::Done(
+                &b""[..],
+                Parameter {
+                    attributes    : vec![],
+                    visibility    : Some(Visibility::Private),
+                    set_visibility: None,
+                    is_readonly   : true,
+                    ty            : Some(Spanned::from(Type::Int)),
+                    is_reference  : false,
+                    is_variadic   : false,
+                    name          : Variable::Name(&b"foo"[..]),
+                    value         : None,
+                    hooks         : vec![]
+                }
+            )
+        );
+    }
+
+    #[test]
+    fn case_parameter_promoted_readonly_without_visibility() {
+        assert_eq!(
+            parameter(b"readonly int $foo"),
+            Result::Done(
+                &b""[..],
+                Parameter {
+                    attributes    : vec![],
+                    visibility    : None,
+                    set_visibility: None,
+                    is_readonly   : true,
+                    ty            : Some(Spanned::from(Type::Int)),
+                    is_reference  : false,
+                    is_variadic   : false,
+                    name          : Variable::Name(&b"foo"[..]),
+                    value         : None,
+                    hooks         : vec![]
+                }
+            )
+        );
+    }
+
+    #[test]
+    fn case_invalid_parameter_promoted_readonly_before_php_81() {
+        let input = b"readonly int $foo";
+
+        version::set_target(Version::Php80);
+        let output = parameter(input);
+        version::set_target(version::LATEST);
+
+        assert_eq!(output, Result::Error(Error::Position(ErrorKind::MapRes, &input[..])));
+    }
+
+    #[test]
+    fn case_invalid_parameter_promoted_with_many_visibilities() {
+        let input = b"public private int $foo";
+
+        assert_eq!(parameter(input), Result::Error(Error::Position(ErrorKind::MapRes, &input[..])));
+    }
+
+    #[test]
+    fn case_parameter_promoted_with_a_set_visibility() {
+        assert_eq!(
+            parameter(b"public private(set) int $foo"),
+            Result::Done(
+                &b""[..],
+                Parameter {
+                    attributes    : vec![],
+                    visibility    : Some(Visibility::Public),
+                    set_visibility: Some(Visibility::Private),
+                    is_readonly   : false,
+                    ty            : Some(Spanned::from(Type::Int)),
+                    is_reference  : false,
+                    is_variadic   : false,
+                    name          : Variable::Name(&b"foo"[..]),
+                    value         : None,
+                    hooks         : vec![]
+                }
+            )
+        );
+    }
+
+    #[test]
+    fn case_invalid_parameter_promoted_with_a_wider_set_visibility() {
+        let input = b"private protected(set) int $foo";
+
+        assert_eq!(parameter(input), Result::Error(Error::Position(ErrorKind::MapRes, &input[..])));
+    }
+
+    #[test]
+    fn case_invalid_parameter_promoted_untyped_with_a_set_visibility() {
+        let input = b"private(set) $foo";
+
+        assert_eq!(parameter(input), Result::Error(Error::Position(ErrorKind::MapRes, &input[..])));
+    }
+
+    #[test]
+    fn case_invalid_parameter_promoted_with_a_set_visibility_before_php_84() {
+        let input = b"private(set) int $foo";
+
+        version::set_target(Version::Php83);
+        let output = parameter(input);
+        version::set_target(version::LATEST);
+
+        assert_eq!(output, Result::Error(Error::Position(ErrorKind::MapRes, &input[..])));
+    }
+
+    #[test]
+    fn case_invalid_parameters_promoted_with_a_set_visibility() {
+        let input = b"(private(set) int $foo)";
+
+        assert_eq!(parameters(input), Result::Error(Error::Position(ErrorKind::MapRes, &input[..])));
+    }
+
+    #[test]
+    fn case_invalid_parameter_hooked_without_visibility() {
+        let input = b"int $foo { get => 42; }";
+
+        assert_eq!(parameter(input), Result::Error(Error::Position(ErrorKind::MapRes, &input[..])));
+    }
+
+    #[test]
+    fn case_invalid_parameter_hooked_and_readonly() {
+        let input = b"public readonly int $foo { get => 42; }";
+
+        assert_eq!(parameter(input), Result::Error(Error::Position(ErrorKind::MapRes, &input[..])));
+    }
+
+    #[test]
+    fn case_invalid_parameters_promoted_readonly() {
+        let input = b"(readonly $foo)";
+
+        assert_eq!(parameters(input), Result::Error(Error::Position(ErrorKind::MapRes, &input[..])));
+    }
+
+    #[test]
+    fn case_parameter_type_starting_like_a_visibility() {
+        assert_eq!(
+            parameter(b"PublicKey $foo"),
+            Result::Done(
+                &b""[..],
+                Parameter {
+                    attributes    : vec![],
+                    visibility    : None,
+                    set_visibility: None,
+                    is_readonly   : false,
+                    ty            : Some(Spanned::from(Type::Name(Name::Unqualified(&b"PublicKey"[..])))),
+                    is_reference  : false,
+                    is_variadic   : false,
+                    name          : Variable::Name(&b"foo"[..]),
+                    value         : None,
+                    hooks         : vec![]
+                }
+            )
+        );
+    }
+
+    #[test]
+    fn case_method_parameters_promoted() {
+        assert_eq!(
+            method_parameters(b"(public $foo, $bar)"),
+            Result::Done(
+                &b""[..],
+                vec![
+                    Spanned::from(Parameter {
+                        attributes    : vec![],
+                        visibility    : Some(Visibility::Public),
+                        set_visibility: None,
+                        is_readonly   : false,
+                        ty            : None,
+                        is_reference  : false,
+                        is_variadic   : false,
+                        name          : Variable::Name(&b"foo"[..]),
+                        value         : None,
+                        hooks         : vec![]
+                    }),
+                    Spanned::from(Parameter {
+                        attributes    : vec![],
+                        visibility    : None,
+                        set_visibility: None,
+                        is_readonly   : false,
+                        ty            : None,
+                        is_reference  : false,
+                        is_variadic   : false,
+                        name          : Variable::Name(&b"bar"[..]),
+                        value         : None,
+                        hooks         : vec![]
+                    })
+                ]
+            )
+        );
+    }
+
+    #[test]
+    fn case_invalid_method_parameters_promoted_and_variadic() {
+        let input = b"(public ...$foo)";
+
+        assert_eq!(method_parameters(input), Result::Error(Error::Position(ErrorKind::MapRes, &input[..])));
+    }
+
+    #[test]
+    fn case_invalid_parameters_promoted() {
+        let input = b"(public $foo)";
+
+        assert_eq!(parameters(input), Result::Error(Error::Position(ErrorKind::MapRes, &input[..])));
+    }
+
     #[test]
     fn case_grouped_by_parenthesis() {
         let input  = b"print (((('foobar'))))";
@@ -0,0 +1,1356 @@
+// Tagua VM
+//
+//
+// New BSD License
+//
+// Copyright © 2016-2016, Ivan Enderlin.
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//     * Redistributions of source code must retain the above copyright
+//       notice, this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright
+//       notice, this list of conditions and the following disclaimer in the
+//       documentation and/or other materials provided with the distribution.
+//     * Neither the name of the Hoa nor the names of its contributors may be
+//       used to endorse or promote products derived from this software without
+//       specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE
+// ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDERS AND CONTRIBUTORS BE
+// LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR
+// CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF
+// SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS
+// INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN
+// CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+
+//! Group of interpolated string rules.
+//!
+//! The list of all interpolated strings is provided by the PHP Language
+//! Specification in the [Grammar chapter, Double-Quoted String Literals
+//! section](https://github.com/php/php-langspec/blob/master/spec/19-grammar.md#double-quoted-string-literals).
+
+use std::result::Result as StdResult;
+use std::str;
+use super::expression;
+use super::super::literals::{
+    StringError,
+    string_document_body,
+    string_document_error,
+    string_document_indentation
+};
+use super::super::tokens::name;
+use super::super::super::ast::{
+    Expression,
+    Literal,
+    Name,
+    StringPart,
+    Variable
+};
+use super::super::super::diagnostics::{
+    self,
+    DiagnosticKind
+};
+use super::super::super::encoding::encode_codepoint;
+use super::super::super::internal::{
+    Error,
+    ErrorKind,
+    Result
+};
+use super::super::super::span::{
+    self,
+    Span,
+    Spanned
+};
+use super::super::super::tokens;
+
+pub fn string_double_quoted(input: &[u8]) -> Result<&[u8], Expression> {
+    let input_length = input.len();
+
+    if input_length < 2 {
+        return Result::Error(Error::Code(ErrorKind::Custom(StringError::TooShort as u32)));
+    }
+
+    if input[0] == b'b' || input[0] == b'B' {
+        if input_length < 3 {
+            return Result::Error(Error::Code(ErrorKind::Custom(StringError::TooShort as u32)));
+        } else if input[1] != b'"' {
+            return Result::Error(Error::Code(ErrorKind::Custom(StringError::InvalidOpeningCharacter as u32)));
+        } else {
+            return string_double_quoted(&input[1..]);
+        }
+    } else if input[0] != b'"' {
+        return Result::Error(Error::Code(ErrorKind::Custom(StringError::InvalidOpeningCharacter as u32)));
+    }
+
+    match interpolate(&input[1..], Some(b'"'), 0) {
+        Ok((length, parts, buffer)) => {
+            Result::Done(&input[length + 1..], into_string(parts, buffer))
+        },
+
+        Err(error) => {
+            Result::Error(Error::Code(ErrorKind::Custom(error as u32)))
+        }
+    }
+}
+
+const STRING_HEREDOC_OPENING: &[u8] = b"<<<";
+
+pub fn string_heredoc(input: &[u8]) -> Result<&[u8], Expression> {
+    let input_length = input.len();
+
+    // `<<<A\nA` is the shortest datum.
+    if input_length < 6 {
+        return Result::Error(Error::Code(ErrorKind::Custom(StringError::TooShort as u32)));
+    }
+
+    if input[0] == b'b' || input[0] == b'B' {
+        if input_length < 7 {
+            return Result::Error(Error::Code(ErrorKind::Custom(StringError::TooShort as u32)));
+        } else if !input[1..].starts_with(STRING_HEREDOC_OPENING) {
+            return Result::Error(Error::Code(ErrorKind::Custom(StringError::InvalidOpeningCharacter as u32)));
+        } else {
+            return string_heredoc(&input[1..]);
+        }
+    } else if !input.starts_with(STRING_HEREDOC_OPENING) {
+        return Result::Error(Error::Code(ErrorKind::Custom(StringError::InvalidOpeningCharacter as u32)));
+    }
+
+    let mut offset = 3;
+
+    for item in input[offset..].iter() {
+        if *item != b' ' && *item != b'\t' {
+            break;
+        }
+
+        offset += 1;
+    }
+
+    if offset == input_length {
+        return Result::Error(Error::Code(ErrorKind::Custom(StringError::InvalidDelimiterIdentifier as u32)));
+    }
+
+    let is_quoted = input[offset] == b'"';
+
+    if is_quoted {
+        offset += 1;
+    }
+
+    let identifier;
+    let mut next_input;
+
+    if let Result::Done(i, n) = name(&input[offset..]) {
+        identifier = n;
+        next_input = i;
+    } else {
+        return Result::Error(Error::Code(ErrorKind::Custom(StringError::InvalidDelimiterIdentifier as u32)));
+    }
+
+    if is_quoted {
+        if next_input.is_empty() || next_input[0] != b'"' {
+            return Result::Error(Error::Code(ErrorKind::Custom(StringError::InvalidOpeningCharacter as u32)));
+        }
+
+        next_input = &next_input[1..];
+    }
+
+    if next_input.starts_with(b"\n") {
+        next_input = &next_input[1..];
+    } else if next_input.starts_with(b"\r\n") {
+        next_input = &next_input[2..];
+    } else {
+        return Result::Error(Error::Code(ErrorKind::Custom(StringError::InvalidOpeningCharacter as u32)));
+    }
+
+    match string_document_body(next_input, identifier) {
+        Ok((body, indentation, rest)) => {
+            match interpolate(body, None, indentation.len()) {
+                Ok((_, mut parts, buffer)) => {
+                    if !buffer.is_empty() {
+                        parts.push(StringPart::Bytes(buffer));
+                    }
+
+                    Result::Done(
+                        rest,
+                        Expression::Heredoc {
+                            label      : identifier,
+                            indentation: indentation,
+                            parts      : parts
+                        }
+                    )
+                },
+
+                Err(kind) => string_document_error(input, "string_heredoc", kind)
+            }
+        },
+
+        Err(kind) => string_document_error(input, "string_heredoc", kind)
+    }
+}
+
+pub fn shell_exec(input: &[u8]) -> Result<&[u8], Expression> {
+    if input.len() < 2 {
+        return Result::Error(Error::Code(ErrorKind::Custom(StringError::TooShort as u32)));
+    }
+
+    if input[0] != b'`' {
+        return Result::Error(Error::Code(ErrorKind::Custom(StringError::InvalidOpeningCharacter as u32)));
+    }
+
+    match interpolate(&input[1..], Some(b'`'), 0) {
+        Ok((length, mut parts, buffer)) => {
+            if !buffer.is_empty() {
+                parts.push(StringPart::Bytes(buffer));
+            }
+
+            Result::Done(&input[length + 1..], Expression::ShellExec(parts))
+        },
+
+        Err(error) => {
+            Result::Error(Error::Code(ErrorKind::Custom(error as u32)))
+        }
+    }
+}
+
+/// Parse the content of an interpolated string until the delimiter, or
+/// until the end of the input if there is no delimiter, and return the
+/// number of consumed bytes (including the delimiter) with the parts
+/// collected so far and the pending bytes. The indentation is removed from
+/// the beginning of each line. An invalid escape sequence is reported as a
+/// diagnostic.
+fn interpolate<'a>(input: &'a [u8], delimiter: Option<u8>, indentation: usize) -> StdResult<(usize, Vec<StringPart<'a>>, Vec<u8>), StringError> {
+    let input_length   = input.len();
+    let mut parts      = Vec::new();
+    let mut buffer     = Vec::new();
+    let mut offset     = 0;
+    let mut line_start = true;
+
+    while offset < input_length {
+        if line_start {
+            offset     += string_document_indentation(&input[offset..], indentation);
+            line_start  = false;
+
+            if offset == input_length {
+                break;
+            }
+        }
+
+        let byte = input[offset];
+
+        if Some(byte) == delimiter {
+            return Ok((offset + 1, parts, buffer));
+        }
+
+        if byte == b'\\' {
+            match unescape(&input[offset + 1..], &mut buffer, delimiter) {
+                Ok(length) => offset += 1 + length,
+
+                Err(length) => {
+                    diagnostics::report(&input[offset..], &input[offset + 1 + length..], DiagnosticKind::InvalidUnicodeEscape);
+                    offset += 1 + length;
+                }
+            }
+        } else if let Some((length, expression)) = interpolation(&input[offset..]) {
+            if !buffer.is_empty() {
+                parts.push(StringPart::Bytes(buffer));
+                buffer = Vec::new();
+            }
+
+            parts.push(StringPart::Expression(expression));
+            offset += length;
+        } else {
+            buffer.push(byte);
+            offset     += 1;
+            line_start  = byte == b'\n';
+        }
+    }
+
+    match delimiter {
+        Some(_) => Err(StringError::InvalidClosingCharacter),
+        None    => Ok((input_length, parts, buffer))
+    }
+}
+
+/// Build the expression representing a string: a literal if nothing has
+/// been interpolated, an interpolated string otherwise.
+fn into_string<'a>(mut parts: Vec<StringPart<'a>>, buffer: Vec<u8>) -> Expression<'a> {
+    if parts.is_empty() {
+        return Expression::Literal(Literal::String(buffer));
+    }
+
+    if !buffer.is_empty() {
+        parts.push(StringPart::Bytes(buffer));
+    }
+
+    Expression::InterpolatedString(parts)
+}
+
+/// Evaluate the escape sequence following a backslash, push the result into
+/// the buffer, and return the number of consumed bytes after the backslash.
+/// An unknown escape sequence is kept verbatim. The delimiter can be
+/// escaped. An escaped `{` is kept verbatim too, but it cannot open a curly
+/// interpolation anymore. A `\u{…}` escape sequence must be terminated and must
+/// represent a codepoint, otherwise it is kept verbatim and its length is
+/// returned as an error.
+fn unescape(input: &[u8], buffer: &mut Vec<u8>, delimiter: Option<u8>) -> StdResult<usize, usize> {
+    if input.is_empty() {
+        buffer.push(b'\\');
+
+        return Ok(0);
+    }
+
+    let length = match input[0] {
+        b'n'  => { buffer.push(b'\n'); 1 },
+        b't'  => { buffer.push(b'\t'); 1 },
+        b'r'  => { buffer.push(b'\r'); 1 },
+        b'v'  => { buffer.push(0x0b); 1 },
+        b'e'  => { buffer.push(0x1b); 1 },
+        b'f'  => { buffer.push(0x0c); 1 },
+        b'\\' => { buffer.push(b'\\'); 1 },
+        b'$'  => { buffer.push(b'$'); 1 },
+
+        byte if (b'0'..=b'7').contains(&byte) => {
+            let length = digits(input, 3, 8);
+            let value  = u32::from_str_radix(unsafe { str::from_utf8_unchecked(&input[..length]) }, 8).unwrap();
+
+            buffer.push((value & 0xff) as u8);
+
+            length
+        },
+
+        b'x' => {
+            let length = digits(&input[1..], 2, 16);
+
+            if length == 0 {
+                buffer.push(b'\\');
+
+                return Ok(0);
+            }
+
+            buffer.push(u8::from_str_radix(unsafe { str::from_utf8_unchecked(&input[1..length + 1]) }, 16).unwrap());
+
+            length + 1
+        },
+
+        b'u' if input.len() > 1 && input[1] == b'{' => {
+            let length        = digits(&input[2..], input.len(), 16);
+            let is_terminated = input.len() > length + 2 && input[length + 2] == b'}';
+
+            if length == 0 || !is_terminated {
+                let length = if is_terminated { length + 3 } else { length + 2 };
+
+                buffer.push(b'\\');
+                buffer.extend_from_slice(&input[..length]);
+
+                return Err(length);
+            }
+
+            match u32::from_str_radix(unsafe { str::from_utf8_unchecked(&input[2..length + 2]) }, 16) {
+                Ok(codepoint) if codepoint <= 0x10ffff => {
+                    encode_codepoint(codepoint, buffer);
+                },
+
+                _ => {
+                    buffer.push(b'\\');
+                    buffer.extend_from_slice(&input[..length + 3]);
+
+                    return Err(length + 3);
+                }
+            }
+
+            length + 3
+        },
+
+        byte if Some(byte) == delimiter => {
+            buffer.push(byte);
+
+            1
+        },
+
+        b'{' => {
+            buffer.push(b'\\');
+            buffer.push(b'{');
+
+            1
+        },
+
+        _ => {
+            buffer.push(b'\\');
+
+            0
+        }
+    };
+
+    Ok(length)
+}
+
+/// Count the leading digits in the given radix, up to a maximum.
+fn digits(input: &[u8], maximum: usize, radix: u32) -> usize {
+    input
+        .iter()
+        .take(maximum)
+        .take_while(|byte| (**byte as char).is_digit(radix))
+        .count()
+}
+
+/// Parse an interpolation if any, and return the number of consumed bytes
+/// with the interpolated expression, spanning the whole interpolation.
+fn interpolation(input: &[u8]) -> Option<(usize, Spanned<Expression>)> {
+    span::within(
+        input,
+        |input| {
+            let result = if input.starts_with(DOLLAR_CURLY_BRACKET) {
+                spanned!(input, dollar_curly_interpolation)
+            } else if input.starts_with(CURLY_BRACKET_DOLLAR) {
+                spanned!(input, curly_interpolation)
+            } else if input.starts_with(tokens::VARIABLE) {
+                return simple_interpolation(input);
+            } else {
+                return None;
+            };
+
+            match result {
+                Result::Done(rest, expression) => Some((input.len() - rest.len(), expression)),
+                _ => None
+            }
+        }
+    )
+}
+
+const DOLLAR_CURLY_BRACKET: &[u8] = b"${";
+const CURLY_BRACKET_DOLLAR: &[u8] = b"{$";
+
+named!(
+    curly_interpolation<Expression>,
+    preceded!(
+        tag!(tokens::LEFT_CURLY_BRACKET),
+        terminated!(
+            expression,
+            first!(tag!(tokens::RIGHT_CURLY_BRACKET))
+        )
+    )
+);
+
+named!(
+    dollar_curly_interpolation<Expression>,
+    alt!(
+        dollar_curly_name_interpolation
+      | preceded!(
+            tag!(DOLLAR_CURLY_BRACKET),
+            terminated!(
+                first!(spanned!(expression)),
+                first!(tag!(tokens::RIGHT_CURLY_BRACKET))
+            )
+        ) => { dynamic_variable_mapper }
+    )
+);
+
+#[inline(always)]
+fn dynamic_variable_mapper<'a>(expression: Spanned<Expression<'a>>) -> Expression<'a> {
+    Expression::Variable(Variable::Dynamic(Box::new(expression)))
+}
+
+named!(
+    dollar_curly_name_interpolation<Expression>,
+    chain!(
+        tag!(DOLLAR_CURLY_BRACKET) ~
+        variable: first!(spanned!(name)) ~
+        index: opt!(
+            preceded!(
+                first!(tag!(tokens::LEFT_SQUARE_BRACKET)),
+                terminated!(
+                    first!(spanned!(expression)),
+                    first!(tag!(tokens::RIGHT_SQUARE_BRACKET))
+                )
+            )
+        ) ~
+        first!(tag!(tokens::RIGHT_CURLY_BRACKET)),
+        || {
+            let variable = variable.map(|name| Expression::Variable(Variable::Name(name)));
+
+            match index {
+                Some(index) => {
+                    Expression::ArrayAccess {
+                        array: Box::new(variable),
+                        index: Some(Box::new(index))
+                    }
+                },
+
+                None => {
+                    variable.node
+                }
+            }
+        }
+    )
+);
+
+/// Parse a simple interpolation, i.e. a variable optionally followed by an
+/// array access, a property fetch or a nullsafe property fetch, and return the number of consumed
+/// bytes with the interpolated expression.
+fn simple_interpolation(input: &[u8]) -> Option<(usize, Spanned<Expression>)> {
+    let (rest, variable) = match name(&input[1..]) {
+        Result::Done(rest, name) => (rest, Spanned::new(Expression::Variable(Variable::Name(name)), Span::new(input, rest))),
+        _ => return None
+    };
+
+    if rest.starts_with(tokens::LEFT_SQUARE_BRACKET) {
+        if let Some((length, index)) = interpolation_index(&rest[1..]) {
+            let next_rest = &rest[1 + length..];
+
+            return Some((
+                input.len() - next_rest.len(),
+                Spanned::new(
+                    Expression::ArrayAccess {
+                        array: Box::new(variable),
+                        index: Some(Box::new(Spanned::new(index, Span::new(&rest[1..], &rest[length..]))))
+                    },
+                    Span::new(input, next_rest)
+                )
+            ));
+        }
+    } else if rest.starts_with(tokens::DYNAMIC_CALL) {
+        if let Result::Done(next_rest, property) = name(&rest[2..]) {
+            return Some((
+                input.len() - next_rest.len(),
+                Spanned::new(
+                    Expression::PropertyFetch {
+                        object  : Box::new(variable),
+                        property: Box::new(Spanned::new(Expression::Name(Name::Unqualified(property)), Span::new(&rest[2..], next_rest)))
+                    },
+                    Span::new(input, next_rest)
+                )
+            ));
+        }
+    } else if rest.starts_with(tokens::NULLSAFE_DYNAMIC_CALL) {
+        if let Result::Done(next_rest, property) = name(&rest[3..]) {
+            return Some((
+                input.len() - next_rest.len(),
+                Spanned::new(
+                    Expression::NullsafePropertyFetch {
+                        object  : Box::new(variable),
+                        property: Box::new(Spanned::new(Expression::Name(Name::Unqualified(property)), Span::new(&rest[3..], next_rest)))
+                    },
+                    Span::new(input, next_rest)
+                )
+            ));
+        }
+    }
+
+    Some((input.len() - rest.len(), variable))
+}
+
+/// Parse the index of an array access inside a simple interpolation, i.e. a
+/// variable, an integer or an unquoted key, followed by a closing bracket,
+/// and return the number of consumed bytes with the index.
+fn interpolation_index(input: &[u8]) -> Option<(usize, Expression)> {
+    let length;
+    let index;
+
+    if input.starts_with(tokens::VARIABLE) {
+        match name(&input[1..]) {
+            Result::Done(rest, name) => {
+                length = input.len() - rest.len();
+                index  = Expression::Variable(Variable::Name(name));
+            },
+
+            _ => return None
+        }
+    } else {
+        let sign = if input.starts_with(b"-") { 1 } else { 0 };
+        let size = digits(&input[sign..], input.len(), 10);
+
+        if size > 0 {
+            let number = &input[..sign + size];
+
+            length = sign + size;
+            index  = match str::from_utf8(number).unwrap().parse::<i64>() {
+                Ok(integer) if size == 1 || input[sign] != b'0' => {
+                    Expression::Literal(Literal::Integer(integer))
+                },
+
+                _ => {
+                    Expression::Literal(Literal::String(number.to_vec()))
+                }
+            };
+        } else {
+            match name(input) {
+                Result::Done(rest, name) => {
+                    length = input.len() - rest.len();
+                    index  = Expression::Literal(Literal::String(name.to_vec()));
+                },
+
+                _ => return None
+            }
+        }
+    }
+
+    if input.len() > length && input[length] == b']' {
+        Some((length + 1, index))
+    } else {
+        None
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::ops::Range;
+    use super::{
+        shell_exec,
+        string_double_quoted,
+        string_heredoc
+    };
+    use super::super::expression;
+    use super::super::primaries::primary;
+    use super::super::super::literals::StringError;
+    use super::super::super::super::ast::{
+        Expression,
+        Literal,
+        Name,
+        StringPart,
+        Variable
+    };
+    use super::super::super::super::diagnostics::{
+        self,
+        Diagnostic,
+        DiagnosticKind
+    };
+    use super::super::super::super::internal::{
+        Error,
+        ErrorKind,
+        Result
+    };
+    use super::super::super::super::span::Spanned;
+
+    #[test]
+    fn case_string_double_quoted_without_interpolation() {
+        let input  = b"\"foobar\"";
+        let output = Result::Done(&b""[..], Expression::Literal(Literal::String(b"foobar".to_vec())));
+
+        assert_eq!(string_double_quoted(input), output);
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_string_double_quoted_empty() {
+        let input  = b"\"\"tail";
+        let output = Result::Done(&b"tail"[..], Expression::Literal(Literal::String(Vec::new())));
+
+        assert_eq!(string_double_quoted(input), output);
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_string_double_quoted_binary_uppercase_interpolated() {
+        let input  = b"B\"foo $bar\"";
+        let output = Result::Done(
+            &b""[..],
+            Expression::InterpolatedString(
+                vec![
+                    StringPart::Bytes(b"foo ".to_vec()),
+                    StringPart::Expression(Spanned::from(Expression::Variable(Variable::Name(&b"bar"[..]))))
+                ]
+            )
+        );
+
+        assert_eq!(string_double_quoted(input), output);
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_string_double_quoted_binary() {
+        let input  = b"b\"foobar\"";
+        let output = Result::Done(&b""[..], Expression::Literal(Literal::String(b"foobar".to_vec())));
+
+        assert_eq!(string_double_quoted(input), output);
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_string_double_quoted_escaped_sequences() {
+        let input  = b"\"\\n\\t\\r\\v\\e\\f\\\\\\$\\\"\\101\\x41\\u{1F418}\"";
+        let output = Result::Done(&b""[..], Expression::Literal(Literal::String(b"\n\t\r\x0b\x1b\x0c\\$\"AA\xf0\x9f\x90\x98".to_vec())));
+
+        assert_eq!(string_double_quoted(input), output);
+    }
+
+    #[test]
+    fn case_string_double_quoted_unknown_escaped_sequences() {
+        let input  = b"\"\\q\\x\\u\\{\"";
+        let output = Result::Done(&b""[..], Expression::Literal(Literal::String(b"\\q\\x\\u\\{".to_vec())));
+
+        assert_eq!(string_double_quoted(input), output);
+    }
+
+    #[test]
+    fn case_string_double_quoted_unicode_escaped_sequences() {
+        let input  = b"\"\\u{41}\\u{00000000e9}\\u{20ac}\\u{10ffff}\\u{d800}\"";
+        let output = Result::Done(&b""[..], Expression::Literal(Literal::String(b"A\xc3\xa9\xe2\x82\xac\xf4\x8f\xbf\xbf\xed\xa0\x80".to_vec())));
+
+        assert_eq!(string_double_quoted(input), output);
+    }
+
+    #[test]
+    fn case_invalid_string_double_quoted_unicode_escaped_sequence_empty() {
+        let input  = b"\"a\\u{}b\"";
+        let output = Result::Done(&b""[..], Expression::Literal(Literal::String(b"a\\u{}b".to_vec())));
+
+        assert_eq!(diagnostics::collect(&input[..], string_double_quoted), (output, vec![invalid_unicode_escape(2..6)]));
+    }
+
+    #[test]
+    fn case_invalid_string_double_quoted_unicode_escaped_sequence_not_terminated() {
+        let input  = b"\"\\u{41\"";
+        let output = Result::Done(&b""[..], Expression::Literal(Literal::String(b"\\u{41".to_vec())));
+
+        assert_eq!(diagnostics::collect(&input[..], string_double_quoted), (output, vec![invalid_unicode_escape(1..6)]));
+    }
+
+    #[test]
+    fn case_invalid_string_double_quoted_unicode_escaped_sequence_not_hexadecimal() {
+        let input  = b"\"\\u{zz}\"";
+        let output = Result::Done(&b""[..], Expression::Literal(Literal::String(b"\\u{zz}".to_vec())));
+
+        assert_eq!(diagnostics::collect(&input[..], string_double_quoted), (output, vec![invalid_unicode_escape(1..4)]));
+    }
+
+    #[test]
+    fn case_invalid_string_double_quoted_unicode_escaped_sequence_too_large() {
+        let input  = b"\"\\u{110000}\\u{fffffffff}\"";
+        let output = Result::Done(&b""[..], Expression::Literal(Literal::String(b"\\u{110000}\\u{fffffffff}".to_vec())));
+
+        assert_eq!(
+            diagnostics::collect(&input[..], string_double_quoted),
+            (output, vec![invalid_unicode_escape(1..11), invalid_unicode_escape(11..24)])
+        );
+    }
+
+    #[test]
+    fn case_invalid_string_heredoc_unicode_escaped_sequence() {
+        let input  = b"<<<FOO\n\\u{}\nFOO\n";
+        let output = Result::Done(
+            &b"\n"[..],
+            Expression::Heredoc {
+                label      : &b"FOO"[..],
+                indentation: &b""[..],
+                parts      : vec![
+                    StringPart::Bytes(b"\\u{}".to_vec())
+                ]
+            }
+        );
+
+        assert_eq!(diagnostics::collect(&input[..], string_heredoc), (output, vec![invalid_unicode_escape(7..11)]));
+    }
+
+    fn invalid_unicode_escape(span: Range<usize>) -> Diagnostic {
+        Diagnostic {
+            kind: DiagnosticKind::InvalidUnicodeEscape,
+            span: span
+        }
+    }
+
+    #[test]
+    fn case_string_double_quoted_octal_overflow() {
+        let input  = b"\"\\400\"";
+        let output = Result::Done(&b""[..], Expression::Literal(Literal::String(b"\x00".to_vec())));
+
+        assert_eq!(string_double_quoted(input), output);
+    }
+
+    #[test]
+    fn case_string_double_quoted_dollar_without_name() {
+        let input  = b"\"$ and $1\"";
+        let output = Result::Done(&b""[..], Expression::Literal(Literal::String(b"$ and $1".to_vec())));
+
+        assert_eq!(string_double_quoted(input), output);
+    }
+
+    #[test]
+    fn case_string_double_quoted_escaped_variable() {
+        let input  = b"\"\\$foo\"";
+        let output = Result::Done(&b""[..], Expression::Literal(Literal::String(b"$foo".to_vec())));
+
+        assert_eq!(string_double_quoted(input), output);
+    }
+
+    #[test]
+    fn case_interpolated_variables() {
+        let input  = b"\"Hello $name and $obj->prop!\"";
+        let output = Result::Done(
+            &b""[..],
+            Expression::InterpolatedString(
+                vec![
+                    StringPart::Bytes(b"Hello ".to_vec()),
+                    StringPart::Expression(Spanned::from(Expression::Variable(Variable::Name(&b"name"[..])))),
+                    StringPart::Bytes(b" and ".to_vec()),
+                    StringPart::Expression(
+                        Spanned::from(Expression::PropertyFetch {
+                            object  : Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"obj"[..])))),
+                            property: Box::new(Spanned::from(Expression::Name(Name::Unqualified(&b"prop"[..]))))
+                        })
+                    ),
+                    StringPart::Bytes(b"!".to_vec())
+                ]
+            )
+        );
+
+        assert_eq!(string_double_quoted(input), output);
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_interpolated_variable_only() {
+        let input  = b"\"$foo\"";
+        let output = Result::Done(
+            &b""[..],
+            Expression::InterpolatedString(
+                vec![StringPart::Expression(Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..]))))]
+            )
+        );
+
+        assert_eq!(string_double_quoted(input), output);
+    }
+
+    #[test]
+    fn case_interpolated_nullsafe_property_fetch() {
+        let input  = b"\"$a?->b?->c $d?->\"";
+        let output = Result::Done(
+            &b""[..],
+            Expression::InterpolatedString(
+                vec![
+                    StringPart::Expression(
+                        Spanned::from(Expression::NullsafePropertyFetch {
+                            object  : Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"a"[..])))),
+                            property: Box::new(Spanned::from(Expression::Name(Name::Unqualified(&b"b"[..]))))
+                        })
+                    ),
+                    StringPart::Bytes(b"?->c ".to_vec()),
+                    StringPart::Expression(Spanned::from(Expression::Variable(Variable::Name(&b"d"[..])))),
+                    StringPart::Bytes(b"?->".to_vec())
+                ]
+            )
+        );
+
+        assert_eq!(string_double_quoted(input), output);
+    }
+
+    #[test]
+    fn case_interpolated_property_fetch_is_not_chained() {
+        let input  = b"\"$a->b->c\"";
+        let output = Result::Done(
+            &b""[..],
+            Expression::InterpolatedString(
+                vec![
+                    StringPart::Expression(
+                        Spanned::from(Expression::PropertyFetch {
+                            object  : Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"a"[..])))),
+                            property: Box::new(Spanned::from(Expression::Name(Name::Unqualified(&b"b"[..]))))
+                        })
+                    ),
+                    StringPart::Bytes(b"->c".to_vec())
+                ]
+            )
+        );
+
+        assert_eq!(string_double_quoted(input), output);
+    }
+
+    macro_rules! test_interpolated_array_access {
+        ($test_case_name:ident: ($input:expr, $index:expr)) => (
+            #[test]
+            fn $test_case_name() {
+                let output = Result::Done(
+                    &b""[..],
+                    Expression::InterpolatedString(
+                        vec![
+                            StringPart::Expression(
+                                Spanned::from(Expression::ArrayAccess {
+                                    array: Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..])))),
+                                    index: Some(Box::new($index))
+                                })
+                            ),
+                            StringPart::Bytes(b"[1]".to_vec())
+                        ]
+                    )
+                );
+
+                assert_eq!(string_double_quoted($input), output);
+            }
+        );
+    }
+
+    test_interpolated_array_access!(case_interpolated_array_access_integer:          (b"\"$foo[42][1]\"", Spanned::from(Expression::Literal(Literal::Integer(42i64)))));
+    test_interpolated_array_access!(case_interpolated_array_access_negative_integer: (b"\"$foo[-7][1]\"", Spanned::from(Expression::Literal(Literal::Integer(-7i64)))));
+    test_interpolated_array_access!(case_interpolated_array_access_leading_zero:     (b"\"$foo[007][1]\"", Spanned::from(Expression::Literal(Literal::String(b"007".to_vec())))));
+    test_interpolated_array_access!(case_interpolated_array_access_unquoted_key:     (b"\"$foo[bar][1]\"", Spanned::from(Expression::Literal(Literal::String(b"bar".to_vec())))));
+    test_interpolated_array_access!(case_interpolated_array_access_variable:         (b"\"$foo[$bar][1]\"", Spanned::from(Expression::Variable(Variable::Name(&b"bar"[..])))));
+
+    #[test]
+    fn case_interpolated_array_access_not_closed() {
+        let input  = b"\"$foo[bar\"";
+        let output = Result::Done(
+            &b""[..],
+            Expression::InterpolatedString(
+                vec![
+                    StringPart::Expression(Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..])))),
+                    StringPart::Bytes(b"[bar".to_vec())
+                ]
+            )
+        );
+
+        assert_eq!(string_double_quoted(input), output);
+    }
+
+    #[test]
+    fn case_invalid_string_double_quoted_too_short() {
+        assert_eq!(string_double_quoted(b"\""), Result::Error(Error::Code(ErrorKind::Custom(StringError::TooShort as u32))));
+    }
+
+    #[test]
+    fn case_invalid_string_double_quoted_opening_character() {
+        assert_eq!(string_double_quoted(b"'foo'"), Result::Error(Error::Code(ErrorKind::Custom(StringError::InvalidOpeningCharacter as u32))));
+    }
+
+    #[test]
+    fn case_invalid_string_double_quoted_not_closed() {
+        assert_eq!(string_double_quoted(b"\"foo $bar"), Result::Error(Error::Code(ErrorKind::Custom(StringError::InvalidClosingCharacter as u32))));
+    }
+
+    #[test]
+    fn case_invalid_string_double_quoted_escaped_closing_character() {
+        assert_eq!(string_double_quoted(b"\"foo\\\""), Result::Error(Error::Code(ErrorKind::Custom(StringError::InvalidClosingCharacter as u32))));
+    }
+
+    #[test]
+    fn case_interpolated_curly_expression() {
+        let input  = b"\"a{$obj->method()['key']}b\"";
+        let output = Result::Done(
+            &b""[..],
+            Expression::InterpolatedString(
+                vec![
+                    StringPart::Bytes(b"a".to_vec()),
+                    StringPart::Expression(
+                        Spanned::from(Expression::ArrayAccess {
+                            array: Box::new(
+                                Spanned::from(Expression::MethodCall {
+                                    object   : Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"obj"[..])))),
+                                    method   : Box::new(Spanned::from(Expression::Name(Name::Unqualified(&b"method"[..])))),
+                                    arguments: vec![]
+                                })
+                            ),
+                            index: Some(Box::new(Spanned::from(Expression::Literal(Literal::String(b"key".to_vec())))))
+                        })
+                    ),
+                    StringPart::Bytes(b"b".to_vec())
+                ]
+            )
+        );
+
+        assert_eq!(string_double_quoted(input), output);
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_interpolated_curly_expression_with_a_string() {
+        let input  = b"\"{$arr[\"k\"]}\"";
+        let output = Result::Done(
+            &b""[..],
+            Expression::InterpolatedString(
+                vec![
+                    StringPart::Expression(
+                        Spanned::from(Expression::ArrayAccess {
+                            array: Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"arr"[..])))),
+                            index: Some(Box::new(Spanned::from(Expression::Literal(Literal::String(b"k".to_vec())))))
+                        })
+                    )
+                ]
+            )
+        );
+
+        assert_eq!(string_double_quoted(input), output);
+    }
+
+    #[test]
+    fn case_interpolated_curly_bracket_without_dollar() {
+        let input  = b"\"{ $foo}\"";
+        let output = Result::Done(
+            &b""[..],
+            Expression::InterpolatedString(
+                vec![
+                    StringPart::Bytes(b"{ ".to_vec()),
+                    StringPart::Expression(Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..])))),
+                    StringPart::Bytes(b"}".to_vec())
+                ]
+            )
+        );
+
+        assert_eq!(string_double_quoted(input), output);
+    }
+
+    #[test]
+    fn case_interpolated_curly_bracket_escaped_dollar() {
+        let input  = b"\"{\\$foo}\"";
+        let output = Result::Done(&b""[..], Expression::Literal(Literal::String(b"{$foo}".to_vec())));
+
+        assert_eq!(string_double_quoted(input), output);
+    }
+
+    #[test]
+    fn case_interpolated_curly_bracket_escaped() {
+        let input  = b"\"\\{$foo}\"";
+        let output = Result::Done(
+            &b""[..],
+            Expression::InterpolatedString(
+                vec![
+                    StringPart::Bytes(b"\\{".to_vec()),
+                    StringPart::Expression(Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..])))),
+                    StringPart::Bytes(b"}".to_vec())
+                ]
+            )
+        );
+
+        assert_eq!(string_double_quoted(input), output);
+    }
+
+    #[test]
+    fn case_interpolated_dollar_curly_name() {
+        let input  = b"\"${name}s\"";
+        let output = Result::Done(
+            &b""[..],
+            Expression::InterpolatedString(
+                vec![
+                    StringPart::Expression(Spanned::from(Expression::Variable(Variable::Name(&b"name"[..])))),
+                    StringPart::Bytes(b"s".to_vec())
+                ]
+            )
+        );
+
+        assert_eq!(string_double_quoted(input), output);
+    }
+
+    #[test]
+    fn case_interpolated_dollar_curly_array_access() {
+        let input  = b"\"${name['key']}\"";
+        let output = Result::Done(
+            &b""[..],
+            Expression::InterpolatedString(
+                vec![
+                    StringPart::Expression(
+                        Spanned::from(Expression::ArrayAccess {
+                            array: Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"name"[..])))),
+                            index: Some(Box::new(Spanned::from(Expression::Literal(Literal::String(b"key".to_vec())))))
+                        })
+                    )
+                ]
+            )
+        );
+
+        assert_eq!(string_double_quoted(input), output);
+    }
+
+    #[test]
+    fn case_interpolated_dollar_curly_expression() {
+        let input  = b"\"a ${$foo} b\"";
+        let output = Result::Done(
+            &b""[..],
+            Expression::InterpolatedString(
+                vec![
+                    StringPart::Bytes(b"a ".to_vec()),
+                    StringPart::Expression(
+                        Spanned::from(Expression::Variable(
+                            Variable::Dynamic(
+                                Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..]))))
+                            )
+                        ))
+                    ),
+                    StringPart::Bytes(b" b".to_vec())
+                ]
+            )
+        );
+
+        assert_eq!(string_double_quoted(input), output);
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_interpolated_curly_expression_not_closed() {
+        let input  = b"\"{$foo\"";
+        let output = Result::Done(
+            &b""[..],
+            Expression::InterpolatedString(
+                vec![
+                    StringPart::Bytes(b"{".to_vec()),
+                    StringPart::Expression(Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..]))))
+                ]
+            )
+        );
+
+        assert_eq!(string_double_quoted(input), output);
+    }
+
+    #[test]
+    fn case_string_heredoc() {
+        let input  = b"<<<FOO\nhello \"$name\"\n  {$world}\\t\nFOO;\n";
+        let output = Result::Done(
+            &b";\n"[..],
+            Expression::Heredoc {
+                label      : &b"FOO"[..],
+                indentation: &b""[..],
+                parts      : vec![
+                    StringPart::Bytes(b"hello \"".to_vec()),
+                    StringPart::Expression(Spanned::from(Expression::Variable(Variable::Name(&b"name"[..])))),
+                    StringPart::Bytes(b"\"\n  ".to_vec()),
+                    StringPart::Expression(Spanned::from(Expression::Variable(Variable::Name(&b"world"[..])))),
+                    StringPart::Bytes(b"\t".to_vec())
+                ]
+            }
+        );
+
+        assert_eq!(string_heredoc(input), output);
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_string_heredoc_quoted_identifier_crlf() {
+        let input  = b"<<<  \"FOO\"\r\nhello\r\nFOO\r\n";
+        let output = Result::Done(
+            &b"\r\n"[..],
+            Expression::Heredoc {
+                label      : &b"FOO"[..],
+                indentation: &b""[..],
+                parts      : vec![
+                    StringPart::Bytes(b"hello".to_vec())
+                ]
+            }
+        );
+
+        assert_eq!(string_heredoc(input), output);
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_string_heredoc_empty() {
+        let input  = b"<<<FOO\nFOO\n";
+        let output = Result::Done(
+            &b"\n"[..],
+            Expression::Heredoc {
+                label      : &b"FOO"[..],
+                indentation: &b""[..],
+                parts      : vec![]
+            }
+        );
+
+        assert_eq!(string_heredoc(input), output);
+    }
+
+    #[test]
+    fn case_string_heredoc_escaped_double_quote_is_kept() {
+        let input  = b"<<<FOO\n\\\"\\$foo\nFOO\n";
+        let output = Result::Done(
+            &b"\n"[..],
+            Expression::Heredoc {
+                label      : &b"FOO"[..],
+                indentation: &b""[..],
+                parts      : vec![
+                    StringPart::Bytes(b"\\\"$foo".to_vec())
+                ]
+            }
+        );
+
+        assert_eq!(string_heredoc(input), output);
+    }
+
+    #[test]
+    fn case_string_heredoc_identifier_prefix_in_the_body() {
+        let input  = b"<<<FOO\nFOOBAR\nFOO\n";
+        let output = Result::Done(
+            &b"\n"[..],
+            Expression::Heredoc {
+                label      : &b"FOO"[..],
+                indentation: &b""[..],
+                parts      : vec![
+                    StringPart::Bytes(b"FOOBAR".to_vec())
+                ]
+            }
+        );
+
+        assert_eq!(string_heredoc(input), output);
+    }
+
+    #[test]
+    fn case_string_binary_heredoc() {
+        let input  = b"b<<<FOO\nhello\nFOO\n";
+        let output = Result::Done(
+            &b"\n"[..],
+            Expression::Heredoc {
+                label      : &b"FOO"[..],
+                indentation: &b""[..],
+                parts      : vec![
+                    StringPart::Bytes(b"hello".to_vec())
+                ]
+            }
+        );
+
+        assert_eq!(string_heredoc(input), output);
+        assert_eq!(primary(input), output);
+    }
+
+    #[test]
+    fn case_nowdoc_is_not_interpolated() {
+        let input  = b"<<<'FOO'\n$foo\nFOO\n";
+        let output = Result::Done(
+            &b"\n"[..],
+            Expression::Literal(
+                Literal::Nowdoc {
+                    label      : b"FOO".to_vec(),
+                    indentation: Vec::new(),
+                    value      : b"$foo".to_vec()
+                }
+            )
+        );
+
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_string_heredoc_indented_closing_identifier() {
+        let input  = b"<<<FOO\n    a $b\n\n      {$c}\n    FOO;";
+        let output = Result::Done(
+            &b";"[..],
+            Expression::Heredoc {
+                label      : &b"FOO"[..],
+                indentation: &b"    "[..],
+                parts      : vec![
+                    StringPart::Bytes(b"a ".to_vec()),
+                    StringPart::Expression(Spanned::from(Expression::Variable(Variable::Name(&b"b"[..])))),
+                    StringPart::Bytes(b"\n\n  ".to_vec()),
+                    StringPart::Expression(Spanned::from(Expression::Variable(Variable::Name(&b"c"[..]))))
+                ]
+            }
+        );
+
+        assert_eq!(string_heredoc(input), output);
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_string_heredoc_indentation_is_not_removed_after_an_escaped_newline() {
+        let input  = b"<<<FOO\n  a\\n  b\n  FOO";
+        let output = Result::Done(
+            &b""[..],
+            Expression::Heredoc {
+                label      : &b"FOO"[..],
+                indentation: &b"  "[..],
+                parts      : vec![
+                    StringPart::Bytes(b"a\n  b".to_vec())
+                ]
+            }
+        );
+
+        assert_eq!(string_heredoc(input), output);
+    }
+
+    #[test]
+    fn case_invalid_string_heredoc_under_indented_line() {
+        assert_eq!(string_heredoc(b"<<<FOO\n  a\n b\n  FOO\n"), Result::Error(Error::Code(ErrorKind::Custom(StringError::InvalidIndentation as u32))));
+    }
+
+    #[test]
+    fn case_invalid_string_heredoc_too_short() {
+        assert_eq!(string_heredoc(b"<<<A\n"), Result::Error(Error::Code(ErrorKind::Custom(StringError::TooShort as u32))));
+    }
+
+    #[test]
+    fn case_invalid_string_heredoc_missing_newline() {
+        assert_eq!(string_heredoc(b"<<<FOO hello\nFOO\n"), Result::Error(Error::Code(ErrorKind::Custom(StringError::InvalidOpeningCharacter as u32))));
+    }
+
+    #[test]
+    fn case_invalid_string_heredoc_missing_closing_quote() {
+        assert_eq!(string_heredoc(b"<<<\"FOO\nhello\nFOO\n"), Result::Error(Error::Code(ErrorKind::Custom(StringError::InvalidOpeningCharacter as u32))));
+    }
+
+    #[test]
+    fn case_invalid_string_heredoc_invalid_identifier() {
+        assert_eq!(string_heredoc(b"<<<42\nhello\n42\n"), Result::Error(Error::Code(ErrorKind::Custom(StringError::InvalidDelimiterIdentifier as u32))));
+    }
+
+    #[test]
+    fn case_invalid_string_heredoc_missing_identifier() {
+        assert_eq!(string_heredoc(b"<<<     "), Result::Error(Error::Code(ErrorKind::Custom(StringError::InvalidDelimiterIdentifier as u32))));
+    }
+
+    #[test]
+    fn case_invalid_string_heredoc_not_closed() {
+        assert_eq!(string_heredoc(b"<<<FOO\nhello\nFO\n"), Result::Error(Error::Code(ErrorKind::Custom(StringError::InvalidClosingCharacter as u32))));
+    }
+
+    #[test]
+    fn case_shell_exec() {
+        let input  = b"`ls -la $dir`";
+        let output = Result::Done(
+            &b""[..],
+            Expression::ShellExec(
+                vec![
+                    StringPart::Bytes(b"ls -la ".to_vec()),
+                    StringPart::Expression(Spanned::from(Expression::Variable(Variable::Name(&b"dir"[..]))))
+                ]
+            )
+        );
+
+        assert_eq!(shell_exec(input), output);
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_shell_exec_without_interpolation() {
+        let input  = b"`echo \\`uptime\\` \"$\"`";
+        let output = Result::Done(&b""[..], Expression::ShellExec(vec![StringPart::Bytes(b"echo `uptime` \"$\"".to_vec())]));
+
+        assert_eq!(shell_exec(input), output);
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_shell_exec_empty() {
+        let input  = b"``";
+        let output = Result::Done(&b""[..], Expression::ShellExec(vec![]));
+
+        assert_eq!(shell_exec(input), output);
+        assert_eq!(primary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_invalid_shell_exec_too_short() {
+        assert_eq!(shell_exec(b"`"), Result::Error(Error::Code(ErrorKind::Custom(StringError::TooShort as u32))));
+    }
+
+    #[test]
+    fn case_invalid_shell_exec_opening_character() {
+        assert_eq!(shell_exec(b"'ls'"), Result::Error(Error::Code(ErrorKind::Custom(StringError::InvalidOpeningCharacter as u32))));
+    }
+
+    #[test]
+    fn case_invalid_shell_exec_not_closed() {
+        assert_eq!(shell_exec(b"`ls $dir"), Result::Error(Error::Code(ErrorKind::Custom(StringError::InvalidClosingCharacter as u32))));
+    }
+}
@@ -0,0 +1,688 @@
+// Tagua VM
+//
+//
+// New BSD License
+//
+// Copyright © 2016-2016, Ivan Enderlin.
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//     * Redistributions of source code must retain the above copyright
+//       notice, this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright
+//       notice, this list of conditions and the following disclaimer in the
+//       documentation and/or other materials provided with the distribution.
+//     * Neither the name of the Hoa nor the names of its contributors may be
+//       used to endorse or promote products derived from this software without
+//       specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE
+// ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDERS AND CONTRIBUTORS BE
+// LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR
+// CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF
+// SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS
+// INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN
+// CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+
+//! Group of unary expression rules.
+//!
+//! The list of all unary expressions is provided by the PHP Language
+//! Specification in the [Grammar chapter, Expressions
+//! section](https://github.com/php/php-langspec/blob/master/spec/19-grammar.md#unary-operators).
+
+use std::result::Result as StdResult;
+use super::binaries::power;
+use super::postfixes::postfix;
+use super::super::super::ast::{
+    CastType,
+    Expression
+};
+use super::super::super::tokens;
+
+named!(
+    pub unary<Expression>,
+    alt!(
+        cast
+      | power
+      | negation
+    )
+);
+
+named!(
+    pub prefix_increment<Expression>,
+    chain!(
+        tag!(tokens::INCREMENT) ~
+        operand: first!(spanned!(modifiable_operand)),
+        || { Expression::PreIncrement(Box::new(operand)) }
+    )
+);
+
+named!(
+    pub prefix_decrement<Expression>,
+    chain!(
+        tag!(tokens::DECREMENT) ~
+        operand: first!(spanned!(modifiable_operand)),
+        || { Expression::PreDecrement(Box::new(operand)) }
+    )
+);
+
+named!(
+    pub postfix_increment<Expression>,
+    chain!(
+        operand: spanned!(modifiable_operand) ~
+        complete!(first!(tag!(tokens::INCREMENT))),
+        || { Expression::PostIncrement(Box::new(operand)) }
+    )
+);
+
+named!(
+    pub postfix_decrement<Expression>,
+    chain!(
+        operand: spanned!(modifiable_operand) ~
+        complete!(first!(tag!(tokens::DECREMENT))),
+        || { Expression::PostDecrement(Box::new(operand)) }
+    )
+);
+
+named!(
+    pub clone<Expression>,
+    chain!(
+        keyword!(tokens::CLONE) ~
+        operand: first!(
+            spanned!(
+                alt!(
+                    clone
+                  | postfix
+                )
+            )
+        ),
+        || { Expression::Clone(Box::new(operand)) }
+    )
+);
+
+named!(
+    pub negation<Expression>,
+    chain!(
+        tag!(tokens::SUBTRACT) ~
+        not!(tag!(tokens::SUBTRACT)) ~
+        operand: first!(spanned!(unary)),
+        || { Expression::Negation(Box::new(operand)) }
+    )
+);
+
+named!(
+    pub cast<Expression>,
+    chain!(
+        tag!(tokens::LEFT_PARENTHESIS) ~
+        opt!(is_a!(" \t")) ~
+        cast_type: cast_type ~
+        opt!(is_a!(" \t")) ~
+        tag!(tokens::RIGHT_PARENTHESIS) ~
+        operand: first!(spanned!(unary)),
+        || {
+            Expression::Cast {
+                ty     : cast_type,
+                operand: Box::new(operand)
+            }
+        }
+    )
+);
+
+named!(
+    cast_type<CastType>,
+    alt_complete!(
+        keyword!(tokens::ARRAY)   => { |_| CastType::Array }
+      | keyword!(tokens::BOOLEAN) => { |_| CastType::Boolean }
+      | keyword!(tokens::BOOL)    => { |_| CastType::Boolean }
+      | keyword!(tokens::DOUBLE)  => { |_| CastType::Real }
+      | keyword!(tokens::FLOAT)   => { |_| CastType::Real }
+      | keyword!(tokens::INTEGER) => { |_| CastType::Integer }
+      | keyword!(tokens::INT)     => { |_| CastType::Integer }
+      | keyword!(tokens::OBJECT)  => { |_| CastType::Object }
+      | keyword!(tokens::REAL)    => { |_| CastType::Real }
+      | keyword!(tokens::STRING)  => { |_| CastType::String }
+      | keyword!(tokens::UNSET)   => { |_| CastType::Unset }
+    )
+);
+
+named!(
+    modifiable_operand<Expression>,
+    map_res!(
+        postfix,
+        modifiable_operand_mapper
+    )
+);
+
+#[inline(always)]
+pub fn modifiable_operand_mapper<'a>(operand: Expression<'a>) -> StdResult<Expression<'a>, ()> {
+    if is_modifiable(&operand) {
+        Ok(operand)
+    } else {
+        Err(())
+    }
+}
+
+/// Check whether an expression designates a storage that can be modified,
+/// i.e. a variable, an array element, a property or a static property.
+pub fn is_modifiable(operand: &Expression) -> bool {
+    match *operand {
+        Expression::Variable(_) |
+        Expression::ArrayAccess { .. } |
+        Expression::PropertyFetch { .. } => {
+            true
+        },
+
+        Expression::StaticFetch { ref member, .. } => {
+            matches!(member.node, Expression::Variable(_))
+        },
+
+        _ => {
+            false
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        cast,
+        clone,
+        negation,
+        postfix_decrement,
+        postfix_increment,
+        prefix_decrement,
+        prefix_increment,
+        unary
+    };
+    use super::super::expression;
+    use super::super::super::super::ast::{
+        CastType,
+        Expression,
+        Literal,
+        Name,
+        Variable
+    };
+    use super::super::super::super::internal::{
+        Error,
+        ErrorKind,
+        Result
+    };
+    use super::super::super::super::span::Spanned;
+
+    #[test]
+    fn case_prefix_increment() {
+        let input  = b"++$foo";
+        let output = Result::Done(
+            &b""[..],
+            Expression::PreIncrement(
+                Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..]))))
+            )
+        );
+
+        assert_eq!(prefix_increment(input), output);
+        assert_eq!(unary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_prefix_increment_with_skip_tokens() {
+        let input  = b"++ /* foo */ $foo";
+        let output = Result::Done(
+            &b""[..],
+            Expression::PreIncrement(
+                Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..]))))
+            )
+        );
+
+        assert_eq!(prefix_increment(input), output);
+        assert_eq!(unary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_invalid_prefix_increment_not_a_variable() {
+        let input  = b"++42";
+        let output = Result::Error(Error::Position(ErrorKind::Alt, &b"++42"[..]));
+
+        assert_eq!(prefix_increment(input), Result::Error(Error::Position(ErrorKind::MapRes, &b"42"[..])));
+        assert_eq!(unary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_prefix_decrement() {
+        let input  = b"--$foo";
+        let output = Result::Done(
+            &b""[..],
+            Expression::PreDecrement(
+                Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..]))))
+            )
+        );
+
+        assert_eq!(prefix_decrement(input), output);
+        assert_eq!(unary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_invalid_prefix_decrement_not_a_variable() {
+        let input  = b"--42";
+        let output = Result::Error(Error::Position(ErrorKind::Alt, &b"--42"[..]));
+
+        assert_eq!(prefix_decrement(input), Result::Error(Error::Position(ErrorKind::MapRes, &b"42"[..])));
+        assert_eq!(unary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_prefix_increment_array_access() {
+        let input  = b"++$foo[42]";
+        let output = Result::Done(
+            &b""[..],
+            Expression::PreIncrement(
+                Box::new(
+                    Spanned::from(Expression::ArrayAccess {
+                        array: Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..])))),
+                        index: Some(Box::new(Spanned::from(Expression::Literal(Literal::Integer(42i64)))))
+                    })
+                )
+            )
+        );
+
+        assert_eq!(prefix_increment(input), output);
+        assert_eq!(unary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_postfix_decrement_array_access() {
+        let input  = b"$foo[0][1]--";
+        let output = Result::Done(
+            &b""[..],
+            Expression::PostDecrement(
+                Box::new(
+                    Spanned::from(Expression::ArrayAccess {
+                        array: Box::new(
+                            Spanned::from(Expression::ArrayAccess {
+                                array: Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..])))),
+                                index: Some(Box::new(Spanned::from(Expression::Literal(Literal::Integer(0i64)))))
+                            })
+                        ),
+                        index: Some(Box::new(Spanned::from(Expression::Literal(Literal::Integer(1i64)))))
+                    })
+                )
+            )
+        );
+
+        assert_eq!(postfix_decrement(input), output);
+        assert_eq!(unary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_postfix_increment_property_fetch() {
+        let input  = b"$foo->bar++";
+        let output = Result::Done(
+            &b""[..],
+            Expression::PostIncrement(
+                Box::new(
+                    Spanned::from(Expression::PropertyFetch {
+                        object  : Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..])))),
+                        property: Box::new(Spanned::from(Expression::Name(Name::Unqualified(&b"bar"[..]))))
+                    })
+                )
+            )
+        );
+
+        assert_eq!(postfix_increment(input), output);
+        assert_eq!(unary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_prefix_decrement_static_property() {
+        let input  = b"--Foo::$bar";
+        let output = Result::Done(
+            &b""[..],
+            Expression::PreDecrement(
+                Box::new(
+                    Spanned::from(Expression::StaticFetch {
+                        class : Box::new(Spanned::from(Expression::Name(Name::Unqualified(&b"Foo"[..])))),
+                        member: Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"bar"[..]))))
+                    })
+                )
+            )
+        );
+
+        assert_eq!(prefix_decrement(input), output);
+        assert_eq!(unary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_invalid_prefix_decrement_class_constant() {
+        let input = b"--Foo::BAR";
+
+        assert_eq!(prefix_decrement(input), Result::Error(Error::Position(ErrorKind::MapRes, &b"Foo::BAR"[..])));
+    }
+
+    #[test]
+    fn case_invalid_prefix_increment_not_modifiable() {
+        let input = b"++foo(...)";
+
+        assert_eq!(prefix_increment(input), Result::Error(Error::Position(ErrorKind::MapRes, &b"foo(...)"[..])));
+    }
+
+    #[test]
+    fn case_postfix_increment() {
+        let input  = b"$foo++";
+        let output = Result::Done(
+            &b""[..],
+            Expression::PostIncrement(
+                Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..]))))
+            )
+        );
+
+        assert_eq!(postfix_increment(input), output);
+        assert_eq!(unary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_postfix_increment_with_skip_tokens() {
+        let input  = b"$foo /* bar */ ++";
+        let output = Result::Done(
+            &b""[..],
+            Expression::PostIncrement(
+                Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..]))))
+            )
+        );
+
+        assert_eq!(postfix_increment(input), output);
+        assert_eq!(unary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_postfix_decrement() {
+        let input  = b"$foo--";
+        let output = Result::Done(
+            &b""[..],
+            Expression::PostDecrement(
+                Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..]))))
+            )
+        );
+
+        assert_eq!(postfix_decrement(input), output);
+        assert_eq!(unary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_variable_without_postfix_operator() {
+        let input  = b"$foo";
+        let output = Result::Done(&b""[..], Expression::Variable(Variable::Name(&b"foo"[..])));
+
+        assert_eq!(unary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_clone() {
+        let input  = b"clone $foo";
+        let output = Result::Done(
+            &b""[..],
+            Expression::Clone(
+                Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..]))))
+            )
+        );
+
+        assert_eq!(clone(input), output);
+        assert_eq!(unary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_clone_case_insensitive_with_skip_tokens() {
+        let input  = b"ClOnE/* bar */$foo";
+        let output = Result::Done(
+            &b""[..],
+            Expression::Clone(
+                Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..]))))
+            )
+        );
+
+        assert_eq!(clone(input), output);
+        assert_eq!(unary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_clone_recursive() {
+        let input  = b"clone clone $foo";
+        let output = Result::Done(
+            &b""[..],
+            Expression::Clone(
+                Box::new(
+                    Spanned::from(Expression::Clone(
+                        Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..]))))
+                    ))
+                )
+            )
+        );
+
+        assert_eq!(clone(input), output);
+        assert_eq!(unary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_clone_new() {
+        let input  = b"clone new Foo";
+        let output = Result::Done(
+            &b""[..],
+            Expression::Clone(
+                Box::new(
+                    Spanned::from(Expression::New {
+                        class    : Box::new(Spanned::from(Expression::Name(Name::Unqualified(&b"Foo"[..])))),
+                        arguments: vec![]
+                    })
+                )
+            )
+        );
+
+        assert_eq!(clone(input), output);
+        assert_eq!(unary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_clone_has_a_higher_precedence_than_instanceof() {
+        let input  = b"clone $foo instanceof Bar";
+        let output = Result::Done(
+            &b""[..],
+            Expression::InstanceOf {
+                subject: Box::new(
+                    Spanned::from(Expression::Clone(
+                        Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..]))))
+                    ))
+                ),
+                class: Box::new(Spanned::from(Expression::Name(Name::Unqualified(&b"Bar"[..]))))
+            }
+        );
+
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_name_starting_with_clone_is_not_a_clone() {
+        let input  = b"cloneable";
+        let output = Result::Done(&b""[..], Expression::Name(Name::Unqualified(&b"cloneable"[..])));
+
+        assert_eq!(unary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_invalid_clone_operand_missing() {
+        let input = b"clone;";
+
+        assert_eq!(clone(input), Result::Error(Error::Position(ErrorKind::Alt, &b";"[..])));
+        assert_eq!(unary(input), Result::Error(Error::Position(ErrorKind::Alt, &b"clone;"[..])));
+    }
+
+    macro_rules! test_cast {
+        ($test_case_name:ident: ($string:expr, $expect:expr)) => (
+            #[test]
+            fn $test_case_name() {
+                let output = Result::Done(
+                    &b""[..],
+                    Expression::Cast {
+                        ty     : $expect,
+                        operand: Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..]))))
+                    }
+                );
+
+                assert_eq!(cast($string), output);
+                assert_eq!(unary($string), output);
+                assert_eq!(expression($string), output);
+            }
+        )
+    }
+
+    test_cast!(case_cast_array:   (b"(array)$foo", CastType::Array));
+    test_cast!(case_cast_bool:    (b"(bool)$foo", CastType::Boolean));
+    test_cast!(case_cast_boolean: (b"(boolean)$foo", CastType::Boolean));
+    test_cast!(case_cast_double:  (b"(double)$foo", CastType::Real));
+    test_cast!(case_cast_float:   (b"(float)$foo", CastType::Real));
+    test_cast!(case_cast_int:     (b"(int)$foo", CastType::Integer));
+    test_cast!(case_cast_integer: (b"(integer)$foo", CastType::Integer));
+    test_cast!(case_cast_object:  (b"(object)$foo", CastType::Object));
+    test_cast!(case_cast_real:    (b"(real)$foo", CastType::Real));
+    test_cast!(case_cast_string:  (b"(string)$foo", CastType::String));
+    test_cast!(case_cast_unset:   (b"(unset)$foo", CastType::Unset));
+
+    test_cast!(case_cast_case_insensitive: (b"(InTeGeR)$foo", CastType::Integer));
+    test_cast!(case_cast_with_spaces_and_tabulations: (b"( \t int\t )  $foo", CastType::Integer));
+    test_cast!(case_cast_with_skip_tokens_before_the_operand: (b"(int) /* bar */ $foo", CastType::Integer));
+
+    #[test]
+    fn case_cast_recursive() {
+        let input  = b"(int) (string) $foo";
+        let output = Result::Done(
+            &b""[..],
+            Expression::Cast {
+                ty     : CastType::Integer,
+                operand: Box::new(
+                    Spanned::from(Expression::Cast {
+                        ty     : CastType::String,
+                        operand: Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..]))))
+                    })
+                )
+            }
+        );
+
+        assert_eq!(cast(input), output);
+        assert_eq!(unary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_cast_a_postfix_increment() {
+        let input  = b"(int) $foo++";
+        let output = Result::Done(
+            &b""[..],
+            Expression::Cast {
+                ty     : CastType::Integer,
+                operand: Box::new(
+                    Spanned::from(Expression::PostIncrement(
+                        Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..]))))
+                    ))
+                )
+            }
+        );
+
+        assert_eq!(cast(input), output);
+        assert_eq!(unary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_invalid_cast_comment_inside_parenthesis() {
+        let input = b"(/* bar */ int) $foo";
+
+        assert_eq!(cast(input), Result::Error(Error::Position(ErrorKind::Alt, &b"/* bar */ int) $foo"[..])));
+        assert_eq!(unary(input), Result::Done(&b" $foo"[..], Expression::Name(Name::Unqualified(&b"int"[..]))));
+    }
+
+    #[test]
+    fn case_invalid_cast_unknown_type() {
+        let input = b"(integral) $foo";
+
+        assert_eq!(cast(input), Result::Error(Error::Position(ErrorKind::Alt, &b"integral) $foo"[..])));
+        assert_eq!(unary(input), Result::Done(&b" $foo"[..], Expression::Name(Name::Unqualified(&b"integral"[..]))));
+    }
+
+    #[test]
+    fn case_parenthesized_expression_is_not_a_cast() {
+        let input  = b"($foo)";
+        let output = Result::Done(&b""[..], Expression::Variable(Variable::Name(&b"foo"[..])));
+
+        assert_eq!(unary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_negation() {
+        let input  = b"- /* foo */ $foo";
+        let output = Result::Done(
+            &b""[..],
+            Expression::Negation(
+                Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..]))))
+            )
+        );
+
+        assert_eq!(negation(input), output);
+        assert_eq!(unary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_negation_recursive() {
+        let input  = b"- -42";
+        let output = Result::Done(
+            &b""[..],
+            Expression::Negation(
+                Box::new(
+                    Spanned::from(Expression::Negation(
+                        Box::new(Spanned::from(Expression::Literal(Literal::Integer(42i64))))
+                    ))
+                )
+            )
+        );
+
+        assert_eq!(negation(input), output);
+        assert_eq!(unary(input), output);
+        assert_eq!(expression(input), output);
+    }
+
+    #[test]
+    fn case_invalid_negation_is_not_a_decrement() {
+        assert_eq!(negation(b"--42"), Result::Error(Error::Position(ErrorKind::Not, &b"-42"[..])));
+    }
+
+    #[test]
+    fn case_invalid_postfix_increment_not_a_variable() {
+        let input  = b"42++";
+
+        assert_eq!(postfix_increment(input), Result::Error(Error::Position(ErrorKind::MapRes, &b"42++"[..])));
+        assert_eq!(unary(input), Result::Done(&b"++"[..], Expression::Literal(Literal::Integer(42i64))));
+        assert_eq!(expression(input), Result::Done(&b"++"[..], Expression::Literal(Literal::Integer(42i64))));
+    }
+}
@@ -0,0 +1,367 @@
+// Tagua VM
+//
+//
+// New BSD License
+//
+// Copyright © 2016-2016, Ivan Enderlin.
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//     * Redistributions of source code must retain the above copyright
+//       notice, this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright
+//       notice, this list of conditions and the following disclaimer in the
+//       documentation and/or other materials provided with the distribution.
+//     * Neither the name of the Hoa nor the names of its contributors may be
+//       used to endorse or promote products derived from this software without
+//       specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE
+// ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDERS AND CONTRIBUTORS BE
+// LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR
+// CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF
+// SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS
+// INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN
+// CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+
+//! Group of property hook rules.
+//!
+//! Property hooks are introduced by PHP 8.4 and are not part of the PHP
+//! Language Specification yet. They can be attached to properties and to
+//! promoted constructor parameters.
+
+use std::result::Result as StdResult;
+use super::attributes::attributes;
+use super::expressions::expression;
+use super::expressions::primaries::parameters;
+use super::statements::compound::block;
+use super::tokens::name;
+use super::super::ast::{
+    Attribute,
+    Parameter,
+    PropertyHook,
+    PropertyHookBody,
+    PropertyHookKind
+};
+use super::super::internal::fold_into_vector;
+use super::super::span::Spanned;
+use super::super::tokens;
+use super::super::version::{
+    self,
+    Version
+};
+
+named!(
+    pub property_hooks< Vec<PropertyHook> >,
+    cond_reduce!(
+        version::is_targeting(Version::Php84),
+        map_res!(
+            preceded!(
+                tag!(tokens::LEFT_CURLY_BRACKET),
+                terminated!(
+                    fold_many0!(
+                        complete!(first!(property_hook)),
+                        Vec::new(),
+                        fold_into_vector
+                    ),
+                    first!(expect!("property_hooks", tokens::RIGHT_CURLY_BRACKET))
+                )
+            ),
+            property_hooks_mapper
+        )
+    )
+);
+
+/// The list of hooks cannot be empty, and each kind of hook can be
+/// declared once.
+#[inline(always)]
+fn property_hooks_mapper(hooks: Vec<PropertyHook>) -> StdResult<Vec<PropertyHook>, ()> {
+    if hooks.is_empty() {
+        return Err(());
+    }
+
+    for (index, hook) in hooks.iter().enumerate() {
+        if hooks[..index].iter().any(|other| other.kind == hook.kind) {
+            return Err(());
+        }
+    }
+
+    Ok(hooks)
+}
+
+named!(
+    property_hook<PropertyHook>,
+    map_res!(
+        chain!(
+            attributes: attributes ~
+            is_final: opt!(first!(keyword!(tokens::FINAL))) ~
+            returns_reference: opt!(first!(tag!(tokens::REFERENCE))) ~
+            kind: first!(property_hook_kind) ~
+            parameters: opt!(first!(parameters)) ~
+            body: first!(property_hook_body),
+            || { (attributes, is_final.is_some(), returns_reference.is_some(), kind, parameters, body) }
+        ),
+        property_hook_mapper
+    )
+);
+
+/// Only a `get` hook can return a reference. Only a `set` hook can declare
+/// a parameter, receiving the assigned value. This parameter is passed by
+/// value and has no default value.
+#[inline(always)]
+fn property_hook_mapper<'a>(
+    (attributes, is_final, returns_reference, kind, parameters, body): (Vec<Attribute<'a>>, bool, bool, PropertyHookKind, Option<Vec<Spanned<Parameter<'a>>>>, PropertyHookBody<'a>)
+) -> StdResult<PropertyHook<'a>, ()> {
+    let parameters = match (kind, parameters) {
+        (PropertyHookKind::Get, None) => {
+            vec![]
+        },
+
+        (PropertyHookKind::Set, None) if !returns_reference => {
+            vec![]
+        },
+
+        (PropertyHookKind::Set, Some(parameters)) if !returns_reference => {
+            if parameters.len() != 1 || parameters.iter().any(|parameter| parameter.is_reference || parameter.is_variadic || parameter.value.is_some()) {
+                return Err(());
+            }
+
+            parameters
+        },
+
+        _ => {
+            return Err(());
+        }
+    };
+
+    Ok(
+        PropertyHook {
+            attributes       : attributes,
+            is_final         : is_final,
+            returns_reference: returns_reference,
+            kind             : kind,
+            parameters       : parameters,
+            body             : body
+        }
+    )
+}
+
+named!(
+    property_hook_kind<PropertyHookKind>,
+    map_res!(
+        name,
+        property_hook_kind_mapper
+    )
+);
+
+#[inline(always)]
+fn property_hook_kind_mapper(kind: &[u8]) -> StdResult<PropertyHookKind, ()> {
+    if kind.eq_ignore_ascii_case(tokens::GET) {
+        Ok(PropertyHookKind::Get)
+    } else if kind.eq_ignore_ascii_case(tokens::SET) {
+        Ok(PropertyHookKind::Set)
+    } else {
+        Err(())
+    }
+}
+
+named!(
+    property_hook_body<PropertyHookBody>,
+    alt!(
+        block => { PropertyHookBody::Block }
+      | chain!(
+            tag!(tokens::MAP) ~
+            expression: first!(spanned!(expression)) ~
+            first!(tag!(tokens::SEMICOLON)),
+            || { PropertyHookBody::Expression(expression) }
+        )
+    )
+);
+
+
+#[cfg(test)]
+mod tests {
+    use super::property_hooks;
+    use super::super::super::ast::{
+        Attribute,
+        Expression,
+        Literal,
+        Name,
+        Parameter,
+        PropertyHook,
+        PropertyHookBody,
+        PropertyHookKind,
+        Statement,
+        Type,
+        Variable
+    };
+    use super::super::super::internal::{
+        Error,
+        ErrorKind,
+        Result
+    };
+    use super::super::super::span::Spanned;
+    use super::super::super::version::{
+        self,
+        Version
+    };
+
+    #[test]
+    fn case_property_hooks_get_expression() {
+        let input  = b"{ get => 42; }";
+        let output = Result::Done(
+            &b""[..],
+            vec![
+                PropertyHook {
+                    attributes       : vec![],
+                    is_final         : false,
+                    returns_reference: false,
+                    kind             : PropertyHookKind::Get,
+                    parameters       : vec![],
+                    body             : PropertyHookBody::Expression(Spanned::from(Expression::Literal(Literal::Integer(42))))
+                }
+            ]
+        );
+
+        assert_eq!(property_hooks(input), output);
+    }
+
+    #[test]
+    fn case_property_hooks_get_and_set_blocks() {
+        let input  = b"{ #[Foo] final GET { return; } set (int $value) {} }";
+        let output = Result::Done(
+            &b""[..],
+            vec![
+                PropertyHook {
+                    attributes       : vec![
+                        Attribute {
+                            name     : Spanned::from(Name::Unqualified(&b"Foo"[..])),
+                            arguments: vec![]
+                        }
+                    ],
+                    is_final         : true,
+                    returns_reference: false,
+                    kind             : PropertyHookKind::Get,
+                    parameters       : vec![],
+                    body             : PropertyHookBody::Block(vec![Spanned::from(Statement::Return(None))])
+                },
+                PropertyHook {
+                    attributes       : vec![],
+                    is_final         : false,
+                    returns_reference: false,
+                    kind             : PropertyHookKind::Set,
+                    parameters       : vec![
+                        Spanned::from(Parameter {
+                            attributes    : vec![],
+                            visibility    : None,
+                            set_visibility: None,
+                            is_readonly   : false,
+                            ty            : Some(Spanned::from(Type::Int)),
+                            is_reference  : false,
+                            is_variadic   : false,
+                            name          : Variable::Name(&b"value"[..]),
+                            value         : None,
+                            hooks         : vec![]
+                        })
+                    ],
+                    body             : PropertyHookBody::Block(vec![])
+                }
+            ]
+        );
+
+        assert_eq!(property_hooks(input), output);
+    }
+
+    #[test]
+    fn case_property_hooks_get_by_reference_and_set_without_parameter() {
+        let input  = b"{ &get => $x; set => $value; }";
+        let output = Result::Done(
+            &b""[..],
+            vec![
+                PropertyHook {
+                    attributes       : vec![],
+                    is_final         : false,
+                    returns_reference: true,
+                    kind             : PropertyHookKind::Get,
+                    parameters       : vec![],
+                    body             : PropertyHookBody::Expression(Spanned::from(Expression::Variable(Variable::Name(&b"x"[..]))))
+                },
+                PropertyHook {
+                    attributes       : vec![],
+                    is_final         : false,
+                    returns_reference: false,
+                    kind             : PropertyHookKind::Set,
+                    parameters       : vec![],
+                    body             : PropertyHookBody::Expression(Spanned::from(Expression::Variable(Variable::Name(&b"value"[..]))))
+                }
+            ]
+        );
+
+        assert_eq!(property_hooks(input), output);
+    }
+
+    #[test]
+    fn case_invalid_property_hooks_empty() {
+        let input = b"{}";
+
+        assert_eq!(property_hooks(input), Result::Error(Error::Position(ErrorKind::MapRes, &input[..])));
+    }
+
+    #[test]
+    fn case_invalid_property_hooks_repeated_kind() {
+        let input = b"{ get => 1; get => 2; }";
+
+        assert_eq!(property_hooks(input), Result::Error(Error::Position(ErrorKind::MapRes, &input[..])));
+    }
+
+    #[test]
+    fn case_invalid_property_hooks_unknown_kind() {
+        let input = b"{ unset => 1; }";
+
+        assert_eq!(property_hooks(input), Result::Error(Error::Position(ErrorKind::Tag, &b"unset => 1; }"[..])));
+    }
+
+    #[test]
+    fn case_invalid_property_hooks_get_with_parameters() {
+        let input = b"{ get () => 1; }";
+
+        assert_eq!(property_hooks(input), Result::Error(Error::Position(ErrorKind::Tag, &b"get () => 1; }"[..])));
+    }
+
+    #[test]
+    fn case_invalid_property_hooks_set_with_many_parameters() {
+        let input = b"{ set ($x, $y) {} }";
+
+        assert_eq!(property_hooks(input), Result::Error(Error::Position(ErrorKind::Tag, &b"set ($x, $y) {} }"[..])));
+    }
+
+    #[test]
+    fn case_invalid_property_hooks_set_with_a_default_value() {
+        let input = b"{ set ($x = 1) {} }";
+
+        assert_eq!(property_hooks(input), Result::Error(Error::Position(ErrorKind::Tag, &b"set ($x = 1) {} }"[..])));
+    }
+
+    #[test]
+    fn case_invalid_property_hooks_set_by_reference() {
+        let input = b"{ &set => $value; }";
+
+        assert_eq!(property_hooks(input), Result::Error(Error::Position(ErrorKind::Tag, &b"&set => $value; }"[..])));
+    }
+
+    #[test]
+    fn case_invalid_property_hooks_before_php_84() {
+        let input = b"{ get => 42; }";
+
+        version::set_target(Version::Php83);
+        let output = property_hooks(input);
+        version::set_target(version::LATEST);
+
+        assert_eq!(output, Result::Error(Error::Position(ErrorKind::CondReduce, &input[..])));
+    }
+}
@@ -39,16 +39,22 @@ use nom::{
     hex_digit,
     oct_digit
 };
-use std::num::ParseIntError;
+use std::borrow::Cow;
 use std::result::Result as StdResult;
 use std::str::FromStr;
 use std::str;
 use super::super::ast::Literal;
+use super::super::error;
 use super::super::internal::{
     Error,
     ErrorKind,
     Result
 };
+use super::super::tokens::NAMESPACE_SEPARATOR;
+use super::super::version::{
+    self,
+    Version
+};
 use super::tokens;
 
 named!(
@@ -56,32 +62,67 @@ named!(
     alt!(
         null
       | boolean
-      | exponential
-      | integer
+      | number
       | string
     )
 );
 
+// A real is tried first as it may start like an integer, e.g. `1.5` or
+// `1e10`.
+named!(
+    pub number<Literal>,
+    alt!(
+        exponential
+      | integer
+    )
+);
+
+// `null` is case-insensitive and must be a whole word, e.g. `NULL` but not
+// `nullable` nor `null\Foo`, which is a qualified name.
 named!(
     pub null<Literal>,
     map_res!(
-        itag!("null"),
-        |_| -> StdResult<Literal, ()> {
-            Ok(Literal::Null)
-        }
+        terminated!(
+            tokens::name,
+            not!(tag!(NAMESPACE_SEPARATOR))
+        ),
+        null_mapper
     )
 );
 
+#[inline(always)]
+fn null_mapper(bytes: &[u8]) -> StdResult<Literal, ()> {
+    if bytes.eq_ignore_ascii_case(b"null") {
+        Ok(Literal::Null)
+    } else {
+        Err(())
+    }
+}
+
+// `true` and `false` are case-insensitive and must be whole words, e.g.
+// `FALSE` but not `true_value` nor `true\Foo`, which is a qualified name.
 named!(
     pub boolean<Literal>,
     map_res!(
-        alt!(itag!(&b"true"[..]) | itag!(&b"false"[..])),
-        |bytes: &[u8]| -> StdResult<Literal, ()> {
-            Ok(Literal::Boolean(bytes[0] == 't' as u8))
-        }
+        terminated!(
+            tokens::name,
+            not!(tag!(NAMESPACE_SEPARATOR))
+        ),
+        boolean_mapper
     )
 );
 
+#[inline(always)]
+fn boolean_mapper(bytes: &[u8]) -> StdResult<Literal, ()> {
+    if bytes.eq_ignore_ascii_case(b"true") {
+        Ok(Literal::Boolean(true))
+    } else if bytes.eq_ignore_ascii_case(b"false") {
+        Ok(Literal::Boolean(false))
+    } else {
+        Err(())
+    }
+}
+
 named!(
     pub integer<Literal>,
     alt_complete!(
@@ -99,41 +140,41 @@ named!(
             tag!("0"),
             preceded!(
                 is_a!("bB"),
-                is_a!("01")
+                recognize!(
+                    preceded!(
+                        is_a!("01"),
+                        many0!(complete!(preceded!(tag!("_"), is_a!("01"))))
+                    )
+                )
             )
         ),
-        |bytes: &[u8]| {
-            i64
-                ::from_str_radix(
-                    unsafe { str::from_utf8_unchecked(bytes) },
-                    2
-                )
-                .and_then(
-                    |binary| {
-                        Ok(Literal::Integer(binary))
-                   }
-                )
-        }
+        |bytes| integer_mapper(bytes, 2)
     )
 );
 
 named!(
     pub octal<Literal>,
     map_res!(
-        preceded!(tag!("0"), opt!(complete!(oct_digit))),
+        preceded!(
+            tag!("0"),
+            opt!(
+                complete!(
+                    alt!(
+                        explicit_octal_digits
+                      | recognize!(
+                            preceded!(
+                                opt!(tag!("_")),
+                                octal_digits
+                            )
+                        )
+                    )
+                )
+            )
+        ),
         |value: Option<&[u8]>| {
             match value {
                 Some(bytes) =>
-                    i64
-                        ::from_str_radix(
-                            unsafe { str::from_utf8_unchecked(bytes) },
-                            8
-                        )
-                        .and_then(
-                            |octal| {
-                                Ok(Literal::Integer(octal))
-                            }
-                        ),
+                    integer_mapper(bytes, 8),
 
                 None =>
                     Ok(Literal::Integer(0i64))
@@ -142,32 +183,35 @@ named!(
     )
 );
 
+// The explicit `0o` prefix is available since PHP 8.1, e.g. `0o777`.
+named!(
+    explicit_octal_digits,
+    complete!(
+        preceded!(
+            cond_reduce!(
+                version::is_targeting(Version::Php81),
+                alt!(tag!("o") | tag!("O"))
+            ),
+            octal_digits
+        )
+    )
+);
+
+named!(
+    octal_digits,
+    recognize!(
+        preceded!(
+            oct_digit,
+            many0!(complete!(preceded!(tag!("_"), oct_digit)))
+        )
+    )
+);
+
 named!(
     pub decimal<Literal>,
     map_res!(
-        re_bytes_find_static!(r"^[1-9][0-9]*"),
-        |bytes: &[u8]| {
-            let string = unsafe { str::from_utf8_unchecked(bytes) };
-
-            i64
-                ::from_str(string)
-                .and_then(
-                    |decimal| {
-                        Ok(Literal::Integer(decimal))
-                   }
-                )
-                .or_else(
-                    |_: ParseIntError| {
-                        f64
-                            ::from_str(string)
-                            .and_then(
-                                |decimal| {
-                                    Ok(Literal::Real(decimal))
-                                }
-                            )
-                    }
-                )
-        }
+        re_bytes_find_static!(r"^[1-9][0-9]*(_[0-9]+)*"),
+        |bytes| integer_mapper(bytes, 10)
     )
 );
 
@@ -178,44 +222,90 @@ named!(
             tag!("0"),
             preceded!(
                 is_a!("xX"),
-                complete!(hex_digit)
+                recognize!(
+                    preceded!(
+                        complete!(hex_digit),
+                        many0!(complete!(preceded!(tag!("_"), hex_digit)))
+                    )
+                )
             )
         ),
-        |bytes: &[u8]| {
-            i64
-                ::from_str_radix(
-                    unsafe { str::from_utf8_unchecked(bytes) },
-                    16
-                )
-                .and_then(
-                    |hexadecimal| {
-                        Ok(Literal::Integer(hexadecimal))
-                    }
-                )
-        }
+        |bytes| integer_mapper(bytes, 16)
     )
 );
 
+/// Convert the digits of an integer in the given radix. An integer
+/// overflowing `i64` becomes a real, as PHP does.
+#[inline(always)]
+fn integer_mapper(bytes: &[u8], radix: u32) -> StdResult<Literal, ()> {
+    let digits = match remove_separators(bytes) {
+        Ok(digits) => digits,
+        Err(_) => return Err(())
+    };
+
+    if digits.is_empty() {
+        return Err(());
+    }
+
+    match i64::from_str_radix(&digits, radix) {
+        Ok(integer) => Ok(Literal::Integer(integer)),
+
+        Err(_) if radix == 10 =>
+            f64
+                ::from_str(&digits).map(Literal::Real)
+                .map_err(|_| ()),
+
+        Err(_) =>
+            Ok(
+                Literal::Real(
+                    digits
+                        .chars()
+                        .filter_map(|digit| digit.to_digit(radix))
+                        .fold(0f64, |real, digit| real * radix as f64 + digit as f64)
+                )
+            )
+    }
+}
+
+// A real overflowing `f64` becomes `INF`, as PHP does.
 named!(
     pub exponential<Literal>,
     map_res!(
-        re_bytes_find_static!(r"^(([0-9]*\.[0-9]+|[0-9]+\.)([eE][+-]?[0-9]+)?|[0-9]+[eE][+-]?[0-9]+)"),
+        re_bytes_find_static!(r"^((([0-9]+(_[0-9]+)*)?\.[0-9]+(_[0-9]+)*|[0-9]+(_[0-9]+)*\.)([eE][+-]?[0-9]+(_[0-9]+)*)?|[0-9]+(_[0-9]+)*[eE][+-]?[0-9]+(_[0-9]+)*)"),
         |bytes: &[u8]| {
-            f64
-                ::from_str(unsafe { str::from_utf8_unchecked(bytes) })
+            remove_separators(bytes)
                 .and_then(
-                    |exponential| {
-                        Ok(Literal::Real(exponential))
+                    |string| {
+                        f64
+                            ::from_str(&string)
+                            .map_err(|_| ())
                     }
-                )
+                ).map(Literal::Real)
         }
     )
 );
 
-/// String errors.
+/// Remove the `_` separators between the digits of a number, e.g.
+/// `1_000_000`. Separators are available since PHP 7.4.
+#[inline(always)]
+fn remove_separators(bytes: &[u8]) -> StdResult<Cow<str>, ()> {
+    let string = unsafe { str::from_utf8_unchecked(bytes) };
+
+    if !string.contains('_') {
+        return Ok(Cow::Borrowed(string));
+    }
+
+    if !version::is_targeting(Version::Php74) || string.ends_with('_') {
+        return Err(());
+    }
+
+    Ok(Cow::Owned(string.replace("_", "")))
+}
+
+/// String errors, from 300.
 pub enum StringError {
     /// The datum starts as a string but is too short to be a string.
-    TooShort,
+    TooShort = 300,
     /// The string open character is not correct.
     InvalidOpeningCharacter,
     /// The string close character is not correct.
@@ -223,7 +313,13 @@ pub enum StringError {
     /// The string is not correctly encoded (expect UTF-8).
     InvalidEncoding,
     /// The string delimiter identifier is syntactically invalid.
-    InvalidDelimiterIdentifier
+    InvalidDelimiterIdentifier,
+    /// A line of a heredoc or a nowdoc is less indented than the closing
+    /// delimiter identifier, or the indentation mixes spaces and tabs.
+    InvalidIndentation,
+    /// A `\u{…}` escape sequence is not terminated, is empty, or does not
+    /// represent a codepoint.
+    InvalidUnicodeEscape
 }
 
 named!(
@@ -234,22 +330,22 @@ named!(
     )
 );
 
-fn string_single_quoted(input: &[u8]) -> Result<&[u8], Literal> {
+pub fn string_single_quoted(input: &[u8]) -> Result<&[u8], Literal> {
     let input_length = input.len();
 
     if input_length < 2 {
         return Result::Error(Error::Code(ErrorKind::Custom(StringError::TooShort as u32)));
     }
 
-    if input[0] == 'b' as u8 || input[0] == 'B' as u8 {
+    if input[0] == b'b' || input[0] == b'B' {
         if input_length < 3 {
             return Result::Error(Error::Code(ErrorKind::Custom(StringError::TooShort as u32)));
-        } else if input[1] != '\'' as u8 {
+        } else if input[1] != b'\'' {
             return Result::Error(Error::Code(ErrorKind::Custom(StringError::InvalidOpeningCharacter as u32)));
         } else {
             return string_single_quoted(&input[1..]);
         }
-    } else if input[0] != '\'' as u8 {
+    } else if input[0] != b'\'' {
         return Result::Error(Error::Code(ErrorKind::Custom(StringError::InvalidOpeningCharacter as u32)));
     }
 
@@ -258,17 +354,17 @@ fn string_single_quoted(input: &[u8]) -> Result<&[u8], Literal> {
     let mut iterator = input[offset..].iter().enumerate();
 
     while let Some((index, item)) = iterator.next() {
-        if *item == '\\' as u8 {
+        if *item == b'\\' {
             if let Some((next_index, next_item)) = iterator.next() {
-                if *next_item == '\'' as u8 ||
-                   *next_item == '\\' as u8 {
+                if *next_item == b'\'' ||
+                   *next_item == b'\\' {
                     output.extend(&input[offset..index + 1]);
                     offset = next_index + 1;
                 }
             } else {
                 return Result::Error(Error::Code(ErrorKind::Custom(StringError::InvalidClosingCharacter as u32)));
             }
-        } else if *item == '\'' as u8 {
+        } else if *item == b'\'' {
             output.extend(&input[offset..index + 1]);
 
             return Result::Done(&input[index + 2..], Literal::String(output));
@@ -278,39 +374,39 @@ fn string_single_quoted(input: &[u8]) -> Result<&[u8], Literal> {
     Result::Error(Error::Code(ErrorKind::Custom(StringError::InvalidClosingCharacter as u32)))
 }
 
-const STRING_NOWDOC_OPENING: &'static [u8] = &['<' as u8, '<' as u8, '<' as u8];
+const STRING_NOWDOC_OPENING: &[u8] = b"<<<";
 
 fn string_nowdoc(input: &[u8]) -> Result<&[u8], Literal> {
     let input_length = input.len();
 
-    // `<<<'A'\nA\n` is the shortest datum.
-    if input_length < 9 {
+    // `<<<'A'\nA` is the shortest datum.
+    if input_length < 8 {
         return Result::Error(Error::Code(ErrorKind::Custom(StringError::TooShort as u32)));
     }
 
-    if input[0] == 'b' as u8 || input[0] == 'B' as u8 {
-        if input_length < 10 {
+    if input[0] == b'b' || input[0] == b'B' {
+        if input_length < 9 {
             return Result::Error(Error::Code(ErrorKind::Custom(StringError::TooShort as u32)));
-        } else if false == input[1..].starts_with(STRING_NOWDOC_OPENING) {
+        } else if !input[1..].starts_with(STRING_NOWDOC_OPENING) {
             return Result::Error(Error::Code(ErrorKind::Custom(StringError::InvalidOpeningCharacter as u32)));
         } else {
             return string_nowdoc(&input[1..]);
         }
-    } else if false == input.starts_with(STRING_NOWDOC_OPENING) {
+    } else if !input.starts_with(STRING_NOWDOC_OPENING) {
         return Result::Error(Error::Code(ErrorKind::Custom(StringError::InvalidOpeningCharacter as u32)));
     }
 
     let mut offset = 3;
 
     for item in input[offset..].iter() {
-        if *item != ' ' as u8 && *item != '\t' as u8 {
+        if *item != b' ' && *item != b'\t' {
             break;
         }
 
         offset += 1;
     }
 
-    if input[offset] != '\'' as u8 {
+    if offset == input_length || input[offset] != b'\'' {
         return Result::Error(Error::Code(ErrorKind::Custom(StringError::InvalidOpeningCharacter as u32)));
     }
 
@@ -326,69 +422,146 @@ fn string_nowdoc(input: &[u8]) -> Result<&[u8], Literal> {
         return Result::Error(Error::Code(ErrorKind::Custom(StringError::InvalidDelimiterIdentifier as u32)))
     }
 
-    let next_input_length = next_input.len();
-    let name_length       = name.len();
-
-    if next_input_length < 3 + name_length || next_input[0] != '\'' as u8 || next_input[1] != '\n' as u8 {
-        if next_input[1] != '\r' as u8 || next_input[2] != '\n' as u8 {
-            return Result::Error(Error::Code(ErrorKind::Custom(StringError::InvalidOpeningCharacter as u32)));
-        }
-    }
-
-    if next_input[1] == '\r' as u8 {
+    if next_input.starts_with(b"'\n") {
         offset = 2;
+    } else if next_input.starts_with(b"'\r\n") {
+        offset = 3;
     } else {
-        offset = 1;
+        return Result::Error(Error::Code(ErrorKind::Custom(StringError::InvalidOpeningCharacter as u32)));
     }
 
-    for (index, item) in next_input[offset..].iter().enumerate() {
-        if *item == '\n' as u8 {
-            if !next_input[offset + index + 1..].starts_with(name) {
-                continue;
-            }
+    match string_document_body(&next_input[offset..], name) {
+        Ok((body, indentation, rest)) => {
+            let mut output = Vec::with_capacity(body.len());
 
-            let mut lookahead_offset = offset + index + name_length + 1;
+            for (index, line) in body.split(|byte| *byte == b'\n').enumerate() {
+                if index > 0 {
+                    output.push(b'\n');
+                }
 
-            if lookahead_offset >= next_input_length {
-                return Result::Error(Error::Code(ErrorKind::Custom(StringError::InvalidClosingCharacter as u32)));
+                output.extend(&line[string_document_indentation(line, indentation.len())..]);
             }
 
-            if next_input[lookahead_offset] == ';' as u8 {
-                lookahead_offset += 1;
+            Result::Done(
+                rest,
+                Literal::Nowdoc {
+                    label      : name.to_vec(),
+                    indentation: indentation.to_vec(),
+                    value      : output
+                }
+            )
+        },
+
+        Err(kind) => string_document_error(input, "string_nowdoc", kind)
+    }
+}
+
+/// Fail with a string error once the opening line of a heredoc or a nowdoc
+/// is recognized. The failure is reported, see `error::expect`, so that it
+/// is not hidden by the error of an enclosing alternative.
+pub fn string_document_error<'a, O>(input: &'a [u8], rule: &'static str, kind: StringError) -> Result<&'a [u8], O> {
+    let result = Result::Error(Error::Code(ErrorKind::Custom(kind as u32)));
+
+    error::expect(input, rule, &[], &result);
+
+    result
+}
+
+/// Search the closing delimiter identifier of a heredoc or a nowdoc, where
+/// the input starts right after the opening line.
+///
+/// The closing identifier is at the beginning of a line, optionally
+/// indented by spaces or tabs, and is not followed by a name character.
+/// Return the body (without the newline preceding the closing identifier),
+/// the indentation of the closing identifier, which must be removed from
+/// all the lines of the body, and the rest of the input right after the
+/// closing identifier.
+///
+/// Each non-empty line of the body must start with the same indentation
+/// as the closing identifier, and this indentation must not mix spaces and
+/// tabs.
+pub fn string_document_body<'a>(input: &'a [u8], identifier: &[u8]) -> StdResult<(&'a [u8], &'a [u8], &'a [u8]), StringError> {
+    let input_length      = input.len();
+    let identifier_length = identifier.len();
+    let mut line_start    = 0;
+
+    while line_start <= input_length {
+        let mut offset = line_start;
+
+        while offset < input_length && (input[offset] == b' ' || input[offset] == b'\t') {
+            offset += 1;
+        }
+
+        let is_closing =
+            input[offset..].starts_with(identifier) &&
+            (
+                offset + identifier_length == input_length ||
+                !is_name_character(input[offset + identifier_length])
+            );
+
+        if is_closing {
+            let indentation = &input[line_start..offset];
+            let mut body    = &input[..line_start];
+
+            if body.ends_with(b"\r\n") {
+                body = &body[..line_start - 2];
+            } else if body.ends_with(b"\n") {
+                body = &body[..line_start - 1];
             }
 
-            if lookahead_offset >= next_input_length {
-                return Result::Error(Error::Code(ErrorKind::Custom(StringError::InvalidClosingCharacter as u32)));
+            if !indentation.iter().all(|byte| *byte == indentation[0]) {
+                return Err(StringError::InvalidIndentation);
             }
 
-            let mut ending_offset = 0;
+            if line_start > 0 && !indentation.is_empty() {
+                for line in body.split(|byte| *byte == b'\n') {
+                    let length = string_document_indentation(line, indentation.len());
 
-            if next_input[lookahead_offset] == '\r' as u8 {
-                if lookahead_offset + 1 >= next_input_length {
-                    return Result::Error(Error::Code(ErrorKind::Custom(StringError::InvalidClosingCharacter as u32)));
-                }
+                    if !line[..length].iter().all(|byte| *byte == indentation[0]) {
+                        return Err(StringError::InvalidIndentation);
+                    }
 
-                ending_offset     = 1;
-                lookahead_offset += 1;
+                    if length < indentation.len() && length < line.len() && line[length] != b'\r' {
+                        return Err(StringError::InvalidIndentation);
+                    }
+                }
             }
 
-            if next_input[lookahead_offset] == '\n' as u8 {
-                if index == 0 {
-                    return Result::Done(
-                        &next_input[lookahead_offset + 1..],
-                        Literal::String(Vec::new())
-                    );
-                }
+            return Ok((body, indentation, &input[offset + identifier_length..]));
+        }
+
+        match input[line_start..].iter().position(|byte| *byte == b'\n') {
+            Some(position) => {
+                line_start += position + 1;
+            },
 
-                return Result::Done(
-                    &next_input[lookahead_offset + 1..],
-                    Literal::String(next_input[offset + 1..offset - ending_offset + index].to_vec())
-                );
+            None => {
+                break;
             }
         }
     }
 
-    Result::Error(Error::Code(ErrorKind::Custom(StringError::InvalidClosingCharacter as u32)))
+    Err(StringError::InvalidClosingCharacter)
+}
+
+/// Compute the number of bytes to remove at the beginning of a line of a
+/// heredoc or a nowdoc body, i.e. the leading spaces and tabs, up to the
+/// indentation length of the closing delimiter identifier.
+pub fn string_document_indentation(line: &[u8], indentation: usize) -> usize {
+    line
+        .iter()
+        .take(indentation)
+        .take_while(|byte| **byte == b' ' || **byte == b'\t')
+        .count()
+}
+
+#[inline(always)]
+fn is_name_character(byte: u8) -> bool {
+    byte.is_ascii_lowercase() ||
+    byte.is_ascii_uppercase() ||
+    byte.is_ascii_digit() ||
+    byte == b'_' ||
+    byte >= 0x80
 }
 
 
@@ -404,6 +577,7 @@ mod tests {
         integer,
         literal,
         null,
+        number,
         octal,
         string,
         string_nowdoc,
@@ -415,6 +589,10 @@ mod tests {
         ErrorKind,
         Result
     };
+    use super::super::super::version::{
+        self,
+        Version
+    };
 
     #[test]
     fn case_null() {
@@ -470,6 +648,38 @@ mod tests {
         assert_eq!(literal(input), output);
     }
 
+    #[test]
+    fn case_invalid_null_prefix_of_a_name() {
+        let input = b"nullable";
+
+        assert_eq!(null(input), Result::Error(Error::Position(ErrorKind::MapRes, &input[..])));
+        assert_eq!(literal(input), Result::Error(Error::Position(ErrorKind::Alt, &input[..])));
+    }
+
+    #[test]
+    fn case_invalid_boolean_prefix_of_a_name() {
+        let input = b"TRUE_VALUE";
+
+        assert_eq!(boolean(input), Result::Error(Error::Position(ErrorKind::MapRes, &input[..])));
+        assert_eq!(literal(input), Result::Error(Error::Position(ErrorKind::Alt, &input[..])));
+    }
+
+    #[test]
+    fn case_invalid_null_namespace_of_a_name() {
+        let input = b"null\\Foo";
+
+        assert_eq!(null(input), Result::Error(Error::Position(ErrorKind::Not, &input[4..])));
+        assert_eq!(literal(input), Result::Error(Error::Position(ErrorKind::Alt, &input[..])));
+    }
+
+    #[test]
+    fn case_invalid_boolean_namespace_of_a_name() {
+        let input = b"true\\Foo";
+
+        assert_eq!(boolean(input), Result::Error(Error::Position(ErrorKind::Not, &input[4..])));
+        assert_eq!(literal(input), Result::Error(Error::Position(ErrorKind::Alt, &input[..])));
+    }
+
     #[test]
     fn case_binary_lowercase_b() {
         let input  = b"0b101010";
@@ -493,7 +703,7 @@ mod tests {
     #[test]
     fn case_binary_maximum_integer_value() {
         let input  = b"0b111111111111111111111111111111111111111111111111111111111111111";
-        let output = Result::Done(&b""[..], Literal::Integer(::std::i64::MAX));
+        let output = Result::Done(&b""[..], Literal::Integer(i64::MAX));
 
         assert_eq!(binary(input), output);
         assert_eq!(integer(input), output);
@@ -501,11 +711,11 @@ mod tests {
     }
 
     #[test]
-    fn case_invalid_binary_overflow() {
+    fn case_binary_overflow_to_real() {
         let input  = b"0b1000000000000000000000000000000000000000000000000000000000000000";
-        let output = Result::Done(&b"b1000000000000000000000000000000000000000000000000000000000000000"[..], Literal::Integer(0i64));
+        let output = Result::Done(&b""[..], Literal::Real(9223372036854775808f64));
 
-        assert_eq!(binary(input), Result::Error(Error::Position(ErrorKind::MapRes, &input[..])));
+        assert_eq!(binary(input), output);
         assert_eq!(integer(input), output);
         assert_eq!(literal(input), output);
     }
@@ -563,7 +773,7 @@ mod tests {
     #[test]
     fn case_octal_maximum_integer_value() {
         let input  = b"0777777777777777777777";
-        let output = Result::Done(&b""[..], Literal::Integer(::std::i64::MAX));
+        let output = Result::Done(&b""[..], Literal::Integer(i64::MAX));
 
         assert_eq!(octal(input), output);
         assert_eq!(integer(input), output);
@@ -571,15 +781,76 @@ mod tests {
     }
 
     #[test]
-    fn case_invalid_octal_overflow() {
+    fn case_octal_overflow_to_real() {
         let input  = b"01000000000000000000000";
-        let output = Result::Error(Error::Position(ErrorKind::Alt, &b"01000000000000000000000"[..]));
+        let output = Result::Done(&b""[..], Literal::Real(9223372036854775808f64));
+
+        assert_eq!(octal(input), output);
+        assert_eq!(integer(input), output);
+        assert_eq!(literal(input), output);
+    }
+
+    #[test]
+    fn case_octal_explicit_lowercase_o() {
+        let input  = b"0o777";
+        let output = Result::Done(&b""[..], Literal::Integer(511i64));
+
+        assert_eq!(octal(input), output);
+        assert_eq!(integer(input), output);
+        assert_eq!(literal(input), output);
+    }
+
+    #[test]
+    fn case_octal_explicit_uppercase_o() {
+        let input  = b"0O52";
+        let output = Result::Done(&b""[..], Literal::Integer(42i64));
+
+        assert_eq!(octal(input), output);
+        assert_eq!(integer(input), output);
+        assert_eq!(literal(input), output);
+    }
+
+    #[test]
+    fn case_octal_explicit_maximum_integer_value() {
+        let input  = b"0o777777777777777777777";
+        let output = Result::Done(&b""[..], Literal::Integer(i64::MAX));
 
-        assert_eq!(octal(input), Result::Error(Error::Position(ErrorKind::MapRes, &b"01000000000000000000000"[..])));
+        assert_eq!(octal(input), output);
         assert_eq!(integer(input), output);
         assert_eq!(literal(input), output);
     }
 
+    #[test]
+    fn case_octal_explicit_overflow_to_real() {
+        let input  = b"0o1000000000000000000000";
+        let output = Result::Done(&b""[..], Literal::Real(9223372036854775808f64));
+
+        assert_eq!(octal(input), output);
+        assert_eq!(integer(input), output);
+        assert_eq!(literal(input), output);
+    }
+
+    #[test]
+    fn case_invalid_octal_explicit_no_number() {
+        let input  = b"0o";
+        let output = Result::Done(&b"o"[..], Literal::Integer(0i64));
+
+        assert_eq!(octal(input), output);
+        assert_eq!(integer(input), output);
+        assert_eq!(literal(input), output);
+    }
+
+    #[test]
+    fn case_invalid_octal_explicit_before_php_81() {
+        let input  = b"0o777";
+
+        version::set_target(Version::Php80);
+        let output = octal(input);
+        version::set_target(version::LATEST);
+
+        assert_eq!(output, Result::Done(&b"o777"[..], Literal::Integer(0i64)));
+    }
+
     #[test]
     fn case_invalid_octal_not_starting_by_zero() {
         let input  = b"7";
@@ -651,7 +922,7 @@ mod tests {
     #[test]
     fn case_decimal_maximum_integer_value() {
         let input  = b"9223372036854775807";
-        let output = Result::Done(&b""[..], Literal::Integer(::std::i64::MAX));
+        let output = Result::Done(&b""[..], Literal::Integer(i64::MAX));
 
         assert_eq!(decimal(input), output);
         assert_eq!(integer(input), output);
@@ -671,7 +942,7 @@ mod tests {
     #[test]
     fn case_decimal_maximum_real_value() {
         let input  = b"179769313486231570000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000";
-        let output = Result::Done(&b""[..], Literal::Real(::std::f64::MAX));
+        let output = Result::Done(&b""[..], Literal::Real(f64::MAX));
 
         assert_eq!(decimal(input), output);
         assert_eq!(integer(input), output);
@@ -679,9 +950,9 @@ mod tests {
     }
 
     #[test]
-    fn case_invalid_decimal_overflow_to_infinity() {
+    fn case_decimal_overflow_to_infinity() {
         let input  = b"1797693134862315700000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000";
-        let output = Result::Done(&b""[..], Literal::Real(::std::f64::INFINITY));
+        let output = Result::Done(&b""[..], Literal::Real(f64::INFINITY));
 
         assert_eq!(decimal(input), output);
         assert_eq!(integer(input), output);
@@ -741,7 +1012,7 @@ mod tests {
     #[test]
     fn case_hexadecimal_maximum_integer_value() {
         let input  = b"0x7fffffffffffffff";
-        let output = Result::Done(&b""[..], Literal::Integer(::std::i64::MAX));
+        let output = Result::Done(&b""[..], Literal::Integer(i64::MAX));
 
         assert_eq!(hexadecimal(input), output);
         assert_eq!(integer(input), output);
@@ -749,11 +1020,11 @@ mod tests {
     }
 
     #[test]
-    fn case_invalid_hexadecimal_overflow() {
+    fn case_hexadecimal_overflow_to_real() {
         let input  = b"0x8000000000000000";
-        let output = Result::Done(&b"x8000000000000000"[..], Literal::Integer(0i64));
+        let output = Result::Done(&b""[..], Literal::Real(9223372036854775808f64));
 
-        assert_eq!(hexadecimal(input), Result::Error(Error::Position(ErrorKind::MapRes, &b"0x8000000000000000"[..])));
+        assert_eq!(hexadecimal(input), output);
         assert_eq!(integer(input), output);
         assert_eq!(literal(input), output);
     }
@@ -866,6 +1137,65 @@ mod tests {
         assert_eq!(literal(input), output);
     }
 
+    #[test]
+    fn case_exponential_only_with_integer_part_and_dot() {
+        let input  = b"5.";
+        let output = Result::Done(&b""[..], Literal::Real(5f64));
+
+        assert_eq!(exponential(input), output);
+        assert_eq!(number(input), output);
+        assert_eq!(literal(input), output);
+    }
+
+    #[test]
+    fn case_exponential_with_negative_uppercase_exponent() {
+        let input  = b"1.5E-3";
+        let output = Result::Done(&b""[..], Literal::Real(0.0015f64));
+
+        assert_eq!(exponential(input), output);
+        assert_eq!(number(input), output);
+        assert_eq!(literal(input), output);
+    }
+
+    #[test]
+    fn case_exponential_overflow_to_infinity() {
+        let input  = b"1e400";
+        let output = Result::Done(&b""[..], Literal::Real(f64::INFINITY));
+
+        assert_eq!(exponential(input), output);
+        assert_eq!(number(input), output);
+        assert_eq!(literal(input), output);
+    }
+
+    #[test]
+    fn case_exponential_underflow_to_zero() {
+        let input  = b"1e-400";
+        let output = Result::Done(&b""[..], Literal::Real(0f64));
+
+        assert_eq!(exponential(input), output);
+        assert_eq!(number(input), output);
+        assert_eq!(literal(input), output);
+    }
+
+    #[test]
+    fn case_hexadecimal_overflow_to_infinity() {
+        let input  = b"0x10000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000";
+        let output = Result::Done(&b""[..], Literal::Real(f64::INFINITY));
+
+        assert_eq!(hexadecimal(input), output);
+        assert_eq!(number(input), output);
+        assert_eq!(literal(input), output);
+    }
+
+    #[test]
+    fn case_number_exponent_without_fractional_part() {
+        let input  = b"1e10";
+        let output = Result::Done(&b""[..], Literal::Real(1e10f64));
+
+        assert_eq!(number(input), output);
+        assert_eq!(decimal(input), Result::Done(&b"e10"[..], Literal::Integer(1i64)));
+    }
+
     #[test]
     fn case_invalid_exponential_only_the_dot() {
         let input = b".";
@@ -874,6 +1204,129 @@ mod tests {
         assert_eq!(literal(input), Result::Error(Error::Position(ErrorKind::Alt, &b"."[..])));
     }
 
+    #[test]
+    fn case_decimal_with_separators() {
+        let input  = b"1_000_000";
+        let output = Result::Done(&b""[..], Literal::Integer(1000000i64));
+
+        assert_eq!(decimal(input), output);
+        assert_eq!(integer(input), output);
+        assert_eq!(literal(input), output);
+    }
+
+    #[test]
+    fn case_decimal_with_separators_overflow_to_real() {
+        let input  = b"9_223_372_036_854_775_808";
+        let output = Result::Done(&b""[..], Literal::Real(9223372036854775808f64));
+
+        assert_eq!(decimal(input), output);
+        assert_eq!(integer(input), output);
+        assert_eq!(literal(input), output);
+    }
+
+    #[test]
+    fn case_binary_with_separators() {
+        let input  = b"0b10_1010";
+        let output = Result::Done(&b""[..], Literal::Integer(42i64));
+
+        assert_eq!(binary(input), output);
+        assert_eq!(integer(input), output);
+        assert_eq!(literal(input), output);
+    }
+
+    #[test]
+    fn case_octal_with_separators() {
+        let input  = b"0_7_77";
+        let output = Result::Done(&b""[..], Literal::Integer(511i64));
+
+        assert_eq!(octal(input), output);
+        assert_eq!(integer(input), output);
+        assert_eq!(literal(input), output);
+    }
+
+    #[test]
+    fn case_octal_explicit_with_separators() {
+        let input  = b"0o7_77";
+        let output = Result::Done(&b""[..], Literal::Integer(511i64));
+
+        assert_eq!(octal(input), output);
+        assert_eq!(integer(input), output);
+        assert_eq!(literal(input), output);
+    }
+
+    #[test]
+    fn case_hexadecimal_with_separators() {
+        let input  = b"0xFF_FF";
+        let output = Result::Done(&b""[..], Literal::Integer(65535i64));
+
+        assert_eq!(hexadecimal(input), output);
+        assert_eq!(integer(input), output);
+        assert_eq!(literal(input), output);
+    }
+
+    #[test]
+    fn case_exponential_with_separators() {
+        let input  = b"1_5.2_5e1_0";
+        let output = Result::Done(&b""[..], Literal::Real(15.25e10f64));
+
+        assert_eq!(exponential(input), output);
+        assert_eq!(literal(input), output);
+    }
+
+    #[test]
+    fn case_exponential_with_separators_only_fractional_part() {
+        let input  = b".0_5";
+        let output = Result::Done(&b""[..], Literal::Real(0.05f64));
+
+        assert_eq!(exponential(input), output);
+        assert_eq!(literal(input), output);
+    }
+
+    #[test]
+    fn case_invalid_decimal_with_consecutive_separators() {
+        let input  = b"1__000";
+        let output = Result::Done(&b"__000"[..], Literal::Integer(1i64));
+
+        assert_eq!(decimal(input), output);
+        assert_eq!(integer(input), output);
+        assert_eq!(literal(input), output);
+    }
+
+    #[test]
+    fn case_invalid_decimal_with_trailing_separator() {
+        let input  = b"1_";
+        let output = Result::Done(&b"_"[..], Literal::Integer(1i64));
+
+        assert_eq!(decimal(input), output);
+        assert_eq!(integer(input), output);
+        assert_eq!(literal(input), output);
+    }
+
+    #[test]
+    fn case_invalid_binary_with_trailing_separator() {
+        let input = b"0b1_";
+
+        assert_eq!(binary(input), Result::Error(Error::Position(ErrorKind::MapRes, &input[..])));
+    }
+
+    #[test]
+    fn case_invalid_hexadecimal_with_separator_after_prefix() {
+        let input = b"0x_FF";
+
+        assert_eq!(hexadecimal(input), Result::Error(Error::Position(ErrorKind::HexDigit, &b"_FF"[..])));
+    }
+
+    #[test]
+    fn case_invalid_separators_before_php_74() {
+        let input = b"1_000";
+
+        version::set_target(Version::Php73);
+        let output = decimal(input);
+        version::set_target(version::LATEST);
+
+        assert_eq!(output, Result::Error(Error::Position(ErrorKind::MapRes, &input[..])));
+    }
+
     #[test]
     fn case_string_single_quoted() {
         let input  = b"'foobar'";
@@ -1037,7 +1490,14 @@ mod tests {
     #[test]
     fn case_string_nowdoc() {
         let input  = b"<<<'FOO'\nhello \n  world \nFOO;\n";
-        let output = Result::Done(&b""[..], Literal::String(b"hello \n  world ".to_vec()));
+        let output = Result::Done(
+            &b";\n"[..],
+            Literal::Nowdoc {
+                label      : b"FOO".to_vec(),
+                indentation: Vec::new(),
+                value      : b"hello \n  world ".to_vec()
+            }
+        );
 
         assert_eq!(string_nowdoc(input), output);
         assert_eq!(string(input), output);
@@ -1047,7 +1507,14 @@ mod tests {
     #[test]
     fn case_string_nowdoc_crlf() {
         let input  = b"<<<'FOO'\r\nhello \r\n  world \r\nFOO;\r\n";
-        let output = Result::Done(&b""[..], Literal::String(b"hello \r\n  world ".to_vec()));
+        let output = Result::Done(
+            &b";\r\n"[..],
+            Literal::Nowdoc {
+                label      : b"FOO".to_vec(),
+                indentation: Vec::new(),
+                value      : b"hello \r\n  world ".to_vec()
+            }
+        );
 
         assert_eq!(string_nowdoc(input), output);
         assert_eq!(string(input), output);
@@ -1057,7 +1524,14 @@ mod tests {
     #[test]
     fn case_string_nowdoc_without_semi_colon() {
         let input  = b"<<<'FOO'\nhello \n  world \nFOO\n";
-        let output = Result::Done(&b""[..], Literal::String(b"hello \n  world ".to_vec()));
+        let output = Result::Done(
+            &b"\n"[..],
+            Literal::Nowdoc {
+                label      : b"FOO".to_vec(),
+                indentation: Vec::new(),
+                value      : b"hello \n  world ".to_vec()
+            }
+        );
 
         assert_eq!(string_nowdoc(input), output);
         assert_eq!(string(input), output);
@@ -1067,7 +1541,14 @@ mod tests {
     #[test]
     fn case_string_nowdoc_without_semi_colon_crlf() {
         let input  = b"<<<'FOO'\r\nhello \r\n  world \r\nFOO\r\n";
-        let output = Result::Done(&b""[..], Literal::String(b"hello \r\n  world ".to_vec()));
+        let output = Result::Done(
+            &b"\r\n"[..],
+            Literal::Nowdoc {
+                label      : b"FOO".to_vec(),
+                indentation: Vec::new(),
+                value      : b"hello \r\n  world ".to_vec()
+            }
+        );
 
         assert_eq!(string_nowdoc(input), output);
         assert_eq!(string(input), output);
@@ -1077,7 +1558,14 @@ mod tests {
     #[test]
     fn case_string_nowdoc_empty() {
         let input  = b"<<<'FOO'\nFOO\n";
-        let output = Result::Done(&b""[..], Literal::String(Vec::new()));
+        let output = Result::Done(
+            &b"\n"[..],
+            Literal::Nowdoc {
+                label      : b"FOO".to_vec(),
+                indentation: Vec::new(),
+                value      : Vec::new()
+            }
+        );
 
         assert_eq!(string_nowdoc(input), output);
         assert_eq!(string(input), output);
@@ -1087,7 +1575,14 @@ mod tests {
     #[test]
     fn case_string_nowdoc_empty_crlf() {
         let input  = b"<<<'FOO'\r\nFOO\r\n";
-        let output = Result::Done(&b""[..], Literal::String(Vec::new()));
+        let output = Result::Done(
+            &b"\r\n"[..],
+            Literal::Nowdoc {
+                label      : b"FOO".to_vec(),
+                indentation: Vec::new(),
+                value      : Vec::new()
+            }
+        );
 
         assert_eq!(string_nowdoc(input), output);
         assert_eq!(string(input), output);
@@ -1097,7 +1592,14 @@ mod tests {
     #[test]
     fn case_string_nowdoc_with_whitespaces_before_identifier() {
         let input  = b"<<<   \t  'FOO'\nhello \n  world \nFOO\n";
-        let output = Result::Done(&b""[..], Literal::String(b"hello \n  world ".to_vec()));
+        let output = Result::Done(
+            &b"\n"[..],
+            Literal::Nowdoc {
+                label      : b"FOO".to_vec(),
+                indentation: Vec::new(),
+                value      : b"hello \n  world ".to_vec()
+            }
+        );
 
         assert_eq!(string_nowdoc(input), output);
         assert_eq!(string(input), output);
@@ -1107,7 +1609,14 @@ mod tests {
     #[test]
     fn case_string_nowdoc_with_whitespaces_before_identifier_crlf() {
         let input  = b"<<<   \t  'FOO'\r\nhello \r\n  world \r\nFOO\r\n";
-        let output = Result::Done(&b""[..], Literal::String(b"hello \r\n  world ".to_vec()));
+        let output = Result::Done(
+            &b"\r\n"[..],
+            Literal::Nowdoc {
+                label      : b"FOO".to_vec(),
+                indentation: Vec::new(),
+                value      : b"hello \r\n  world ".to_vec()
+            }
+        );
 
         assert_eq!(string_nowdoc(input), output);
         assert_eq!(string(input), output);
@@ -1117,7 +1626,14 @@ mod tests {
     #[test]
     fn case_string_binary_nowdoc() {
         let input  = b"b<<<'FOO'\nhello \n  world \nFOO\n";
-        let output = Result::Done(&b""[..], Literal::String(b"hello \n  world ".to_vec()));
+        let output = Result::Done(
+            &b"\n"[..],
+            Literal::Nowdoc {
+                label      : b"FOO".to_vec(),
+                indentation: Vec::new(),
+                value      : b"hello \n  world ".to_vec()
+            }
+        );
 
         assert_eq!(string_nowdoc(input), output);
         assert_eq!(string(input), output);
@@ -1127,7 +1643,14 @@ mod tests {
     #[test]
     fn case_string_binary_nowdoc_crlf() {
         let input  = b"b<<<'FOO'\r\nhello \r\n  world \r\nFOO\r\n";
-        let output = Result::Done(&b""[..], Literal::String(b"hello \r\n  world ".to_vec()));
+        let output = Result::Done(
+            &b"\r\n"[..],
+            Literal::Nowdoc {
+                label      : b"FOO".to_vec(),
+                indentation: Vec::new(),
+                value      : b"hello \r\n  world ".to_vec()
+            }
+        );
 
         assert_eq!(string_nowdoc(input), output);
         assert_eq!(string(input), output);
@@ -1137,7 +1660,14 @@ mod tests {
     #[test]
     fn case_string_binary_uppercase_nowdoc() {
         let input  = b"B<<<'FOO'\nhello \n  world \nFOO\n";
-        let output = Result::Done(&b""[..], Literal::String(b"hello \n  world ".to_vec()));
+        let output = Result::Done(
+            &b"\n"[..],
+            Literal::Nowdoc {
+                label      : b"FOO".to_vec(),
+                indentation: Vec::new(),
+                value      : b"hello \n  world ".to_vec()
+            }
+        );
 
         assert_eq!(string_nowdoc(input), output);
         assert_eq!(string(input), output);
@@ -1147,16 +1677,104 @@ mod tests {
     #[test]
     fn case_string_binary_uppercase_nowdoc_crlf() {
         let input  = b"B<<<'FOO'\r\nhello \r\n  world \r\nFOO\r\n";
-        let output = Result::Done(&b""[..], Literal::String(b"hello \r\n  world ".to_vec()));
+        let output = Result::Done(
+            &b"\r\n"[..],
+            Literal::Nowdoc {
+                label      : b"FOO".to_vec(),
+                indentation: Vec::new(),
+                value      : b"hello \r\n  world ".to_vec()
+            }
+        );
+
+        assert_eq!(string_nowdoc(input), output);
+        assert_eq!(string(input), output);
+        assert_eq!(literal(input), output);
+    }
+
+    #[test]
+    fn case_string_nowdoc_indented_closing_identifier() {
+        let input  = b"<<<'FOO'\n    hello\n\n      world\n    FOO, 42";
+        let output = Result::Done(
+            &b", 42"[..],
+            Literal::Nowdoc {
+                label      : b"FOO".to_vec(),
+                indentation: b"    ".to_vec(),
+                value      : b"hello\n\n  world".to_vec()
+            }
+        );
+
+        assert_eq!(string_nowdoc(input), output);
+        assert_eq!(string(input), output);
+        assert_eq!(literal(input), output);
+    }
+
+    #[test]
+    fn case_string_nowdoc_indented_closing_identifier_with_tabs_crlf() {
+        let input  = b"<<<'FOO'\r\n\t\thello\r\n\t\r\n\t\tworld\r\n\t\tFOO)";
+        let output = Result::Done(
+            &b")"[..],
+            Literal::Nowdoc {
+                label      : b"FOO".to_vec(),
+                indentation: b"\t\t".to_vec(),
+                value      : b"hello\r\n\r\nworld".to_vec()
+            }
+        );
+
+        assert_eq!(string_nowdoc(input), output);
+        assert_eq!(string(input), output);
+        assert_eq!(literal(input), output);
+    }
+
+    #[test]
+    fn case_string_nowdoc_closing_identifier_is_not_a_prefix() {
+        let input  = b"<<<'FOO'\nFOOBAR\n  FOO_\nFOO";
+        let output = Result::Done(
+            &b""[..],
+            Literal::Nowdoc {
+                label      : b"FOO".to_vec(),
+                indentation: Vec::new(),
+                value      : b"FOOBAR\n  FOO_".to_vec()
+            }
+        );
 
         assert_eq!(string_nowdoc(input), output);
         assert_eq!(string(input), output);
         assert_eq!(literal(input), output);
     }
 
+    #[test]
+    fn case_invalid_string_nowdoc_under_indented_line() {
+        let input  = b"<<<'FOO'\n    hello\n  world\n    FOO\n";
+        let output = Result::Error(Error::Position(ErrorKind::Alt, &input[..]));
+
+        assert_eq!(string_nowdoc(input), Result::Error(Error::Code(ErrorKind::Custom(StringError::InvalidIndentation as u32))));
+        assert_eq!(string(input), output);
+        assert_eq!(literal(input), output);
+    }
+
+    #[test]
+    fn case_invalid_string_nowdoc_mixed_indentation() {
+        let input  = b"<<<'FOO'\n \thello\n \tFOO\n";
+        let output = Result::Error(Error::Position(ErrorKind::Alt, &input[..]));
+
+        assert_eq!(string_nowdoc(input), Result::Error(Error::Code(ErrorKind::Custom(StringError::InvalidIndentation as u32))));
+        assert_eq!(string(input), output);
+        assert_eq!(literal(input), output);
+    }
+
+    #[test]
+    fn case_invalid_string_nowdoc_line_indented_with_another_character() {
+        let input  = b"<<<'FOO'\n\thello\n  FOO\n";
+        let output = Result::Error(Error::Position(ErrorKind::Alt, &input[..]));
+
+        assert_eq!(string_nowdoc(input), Result::Error(Error::Code(ErrorKind::Custom(StringError::InvalidIndentation as u32))));
+        assert_eq!(string(input), output);
+        assert_eq!(literal(input), output);
+    }
+
     #[test]
     fn case_invalid_string_nowdoc_too_short() {
-        let input  = b"<<<'A'\nA";
+        let input  = b"<<<'A'\n";
         let output = Result::Error(Error::Position(ErrorKind::Alt, &input[..]));
 
         assert_eq!(string_nowdoc(input), Result::Error(Error::Code(ErrorKind::Custom(StringError::TooShort as u32))));
@@ -1235,58 +1853,93 @@ mod tests {
     }
 
     #[test]
-    fn case_invalid_string_nowdoc_closing_character_no_semi_colon_no_newline() {
+    fn case_string_nowdoc_without_semi_colon_without_newline() {
         let input  = b"<<<'FOO'\nhello \n  world \nFOO";
-        let output = Result::Error(Error::Position(ErrorKind::Alt, &input[..]));
+        let output = Result::Done(
+            &b""[..],
+            Literal::Nowdoc {
+                label      : b"FOO".to_vec(),
+                indentation: Vec::new(),
+                value      : b"hello \n  world ".to_vec()
+            }
+        );
 
-        assert_eq!(string_nowdoc(input), Result::Error(Error::Code(ErrorKind::Custom(StringError::InvalidClosingCharacter as u32))));
+        assert_eq!(string_nowdoc(input), output);
         assert_eq!(string(input), output);
         assert_eq!(literal(input), output);
     }
 
     #[test]
-    fn case_invalid_string_nowdoc_closing_character_no_semi_colon_no_newline_crlf() {
+    fn case_string_nowdoc_without_semi_colon_without_newline_crlf() {
         let input  = b"<<<'FOO'\r\nhello \r\n  world \r\nFOO";
-        let output = Result::Error(Error::Position(ErrorKind::Alt, &input[..]));
+        let output = Result::Done(
+            &b""[..],
+            Literal::Nowdoc {
+                label      : b"FOO".to_vec(),
+                indentation: Vec::new(),
+                value      : b"hello \r\n  world ".to_vec()
+            }
+        );
 
-        assert_eq!(string_nowdoc(input), Result::Error(Error::Code(ErrorKind::Custom(StringError::InvalidClosingCharacter as u32))));
+        assert_eq!(string_nowdoc(input), output);
         assert_eq!(string(input), output);
         assert_eq!(literal(input), output);
     }
 
     #[test]
-    fn case_invalid_string_nowdoc_closing_character_no_newline() {
+    fn case_string_nowdoc_without_newline() {
         let input  = b"<<<'FOO'\nhello \n  world \nFOO;";
-        let output = Result::Error(Error::Position(ErrorKind::Alt, &input[..]));
+        let output = Result::Done(
+            &b";"[..],
+            Literal::Nowdoc {
+                label      : b"FOO".to_vec(),
+                indentation: Vec::new(),
+                value      : b"hello \n  world ".to_vec()
+            }
+        );
 
-        assert_eq!(string_nowdoc(input), Result::Error(Error::Code(ErrorKind::Custom(StringError::InvalidClosingCharacter as u32))));
+        assert_eq!(string_nowdoc(input), output);
         assert_eq!(string(input), output);
         assert_eq!(literal(input), output);
     }
 
     #[test]
-    fn case_invalid_string_nowdoc_closing_character_no_newline_crlf() {
+    fn case_string_nowdoc_without_newline_crlf() {
         let input  = b"<<<'FOO'\r\nhello \r\n  world \r\nFOO;";
-        let output = Result::Error(Error::Position(ErrorKind::Alt, &input[..]));
+        let output = Result::Done(
+            &b";"[..],
+            Literal::Nowdoc {
+                label      : b"FOO".to_vec(),
+                indentation: Vec::new(),
+                value      : b"hello \r\n  world ".to_vec()
+            }
+        );
 
-        assert_eq!(string_nowdoc(input), Result::Error(Error::Code(ErrorKind::Custom(StringError::InvalidClosingCharacter as u32))));
+        assert_eq!(string_nowdoc(input), output);
         assert_eq!(string(input), output);
         assert_eq!(literal(input), output);
     }
 
     #[test]
-    fn case_invalid_string_nowdoc_closing_character_missing_lf_in_crlf() {
+    fn case_string_nowdoc_missing_lf_in_crlf() {
         let input  = b"<<<'FOO'\r\nhello \r\n  world \r\nFOO\r";
-        let output = Result::Error(Error::Position(ErrorKind::Alt, &input[..]));
+        let output = Result::Done(
+            &b"\r"[..],
+            Literal::Nowdoc {
+                label      : b"FOO".to_vec(),
+                indentation: Vec::new(),
+                value      : b"hello \r\n  world ".to_vec()
+            }
+        );
 
-        assert_eq!(string_nowdoc(input), Result::Error(Error::Code(ErrorKind::Custom(StringError::InvalidClosingCharacter as u32))));
+        assert_eq!(string_nowdoc(input), output);
         assert_eq!(string(input), output);
         assert_eq!(literal(input), output);
     }
 
     #[test]
     fn case_invalid_string_binary_nowdoc_too_short() {
-        let input  = b"b<<<'A'\nA";
+        let input  = b"b<<<'A'\n";
         let output = Result::Error(Error::Position(ErrorKind::Alt, &input[..]));
 
         assert_eq!(string_nowdoc(input), Result::Error(Error::Code(ErrorKind::Custom(StringError::TooShort as u32))));
@@ -1326,7 +1979,7 @@ mod tests {
 
     #[test]
     fn case_invalid_string_binary_uppercase_nowdoc_too_short() {
-        let input  = b"B<<<'A'\nA";
+        let input  = b"B<<<'A'\n";
         let output = Result::Error(Error::Position(ErrorKind::Alt, &input[..]));
 
         assert_eq!(string_nowdoc(input), Result::Error(Error::Code(ErrorKind::Custom(StringError::TooShort as u32))));
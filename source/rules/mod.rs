@@ -33,11 +33,16 @@
 //!
 //! The grammar is splitted into group of rules for the sake of clarity.
 
+pub mod attributes;
 pub mod comments;
 pub mod expressions;
+pub mod hooks;
 pub mod literals;
+pub mod script;
 pub mod skip;
+pub mod statements;
 pub mod tokens;
+pub mod types;
 pub mod whitespaces;
 
 use super::ast;
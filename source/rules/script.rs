@@ -0,0 +1,1272 @@
+// Tagua VM
+//
+//
+// New BSD License
+//
+// Copyright © 2016-2016, Ivan Enderlin.
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//     * Redistributions of source code must retain the above copyright
+//       notice, this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright
+//       notice, this list of conditions and the following disclaimer in the
+//       documentation and/or other materials provided with the distribution.
+//     * Neither the name of the Hoa nor the names of its contributors may be
+//       used to endorse or promote products derived from this software without
+//       specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE
+// ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDERS AND CONTRIBUTORS BE
+// LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR
+// CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF
+// SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS
+// INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN
+// CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+
+//! The script rule.
+//!
+//! A script is a sequence of inline HTML segments and PHP sections, a PHP
+//! section being delimited by an opening tag and an optional closing tag.
+//! The script rule is described by the PHP Language Specification in the
+//! [Grammar chapter, Basic Concepts
+//! section](https://github.com/php/php-langspec/blob/master/spec/19-grammar.md#basic-concepts).
+
+use std::cmp;
+use std::result::Result as StdResult;
+use super::comments::comment;
+use super::expressions::expression;
+use super::skip::skip;
+use super::statements::compound::statements;
+use super::statements::statement_terminator;
+use super::super::ast::Statement;
+use super::super::diagnostics::{
+    self,
+    Diagnostic,
+    DiagnosticKind
+};
+use super::super::encoding::skip_bom;
+use super::super::macros::is_name_byte;
+use super::super::internal::{
+    Error,
+    ErrorKind,
+    Result,
+    fold_into_vector,
+    into_vector_mapper
+};
+use super::super::span::{
+    self,
+    Span,
+    Spanned
+};
+use super::super::tokens;
+use super::super::trivia::{
+    self,
+    CommentKind
+};
+
+/// Script errors, from 200.
+pub enum ScriptError {
+    /// Namespaces are declared with and without a body in the same script.
+    MixedNamespaceDeclarations = 200,
+    /// A statement is declared outside the namespaces declared with a
+    /// body.
+    StatementOutsideNamespace,
+    /// A statement, other than a declare statement, is declared before the
+    /// first namespace.
+    StatementBeforeNamespace
+}
+
+/// Parse a script. The spans are relative to the input, see the `span`
+/// module.
+pub fn script(input: &[u8]) -> Result<&[u8], Vec<Spanned<Statement>>> {
+    span::within(
+        input,
+        |input| {
+            match sections(input, false) {
+                Result::Done(rest, statements) => {
+                    match namespaces_validation(&statements) {
+                        Ok(()) => Result::Done(rest, statements),
+                        Err((error, statement)) => Result::Error(Error::Position(ErrorKind::Custom(error as u32), &input[statement.span.start..]))
+                    }
+                },
+
+                result => result
+            }
+        }
+    )
+}
+
+/// Parse a script like `script`, but recover from the syntax errors. An
+/// invalid statement is skipped up to a synchronization point, and is
+/// replaced by a `Statement::Error` placeholder. Return the best-effort
+/// statements, with the diagnostics reported while parsing, see the
+/// `diagnostics` module. Namespace declarations are not validated.
+pub fn recovering_script(input: &[u8]) -> (Vec<Spanned<Statement>>, Vec<Diagnostic>) {
+    let (output, diagnostics) = span::within(input, |input| diagnostics::collect(input, |input| sections(input, true)));
+    let statements            = match output {
+        Result::Done(_, statements) => statements,
+        _ => vec![]
+    };
+
+    (statements, diagnostics)
+}
+
+/// A script cannot mix namespaces declared with and without a body. Only
+/// declare statements are allowed before the first namespace, and outside
+/// the namespaces declared with a body. Return the error with the offending
+/// statement.
+fn namespaces_validation<'a, 'b>(statements: &'b [Spanned<Statement<'a>>]) -> StdResult<(), (ScriptError, &'b Spanned<Statement<'a>>)> {
+    let mut with_body    = false;
+    let mut without_body = false;
+    let mut before       = None;
+
+    for statement in statements {
+        match statement.node {
+            Statement::Namespace { ref body, .. } => {
+                if let Some(before) = before {
+                    return Err((ScriptError::StatementBeforeNamespace, before));
+                }
+
+                if body.is_some() {
+                    with_body = true;
+                } else {
+                    without_body = true;
+                }
+
+                if with_body && without_body {
+                    return Err((ScriptError::MixedNamespaceDeclarations, statement));
+                }
+            },
+
+            Statement::Declare { .. } | Statement::HaltCompiler { .. } | Statement::InlineHtml(_) => (),
+
+            _ if with_body => return Err((ScriptError::StatementOutsideNamespace, statement)),
+            _ if !without_body && before.is_none() => before = Some(statement),
+            _ => ()
+        }
+    }
+
+    Ok(())
+}
+
+fn sections(input: &[u8], recover: bool) -> Result<&[u8], Vec<Spanned<Statement>>> {
+    let source     = input;
+    let mut output = vec![];
+    let mut input  = skip_shebang(skip_bom(input));
+
+    loop {
+        let (html, section) = match opening_tag(input) {
+            Some((offset, length)) => (&input[..offset], Some(&input[offset + length..])),
+            None => (input, None)
+        };
+
+        if !html.is_empty() {
+            output.push(Spanned::new(Statement::InlineHtml(html), Span::new(html, &html[html.len()..])));
+        }
+
+        let mut section = match section {
+            Some(section) => section,
+            None => return Result::Done(&input[input.len()..], output)
+        };
+
+        let tag = &input[html.len()..];
+
+        if tag.starts_with(tokens::OPEN_TAG_WITH_ECHO) {
+            match echo_section(section) {
+                Result::Done(rest, statement) => {
+                    output.push(Spanned::new(statement, Span::new(tag, rest)));
+                    section = rest;
+                },
+
+                _ if recover => section = skip_invalid_statement(section, &mut output),
+                Result::Error(error) => return Result::Error(error),
+                Result::Incomplete(needed) => return Result::Incomplete(needed)
+            }
+        }
+
+        let statements = if recover {
+            recovering_statements(section)
+        } else {
+            statements(section)
+        };
+
+        let rest = match statements {
+            Result::Done(rest, mut section_statements) => {
+                output.append(&mut section_statements);
+
+                match skip(rest) {
+                    Result::Done(rest, _) => rest,
+                    _ => rest
+                }
+            },
+
+            Result::Error(error) => return Result::Error(error),
+            Result::Incomplete(needed) => return Result::Incomplete(needed)
+        };
+
+        if rest.is_empty() {
+            return Result::Done(rest, output);
+        }
+
+        if let Result::Done(data, _) = halt_compiler(rest) {
+            let data = if data.starts_with(tokens::CLOSE_TAG) {
+                skip_newline(&data[tokens::CLOSE_TAG.len()..])
+            } else {
+                data
+            };
+
+            output.push(
+                Spanned::new(
+                    Statement::HaltCompiler {
+                        offset: source.len() - data.len(),
+                        data  : data
+                    },
+                    Span::new(rest, &data[data.len()..])
+                )
+            );
+
+            return Result::Done(&data[data.len()..], output);
+        }
+
+        if !rest.starts_with(tokens::CLOSE_TAG) {
+            return Result::Error(Error::Position(ErrorKind::Tag, rest));
+        }
+
+        input = skip_newline(&rest[tokens::CLOSE_TAG.len()..]);
+    }
+}
+
+/// Parse statements like `statements`, and skip the invalid ones, until
+/// the end of the section.
+fn recovering_statements(input: &[u8]) -> Result<&[u8], Vec<Spanned<Statement>>> {
+    let mut output = vec![];
+    let mut input  = input;
+
+    loop {
+        if let Result::Done(rest, mut statements) = statements(input) {
+            output.append(&mut statements);
+            input = rest;
+        }
+
+        let start = match skip(input) {
+            Result::Done(start, _) => start,
+            _ => input
+        };
+
+        let is_halted = matches!(halt_compiler(start), Result::Done(_, _));
+
+        if start.is_empty() || start.starts_with(tokens::CLOSE_TAG) || is_halted {
+            return Result::Done(input, output);
+        }
+
+        input = skip_invalid_statement(start, &mut output);
+    }
+}
+
+/// Skip an invalid statement up to its synchronization point, replace it
+/// by a `Statement::Error` placeholder, and report it.
+fn skip_invalid_statement<'a>(input: &'a [u8], output: &mut Vec<Spanned<Statement<'a>>>) -> &'a [u8] {
+    let start = match skip(input) {
+        Result::Done(start, _) => start,
+        _ => input
+    };
+    let length = synchronization_point(start);
+    let rest   = &start[length..];
+
+    diagnostics::report(start, rest, DiagnosticKind::SyntaxError);
+    output.push(Spanned::new(Statement::Error(&start[..length]), Span::new(start, rest)));
+
+    rest
+}
+
+/// Find the synchronization point following an invalid statement: after a
+/// `;` or a block, or before a `}` closing an outer block or before a
+/// closing tag. Comments and strings are skipped, so that their content is
+/// not considered. A curly bracket offset, like `$foo{0}`, is not a block.
+/// At least one byte is skipped, if any.
+fn synchronization_point(input: &[u8]) -> usize {
+    let input_length = input.len();
+    let mut depth    = 0;
+    let mut offset   = 0;
+    let mut is_block = false;
+
+    while offset < input_length {
+        let rest = &input[offset..];
+
+        if depth == 0 && rest.starts_with(tokens::CLOSE_TAG) {
+            break;
+        }
+
+        if let Result::Done(after_comment, _) = comment(rest) {
+            offset = input_length - after_comment.len();
+
+            continue;
+        }
+
+        let byte = rest[0];
+
+        if byte == b'\'' || byte == b'"' || byte == b'`' {
+            offset += quoted_length(rest);
+
+            continue;
+        }
+
+        if byte == b'{' {
+            if depth == 0 {
+                is_block = !is_curly_bracket_offset(&input[..offset]);
+            }
+
+            depth += 1;
+        } else if byte == b'}' {
+            if depth == 0 {
+                break;
+            }
+
+            depth -= 1;
+
+            if depth == 0 && is_block {
+                offset += 1;
+
+                break;
+            }
+        } else if byte == b';' && depth == 0 {
+            offset += 1;
+
+            break;
+        }
+
+        offset += 1;
+    }
+
+    cmp::min(cmp::max(offset, 1), input_length)
+}
+
+/// Check whether a `{` following the given bytes opens a curly bracket
+/// offset, like `$foo{0}`, `$foo->bar{0}` or `$foo[0]{1}`, rather than a
+/// block.
+fn is_curly_bracket_offset(input: &[u8]) -> bool {
+    let input = match input.iter().rposition(|byte| !b" \t\n\r".contains(byte)) {
+        Some(position) => &input[..position + 1],
+        None => return false
+    };
+    let name_start = match input.iter().rposition(|byte| !is_name_byte(*byte)) {
+        Some(position) => position + 1,
+        None => 0
+    };
+
+    if name_start == input.len() {
+        return input.ends_with(tokens::RIGHT_SQUARE_BRACKET) || input.ends_with(tokens::RIGHT_CURLY_BRACKET);
+    }
+
+    input[..name_start].ends_with(tokens::VARIABLE) || input[..name_start].ends_with(tokens::DYNAMIC_CALL)
+}
+
+/// Compute the length of a quoted string, quotes included. An unterminated
+/// string runs until the end of the input.
+fn quoted_length(input: &[u8]) -> usize {
+    let quote        = input[0];
+    let input_length = input.len();
+    let mut offset   = 1;
+
+    while offset < input_length {
+        if input[offset] == b'\\' {
+            offset += 2;
+        } else if input[offset] == quote {
+            return offset + 1;
+        } else {
+            offset += 1;
+        }
+    }
+
+    input_length
+}
+
+/// Parse a closing tag followed by inline HTML and an opening tag, inside
+/// a statement list, e.g. `<?php if ($x): ?>html<?php endif;`. The inline
+/// HTML is returned as a statement if it is not empty, followed by an echo
+/// statement if the opening tag is `<?=`.
+pub fn inline_html(input: &[u8]) -> Result<&[u8], Vec<Spanned<Statement>>> {
+    if !input.starts_with(tokens::CLOSE_TAG) {
+        return Result::Error(Error::Position(ErrorKind::Tag, input));
+    }
+
+    let html_start       = skip_newline(&input[tokens::CLOSE_TAG.len()..]);
+    let (offset, length) = match opening_tag(html_start) {
+        Some(tag) => tag,
+        None => return Result::Error(Error::Position(ErrorKind::Tag, input))
+    };
+    let html       = &html_start[..offset];
+    let section    = &html_start[offset + length..];
+    let mut output = vec![];
+
+    if !html.is_empty() {
+        output.push(Spanned::new(Statement::InlineHtml(html), Span::new(html, &html[html.len()..])));
+    }
+
+    if !html_start[offset..].starts_with(tokens::OPEN_TAG_WITH_ECHO) {
+        return Result::Done(section, output);
+    }
+
+    match echo_section(section) {
+        Result::Done(rest, statement) => {
+            output.push(Spanned::new(statement, Span::new(&html_start[offset..], rest)));
+
+            Result::Done(rest, output)
+        },
+
+        Result::Error(error) => Result::Error(error),
+        Result::Incomplete(needed) => Result::Incomplete(needed)
+    }
+}
+
+named!(
+    echo_section<Statement>,
+    chain!(
+        accumulator: map_res!(
+            first!(spanned!(expression)),
+            into_vector_mapper
+        ) ~
+        result: fold_many0!(
+            complete!(
+                preceded!(
+                    first!(tag!(tokens::COMMA)),
+                    first!(spanned!(expression))
+                )
+            ),
+            accumulator,
+            fold_into_vector
+        ) ~
+        first!(expect!("echo_section", &[tokens::SEMICOLON], statement_terminator)),
+        || { Statement::Echo(result) }
+    )
+);
+
+named!(
+    halt_compiler,
+    chain!(
+        keyword!(tokens::HALT_COMPILER) ~
+        first!(tag!(tokens::LEFT_PARENTHESIS)) ~
+        first!(expect!("halt_compiler", tokens::RIGHT_PARENTHESIS)) ~
+        terminator: first!(expect!("halt_compiler", &[tokens::SEMICOLON], statement_terminator)),
+        || { terminator }
+    )
+);
+
+/// Find the first opening tag in the input. Return its offset and its
+/// length, including the whitespace that must follow `<?php`.
+fn opening_tag(input: &[u8]) -> Option<(usize, usize)> {
+    let tag_length   = tokens::OPEN_TAG.len();
+    let input_length = input.len();
+    let mut offset   = 0;
+
+    while offset < input_length {
+        if input[offset..].starts_with(tokens::OPEN_TAG_WITH_ECHO) {
+            return Some((offset, tokens::OPEN_TAG_WITH_ECHO.len()));
+        }
+
+        if offset + tag_length <= input_length && input[offset..offset + tag_length].eq_ignore_ascii_case(tokens::OPEN_TAG) {
+            let rest = &input[offset + tag_length..];
+
+            if rest.is_empty() {
+                return Some((offset, tag_length));
+            }
+
+            let whitespace_length = newline_length(rest);
+
+            if whitespace_length > 0 {
+                return Some((offset, tag_length + whitespace_length));
+            }
+
+            if rest[0] == b' ' || rest[0] == b'\t' {
+                return Some((offset, tag_length + 1));
+            }
+        }
+
+        offset += 1;
+    }
+
+    None
+}
+
+/// A script can start with a shebang line, e.g. `#!/usr/bin/env php`.
+/// Return its content, without `#!` and the newline, e.g. `/usr/bin/env
+/// php`. The byte order mark, if any, must be skipped first.
+pub fn shebang(input: &[u8]) -> Option<&[u8]> {
+    if !input.starts_with(b"#!") {
+        return None;
+    }
+
+    let length = input
+        .iter()
+        .position(|&byte| byte == b'\n' || byte == b'\r')
+        .unwrap_or(input.len());
+
+    Some(&input[2..length])
+}
+
+/// The shebang line is skipped with its newline, and recorded if the
+/// comments are being collected, see the `trivia` module.
+fn skip_shebang(input: &[u8]) -> &[u8] {
+    match shebang(input) {
+        Some(content) => {
+            let rest = skip_newline(&input[2 + content.len()..]);
+
+            trivia::record(input, content, rest, CommentKind::Shebang);
+
+            rest
+        },
+
+        None => input
+    }
+}
+
+/// A closing tag eats the newline directly following it, if any.
+fn skip_newline(input: &[u8]) -> &[u8] {
+    &input[newline_length(input)..]
+}
+
+fn newline_length(input: &[u8]) -> usize {
+    if input.starts_with(b"\r\n") {
+        2
+    } else if input.starts_with(b"\n") || input.starts_with(b"\r") {
+        1
+    } else {
+        0
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        ScriptError,
+        recovering_script,
+        script,
+        shebang
+    };
+    use super::super::super::ast::{
+        DeclareDirective,
+        Expression,
+        Literal,
+        Name,
+        Statement,
+        Variable
+    };
+    use super::super::super::diagnostics::{
+        Diagnostic,
+        DiagnosticKind
+    };
+    use super::super::super::internal::{
+        Error,
+        ErrorKind,
+        Result
+    };
+    use super::super::super::span::{
+        Span,
+        Spanned
+    };
+    use super::super::super::trivia::{
+        self,
+        Comment,
+        CommentKind
+    };
+
+    #[test]
+    fn case_script_empty() {
+        assert_eq!(script(b""), Result::Done(&b""[..], vec![]));
+    }
+
+    #[test]
+    fn case_script_spans() {
+        let input = b"#!php\n<p><?= $a ?>\n<?php {}\n{ } __halt_compiler();foo";
+
+        match script(input) {
+            Result::Done(_, statements) => {
+                assert_eq!(
+                    statements.iter().map(|statement| statement.span).collect::<Vec<_>>(),
+                    vec![
+                        Span { start: 6, end: 9 },
+                        Span { start: 9, end: 16 },
+                        Span { start: 25, end: 27 },
+                        Span { start: 28, end: 31 },
+                        Span { start: 32, end: 53 }
+                    ]
+                );
+
+                match statements[1].node {
+                    Statement::Echo(ref expressions) => {
+                        assert_eq!(expressions[0].span, Span { start: 13, end: 15 });
+                    },
+
+                    _ => panic!("An echo was expected.")
+                }
+            },
+
+            _ => panic!("The script is invalid.")
+        }
+    }
+
+    #[test]
+    fn case_script_alternative_syntax_with_inline_html() {
+        let input  = b"<?php if ($x): ?>html<?php endif; ?>";
+        let output = Result::Done(
+            &b""[..],
+            vec![
+                Spanned::from(Statement::If {
+                    condition: Spanned::from(Expression::Variable(Variable::Name(&b"x"[..]))),
+                    then     : Box::new(Spanned::from(Statement::Block(vec![Spanned::from(Statement::InlineHtml(&b"html"[..]))]))),
+                    elseifs  : vec![],
+                    otherwise: None
+                })
+            ]
+        );
+
+        assert_eq!(script(input), output);
+    }
+
+    #[test]
+    fn case_script_template_loop() {
+        let input  = b"<ul>\n<?php foreach ($a as $b): ?>\n<li><?= $b ?></li>\n<?php endforeach; ?>\n</ul>";
+        let output = Result::Done(
+            &b""[..],
+            vec![
+                Spanned::from(Statement::InlineHtml(&b"<ul>\n"[..])),
+                Spanned::from(Statement::Foreach {
+                    subject     : Spanned::from(Expression::Variable(Variable::Name(&b"a"[..]))),
+                    key         : None,
+                    value       : Spanned::from(Expression::Variable(Variable::Name(&b"b"[..]))),
+                    is_reference: false,
+                    body        : Box::new(
+                        Spanned::from(Statement::Block(
+                            vec![
+                                Spanned::from(Statement::InlineHtml(&b"<li>"[..])),
+                                Spanned::from(Statement::Echo(vec![Spanned::from(Expression::Variable(Variable::Name(&b"b"[..])))])),
+                                Spanned::from(Statement::InlineHtml(&b"</li>\n"[..]))
+                            ]
+                        ))
+                    )
+                }),
+                Spanned::from(Statement::InlineHtml(&b"</ul>"[..]))
+            ]
+        );
+
+        assert_eq!(script(input), output);
+    }
+
+    #[test]
+    fn case_script_block_with_inline_html() {
+        let input  = b"<?php if ($x) { ?>a<?php } else { ?>b<?php } ?>";
+        let output = Result::Done(
+            &b""[..],
+            vec![
+                Spanned::from(Statement::If {
+                    condition: Spanned::from(Expression::Variable(Variable::Name(&b"x"[..]))),
+                    then     : Box::new(Spanned::from(Statement::Block(vec![Spanned::from(Statement::InlineHtml(&b"a"[..]))]))),
+                    elseifs  : vec![],
+                    otherwise: Some(Box::new(Spanned::from(Statement::Block(vec![Spanned::from(Statement::InlineHtml(&b"b"[..]))]))))
+                })
+            ]
+        );
+
+        assert_eq!(script(input), output);
+    }
+
+    #[test]
+    fn case_script_block_with_empty_inline_html() {
+        let input  = b"<?php { ?><?php $a; }";
+        let output = Result::Done(
+            &b""[..],
+            vec![
+                Spanned::from(Statement::Block(vec![Spanned::from(Statement::Expression(Spanned::from(Expression::Variable(Variable::Name(&b"a"[..])))))]))
+            ]
+        );
+
+        assert_eq!(script(input), output);
+    }
+
+    #[test]
+    fn case_invalid_script_block_with_unterminated_inline_html() {
+        let input = b"<?php { ?>html }";
+
+        assert_eq!(script(input), Result::Error(Error::Position(ErrorKind::Tag, &b"{ ?>html }"[..])));
+    }
+
+    #[test]
+    fn case_recovering_script_without_errors() {
+        let input  = b"<p><?php {} ?></p>";
+        let output = (
+            vec![
+                Spanned::from(Statement::InlineHtml(&b"<p>"[..])),
+                Spanned::from(Statement::Block(vec![])),
+                Spanned::from(Statement::InlineHtml(&b"</p>"[..]))
+            ],
+            vec![]
+        );
+
+        assert_eq!(recovering_script(input), output);
+    }
+
+    #[test]
+    fn case_recovering_script_after_a_semicolon() {
+        let input  = b"<?php {} $a = ; $b;";
+        let output = (
+            vec![
+                Spanned::from(Statement::Block(vec![])),
+                Spanned::from(Statement::Error(&b"$a = ;"[..])),
+                Spanned::from(Statement::Expression(Spanned::from(Expression::Variable(Variable::Name(&b"b"[..])))))
+            ],
+            vec![
+                Diagnostic {
+                    kind: DiagnosticKind::SyntaxError,
+                    span: 9..15
+                }
+            ]
+        );
+
+        assert_eq!(recovering_script(input), output);
+    }
+
+    #[test]
+    fn case_recovering_script_after_a_block() {
+        let input  = b"<?php function f() { $a = ; { } } {}";
+        let output = (
+            vec![
+                Spanned::from(Statement::Error(&b"function f() { $a = ; { } }"[..])),
+                Spanned::from(Statement::Block(vec![]))
+            ],
+            vec![
+                Diagnostic {
+                    kind: DiagnosticKind::SyntaxError,
+                    span: 6..33
+                }
+            ]
+        );
+
+        assert_eq!(recovering_script(input), output);
+    }
+
+    #[test]
+    fn case_recovering_script_after_curly_bracket_offsets() {
+        let input  = b"<?php $a{0} = ; $a->b {0}{1} = ; $a[0]{1} = ; {}";
+        let output = (
+            vec![
+                Spanned::from(Statement::Error(&b"$a{0} = ;"[..])),
+                Spanned::from(Statement::Error(&b"$a->b {0}{1} = ;"[..])),
+                Spanned::from(Statement::Error(&b"$a[0]{1} = ;"[..])),
+                Spanned::from(Statement::Block(vec![]))
+            ],
+            vec![
+                Diagnostic {
+                    kind: DiagnosticKind::SyntaxError,
+                    span: 6..15
+                },
+                Diagnostic {
+                    kind: DiagnosticKind::SyntaxError,
+                    span: 16..32
+                },
+                Diagnostic {
+                    kind: DiagnosticKind::SyntaxError,
+                    span: 33..45
+                }
+            ]
+        );
+
+        assert_eq!(recovering_script(input), output);
+    }
+
+    #[test]
+    fn case_recovering_script_ignores_strings_and_comments() {
+        let input  = b"<?php f('};', \"\\\";\", /* ; } */ 1 2); {}";
+        let output = (
+            vec![
+                Spanned::from(Statement::Error(&b"f('};', \"\\\";\", /* ; } */ 1 2);"[..])),
+                Spanned::from(Statement::Block(vec![]))
+            ],
+            vec![
+                Diagnostic {
+                    kind: DiagnosticKind::SyntaxError,
+                    span: 6..36
+                }
+            ]
+        );
+
+        assert_eq!(recovering_script(input), output);
+    }
+
+    #[test]
+    fn case_recovering_script_with_an_unexpected_closing_bracket() {
+        let input  = b"<?php } {}";
+        let output = (
+            vec![
+                Spanned::from(Statement::Error(&b"}"[..])),
+                Spanned::from(Statement::Block(vec![]))
+            ],
+            vec![
+                Diagnostic {
+                    kind: DiagnosticKind::SyntaxError,
+                    span: 6..7
+                }
+            ]
+        );
+
+        assert_eq!(recovering_script(input), output);
+    }
+
+    #[test]
+    fn case_recovering_script_before_a_closing_tag() {
+        let input  = b"<?php $a = ?>foo<?= ; ?>bar";
+        let output = (
+            vec![
+                Spanned::from(Statement::Error(&b"$a = "[..])),
+                Spanned::from(Statement::InlineHtml(&b"foo"[..])),
+                Spanned::from(Statement::Error(&b";"[..])),
+                Spanned::from(Statement::InlineHtml(&b"bar"[..]))
+            ],
+            vec![
+                Diagnostic {
+                    kind: DiagnosticKind::SyntaxError,
+                    span: 6..11
+                },
+                Diagnostic {
+                    kind: DiagnosticKind::SyntaxError,
+                    span: 20..21
+                }
+            ]
+        );
+
+        assert_eq!(recovering_script(input), output);
+    }
+
+    #[test]
+    fn case_recovering_script_before_halt_compiler() {
+        let input  = b"<?php $a = ; __halt_compiler();foo";
+        let output = (
+            vec![
+                Spanned::from(Statement::Error(&b"$a = ;"[..])),
+                Spanned::from(Statement::HaltCompiler {
+                    offset: 31,
+                    data  : &b"foo"[..]
+                })
+            ],
+            vec![
+                Diagnostic {
+                    kind: DiagnosticKind::SyntaxError,
+                    span: 6..12
+                }
+            ]
+        );
+
+        assert_eq!(recovering_script(input), output);
+    }
+
+    #[test]
+    fn case_script_bom() {
+        let input  = b"\xef\xbb\xbf<?php {}";
+        let output = Result::Done(&b""[..], vec![Spanned::from(Statement::Block(vec![]))]);
+
+        assert_eq!(script(input), output);
+    }
+
+    #[test]
+    fn case_script_bom_and_shebang() {
+        let input  = b"\xef\xbb\xbf#!/usr/bin/env php\nfoo";
+        let output = Result::Done(&b""[..], vec![Spanned::from(Statement::InlineHtml(&b"foo"[..]))]);
+
+        assert_eq!(script(input), output);
+    }
+
+    #[test]
+    fn case_script_bom_not_at_the_start() {
+        let input  = b"foo\xef\xbb\xbf";
+        let output = Result::Done(&b""[..], vec![Spanned::from(Statement::InlineHtml(&b"foo\xef\xbb\xbf"[..]))]);
+
+        assert_eq!(script(input), output);
+    }
+
+    #[test]
+    fn case_script_latin1_bytes_in_literals_and_comments() {
+        let input  = b"<?php /* caf\xe9 */ \"caf\xe9\"; # \x80\n'\xff';";
+        let output = Result::Done(
+            &b""[..],
+            vec![
+                Spanned::from(Statement::Expression(Spanned::from(Expression::Literal(Literal::String(b"caf\xe9".to_vec()))))),
+                Spanned::from(Statement::Expression(Spanned::from(Expression::Literal(Literal::String(b"\xff".to_vec())))))
+            ]
+        );
+
+        assert_eq!(script(input), output);
+    }
+
+    #[test]
+    fn case_script_shebang() {
+        let input  = b"#!/usr/bin/env php\n<?php {}";
+        let output = Result::Done(&b""[..], vec![Spanned::from(Statement::Block(vec![]))]);
+
+        assert_eq!(script(input), output);
+    }
+
+    #[test]
+    fn case_script_shebang_with_crlf_and_inline_html() {
+        let input  = b"#!/usr/bin/php -n\r\nfoo<?php {}";
+        let output = Result::Done(
+            &b""[..],
+            vec![
+                Spanned::from(Statement::InlineHtml(&b"foo"[..])),
+                Spanned::from(Statement::Block(vec![]))
+            ]
+        );
+
+        assert_eq!(script(input), output);
+    }
+
+    #[test]
+    fn case_script_shebang_only() {
+        assert_eq!(script(b"#!/usr/bin/env php"), Result::Done(&b""[..], vec![]));
+    }
+
+    #[test]
+    fn case_script_shebang_not_at_the_start() {
+        let input  = b"\n#!/usr/bin/env php\n";
+        let output = Result::Done(&b""[..], vec![Spanned::from(Statement::InlineHtml(&b"\n#!/usr/bin/env php\n"[..]))]);
+
+        assert_eq!(script(input), output);
+    }
+
+    #[test]
+    fn case_shebang() {
+        assert_eq!(shebang(b"#!/usr/bin/php -n\r\nfoo"), Some(&b"/usr/bin/php -n"[..]));
+        assert_eq!(shebang(b"#!/usr/bin/env php"), Some(&b"/usr/bin/env php"[..]));
+        assert_eq!(shebang(b"<?php #!foo"), None);
+    }
+
+    #[test]
+    fn case_script_shebang_is_recorded() {
+        let input = b"#!/usr/bin/env php\n<?php // foo\n";
+        let (output, comments) = trivia::collect(&input[..], script);
+
+        assert_eq!(output, Result::Done(&b""[..], vec![]));
+        assert_eq!(
+            comments,
+            vec![
+                Comment {
+                    kind   : CommentKind::Shebang,
+                    content: &b"/usr/bin/env php"[..],
+                    span   : 0..19
+                },
+                Comment {
+                    kind   : CommentKind::SingleLine,
+                    content: &b" foo\n"[..],
+                    span   : 25..32
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn case_script_shebang_and_halt_compiler_offset() {
+        let input  = b"#!/usr/bin/env php\n<?php __halt_compiler();foo";
+        let output = Result::Done(
+            &b""[..],
+            vec![
+                Spanned::from(Statement::HaltCompiler {
+                    offset: 43,
+                    data  : &b"foo"[..]
+                })
+            ]
+        );
+
+        assert_eq!(script(input), output);
+    }
+
+    #[test]
+    fn case_script_inline_html_only() {
+        let input  = b"<p>Hello, World!</p>\n";
+        let output = Result::Done(&b""[..], vec![Spanned::from(Statement::InlineHtml(&b"<p>Hello, World!</p>\n"[..]))]);
+
+        assert_eq!(script(input), output);
+    }
+
+    #[test]
+    fn case_script_opening_tag_only() {
+        assert_eq!(script(b"<?php"), Result::Done(&b""[..], vec![]));
+    }
+
+    #[test]
+    fn case_script_without_closing_tag() {
+        let input  = b"<?php\n{}\n{ }\n";
+        let output = Result::Done(&b""[..], vec![Spanned::from(Statement::Block(vec![])), Spanned::from(Statement::Block(vec![]))]);
+
+        assert_eq!(script(input), output);
+    }
+
+    #[test]
+    fn case_script_opening_tag_is_case_insensitive() {
+        assert_eq!(script(b"<?PhP {}"), Result::Done(&b""[..], vec![Spanned::from(Statement::Block(vec![]))]));
+    }
+
+    #[test]
+    fn case_script_opening_tag_must_be_followed_by_a_whitespace() {
+        let input  = b"<?phpfoo";
+        let output = Result::Done(&b""[..], vec![Spanned::from(Statement::InlineHtml(&b"<?phpfoo"[..]))]);
+
+        assert_eq!(script(input), output);
+    }
+
+    #[test]
+    fn case_script_interleaved_html() {
+        let input  = b"<html>\n<?php {} ?>\n<body><?php\r\n{}?>\r\n</body>\n<?php /* foo */";
+        let output = Result::Done(
+            &b""[..],
+            vec![
+                Spanned::from(Statement::InlineHtml(&b"<html>\n"[..])),
+                Spanned::from(Statement::Block(vec![])),
+                Spanned::from(Statement::InlineHtml(&b"<body>"[..])),
+                Spanned::from(Statement::Block(vec![])),
+                Spanned::from(Statement::InlineHtml(&b"</body>\n"[..]))
+            ]
+        );
+
+        assert_eq!(script(input), output);
+    }
+
+    #[test]
+    fn case_script_closing_tag_eats_one_newline_only() {
+        let input  = b"<?php ?>\n\nfoo";
+        let output = Result::Done(&b""[..], vec![Spanned::from(Statement::InlineHtml(&b"\nfoo"[..]))]);
+
+        assert_eq!(script(input), output);
+    }
+
+    #[test]
+    fn case_script_closing_tag_in_a_single_line_comment() {
+        let input  = b"<?php // foo ?>bar";
+        let output = Result::Done(&b""[..], vec![Spanned::from(Statement::InlineHtml(&b"bar"[..]))]);
+
+        assert_eq!(script(input), output);
+    }
+
+    #[test]
+    fn case_script_short_echo_tag() {
+        let input  = b"<title><?= $title ?></title>";
+        let output = Result::Done(
+            &b""[..],
+            vec![
+                Spanned::from(Statement::InlineHtml(&b"<title>"[..])),
+                Spanned::from(Statement::Echo(vec![Spanned::from(Expression::Variable(Variable::Name(&b"title"[..])))])),
+                Spanned::from(Statement::InlineHtml(&b"</title>"[..]))
+            ]
+        );
+
+        assert_eq!(script(input), output);
+    }
+
+    #[test]
+    fn case_script_short_echo_tag_with_many_expressions_and_statements() {
+        let input  = b"<?=$foo, 'bar'; {} ?>\n";
+        let output = Result::Done(
+            &b""[..],
+            vec![
+                Spanned::from(Statement::Echo(vec![
+                    Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..]))),
+                    Spanned::from(Expression::Literal(Literal::String(b"bar".to_vec())))
+                ])),
+                Spanned::from(Statement::Block(vec![]))
+            ]
+        );
+
+        assert_eq!(script(input), output);
+    }
+
+    #[test]
+    fn case_script_closing_tag_terminates_a_statement() {
+        let input  = b"<?php $foo = 42 ?>bar";
+        let output = Result::Done(
+            &b""[..],
+            vec![
+                Spanned::from(Statement::Expression(
+                    Spanned::from(Expression::Assignment {
+                        variable: Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..])))),
+                        value   : Box::new(Spanned::from(Expression::Literal(Literal::Integer(42))))
+                    })
+                )),
+                Spanned::from(Statement::InlineHtml(&b"bar"[..]))
+            ]
+        );
+
+        assert_eq!(script(input), output);
+    }
+
+    #[test]
+    fn case_script_halt_compiler() {
+        let input  = b"<p><?php {} __HALT_COMPILER ( ) ; <?php {} ?>\x00\xff";
+        let output = Result::Done(
+            &b""[..],
+            vec![
+                Spanned::from(Statement::InlineHtml(&b"<p>"[..])),
+                Spanned::from(Statement::Block(vec![])),
+                Spanned::from(Statement::HaltCompiler {
+                    offset: 33,
+                    data  : &b" <?php {} ?>\x00\xff"[..]
+                })
+            ]
+        );
+
+        assert_eq!(script(input), output);
+    }
+
+    #[test]
+    fn case_script_halt_compiler_terminated_by_a_closing_tag() {
+        let input  = b"<?php __halt_compiler() ?>\nfoo";
+        let output = Result::Done(
+            &b""[..],
+            vec![
+                Spanned::from(Statement::HaltCompiler {
+                    offset: 27,
+                    data  : &b"foo"[..]
+                })
+            ]
+        );
+
+        assert_eq!(script(input), output);
+    }
+
+    #[test]
+    fn case_script_halt_compiler_without_data() {
+        let input  = b"<?php __halt_compiler();";
+        let output = Result::Done(
+            &b""[..],
+            vec![
+                Spanned::from(Statement::HaltCompiler {
+                    offset: 24,
+                    data  : &b""[..]
+                })
+            ]
+        );
+
+        assert_eq!(script(input), output);
+    }
+
+    #[test]
+    fn case_invalid_script_halt_compiler_with_arguments() {
+        let input = b"<?php __halt_compiler(42);";
+
+        assert_eq!(script(input), Result::Error(Error::Position(ErrorKind::Tag, &b"__halt_compiler(42);"[..])));
+    }
+
+    #[test]
+    fn case_invalid_script_short_echo_tag_without_terminator() {
+        let input = b"<?= $foo $bar ?>";
+
+        assert_eq!(script(input), Result::Error(Error::Position(ErrorKind::Alt, &b"$bar ?>"[..])));
+    }
+
+    #[test]
+    fn case_invalid_script_short_echo_tag_without_closing_tag() {
+        let input = b"<?= $foo";
+
+        assert_eq!(script(input), Result::Error(Error::Position(ErrorKind::Alt, &b""[..])));
+    }
+
+    #[test]
+    fn case_invalid_script_unexpected_input() {
+        let input = b"<?php {} } ?>";
+
+        assert_eq!(script(input), Result::Error(Error::Position(ErrorKind::Tag, &b"} ?>"[..])));
+    }
+
+    #[test]
+    fn case_script_namespaces_without_body() {
+        let input  = b"<?php namespace A; $a; namespace B\\C; $b;";
+        let output = Result::Done(
+            &b""[..],
+            vec![
+                Spanned::from(Statement::Namespace {
+                    name: Some(Spanned::from(Name::Unqualified(&b"A"[..]))),
+                    body: None
+                }),
+                Spanned::from(Statement::Expression(Spanned::from(Expression::Variable(Variable::Name(&b"a"[..]))))),
+                Spanned::from(Statement::Namespace {
+                    name: Some(Spanned::from(Name::Qualified(vec![&b"B"[..], &b"C"[..]]))),
+                    body: None
+                }),
+                Spanned::from(Statement::Expression(Spanned::from(Expression::Variable(Variable::Name(&b"b"[..])))))
+            ]
+        );
+
+        assert_eq!(script(input), output);
+    }
+
+    #[test]
+    fn case_script_namespaces_with_body() {
+        let input  = b"<?php namespace A { $a; } namespace { $b; }";
+        let output = Result::Done(
+            &b""[..],
+            vec![
+                Spanned::from(Statement::Namespace {
+                    name: Some(Spanned::from(Name::Unqualified(&b"A"[..]))),
+                    body: Some(vec![Spanned::from(Statement::Expression(Spanned::from(Expression::Variable(Variable::Name(&b"a"[..])))))])
+                }),
+                Spanned::from(Statement::Namespace {
+                    name: None,
+                    body: Some(vec![Spanned::from(Statement::Expression(Spanned::from(Expression::Variable(Variable::Name(&b"b"[..])))))])
+                })
+            ]
+        );
+
+        assert_eq!(script(input), output);
+    }
+
+    #[test]
+    fn case_invalid_script_mixed_namespace_declarations() {
+        let input = b"<?php namespace A; namespace B { }";
+
+        assert_eq!(script(input), Result::Error(Error::Position(ErrorKind::Custom(ScriptError::MixedNamespaceDeclarations as u32), &input[19..])));
+    }
+
+    #[test]
+    fn case_invalid_script_statement_outside_namespace() {
+        let input = b"<?php namespace A { } $a;";
+
+        assert_eq!(script(input), Result::Error(Error::Position(ErrorKind::Custom(ScriptError::StatementOutsideNamespace as u32), &input[22..])));
+    }
+
+    #[test]
+    fn case_script_declare_before_namespace() {
+        let input  = b"<?php declare(strict_types=1); namespace A;";
+        let output = Result::Done(
+            &b""[..],
+            vec![
+                Spanned::from(Statement::Declare {
+                    directives: vec![DeclareDirective::StrictTypes(true)],
+                    body      : None
+                }),
+                Spanned::from(Statement::Namespace {
+                    name: Some(Spanned::from(Name::Unqualified(&b"A"[..]))),
+                    body: None
+                })
+            ]
+        );
+
+        assert_eq!(script(input), output);
+    }
+
+    #[test]
+    fn case_invalid_script_statement_before_namespace_without_body() {
+        let input = b"<?php $x; namespace A;";
+
+        assert_eq!(script(input), Result::Error(Error::Position(ErrorKind::Custom(ScriptError::StatementBeforeNamespace as u32), &input[6..])));
+    }
+
+    #[test]
+    fn case_invalid_script_statement_before_namespace_with_body() {
+        let input = b"<?php declare(ticks=1); $x; namespace A { }";
+
+        assert_eq!(script(input), Result::Error(Error::Position(ErrorKind::Custom(ScriptError::StatementBeforeNamespace as u32), &input[24..])));
+    }
+}
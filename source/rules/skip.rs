@@ -35,11 +35,27 @@
 //! required. For instance, whitespaces and comments can most of the time be
 //! skipped.
 
-use super::comments::comment;
+use super::comments::{
+    comment,
+    remember_doc_comment
+};
 use super::whitespaces::whitespace;
+use super::super::internal::Result;
+
+/// Skip the comments and the whitespaces. The last documentation comment is
+/// remembered for the declaration that follows.
+pub fn skip(input: &[u8]) -> Result<&[u8], Vec<&[u8]>> {
+    let output = skip_all(input);
+
+    if let Result::Done(rest, ref skipped) = output {
+        remember_doc_comment(input, skipped, rest);
+    }
+
+    output
+}
 
 named!(
-    pub skip< Vec<&[u8]> >,
+    skip_all< Vec<&[u8]> >,
     many0!(
         alt!(
             comment
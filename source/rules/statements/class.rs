@@ -0,0 +1,2783 @@
+// Tagua VM
+//
+//
+// New BSD License
+//
+// Copyright © 2016-2016, Ivan Enderlin.
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//     * Redistributions of source code must retain the above copyright
+//       notice, this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright
+//       notice, this list of conditions and the following disclaimer in the
+//       documentation and/or other materials provided with the distribution.
+//     * Neither the name of the Hoa nor the names of its contributors may be
+//       used to endorse or promote products derived from this software without
+//       specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE
+// ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDERS AND CONTRIBUTORS BE
+// LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR
+// CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF
+// SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS
+// INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN
+// CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+
+//! Group of class, interface, trait and enumeration declaration rules.
+//!
+//! The class, interface and trait declarations are provided by the PHP
+//! Language Specification in the [Grammar chapter, Classes
+//! section](https://github.com/php/php-langspec/blob/master/spec/19-grammar.md#classes),
+//! [Interfaces
+//! section](https://github.com/php/php-langspec/blob/master/spec/19-grammar.md#interfaces)
+//! and [Traits
+//! section](https://github.com/php/php-langspec/blob/master/spec/19-grammar.md#traits).
+//! Enumerations are introduced by PHP 8.1 and are not part of the
+//! specification yet.
+
+use std::result::Result as StdResult;
+use super::compound::block;
+use super::super::attributes::attributes;
+use super::super::comments::doc_comment;
+use super::super::hooks::property_hooks;
+use super::super::expressions::constants::constant_expression;
+use super::super::expressions::primaries::{
+    is_promoted,
+    is_set_visibility_compatible,
+    method_parameters,
+    qualified_names
+};
+use super::super::tokens::{
+    member_name,
+    name,
+    qualified_name,
+    variable
+};
+use super::super::types::{
+    return_type,
+    type_declaration
+};
+use super::super::super::ast::{
+    Attribute,
+    ClassMember,
+    ConstantExpression,
+    METHOD_ABSTRACT,
+    METHOD_FINAL,
+    METHOD_PRIVATE,
+    METHOD_PROTECTED,
+    METHOD_PUBLIC,
+    METHOD_STATIC,
+    Method,
+    Name,
+    Parameter,
+    PropertyHook,
+    Statement,
+    TraitAdaptation,
+    Type,
+    Variable,
+    Visibility
+};
+use super::super::super::internal::{
+    fold_into_vector,
+    into_vector_mapper
+};
+use super::super::super::span::Spanned;
+use super::super::super::tokens;
+use super::super::super::version::{
+    self,
+    Version
+};
+
+named!(
+    pub class_declaration<Statement>,
+    map_res!(
+        chain!(
+            doc_comment: doc_comment ~
+            attributes: attributes ~
+            modifiers: fold_many0!(
+                first!(class_modifier),
+                Vec::new(),
+                fold_into_vector
+            ) ~
+            first!(keyword!(tokens::CLASS)) ~
+            name: first!(class_name) ~
+            extends: opt!(
+                preceded!(
+                    first!(keyword!(tokens::EXTENDS)),
+                    first!(spanned!(qualified_name))
+                )
+            ) ~
+            implements: opt!(
+                preceded!(
+                    first!(keyword!(tokens::IMPLEMENTS)),
+                    first!(qualified_names)
+                )
+            ) ~
+            members: first!(class_body),
+            || { (doc_comment, attributes, modifiers, name, extends, implements, members) }
+        ),
+        class_declaration_mapper
+    )
+);
+
+named!(
+    class_modifier,
+    alt!(
+        keyword!(tokens::ABSTRACT)
+      | keyword!(tokens::FINAL)
+      | keyword!(tokens::READONLY)
+    )
+);
+
+#[inline(always)]
+fn class_declaration_mapper<'a>(
+    (doc_comment, attributes, modifiers, name, extends, implements, members): (Option<Vec<u8>>, Vec<Attribute<'a>>, Vec<&[u8]>, &'a [u8], Option<Spanned<Name<'a>>>, Option<Vec<Spanned<Name<'a>>>>, Vec<Spanned<ClassMember<'a>>>)
+) -> StdResult<Statement<'a>, ()> {
+    let modifiers = modifiers_mapper(modifiers)?;
+
+    if modifiers.visibility.is_some() || modifiers.set_visibility.is_some() || modifiers.is_static || modifiers.is_var || (modifiers.is_abstract && modifiers.is_final) {
+        return Err(());
+    }
+
+    if modifiers.is_readonly && (!version::is_targeting(Version::Php82) || !members.iter().all(|member| is_readonly_compatible(member))) {
+        return Err(());
+    }
+
+    let members = if modifiers.is_readonly {
+        members.into_iter().map(|member| member.map(readonly_member_mapper)).collect()
+    } else {
+        members
+    };
+
+    Ok(
+        Statement::Class {
+            doc_comment: doc_comment,
+            attributes : attributes,
+            is_abstract: modifiers.is_abstract,
+            is_final   : modifiers.is_final,
+            is_readonly: modifiers.is_readonly,
+            name       : name,
+            extends    : extends,
+            implements : implements.unwrap_or(vec![]),
+            members    : members
+        }
+    )
+}
+
+/// The properties of a readonly class are implicitly readonly, thus they
+/// must be typed, and cannot be static nor hooked. The same goes for the
+/// promoted parameters of its constructor.
+#[inline(always)]
+fn is_readonly_compatible(member: &ClassMember) -> bool {
+    match *member {
+        ClassMember::Property { is_static, ref ty, ref hooks, .. } => !is_static && ty.is_some() && hooks.is_empty(),
+
+        ClassMember::Method(ref method) => {
+            method
+                .parameters
+                .iter()
+                .all(|parameter| !is_promoted(parameter) || parameter.ty.is_some())
+        },
+
+        _ => true
+    }
+}
+
+/// Mark the properties of a readonly class, and the promoted parameters of
+/// its constructor, as readonly.
+#[inline(always)]
+fn readonly_member_mapper(mut member: ClassMember) -> ClassMember {
+    match member {
+        ClassMember::Property { ref mut is_readonly, .. } => {
+            *is_readonly = true;
+        },
+
+        ClassMember::Method(ref mut method) => {
+            for parameter in method.parameters.iter_mut() {
+                if is_promoted(parameter) {
+                    parameter.is_readonly = true;
+                }
+            }
+        },
+
+        _ => ()
+    }
+
+    member
+}
+
+named!(
+    class_name<&[u8]>,
+    map_res!(
+        qualified_name,
+        class_name_mapper
+    )
+);
+
+#[inline(always)]
+fn class_name_mapper(name: Name) -> StdResult<&[u8], ()> {
+    match name {
+        Name::Unqualified(name) => Ok(name),
+        _ => Err(())
+    }
+}
+
+named!(
+    pub class_body< Vec<Spanned<ClassMember>> >,
+    preceded!(
+        tag!(tokens::LEFT_CURLY_BRACKET),
+        terminated!(
+            fold_many0!(
+                complete!(first!(spanned!(class_body_member))),
+                Vec::new(),
+                fold_into_vector
+            ),
+            first!(expect!("class_body", tokens::RIGHT_CURLY_BRACKET))
+        )
+    )
+);
+
+named!(
+    class_body_member<ClassMember>,
+    map_res!(
+        class_member,
+        class_body_member_mapper
+    )
+);
+
+/// Outside an interface, a method without a body must be abstract.
+#[inline(always)]
+fn class_body_member_mapper(member: ClassMember) -> StdResult<ClassMember, ()> {
+    match member {
+        ClassMember::Method(Method { modifiers, body: None, .. }) if !modifiers.is_abstract() => {
+            Err(())
+        },
+
+        _ => {
+            Ok(member)
+        }
+    }
+}
+
+named!(
+    pub interface_declaration<Statement>,
+    chain!(
+        doc_comment: doc_comment ~
+        attributes: attributes ~
+        first!(keyword!(tokens::INTERFACE)) ~
+        name: first!(class_name) ~
+        extends: opt!(
+            preceded!(
+                first!(keyword!(tokens::EXTENDS)),
+                first!(qualified_names)
+            )
+        ) ~
+        members: first!(interface_body),
+        || {
+            Statement::Interface {
+                doc_comment: doc_comment,
+                attributes : attributes,
+                name       : name,
+                extends    : extends.unwrap_or(vec![]),
+                members    : members
+            }
+        }
+    )
+);
+
+named!(
+    interface_body< Vec<Spanned<ClassMember>> >,
+    preceded!(
+        tag!(tokens::LEFT_CURLY_BRACKET),
+        terminated!(
+            fold_many0!(
+                complete!(first!(spanned!(interface_member))),
+                Vec::new(),
+                fold_into_vector
+            ),
+            first!(expect!("interface_body", tokens::RIGHT_CURLY_BRACKET))
+        )
+    )
+);
+
+named!(
+    interface_member<ClassMember>,
+    map_res!(
+        class_member,
+        interface_member_mapper
+    )
+);
+
+/// An interface only declares public constants and public methods without
+/// a body.
+#[inline(always)]
+fn interface_member_mapper(member: ClassMember) -> StdResult<ClassMember, ()> {
+    match member {
+        ClassMember::Constant { visibility: Visibility::Public, .. } => {
+            Ok(member)
+        },
+
+        ClassMember::Method(Method { modifiers, body: None, .. }) if modifiers.visibility() == Visibility::Public && !modifiers.is_abstract() && !modifiers.is_final() => {
+            Ok(member)
+        },
+
+        _ => {
+            Err(())
+        }
+    }
+}
+
+named!(
+    pub trait_declaration<Statement>,
+    chain!(
+        doc_comment: doc_comment ~
+        attributes: attributes ~
+        first!(keyword!(tokens::TRAIT)) ~
+        name: first!(class_name) ~
+        members: first!(class_body),
+        || {
+            Statement::Trait {
+                doc_comment: doc_comment,
+                attributes : attributes,
+                name       : name,
+                members    : members
+            }
+        }
+    )
+);
+
+named!(
+    pub enum_declaration<Statement>,
+    cond_reduce!(
+        version::is_targeting(Version::Php81),
+        map_res!(
+            chain!(
+                doc_comment: doc_comment ~
+                attributes: attributes ~
+                first!(keyword!(tokens::ENUM)) ~
+                name: first!(class_name) ~
+                backing_type: opt!(
+                    preceded!(
+                        first!(tag!(tokens::ENUM_BACKING_TYPE)),
+                        first!(spanned!(type_declaration))
+                    )
+                ) ~
+                implements: opt!(
+                    preceded!(
+                        first!(keyword!(tokens::IMPLEMENTS)),
+                        first!(qualified_names)
+                    )
+                ) ~
+                members: first!(enum_body),
+                || { (doc_comment, attributes, name, backing_type, implements, members) }
+            ),
+            enum_declaration_mapper
+        )
+    )
+);
+
+/// A backed enumeration is backed by `int` or `string`, and all its cases
+/// have a value; the cases of a pure enumeration have no value.
+#[inline(always)]
+fn enum_declaration_mapper<'a>(
+    (doc_comment, attributes, name, backing_type, implements, members): (Option<Vec<u8>>, Vec<Attribute<'a>>, &'a [u8], Option<Spanned<Type<'a>>>, Option<Vec<Spanned<Name<'a>>>>, Vec<Spanned<ClassMember<'a>>>)
+) -> StdResult<Statement<'a>, ()> {
+    match backing_type {
+        None | Some(Spanned { node: Type::Int, .. }) | Some(Spanned { node: Type::String, .. }) => (),
+        _ => return Err(())
+    }
+
+    for member in &members {
+        if let ClassMember::Case { ref value, .. } = member.node {
+            if value.is_some() != backing_type.is_some() {
+                return Err(());
+            }
+        }
+    }
+
+    Ok(
+        Statement::Enum {
+            doc_comment : doc_comment,
+            attributes  : attributes,
+            name        : name,
+            backing_type: backing_type,
+            implements  : implements.unwrap_or(vec![]),
+            members     : members
+        }
+    )
+}
+
+named!(
+    enum_body< Vec<Spanned<ClassMember>> >,
+    preceded!(
+        tag!(tokens::LEFT_CURLY_BRACKET),
+        terminated!(
+            fold_many0!(
+                complete!(first!(spanned!(enum_member))),
+                Vec::new(),
+                fold_into_vector
+            ),
+            first!(expect!("enum_body", tokens::RIGHT_CURLY_BRACKET))
+        )
+    )
+);
+
+named!(
+    enum_member<ClassMember>,
+    alt!(
+        enum_case
+      | map_res!(
+            class_member,
+            enum_member_mapper
+        )
+    )
+);
+
+named!(
+    enum_case<ClassMember>,
+    chain!(
+        attributes: attributes ~
+        first!(keyword!(tokens::CASE)) ~
+        name: first!(member_name) ~
+        value: opt!(
+            preceded!(
+                first!(tag!(tokens::ASSIGN)),
+                first!(constant_expression)
+            )
+        ) ~
+        first!(tag!(tokens::SEMICOLON)),
+        || {
+            ClassMember::Case {
+                attributes: attributes,
+                name      : name,
+                value     : value
+            }
+        }
+    )
+);
+
+/// An enumeration has no state, thus no property. It cannot be extended,
+/// thus all its methods have a body.
+#[inline(always)]
+fn enum_member_mapper(member: ClassMember) -> StdResult<ClassMember, ()> {
+    match member {
+        ClassMember::Property { .. } => {
+            Err(())
+        },
+
+        ClassMember::Method(Method { body: None, .. }) => {
+            Err(())
+        },
+
+        _ => {
+            Ok(member)
+        }
+    }
+}
+
+named!(
+    pub class_member<ClassMember>,
+    alt!(
+        trait_use
+      | map_res!(
+            chain!(
+                doc_comment: doc_comment ~
+                attributes: attributes ~
+                modifiers: fold_many0!(
+                    first!(member_modifier),
+                    Vec::new(),
+                    fold_into_vector
+                ) ~
+                member: first!(
+                    alt!(
+                        class_constant
+                      | method
+                      | property
+                    )
+                ),
+                || { (doc_comment, attributes, modifiers, member) }
+            ),
+            class_member_mapper
+        )
+    )
+);
+
+named!(
+    member_modifier,
+    alt!(
+        keyword!(tokens::PUBLIC_SET) => { |_| tokens::PUBLIC_SET }
+      | keyword!(tokens::PROTECTED_SET) => { |_| tokens::PROTECTED_SET }
+      | keyword!(tokens::PRIVATE_SET) => { |_| tokens::PRIVATE_SET }
+      | map_res!(
+            name,
+            member_modifier_mapper
+        )
+    )
+);
+
+/// The modifier is recognized as a whole name so that a property type like
+/// `Finality` is not split.
+#[inline(always)]
+fn member_modifier_mapper(modifier: &[u8]) -> StdResult<&'static [u8], ()> {
+    let modifiers = [
+        tokens::ABSTRACT,
+        tokens::FINAL,
+        tokens::PRIVATE,
+        tokens::PROTECTED,
+        tokens::PUBLIC,
+        tokens::READONLY,
+        tokens::STATIC,
+        tokens::VAR
+    ];
+
+    match modifiers.iter().find(|candidate| modifier.eq_ignore_ascii_case(candidate)) {
+        Some(candidate) => Ok(candidate),
+        None => Err(())
+    }
+}
+
+/// Modifiers of a declaration, before being validated against the kind of
+/// the declaration.
+struct Modifiers {
+    visibility: Option<Visibility>,
+    set_visibility: Option<Visibility>,
+    is_static: bool,
+    is_abstract: bool,
+    is_final: bool,
+    is_readonly: bool,
+    is_var: bool
+}
+
+/// Collect modifiers. A modifier cannot be repeated, and only one
+/// visibility and one write visibility can be declared.
+fn modifiers_mapper(modifiers: Vec<&[u8]>) -> StdResult<Modifiers, ()> {
+    let mut output = Modifiers {
+        visibility    : None,
+        set_visibility: None,
+        is_static     : false,
+        is_abstract   : false,
+        is_final      : false,
+        is_readonly   : false,
+        is_var        : false
+    };
+
+    for modifier in modifiers {
+        let flag = if modifier == tokens::STATIC {
+            &mut output.is_static
+        } else if modifier == tokens::ABSTRACT {
+            &mut output.is_abstract
+        } else if modifier == tokens::FINAL {
+            &mut output.is_final
+        } else if modifier == tokens::READONLY {
+            &mut output.is_readonly
+        } else if modifier == tokens::VAR {
+            &mut output.is_var
+        } else if modifier == tokens::PUBLIC_SET || modifier == tokens::PROTECTED_SET || modifier == tokens::PRIVATE_SET {
+            if output.set_visibility.is_some() {
+                return Err(());
+            }
+
+            output.set_visibility = Some(
+                if modifier == tokens::PUBLIC_SET {
+                    Visibility::Public
+                } else if modifier == tokens::PROTECTED_SET {
+                    Visibility::Protected
+                } else {
+                    Visibility::Private
+                }
+            );
+
+            continue;
+        } else {
+            if output.visibility.is_some() {
+                return Err(());
+            }
+
+            output.visibility = Some(
+                if modifier == tokens::PUBLIC {
+                    Visibility::Public
+                } else if modifier == tokens::PROTECTED {
+                    Visibility::Protected
+                } else {
+                    Visibility::Private
+                }
+            );
+
+            continue;
+        };
+
+        if *flag {
+            return Err(());
+        }
+
+        *flag = true;
+    }
+
+    Ok(output)
+}
+
+#[inline(always)]
+fn class_member_mapper<'a>((doc_comment, attributes, modifiers, member): (Option<Vec<u8>>, Vec<Attribute<'a>>, Vec<&[u8]>, ClassMember<'a>)) -> StdResult<ClassMember<'a>, ()> {
+    let modifiers = modifiers_mapper(modifiers)?;
+    let has_visibility = modifiers.visibility.is_some();
+    let visibility     = modifiers.visibility.unwrap_or(Visibility::Public);
+
+    match member {
+        ClassMember::Constant { ty, constants, .. } => {
+            if modifiers.set_visibility.is_some() || modifiers.is_static || modifiers.is_abstract || modifiers.is_readonly || modifiers.is_var {
+                return Err(());
+            }
+
+            if modifiers.is_final && (!version::is_targeting(Version::Php81) || visibility == Visibility::Private) {
+                return Err(());
+            }
+
+            match ty {
+                Some(Spanned { node: Type::Callable, .. }) | Some(Spanned { node: Type::Void, .. }) | Some(Spanned { node: Type::Never, .. }) => {
+                    return Err(());
+                },
+
+                Some(_) if !version::is_targeting(Version::Php83) => {
+                    return Err(());
+                },
+
+                _ => {}
+            }
+
+            Ok(
+                ClassMember::Constant {
+                    doc_comment: doc_comment,
+                    attributes : attributes,
+                    visibility : visibility,
+                    is_final   : modifiers.is_final,
+                    ty         : ty,
+                    constants  : constants
+                }
+            )
+        },
+
+        ClassMember::Method(method) => {
+            if modifiers.set_visibility.is_some() || modifiers.is_readonly || modifiers.is_var {
+                return Err(());
+            }
+
+            if modifiers.is_abstract && (modifiers.is_final || visibility == Visibility::Private || method.body.is_some()) {
+                return Err(());
+            }
+
+            let mut method_modifiers = match visibility {
+                Visibility::Public    => METHOD_PUBLIC,
+                Visibility::Protected => METHOD_PROTECTED,
+                Visibility::Private   => METHOD_PRIVATE
+            };
+
+            if modifiers.is_static {
+                method_modifiers.insert(METHOD_STATIC);
+            }
+
+            if modifiers.is_abstract {
+                method_modifiers.insert(METHOD_ABSTRACT);
+            }
+
+            if modifiers.is_final {
+                method_modifiers.insert(METHOD_FINAL);
+            }
+
+            Ok(
+                ClassMember::Method(
+                    Method {
+                        doc_comment: doc_comment,
+                        attributes : attributes,
+                        modifiers  : method_modifiers,
+                        .. method
+                    }
+                )
+            )
+        },
+
+        ClassMember::Property { ty, properties, hooks, .. } => {
+            let has_modifier = has_visibility || modifiers.set_visibility.is_some() || modifiers.is_static || modifiers.is_readonly || modifiers.is_var;
+
+            if !has_modifier || modifiers.is_abstract || modifiers.is_final || (modifiers.is_var && (has_visibility || modifiers.set_visibility.is_some() || modifiers.is_static || modifiers.is_readonly)) {
+                return Err(());
+            }
+
+            if let Some(ref set_visibility) = modifiers.set_visibility {
+                if !version::is_targeting(Version::Php84) || modifiers.is_static || ty.is_none() || !is_set_visibility_compatible(&visibility, set_visibility) {
+                    return Err(());
+                }
+            }
+
+            if modifiers.is_readonly && (!version::is_targeting(Version::Php81) || modifiers.is_static || ty.is_none() || properties.iter().any(|(_, value)| value.is_some())) {
+                return Err(());
+            }
+
+            if !hooks.is_empty() && (modifiers.is_static || modifiers.is_readonly) {
+                return Err(());
+            }
+
+            Ok(
+                ClassMember::Property {
+                    doc_comment   : doc_comment,
+                    attributes    : attributes,
+                    visibility    : visibility,
+                    set_visibility: modifiers.set_visibility,
+                    is_static     : modifiers.is_static,
+                    is_readonly   : modifiers.is_readonly,
+                    ty            : ty,
+                    properties    : properties,
+                    hooks         : hooks
+                }
+            )
+        },
+
+        ClassMember::Case { .. } | ClassMember::TraitUse { .. } => {
+            Err(())
+        }
+    }
+}
+
+named!(
+    class_constant<ClassMember>,
+    chain!(
+        keyword!(tokens::CONST) ~
+        ty: opt!(
+            terminated!(
+                first!(spanned!(type_declaration)),
+                peek!(first!(member_name))
+            )
+        ) ~
+        accumulator: map_res!(
+            first!(class_constant_definition),
+            into_vector_mapper
+        ) ~
+        constants: fold_many0!(
+            preceded!(
+                first!(tag!(tokens::COMMA)),
+                first!(class_constant_definition)
+            ),
+            accumulator,
+            fold_into_vector
+        ) ~
+        first!(tag!(tokens::SEMICOLON)),
+        || {
+            ClassMember::Constant {
+                doc_comment: None,
+                attributes : vec![],
+                visibility : Visibility::Public,
+                is_final   : false,
+                ty         : ty,
+                constants  : constants
+            }
+        }
+    )
+);
+
+named!(
+    class_constant_definition<(&[u8], ConstantExpression)>,
+    chain!(
+        name: member_name ~
+        first!(tag!(tokens::ASSIGN)) ~
+        value: first!(constant_expression),
+        || { (name, value) }
+    )
+);
+
+named!(
+    method<ClassMember>,
+    map_res!(
+        chain!(
+            keyword!(tokens::FUNCTION) ~
+            returns_reference: opt!(first!(tag!(tokens::REFERENCE))) ~
+            name: first!(member_name) ~
+            parameters: first!(method_parameters) ~
+            return_type: opt!(first!(spanned!(return_type))) ~
+            body: first!(
+                alt!(
+                    block => { Some }
+                  | tag!(tokens::SEMICOLON) => { |_| None }
+                )
+            ),
+            || { (returns_reference.is_some(), name, parameters, return_type, body) }
+        ),
+        method_mapper
+    )
+);
+
+/// Only the parameters of a non-abstract constructor can be promoted to
+/// properties.
+#[inline(always)]
+fn method_mapper<'a>(
+    (returns_reference, name, parameters, return_type, body): (bool, &'a [u8], Vec<Spanned<Parameter<'a>>>, Option<Spanned<Type<'a>>>, Option<Vec<Spanned<Statement<'a>>>>)
+) -> StdResult<ClassMember<'a>, ()> {
+    let is_promoting = parameters.iter().any(|parameter| is_promoted(parameter));
+
+    if is_promoting && (!name.eq_ignore_ascii_case(b"__construct") || body.is_none()) {
+        return Err(());
+    }
+
+    Ok(
+        ClassMember::Method(
+            Method {
+                doc_comment      : None,
+                attributes       : vec![],
+                modifiers        : METHOD_PUBLIC,
+                returns_reference: returns_reference,
+                name             : name,
+                parameters       : parameters,
+                return_type      : return_type,
+                body             : body
+            }
+        )
+    )
+}
+
+named!(
+    property<ClassMember>,
+    chain!(
+        ty: opt!(spanned!(property_type)) ~
+        definitions: first!(
+            alt!(
+                hooked_property_definition
+              | property_definitions
+            )
+        ),
+        || {
+            let (properties, hooks) = definitions;
+
+            ClassMember::Property {
+                doc_comment   : None,
+                attributes    : vec![],
+                visibility    : Visibility::Public,
+                set_visibility: None,
+                is_static     : false,
+                is_readonly   : false,
+                ty            : ty,
+                properties    : properties,
+                hooks         : hooks
+            }
+        }
+    )
+);
+
+named!(
+    hooked_property_definition<(Vec<(Variable, Option<ConstantExpression>)>, Vec<PropertyHook>)>,
+    chain!(
+        property: property_definition ~
+        hooks: first!(property_hooks),
+        || { (vec![property], hooks) }
+    )
+);
+
+named!(
+    property_definitions<(Vec<(Variable, Option<ConstantExpression>)>, Vec<PropertyHook>)>,
+    chain!(
+        accumulator: map_res!(
+            property_definition,
+            into_vector_mapper
+        ) ~
+        properties: fold_many0!(
+            preceded!(
+                first!(tag!(tokens::COMMA)),
+                first!(property_definition)
+            ),
+            accumulator,
+            fold_into_vector
+        ) ~
+        first!(tag!(tokens::SEMICOLON)),
+        || { (properties, vec![]) }
+    )
+);
+
+named!(
+    property_type<Type>,
+    map_res!(
+        type_declaration,
+        property_type_mapper
+    )
+);
+
+/// A property cannot be typed as `callable`, `void` or `never`.
+#[inline(always)]
+fn property_type_mapper(ty: Type) -> StdResult<Type, ()> {
+    match ty {
+        Type::Callable | Type::Void | Type::Never => Err(()),
+        _ => Ok(ty)
+    }
+}
+
+named!(
+    property_definition<(Variable, Option<ConstantExpression>)>,
+    chain!(
+        name: variable ~
+        value: opt!(
+            preceded!(
+                first!(tag!(tokens::ASSIGN)),
+                first!(constant_expression)
+            )
+        ),
+        || { (name, value) }
+    )
+);
+
+named!(
+    trait_use<ClassMember>,
+    chain!(
+        keyword!(tokens::USE) ~
+        traits: first!(qualified_names) ~
+        adaptations: first!(
+            alt!(
+                tag!(tokens::SEMICOLON) => { |_| vec![] }
+              | trait_adaptations
+            )
+        ),
+        || {
+            ClassMember::TraitUse {
+                traits     : traits,
+                adaptations: adaptations
+            }
+        }
+    )
+);
+
+named!(
+    trait_adaptations< Vec<TraitAdaptation> >,
+    preceded!(
+        tag!(tokens::LEFT_CURLY_BRACKET),
+        terminated!(
+            fold_many0!(
+                complete!(
+                    first!(
+                        alt!(
+                            trait_insteadof_adaptation
+                          | trait_alias_adaptation
+                        )
+                    )
+                ),
+                Vec::new(),
+                fold_into_vector
+            ),
+            first!(expect!("trait_adaptations", tokens::RIGHT_CURLY_BRACKET))
+        )
+    )
+);
+
+named!(
+    trait_insteadof_adaptation<TraitAdaptation>,
+    chain!(
+        trait_name: spanned!(qualified_name) ~
+        first!(tag!(tokens::STATIC_CALL)) ~
+        method: first!(member_name) ~
+        first!(keyword!(tokens::INSTEADOF)) ~
+        excluded: first!(qualified_names) ~
+        first!(tag!(tokens::SEMICOLON)),
+        || {
+            TraitAdaptation::Insteadof {
+                trait_name: trait_name,
+                method    : method,
+                excluded  : excluded
+            }
+        }
+    )
+);
+
+named!(
+    trait_alias_adaptation<TraitAdaptation>,
+    map_res!(
+        chain!(
+            method: alt!(
+                chain!(
+                    trait_name: spanned!(qualified_name) ~
+                    first!(tag!(tokens::STATIC_CALL)) ~
+                    method: first!(member_name),
+                    || { (Some(trait_name), method) }
+                )
+              | member_name => { |method| (None, method) }
+            ) ~
+            first!(keyword!(tokens::AS)) ~
+            modifier: first!(member_name) ~
+            alias: opt!(first!(member_name)) ~
+            first!(tag!(tokens::SEMICOLON)),
+            || { (method, modifier, alias) }
+        ),
+        trait_alias_adaptation_mapper
+    )
+);
+
+/// The name following `as` is either a visibility, optionally followed by
+/// an alias, or an alias alone. Visibilities are recognized as whole names
+/// so that an alias like `publicFoo` is not split.
+#[inline(always)]
+fn trait_alias_adaptation_mapper<'a>(
+    ((trait_name, method), modifier, alias): ((Option<Spanned<Name<'a>>>, &'a [u8]), &'a [u8], Option<&'a [u8]>)
+) -> StdResult<TraitAdaptation<'a>, ()> {
+    let visibility =
+        if modifier.eq_ignore_ascii_case(tokens::PUBLIC) {
+            Some(Visibility::Public)
+        } else if modifier.eq_ignore_ascii_case(tokens::PROTECTED) {
+            Some(Visibility::Protected)
+        } else if modifier.eq_ignore_ascii_case(tokens::PRIVATE) {
+            Some(Visibility::Private)
+        } else {
+            None
+        };
+
+    let alias = match (visibility.is_some(), alias) {
+        (true, alias)    => alias,
+        (false, None)    => Some(modifier),
+        (false, Some(_)) => return Err(())
+    };
+
+    Ok(
+        TraitAdaptation::Alias {
+            trait_name: trait_name,
+            method    : method,
+            visibility: visibility,
+            alias     : alias
+        }
+    )
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        class_declaration,
+        class_member,
+        enum_declaration,
+        interface_declaration,
+        trait_declaration
+    };
+    use super::super::statement;
+    use super::super::super::super::ast::{
+        Argument,
+        Attribute,
+        ClassMember,
+        ConstantExpression,
+        Expression,
+        Literal,
+        METHOD_ABSTRACT,
+        METHOD_FINAL,
+        METHOD_PRIVATE,
+        METHOD_PROTECTED,
+        METHOD_PUBLIC,
+        METHOD_STATIC,
+        Method,
+        Name,
+        Parameter,
+        PropertyHook,
+        PropertyHookBody,
+        PropertyHookKind,
+        RelativeScope,
+        Statement,
+        TraitAdaptation,
+        Type,
+        Variable,
+        Visibility
+    };
+    use super::super::super::super::internal::{
+        Error,
+        ErrorKind,
+        Result
+    };
+    use super::super::super::super::macros::ErrorKindCustom;
+    use super::super::super::super::span::Spanned;
+    use super::super::super::super::version::{
+        self,
+        Version
+    };
+
+    #[test]
+    fn case_class_empty() {
+        let input  = b"class Foo {}";
+        let output = Result::Done(
+            &b""[..],
+            Statement::Class {
+                doc_comment: None,
+                attributes : vec![],
+                is_abstract: false,
+                is_final   : false,
+                is_readonly: false,
+                name       : &b"Foo"[..],
+                extends    : None,
+                implements : vec![],
+                members    : vec![]
+            }
+        );
+
+        assert_eq!(class_declaration(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_class_with_modifiers_parent_and_interfaces() {
+        let input  = b"abstract READONLY class Foo extends \\Bar implements Baz, Qux\\Quux {}";
+        let output = Result::Done(
+            &b""[..],
+            Statement::Class {
+                doc_comment: None,
+                attributes : vec![],
+                is_abstract: true,
+                is_final   : false,
+                is_readonly: true,
+                name       : &b"Foo"[..],
+                extends    : Some(Spanned::from(Name::FullyQualified(vec![&b"Bar"[..]]))),
+                implements : vec![
+                    Spanned::from(Name::Unqualified(&b"Baz"[..])),
+                    Spanned::from(Name::Qualified(vec![&b"Qux"[..], &b"Quux"[..]]))
+                ],
+                members    : vec![]
+            }
+        );
+
+        assert_eq!(class_declaration(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_class_with_doc_comments() {
+        let input  = b"{\n    /**\n     * Foo.\n     */\n    final class Foo {\n        /** C. */\n        const C = 1;\n        /** @var int */\n        public $d;\n        /** F. */\n        public function f() {}\n    }\n}";
+        let output = Result::Done(
+            &b""[..],
+            Statement::Block(vec![
+                Spanned::from(Statement::Class {
+                    doc_comment: Some(b"/**\n     * Foo.\n     */".to_vec()),
+                    attributes : vec![],
+                    is_abstract: false,
+                    is_final   : true,
+                    is_readonly: false,
+                    name       : &b"Foo"[..],
+                    extends    : None,
+                    implements : vec![],
+                    members    : vec![
+                        Spanned::from(ClassMember::Constant {
+                            doc_comment: Some(b"/** C. */".to_vec()),
+                            attributes : vec![],
+                            visibility : Visibility::Public,
+                            is_final   : false,
+                            ty         : None,
+                            constants  : vec![(&b"C"[..], ConstantExpression(Spanned::from(Expression::Literal(Literal::Integer(1)))))]
+                        }),
+                        Spanned::from(ClassMember::Property {
+                            doc_comment   : Some(b"/** @var int */".to_vec()),
+                            attributes    : vec![],
+                            visibility    : Visibility::Public,
+                            set_visibility: None,
+                            is_static     : false,
+                            is_readonly   : false,
+                            ty            : None,
+                            properties    : vec![(Variable::Name(&b"d"[..]), None)],
+                            hooks         : vec![]
+                        }),
+                        Spanned::from(ClassMember::Method(
+                            Method {
+                                doc_comment      : Some(b"/** F. */".to_vec()),
+                                attributes       : vec![],
+                                modifiers        : METHOD_PUBLIC,
+                                returns_reference: false,
+                                name             : &b"f"[..],
+                                parameters       : vec![],
+                                return_type      : None,
+                                body             : Some(vec![])
+                            }
+                        ))
+                    ]
+                })
+            ])
+        );
+
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_interface_trait_and_enum_with_doc_comments() {
+        let input  = b"{ /** I. */ interface I {} /** T. */ trait T {} /** E. */ enum E {} }";
+        let output = Result::Done(
+            &b""[..],
+            Statement::Block(vec![
+                Spanned::from(Statement::Interface {
+                    doc_comment: Some(b"/** I. */".to_vec()),
+                    attributes : vec![],
+                    name       : &b"I"[..],
+                    extends    : vec![],
+                    members    : vec![]
+                }),
+                Spanned::from(Statement::Trait {
+                    doc_comment: Some(b"/** T. */".to_vec()),
+                    attributes : vec![],
+                    name       : &b"T"[..],
+                    members    : vec![]
+                }),
+                Spanned::from(Statement::Enum {
+                    doc_comment : Some(b"/** E. */".to_vec()),
+                    attributes  : vec![],
+                    name        : &b"E"[..],
+                    backing_type: None,
+                    implements  : vec![],
+                    members     : vec![]
+                })
+            ])
+        );
+
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_class_with_members() {
+        let input  = b"final class Foo { use A, B; const C = 1; private static $d = 2, $e; public function f($x): int { return $x; } }";
+        let output = Result::Done(
+            &b""[..],
+            Statement::Class {
+                doc_comment: None,
+                attributes : vec![],
+                is_abstract: false,
+                is_final   : true,
+                is_readonly: false,
+                name       : &b"Foo"[..],
+                extends    : None,
+                implements : vec![],
+                members    : vec![
+                    Spanned::from(ClassMember::TraitUse {
+                        traits     : vec![Spanned::from(Name::Unqualified(&b"A"[..])), Spanned::from(Name::Unqualified(&b"B"[..]))],
+                        adaptations: vec![]
+                    }),
+                    Spanned::from(ClassMember::Constant {
+                        doc_comment: None,
+                        attributes : vec![],
+                        visibility : Visibility::Public,
+                        is_final   : false,
+                        ty         : None,
+                        constants  : vec![(&b"C"[..], ConstantExpression(Spanned::from(Expression::Literal(Literal::Integer(1)))))]
+                    }),
+                    Spanned::from(ClassMember::Property {
+                        doc_comment   : None,
+                        attributes    : vec![],
+                        visibility    : Visibility::Private,
+                        set_visibility: None,
+                        is_static     : true,
+                        is_readonly   : false,
+                        ty            : None,
+                        properties    : vec![
+                            (Variable::Name(&b"d"[..]), Some(ConstantExpression(Spanned::from(Expression::Literal(Literal::Integer(2)))))),
+                            (Variable::Name(&b"e"[..]), None)
+                        ],
+                        hooks         : vec![]
+                    }),
+                    Spanned::from(ClassMember::Method(
+                        Method {
+                            doc_comment      : None,
+                            attributes       : vec![],
+                            modifiers        : METHOD_PUBLIC,
+                            returns_reference: false,
+                            name             : &b"f"[..],
+                            parameters       : vec![
+                                Spanned::from(Parameter {
+                                    attributes    : vec![],
+                                    visibility    : None,
+                                    set_visibility: None,
+                                    is_readonly   : false,
+                                    ty            : None,
+                                    is_reference  : false,
+                                    is_variadic   : false,
+                                    name          : Variable::Name(&b"x"[..]),
+                                    value         : None,
+                                    hooks         : vec![]
+                                })
+                            ],
+                            return_type      : Some(Spanned::from(Type::Int)),
+                            body             : Some(vec![Spanned::from(Statement::Return(Some(Spanned::from(Expression::Variable(Variable::Name(&b"x"[..]))))))])
+                        }
+                    ))
+                ]
+            }
+        );
+
+        assert_eq!(class_declaration(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_class_member_constants_with_visibility() {
+        let input  = b"protected const FOO = 1, LIST = 2;";
+        let output = Result::Done(
+            &b""[..],
+            ClassMember::Constant {
+                doc_comment: None,
+                attributes : vec![],
+                visibility : Visibility::Protected,
+                is_final   : false,
+                ty         : None,
+                constants  : vec![
+                    (&b"FOO"[..], ConstantExpression(Spanned::from(Expression::Literal(Literal::Integer(1))))),
+                    (&b"LIST"[..], ConstantExpression(Spanned::from(Expression::Literal(Literal::Integer(2)))))
+                ]
+            }
+        );
+
+        assert_eq!(class_member(input), output);
+    }
+
+    #[test]
+    fn case_class_member_typed_constants() {
+        let input  = b"final public const ?int FOO = 1, BAR = 2;";
+        let output = Result::Done(
+            &b""[..],
+            ClassMember::Constant {
+                doc_comment: None,
+                attributes : vec![],
+                visibility : Visibility::Public,
+                is_final   : true,
+                ty         : Some(Spanned::from(Type::Nullable(Box::new(Spanned::from(Type::Int))))),
+                constants  : vec![
+                    (&b"FOO"[..], ConstantExpression(Spanned::from(Expression::Literal(Literal::Integer(1))))),
+                    (&b"BAR"[..], ConstantExpression(Spanned::from(Expression::Literal(Literal::Integer(2)))))
+                ]
+            }
+        );
+
+        assert_eq!(class_member(input), output);
+    }
+
+    #[test]
+    fn case_class_member_constant_typed_with_a_name() {
+        let input  = b"const Suit DEFAULT = Suit::Hearts;";
+        let output = Result::Done(
+            &b""[..],
+            ClassMember::Constant {
+                doc_comment: None,
+                attributes : vec![],
+                visibility : Visibility::Public,
+                is_final   : false,
+                ty         : Some(Spanned::from(Type::Name(Name::Unqualified(&b"Suit"[..])))),
+                constants  : vec![
+                    (
+                        &b"DEFAULT"[..],
+                        ConstantExpression(
+                            Spanned::from(Expression::StaticFetch {
+                                class : Box::new(Spanned::from(Expression::Name(Name::Unqualified(&b"Suit"[..])))),
+                                member: Box::new(Spanned::from(Expression::Name(Name::Unqualified(&b"Hearts"[..]))))
+                            })
+                        )
+                    )
+                ]
+            }
+        );
+
+        assert_eq!(class_member(input), output);
+    }
+
+    #[test]
+    fn case_invalid_class_member_constant_typed_void() {
+        let input = b"const void FOO = 1;";
+
+        assert_eq!(class_member(input), Result::Error(Error::Position(ErrorKind::Alt, &input[..])));
+    }
+
+    #[test]
+    fn case_invalid_class_member_typed_constant_before_php_83() {
+        let input = b"const int FOO = 1;";
+
+        version::set_target(Version::Php82);
+        let output = class_member(input);
+        version::set_target(version::LATEST);
+
+        assert_eq!(output, Result::Error(Error::Position(ErrorKind::Alt, &input[..])));
+    }
+
+    #[test]
+    fn case_class_member_property_declared_with_var() {
+        let input  = b"var $foo;";
+        let output = Result::Done(
+            &b""[..],
+            ClassMember::Property {
+                doc_comment   : None,
+                attributes    : vec![],
+                visibility    : Visibility::Public,
+                set_visibility: None,
+                is_static     : false,
+                is_readonly   : false,
+                ty            : None,
+                properties    : vec![(Variable::Name(&b"foo"[..]), None)],
+                hooks         : vec![]
+            }
+        );
+
+        assert_eq!(class_member(input), output);
+    }
+
+    #[test]
+    fn case_class_member_typed_property() {
+        let input  = b"private ?Logger $logger;";
+        let output = Result::Done(
+            &b""[..],
+            ClassMember::Property {
+                doc_comment   : None,
+                attributes    : vec![],
+                visibility    : Visibility::Private,
+                set_visibility: None,
+                is_static     : false,
+                is_readonly   : false,
+                ty            : Some(Spanned::from(Type::Nullable(Box::new(Spanned::from(Type::Name(Name::Unqualified(&b"Logger"[..]))))))),
+                properties    : vec![(Variable::Name(&b"logger"[..]), None)],
+                hooks         : vec![]
+            }
+        );
+
+        assert_eq!(class_member(input), output);
+    }
+
+    #[test]
+    fn case_class_member_typed_property_with_a_default_value() {
+        let input  = b"public static int $count = 42;";
+        let output = Result::Done(
+            &b""[..],
+            ClassMember::Property {
+                doc_comment   : None,
+                attributes    : vec![],
+                visibility    : Visibility::Public,
+                set_visibility: None,
+                is_static     : true,
+                is_readonly   : false,
+                ty            : Some(Spanned::from(Type::Int)),
+                properties    : vec![(Variable::Name(&b"count"[..]), Some(ConstantExpression(Spanned::from(Expression::Literal(Literal::Integer(42))))))],
+                hooks         : vec![]
+            }
+        );
+
+        assert_eq!(class_member(input), output);
+    }
+
+    #[test]
+    fn case_class_member_typed_property_with_a_type_prefixed_by_a_modifier() {
+        let input  = b"public Finality $finality;";
+        let output = Result::Done(
+            &b""[..],
+            ClassMember::Property {
+                doc_comment   : None,
+                attributes    : vec![],
+                visibility    : Visibility::Public,
+                set_visibility: None,
+                is_static     : false,
+                is_readonly   : false,
+                ty            : Some(Spanned::from(Type::Name(Name::Unqualified(&b"Finality"[..])))),
+                properties    : vec![(Variable::Name(&b"finality"[..]), None)],
+                hooks         : vec![]
+            }
+        );
+
+        assert_eq!(class_member(input), output);
+    }
+
+    #[test]
+    fn case_class_member_hooked_property() {
+        let input  = b"public string $name = 'foo' { get => $x; }";
+        let output = Result::Done(
+            &b""[..],
+            ClassMember::Property {
+                doc_comment   : None,
+                attributes    : vec![],
+                visibility    : Visibility::Public,
+                set_visibility: None,
+                is_static     : false,
+                is_readonly   : false,
+                ty            : Some(Spanned::from(Type::String)),
+                properties    : vec![(Variable::Name(&b"name"[..]), Some(ConstantExpression(Spanned::from(Expression::Literal(Literal::String(b"foo".to_vec()))))))],
+                hooks         : vec![
+                    PropertyHook {
+                        attributes       : vec![],
+                        is_final         : false,
+                        returns_reference: false,
+                        kind             : PropertyHookKind::Get,
+                        parameters       : vec![],
+                        body             : PropertyHookBody::Expression(Spanned::from(Expression::Variable(Variable::Name(&b"x"[..]))))
+                    }
+                ]
+            }
+        );
+
+        assert_eq!(class_member(input), output);
+    }
+
+    #[test]
+    fn case_class_member_method_with_a_hooked_promoted_parameter() {
+        let input  = b"public function __construct(public string $name { set => $value; }) {}";
+        let output = Result::Done(
+            &b""[..],
+            ClassMember::Method(
+                Method {
+                    doc_comment      : None,
+                    attributes       : vec![],
+                    modifiers        : METHOD_PUBLIC,
+                    returns_reference: false,
+                    name             : &b"__construct"[..],
+                    parameters       : vec![
+                        Spanned::from(Parameter {
+                            attributes    : vec![],
+                            visibility    : Some(Visibility::Public),
+                            set_visibility: None,
+                            is_readonly   : false,
+                            ty            : Some(Spanned::from(Type::String)),
+                            is_reference  : false,
+                            is_variadic   : false,
+                            name          : Variable::Name(&b"name"[..]),
+                            value         : None,
+                            hooks         : vec![
+                                PropertyHook {
+                                    attributes       : vec![],
+                                    is_final         : false,
+                                    returns_reference: false,
+                                    kind             : PropertyHookKind::Set,
+                                    parameters       : vec![],
+                                    body             : PropertyHookBody::Expression(Spanned::from(Expression::Variable(Variable::Name(&b"value"[..]))))
+                                }
+                            ]
+                        })
+                    ],
+                    return_type      : None,
+                    body             : Some(vec![])
+                }
+            )
+        );
+
+        assert_eq!(class_member(input), output);
+    }
+
+    #[test]
+    fn case_invalid_class_member_many_hooked_properties() {
+        let input = b"public $a, $b { get => 42; }";
+
+        assert_eq!(class_member(input), Result::Error(Error::Position(ErrorKind::Alt, &input[..])));
+    }
+
+    #[test]
+    fn case_invalid_class_member_static_hooked_property() {
+        let input = b"public static int $x { get => 42; }";
+
+        assert_eq!(class_member(input), Result::Error(Error::Position(ErrorKind::Alt, &input[..])));
+    }
+
+    #[test]
+    fn case_invalid_class_member_readonly_hooked_property() {
+        let input = b"public readonly int $x { get => 42; }";
+
+        assert_eq!(class_member(input), Result::Error(Error::Position(ErrorKind::Alt, &input[..])));
+    }
+
+    #[test]
+    fn case_invalid_class_member_hooked_property_before_php_84() {
+        let input = b"public int $x { get => 42; }";
+
+        version::set_target(Version::Php83);
+        let output = class_member(input);
+        version::set_target(version::LATEST);
+
+        assert_eq!(output, Result::Error(Error::Position(ErrorKind::Alt, &input[..])));
+    }
+
+    #[test]
+    fn case_invalid_class_readonly_with_a_hooked_property() {
+        let input = b"readonly class Foo { public int $x { get => 42; } }";
+
+        assert_eq!(class_declaration(input), Result::Error(Error::Position(ErrorKind::MapRes, &input[..])));
+    }
+
+    #[test]
+    fn case_class_member_property_with_a_set_visibility() {
+        let input  = b"public private(set) int $x;";
+        let output = Result::Done(
+            &b""[..],
+            ClassMember::Property {
+                doc_comment   : None,
+                attributes    : vec![],
+                visibility    : Visibility::Public,
+                set_visibility: Some(Visibility::Private),
+                is_static     : false,
+                is_readonly   : false,
+                ty            : Some(Spanned::from(Type::Int)),
+                properties    : vec![(Variable::Name(&b"x"[..]), None)],
+                hooks         : vec![]
+            }
+        );
+
+        assert_eq!(class_member(input), output);
+    }
+
+    #[test]
+    fn case_class_member_property_with_a_set_visibility_only() {
+        let input  = b"PROTECTED(SET) readonly string $name;";
+        let output = Result::Done(
+            &b""[..],
+            ClassMember::Property {
+                doc_comment   : None,
+                attributes    : vec![],
+                visibility    : Visibility::Public,
+                set_visibility: Some(Visibility::Protected),
+                is_static     : false,
+                is_readonly   : true,
+                ty            : Some(Spanned::from(Type::String)),
+                properties    : vec![(Variable::Name(&b"name"[..]), None)],
+                hooks         : vec![]
+            }
+        );
+
+        assert_eq!(class_member(input), output);
+    }
+
+    #[test]
+    fn case_invalid_class_member_property_with_a_wider_set_visibility() {
+        let input = b"protected public(set) int $x;";
+
+        assert_eq!(class_member(input), Result::Error(Error::Position(ErrorKind::Alt, &input[..])));
+    }
+
+    #[test]
+    fn case_invalid_class_member_property_with_many_set_visibilities() {
+        let input = b"public private(set) protected(set) int $x;";
+
+        assert_eq!(class_member(input), Result::Error(Error::Position(ErrorKind::Alt, &input[..])));
+    }
+
+    #[test]
+    fn case_invalid_class_member_untyped_property_with_a_set_visibility() {
+        let input = b"public private(set) $x;";
+
+        assert_eq!(class_member(input), Result::Error(Error::Position(ErrorKind::Alt, &input[..])));
+    }
+
+    #[test]
+    fn case_invalid_class_member_static_property_with_a_set_visibility() {
+        let input = b"public private(set) static int $x;";
+
+        assert_eq!(class_member(input), Result::Error(Error::Position(ErrorKind::Alt, &input[..])));
+    }
+
+    #[test]
+    fn case_invalid_class_member_method_with_a_set_visibility() {
+        let input = b"private(set) function f() {}";
+
+        assert_eq!(class_member(input), Result::Error(Error::Position(ErrorKind::Alt, &input[..])));
+    }
+
+    #[test]
+    fn case_invalid_class_member_property_with_a_set_visibility_before_php_84() {
+        let input = b"public private(set) int $x;";
+
+        version::set_target(Version::Php83);
+        let output = class_member(input);
+        version::set_target(version::LATEST);
+
+        assert_eq!(output, Result::Error(Error::Position(ErrorKind::Alt, &input[..])));
+    }
+
+    #[test]
+    fn case_invalid_class_member_property_callable_type() {
+        let input = b"public callable $foo;";
+
+        assert_eq!(class_member(input), Result::Error(Error::Position(ErrorKind::Alt, &input[..])));
+    }
+
+    #[test]
+    fn case_invalid_class_member_property_void_type() {
+        let input = b"public void $foo;";
+
+        assert_eq!(class_member(input), Result::Error(Error::Position(ErrorKind::Alt, &input[..])));
+    }
+
+    #[test]
+    fn case_class_member_abstract_method() {
+        let input  = b"abstract protected static function list();";
+        let output = Result::Done(
+            &b""[..],
+            ClassMember::Method(
+                Method {
+                    doc_comment      : None,
+                    attributes       : vec![],
+                    modifiers        : METHOD_PROTECTED | METHOD_STATIC | METHOD_ABSTRACT,
+                    returns_reference: false,
+                    name             : &b"list"[..],
+                    parameters       : vec![],
+                    return_type      : None,
+                    body             : None
+                }
+            )
+        );
+
+        assert_eq!(class_member(input), output);
+    }
+
+    #[test]
+    fn case_class_member_method_without_modifiers() {
+        let input  = b"function f() {}";
+        let output = Result::Done(
+            &b""[..],
+            ClassMember::Method(
+                Method {
+                    doc_comment      : None,
+                    attributes       : vec![],
+                    modifiers        : METHOD_PUBLIC,
+                    returns_reference: false,
+                    name             : &b"f"[..],
+                    parameters       : vec![],
+                    return_type      : None,
+                    body             : Some(vec![])
+                }
+            )
+        );
+
+        assert_eq!(class_member(input), output);
+    }
+
+    #[test]
+    fn case_class_member_final_private_method() {
+        let input  = b"final private function f() {}";
+        let output = Result::Done(
+            &b""[..],
+            ClassMember::Method(
+                Method {
+                    doc_comment      : None,
+                    attributes       : vec![],
+                    modifiers        : METHOD_PRIVATE | METHOD_FINAL,
+                    returns_reference: false,
+                    name             : &b"f"[..],
+                    parameters       : vec![],
+                    return_type      : None,
+                    body             : Some(vec![])
+                }
+            )
+        );
+
+        assert_eq!(class_member(input), output);
+    }
+
+    #[test]
+    fn case_class_member_method_returning_a_reference() {
+        let input  = b"public function &getRef(): array {}";
+        let output = Result::Done(
+            &b""[..],
+            ClassMember::Method(
+                Method {
+                    doc_comment      : None,
+                    attributes       : vec![],
+                    modifiers        : METHOD_PUBLIC,
+                    returns_reference: true,
+                    name             : &b"getRef"[..],
+                    parameters       : vec![],
+                    return_type      : Some(Spanned::from(Type::Array)),
+                    body             : Some(vec![])
+                }
+            )
+        );
+
+        assert_eq!(class_member(input), output);
+    }
+
+    #[test]
+    fn case_invalid_class_member_abstract_method_with_a_body() {
+        let input = b"abstract public function f() {}";
+
+        assert_eq!(class_member(input), Result::Error(Error::Position(ErrorKind::Alt, &input[..])));
+    }
+
+    #[test]
+    fn case_invalid_class_member_private_abstract_method() {
+        let input = b"abstract private function f();";
+
+        assert_eq!(class_member(input), Result::Error(Error::Position(ErrorKind::Alt, &input[..])));
+    }
+
+    #[test]
+    fn case_invalid_class_member_abstract_final_method() {
+        let input = b"abstract final function f();";
+
+        assert_eq!(class_member(input), Result::Error(Error::Position(ErrorKind::Alt, &input[..])));
+    }
+
+    #[test]
+    fn case_invalid_class_method_without_a_body() {
+        let input = b"class Foo { public function f(); }";
+
+        assert_eq!(class_declaration(input), Result::Error(Error::Position(ErrorKind::Tag, &b"public function f(); }"[..])));
+        assert_eq!(statement(input), Result::Error(Error::Position(ErrorKind::Alt, &input[..])));
+    }
+
+    #[test]
+    fn case_invalid_class_abstract_and_final() {
+        let input = b"abstract final class Foo {}";
+
+        assert_eq!(class_declaration(input), Result::Error(Error::Position(ErrorKind::MapRes, &input[..])));
+    }
+
+    #[test]
+    fn case_invalid_class_name_is_a_keyword() {
+        let input = b"class List {}";
+
+        assert_eq!(class_declaration(input), Result::Error(Error::Position(ErrorKind::Custom(0), &b"List {}"[..])));
+    }
+
+    #[test]
+    fn case_invalid_class_missing_body() {
+        let input = b"class Foo;";
+
+        assert_eq!(class_declaration(input), Result::Error(Error::Position(ErrorKind::Tag, &b";"[..])));
+    }
+
+    #[test]
+    fn case_invalid_class_member_many_visibilities() {
+        let input = b"public private $foo;";
+
+        assert_eq!(class_member(input), Result::Error(Error::Position(ErrorKind::Alt, &input[..])));
+    }
+
+    #[test]
+    fn case_invalid_class_member_repeated_modifier() {
+        let input = b"static static function f() {}";
+
+        assert_eq!(class_member(input), Result::Error(Error::Position(ErrorKind::Alt, &input[..])));
+    }
+
+    #[test]
+    fn case_invalid_class_member_property_without_modifier() {
+        let input = b"$foo;";
+
+        assert_eq!(class_member(input), Result::Error(Error::Position(ErrorKind::Alt, &input[..])));
+    }
+
+    #[test]
+    fn case_invalid_class_member_static_constant() {
+        let input = b"static const FOO = 1;";
+
+        assert_eq!(class_member(input), Result::Error(Error::Position(ErrorKind::Alt, &input[..])));
+    }
+
+    #[test]
+    fn case_invalid_class_member_property_default_value_is_not_constant() {
+        let input = b"public $foo = $bar;";
+
+        assert_eq!(class_member(input), Result::Error(Error::Position(ErrorKind::Alt, &input[..])));
+    }
+
+    #[test]
+    fn case_interface_empty() {
+        let input  = b"interface Foo {}";
+        let output = Result::Done(
+            &b""[..],
+            Statement::Interface {
+                doc_comment: None,
+                attributes : vec![],
+                name       : &b"Foo"[..],
+                extends    : vec![],
+                members    : vec![]
+            }
+        );
+
+        assert_eq!(interface_declaration(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_interface_with_parents_and_members() {
+        let input  = b"interface Foo extends A, \\B { const X = 1; public static function m(): void; function n(); }";
+        let output = Result::Done(
+            &b""[..],
+            Statement::Interface {
+                doc_comment: None,
+                attributes : vec![],
+                name       : &b"Foo"[..],
+                extends    : vec![
+                    Spanned::from(Name::Unqualified(&b"A"[..])),
+                    Spanned::from(Name::FullyQualified(vec![&b"B"[..]]))
+                ],
+                members    : vec![
+                    Spanned::from(ClassMember::Constant {
+                        doc_comment: None,
+                        attributes : vec![],
+                        visibility : Visibility::Public,
+                        is_final   : false,
+                        ty         : None,
+                        constants  : vec![(&b"X"[..], ConstantExpression(Spanned::from(Expression::Literal(Literal::Integer(1)))))]
+                    }),
+                    Spanned::from(ClassMember::Method(
+                        Method {
+                            doc_comment      : None,
+                            attributes       : vec![],
+                            modifiers        : METHOD_PUBLIC | METHOD_STATIC,
+                            returns_reference: false,
+                            name             : &b"m"[..],
+                            parameters       : vec![],
+                            return_type      : Some(Spanned::from(Type::Void)),
+                            body             : None
+                        }
+                    )),
+                    Spanned::from(ClassMember::Method(
+                        Method {
+                            doc_comment      : None,
+                            attributes       : vec![],
+                            modifiers        : METHOD_PUBLIC,
+                            returns_reference: false,
+                            name             : &b"n"[..],
+                            parameters       : vec![],
+                            return_type      : None,
+                            body             : None
+                        }
+                    ))
+                ]
+            }
+        );
+
+        assert_eq!(interface_declaration(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_invalid_interface_method_with_a_body() {
+        let input = b"interface Foo { function m() {} }";
+
+        assert_eq!(interface_declaration(input), Result::Error(Error::Position(ErrorKind::Tag, &b"function m() {} }"[..])));
+    }
+
+    #[test]
+    fn case_invalid_interface_private_method() {
+        let input = b"interface Foo { private function m(); }";
+
+        assert_eq!(interface_declaration(input), Result::Error(Error::Position(ErrorKind::Tag, &b"private function m(); }"[..])));
+    }
+
+    #[test]
+    fn case_invalid_interface_property() {
+        let input = b"interface Foo { public $x; }";
+
+        assert_eq!(interface_declaration(input), Result::Error(Error::Position(ErrorKind::Tag, &b"public $x; }"[..])));
+    }
+
+    #[test]
+    fn case_invalid_interface_trait_use() {
+        let input = b"interface Foo { use T; }";
+
+        assert_eq!(interface_declaration(input), Result::Error(Error::Position(ErrorKind::Tag, &b"use T; }"[..])));
+    }
+
+    #[test]
+    fn case_trait_empty() {
+        let input  = b"trait Foo {}";
+        let output = Result::Done(
+            &b""[..],
+            Statement::Trait {
+                doc_comment: None,
+                attributes : vec![],
+                name       : &b"Foo"[..],
+                members    : vec![]
+            }
+        );
+
+        assert_eq!(trait_declaration(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_trait_with_members() {
+        let input  = b"trait Foo { use Bar; abstract public function f(); }";
+        let output = Result::Done(
+            &b""[..],
+            Statement::Trait {
+                doc_comment: None,
+                attributes : vec![],
+                name       : &b"Foo"[..],
+                members    : vec![
+                    Spanned::from(ClassMember::TraitUse {
+                        traits     : vec![Spanned::from(Name::Unqualified(&b"Bar"[..]))],
+                        adaptations: vec![]
+                    }),
+                    Spanned::from(ClassMember::Method(
+                        Method {
+                            doc_comment      : None,
+                            attributes       : vec![],
+                            modifiers        : METHOD_PUBLIC | METHOD_ABSTRACT,
+                            returns_reference: false,
+                            name             : &b"f"[..],
+                            parameters       : vec![],
+                            return_type      : None,
+                            body             : None
+                        }
+                    ))
+                ]
+            }
+        );
+
+        assert_eq!(trait_declaration(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_invalid_trait_with_modifier() {
+        let input = b"final trait Foo {}";
+
+        assert_eq!(trait_declaration(input), Result::Error(Error::Position(ErrorKind::Custom(ErrorKindCustom::ITag as u32), &input[..])));
+    }
+
+    #[test]
+    fn case_trait_use_with_empty_adaptations() {
+        let input  = b"use A {}";
+        let output = Result::Done(
+            &b""[..],
+            ClassMember::TraitUse {
+                traits     : vec![Spanned::from(Name::Unqualified(&b"A"[..]))],
+                adaptations: vec![]
+            }
+        );
+
+        assert_eq!(class_member(input), output);
+    }
+
+    #[test]
+    fn case_trait_use_with_adaptations() {
+        let input  = b"use A, B { A::foo insteadof B, C; B::bar as protected baz; qux as private; list as publicList; }";
+        let output = Result::Done(
+            &b""[..],
+            ClassMember::TraitUse {
+                traits     : vec![Spanned::from(Name::Unqualified(&b"A"[..])), Spanned::from(Name::Unqualified(&b"B"[..]))],
+                adaptations: vec![
+                    TraitAdaptation::Insteadof {
+                        trait_name: Spanned::from(Name::Unqualified(&b"A"[..])),
+                        method    : &b"foo"[..],
+                        excluded  : vec![Spanned::from(Name::Unqualified(&b"B"[..])), Spanned::from(Name::Unqualified(&b"C"[..]))]
+                    },
+                    TraitAdaptation::Alias {
+                        trait_name: Some(Spanned::from(Name::Unqualified(&b"B"[..]))),
+                        method    : &b"bar"[..],
+                        visibility: Some(Visibility::Protected),
+                        alias     : Some(&b"baz"[..])
+                    },
+                    TraitAdaptation::Alias {
+                        trait_name: None,
+                        method    : &b"qux"[..],
+                        visibility: Some(Visibility::Private),
+                        alias     : None
+                    },
+                    TraitAdaptation::Alias {
+                        trait_name: None,
+                        method    : &b"list"[..],
+                        visibility: None,
+                        alias     : Some(&b"publicList"[..])
+                    }
+                ]
+            }
+        );
+
+        assert_eq!(class_member(input), output);
+    }
+
+    #[test]
+    fn case_invalid_trait_use_insteadof_without_trait_name() {
+        let input = b"use A, B { foo insteadof B; }";
+
+        assert_eq!(class_member(input), Result::Error(Error::Position(ErrorKind::Alt, &input[..])));
+    }
+
+    #[test]
+    fn case_invalid_trait_use_alias_with_two_names() {
+        let input = b"use A { foo as bar baz; }";
+
+        assert_eq!(class_member(input), Result::Error(Error::Position(ErrorKind::Alt, &input[..])));
+    }
+
+    #[test]
+    fn case_enum_pure() {
+        let input  = b"enum Suit implements HasColor { case Hearts; case Spades; const Wild = self::Spades; public function color(): string { return 'Red'; } }";
+        let output = Result::Done(
+            &b""[..],
+            Statement::Enum {
+                doc_comment : None,
+                attributes  : vec![],
+                name        : &b"Suit"[..],
+                backing_type: None,
+                implements  : vec![Spanned::from(Name::Unqualified(&b"HasColor"[..]))],
+                members     : vec![
+                    Spanned::from(ClassMember::Case {
+                        attributes: vec![],
+                        name      : &b"Hearts"[..],
+                        value     : None
+                    }),
+                    Spanned::from(ClassMember::Case {
+                        attributes: vec![],
+                        name      : &b"Spades"[..],
+                        value     : None
+                    }),
+                    Spanned::from(ClassMember::Constant {
+                        doc_comment: None,
+                        attributes : vec![],
+                        visibility : Visibility::Public,
+                        is_final   : false,
+                        ty         : None,
+                        constants  : vec![
+                            (
+                                &b"Wild"[..],
+                                ConstantExpression(
+                                    Spanned::from(Expression::StaticFetch {
+                                        class : Box::new(Spanned::from(Expression::RelativeScope(RelativeScope::Current))),
+                                        member: Box::new(Spanned::from(Expression::Name(Name::Unqualified(&b"Spades"[..]))))
+                                    })
+                                )
+                            )
+                        ]
+                    }),
+                    Spanned::from(ClassMember::Method(
+                        Method {
+                            doc_comment      : None,
+                            attributes       : vec![],
+                            modifiers        : METHOD_PUBLIC,
+                            returns_reference: false,
+                            name             : &b"color"[..],
+                            parameters       : vec![],
+                            return_type      : Some(Spanned::from(Type::String)),
+                            body             : Some(vec![Spanned::from(Statement::Return(Some(Spanned::from(Expression::Literal(Literal::String(b"Red".to_vec()))))))])
+                        }
+                    ))
+                ]
+            }
+        );
+
+        assert_eq!(enum_declaration(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_enum_backed() {
+        let input  = b"enum Status: int { case Active = 1; case Inactive = 0; }";
+        let output = Result::Done(
+            &b""[..],
+            Statement::Enum {
+                doc_comment : None,
+                attributes  : vec![],
+                name        : &b"Status"[..],
+                backing_type: Some(Spanned::from(Type::Int)),
+                implements  : vec![],
+                members     : vec![
+                    Spanned::from(ClassMember::Case {
+                        attributes: vec![],
+                        name      : &b"Active"[..],
+                        value     : Some(ConstantExpression(Spanned::from(Expression::Literal(Literal::Integer(1)))))
+                    }),
+                    Spanned::from(ClassMember::Case {
+                        attributes: vec![],
+                        name      : &b"Inactive"[..],
+                        value     : Some(ConstantExpression(Spanned::from(Expression::Literal(Literal::Integer(0)))))
+                    })
+                ]
+            }
+        );
+
+        assert_eq!(enum_declaration(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_invalid_enum_before_php_81() {
+        let input = b"enum Suit {}";
+
+        version::set_target(Version::Php80);
+        let output = enum_declaration(input);
+        version::set_target(version::LATEST);
+
+        assert_eq!(output, Result::Error(Error::Position(ErrorKind::CondReduce, &input[..])));
+    }
+
+    #[test]
+    fn case_invalid_enum_backed_by_float() {
+        let input = b"enum Suit: float {}";
+
+        assert_eq!(enum_declaration(input), Result::Error(Error::Position(ErrorKind::MapRes, &input[..])));
+    }
+
+    #[test]
+    fn case_invalid_enum_pure_with_a_valued_case() {
+        let input = b"enum Suit { case Hearts = 'H'; }";
+
+        assert_eq!(enum_declaration(input), Result::Error(Error::Position(ErrorKind::MapRes, &input[..])));
+    }
+
+    #[test]
+    fn case_invalid_enum_backed_with_a_case_without_value() {
+        let input = b"enum Suit: string { case Hearts; }";
+
+        assert_eq!(enum_declaration(input), Result::Error(Error::Position(ErrorKind::MapRes, &input[..])));
+    }
+
+    #[test]
+    fn case_invalid_enum_with_a_property() {
+        let input = b"enum Suit { public $x; }";
+
+        assert_eq!(enum_declaration(input), Result::Error(Error::Position(ErrorKind::Tag, &b"public $x; }"[..])));
+    }
+
+    #[test]
+    fn case_invalid_enum_with_an_abstract_method() {
+        let input = b"enum Suit { abstract public function color(); }";
+
+        assert_eq!(enum_declaration(input), Result::Error(Error::Position(ErrorKind::Tag, &b"abstract public function color(); }"[..])));
+    }
+
+    #[test]
+    fn case_invalid_class_with_a_case() {
+        let input = b"class Suit { case Hearts; }";
+
+        assert_eq!(class_declaration(input), Result::Error(Error::Position(ErrorKind::Tag, &b"case Hearts; }"[..])));
+    }
+
+    #[test]
+    fn case_enum_as_a_function_name() {
+        let input  = b"enum_exists($x);";
+        let output = Result::Done(
+            &b""[..],
+            Statement::Expression(
+                Spanned::from(Expression::Call {
+                    callee   : Box::new(Spanned::from(Expression::Name(Name::Unqualified(&b"enum_exists"[..])))),
+                    arguments: vec![
+                        Spanned::from(Argument {
+                            name       : None,
+                            is_unpacked: false,
+                            value      : Spanned::from(Expression::Variable(Variable::Name(&b"x"[..])))
+                        })
+                    ]
+                })
+            )
+        );
+
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_class_member_final_constant() {
+        let input  = b"final protected const FOO = 1;";
+        let output = Result::Done(
+            &b""[..],
+            ClassMember::Constant {
+                doc_comment: None,
+                attributes : vec![],
+                visibility : Visibility::Protected,
+                is_final   : true,
+                ty         : None,
+                constants  : vec![(&b"FOO"[..], ConstantExpression(Spanned::from(Expression::Literal(Literal::Integer(1)))))]
+            }
+        );
+
+        assert_eq!(class_member(input), output);
+    }
+
+    #[test]
+    fn case_invalid_class_member_final_constant_before_php_81() {
+        let input = b"final const FOO = 1;";
+
+        version::set_target(Version::Php80);
+        let output = class_member(input);
+        version::set_target(version::LATEST);
+
+        assert_eq!(output, Result::Error(Error::Position(ErrorKind::Alt, &input[..])));
+    }
+
+    #[test]
+    fn case_invalid_class_member_final_private_constant() {
+        let input = b"final private const FOO = 1;";
+
+        assert_eq!(class_member(input), Result::Error(Error::Position(ErrorKind::Alt, &input[..])));
+    }
+
+    #[test]
+    fn case_class_member_constructor_with_promoted_parameters() {
+        let input  = b"public function __CONSTRUCT(private int $x, $y) {}";
+        let output = Result::Done(
+            &b""[..],
+            ClassMember::Method(
+                Method {
+                    doc_comment      : None,
+                    attributes       : vec![],
+                    modifiers        : METHOD_PUBLIC,
+                    returns_reference: false,
+                    name             : &b"__CONSTRUCT"[..],
+                    parameters       : vec![
+                        Spanned::from(Parameter {
+                            attributes    : vec![],
+                            visibility    : Some(Visibility::Private),
+                            set_visibility: None,
+                            is_readonly   : false,
+                            ty            : Some(Spanned::from(Type::Int)),
+                            is_reference  : false,
+                            is_variadic   : false,
+                            name          : Variable::Name(&b"x"[..]),
+                            value         : None,
+                            hooks         : vec![]
+                        }),
+                        Spanned::from(Parameter {
+                            attributes    : vec![],
+                            visibility    : None,
+                            set_visibility: None,
+                            is_readonly   : false,
+                            ty            : None,
+                            is_reference  : false,
+                            is_variadic   : false,
+                            name          : Variable::Name(&b"y"[..]),
+                            value         : None,
+                            hooks         : vec![]
+                        })
+                    ],
+                    return_type      : None,
+                    body             : Some(vec![])
+                }
+            )
+        );
+
+        assert_eq!(class_member(input), output);
+    }
+
+    #[test]
+    fn case_invalid_class_member_promoted_parameters_outside_constructor() {
+        let input = b"public function f(private $x) {}";
+
+        assert_eq!(class_member(input), Result::Error(Error::Position(ErrorKind::Alt, &input[..])));
+    }
+
+    #[test]
+    fn case_invalid_class_member_promoted_parameters_in_abstract_constructor() {
+        let input = b"abstract public function __construct(private $x);";
+
+        assert_eq!(class_member(input), Result::Error(Error::Position(ErrorKind::Alt, &input[..])));
+    }
+
+    #[test]
+    fn case_invalid_function_with_promoted_parameters() {
+        let input = b"function __construct(private $x) {}";
+
+        assert_eq!(statement(input), Result::Error(Error::Position(ErrorKind::Alt, &input[..])));
+    }
+
+    #[test]
+    fn case_invalid_class_readonly_before_php_82() {
+        let input = b"readonly class Foo {}";
+
+        version::set_target(Version::Php81);
+        let output = class_declaration(input);
+        version::set_target(version::LATEST);
+
+        assert_eq!(output, Result::Error(Error::Position(ErrorKind::MapRes, &input[..])));
+    }
+
+    #[test]
+    fn case_class_readonly_marks_its_properties_as_readonly() {
+        let input  = b"readonly class Foo { public int $x; public function __construct(private string $y, $z) {} }";
+        let output = Result::Done(
+            &b""[..],
+            Statement::Class {
+                doc_comment: None,
+                attributes : vec![],
+                is_abstract: false,
+                is_final   : false,
+                is_readonly: true,
+                name       : &b"Foo"[..],
+                extends    : None,
+                implements : vec![],
+                members    : vec![
+                    Spanned::from(ClassMember::Property {
+                        doc_comment   : None,
+                        attributes    : vec![],
+                        visibility    : Visibility::Public,
+                        set_visibility: None,
+                        is_static     : false,
+                        is_readonly   : true,
+                        ty            : Some(Spanned::from(Type::Int)),
+                        properties    : vec![(Variable::Name(&b"x"[..]), None)],
+                        hooks         : vec![]
+                    }),
+                    Spanned::from(ClassMember::Method(
+                        Method {
+                            doc_comment      : None,
+                            attributes       : vec![],
+                            modifiers        : METHOD_PUBLIC,
+                            returns_reference: false,
+                            name             : &b"__construct"[..],
+                            parameters       : vec![
+                                Spanned::from(Parameter {
+                                    attributes    : vec![],
+                                    visibility    : Some(Visibility::Private),
+                                    set_visibility: None,
+                                    is_readonly   : true,
+                                    ty            : Some(Spanned::from(Type::String)),
+                                    is_reference  : false,
+                                    is_variadic   : false,
+                                    name          : Variable::Name(&b"y"[..]),
+                                    value         : None,
+                                    hooks         : vec![]
+                                }),
+                                Spanned::from(Parameter {
+                                    attributes    : vec![],
+                                    visibility    : None,
+                                    set_visibility: None,
+                                    is_readonly   : false,
+                                    ty            : None,
+                                    is_reference  : false,
+                                    is_variadic   : false,
+                                    name          : Variable::Name(&b"z"[..]),
+                                    value         : None,
+                                    hooks         : vec![]
+                                })
+                            ],
+                            return_type      : None,
+                            body             : Some(vec![])
+                        }
+                    ))
+                ]
+            }
+        );
+
+        assert_eq!(class_declaration(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_invalid_class_readonly_with_an_untyped_promoted_parameter() {
+        let input = b"readonly class Foo { public function __construct(private $x) {} }";
+
+        assert_eq!(class_declaration(input), Result::Error(Error::Position(ErrorKind::MapRes, &input[..])));
+    }
+
+    #[test]
+    fn case_invalid_class_readonly_with_a_static_property() {
+        let input = b"readonly class Foo { public static int $x; }";
+
+        assert_eq!(class_declaration(input), Result::Error(Error::Position(ErrorKind::MapRes, &input[..])));
+    }
+
+    #[test]
+    fn case_class_member_readonly_property() {
+        let input  = b"protected readonly int $foo;";
+        let output = Result::Done(
+            &b""[..],
+            ClassMember::Property {
+                doc_comment   : None,
+                attributes    : vec![],
+                visibility    : Visibility::Protected,
+                set_visibility: None,
+                is_static     : false,
+                is_readonly   : true,
+                ty            : Some(Spanned::from(Type::Int)),
+                properties    : vec![(Variable::Name(&b"foo"[..]), None)],
+                hooks         : vec![]
+            }
+        );
+
+        assert_eq!(class_member(input), output);
+    }
+
+    #[test]
+    fn case_class_member_readonly_property_without_visibility() {
+        let input  = b"readonly int $foo;";
+        let output = Result::Done(
+            &b""[..],
+            ClassMember::Property {
+                doc_comment   : None,
+                attributes    : vec![],
+                visibility    : Visibility::Public,
+                set_visibility: None,
+                is_static     : false,
+                is_readonly   : true,
+                ty            : Some(Spanned::from(Type::Int)),
+                properties    : vec![(Variable::Name(&b"foo"[..]), None)],
+                hooks         : vec![]
+            }
+        );
+
+        assert_eq!(class_member(input), output);
+    }
+
+    #[test]
+    fn case_invalid_class_member_readonly_property_before_php_81() {
+        let input = b"public readonly int $foo;";
+
+        version::set_target(Version::Php80);
+        let output = class_member(input);
+        version::set_target(version::LATEST);
+
+        assert_eq!(output, Result::Error(Error::Position(ErrorKind::Alt, &input[..])));
+    }
+
+    #[test]
+    fn case_invalid_class_member_readonly_property_without_type() {
+        let input = b"public readonly $foo;";
+
+        assert_eq!(class_member(input), Result::Error(Error::Position(ErrorKind::Alt, &input[..])));
+    }
+
+    #[test]
+    fn case_invalid_class_readonly_with_an_untyped_property() {
+        let input = b"readonly class Foo { public $x; }";
+
+        assert_eq!(class_declaration(input), Result::Error(Error::Position(ErrorKind::MapRes, &input[..])));
+    }
+
+    #[test]
+    fn case_invalid_class_member_readonly_static_property() {
+        let input = b"public readonly static int $foo;";
+
+        assert_eq!(class_member(input), Result::Error(Error::Position(ErrorKind::Alt, &input[..])));
+    }
+
+    #[test]
+    fn case_invalid_class_member_readonly_property_with_a_default_value() {
+        let input = b"public readonly int $foo = 42;";
+
+        assert_eq!(class_member(input), Result::Error(Error::Position(ErrorKind::Alt, &input[..])));
+    }
+
+    #[test]
+    fn case_invalid_class_member_readonly_method() {
+        let input = b"public readonly function f() {}";
+
+        assert_eq!(class_member(input), Result::Error(Error::Position(ErrorKind::Alt, &input[..])));
+    }
+
+    #[test]
+    fn case_class_member_constructor_with_readonly_promoted_parameter() {
+        let input  = b"function __construct(readonly int $x) {}";
+        let output = Result::Done(
+            &b""[..],
+            ClassMember::Method(
+                Method {
+                    doc_comment      : None,
+                    attributes       : vec![],
+                    modifiers        : METHOD_PUBLIC,
+                    returns_reference: false,
+                    name             : &b"__construct"[..],
+                    parameters       : vec![
+                        Spanned::from(Parameter {
+                            attributes    : vec![],
+                            visibility    : None,
+                            set_visibility: None,
+                            is_readonly   : true,
+                            ty            : Some(Spanned::from(Type::Int)),
+                            is_reference  : false,
+                            is_variadic   : false,
+                            name          : Variable::Name(&b"x"[..]),
+                            value         : None,
+                            hooks         : vec![]
+                        })
+                    ],
+                    return_type      : None,
+                    body             : Some(vec![])
+                }
+            )
+        );
+
+        assert_eq!(class_member(input), output);
+    }
+
+    #[test]
+    fn case_class_with_attributes() {
+        let input  = b"#[A] #[B(1)] final class Foo { #[C] const D = 1; #[E] public $f; #[G] public function h(#[I] $j) {} }";
+        let output = Result::Done(
+            &b""[..],
+            Statement::Class {
+                doc_comment: None,
+                attributes : vec![
+                    Attribute {
+                        name     : Spanned::from(Name::Unqualified(&b"A"[..])),
+                        arguments: vec![]
+                    },
+                    Attribute {
+                        name     : Spanned::from(Name::Unqualified(&b"B"[..])),
+                        arguments: vec![
+                            Spanned::from(Argument {
+                                name       : None,
+                                is_unpacked: false,
+                                value      : Spanned::from(Expression::Literal(Literal::Integer(1)))
+                            })
+                        ]
+                    }
+                ],
+                is_abstract: false,
+                is_final   : true,
+                is_readonly: false,
+                name       : &b"Foo"[..],
+                extends    : None,
+                implements : vec![],
+                members    : vec![
+                    Spanned::from(ClassMember::Constant {
+                        doc_comment: None,
+                        attributes : vec![
+                            Attribute {
+                                name     : Spanned::from(Name::Unqualified(&b"C"[..])),
+                                arguments: vec![]
+                            }
+                        ],
+                        visibility : Visibility::Public,
+                        is_final   : false,
+                        ty         : None,
+                        constants  : vec![(&b"D"[..], ConstantExpression(Spanned::from(Expression::Literal(Literal::Integer(1)))))]
+                    }),
+                    Spanned::from(ClassMember::Property {
+                        doc_comment   : None,
+                        attributes    : vec![
+                            Attribute {
+                                name     : Spanned::from(Name::Unqualified(&b"E"[..])),
+                                arguments: vec![]
+                            }
+                        ],
+                        visibility    : Visibility::Public,
+                        set_visibility: None,
+                        is_static     : false,
+                        is_readonly   : false,
+                        ty            : None,
+                        properties    : vec![(Variable::Name(&b"f"[..]), None)],
+                        hooks         : vec![]
+                    }),
+                    Spanned::from(ClassMember::Method(
+                        Method {
+                            doc_comment      : None,
+                            attributes       : vec![
+                                Attribute {
+                                    name     : Spanned::from(Name::Unqualified(&b"G"[..])),
+                                    arguments: vec![]
+                                }
+                            ],
+                            modifiers        : METHOD_PUBLIC,
+                            returns_reference: false,
+                            name             : &b"h"[..],
+                            parameters       : vec![
+                                Spanned::from(Parameter {
+                                    attributes    : vec![
+                                        Attribute {
+                                            name     : Spanned::from(Name::Unqualified(&b"I"[..])),
+                                            arguments: vec![]
+                                        }
+                                    ],
+                                    visibility    : None,
+                                    set_visibility: None,
+                                    is_readonly   : false,
+                                    ty            : None,
+                                    is_reference  : false,
+                                    is_variadic   : false,
+                                    name          : Variable::Name(&b"j"[..]),
+                                    value         : None,
+                                    hooks         : vec![]
+                                })
+                            ],
+                            return_type      : None,
+                            body             : Some(vec![])
+                        }
+                    ))
+                ]
+            }
+        );
+
+        assert_eq!(class_declaration(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_class_with_attributes_as_comments_before_php_80() {
+        let input  = b"#[A]\nclass Foo {}";
+        let output = Result::Done(
+            &b""[..],
+            Statement::Class {
+                doc_comment: None,
+                attributes : vec![],
+                is_abstract: false,
+                is_final   : false,
+                is_readonly: false,
+                name       : &b"Foo"[..],
+                extends    : None,
+                implements : vec![],
+                members    : vec![]
+            }
+        );
+
+        version::set_target(Version::Php74);
+        let result = statement(input);
+        version::set_target(version::LATEST);
+
+        assert_eq!(result, output);
+    }
+
+    #[test]
+    fn case_enum_with_attributes() {
+        let input  = b"#[A] enum Suit { #[B] case Hearts; }";
+        let output = Result::Done(
+            &b""[..],
+            Statement::Enum {
+                doc_comment : None,
+                attributes  : vec![
+                    Attribute {
+                        name     : Spanned::from(Name::Unqualified(&b"A"[..])),
+                        arguments: vec![]
+                    }
+                ],
+                name        : &b"Suit"[..],
+                backing_type: None,
+                implements  : vec![],
+                members     : vec![
+                    Spanned::from(ClassMember::Case {
+                        attributes: vec![
+                            Attribute {
+                                name     : Spanned::from(Name::Unqualified(&b"B"[..])),
+                                arguments: vec![]
+                            }
+                        ],
+                        name      : &b"Hearts"[..],
+                        value     : None
+                    })
+                ]
+            }
+        );
+
+        assert_eq!(enum_declaration(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_interface_and_trait_with_attributes() {
+        let attribute = || {
+            vec![
+                Attribute {
+                    name     : Spanned::from(Name::Unqualified(&b"A"[..])),
+                    arguments: vec![]
+                }
+            ]
+        };
+
+        assert_eq!(
+            statement(b"#[A] interface Foo {}"),
+            Result::Done(
+                &b""[..],
+                Statement::Interface {
+                    doc_comment: None,
+                    attributes : attribute(),
+                    name       : &b"Foo"[..],
+                    extends    : vec![],
+                    members    : vec![]
+                }
+            )
+        );
+        assert_eq!(
+            statement(b"#[A] trait Foo {}"),
+            Result::Done(
+                &b""[..],
+                Statement::Trait {
+                    doc_comment: None,
+                    attributes : attribute(),
+                    name       : &b"Foo"[..],
+                    members    : vec![]
+                }
+            )
+        );
+    }
+
+    #[test]
+    fn case_invalid_class_member_trait_use_with_attributes() {
+        let input = b"#[A] use T;";
+
+        assert_eq!(class_member(input), Result::Error(Error::Position(ErrorKind::Alt, &input[..])));
+    }
+}
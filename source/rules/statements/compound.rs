@@ -0,0 +1,189 @@
+// Tagua VM
+//
+//
+// New BSD License
+//
+// Copyright © 2016-2016, Ivan Enderlin.
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//     * Redistributions of source code must retain the above copyright
+//       notice, this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright
+//       notice, this list of conditions and the following disclaimer in the
+//       documentation and/or other materials provided with the distribution.
+//     * Neither the name of the Hoa nor the names of its contributors may be
+//       used to endorse or promote products derived from this software without
+//       specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE
+// ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDERS AND CONTRIBUTORS BE
+// LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR
+// CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF
+// SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS
+// INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN
+// CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+
+//! Group of compound statement rules.
+//!
+//! The list of all compound statements is provided by the PHP Language
+//! Specification in the [Grammar chapter, Statements
+//! section](https://github.com/php/php-langspec/blob/master/spec/19-grammar.md#compound-statements).
+
+use super::statement;
+use super::super::script::inline_html;
+use super::super::super::ast::Statement;
+use super::super::super::span::Spanned;
+use super::super::super::tokens;
+
+named!(
+    pub compound_statement<Statement>,
+    map!(
+        block,
+        compound_statement_mapper
+    )
+);
+
+#[inline(always)]
+fn compound_statement_mapper<'a>(statements: Vec<Spanned<Statement<'a>>>) -> Statement<'a> {
+    Statement::Block(statements)
+}
+
+named!(
+    pub block< Vec<Spanned<Statement>> >,
+    preceded!(
+        tag!(tokens::LEFT_CURLY_BRACKET),
+        terminated!(
+            statements,
+            first!(expect!("block", tokens::RIGHT_CURLY_BRACKET))
+        )
+    )
+);
+
+// Parse statements. Inside a statement list, the script can leave PHP
+// for inline HTML and come back, e.g. `{ ?>html<?php }`.
+named!(
+    pub statements< Vec<Spanned<Statement>> >,
+    fold_many0!(
+        complete!(
+            first!(
+                alt!(
+                    spanned!(statement) => { statement_mapper }
+                  | inline_html
+                )
+            )
+        ),
+        Vec::new(),
+        append_into_vector
+    )
+);
+
+#[inline(always)]
+fn statement_mapper<'a>(statement: Spanned<Statement<'a>>) -> Vec<Spanned<Statement<'a>>> {
+    vec![statement]
+}
+
+#[inline(always)]
+fn append_into_vector<'a>(mut accumulator: Vec<Spanned<Statement<'a>>>, mut statements: Vec<Spanned<Statement<'a>>>) -> Vec<Spanned<Statement<'a>>> {
+    accumulator.append(&mut statements);
+
+    accumulator
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        compound_statement,
+        statements
+    };
+    use super::super::statement;
+    use super::super::super::super::ast::Statement;
+    use super::super::super::super::internal::{
+        Error,
+        ErrorKind,
+        Needed,
+        Result
+    };
+    use super::super::super::super::span::{
+        Span,
+        Spanned
+    };
+
+    #[test]
+    fn case_compound_statement_empty() {
+        let input  = b"{}";
+        let output = Result::Done(&b""[..], Statement::Block(vec![]));
+
+        assert_eq!(compound_statement(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_compound_statement_nested() {
+        let input  = b"{ {} /* foo */ { { } } // bar\n }";
+        let output = Result::Done(
+            &b""[..],
+            Statement::Block(vec![
+                Spanned::from(Statement::Block(vec![])),
+                Spanned::from(Statement::Block(vec![
+                    Spanned::from(Statement::Block(vec![]))
+                ]))
+            ])
+        );
+
+        assert_eq!(compound_statement(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_statements() {
+        assert_eq!(statements(b"{} {}"), Result::Done(&b""[..], vec![Spanned::from(Statement::Block(vec![])), Spanned::from(Statement::Block(vec![]))]));
+    }
+
+    #[test]
+    fn case_statements_empty() {
+        assert_eq!(statements(b""), Result::Done(&b""[..], vec![]));
+    }
+
+    #[test]
+    fn case_compound_statement_spans() {
+        let input = b"{ {} /* foo */ { { } } }";
+
+        match statement(input) {
+            Result::Done(_, Statement::Block(statements)) => {
+                assert_eq!(statements[0].span, Span { start: 2, end: 4 });
+                assert_eq!(statements[1].span, Span { start: 15, end: 22 });
+
+                match statements[1].node {
+                    Statement::Block(ref statements) => {
+                        assert_eq!(statements[0].span, Span { start: 17, end: 20 });
+                    },
+
+                    _ => panic!("A block was expected.")
+                }
+            },
+
+            _ => panic!("A block was expected.")
+        }
+    }
+
+    #[test]
+    fn case_invalid_compound_statement_not_closed() {
+        let input = b"{ {}";
+
+        assert_eq!(compound_statement(input), Result::Incomplete(Needed::Size(5)));
+    }
+
+    #[test]
+    fn case_invalid_compound_statement_not_opened() {
+        let input = b"}";
+
+        assert_eq!(compound_statement(input), Result::Error(Error::Position(ErrorKind::Tag, &input[..])));
+    }
+}
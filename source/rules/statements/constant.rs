@@ -0,0 +1,169 @@
+// Tagua VM
+//
+//
+// New BSD License
+//
+// Copyright © 2016-2016, Ivan Enderlin.
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//     * Redistributions of source code must retain the above copyright
+//       notice, this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright
+//       notice, this list of conditions and the following disclaimer in the
+//       documentation and/or other materials provided with the distribution.
+//     * Neither the name of the Hoa nor the names of its contributors may be
+//       used to endorse or promote products derived from this software without
+//       specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE
+// ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDERS AND CONTRIBUTORS BE
+// LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR
+// CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF
+// SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS
+// INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN
+// CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+
+//! Group of constant declaration rules.
+//!
+//! The constant declaration is provided by the PHP Language Specification
+//! in the [Grammar chapter, Constants
+//! section](https://github.com/php/php-langspec/blob/master/spec/19-grammar.md#constants).
+
+use std::result::Result as StdResult;
+use super::statement_terminator;
+use super::super::expressions::constants::constant_expression;
+use super::super::tokens::qualified_name;
+use super::super::super::ast::{
+    ConstantExpression,
+    Name,
+    Statement
+};
+use super::super::super::internal::{
+    fold_into_vector,
+    into_vector_mapper
+};
+use super::super::super::tokens;
+
+named!(
+    pub constant_declaration<Statement>,
+    chain!(
+        keyword!(tokens::CONST) ~
+        accumulator: map_res!(
+            first!(constant_definition),
+            into_vector_mapper
+        ) ~
+        constants: fold_many0!(
+            preceded!(
+                first!(tag!(tokens::COMMA)),
+                first!(constant_definition)
+            ),
+            accumulator,
+            fold_into_vector
+        ) ~
+        first!(expect!("constant_declaration", &[tokens::SEMICOLON], statement_terminator)),
+        || { Statement::Constant(constants) }
+    )
+);
+
+named!(
+    constant_definition<(&[u8], ConstantExpression)>,
+    chain!(
+        name: constant_name ~
+        first!(tag!(tokens::ASSIGN)) ~
+        value: first!(constant_expression),
+        || { (name, value) }
+    )
+);
+
+named!(
+    constant_name<&[u8]>,
+    map_res!(
+        qualified_name,
+        constant_name_mapper
+    )
+);
+
+#[inline(always)]
+fn constant_name_mapper(name: Name) -> StdResult<&[u8], ()> {
+    match name {
+        Name::Unqualified(name) => Ok(name),
+        _ => Err(())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::constant_declaration;
+    use super::super::statement;
+    use super::super::super::super::ast::{
+        ConstantExpression,
+        Expression,
+        Literal,
+        Name,
+        Statement
+    };
+    use super::super::super::super::internal::{
+        Error,
+        ErrorKind,
+        Result
+    };
+    use super::super::super::super::macros::ErrorKindCustom;
+    use super::super::super::super::span::Spanned;
+
+    #[test]
+    fn case_constant() {
+        let input  = b"const FOO = 42;";
+        let output = Result::Done(
+            &b""[..],
+            Statement::Constant(vec![(&b"FOO"[..], ConstantExpression(Spanned::from(Expression::Literal(Literal::Integer(42)))))])
+        );
+
+        assert_eq!(constant_declaration(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_constants() {
+        let input  = b"const FOO = 42, BAR = FOO;";
+        let output = Result::Done(
+            &b""[..],
+            Statement::Constant(
+                vec![
+                    (&b"FOO"[..], ConstantExpression(Spanned::from(Expression::Literal(Literal::Integer(42))))),
+                    (&b"BAR"[..], ConstantExpression(Spanned::from(Expression::Name(Name::Unqualified(&b"FOO"[..])))))
+                ]
+            )
+        );
+
+        assert_eq!(constant_declaration(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_invalid_constant_name_is_a_keyword() {
+        let input = b"const LIST = 42;";
+
+        assert_eq!(constant_declaration(input), Result::Error(Error::Position(ErrorKind::Custom(ErrorKindCustom::Exclude as u32), &b"LIST = 42;"[..])));
+    }
+
+    #[test]
+    fn case_invalid_constant_name_is_qualified() {
+        let input = b"const A\\B = 42;";
+
+        assert_eq!(constant_declaration(input), Result::Error(Error::Position(ErrorKind::MapRes, &b"A\\B = 42;"[..])));
+    }
+
+    #[test]
+    fn case_invalid_constant_value_is_not_constant() {
+        let input = b"const FOO = $bar;";
+
+        assert_eq!(constant_declaration(input), Result::Error(Error::Position(ErrorKind::MapRes, &b"$bar;"[..])));
+    }
+}
@@ -0,0 +1,251 @@
+// Tagua VM
+//
+//
+// New BSD License
+//
+// Copyright © 2016-2016, Ivan Enderlin.
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//     * Redistributions of source code must retain the above copyright
+//       notice, this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright
+//       notice, this list of conditions and the following disclaimer in the
+//       documentation and/or other materials provided with the distribution.
+//     * Neither the name of the Hoa nor the names of its contributors may be
+//       used to endorse or promote products derived from this software without
+//       specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE
+// ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDERS AND CONTRIBUTORS BE
+// LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR
+// CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF
+// SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS
+// INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN
+// CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+
+//! Group of declare statement rules.
+//!
+//! The declare statement is provided by the PHP Language Specification in
+//! the [Grammar chapter, Statements
+//! section](https://github.com/php/php-langspec/blob/master/spec/19-grammar.md#the-declare-statement).
+
+use std::result::Result as StdResult;
+use super::{
+    alternative_syntax_block,
+    statement
+};
+use super::super::literals::{
+    integer,
+    string
+};
+use super::super::super::ast::{
+    DeclareDirective,
+    Literal,
+    Statement
+};
+use super::super::super::internal::{
+    fold_into_vector,
+    into_vector_mapper
+};
+use super::super::super::tokens;
+
+named!(
+    pub declare_statement<Statement>,
+    chain!(
+        keyword!(tokens::DECLARE) ~
+        first!(tag!(tokens::LEFT_PARENTHESIS)) ~
+        directives: first!(declare_directives) ~
+        first!(expect!("declare_statement", tokens::RIGHT_PARENTHESIS)) ~
+        body: first!(
+            alt!(
+                tag!(tokens::SEMICOLON) => { |_| None }
+              | spanned!(call!(alternative_syntax_block, tokens::ENDDECLARE)) => { |block| Some(Box::new(block)) }
+              | spanned!(statement) => { |statement| Some(Box::new(statement)) }
+            )
+        ),
+        || {
+            Statement::Declare {
+                directives: directives,
+                body      : body
+            }
+        }
+    )
+);
+
+named!(
+    declare_directives< Vec<DeclareDirective> >,
+    chain!(
+        accumulator: map_res!(
+            declare_directive,
+            into_vector_mapper
+        ) ~
+        result: fold_many0!(
+            preceded!(
+                first!(tag!(tokens::COMMA)),
+                first!(declare_directive)
+            ),
+            accumulator,
+            fold_into_vector
+        ),
+        || { result }
+    )
+);
+
+named!(
+    declare_directive<DeclareDirective>,
+    alt!(
+        preceded!(
+            keyword!(tokens::ENCODING),
+            preceded!(
+                first!(tag!(tokens::ASSIGN)),
+                first!(map_res!(string, encoding_mapper))
+            )
+        )
+      | preceded!(
+            keyword!(tokens::STRICT_TYPES),
+            preceded!(
+                first!(tag!(tokens::ASSIGN)),
+                first!(map_res!(integer, strict_types_mapper))
+            )
+        )
+      | preceded!(
+            keyword!(tokens::TICKS),
+            preceded!(
+                first!(tag!(tokens::ASSIGN)),
+                first!(map_res!(integer, ticks_mapper))
+            )
+        )
+    )
+);
+
+#[inline(always)]
+fn encoding_mapper(literal: Literal) -> StdResult<DeclareDirective, ()> {
+    match literal {
+        Literal::String(encoding) => Ok(DeclareDirective::Encoding(encoding)),
+        _ => Err(())
+    }
+}
+
+#[inline(always)]
+fn strict_types_mapper(literal: Literal) -> StdResult<DeclareDirective, ()> {
+    match literal {
+        Literal::Integer(0) => Ok(DeclareDirective::StrictTypes(false)),
+        Literal::Integer(1) => Ok(DeclareDirective::StrictTypes(true)),
+        _ => Err(())
+    }
+}
+
+#[inline(always)]
+fn ticks_mapper(literal: Literal) -> StdResult<DeclareDirective, ()> {
+    match literal {
+        Literal::Integer(ticks) => Ok(DeclareDirective::Ticks(ticks)),
+        _ => Err(())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::declare_statement;
+    use super::super::statement;
+    use super::super::super::super::ast::{
+        DeclareDirective,
+        Statement
+    };
+    use super::super::super::super::internal::{
+        Error,
+        ErrorKind,
+        Result
+    };
+    use super::super::super::super::span::Spanned;
+
+    #[test]
+    fn case_declare_strict_types() {
+        let input  = b"declare(strict_types=1);";
+        let output = Result::Done(
+            &b""[..],
+            Statement::Declare {
+                directives: vec![DeclareDirective::StrictTypes(true)],
+                body      : None
+            }
+        );
+
+        assert_eq!(declare_statement(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_declare_strict_types_disabled() {
+        let input  = b"DECLARE ( STRICT_TYPES = 0 ) ;";
+        let output = Result::Done(
+            &b""[..],
+            Statement::Declare {
+                directives: vec![DeclareDirective::StrictTypes(false)],
+                body      : None
+            }
+        );
+
+        assert_eq!(declare_statement(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_declare_many_directives_with_block() {
+        let input  = b"declare(ticks=1, encoding='UTF-8') { {} }";
+        let output = Result::Done(
+            &b""[..],
+            Statement::Declare {
+                directives: vec![
+                    DeclareDirective::Ticks(1),
+                    DeclareDirective::Encoding(b"UTF-8".to_vec())
+                ],
+                body      : Some(Box::new(Spanned::from(Statement::Block(vec![Spanned::from(Statement::Block(vec![]))]))))
+            }
+        );
+
+        assert_eq!(declare_statement(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_declare_alternative_syntax() {
+        let input  = b"declare(ticks=0x2): {} enddeclare;";
+        let output = Result::Done(
+            &b""[..],
+            Statement::Declare {
+                directives: vec![DeclareDirective::Ticks(2)],
+                body      : Some(Box::new(Spanned::from(Statement::Block(vec![Spanned::from(Statement::Block(vec![]))]))))
+            }
+        );
+
+        assert_eq!(declare_statement(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_invalid_declare_strict_types_out_of_range() {
+        let input = b"declare(strict_types=2);";
+
+        assert_eq!(declare_statement(input), Result::Error(Error::Position(ErrorKind::Alt, &b"strict_types=2);"[..])));
+    }
+
+    #[test]
+    fn case_invalid_declare_unknown_directive() {
+        let input = b"declare(foo=1);";
+
+        assert_eq!(declare_statement(input), Result::Error(Error::Position(ErrorKind::Alt, &b"foo=1);"[..])));
+    }
+
+    #[test]
+    fn case_invalid_declare_without_directive() {
+        let input = b"declare();";
+
+        assert_eq!(declare_statement(input), Result::Error(Error::Position(ErrorKind::Alt, &b");"[..])));
+    }
+}
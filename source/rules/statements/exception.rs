@@ -0,0 +1,240 @@
+// Tagua VM
+//
+//
+// New BSD License
+//
+// Copyright © 2016-2016, Ivan Enderlin.
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//     * Redistributions of source code must retain the above copyright
+//       notice, this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright
+//       notice, this list of conditions and the following disclaimer in the
+//       documentation and/or other materials provided with the distribution.
+//     * Neither the name of the Hoa nor the names of its contributors may be
+//       used to endorse or promote products derived from this software without
+//       specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE
+// ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDERS AND CONTRIBUTORS BE
+// LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR
+// CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF
+// SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS
+// INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN
+// CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+
+//! Group of exception handling statement rules.
+//!
+//! The list of all exception handling statements is provided by the PHP
+//! Language Specification in the [Grammar chapter, Statements
+//! section](https://github.com/php/php-langspec/blob/master/spec/19-grammar.md#the-try-statement).
+
+use std::result::Result as StdResult;
+use super::compound::block;
+use super::super::tokens::{
+    qualified_name,
+    variable
+};
+use super::super::super::ast::{
+    Catch,
+    Name,
+    Statement
+};
+use super::super::super::internal::{
+    fold_into_vector,
+    into_vector_mapper
+};
+use super::super::super::span::Spanned;
+use super::super::super::tokens;
+
+named!(
+    pub try_statement<Statement>,
+    map_res!(
+        chain!(
+            keyword!(tokens::TRY) ~
+            body: first!(block) ~
+            catches: fold_many0!(
+                complete!(first!(catch_block)),
+                Vec::new(),
+                fold_into_vector
+            ) ~
+            finally: opt!(
+                complete!(
+                    preceded!(
+                        first!(keyword!(tokens::FINALLY)),
+                        first!(block)
+                    )
+                )
+            ),
+            || { (body, catches, finally) }
+        ),
+        try_mapper
+    )
+);
+
+#[inline(always)]
+fn try_mapper<'a>((body, catches, finally): (Vec<Spanned<Statement<'a>>>, Vec<Catch<'a>>, Option<Vec<Spanned<Statement<'a>>>>)) -> StdResult<Statement<'a>, ()> {
+    if catches.is_empty() && finally.is_none() {
+        return Err(());
+    }
+
+    Ok(
+        Statement::Try {
+            body   : body,
+            catches: catches,
+            finally: finally
+        }
+    )
+}
+
+named!(
+    catch_block<Catch>,
+    chain!(
+        keyword!(tokens::CATCH) ~
+        first!(tag!(tokens::LEFT_PARENTHESIS)) ~
+        types: first!(catch_types) ~
+        variable: opt!(first!(variable)) ~
+        first!(expect!("catch_block", tokens::RIGHT_PARENTHESIS)) ~
+        body: first!(block),
+        || {
+            Catch {
+                types   : types,
+                variable: variable,
+                body    : body
+            }
+        }
+    )
+);
+
+named!(
+    catch_types< Vec<Spanned<Name>> >,
+    chain!(
+        accumulator: map_res!(
+            spanned!(qualified_name),
+            into_vector_mapper
+        ) ~
+        result: fold_many0!(
+            preceded!(
+                first!(tag!(tokens::BITWISE_OR)),
+                first!(spanned!(qualified_name))
+            ),
+            accumulator,
+            fold_into_vector
+        ),
+        || { result }
+    )
+);
+
+
+#[cfg(test)]
+mod tests {
+    use super::try_statement;
+    use super::super::statement;
+    use super::super::super::super::ast::{
+        Catch,
+        Name,
+        Statement,
+        Variable
+    };
+    use super::super::super::super::internal::{
+        Error,
+        ErrorKind,
+        Result
+    };
+    use super::super::super::super::span::Spanned;
+
+    #[test]
+    fn case_try_catch() {
+        let input  = b"try { {} } catch (Exception $e) { {} }";
+        let output = Result::Done(
+            &b""[..],
+            Statement::Try {
+                body   : vec![Spanned::from(Statement::Block(vec![]))],
+                catches: vec![
+                    Catch {
+                        types   : vec![Spanned::from(Name::Unqualified(&b"Exception"[..]))],
+                        variable: Some(Variable::Name(&b"e"[..])),
+                        body    : vec![Spanned::from(Statement::Block(vec![]))]
+                    }
+                ],
+                finally: None
+            }
+        );
+
+        assert_eq!(try_statement(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_try_many_catches() {
+        let input  = b"TRY{}CATCH(\\Foo\\A|B $e){}catch(C){}";
+        let output = Result::Done(
+            &b""[..],
+            Statement::Try {
+                body   : vec![],
+                catches: vec![
+                    Catch {
+                        types   : vec![
+                            Spanned::from(Name::FullyQualified(vec![&b"Foo"[..], &b"A"[..]])),
+                            Spanned::from(Name::Unqualified(&b"B"[..]))
+                        ],
+                        variable: Some(Variable::Name(&b"e"[..])),
+                        body    : vec![]
+                    },
+                    Catch {
+                        types   : vec![Spanned::from(Name::Unqualified(&b"C"[..]))],
+                        variable: None,
+                        body    : vec![]
+                    }
+                ],
+                finally: None
+            }
+        );
+
+        assert_eq!(try_statement(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_try_finally() {
+        let input  = b"try {} /* foo */ finally { {} }";
+        let output = Result::Done(
+            &b""[..],
+            Statement::Try {
+                body   : vec![],
+                catches: vec![],
+                finally: Some(vec![Spanned::from(Statement::Block(vec![]))])
+            }
+        );
+
+        assert_eq!(try_statement(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_invalid_try_without_catch_nor_finally() {
+        let input = b"try {}";
+
+        assert_eq!(try_statement(input), Result::Error(Error::Position(ErrorKind::MapRes, &input[..])));
+    }
+
+    #[test]
+    fn case_invalid_try_body_is_not_a_block() {
+        let input = b"try return; finally {}";
+
+        assert_eq!(try_statement(input), Result::Error(Error::Position(ErrorKind::Tag, &b"return; finally {}"[..])));
+    }
+
+    #[test]
+    fn case_invalid_catch_missing_type() {
+        let input = b"try {} catch ($e) {}";
+
+        assert_eq!(try_statement(input), Result::Error(Error::Position(ErrorKind::MapRes, &input[..])));
+    }
+}
@@ -0,0 +1,277 @@
+// Tagua VM
+//
+//
+// New BSD License
+//
+// Copyright © 2016-2016, Ivan Enderlin.
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//     * Redistributions of source code must retain the above copyright
+//       notice, this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright
+//       notice, this list of conditions and the following disclaimer in the
+//       documentation and/or other materials provided with the distribution.
+//     * Neither the name of the Hoa nor the names of its contributors may be
+//       used to endorse or promote products derived from this software without
+//       specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE
+// ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDERS AND CONTRIBUTORS BE
+// LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR
+// CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF
+// SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS
+// INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN
+// CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+
+//! Group of expression statement rules.
+//!
+//! The expression statement is provided by the PHP Language Specification
+//! in the [Grammar chapter, Expression Statements
+//! section](https://github.com/php/php-langspec/blob/master/spec/19-grammar.md#expression-statements).
+
+use super::statement_terminator;
+use super::super::expressions::expression;
+use super::super::super::ast::Statement;
+use super::super::super::internal::{
+    fold_into_vector,
+    into_vector_mapper
+};
+use super::super::super::tokens;
+
+named!(
+    pub expression_statement<Statement>,
+    chain!(
+        expression: spanned!(expression) ~
+        first!(expect!("expression_statement", &[tokens::SEMICOLON], statement_terminator)),
+        || { Statement::Expression(expression) }
+    )
+);
+
+named!(
+    pub echo_statement<Statement>,
+    chain!(
+        accumulator: map_res!(
+            preceded!(
+                keyword!(tokens::ECHO),
+                first!(spanned!(expression))
+            ),
+            into_vector_mapper
+        ) ~
+        result: fold_many0!(
+            complete!(
+                preceded!(
+                    first!(tag!(tokens::COMMA)),
+                    first!(spanned!(expression))
+                )
+            ),
+            accumulator,
+            fold_into_vector
+        ) ~
+        first!(expect!("echo_statement", &[tokens::SEMICOLON], statement_terminator)),
+        || { Statement::Echo(result) }
+    )
+);
+
+named!(
+    pub empty_statement<Statement>,
+    map!(
+        tag!(tokens::SEMICOLON),
+        |_| { Statement::Empty }
+    )
+);
+
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        echo_statement,
+        empty_statement,
+        expression_statement
+    };
+    use super::super::statement;
+    use super::super::compound::statements;
+    use super::super::super::super::ast::{
+        Expression,
+        Literal,
+        Name,
+        Statement,
+        Variable
+    };
+    use super::super::super::super::internal::{
+        Error,
+        ErrorKind,
+        Result
+    };
+    use super::super::super::super::macros::ErrorKindCustom;
+    use super::super::super::super::span::Spanned;
+
+    #[test]
+    fn case_expression_statement_call() {
+        let input  = b"foo();";
+        let output = Result::Done(
+            &b""[..],
+            Statement::Expression(
+                Spanned::from(Expression::Call {
+                    callee   : Box::new(Spanned::from(Expression::Name(Name::Unqualified(&b"foo"[..])))),
+                    arguments: vec![]
+                })
+            )
+        );
+
+        assert_eq!(expression_statement(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_expression_statement_assignment() {
+        let input  = b"$a = 1 /* foo */ ;";
+        let output = Result::Done(
+            &b""[..],
+            Statement::Expression(
+                Spanned::from(Expression::Assignment {
+                    variable: Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"a"[..])))),
+                    value   : Box::new(Spanned::from(Expression::Literal(Literal::Integer(1))))
+                })
+            )
+        );
+
+        assert_eq!(expression_statement(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_expression_statement_terminated_by_a_closing_tag() {
+        let input  = b"$a ?>";
+        let output = Result::Done(&b"?>"[..], Statement::Expression(Spanned::from(Expression::Variable(Variable::Name(&b"a"[..])))));
+
+        assert_eq!(expression_statement(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_echo_statement() {
+        let input  = b"echo $a, 42;";
+        let output = Result::Done(
+            &b""[..],
+            Statement::Echo(vec![
+                Spanned::from(Expression::Variable(Variable::Name(&b"a"[..]))),
+                Spanned::from(Expression::Literal(Literal::Integer(42)))
+            ])
+        );
+
+        assert_eq!(echo_statement(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_echo_statement_terminated_by_a_closing_tag() {
+        let input  = b"echo $a ?>";
+        let output = Result::Done(&b"?>"[..], Statement::Echo(vec![Spanned::from(Expression::Variable(Variable::Name(&b"a"[..])))]));
+
+        assert_eq!(echo_statement(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_echo_statement_case_insensitive() {
+        let input  = b"EcHo 42;";
+        let output = Result::Done(&b""[..], Statement::Echo(vec![Spanned::from(Expression::Literal(Literal::Integer(42)))]));
+
+        assert_eq!(echo_statement(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_call_starting_with_the_echo_keyword() {
+        let input  = b"echoIt();";
+        let output = Result::Done(
+            &b""[..],
+            Statement::Expression(
+                Spanned::from(Expression::Call {
+                    callee   : Box::new(Spanned::from(Expression::Name(Name::Unqualified(&b"echoIt"[..])))),
+                    arguments: vec![]
+                })
+            )
+        );
+
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_constant_starting_with_the_echo_keyword() {
+        let input  = b"echoes;";
+        let output = Result::Done(&b""[..], Statement::Expression(Spanned::from(Expression::Name(Name::Unqualified(&b"echoes"[..])))));
+
+        assert_eq!(echo_statement(input), Result::Error(Error::Position(ErrorKind::Custom(ErrorKindCustom::Keyword as u32), &input[..])));
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_invalid_echo_statement_without_expression() {
+        let input = b"echo;";
+
+        assert_eq!(echo_statement(input), Result::Error(Error::Position(ErrorKind::Alt, &b";"[..])));
+    }
+
+    #[test]
+    fn case_invalid_echo_statement_with_a_trailing_comma() {
+        let input = b"echo $a,;";
+
+        assert_eq!(echo_statement(input), Result::Error(Error::Position(ErrorKind::Alt, &b",;"[..])));
+    }
+
+    #[test]
+    fn case_expression_statement_is_not_a_label() {
+        let input  = b"Foo::bar();";
+        let output = Result::Done(
+            &b""[..],
+            Statement::Expression(
+                Spanned::from(Expression::StaticMethodCall {
+                    class    : Box::new(Spanned::from(Expression::Name(Name::Unqualified(&b"Foo"[..])))),
+                    method   : Box::new(Spanned::from(Expression::Name(Name::Unqualified(&b"bar"[..])))),
+                    arguments: vec![]
+                })
+            )
+        );
+
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_empty_statement() {
+        let input  = b";";
+        let output = Result::Done(&b""[..], Statement::Empty);
+
+        assert_eq!(empty_statement(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_statements_mixed_with_empty_statements() {
+        let input  = b"$a;; ;$b;";
+        let output = Result::Done(
+            &b""[..],
+            vec![
+                Spanned::from(Statement::Expression(Spanned::from(Expression::Variable(Variable::Name(&b"a"[..]))))),
+                Spanned::from(Statement::Empty),
+                Spanned::from(Statement::Empty),
+                Spanned::from(Statement::Expression(Spanned::from(Expression::Variable(Variable::Name(&b"b"[..])))))
+            ]
+        );
+
+        assert_eq!(statements(input), output);
+    }
+
+    #[test]
+    fn case_invalid_expression_statement_without_terminator() {
+        let input = b"$a $b;";
+
+        assert_eq!(expression_statement(input), Result::Error(Error::Position(ErrorKind::Alt, &b"$b;"[..])));
+    }
+}
@@ -0,0 +1,394 @@
+// Tagua VM
+//
+//
+// New BSD License
+//
+// Copyright © 2016-2016, Ivan Enderlin.
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//     * Redistributions of source code must retain the above copyright
+//       notice, this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright
+//       notice, this list of conditions and the following disclaimer in the
+//       documentation and/or other materials provided with the distribution.
+//     * Neither the name of the Hoa nor the names of its contributors may be
+//       used to endorse or promote products derived from this software without
+//       specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE
+// ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDERS AND CONTRIBUTORS BE
+// LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR
+// CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF
+// SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS
+// INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN
+// CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+
+//! Group of function declaration rules.
+//!
+//! The function declaration is provided by the PHP Language Specification
+//! in the [Grammar chapter, Functions
+//! section](https://github.com/php/php-langspec/blob/master/spec/19-grammar.md#function-definition).
+
+use std::result::Result as StdResult;
+use super::compound::block;
+use super::super::attributes::attributes;
+use super::super::comments::doc_comment;
+use super::super::expressions::primaries::parameters;
+use super::super::tokens::qualified_name;
+use super::super::types::return_type;
+use super::super::super::ast::{
+    Name,
+    Statement
+};
+use super::super::super::tokens;
+
+named!(
+    pub function_declaration<Statement>,
+    chain!(
+        doc_comment: doc_comment ~
+        attributes: attributes ~
+        first!(keyword!(tokens::FUNCTION)) ~
+        returns_reference: opt!(first!(tag!(tokens::REFERENCE))) ~
+        name: first!(function_name) ~
+        parameters: first!(parameters) ~
+        return_type: opt!(first!(spanned!(return_type))) ~
+        body: first!(block),
+        || {
+            Statement::Function {
+                doc_comment      : doc_comment,
+                attributes       : attributes,
+                returns_reference: returns_reference.is_some(),
+                name             : name,
+                parameters       : parameters,
+                return_type      : return_type,
+                body             : body
+            }
+        }
+    )
+);
+
+named!(
+    function_name<&[u8]>,
+    map_res!(
+        qualified_name,
+        function_name_mapper
+    )
+);
+
+#[inline(always)]
+fn function_name_mapper(name: Name) -> StdResult<&[u8], ()> {
+    match name {
+        Name::Unqualified(name) => Ok(name),
+        _ => Err(())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::function_declaration;
+    use super::super::statement;
+    use super::super::super::super::ast::{
+        Argument,
+        Attribute,
+        ConstantExpression,
+        Expression,
+        Literal,
+        Name,
+        Parameter,
+        Statement,
+        Type,
+        Variable
+    };
+    use super::super::super::super::internal::{
+        Error,
+        ErrorKind,
+        Result
+    };
+    use super::super::super::super::macros::ErrorKindCustom;
+    use super::super::super::super::span::Spanned;
+
+    #[test]
+    fn case_function() {
+        let input  = b"function foo() { return; }";
+        let output = Result::Done(
+            &b""[..],
+            Statement::Function {
+                doc_comment      : None,
+                attributes       : vec![],
+                returns_reference: false,
+                name             : &b"foo"[..],
+                parameters       : vec![],
+                return_type      : None,
+                body             : vec![Spanned::from(Statement::Return(None))]
+            }
+        );
+
+        assert_eq!(function_declaration(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_function_with_doc_comment() {
+        let input  = b"{\n    /** Foo. */\n    // Not a doc comment.\n    function foo() {}\n}";
+        let output = Result::Done(
+            &b""[..],
+            Statement::Block(vec![
+                Spanned::from(Statement::Function {
+                    doc_comment      : Some(b"/** Foo. */".to_vec()),
+                    attributes       : vec![],
+                    returns_reference: false,
+                    name             : &b"foo"[..],
+                    parameters       : vec![],
+                    return_type      : None,
+                    body             : vec![]
+                })
+            ])
+        );
+
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_function_with_the_last_doc_comment() {
+        let input  = b"{ /** Foo. */ /** Bar. */ #[A] function foo() {} }";
+        let output = Result::Done(
+            &b""[..],
+            Statement::Block(vec![
+                Spanned::from(Statement::Function {
+                    doc_comment      : Some(b"/** Bar. */".to_vec()),
+                    attributes       : vec![
+                        Attribute {
+                            name     : Spanned::from(Name::Unqualified(&b"A"[..])),
+                            arguments: vec![]
+                        }
+                    ],
+                    returns_reference: false,
+                    name             : &b"foo"[..],
+                    parameters       : vec![],
+                    return_type      : None,
+                    body             : vec![]
+                })
+            ])
+        );
+
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_function_without_doc_comment() {
+        let input  = b"{ /* Foo. */ /**/ /**Bar. */ function foo() {} }";
+        let output = Result::Done(
+            &b""[..],
+            Statement::Block(vec![
+                Spanned::from(Statement::Function {
+                    doc_comment      : None,
+                    attributes       : vec![],
+                    returns_reference: false,
+                    name             : &b"foo"[..],
+                    parameters       : vec![],
+                    return_type      : None,
+                    body             : vec![]
+                })
+            ])
+        );
+
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_function_doc_comment_of_a_previous_statement() {
+        let input  = b"{ /** Foo. */ $foo; function foo() {} }";
+        let output = Result::Done(
+            &b""[..],
+            Statement::Block(vec![
+                Spanned::from(Statement::Expression(Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..]))))),
+                Spanned::from(Statement::Function {
+                    doc_comment      : None,
+                    attributes       : vec![],
+                    returns_reference: false,
+                    name             : &b"foo"[..],
+                    parameters       : vec![],
+                    return_type      : None,
+                    body             : vec![]
+                })
+            ])
+        );
+
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_function_returning_a_reference() {
+        let input  = b"function &getRef() { return; }";
+        let output = Result::Done(
+            &b""[..],
+            Statement::Function {
+                doc_comment      : None,
+                attributes       : vec![],
+                returns_reference: true,
+                name             : &b"getRef"[..],
+                parameters       : vec![],
+                return_type      : None,
+                body             : vec![Spanned::from(Statement::Return(None))]
+            }
+        );
+
+        assert_eq!(function_declaration(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_function_with_attributes() {
+        let input  = b"#[Foo(Bar::class)] function foo() {}";
+        let output = Result::Done(
+            &b""[..],
+            Statement::Function {
+                doc_comment      : None,
+                attributes       : vec![
+                    Attribute {
+                        name     : Spanned::from(Name::Unqualified(&b"Foo"[..])),
+                        arguments: vec![
+                            Spanned::from(Argument {
+                                name       : None,
+                                is_unpacked: false,
+                                value      : Spanned::from(Expression::ClassNameConstant(Box::new(Spanned::from(Expression::Name(Name::Unqualified(&b"Bar"[..]))))))
+                            })
+                        ]
+                    }
+                ],
+                returns_reference: false,
+                name             : &b"foo"[..],
+                parameters       : vec![],
+                return_type      : None,
+                body             : vec![]
+            }
+        );
+
+        assert_eq!(function_declaration(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_function_with_parameters() {
+        let input  = b"FUNCTION foo(int $a, Bar $b = 42, &$c, ...$rest): Foo\\Bar {}";
+        let output = Result::Done(
+            &b""[..],
+            Statement::Function {
+                doc_comment      : None,
+                attributes       : vec![],
+                returns_reference: false,
+                name             : &b"foo"[..],
+                parameters       : vec![
+                    Spanned::from(Parameter {
+                        attributes    : vec![],
+                        visibility    : None,
+                        set_visibility: None,
+                        is_readonly   : false,
+                        ty            : Some(Spanned::from(Type::Int)),
+                        is_reference  : false,
+                        is_variadic   : false,
+                        name          : Variable::Name(&b"a"[..]),
+                        value         : None,
+                        hooks         : vec![]
+                    }),
+                    Spanned::from(Parameter {
+                        attributes    : vec![],
+                        visibility    : None,
+                        set_visibility: None,
+                        is_readonly   : false,
+                        ty            : Some(Spanned::from(Type::Name(Name::Unqualified(&b"Bar"[..])))),
+                        is_reference  : false,
+                        is_variadic   : false,
+                        name          : Variable::Name(&b"b"[..]),
+                        value         : Some(ConstantExpression(Spanned::from(Expression::Literal(Literal::Integer(42))))),
+                        hooks         : vec![]
+                    }),
+                    Spanned::from(Parameter {
+                        attributes    : vec![],
+                        visibility    : None,
+                        set_visibility: None,
+                        is_readonly   : false,
+                        ty            : None,
+                        is_reference  : true,
+                        is_variadic   : false,
+                        name          : Variable::Name(&b"c"[..]),
+                        value         : None,
+                        hooks         : vec![]
+                    }),
+                    Spanned::from(Parameter {
+                        attributes    : vec![],
+                        visibility    : None,
+                        set_visibility: None,
+                        is_readonly   : false,
+                        ty            : None,
+                        is_reference  : false,
+                        is_variadic   : true,
+                        name          : Variable::Name(&b"rest"[..]),
+                        value         : None,
+                        hooks         : vec![]
+                    })
+                ],
+                return_type      : Some(Spanned::from(Type::Name(Name::Qualified(vec![&b"Foo"[..], &b"Bar"[..]])))),
+                body             : vec![]
+            }
+        );
+
+        assert_eq!(function_declaration(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_function_with_nullable_return_type() {
+        let input  = b"function foo(): ?Foo\\Bar {}";
+        let output = Result::Done(
+            &b""[..],
+            Statement::Function {
+                doc_comment      : None,
+                attributes       : vec![],
+                returns_reference: false,
+                name             : &b"foo"[..],
+                parameters       : vec![],
+                return_type      : Some(Spanned::from(Type::Nullable(Box::new(Spanned::from(Type::Name(Name::Qualified(vec![&b"Foo"[..], &b"Bar"[..]]))))))),
+                body             : vec![]
+            }
+        );
+
+        assert_eq!(function_declaration(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_invalid_function_missing_name() {
+        let input = b"function () {}";
+
+        assert_eq!(function_declaration(input), Result::Error(Error::Code(ErrorKind::RegexpFind)));
+    }
+
+    #[test]
+    fn case_invalid_function_name_is_a_keyword() {
+        let input = b"function class() {}";
+
+        assert_eq!(function_declaration(input), Result::Error(Error::Position(ErrorKind::Custom(ErrorKindCustom::Exclude as u32), &b"class() {}"[..])));
+    }
+
+    #[test]
+    fn case_invalid_function_name_is_qualified() {
+        let input = b"function Foo\\bar() {}";
+
+        assert_eq!(function_declaration(input), Result::Error(Error::Position(ErrorKind::MapRes, &b"Foo\\bar() {}"[..])));
+    }
+
+    #[test]
+    fn case_invalid_function_missing_body() {
+        let input = b"function foo();";
+
+        assert_eq!(function_declaration(input), Result::Error(Error::Position(ErrorKind::Tag, &b";"[..])));
+    }
+}
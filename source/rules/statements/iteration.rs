@@ -0,0 +1,669 @@
+// Tagua VM
+//
+//
+// New BSD License
+//
+// Copyright © 2016-2016, Ivan Enderlin.
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//     * Redistributions of source code must retain the above copyright
+//       notice, this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright
+//       notice, this list of conditions and the following disclaimer in the
+//       documentation and/or other materials provided with the distribution.
+//     * Neither the name of the Hoa nor the names of its contributors may be
+//       used to endorse or promote products derived from this software without
+//       specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE
+// ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDERS AND CONTRIBUTORS BE
+// LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR
+// CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF
+// SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS
+// INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN
+// CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+
+//! Group of iteration statement rules.
+//!
+//! The list of all iteration statements is provided by the PHP Language
+//! Specification in the [Grammar chapter, Statements
+//! section](https://github.com/php/php-langspec/blob/master/spec/19-grammar.md#iteration-statements).
+
+use std::result::Result as StdResult;
+use super::{
+    alternative_syntax_block,
+    parenthesized_expression,
+    statement,
+    statement_terminator
+};
+use super::super::expressions::assignments::destructuring_mapper;
+use super::super::expressions::expression;
+use super::super::expressions::postfixes::postfix;
+use super::super::expressions::primaries::short_list;
+use super::super::expressions::unaries::modifiable_operand_mapper;
+use super::super::super::ast::{
+    Expression,
+    Statement
+};
+use super::super::super::internal::{
+    fold_into_vector,
+    into_vector_mapper
+};
+use super::super::super::span::Spanned;
+use super::super::super::tokens;
+
+named!(
+    pub while_statement<Statement>,
+    chain!(
+        keyword!(tokens::WHILE) ~
+        condition: first!(parenthesized_expression) ~
+        body: first!(
+            spanned!(
+                alt!(
+                    call!(alternative_syntax_block, tokens::ENDWHILE)
+                  | statement
+                )
+            )
+        ),
+        || {
+            Statement::While {
+                condition: condition,
+                body     : Box::new(body)
+            }
+        }
+    )
+);
+
+named!(
+    pub do_while_statement<Statement>,
+    chain!(
+        keyword!(tokens::DO) ~
+        body: first!(spanned!(statement)) ~
+        first!(keyword!(tokens::WHILE)) ~
+        condition: first!(parenthesized_expression) ~
+        first!(expect!("do_while_statement", &[tokens::SEMICOLON], statement_terminator)),
+        || {
+            Statement::DoWhile {
+                body     : Box::new(body),
+                condition: condition
+            }
+        }
+    )
+);
+
+named!(
+    pub for_statement<Statement>,
+    chain!(
+        keyword!(tokens::FOR) ~
+        first!(tag!(tokens::LEFT_PARENTHESIS)) ~
+        init: opt!(first!(for_expressions)) ~
+        first!(tag!(tokens::SEMICOLON)) ~
+        condition: opt!(first!(for_expressions)) ~
+        first!(tag!(tokens::SEMICOLON)) ~
+        step: opt!(first!(for_expressions)) ~
+        first!(expect!("for_statement", tokens::RIGHT_PARENTHESIS)) ~
+        body: first!(
+            spanned!(
+                alt!(
+                    call!(alternative_syntax_block, tokens::ENDFOR)
+                  | statement
+                )
+            )
+        ),
+        || {
+            Statement::For {
+                init     : init.unwrap_or(vec![]),
+                condition: condition.unwrap_or(vec![]),
+                step     : step.unwrap_or(vec![]),
+                body     : Box::new(body)
+            }
+        }
+    )
+);
+
+named!(
+    for_expressions< Vec<Spanned<Expression>> >,
+    chain!(
+        accumulator: map_res!(
+            spanned!(expression),
+            into_vector_mapper
+        ) ~
+        result: fold_many0!(
+            preceded!(
+                first!(tag!(tokens::COMMA)),
+                first!(spanned!(expression))
+            ),
+            accumulator,
+            fold_into_vector
+        ),
+        || { result }
+    )
+);
+
+named!(
+    pub foreach_statement<Statement>,
+    chain!(
+        keyword!(tokens::FOREACH) ~
+        first!(tag!(tokens::LEFT_PARENTHESIS)) ~
+        subject: first!(spanned!(expression)) ~
+        first!(keyword!(tokens::AS)) ~
+        binding: first!(
+            alt!(
+                chain!(
+                    key: spanned!(map_res!(postfix, modifiable_operand_mapper)) ~
+                    first!(tag!(tokens::MAP)) ~
+                    value: first!(foreach_value),
+                    || { (Some(key), value) }
+                )
+              | foreach_value => { |value| (None, value) }
+            )
+        ) ~
+        first!(expect!("foreach_statement", tokens::RIGHT_PARENTHESIS)) ~
+        body: first!(
+            spanned!(
+                alt!(
+                    call!(alternative_syntax_block, tokens::ENDFOREACH)
+                  | statement
+                )
+            )
+        ),
+        || {
+            let (key, (is_reference, value)) = binding;
+
+            Statement::Foreach {
+                subject     : subject,
+                key         : key,
+                value       : value,
+                is_reference: is_reference,
+                body        : Box::new(body)
+            }
+        }
+    )
+);
+
+named!(
+    foreach_value<(bool, Spanned<Expression>)>,
+    chain!(
+        reference: opt!(tag!(tokens::REFERENCE)) ~
+        value: first!(
+            spanned!(
+                map_res!(
+                    alt!(
+                        postfix
+                      | short_list
+                    ),
+                    foreach_value_mapper
+                )
+            )
+        ),
+        || { (reference.is_some(), value) }
+    )
+);
+
+#[inline(always)]
+fn foreach_value_mapper<'a>(value: Expression<'a>) -> StdResult<Expression<'a>, ()> {
+    match value {
+        Expression::Array(_) |
+        Expression::List(_) |
+        Expression::ShortList(_) => {
+            destructuring_mapper(value)
+        },
+
+        _ => {
+            modifiable_operand_mapper(value)
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        do_while_statement,
+        for_statement,
+        foreach_statement,
+        while_statement
+    };
+    use super::super::statement;
+    use super::super::super::super::ast::{
+        Expression,
+        Literal,
+        Name,
+        Statement,
+        Variable
+    };
+    use super::super::super::super::internal::{
+        Error,
+        ErrorKind,
+        Needed,
+        Result
+    };
+    use super::super::super::super::macros::ErrorKindCustom;
+    use super::super::super::super::span::Spanned;
+
+    #[test]
+    fn case_while() {
+        let input  = b"while ($foo) { {} }";
+        let output = Result::Done(
+            &b""[..],
+            Statement::While {
+                condition: Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..]))),
+                body     : Box::new(Spanned::from(Statement::Block(vec![Spanned::from(Statement::Block(vec![]))])))
+            }
+        );
+
+        assert_eq!(while_statement(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_while_without_braces() {
+        let input  = b"WHILE($foo)while($bar){}";
+        let output = Result::Done(
+            &b""[..],
+            Statement::While {
+                condition: Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..]))),
+                body     : Box::new(
+                    Spanned::from(Statement::While {
+                        condition: Spanned::from(Expression::Variable(Variable::Name(&b"bar"[..]))),
+                        body     : Box::new(Spanned::from(Statement::Block(vec![])))
+                    })
+                )
+            }
+        );
+
+        assert_eq!(while_statement(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_while_alternative_syntax() {
+        let input  = b"while ($foo): {} $bar; endwhile;";
+        let output = Result::Done(
+            &b""[..],
+            Statement::While {
+                condition: Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..]))),
+                body     : Box::new(
+                    Spanned::from(Statement::Block(vec![
+                        Spanned::from(Statement::Block(vec![])),
+                        Spanned::from(Statement::Expression(Spanned::from(Expression::Variable(Variable::Name(&b"bar"[..])))))
+                    ]))
+                )
+            }
+        );
+
+        assert_eq!(while_statement(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_invalid_while_alternative_syntax_missing_terminator() {
+        let input = b"while ($foo): endwhile {}";
+
+        assert_eq!(while_statement(input), Result::Error(Error::Position(ErrorKind::Alt, &b": endwhile {}"[..])));
+    }
+
+    #[test]
+    fn case_invalid_while_missing_body() {
+        let input = b"while ($foo)";
+
+        assert_eq!(while_statement(input), Result::Incomplete(Needed::Size(13)));
+    }
+
+    #[test]
+    fn case_do_while() {
+        let input  = b"do {} while ($foo);";
+        let output = Result::Done(
+            &b""[..],
+            Statement::DoWhile {
+                body     : Box::new(Spanned::from(Statement::Block(vec![]))),
+                condition: Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..])))
+            }
+        );
+
+        assert_eq!(do_while_statement(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_do_while_without_braces() {
+        let input  = b"do do {} while ($foo); while ($bar);";
+        let output = Result::Done(
+            &b""[..],
+            Statement::DoWhile {
+                body     : Box::new(
+                    Spanned::from(Statement::DoWhile {
+                        body     : Box::new(Spanned::from(Statement::Block(vec![]))),
+                        condition: Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..])))
+                    })
+                ),
+                condition: Spanned::from(Expression::Variable(Variable::Name(&b"bar"[..])))
+            }
+        );
+
+        assert_eq!(do_while_statement(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_do_while_terminated_by_a_closing_tag() {
+        let input  = b"do {} while ($foo) ?>";
+        let output = Result::Done(
+            &b"?>"[..],
+            Statement::DoWhile {
+                body     : Box::new(Spanned::from(Statement::Block(vec![]))),
+                condition: Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..])))
+            }
+        );
+
+        assert_eq!(do_while_statement(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_constant_starting_with_the_do_keyword() {
+        let input  = b"dox;";
+        let output = Result::Done(&b""[..], Statement::Expression(Spanned::from(Expression::Name(Name::Unqualified(&b"dox"[..])))));
+
+        assert_eq!(do_while_statement(input), Result::Error(Error::Position(ErrorKind::Custom(ErrorKindCustom::Keyword as u32), &input[..])));
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_invalid_do_while_missing_semicolon() {
+        let input = b"do {} while ($foo) {}";
+
+        assert_eq!(do_while_statement(input), Result::Error(Error::Position(ErrorKind::Alt, &b"{}"[..])));
+    }
+
+    #[test]
+    fn case_for() {
+        let input  = b"for ($i = 0, $j = 1; $i, $j; $i++, $j++) {}";
+        let output = Result::Done(
+            &b""[..],
+            Statement::For {
+                init     : vec![
+                    Spanned::from(Expression::Assignment {
+                        variable: Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"i"[..])))),
+                        value   : Box::new(Spanned::from(Expression::Literal(Literal::Integer(0i64))))
+                    }),
+                    Spanned::from(Expression::Assignment {
+                        variable: Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"j"[..])))),
+                        value   : Box::new(Spanned::from(Expression::Literal(Literal::Integer(1i64))))
+                    })
+                ],
+                condition: vec![
+                    Spanned::from(Expression::Variable(Variable::Name(&b"i"[..]))),
+                    Spanned::from(Expression::Variable(Variable::Name(&b"j"[..])))
+                ],
+                step     : vec![
+                    Spanned::from(Expression::PostIncrement(Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"i"[..])))))),
+                    Spanned::from(Expression::PostIncrement(Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"j"[..]))))))
+                ],
+                body     : Box::new(Spanned::from(Statement::Block(vec![])))
+            }
+        );
+
+        assert_eq!(for_statement(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_for_empty() {
+        let input  = b"FOR(;;)for ( ; /* foo */ ; ) {}";
+        let output = Result::Done(
+            &b""[..],
+            Statement::For {
+                init     : vec![],
+                condition: vec![],
+                step     : vec![],
+                body     : Box::new(
+                    Spanned::from(Statement::For {
+                        init     : vec![],
+                        condition: vec![],
+                        step     : vec![],
+                        body     : Box::new(Spanned::from(Statement::Block(vec![])))
+                    })
+                )
+            }
+        );
+
+        assert_eq!(for_statement(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_for_condition_only() {
+        let input  = b"for (; $i;) {}";
+        let output = Result::Done(
+            &b""[..],
+            Statement::For {
+                init     : vec![],
+                condition: vec![Spanned::from(Expression::Variable(Variable::Name(&b"i"[..])))],
+                step     : vec![],
+                body     : Box::new(Spanned::from(Statement::Block(vec![])))
+            }
+        );
+
+        assert_eq!(for_statement(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_for_alternative_syntax() {
+        let input  = b"for (;;): {} ENDFOR;";
+        let output = Result::Done(
+            &b""[..],
+            Statement::For {
+                init     : vec![],
+                condition: vec![],
+                step     : vec![],
+                body     : Box::new(Spanned::from(Statement::Block(vec![Spanned::from(Statement::Block(vec![]))])))
+            }
+        );
+
+        assert_eq!(for_statement(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_invalid_for_missing_semicolon() {
+        let input = b"for ($i; $i) {}";
+
+        assert_eq!(for_statement(input), Result::Error(Error::Position(ErrorKind::Tag, &b") {}"[..])));
+    }
+
+    #[test]
+    fn case_invalid_for_trailing_comma() {
+        let input = b"for ($i,;;) {}";
+
+        assert_eq!(for_statement(input), Result::Error(Error::Position(ErrorKind::Tag, &b",;;) {}"[..])));
+    }
+
+    #[test]
+    fn case_foreach_value() {
+        let input  = b"foreach ($foo as $value) {}";
+        let output = Result::Done(
+            &b""[..],
+            Statement::Foreach {
+                subject     : Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..]))),
+                key         : None,
+                value       : Spanned::from(Expression::Variable(Variable::Name(&b"value"[..]))),
+                is_reference: false,
+                body        : Box::new(Spanned::from(Statement::Block(vec![])))
+            }
+        );
+
+        assert_eq!(foreach_statement(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_foreach_key_and_value_by_reference() {
+        let input  = b"FOREACH([1] AS $key=>&$value->bar){}";
+        let output = Result::Done(
+            &b""[..],
+            Statement::Foreach {
+                subject     : Spanned::from(Expression::Array(vec![(None, Spanned::from(Expression::Literal(Literal::Integer(1i64))))])),
+                key         : Some(Spanned::from(Expression::Variable(Variable::Name(&b"key"[..])))),
+                value       : Spanned::from(Expression::PropertyFetch {
+                    object  : Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"value"[..])))),
+                    property: Box::new(Spanned::from(Expression::Name(Name::Unqualified(&b"bar"[..]))))
+                }),
+                is_reference: true,
+                body        : Box::new(Spanned::from(Statement::Block(vec![])))
+            }
+        );
+
+        assert_eq!(foreach_statement(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_foreach_destructuring() {
+        let input  = b"foreach ($foo as $key => [$a, 'b' => $b]) {}";
+        let output = Result::Done(
+            &b""[..],
+            Statement::Foreach {
+                subject     : Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..]))),
+                key         : Some(Spanned::from(Expression::Variable(Variable::Name(&b"key"[..])))),
+                value       : Spanned::from(Expression::ShortList(vec![
+                    Some((None, Spanned::from(Expression::Variable(Variable::Name(&b"a"[..]))))),
+                    Some((
+                        Some(Spanned::from(Expression::Literal(Literal::String(b"b".to_vec())))),
+                        Spanned::from(Expression::Variable(Variable::Name(&b"b"[..])))
+                    ))
+                ])),
+                is_reference: false,
+                body        : Box::new(Spanned::from(Statement::Block(vec![])))
+            }
+        );
+
+        assert_eq!(foreach_statement(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_foreach_destructuring_with_skipped_elements() {
+        let input  = b"foreach ($foo as [, $a]) {}";
+        let output = Result::Done(
+            &b""[..],
+            Statement::Foreach {
+                subject     : Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..]))),
+                key         : None,
+                value       : Spanned::from(Expression::ShortList(vec![
+                    None,
+                    Some((None, Spanned::from(Expression::Variable(Variable::Name(&b"a"[..])))))
+                ])),
+                is_reference: false,
+                body        : Box::new(Spanned::from(Statement::Block(vec![])))
+            }
+        );
+
+        assert_eq!(foreach_statement(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_foreach_destructuring_with_list() {
+        let input  = b"foreach ($foo as list($a, $b)) {}";
+        let output = Result::Done(
+            &b""[..],
+            Statement::Foreach {
+                subject     : Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..]))),
+                key         : None,
+                value       : Spanned::from(Expression::List(vec![
+                    Some((None, Spanned::from(Expression::Variable(Variable::Name(&b"a"[..]))))),
+                    Some((None, Spanned::from(Expression::Variable(Variable::Name(&b"b"[..])))))
+                ])),
+                is_reference: false,
+                body        : Box::new(Spanned::from(Statement::Block(vec![])))
+            }
+        );
+
+        assert_eq!(foreach_statement(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_foreach_destructuring_keyed_and_nested() {
+        let input  = b"foreach ($rows as ['id' => $id, 'tags' => [$first, &$second]]) {}";
+        let output = Result::Done(
+            &b""[..],
+            Statement::Foreach {
+                subject     : Spanned::from(Expression::Variable(Variable::Name(&b"rows"[..]))),
+                key         : None,
+                value       : Spanned::from(Expression::ShortList(vec![
+                    Some((
+                        Some(Spanned::from(Expression::Literal(Literal::String(b"id".to_vec())))),
+                        Spanned::from(Expression::Variable(Variable::Name(&b"id"[..])))
+                    )),
+                    Some((
+                        Some(Spanned::from(Expression::Literal(Literal::String(b"tags".to_vec())))),
+                        Spanned::from(Expression::ShortList(vec![
+                            Some((None, Spanned::from(Expression::Variable(Variable::Name(&b"first"[..]))))),
+                            Some((None, Spanned::from(Expression::Reference(Box::new(Spanned::from(Expression::Variable(Variable::Name(&b"second"[..]))))))))
+                        ]))
+                    ))
+                ])),
+                is_reference: false,
+                body        : Box::new(Spanned::from(Statement::Block(vec![])))
+            }
+        );
+
+        assert_eq!(foreach_statement(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_foreach_alternative_syntax() {
+        let input  = b"foreach ($foo as $bar): {} endforeach;";
+        let output = Result::Done(
+            &b""[..],
+            Statement::Foreach {
+                subject     : Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..]))),
+                key         : None,
+                value       : Spanned::from(Expression::Variable(Variable::Name(&b"bar"[..]))),
+                is_reference: false,
+                body        : Box::new(Spanned::from(Statement::Block(vec![Spanned::from(Statement::Block(vec![]))])))
+            }
+        );
+
+        assert_eq!(foreach_statement(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_invalid_foreach_alternative_syntax_wrong_end_keyword() {
+        let input = b"foreach ($foo as $bar): endfor;";
+
+        assert_eq!(foreach_statement(input), Result::Error(Error::Position(ErrorKind::Alt, &b": endfor;"[..])));
+    }
+
+    #[test]
+    fn case_invalid_foreach_value_not_modifiable() {
+        let input = b"foreach ($foo as 42) {}";
+
+        assert_eq!(foreach_statement(input), Result::Error(Error::Position(ErrorKind::Alt, &b"42) {}"[..])));
+    }
+
+    #[test]
+    fn case_invalid_foreach_destructuring_not_modifiable() {
+        let input = b"foreach ($foo as [$bar, 42]) {}";
+
+        assert_eq!(foreach_statement(input), Result::Error(Error::Position(ErrorKind::Alt, &b"[$bar, 42]) {}"[..])));
+    }
+
+    #[test]
+    fn case_invalid_foreach_key_by_reference() {
+        let input = b"foreach ($foo as &$key => $value) {}";
+
+        assert_eq!(foreach_statement(input), Result::Error(Error::Position(ErrorKind::Tag, &b"=> $value) {}"[..])));
+    }
+}
@@ -0,0 +1,354 @@
+// Tagua VM
+//
+//
+// New BSD License
+//
+// Copyright © 2016-2016, Ivan Enderlin.
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//     * Redistributions of source code must retain the above copyright
+//       notice, this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright
+//       notice, this list of conditions and the following disclaimer in the
+//       documentation and/or other materials provided with the distribution.
+//     * Neither the name of the Hoa nor the names of its contributors may be
+//       used to endorse or promote products derived from this software without
+//       specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE
+// ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDERS AND CONTRIBUTORS BE
+// LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR
+// CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF
+// SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS
+// INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN
+// CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+
+//! Group of jump statement rules.
+//!
+//! The list of all jump statements is provided by the PHP Language
+//! Specification in the [Grammar chapter, Statements
+//! section](https://github.com/php/php-langspec/blob/master/spec/19-grammar.md#jump-statements).
+
+use std::result::Result as StdResult;
+use super::statement_terminator;
+use super::super::expressions::expression;
+use super::super::literals::integer;
+use super::super::tokens::qualified_name;
+use super::super::super::ast::{
+    Literal,
+    Name,
+    Statement
+};
+use super::super::super::tokens;
+
+named!(
+    pub break_statement<Statement>,
+    chain!(
+        keyword!(tokens::BREAK) ~
+        level: opt!(first!(jump_level)) ~
+        first!(expect!("break_statement", &[tokens::SEMICOLON], statement_terminator)),
+        || { Statement::Break(level) }
+    )
+);
+
+named!(
+    pub continue_statement<Statement>,
+    chain!(
+        keyword!(tokens::CONTINUE) ~
+        level: opt!(first!(jump_level)) ~
+        first!(expect!("continue_statement", &[tokens::SEMICOLON], statement_terminator)),
+        || { Statement::Continue(level) }
+    )
+);
+
+named!(
+    jump_level<u64>,
+    map_res!(
+        integer,
+        jump_level_mapper
+    )
+);
+
+#[inline(always)]
+fn jump_level_mapper(level: Literal) -> StdResult<u64, ()> {
+    match level {
+        Literal::Integer(level) if level > 0 => Ok(level as u64),
+        _ => Err(())
+    }
+}
+
+named!(
+    pub return_statement<Statement>,
+    chain!(
+        keyword!(tokens::RETURN) ~
+        value: opt!(complete!(first!(spanned!(expression)))) ~
+        first!(expect!("return_statement", &[tokens::SEMICOLON], statement_terminator)),
+        || { Statement::Return(value) }
+    )
+);
+
+named!(
+    pub goto_statement<Statement>,
+    chain!(
+        keyword!(tokens::GOTO) ~
+        label: first!(label) ~
+        first!(expect!("goto_statement", &[tokens::SEMICOLON], statement_terminator)),
+        || { Statement::Goto(label) }
+    )
+);
+
+named!(
+    pub label_statement<Statement>,
+    chain!(
+        label: label ~
+        first!(tag!(tokens::LABEL_SEPARATOR)) ~
+        not!(complete!(tag!(tokens::LABEL_SEPARATOR))),
+        || { Statement::Label(label) }
+    )
+);
+
+named!(
+    label<&[u8]>,
+    map_res!(
+        qualified_name,
+        label_mapper
+    )
+);
+
+#[inline(always)]
+fn label_mapper(name: Name) -> StdResult<&[u8], ()> {
+    match name {
+        Name::Unqualified(name) => Ok(name),
+        _ => Err(())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        break_statement,
+        continue_statement,
+        goto_statement,
+        label_statement,
+        return_statement
+    };
+    use super::super::statement;
+    use super::super::super::super::ast::{
+        Expression,
+        Literal,
+        Name,
+        Statement,
+        Variable
+    };
+    use super::super::super::super::internal::{
+        Error,
+        ErrorKind,
+        Result
+    };
+    use super::super::super::super::macros::ErrorKindCustom;
+    use super::super::super::super::span::Spanned;
+
+    #[test]
+    fn case_break() {
+        let input  = b"break;";
+        let output = Result::Done(&b""[..], Statement::Break(None));
+
+        assert_eq!(break_statement(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_break_with_a_level() {
+        let input  = b"BREAK /* foo */ 0x2 ;";
+        let output = Result::Done(&b""[..], Statement::Break(Some(2)));
+
+        assert_eq!(break_statement(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_break_terminated_by_a_closing_tag() {
+        let input  = b"break 3 ?>";
+        let output = Result::Done(&b"?>"[..], Statement::Break(Some(3)));
+
+        assert_eq!(break_statement(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_invalid_break_with_a_zero_level() {
+        let input = b"break 0;";
+
+        assert_eq!(break_statement(input), Result::Error(Error::Position(ErrorKind::Alt, &b"0;"[..])));
+    }
+
+    #[test]
+    fn case_invalid_break_with_a_variable_level() {
+        let input = b"break $foo;";
+
+        assert_eq!(break_statement(input), Result::Error(Error::Position(ErrorKind::Alt, &b"$foo;"[..])));
+    }
+
+    #[test]
+    fn case_continue() {
+        let input  = b"continue;";
+        let output = Result::Done(&b""[..], Statement::Continue(None));
+
+        assert_eq!(continue_statement(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_continue_with_a_level() {
+        let input  = b"continue 2;";
+        let output = Result::Done(&b""[..], Statement::Continue(Some(2)));
+
+        assert_eq!(continue_statement(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_invalid_continue_with_a_negative_level() {
+        let input = b"continue -1;";
+
+        assert_eq!(continue_statement(input), Result::Error(Error::Position(ErrorKind::Alt, &b"-1;"[..])));
+    }
+
+    #[test]
+    fn case_return() {
+        let input  = b"return;";
+        let output = Result::Done(&b""[..], Statement::Return(None));
+
+        assert_eq!(return_statement(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_return_an_expression() {
+        let input  = b"RETURN [$foo, 42] ;";
+        let output = Result::Done(
+            &b""[..],
+            Statement::Return(
+                Some(
+                    Spanned::from(Expression::Array(vec![
+                        (None, Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..])))),
+                        (None, Spanned::from(Expression::Literal(Literal::Integer(42i64))))
+                    ]))
+                )
+            )
+        );
+
+        assert_eq!(return_statement(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_return_terminated_by_a_closing_tag() {
+        let input  = b"return $foo?>";
+        let output = Result::Done(&b"?>"[..], Statement::Return(Some(Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..]))))));
+
+        assert_eq!(return_statement(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_call_starting_with_the_return_keyword() {
+        let input  = b"returnValue();";
+        let output = Result::Done(
+            &b""[..],
+            Statement::Expression(
+                Spanned::from(Expression::Call {
+                    callee   : Box::new(Spanned::from(Expression::Name(Name::Unqualified(&b"returnValue"[..])))),
+                    arguments: vec![]
+                })
+            )
+        );
+
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_invalid_return_followed_by_a_name_byte() {
+        let input = b"return_value;";
+
+        assert_eq!(return_statement(input), Result::Error(Error::Position(ErrorKind::Custom(ErrorKindCustom::Keyword as u32), &input[..])));
+    }
+
+    #[test]
+    fn case_invalid_return_missing_semicolon() {
+        let input = b"return $foo $bar;";
+
+        assert_eq!(return_statement(input), Result::Error(Error::Position(ErrorKind::Alt, &b"$bar;"[..])));
+    }
+
+    #[test]
+    fn case_goto() {
+        let input  = b"GOTO /* foo */ bar;";
+        let output = Result::Done(&b""[..], Statement::Goto(&b"bar"[..]));
+
+        assert_eq!(goto_statement(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_invalid_goto_keyword() {
+        let input = b"goto default;";
+
+        assert_eq!(goto_statement(input), Result::Error(Error::Position(ErrorKind::Custom(ErrorKindCustom::Exclude as u32), &b"default;"[..])));
+    }
+
+    #[test]
+    fn case_constant_starting_with_the_goto_keyword() {
+        let input  = b"gotox;";
+        let output = Result::Done(&b""[..], Statement::Expression(Spanned::from(Expression::Name(Name::Unqualified(&b"gotox"[..])))));
+
+        assert_eq!(goto_statement(input), Result::Error(Error::Position(ErrorKind::Custom(ErrorKindCustom::Keyword as u32), &input[..])));
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_invalid_goto_qualified_name() {
+        let input = b"goto foo\\bar;";
+
+        assert_eq!(goto_statement(input), Result::Error(Error::Position(ErrorKind::MapRes, &b"foo\\bar;"[..])));
+    }
+
+    #[test]
+    fn case_label() {
+        let input  = b"foo :";
+        let output = Result::Done(&b""[..], Statement::Label(&b"foo"[..]));
+
+        assert_eq!(label_statement(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_label_followed_by_a_statement() {
+        let input  = b"foo: {}";
+        let output = Result::Done(&b" {}"[..], Statement::Label(&b"foo"[..]));
+
+        assert_eq!(label_statement(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_invalid_label_static_call() {
+        let input = b"foo::bar();";
+
+        assert_eq!(label_statement(input), Result::Error(Error::Position(ErrorKind::Not, &b":bar();"[..])));
+    }
+
+    #[test]
+    fn case_invalid_label_keyword() {
+        let input = b"default:";
+
+        assert_eq!(label_statement(input), Result::Error(Error::Position(ErrorKind::Custom(ErrorKindCustom::Exclude as u32), &input[..])));
+    }
+}
@@ -0,0 +1,185 @@
+// Tagua VM
+//
+//
+// New BSD License
+//
+// Copyright © 2016-2016, Ivan Enderlin.
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//     * Redistributions of source code must retain the above copyright
+//       notice, this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright
+//       notice, this list of conditions and the following disclaimer in the
+//       documentation and/or other materials provided with the distribution.
+//     * Neither the name of the Hoa nor the names of its contributors may be
+//       used to endorse or promote products derived from this software without
+//       specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE
+// ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDERS AND CONTRIBUTORS BE
+// LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR
+// CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF
+// SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS
+// INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN
+// CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+
+//! Group of statement rules.
+//!
+//! The list of all statements is provided by the PHP Language Specification
+//! in the [Grammar chapter, Statements
+//! section](https://github.com/php/php-langspec/blob/master/spec/19-grammar.md#statements).
+
+pub mod class;
+pub mod compound;
+pub mod constant;
+pub mod declare;
+pub mod exception;
+pub mod expression;
+pub mod function;
+pub mod iteration;
+pub mod jump;
+pub mod namespace;
+pub mod selection;
+pub mod variables;
+
+use self::class::{
+    class_declaration,
+    enum_declaration,
+    interface_declaration,
+    trait_declaration
+};
+use self::compound::{
+    compound_statement,
+    statements
+};
+use self::constant::constant_declaration;
+use self::declare::declare_statement;
+use self::exception::try_statement;
+use self::expression::{
+    echo_statement,
+    empty_statement,
+    expression_statement
+};
+use self::function::function_declaration;
+use self::iteration::{
+    do_while_statement,
+    for_statement,
+    foreach_statement,
+    while_statement
+};
+use self::jump::{
+    break_statement,
+    continue_statement,
+    goto_statement,
+    label_statement,
+    return_statement
+};
+use self::namespace::{
+    namespace_declaration,
+    use_statement
+};
+use self::selection::{
+    if_statement,
+    switch_statement
+};
+use self::variables::{
+    global_statement,
+    static_variables_statement
+};
+use super::expressions::expression;
+use super::super::ast::{
+    Expression,
+    Statement
+};
+use super::super::diagnostics;
+use super::super::internal::Result;
+use super::super::span::{
+    self,
+    Spanned
+};
+use super::super::tokens;
+
+/// Parse a statement. The diagnostics reported while parsing a
+/// backtracked statement are discarded, see `diagnostics::attempt`. The
+/// spans of the nested nodes are relative to the input, unless a datum has
+/// already been set, see `span::within`.
+pub fn statement(input: &[u8]) -> Result<&[u8], Statement> {
+    span::within(input, |input| diagnostics::attempt(input, any_statement))
+}
+
+named!(
+    any_statement<Statement>,
+    alt!(
+        compound_statement
+      | if_statement
+      | switch_statement
+      | while_statement
+      | do_while_statement
+      | foreach_statement
+      | for_statement
+      | break_statement
+      | continue_statement
+      | return_statement
+      | goto_statement
+      | try_statement
+      | declare_statement
+      | function_declaration
+      | class_declaration
+      | interface_declaration
+      | enum_declaration
+      | namespace_declaration
+      | use_statement
+      | constant_declaration
+      | trait_declaration
+      | global_statement
+      | static_variables_statement
+      | label_statement
+      | empty_statement
+      | echo_statement
+      | expression_statement
+    )
+);
+
+named!(
+    pub parenthesized_expression< Spanned<Expression> >,
+    preceded!(
+        tag!(tokens::LEFT_PARENTHESIS),
+        terminated!(
+            first!(spanned!(expression)),
+            first!(expect!("parenthesized_expression", tokens::RIGHT_PARENTHESIS))
+        )
+    )
+);
+
+/// Parse the body of a control structure written with the alternative
+/// syntax, i.e. `: statements end_keyword;`, into a block statement.
+pub fn alternative_syntax_block<'a>(input: &'a [u8], end_keyword: &[u8]) -> Result<&'a [u8], Statement<'a>> {
+    map!(
+        input,
+        preceded!(
+            tag!(tokens::ALTERNATIVE_SYNTAX),
+            terminated!(
+                statements,
+                preceded!(
+                    first!(keyword!(end_keyword)),
+                    first!(expect!("alternative_syntax_block", &[tokens::SEMICOLON], statement_terminator))
+                )
+            )
+        ),
+        Statement::Block
+    )
+}
+
+named!(
+    pub statement_terminator,
+    alt_complete!(
+        tag!(tokens::SEMICOLON)
+      | complete!(peek!(tag!(tokens::CLOSE_TAG)))
+    )
+);
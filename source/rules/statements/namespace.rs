@@ -0,0 +1,579 @@
+// Tagua VM
+//
+//
+// New BSD License
+//
+// Copyright © 2016-2016, Ivan Enderlin.
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//     * Redistributions of source code must retain the above copyright
+//       notice, this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright
+//       notice, this list of conditions and the following disclaimer in the
+//       documentation and/or other materials provided with the distribution.
+//     * Neither the name of the Hoa nor the names of its contributors may be
+//       used to endorse or promote products derived from this software without
+//       specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE
+// ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDERS AND CONTRIBUTORS BE
+// LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR
+// CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF
+// SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS
+// INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN
+// CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+
+//! Group of namespace rules.
+//!
+//! The namespace definition and the namespace use declaration are provided
+//! by the PHP Language Specification in the [Grammar chapter, Namespaces
+//! section](https://github.com/php/php-langspec/blob/master/spec/19-grammar.md#namespaces).
+
+use std::result::Result as StdResult;
+use super::compound::block;
+use super::statement_terminator;
+use super::super::tokens::{
+    name,
+    qualified_name
+};
+use super::super::super::ast::{
+    Name,
+    Statement,
+    Use,
+    UseKind
+};
+use super::super::super::internal::{
+    Result,
+    fold_into_vector,
+    into_vector_mapper
+};
+use super::super::super::span::Spanned;
+use super::super::super::tokens;
+
+named!(
+    pub namespace_declaration<Statement>,
+    map_res!(
+        chain!(
+            keyword!(tokens::NAMESPACE) ~
+            name: opt!(first!(spanned!(namespace_name))) ~
+            body: first!(
+                alt!(
+                    statement_terminator => { |_| None }
+                  | block => { Some }
+                )
+            ),
+            || { (name, body) }
+        ),
+        namespace_declaration_mapper
+    )
+);
+
+/// A namespace declaration without a body applies to the rest of the file,
+/// thus it must be named.
+#[inline(always)]
+fn namespace_declaration_mapper<'a>((name, body): (Option<Spanned<Name<'a>>>, Option<Vec<Spanned<Statement<'a>>>>)) -> StdResult<Statement<'a>, ()> {
+    if name.is_none() && body.is_none() {
+        return Err(());
+    }
+
+    Ok(
+        Statement::Namespace {
+            name: name,
+            body: body
+        }
+    )
+}
+
+named!(
+    namespace_name<Name>,
+    map_res!(
+        qualified_name,
+        namespace_name_mapper
+    )
+);
+
+#[inline(always)]
+fn namespace_name_mapper(name: Name) -> StdResult<Name, ()> {
+    match name {
+        Name::Unqualified(_) | Name::Qualified(_) => Ok(name),
+        _ => Err(())
+    }
+}
+
+
+named!(
+    pub use_statement<Statement>,
+    chain!(
+        keyword!(tokens::USE) ~
+        kind: opt!(first!(use_kind)) ~
+        uses: first!(
+            alt!(
+                call!(group_use, kind)
+              | call!(use_clauses, kind)
+            )
+        ) ~
+        first!(expect!("use_statement", &[tokens::SEMICOLON], statement_terminator)),
+        || { Statement::Use(uses) }
+    )
+);
+
+named!(
+    use_kind<UseKind>,
+    map_res!(
+        name,
+        use_kind_mapper
+    )
+);
+
+/// The kind is recognized as a whole name so that a namespace like
+/// `functions` is not split.
+#[inline(always)]
+fn use_kind_mapper(kind: &[u8]) -> StdResult<UseKind, ()> {
+    if kind.eq_ignore_ascii_case(tokens::FUNCTION) {
+        Ok(UseKind::Function)
+    } else if kind.eq_ignore_ascii_case(tokens::CONST) {
+        Ok(UseKind::Constant)
+    } else {
+        Err(())
+    }
+}
+
+fn use_clauses(input: &[u8], kind: Option<UseKind>) -> Result<&[u8], Vec<Use>> {
+    chain!(
+        input,
+        accumulator: map_res!(
+            call!(use_clause, kind),
+            into_vector_mapper
+        ) ~
+        uses: fold_many0!(
+            preceded!(
+                first!(tag!(tokens::COMMA)),
+                first!(call!(use_clause, kind))
+            ),
+            accumulator,
+            fold_into_vector
+        ),
+        || { uses }
+    )
+}
+
+fn use_clause(input: &[u8], kind: Option<UseKind>) -> Result<&[u8], Use> {
+    chain!(
+        input,
+        name: spanned!(map_res!(qualified_name, use_name_mapper)) ~
+        alias: opt!(first!(use_alias)),
+        || {
+            Use {
+                kind : kind.unwrap_or(UseKind::Class),
+                name : name,
+                alias: alias
+            }
+        }
+    )
+}
+
+#[inline(always)]
+fn use_name_mapper(name: Name) -> StdResult<Name, ()> {
+    match name {
+        Name::RelativeQualified(_) => Err(()),
+        _ => Ok(name)
+    }
+}
+
+named!(
+    use_alias<&[u8]>,
+    preceded!(
+        keyword!(tokens::AS),
+        first!(
+            map_res!(
+                qualified_name,
+                use_alias_mapper
+            )
+        )
+    )
+);
+
+#[inline(always)]
+fn use_alias_mapper(name: Name) -> StdResult<&[u8], ()> {
+    match name {
+        Name::Unqualified(name) => Ok(name),
+        _ => Err(())
+    }
+}
+
+/// A group use, e.g. `use Foo\{Bar, function baz};`. The kind of the
+/// imported names can be declared for the whole group or for each name,
+/// but not both.
+fn group_use(input: &[u8], kind: Option<UseKind>) -> Result<&[u8], Vec<Use>> {
+    chain!(
+        input,
+        prefix: map_res!(qualified_name, use_name_mapper) ~
+        first!(tag!(tokens::NAMESPACE_SEPARATOR)) ~
+        first!(tag!(tokens::LEFT_CURLY_BRACKET)) ~
+        accumulator: map_res!(
+            first!(call!(group_use_element, kind, &prefix)),
+            into_vector_mapper
+        ) ~
+        uses: fold_many0!(
+            preceded!(
+                first!(tag!(tokens::COMMA)),
+                first!(call!(group_use_element, kind, &prefix))
+            ),
+            accumulator,
+            fold_into_vector
+        ) ~
+        opt!(first!(tag!(tokens::COMMA))) ~
+        first!(expect!("group_use", tokens::RIGHT_CURLY_BRACKET)),
+        || { uses }
+    )
+}
+
+fn group_use_element<'a>(input: &'a [u8], group_kind: Option<UseKind>, prefix: &Name<'a>) -> Result<&'a [u8], Use<'a>> {
+    map_res!(
+        input,
+        chain!(
+            kind: opt!(use_kind) ~
+            name: first!(spanned!(qualified_name)) ~
+            alias: opt!(first!(use_alias)),
+            || { (kind, name, alias) }
+        ),
+        |(kind, name, alias): (Option<UseKind>, Spanned<Name<'a>>, Option<&'a [u8]>)| -> StdResult<Use<'a>, ()> {
+            if kind.is_some() && group_kind.is_some() {
+                return Err(());
+            }
+
+            let mut parts = match *prefix {
+                Name::Unqualified(part) => vec![part],
+                Name::Qualified(ref parts) | Name::FullyQualified(ref parts) => parts.clone(),
+                _ => return Err(())
+            };
+
+            let span = name.span;
+
+            match name.node {
+                Name::Unqualified(part) => parts.push(part),
+                Name::Qualified(mut element_parts) => parts.append(&mut element_parts),
+                _ => return Err(())
+            }
+
+            Ok(
+                Use {
+                    kind : kind.or(group_kind).unwrap_or(UseKind::Class),
+                    name : Spanned::new(
+                        match *prefix {
+                            Name::FullyQualified(_) => Name::FullyQualified(parts),
+                            _ => Name::Qualified(parts)
+                        },
+                        span
+                    ),
+                    alias: alias
+                }
+            )
+        }
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        namespace_declaration,
+        use_statement
+    };
+    use super::super::statement;
+    use super::super::super::super::ast::{
+        Expression,
+        Name,
+        Statement,
+        Use,
+        UseKind
+    };
+    use super::super::super::super::internal::{
+        Error,
+        ErrorKind,
+        Result
+    };
+    use super::super::super::super::macros::ErrorKindCustom;
+    use super::super::super::super::span::Spanned;
+
+    #[test]
+    fn case_namespace_without_body() {
+        let input  = b"namespace Foo\\Bar;";
+        let output = Result::Done(
+            &b""[..],
+            Statement::Namespace {
+                name: Some(Spanned::from(Name::Qualified(vec![&b"Foo"[..], &b"Bar"[..]]))),
+                body: None
+            }
+        );
+
+        assert_eq!(namespace_declaration(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_namespace_with_body() {
+        let input  = b"namespace Foo { function f() {} }";
+        let output = Result::Done(
+            &b""[..],
+            Statement::Namespace {
+                name: Some(Spanned::from(Name::Unqualified(&b"Foo"[..]))),
+                body: Some(
+                    vec![
+                        Spanned::from(Statement::Function {
+                            doc_comment      : None,
+                            attributes       : vec![],
+                            returns_reference: false,
+                            name             : &b"f"[..],
+                            parameters       : vec![],
+                            return_type      : None,
+                            body             : vec![]
+                        })
+                    ]
+                )
+            }
+        );
+
+        assert_eq!(namespace_declaration(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_namespace_global() {
+        let input  = b"namespace {}";
+        let output = Result::Done(
+            &b""[..],
+            Statement::Namespace {
+                name: None,
+                body: Some(vec![])
+            }
+        );
+
+        assert_eq!(namespace_declaration(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_invalid_namespace_global_without_body() {
+        let input = b"namespace;";
+
+        assert_eq!(namespace_declaration(input), Result::Error(Error::Position(ErrorKind::MapRes, &input[..])));
+    }
+
+    #[test]
+    fn case_invalid_namespace_fully_qualified() {
+        let input = b"namespace \\Foo;";
+
+        assert_eq!(namespace_declaration(input), Result::Error(Error::Position(ErrorKind::Alt, &b"\\Foo;"[..])));
+    }
+
+    #[test]
+    fn case_relative_name_is_not_a_namespace_declaration() {
+        let input  = b"namespace\\f();";
+        let output = Result::Done(
+            &b""[..],
+            Statement::Expression(
+                Spanned::from(Expression::Call {
+                    callee   : Box::new(Spanned::from(Expression::Name(Name::RelativeQualified(vec![&b"f"[..]])))),
+                    arguments: vec![]
+                })
+            )
+        );
+
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_constant_starting_with_the_namespace_keyword() {
+        let input  = b"namespaced;";
+        let output = Result::Done(&b""[..], Statement::Expression(Spanned::from(Expression::Name(Name::Unqualified(&b"namespaced"[..])))));
+
+        assert_eq!(namespace_declaration(input), Result::Error(Error::Position(ErrorKind::Custom(ErrorKindCustom::Keyword as u32), &input[..])));
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_use() {
+        let input  = b"use Foo\\Bar, \\Baz as Qux;";
+        let output = Result::Done(
+            &b""[..],
+            Statement::Use(
+                vec![
+                    Use {
+                        kind : UseKind::Class,
+                        name : Spanned::from(Name::Qualified(vec![&b"Foo"[..], &b"Bar"[..]])),
+                        alias: None
+                    },
+                    Use {
+                        kind : UseKind::Class,
+                        name : Spanned::from(Name::FullyQualified(vec![&b"Baz"[..]])),
+                        alias: Some(&b"Qux"[..])
+                    }
+                ]
+            )
+        );
+
+        assert_eq!(use_statement(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_use_function() {
+        let input  = b"use function foo\\len;";
+        let output = Result::Done(
+            &b""[..],
+            Statement::Use(
+                vec![
+                    Use {
+                        kind : UseKind::Function,
+                        name : Spanned::from(Name::Qualified(vec![&b"foo"[..], &b"len"[..]])),
+                        alias: None
+                    }
+                ]
+            )
+        );
+
+        assert_eq!(use_statement(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_use_constant() {
+        let input  = b"USE CONST Foo\\BAR AS BAZ;";
+        let output = Result::Done(
+            &b""[..],
+            Statement::Use(
+                vec![
+                    Use {
+                        kind : UseKind::Constant,
+                        name : Spanned::from(Name::Qualified(vec![&b"Foo"[..], &b"BAR"[..]])),
+                        alias: Some(&b"BAZ"[..])
+                    }
+                ]
+            )
+        );
+
+        assert_eq!(use_statement(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_use_namespace_starting_like_a_kind() {
+        let input  = b"use functions\\foo;";
+        let output = Result::Done(
+            &b""[..],
+            Statement::Use(
+                vec![
+                    Use {
+                        kind : UseKind::Class,
+                        name : Spanned::from(Name::Qualified(vec![&b"functions"[..], &b"foo"[..]])),
+                        alias: None
+                    }
+                ]
+            )
+        );
+
+        assert_eq!(use_statement(input), output);
+    }
+
+    #[test]
+    fn case_use_group_with_mixed_kinds() {
+        let input  = b"use Foo\\{Bar, Baz\\Qux as Q, function f, const C,};";
+        let output = Result::Done(
+            &b""[..],
+            Statement::Use(
+                vec![
+                    Use {
+                        kind : UseKind::Class,
+                        name : Spanned::from(Name::Qualified(vec![&b"Foo"[..], &b"Bar"[..]])),
+                        alias: None
+                    },
+                    Use {
+                        kind : UseKind::Class,
+                        name : Spanned::from(Name::Qualified(vec![&b"Foo"[..], &b"Baz"[..], &b"Qux"[..]])),
+                        alias: Some(&b"Q"[..])
+                    },
+                    Use {
+                        kind : UseKind::Function,
+                        name : Spanned::from(Name::Qualified(vec![&b"Foo"[..], &b"f"[..]])),
+                        alias: None
+                    },
+                    Use {
+                        kind : UseKind::Constant,
+                        name : Spanned::from(Name::Qualified(vec![&b"Foo"[..], &b"C"[..]])),
+                        alias: None
+                    }
+                ]
+            )
+        );
+
+        assert_eq!(use_statement(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_use_group_of_functions() {
+        let input  = b"use function \\Foo\\{f, g};";
+        let output = Result::Done(
+            &b""[..],
+            Statement::Use(
+                vec![
+                    Use {
+                        kind : UseKind::Function,
+                        name : Spanned::from(Name::FullyQualified(vec![&b"Foo"[..], &b"f"[..]])),
+                        alias: None
+                    },
+                    Use {
+                        kind : UseKind::Function,
+                        name : Spanned::from(Name::FullyQualified(vec![&b"Foo"[..], &b"g"[..]])),
+                        alias: None
+                    }
+                ]
+            )
+        );
+
+        assert_eq!(use_statement(input), output);
+    }
+
+    #[test]
+    fn case_invalid_use_group_with_kinds_declared_twice() {
+        let input = b"use function Foo\\{function f};";
+
+        assert_eq!(use_statement(input), Result::Error(Error::Position(ErrorKind::Alt, &b"\\{function f};"[..])));
+    }
+
+    #[test]
+    fn case_invalid_use_group_with_a_fully_qualified_element() {
+        let input = b"use Foo\\{\\Bar};";
+
+        assert_eq!(use_statement(input), Result::Error(Error::Position(ErrorKind::Alt, &b"\\{\\Bar};"[..])));
+    }
+
+    #[test]
+    fn case_invalid_use_relative_name() {
+        let input = b"use namespace\\Foo;";
+
+        assert_eq!(use_statement(input), Result::Error(Error::Position(ErrorKind::Alt, &b"namespace\\Foo;"[..])));
+    }
+
+    #[test]
+    fn case_invalid_use_alias_is_qualified() {
+        let input = b"use Foo as Bar\\Baz;";
+
+        assert_eq!(use_statement(input), Result::Error(Error::Position(ErrorKind::Alt, &b"as Bar\\Baz;"[..])));
+    }
+
+    #[test]
+    fn case_constant_starting_with_the_use_keyword() {
+        let input  = b"use_x;";
+        let output = Result::Done(&b""[..], Statement::Expression(Spanned::from(Expression::Name(Name::Unqualified(&b"use_x"[..])))));
+
+        assert_eq!(use_statement(input), Result::Error(Error::Position(ErrorKind::Custom(ErrorKindCustom::Keyword as u32), &input[..])));
+        assert_eq!(statement(input), output);
+    }
+}
@@ -0,0 +1,615 @@
+// Tagua VM
+//
+//
+// New BSD License
+//
+// Copyright © 2016-2016, Ivan Enderlin.
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//     * Redistributions of source code must retain the above copyright
+//       notice, this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright
+//       notice, this list of conditions and the following disclaimer in the
+//       documentation and/or other materials provided with the distribution.
+//     * Neither the name of the Hoa nor the names of its contributors may be
+//       used to endorse or promote products derived from this software without
+//       specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE
+// ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDERS AND CONTRIBUTORS BE
+// LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR
+// CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF
+// SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS
+// INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN
+// CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+
+//! Group of selection statement rules.
+//!
+//! The list of all selection statements is provided by the PHP Language
+//! Specification in the [Grammar chapter, Statements
+//! section](https://github.com/php/php-langspec/blob/master/spec/19-grammar.md#selection-statements).
+
+use std::result::Result as StdResult;
+use super::{
+    parenthesized_expression,
+    statement,
+    statement_terminator
+};
+use super::compound::statements;
+use super::super::expressions::expression;
+use super::super::super::ast::{
+    Expression,
+    Statement,
+    SwitchCase
+};
+use super::super::super::internal::fold_into_vector;
+use super::super::super::span::Spanned;
+use super::super::super::tokens;
+
+named!(
+    pub if_statement<Statement>,
+    chain!(
+        keyword!(tokens::IF) ~
+        condition: first!(parenthesized_expression) ~
+        clauses: first!(
+            alt!(
+                if_alternative_syntax_clauses
+              | if_clauses
+            )
+        ),
+        || {
+            let (then, elseifs, otherwise) = clauses;
+
+            Statement::If {
+                condition: condition,
+                then     : Box::new(then),
+                elseifs  : elseifs,
+                otherwise: otherwise.map(Box::new)
+            }
+        }
+    )
+);
+
+named!(
+    if_clauses<(Spanned<Statement>, Vec<(Spanned<Expression>, Spanned<Statement>)>, Option<Spanned<Statement>>)>,
+    chain!(
+        then: spanned!(statement) ~
+        elseifs: fold_many0!(
+            complete!(
+                chain!(
+                    first!(keyword!(tokens::ELSEIF)) ~
+                    condition: first!(parenthesized_expression) ~
+                    body: first!(spanned!(statement)),
+                    || { (condition, body) }
+                )
+            ),
+            Vec::new(),
+            fold_into_vector
+        ) ~
+        otherwise: opt!(
+            complete!(
+                preceded!(
+                    first!(keyword!(tokens::ELSE)),
+                    first!(spanned!(statement))
+                )
+            )
+        ),
+        || { (then, elseifs, otherwise) }
+    )
+);
+
+named!(
+    if_alternative_syntax_clauses<(Spanned<Statement>, Vec<(Spanned<Expression>, Spanned<Statement>)>, Option<Spanned<Statement>>)>,
+    chain!(
+        then: spanned!(alternative_syntax_statements) ~
+        elseifs: fold_many0!(
+            complete!(
+                chain!(
+                    first!(keyword!(tokens::ELSEIF)) ~
+                    condition: first!(parenthesized_expression) ~
+                    body: first!(spanned!(alternative_syntax_statements)),
+                    || { (condition, body) }
+                )
+            ),
+            Vec::new(),
+            fold_into_vector
+        ) ~
+        otherwise: opt!(
+            complete!(
+                preceded!(
+                    first!(keyword!(tokens::ELSE)),
+                    first!(spanned!(alternative_syntax_statements))
+                )
+            )
+        ) ~
+        first!(keyword!(tokens::ENDIF)) ~
+        first!(expect!("if_alternative_syntax_clauses", &[tokens::SEMICOLON], statement_terminator)),
+        || { (then, elseifs, otherwise) }
+    )
+);
+
+named!(
+    alternative_syntax_statements<Statement>,
+    preceded!(
+        tag!(tokens::ALTERNATIVE_SYNTAX),
+        map!(statements, Statement::Block)
+    )
+);
+
+named!(
+    pub switch_statement<Statement>,
+    chain!(
+        keyword!(tokens::SWITCH) ~
+        subject: first!(parenthesized_expression) ~
+        cases: first!(
+            alt!(
+                preceded!(
+                    tag!(tokens::LEFT_CURLY_BRACKET),
+                    terminated!(
+                        switch_cases,
+                        first!(expect!("switch_statement", tokens::RIGHT_CURLY_BRACKET))
+                    )
+                )
+              | preceded!(
+                    tag!(tokens::ALTERNATIVE_SYNTAX),
+                    terminated!(
+                        switch_cases,
+                        preceded!(
+                            first!(keyword!(tokens::ENDSWITCH)),
+                            first!(expect!("switch_statement", &[tokens::SEMICOLON], statement_terminator))
+                        )
+                    )
+                )
+            )
+        ),
+        || {
+            Statement::Switch {
+                subject: subject,
+                cases  : cases
+            }
+        }
+    )
+);
+
+named!(
+    switch_cases< Vec<SwitchCase> >,
+    map_res!(
+        preceded!(
+            opt!(complete!(first!(tag!(tokens::SEMICOLON)))),
+            fold_many0!(
+                complete!(first!(switch_case)),
+                Vec::new(),
+                fold_into_vector
+            )
+        ),
+        switch_cases_mapper
+    )
+);
+
+/// A switch has at most one default case.
+#[inline(always)]
+fn switch_cases_mapper(cases: Vec<SwitchCase>) -> StdResult<Vec<SwitchCase>, ()> {
+    if cases.iter().filter(|case| case.condition.is_none()).count() > 1 {
+        Err(())
+    } else {
+        Ok(cases)
+    }
+}
+
+named!(
+    switch_case<SwitchCase>,
+    chain!(
+        condition: alt!(
+            preceded!(
+                keyword!(tokens::CASE),
+                map!(first!(spanned!(expression)), Some)
+            )
+          | keyword!(tokens::DEFAULT) => { |_| None }
+        ) ~
+        first!(
+            alt!(
+                tag!(tokens::CASE_SEPARATOR)
+              | tag!(tokens::SEMICOLON)
+            )
+        ) ~
+        body: statements,
+        || {
+            SwitchCase {
+                condition: condition,
+                body     : body
+            }
+        }
+    )
+);
+
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        if_statement,
+        switch_case,
+        switch_statement
+    };
+    use super::super::statement;
+    use super::super::super::super::ast::{
+        Expression,
+        Literal,
+        Name,
+        Statement,
+        SwitchCase,
+        Variable
+    };
+    use super::super::super::super::internal::{
+        Error,
+        ErrorKind,
+        Needed,
+        Result
+    };
+    use super::super::super::super::span::Spanned;
+
+    #[test]
+    fn case_if() {
+        let input  = b"if ($foo) {}";
+        let output = Result::Done(
+            &b""[..],
+            Statement::If {
+                condition: Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..]))),
+                then     : Box::new(Spanned::from(Statement::Block(vec![]))),
+                elseifs  : vec![],
+                otherwise: None
+            }
+        );
+
+        assert_eq!(if_statement(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_if_else() {
+        let input  = b"IF(42){}ELSE{}";
+        let output = Result::Done(
+            &b""[..],
+            Statement::If {
+                condition: Spanned::from(Expression::Literal(Literal::Integer(42i64))),
+                then     : Box::new(Spanned::from(Statement::Block(vec![]))),
+                elseifs  : vec![],
+                otherwise: Some(Box::new(Spanned::from(Statement::Block(vec![]))))
+            }
+        );
+
+        assert_eq!(if_statement(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_if_elseifs_else() {
+        let input  = b"if ($a) {} elseif ($b) {} /* foo */ elseif ($c) {} else {}";
+        let output = Result::Done(
+            &b""[..],
+            Statement::If {
+                condition: Spanned::from(Expression::Variable(Variable::Name(&b"a"[..]))),
+                then     : Box::new(Spanned::from(Statement::Block(vec![]))),
+                elseifs  : vec![
+                    (Spanned::from(Expression::Variable(Variable::Name(&b"b"[..]))), Spanned::from(Statement::Block(vec![]))),
+                    (Spanned::from(Expression::Variable(Variable::Name(&b"c"[..]))), Spanned::from(Statement::Block(vec![])))
+                ],
+                otherwise: Some(Box::new(Spanned::from(Statement::Block(vec![]))))
+            }
+        );
+
+        assert_eq!(if_statement(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_if_else_if() {
+        let input  = b"if ($a) {} else if ($b) {}";
+        let output = Result::Done(
+            &b""[..],
+            Statement::If {
+                condition: Spanned::from(Expression::Variable(Variable::Name(&b"a"[..]))),
+                then     : Box::new(Spanned::from(Statement::Block(vec![]))),
+                elseifs  : vec![],
+                otherwise: Some(
+                    Box::new(
+                        Spanned::from(Statement::If {
+                            condition: Spanned::from(Expression::Variable(Variable::Name(&b"b"[..]))),
+                            then     : Box::new(Spanned::from(Statement::Block(vec![]))),
+                            elseifs  : vec![],
+                            otherwise: None
+                        })
+                    )
+                )
+            }
+        );
+
+        assert_eq!(if_statement(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_if_without_braces() {
+        let input  = b"if ($a) if ($b) {} else {}";
+        let output = Result::Done(
+            &b""[..],
+            Statement::If {
+                condition: Spanned::from(Expression::Variable(Variable::Name(&b"a"[..]))),
+                then     : Box::new(
+                    Spanned::from(Statement::If {
+                        condition: Spanned::from(Expression::Variable(Variable::Name(&b"b"[..]))),
+                        then     : Box::new(Spanned::from(Statement::Block(vec![]))),
+                        elseifs  : vec![],
+                        otherwise: Some(Box::new(Spanned::from(Statement::Block(vec![]))))
+                    })
+                ),
+                elseifs  : vec![],
+                otherwise: None
+            }
+        );
+
+        assert_eq!(if_statement(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_if_alternative_syntax() {
+        let input  = b"if ($foo): $a; {} elseif ($bar): ElseIf ($baz): else: $b; endif;";
+        let output = Result::Done(
+            &b""[..],
+            Statement::If {
+                condition: Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..]))),
+                then     : Box::new(
+                    Spanned::from(Statement::Block(vec![
+                        Spanned::from(Statement::Expression(Spanned::from(Expression::Variable(Variable::Name(&b"a"[..]))))),
+                        Spanned::from(Statement::Block(vec![]))
+                    ]))
+                ),
+                elseifs  : vec![
+                    (Spanned::from(Expression::Variable(Variable::Name(&b"bar"[..]))), Spanned::from(Statement::Block(vec![]))),
+                    (Spanned::from(Expression::Variable(Variable::Name(&b"baz"[..]))), Spanned::from(Statement::Block(vec![])))
+                ],
+                otherwise: Some(
+                    Box::new(
+                        Spanned::from(Statement::Block(vec![
+                            Spanned::from(Statement::Expression(Spanned::from(Expression::Variable(Variable::Name(&b"b"[..])))))
+                        ]))
+                    )
+                )
+            }
+        );
+
+        assert_eq!(if_statement(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_if_alternative_syntax_terminated_by_a_closing_tag() {
+        let input  = b"if ($foo): endif ?>";
+        let output = Result::Done(
+            &b"?>"[..],
+            Statement::If {
+                condition: Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..]))),
+                then     : Box::new(Spanned::from(Statement::Block(vec![]))),
+                elseifs  : vec![],
+                otherwise: None
+            }
+        );
+
+        assert_eq!(if_statement(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_if_followed_by_a_name_starting_with_the_else_keyword() {
+        let input  = b"if ($a) $b; elsewhere;";
+        let output = Result::Done(
+            &b" elsewhere;"[..],
+            Statement::If {
+                condition: Spanned::from(Expression::Variable(Variable::Name(&b"a"[..]))),
+                then     : Box::new(Spanned::from(Statement::Expression(Spanned::from(Expression::Variable(Variable::Name(&b"b"[..])))))),
+                elseifs  : vec![],
+                otherwise: None
+            }
+        );
+
+        assert_eq!(if_statement(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_if_followed_by_a_call_starting_with_the_elseif_keyword() {
+        let input  = b"if ($a) {} elseifx($b);";
+        let output = Result::Done(
+            &b" elseifx($b);"[..],
+            Statement::If {
+                condition: Spanned::from(Expression::Variable(Variable::Name(&b"a"[..]))),
+                then     : Box::new(Spanned::from(Statement::Block(vec![]))),
+                elseifs  : vec![],
+                otherwise: None
+            }
+        );
+
+        assert_eq!(if_statement(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_if_alternative_syntax_with_a_name_starting_with_the_endif_keyword() {
+        let input  = b"if ($a): endiff; endif;";
+        let output = Result::Done(
+            &b""[..],
+            Statement::If {
+                condition: Spanned::from(Expression::Variable(Variable::Name(&b"a"[..]))),
+                then     : Box::new(Spanned::from(Statement::Block(vec![Spanned::from(Statement::Expression(Spanned::from(Expression::Name(Name::Unqualified(&b"endiff"[..])))))]))),
+                elseifs  : vec![],
+                otherwise: None
+            }
+        );
+
+        assert_eq!(if_statement(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_invalid_if_alternative_syntax_missing_endif() {
+        let input = b"if ($foo): {} else: {}";
+
+        assert_eq!(if_statement(input), Result::Incomplete(Needed::Size(27)));
+    }
+
+    #[test]
+    fn case_invalid_if_alternative_syntax_mixed_with_blocks() {
+        let input = b"if ($foo): {} else {} endif;";
+
+        assert_eq!(if_statement(input), Result::Error(Error::Position(ErrorKind::Alt, &b": {} else {} endif;"[..])));
+    }
+
+    #[test]
+    fn case_invalid_if_missing_condition() {
+        let input = b"if {}";
+
+        assert_eq!(if_statement(input), Result::Error(Error::Position(ErrorKind::Tag, &b"{}"[..])));
+    }
+
+    #[test]
+    fn case_if_with_an_empty_statement() {
+        let input  = b"if ($a) ;";
+        let output = Result::Done(
+            &b""[..],
+            Statement::If {
+                condition: Spanned::from(Expression::Variable(Variable::Name(&b"a"[..]))),
+                then     : Box::new(Spanned::from(Statement::Empty)),
+                elseifs  : vec![],
+                otherwise: None
+            }
+        );
+
+        assert_eq!(if_statement(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_invalid_if_missing_body() {
+        let input = b"if ($a) }";
+
+        assert_eq!(if_statement(input), Result::Error(Error::Position(ErrorKind::Alt, &b"}"[..])));
+    }
+
+    #[test]
+    fn case_switch_empty() {
+        let input  = b"switch ($foo) {}";
+        let output = Result::Done(
+            &b""[..],
+            Statement::Switch {
+                subject: Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..]))),
+                cases  : vec![]
+            }
+        );
+
+        assert_eq!(switch_statement(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_switch_fall_through() {
+        let input  = b"SWITCH ($foo) { ; CASE 1: case 2; {} {} DeFaUlT: {} }";
+        let output = Result::Done(
+            &b""[..],
+            Statement::Switch {
+                subject: Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..]))),
+                cases  : vec![
+                    SwitchCase {
+                        condition: Some(Spanned::from(Expression::Literal(Literal::Integer(1i64)))),
+                        body     : vec![]
+                    },
+                    SwitchCase {
+                        condition: Some(Spanned::from(Expression::Literal(Literal::Integer(2i64)))),
+                        body     : vec![Spanned::from(Statement::Block(vec![])), Spanned::from(Statement::Block(vec![]))]
+                    },
+                    SwitchCase {
+                        condition: None,
+                        body     : vec![Spanned::from(Statement::Block(vec![]))]
+                    }
+                ]
+            }
+        );
+
+        assert_eq!(switch_statement(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_switch_with_a_label_starting_with_the_default_keyword() {
+        let input  = b"switch ($foo) { case 1: defaultx: }";
+        let output = Result::Done(
+            &b""[..],
+            Statement::Switch {
+                subject: Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..]))),
+                cases  : vec![
+                    SwitchCase {
+                        condition: Some(Spanned::from(Expression::Literal(Literal::Integer(1i64)))),
+                        body     : vec![Spanned::from(Statement::Label(&b"defaultx"[..]))]
+                    }
+                ]
+            }
+        );
+
+        assert_eq!(switch_case(b"defaultx:"), Result::Error(Error::Position(ErrorKind::Alt, &b"defaultx:"[..])));
+        assert_eq!(switch_statement(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_switch_alternative_syntax() {
+        let input  = b"switch ($foo): case $bar: {} endswitch;";
+        let output = Result::Done(
+            &b""[..],
+            Statement::Switch {
+                subject: Spanned::from(Expression::Variable(Variable::Name(&b"foo"[..]))),
+                cases  : vec![
+                    SwitchCase {
+                        condition: Some(Spanned::from(Expression::Variable(Variable::Name(&b"bar"[..])))),
+                        body     : vec![Spanned::from(Statement::Block(vec![]))]
+                    }
+                ]
+            }
+        );
+
+        assert_eq!(switch_statement(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_invalid_switch_alternative_syntax_missing_semicolon() {
+        let input = b"switch ($foo): endswitch {}";
+
+        assert_eq!(switch_statement(input), Result::Error(Error::Position(ErrorKind::Alt, &b": endswitch {}"[..])));
+    }
+
+    #[test]
+    fn case_invalid_switch_case_missing_separator() {
+        let input = b"switch ($foo) { case 42 {} }";
+
+        assert_eq!(switch_statement(input), Result::Error(Error::Position(ErrorKind::Alt, &b"{ case 42 {} }"[..])));
+    }
+
+    #[test]
+    fn case_invalid_switch_many_default_cases() {
+        let input = b"switch ($foo) { default: case 42: default: }";
+
+        assert_eq!(switch_statement(input), Result::Error(Error::Position(ErrorKind::Alt, &b"{ default: case 42: default: }"[..])));
+    }
+
+    #[test]
+    fn case_invalid_switch_alternative_syntax_many_default_cases() {
+        let input = b"switch ($foo): default; default; endswitch;";
+
+        assert_eq!(switch_statement(input), Result::Error(Error::Position(ErrorKind::Alt, &b": default; default; endswitch;"[..])));
+    }
+}
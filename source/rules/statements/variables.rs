@@ -0,0 +1,279 @@
+// Tagua VM
+//
+//
+// New BSD License
+//
+// Copyright © 2016-2016, Ivan Enderlin.
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//     * Redistributions of source code must retain the above copyright
+//       notice, this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright
+//       notice, this list of conditions and the following disclaimer in the
+//       documentation and/or other materials provided with the distribution.
+//     * Neither the name of the Hoa nor the names of its contributors may be
+//       used to endorse or promote products derived from this software without
+//       specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE
+// ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDERS AND CONTRIBUTORS BE
+// LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR
+// CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF
+// SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS
+// INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN
+// CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+
+//! Group of variable declaration statement rules.
+//!
+//! The list of all variable declaration statements is provided by the PHP
+//! Language Specification in the [Grammar chapter, Functions
+//! section](https://github.com/php/php-langspec/blob/master/spec/19-grammar.md#functions).
+
+use super::statement_terminator;
+use super::super::expressions::constants::initializer_expression;
+use super::super::tokens::variable;
+use super::super::super::ast::{
+    ConstantExpression,
+    Expression,
+    Statement,
+    Variable
+};
+use super::super::super::internal::{
+    fold_into_vector,
+    into_vector_mapper
+};
+use super::super::super::span::Spanned;
+use super::super::super::tokens;
+
+named!(
+    pub global_statement<Statement>,
+    chain!(
+        keyword!(tokens::GLOBAL) ~
+        variables: first!(variables) ~
+        first!(expect!("global_statement", &[tokens::SEMICOLON], statement_terminator)),
+        || {
+            Statement::Global(variables)
+        }
+    )
+);
+
+named!(
+    variables< Vec<Variable> >,
+    chain!(
+        accumulator: map_res!(
+            variable,
+            into_vector_mapper
+        ) ~
+        result: fold_many0!(
+            preceded!(
+                first!(tag!(tokens::COMMA)),
+                first!(variable)
+            ),
+            accumulator,
+            fold_into_vector
+        ),
+        || { result }
+    )
+);
+
+named!(
+    pub static_variables_statement<Statement>,
+    chain!(
+        keyword!(tokens::STATIC) ~
+        variables: first!(static_variables) ~
+        first!(expect!("static_variables_statement", &[tokens::SEMICOLON], statement_terminator)),
+        || {
+            Statement::StaticVariables(variables)
+        }
+    )
+);
+
+named!(
+    static_variables< Vec<(Variable, Option<Spanned<Expression>>)> >,
+    chain!(
+        accumulator: map_res!(
+            static_variable,
+            into_vector_mapper
+        ) ~
+        result: fold_many0!(
+            preceded!(
+                first!(tag!(tokens::COMMA)),
+                first!(static_variable)
+            ),
+            accumulator,
+            fold_into_vector
+        ),
+        || { result }
+    )
+);
+
+named!(
+    static_variable<(Variable, Option<Spanned<Expression>>)>,
+    chain!(
+        variable: variable ~
+        value: opt!(
+            complete!(
+                preceded!(
+                    first!(tag!(tokens::ASSIGN)),
+                    first!(initializer_expression)
+                )
+            )
+        ),
+        || {
+            (variable, value.map(|ConstantExpression(expression)| expression))
+        }
+    )
+);
+
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        global_statement,
+        static_variables_statement
+    };
+    use super::super::statement;
+    use super::super::super::super::ast::{
+        Expression,
+        Literal,
+        Name,
+        Statement,
+        Variable
+    };
+    use super::super::super::super::internal::{
+        Error,
+        ErrorKind,
+        Result
+    };
+    use super::super::super::super::span::Spanned;
+    use super::super::super::super::version::{
+        self,
+        Version
+    };
+
+    #[test]
+    fn case_global_one_variable() {
+        let input  = b"global $foo;";
+        let output = Result::Done(&b""[..], Statement::Global(vec![Variable::Name(&b"foo"[..])]));
+
+        assert_eq!(global_statement(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_global_many_variables() {
+        let input  = b"GLOBAL $db , /* foo */ $config ?>";
+        let output = Result::Done(
+            &b"?>"[..],
+            Statement::Global(vec![
+                Variable::Name(&b"db"[..]),
+                Variable::Name(&b"config"[..])
+            ])
+        );
+
+        assert_eq!(global_statement(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_invalid_global_without_variable() {
+        let input = b"global;";
+
+        assert_eq!(global_statement(input), Result::Error(Error::Position(ErrorKind::Tag, &b";"[..])));
+    }
+
+    #[test]
+    fn case_invalid_global_trailing_comma() {
+        let input = b"global $foo,;";
+
+        assert_eq!(global_statement(input), Result::Error(Error::Position(ErrorKind::Alt, &b",;"[..])));
+    }
+
+    #[test]
+    fn case_static_variable() {
+        let input  = b"static $count;";
+        let output = Result::Done(&b""[..], Statement::StaticVariables(vec![(Variable::Name(&b"count"[..]), None)]));
+
+        assert_eq!(static_variables_statement(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_static_variables_with_initializers() {
+        let input  = b"STATIC $count = 0, $cache, $default = [FOO => 'bar'];";
+        let output = Result::Done(
+            &b""[..],
+            Statement::StaticVariables(vec![
+                (Variable::Name(&b"count"[..]), Some(Spanned::from(Expression::Literal(Literal::Integer(0))))),
+                (Variable::Name(&b"cache"[..]), None),
+                (
+                    Variable::Name(&b"default"[..]),
+                    Some(
+                        Spanned::from(Expression::Array(vec![
+                            (
+                                Some(Spanned::from(Expression::Name(Name::Unqualified(&b"FOO"[..])))),
+                                Spanned::from(Expression::Literal(Literal::String(b"bar".to_vec())))
+                            )
+                        ]))
+                    )
+                )
+            ])
+        );
+
+        assert_eq!(static_variables_statement(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_static_variable_with_new_initializer() {
+        let input  = b"static $foo = new Foo;";
+        let output = Result::Done(
+            &b""[..],
+            Statement::StaticVariables(vec![
+                (
+                    Variable::Name(&b"foo"[..]),
+                    Some(
+                        Spanned::from(Expression::New {
+                            class    : Box::new(Spanned::from(Expression::Name(Name::Unqualified(&b"Foo"[..])))),
+                            arguments: vec![]
+                        })
+                    )
+                )
+            ])
+        );
+
+        assert_eq!(static_variables_statement(input), output);
+        assert_eq!(statement(input), output);
+    }
+
+    #[test]
+    fn case_invalid_static_variable_with_new_initializer_before_php_81() {
+        let input = b"static $foo = new Foo;";
+
+        version::set_target(Version::Php80);
+        let output = static_variables_statement(input);
+        version::set_target(version::LATEST);
+
+        assert_eq!(output, Result::Error(Error::Position(ErrorKind::Alt, &b"= new Foo;"[..])));
+    }
+
+    #[test]
+    fn case_invalid_static_variable_with_non_constant_initializer() {
+        let input = b"static $foo = $bar;";
+
+        assert_eq!(static_variables_statement(input), Result::Error(Error::Position(ErrorKind::Alt, &b"= $bar;"[..])));
+    }
+
+    #[test]
+    fn case_invalid_static_without_variable() {
+        let input = b"static;";
+
+        assert_eq!(static_variables_statement(input), Result::Error(Error::Position(ErrorKind::Tag, &b";"[..])));
+    }
+}
@@ -35,8 +35,10 @@
 //! the [Grammar chapter, Tokens
 //! section](https://github.com/php/php-langspec/blob/master/spec/19-grammar.md#tokens).
 
+use std::result::Result as StdResult;
 use super::super::ast::{
     Name,
+    RelativeScope,
     Variable
 };
 use super::super::internal::fold_into_vector;
@@ -55,7 +57,7 @@ named!(
 
 #[inline(always)]
 fn variable_mapper(string: &[u8]) -> Result<Variable, ()> {
-    Ok(Variable(string))
+    Ok(Variable::Name(string))
 }
 
 named!(
@@ -69,13 +71,13 @@ named!(
             )
         )? ~
         accumulator: map_res!(
-            exclude!(first!(name), tokens::keywords),
+            exclude!(first!(name), keyword_name),
             wrap_into_vector_mapper
         ) ~
         result: fold_many0!(
             preceded!(
                 first!(tag!(tokens::NAMESPACE_SEPARATOR)),
-                exclude!(first!(name), tokens::keywords)
+                exclude!(first!(name), keyword_name)
             ),
             accumulator,
             fold_into_vector
@@ -102,26 +104,65 @@ named!(
     )
 );
 
+named!(
+    keyword_name,
+    terminated!(
+        tokens::keywords,
+        eof!()
+    )
+);
+
 #[inline(always)]
 fn wrap_into_vector_mapper(string: &[u8]) -> Result<Vec<&[u8]>, ()> {
     Ok(vec![string])
 }
 
+named!(
+    pub relative_scope<RelativeScope>,
+    map_res!(
+        name,
+        relative_scope_mapper
+    )
+);
+
+#[inline(always)]
+fn relative_scope_mapper(string: &[u8]) -> StdResult<RelativeScope, ()> {
+    if string.eq_ignore_ascii_case(tokens::SELF) {
+        Ok(RelativeScope::Current)
+    } else if string.eq_ignore_ascii_case(tokens::PARENT) {
+        Ok(RelativeScope::Parent)
+    } else if string.eq_ignore_ascii_case(tokens::STATIC) {
+        Ok(RelativeScope::Static)
+    } else {
+        Err(())
+    }
+}
+
+named!(
+    pub member_name,
+    call!(name)
+);
+
+// The name grammar is byte-oriented: any byte from `0x80` to `0xff` is
+// allowed, whatever the encoding, hence the disabled Unicode mode.
 named!(
     pub name,
-    re_bytes_find_static!(r"^[a-zA-Z_\x80-\xff][a-zA-Z0-9_\x80-\xff]*")
+    re_bytes_find_static!(r"(?-u)^[a-zA-Z_\x80-\xff][a-zA-Z0-9_\x80-\xff]*")
 );
 
 
 #[cfg(test)]
 mod tests {
     use super::{
+        member_name,
         name,
         qualified_name,
+        relative_scope,
         variable
     };
     use super::super::super::ast::{
         Name,
+        RelativeScope,
         Variable
     };
     use super::super::super::internal::{
@@ -133,12 +174,18 @@ mod tests {
 
     #[test]
     fn case_variable() {
-        assert_eq!(variable(b"$foo"), Result::Done(&b""[..], Variable(&b"foo"[..])));
+        assert_eq!(variable(b"$foo"), Result::Done(&b""[..], Variable::Name(&b"foo"[..])));
+    }
+
+    #[test]
+    fn case_variable_with_non_ascii_bytes() {
+        assert_eq!(variable("$variável".as_bytes()), Result::Done(&b""[..], Variable::Name("variável".as_bytes())));
+        assert_eq!(variable(b"$caf\xe9"), Result::Done(&b""[..], Variable::Name(&b"caf\xe9"[..])));
     }
 
     #[test]
     fn case_variable_shortest() {
-        assert_eq!(variable(b"$x"), Result::Done(&b""[..], Variable(&b"x"[..])));
+        assert_eq!(variable(b"$x"), Result::Done(&b""[..], Variable::Name(&b"x"[..])));
     }
 
     #[test]
@@ -162,6 +209,17 @@ mod tests {
         assert_eq!(qualified_name(b"ClAsS"), Result::Error(Error::Position(ErrorKind::Custom(ErrorKindCustom::Exclude as u32), &b"ClAsS"[..])));
     }
 
+    #[test]
+    fn case_unqualified_name_starting_with_a_keyword() {
+        assert_eq!(qualified_name(b"newFoo"), Result::Done(&b""[..], Name::Unqualified(&b"newFoo"[..])));
+        assert_eq!(qualified_name(b"print_r"), Result::Done(&b""[..], Name::Unqualified(&b"print_r"[..])));
+    }
+
+    #[test]
+    fn case_qualified_name_with_parts_starting_with_a_keyword() {
+        assert_eq!(qualified_name(b"Format\\Classy"), Result::Done(&b""[..], Name::Qualified(vec![&b"Format"[..], &b"Classy"[..]])));
+    }
+
     #[test]
     fn case_qualified_name() {
         assert_eq!(qualified_name(b"Foo\\Bar\\Baz"), Result::Done(&b""[..], Name::Qualified(vec![&b"Foo"[..], &b"Bar"[..], &b"Baz"[..]])));
@@ -178,6 +236,12 @@ mod tests {
         assert_eq!(qualified_name(b"Foo\\ClAsS\\Baz"), Result::Done(&b"\\ClAsS\\Baz"[..], Name::Unqualified(&b"Foo"[..])));
     }
 
+    #[test]
+    fn case_qualified_name_with_non_ascii_bytes() {
+        assert_eq!(qualified_name("über\\Foo".as_bytes()), Result::Done(&b""[..], Name::Qualified(vec!["über".as_bytes(), &b"Foo"[..]])));
+        assert_eq!(qualified_name("\\Привет\\世界".as_bytes()), Result::Done(&b""[..], Name::FullyQualified(vec!["Привет".as_bytes(), "世界".as_bytes()])));
+    }
+
     #[test]
     fn case_relative_qualified_name() {
         assert_eq!(qualified_name(b"namespace\\Foo\\Bar\\Baz"), Result::Done(&b""[..], Name::RelativeQualified(vec![&b"Foo"[..], &b"Bar"[..], &b"Baz"[..]])));
@@ -231,6 +295,46 @@ mod tests {
         assert_eq!(qualified_name(b"Foo\\Bar\\"), Result::Done(&b"\\"[..], Name::Qualified(vec![&b"Foo"[..], &b"Bar"[..]])));
     }
 
+    #[test]
+    fn case_relative_scope_self() {
+        assert_eq!(relative_scope(b"self"), Result::Done(&b""[..], RelativeScope::Current));
+        assert_eq!(relative_scope(b"SeLf"), Result::Done(&b""[..], RelativeScope::Current));
+    }
+
+    #[test]
+    fn case_relative_scope_parent() {
+        assert_eq!(relative_scope(b"parent"), Result::Done(&b""[..], RelativeScope::Parent));
+        assert_eq!(relative_scope(b"PaReNt"), Result::Done(&b""[..], RelativeScope::Parent));
+    }
+
+    #[test]
+    fn case_relative_scope_static() {
+        assert_eq!(relative_scope(b"static"), Result::Done(&b""[..], RelativeScope::Static));
+        assert_eq!(relative_scope(b"StAtIc"), Result::Done(&b""[..], RelativeScope::Static));
+    }
+
+    #[test]
+    fn case_invalid_relative_scope() {
+        assert_eq!(relative_scope(b"selfish"), Result::Error(Error::Position(ErrorKind::MapRes, &b"selfish"[..])));
+    }
+
+    #[test]
+    fn case_member_name() {
+        assert_eq!(member_name(b"fooBar"), Result::Done(&b""[..], &b"fooBar"[..]));
+    }
+
+    #[test]
+    fn case_member_name_semi_reserved_keyword() {
+        assert_eq!(member_name(b"list"), Result::Done(&b""[..], &b"list"[..]));
+        assert_eq!(member_name(b"PRINT"), Result::Done(&b""[..], &b"PRINT"[..]));
+        assert_eq!(member_name(b"__CLASS__"), Result::Done(&b""[..], &b"__CLASS__"[..]));
+    }
+
+    #[test]
+    fn case_invalid_member_name() {
+        assert_eq!(member_name(b"0x"), Result::Error(Error::Code(ErrorKind::RegexpFind)));
+    }
+
     #[test]
     fn case_name() {
         assert_eq!(name(b"_fooBar42"), Result::Done(&b""[..], &b"_fooBar42"[..]));
@@ -257,6 +361,16 @@ mod tests {
         assert_eq!(name(b"\xa9"), Result::Done(&b""[..], &b"\xa9"[..]));
     }
 
+    #[test]
+    fn case_name_utf8_beyond_latin1() {
+        assert_eq!(name("ĳ_€🐘".as_bytes()), Result::Done(&b""[..], "ĳ_€🐘".as_bytes()));
+    }
+
+    #[test]
+    fn case_invalid_name_starting_with_a_digit() {
+        assert_eq!(name(b"7\xe9"), Result::Error(Error::Code(ErrorKind::RegexpFind)));
+    }
+
     #[test]
     fn case_name_non_breaking_space() {
         //   = 0xa0
@@ -0,0 +1,549 @@
+// Tagua VM
+//
+//
+// New BSD License
+//
+// Copyright © 2016-2016, Ivan Enderlin.
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//     * Redistributions of source code must retain the above copyright
+//       notice, this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright
+//       notice, this list of conditions and the following disclaimer in the
+//       documentation and/or other materials provided with the distribution.
+//     * Neither the name of the Hoa nor the names of its contributors may be
+//       used to endorse or promote products derived from this software without
+//       specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE
+// ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDERS AND CONTRIBUTORS BE
+// LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR
+// CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF
+// SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS
+// INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN
+// CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+
+//! Group of type rules.
+//!
+//! The list of all types is provided by the PHP Language Specification in
+//! the [Grammar chapter, Functions
+//! section](https://github.com/php/php-langspec/blob/master/spec/19-grammar.md#function-definition).
+
+use std::result::Result as StdResult;
+use super::tokens::{
+    name,
+    qualified_name
+};
+use super::super::ast::{
+    Name,
+    Type
+};
+use super::super::internal::{
+    Result,
+    fold_into_vector,
+    into_vector_mapper
+};
+use super::super::span::Spanned;
+use super::super::tokens;
+use super::super::version::{
+    self,
+    Version
+};
+
+named!(
+    pub type_declaration<Type>,
+    call!(nullable_or_union_type, named_type)
+);
+
+/// Parse a type designated by a name, i.e. a class or a built-in type.
+fn named_type(input: &[u8]) -> Result<&[u8], Type> {
+    if let Result::Done(output, ty) = map_res!(input, name, keyword_type_mapper) {
+        return Result::Done(output, ty);
+    }
+
+    map!(input, qualified_name, name_mapper)
+}
+
+#[inline(always)]
+fn keyword_type_mapper<'a>(string: &[u8]) -> StdResult<Type<'a>, ()> {
+    if string.eq_ignore_ascii_case(tokens::ARRAY) {
+        Ok(Type::Array)
+    } else if string.eq_ignore_ascii_case(tokens::CALLABLE) {
+        Ok(Type::Callable)
+    } else {
+        Err(())
+    }
+}
+
+#[inline(always)]
+fn name_mapper<'a>(name: Name<'a>) -> Type<'a> {
+    let string = match name {
+        Name::Unqualified(string) => string,
+        _ => return Type::Name(name)
+    };
+
+    if string.eq_ignore_ascii_case(tokens::BOOL) {
+        Type::Bool
+    } else if string.eq_ignore_ascii_case(tokens::FALSE) {
+        Type::False
+    } else if string.eq_ignore_ascii_case(tokens::FLOAT) {
+        Type::Float
+    } else if string.eq_ignore_ascii_case(tokens::INT) {
+        Type::Int
+    } else if string.eq_ignore_ascii_case(tokens::ITERABLE) {
+        Type::Iterable
+    } else if string.eq_ignore_ascii_case(tokens::MIXED) {
+        Type::Mixed
+    } else if string.eq_ignore_ascii_case(tokens::NEVER) {
+        Type::Never
+    } else if string.eq_ignore_ascii_case(tokens::NULL) {
+        Type::Null
+    } else if string.eq_ignore_ascii_case(tokens::OBJECT) {
+        Type::Object
+    } else if string.eq_ignore_ascii_case(tokens::PARENT) {
+        Type::Parent
+    } else if string.eq_ignore_ascii_case(tokens::SELF) {
+        Type::Current
+    } else if string.eq_ignore_ascii_case(tokens::STRING) {
+        Type::String
+    } else if string.eq_ignore_ascii_case(tokens::TRUE) {
+        Type::True
+    } else if string.eq_ignore_ascii_case(tokens::VOID) {
+        Type::Void
+    } else {
+        Type::Name(name)
+    }
+}
+
+named!(
+    pub return_type<Type>,
+    preceded!(
+        tag!(tokens::FUNCTION_OUTPUT),
+        first!(call!(nullable_or_union_type, return_single_type))
+    )
+);
+
+named!(
+    return_single_type<Type>,
+    alt!(
+        named_type
+      | static_type
+    )
+);
+
+named!(
+    static_type<Type>,
+    map!(
+        keyword!(tokens::STATIC),
+        static_mapper
+    )
+);
+
+/// Parse a nullable type, e.g. `?int`, an intersection type or a union
+/// type.
+fn nullable_or_union_type<'a>(input: &'a [u8], single_type: fn(&'a [u8]) -> Result<&'a [u8], Type<'a>>) -> Result<&'a [u8], Type<'a>> {
+    if input.starts_with(tokens::NULLABLE) {
+        return map!(
+            &input[tokens::NULLABLE.len()..],
+            first!(spanned!(call!(single_type))),
+            nullable_mapper
+        );
+    }
+
+    if version::is_targeting(Version::Php81) {
+        if let Result::Done(output, intersection) = intersection_type(input, single_type) {
+            return Result::Done(output, intersection);
+        }
+    }
+
+    union_type(input, single_type)
+}
+
+/// Parse one type, or many types separated by `|` into a union type when
+/// targeting PHP 8.0 or later. Since PHP 8.2, an element of the union can
+/// be a parenthesized intersection type, e.g. `(A&B)|null`.
+fn union_type<'a>(input: &'a [u8], single_type: fn(&'a [u8]) -> Result<&'a [u8], Type<'a>>) -> Result<&'a [u8], Type<'a>> {
+    if !version::is_targeting(Version::Php80) {
+        return single_type(input);
+    }
+
+    map!(
+        input,
+        chain!(
+            accumulator: map_res!(
+                spanned!(call!(union_element_type, single_type)),
+                into_vector_mapper
+            ) ~
+            result: fold_many0!(
+                preceded!(
+                    first!(tag!(tokens::BITWISE_OR)),
+                    first!(spanned!(call!(union_element_type, single_type)))
+                ),
+                accumulator,
+                fold_into_vector
+            ),
+            || { result }
+        ),
+        union_mapper
+    )
+}
+
+fn union_element_type<'a>(input: &'a [u8], single_type: fn(&'a [u8]) -> Result<&'a [u8], Type<'a>>) -> Result<&'a [u8], Type<'a>> {
+    if version::is_targeting(Version::Php82) && input.starts_with(tokens::LEFT_PARENTHESIS) {
+        terminated!(
+            &input[tokens::LEFT_PARENTHESIS.len()..],
+            first!(call!(intersection_type, single_type)),
+            first!(expect!("union_element_type", tokens::RIGHT_PARENTHESIS))
+        )
+    } else {
+        single_type(input)
+    }
+}
+
+/// Parse at least two types separated by `&` into an intersection type.
+fn intersection_type<'a>(input: &'a [u8], single_type: fn(&'a [u8]) -> Result<&'a [u8], Type<'a>>) -> Result<&'a [u8], Type<'a>> {
+    map!(
+        input,
+        chain!(
+            head: spanned!(call!(single_type)) ~
+            tail: many1!(
+                preceded!(
+                    intersection_separator,
+                    first!(spanned!(call!(single_type)))
+                )
+            ),
+            || { (head, tail) }
+        ),
+        intersection_mapper
+    )
+}
+
+// An intersection separator is an `&` not followed by a variable or an
+// ellipsis, otherwise it is a reference of a parameter, e.g. `A &$x`.
+named!(
+    intersection_separator,
+    terminated!(
+        first!(tag!(tokens::BITWISE_AND)),
+        not!(
+            complete!(
+                first!(
+                    alt!(
+                        tag!(tokens::VARIABLE)
+                      | tag!(tokens::ELLIPSIS)
+                    )
+                )
+            )
+        )
+    )
+);
+
+#[inline(always)]
+fn union_mapper<'a>(mut types: Vec<Spanned<Type<'a>>>) -> Type<'a> {
+    if types.len() == 1 {
+        types.pop().unwrap().node
+    } else {
+        Type::Union(types)
+    }
+}
+
+#[inline(always)]
+fn intersection_mapper<'a>((head, mut tail): (Spanned<Type<'a>>, Vec<Spanned<Type<'a>>>)) -> Type<'a> {
+    tail.insert(0, head);
+
+    Type::Intersection(tail)
+}
+
+#[inline(always)]
+fn nullable_mapper<'a>(ty: Spanned<Type<'a>>) -> Type<'a> {
+    Type::Nullable(Box::new(ty))
+}
+
+#[inline(always)]
+fn static_mapper<'a>(_: &[u8]) -> Type<'a> {
+    Type::Static
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        return_type,
+        type_declaration
+    };
+    use super::super::super::ast::{
+        Name,
+        Type
+    };
+    use super::super::super::internal::{
+        Error,
+        ErrorKind,
+        Result
+    };
+    use super::super::super::macros::ErrorKindCustom;
+    use super::super::super::span::Spanned;
+    use super::super::super::version::{
+        self,
+        Version
+    };
+
+    #[test]
+    fn case_type_unqualified_name() {
+        assert_eq!(type_declaration(b"Foo"), Result::Done(&b""[..], Type::Name(Name::Unqualified(&b"Foo"[..]))));
+    }
+
+    macro_rules! test_builtin_type {
+        ($test_case_name:ident: ($input:expr, $expect:expr)) => (
+            #[test]
+            fn $test_case_name() {
+                assert_eq!(type_declaration($input), Result::Done(&b""[..], $expect));
+            }
+        )
+    }
+
+    test_builtin_type!(case_type_array:    (b"array", Type::Array));
+    test_builtin_type!(case_type_bool:     (b"bool", Type::Bool));
+    test_builtin_type!(case_type_callable: (b"callable", Type::Callable));
+    test_builtin_type!(case_type_false:    (b"false", Type::False));
+    test_builtin_type!(case_type_float:    (b"float", Type::Float));
+    test_builtin_type!(case_type_int:      (b"int", Type::Int));
+    test_builtin_type!(case_type_iterable: (b"iterable", Type::Iterable));
+    test_builtin_type!(case_type_mixed:    (b"mixed", Type::Mixed));
+    test_builtin_type!(case_type_never:    (b"never", Type::Never));
+    test_builtin_type!(case_type_null:     (b"null", Type::Null));
+    test_builtin_type!(case_type_object:   (b"object", Type::Object));
+    test_builtin_type!(case_type_parent:   (b"parent", Type::Parent));
+    test_builtin_type!(case_type_self:     (b"self", Type::Current));
+    test_builtin_type!(case_type_string:   (b"string", Type::String));
+    test_builtin_type!(case_type_true:     (b"true", Type::True));
+    test_builtin_type!(case_type_void:     (b"void", Type::Void));
+
+    #[test]
+    fn case_type_builtin_is_case_insensitive() {
+        assert_eq!(type_declaration(b"ARRAY|Int|sTrInG"), Result::Done(&b""[..], Type::Union(vec![Spanned::from(Type::Array), Spanned::from(Type::Int), Spanned::from(Type::String)])));
+    }
+
+    #[test]
+    fn case_type_builtin_prefix_is_a_name() {
+        assert_eq!(type_declaration(b"arrayable"), Result::Done(&b""[..], Type::Name(Name::Unqualified(&b"arrayable"[..]))));
+        assert_eq!(type_declaration(b"integer"), Result::Done(&b""[..], Type::Name(Name::Unqualified(&b"integer"[..]))));
+    }
+
+    #[test]
+    fn case_type_qualified_builtin_is_a_name() {
+        assert_eq!(type_declaration(b"\\int"), Result::Done(&b""[..], Type::Name(Name::FullyQualified(vec![&b"int"[..]]))));
+    }
+
+    #[test]
+    fn case_invalid_type_static_outside_return_type() {
+        assert_eq!(type_declaration(b"static"), Result::Error(Error::Position(ErrorKind::Custom(ErrorKindCustom::Exclude as u32), &b"static"[..])));
+    }
+
+    #[test]
+    fn case_type_qualified_name() {
+        assert_eq!(type_declaration(b"Foo\\Bar"), Result::Done(&b""[..], Type::Name(Name::Qualified(vec![&b"Foo"[..], &b"Bar"[..]]))));
+    }
+
+    #[test]
+    fn case_type_fully_qualified_name() {
+        assert_eq!(type_declaration(b"\\Foo"), Result::Done(&b""[..], Type::Name(Name::FullyQualified(vec![&b"Foo"[..]]))));
+    }
+
+    #[test]
+    fn case_type_nullable() {
+        assert_eq!(type_declaration(b"?int"), Result::Done(&b""[..], Type::Nullable(Box::new(Spanned::from(Type::Int)))));
+    }
+
+    #[test]
+    fn case_type_nullable_qualified_name() {
+        assert_eq!(type_declaration(b"? Foo\\Bar"), Result::Done(&b""[..], Type::Nullable(Box::new(Spanned::from(Type::Name(Name::Qualified(vec![&b"Foo"[..], &b"Bar"[..]])))))));
+    }
+
+    #[test]
+    fn case_invalid_type_nullable_twice() {
+        assert_eq!(type_declaration(b"??int"), Result::Error(Error::Code(ErrorKind::RegexpFind)));
+    }
+
+    #[test]
+    fn case_type_union() {
+        assert_eq!(
+            type_declaration(b"int|string|null"),
+            Result::Done(
+                &b""[..],
+                Type::Union(vec![
+                    Spanned::from(Type::Int),
+                    Spanned::from(Type::String),
+                    Spanned::from(Type::Null)
+                ])
+            )
+        );
+    }
+
+    #[test]
+    fn case_type_union_with_comments() {
+        assert_eq!(
+            type_declaration(b"\\Foo /* a */ | /* b */ false"),
+            Result::Done(
+                &b""[..],
+                Type::Union(vec![
+                    Spanned::from(Type::Name(Name::FullyQualified(vec![&b"Foo"[..]]))),
+                    Spanned::from(Type::False)
+                ])
+            )
+        );
+    }
+
+    #[test]
+    fn case_type_union_before_php_80() {
+        let input = b"int|string";
+
+        version::set_target(Version::Php74);
+        let output = type_declaration(input);
+        version::set_target(version::LATEST);
+
+        assert_eq!(output, Result::Done(&b"|string"[..], Type::Int));
+    }
+
+    #[test]
+    fn case_type_nullable_is_not_a_union() {
+        assert_eq!(type_declaration(b"?int|string"), Result::Done(&b"|string"[..], Type::Nullable(Box::new(Spanned::from(Type::Int)))));
+    }
+
+    #[test]
+    fn case_type_intersection() {
+        assert_eq!(
+            type_declaration(b"Countable & Traversable&\\Foo"),
+            Result::Done(
+                &b""[..],
+                Type::Intersection(vec![
+                    Spanned::from(Type::Name(Name::Unqualified(&b"Countable"[..]))),
+                    Spanned::from(Type::Name(Name::Unqualified(&b"Traversable"[..]))),
+                    Spanned::from(Type::Name(Name::FullyQualified(vec![&b"Foo"[..]])))
+                ])
+            )
+        );
+    }
+
+    #[test]
+    fn case_type_intersection_is_not_a_union() {
+        assert_eq!(
+            type_declaration(b"A&B|C"),
+            Result::Done(
+                &b"|C"[..],
+                Type::Intersection(vec![
+                    Spanned::from(Type::Name(Name::Unqualified(&b"A"[..]))),
+                    Spanned::from(Type::Name(Name::Unqualified(&b"B"[..])))
+                ])
+            )
+        );
+    }
+
+    #[test]
+    fn case_type_intersection_before_php_81() {
+        let input = b"A&B";
+
+        version::set_target(Version::Php80);
+        let output = type_declaration(input);
+        version::set_target(version::LATEST);
+
+        assert_eq!(output, Result::Done(&b"&B"[..], Type::Name(Name::Unqualified(&b"A"[..]))));
+    }
+
+    #[test]
+    fn case_type_intersection_separator_is_not_a_reference() {
+        assert_eq!(type_declaration(b"A & $x"), Result::Done(&b" & $x"[..], Type::Name(Name::Unqualified(&b"A"[..]))));
+        assert_eq!(type_declaration(b"A &...$x"), Result::Done(&b" &...$x"[..], Type::Name(Name::Unqualified(&b"A"[..]))));
+    }
+
+    #[test]
+    fn case_type_disjunctive_normal_form() {
+        assert_eq!(
+            type_declaration(b"( A&B ) | C|(D&E)"),
+            Result::Done(
+                &b""[..],
+                Type::Union(vec![
+                    Spanned::from(Type::Intersection(vec![
+                        Spanned::from(Type::Name(Name::Unqualified(&b"A"[..]))),
+                        Spanned::from(Type::Name(Name::Unqualified(&b"B"[..])))
+                    ])),
+                    Spanned::from(Type::Name(Name::Unqualified(&b"C"[..]))),
+                    Spanned::from(Type::Intersection(vec![
+                        Spanned::from(Type::Name(Name::Unqualified(&b"D"[..]))),
+                        Spanned::from(Type::Name(Name::Unqualified(&b"E"[..])))
+                    ]))
+                ])
+            )
+        );
+    }
+
+    #[test]
+    fn case_invalid_type_disjunctive_normal_form_before_php_82() {
+        let input = b"(A&B)|C";
+
+        version::set_target(Version::Php81);
+        let output = type_declaration(input);
+        version::set_target(version::LATEST);
+
+        assert_eq!(output, Result::Error(Error::Code(ErrorKind::RegexpFind)));
+    }
+
+    #[test]
+    fn case_invalid_type_disjunctive_normal_form_group_of_one_type() {
+        assert_eq!(type_declaration(b"(A)|B"), Result::Error(Error::Position(ErrorKind::Many1, &b")|B"[..])));
+    }
+
+    #[test]
+    fn case_invalid_type_keyword() {
+        assert_eq!(type_declaration(b"class"), Result::Error(Error::Position(ErrorKind::Custom(ErrorKindCustom::Exclude as u32), &b"class"[..])));
+    }
+
+    #[test]
+    fn case_return_type() {
+        assert_eq!(return_type(b": void"), Result::Done(&b""[..], Type::Void));
+    }
+
+    #[test]
+    fn case_return_type_static() {
+        assert_eq!(return_type(b":STATIC"), Result::Done(&b""[..], Type::Static));
+    }
+
+    #[test]
+    fn case_return_type_nullable() {
+        assert_eq!(
+            return_type(b": ? /* foo */ Foo\\Bar"),
+            Result::Done(
+                &b""[..],
+                Type::Nullable(Box::new(Spanned::from(Type::Name(Name::Qualified(vec![&b"Foo"[..], &b"Bar"[..]])))))
+            )
+        );
+    }
+
+    #[test]
+    fn case_return_type_nullable_static() {
+        assert_eq!(return_type(b": ?static"), Result::Done(&b""[..], Type::Nullable(Box::new(Spanned::from(Type::Static)))));
+    }
+
+    #[test]
+    fn case_invalid_return_type_missing_type() {
+        assert_eq!(return_type(b": {}"), Result::Error(Error::Position(ErrorKind::Alt, &b"{}"[..])));
+    }
+
+    #[test]
+    fn case_return_type_union_with_static() {
+        assert_eq!(
+            return_type(b": static|Foo"),
+            Result::Done(
+                &b""[..],
+                Type::Union(vec![
+                    Spanned::from(Type::Static),
+                    Spanned::from(Type::Name(Name::Unqualified(&b"Foo"[..])))
+                ])
+            )
+        );
+    }
+}
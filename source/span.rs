@@ -0,0 +1,414 @@
+// Tagua VM
+//
+//
+// New BSD License
+//
+// Copyright © 2016-2016, Ivan Enderlin.
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//     * Redistributions of source code must retain the above copyright
+//       notice, this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright
+//       notice, this list of conditions and the following disclaimer in the
+//       documentation and/or other materials provided with the distribution.
+//     * Neither the name of the Hoa nor the names of its contributors may be
+//       used to endorse or promote products derived from this software without
+//       specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE
+// ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDERS AND CONTRIBUTORS BE
+// LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR
+// CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF
+// SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS
+// INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN
+// CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+
+//! Positions of the nodes in the parsed datum.
+//!
+//! Every expression, statement, class member, parameter, argument, name and
+//! type of the AST is wrapped in `Spanned`, with the byte range of the datum
+//! it has been parsed from. Lines and columns are not counted while parsing:
+//! they are computed on demand from the datum.
+//!
+//! The rules consume and produce slices of the datum, so the position of
+//! any slice, like the rest of a rule or the position of an error, is
+//! known by its address. The spans are relative to the datum given to
+//! `within`, or else to the input of the outermost rule producing a span.
+//!
+//! # Examples
+//!
+//! ```
+//! use tagua_parser::ast::Expression;
+//! use tagua_parser::rules::expressions::expression;
+//! use tagua_parser::span::Location;
+//!
+//! let input           = b"$a =\n  $b;";
+//! let (_, assignment) = expression(&input[..]).unwrap();
+//!
+//! if let Expression::Assignment { value, .. } = assignment {
+//!     assert_eq!(value.span.start..value.span.end, 7..9);
+//!     assert_eq!(
+//!         value.span.start_location(&input[..]),
+//!         Location {
+//!             offset: 7,
+//!             line  : 2,
+//!             column: 3
+//!         }
+//!     );
+//! }
+//! ```
+
+use std::cell::Cell;
+use std::ops::{
+    Deref,
+    DerefMut
+};
+use super::internal::Result;
+
+/// A byte range in the parsed datum.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Span {
+    /// The offset of the first byte.
+    pub start: usize,
+
+    /// The offset following the last byte.
+    pub end: usize
+}
+
+impl Span {
+    /// Compute the span of what has been consumed by a parser, from
+    /// `input` to `rest`, relative to the parsed datum, see `within`.
+    pub fn new(input: &[u8], rest: &[u8]) -> Span {
+        let datum_start = DATUM.with(|datum| datum.get()).unwrap_or(input.as_ptr() as usize);
+
+        Span {
+            start: input.as_ptr() as usize - datum_start,
+            end  : rest.as_ptr() as usize - datum_start
+        }
+    }
+
+    /// Compute the span from the start of this span to the end of `other`.
+    pub fn to(&self, other: Span) -> Span {
+        Span {
+            start: self.start,
+            end  : other.end
+        }
+    }
+
+    /// Compute the location of the start of the span.
+    pub fn start_location(&self, datum: &[u8]) -> Location {
+        locate(datum, self.start)
+    }
+
+    /// Compute the location of the end of the span.
+    pub fn end_location(&self, datum: &[u8]) -> Location {
+        locate(datum, self.end)
+    }
+}
+
+/// A location in the parsed datum.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Location {
+    /// The offset of the byte.
+    pub offset: usize,
+
+    /// The line, starting from 1.
+    pub line: usize,
+
+    /// The column, in bytes, starting from 1.
+    pub column: usize
+}
+
+/// A node with its span.
+///
+/// Two spanned nodes are equal if their nodes are equal, whatever their
+/// spans, so that the nodes parsed from different data, e.g. a script and
+/// its printed form, can be compared. A node built from scratch, see
+/// `From`, has an empty span at the start of the datum.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Spanned<T> {
+    /// The node.
+    pub node: T,
+
+    /// The span of the node.
+    pub span: Span
+}
+
+impl<T> Spanned<T> {
+    /// Wrap a node with its span.
+    pub fn new(node: T, span: Span) -> Spanned<T> {
+        Spanned {
+            node: node,
+            span: span
+        }
+    }
+
+    /// Transform the node, and keep its span.
+    pub fn map<U, F>(self, mapper: F) -> Spanned<U>
+        where F: FnOnce(T) -> U
+    {
+        Spanned {
+            node: mapper(self.node),
+            span: self.span
+        }
+    }
+}
+
+impl<T> From<T> for Spanned<T> {
+    fn from(node: T) -> Spanned<T> {
+        Spanned::new(node, Span::default())
+    }
+}
+
+impl<T> Deref for Spanned<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.node
+    }
+}
+
+impl<T> DerefMut for Spanned<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.node
+    }
+}
+
+impl<T: PartialEq> PartialEq for Spanned<T> {
+    fn eq(&self, other: &Spanned<T>) -> bool {
+        self.node == other.node
+    }
+}
+
+thread_local!(
+    static DATUM: Cell<Option<usize>> = const { Cell::new(None) }
+);
+
+/// Run a parser on a datum, the spans of the nodes it produces being
+/// relative to the start of the datum. If the spans are already relative to
+/// a datum on the current thread, it is kept: the parser is run on a slice of
+/// this datum.
+pub fn within<'a, F, T>(datum: &'a [u8], parser: F) -> T
+    where F: FnOnce(&'a [u8]) -> T
+{
+    let _datum = DatumGuard::new(datum);
+
+    parser(datum)
+}
+
+/// Set the datum on the current thread if not set, and unset it when
+/// dropped, even if the parser panics.
+struct DatumGuard {
+    is_outermost: bool
+}
+
+impl DatumGuard {
+    fn new(datum: &[u8]) -> DatumGuard {
+        let is_outermost = DATUM.with(|current| current.get()).is_none();
+
+        if is_outermost {
+            DATUM.with(|current| current.set(Some(datum.as_ptr() as usize)));
+        }
+
+        DatumGuard {
+            is_outermost: is_outermost
+        }
+    }
+}
+
+impl Drop for DatumGuard {
+    fn drop(&mut self) {
+        if self.is_outermost {
+            DATUM.with(|current| current.set(None));
+        }
+    }
+}
+
+/// Run a parser, and wrap the node it produces with its span, from `input`
+/// to the rest of the parser.
+pub fn spanned<'a, F, O>(input: &'a [u8], parser: F) -> Result<&'a [u8], Spanned<O>>
+    where F: FnOnce(&'a [u8]) -> Result<&'a [u8], O>
+{
+    within(
+        input,
+        |input| {
+            match parser(input) {
+                Result::Done(rest, node) => Result::Done(rest, Spanned::new(node, Span::new(input, rest))),
+                Result::Error(error) => Result::Error(error),
+                Result::Incomplete(needed) => Result::Incomplete(needed)
+            }
+        }
+    )
+}
+
+/// Compute the location of an offset in the datum. A line ends with `\n`,
+/// `\r\n` or `\r`.
+pub fn locate(datum: &[u8], offset: usize) -> Location {
+    let mut line       = 1;
+    let mut line_start = 0;
+    let mut index      = 0;
+
+    while index < offset && index < datum.len() {
+        if datum[index] == b'\n' || (datum[index] == b'\r' && datum.get(index + 1) != Some(&(b'\n'))) {
+            line      += 1;
+            line_start = index + 1;
+        }
+
+        index += 1;
+    }
+
+    Location {
+        offset: offset,
+        line  : line,
+        column: offset - line_start + 1
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::panic;
+    use super::{
+        DATUM,
+        Location,
+        Span,
+        Spanned,
+        locate,
+        spanned,
+        within
+    };
+    use super::super::internal::{
+        Error,
+        ErrorKind,
+        Result
+    };
+
+    #[test]
+    fn case_span() {
+        let datum = b"foobar";
+
+        assert_eq!(within(&datum[..], |datum| Span::new(&datum[2..], &datum[5..])), Span { start: 2, end: 5 });
+    }
+
+    #[test]
+    fn case_span_outside_a_datum() {
+        let datum = b"foobar";
+
+        assert_eq!(Span::new(&datum[2..], &datum[5..]), Span { start: 0, end: 3 });
+    }
+
+    #[test]
+    fn case_span_to() {
+        assert_eq!(Span { start: 1, end: 3 }.to(Span { start: 5, end: 8 }), Span { start: 1, end: 8 });
+    }
+
+    #[test]
+    fn case_span_locations() {
+        let datum = b"foo\nbar";
+        let span  = Span { start: 1, end: 6 };
+
+        assert_eq!(span.start_location(&datum[..]), Location { offset: 1, line: 1, column: 2 });
+        assert_eq!(span.end_location(&datum[..]), Location { offset: 6, line: 2, column: 3 });
+    }
+
+    #[test]
+    fn case_locate_first_byte() {
+        assert_eq!(locate(b"", 0), Location { offset: 0, line: 1, column: 1 });
+    }
+
+    #[test]
+    fn case_locate_after_newlines() {
+        let datum = b"a\nb\r\nc\rd";
+
+        assert_eq!(locate(&datum[..], 2), Location { offset: 2, line: 2, column: 1 });
+        assert_eq!(locate(&datum[..], 4), Location { offset: 4, line: 2, column: 3 });
+        assert_eq!(locate(&datum[..], 5), Location { offset: 5, line: 3, column: 1 });
+        assert_eq!(locate(&datum[..], 7), Location { offset: 7, line: 4, column: 1 });
+    }
+
+    #[test]
+    fn case_locate_at_the_end() {
+        assert_eq!(locate(b"ab\n", 3), Location { offset: 3, line: 2, column: 1 });
+    }
+
+    #[test]
+    fn case_spanned_equality_ignores_the_spans() {
+        assert_eq!(Spanned::new(42, Span { start: 1, end: 3 }), Spanned::from(42));
+        assert!(Spanned::new(42, Span { start: 1, end: 3 }) != Spanned::new(43, Span { start: 1, end: 3 }));
+    }
+
+    #[test]
+    fn case_spanned_map() {
+        let node = Spanned::new(42, Span { start: 1, end: 3 }).map(|node| node + 1);
+
+        assert_eq!(node.node, 43);
+        assert_eq!(node.span, Span { start: 1, end: 3 });
+    }
+
+    #[test]
+    fn case_spanned() {
+        let datum  = b"foobar";
+        let output = spanned(&datum[..], |input| Result::Done(&input[3..], 42));
+
+        match output {
+            Result::Done(rest, node) => {
+                assert_eq!(rest, &b"bar"[..]);
+                assert_eq!(node.span, Span { start: 0, end: 3 });
+                assert_eq!(*node, 42);
+            },
+
+            _ => panic!("The parser fails.")
+        }
+    }
+
+    #[test]
+    fn case_spanned_within_a_datum() {
+        let datum  = b"foobar";
+        let output = within(
+            &datum[..],
+            |datum| {
+                spanned(
+                    &datum[2..],
+                    |input| spanned(&input[1..], |input| Result::Done(&input[2..], 42))
+                )
+            }
+        );
+
+        match output {
+            Result::Done(_, node) => {
+                assert_eq!(node.span, Span { start: 2, end: 5 });
+                assert_eq!(node.node.span, Span { start: 3, end: 5 });
+            },
+
+            _ => panic!("The parser fails.")
+        }
+
+        assert!(DATUM.with(|datum| datum.get()).is_none());
+    }
+
+    #[test]
+    fn case_spanned_error() {
+        let datum  = b"foo";
+        let output = spanned(&datum[..], |input| -> Result<&[u8], ()> { Result::Error(Error::Position(ErrorKind::Tag, input)) });
+
+        assert_eq!(output, Result::Error(Error::Position(ErrorKind::Tag, &datum[..])));
+    }
+
+    #[test]
+    fn case_within_unsets_the_datum_on_panic() {
+        let datum  = b"foo";
+        let result = panic::catch_unwind(|| within(&datum[..], |_| -> () { panic!("The parser panics.") }));
+
+        assert!(result.is_err());
+        assert!(DATUM.with(|datum| datum.get()).is_none());
+    }
+}
@@ -45,7 +45,7 @@ use rules::whitespaces::whitespace;
 ///
 /// The following example declares the `FOO_BAR` token:
 ///
-/// ```
+/// ```ignore
 /// token!(FOO_BAR: b"foobar"; "The `FOO_BAR` token, mostly used in example.");
 /// ```
 macro_rules! token {
@@ -72,6 +72,10 @@ token!(
     pub ADD_AND_ASSIGN: b"+=";
     "The `ADD_AND_ASSIGN` token.\n\nRepresent the addition assignment operator, e.g. `$x += $y;`."
 );
+token!(
+    pub ALTERNATIVE_SYNTAX: COLON;
+    "The `ALTERNATIVE_SYNTAX` token.\n\nRepresent the beginning of the body of a control structure in the alternative syntax, e.g. `switch ($x): … endswitch;`."
+);
 token!(
     pub AND: b"and";
     "The `AND` token.\n\nRepresent the conjunction operator, used in a logical expression, e.g. `$x and $y`."
@@ -88,6 +92,10 @@ token!(
     pub ASSIGN: b"=";
     "The `ASSIGN` token.\n\nRepresent a binding of a value to a variable, e.g. `$x = 42`."
 );
+token!(
+    pub ATTRIBUTE: b"#[";
+    "The `ATTRIBUTE` token.\n\nRepresent the opening of an attribute group, e.g. `#[A, B(42)]`."
+);
 token!(
     pub BITWISE_AND: b"&";
     "The `BITWISE_AND` token.\n\nRepresent the bitwise conjunction operator, e.g. `$x & $y`."
@@ -132,6 +140,14 @@ token!(
     pub BITWISE_XOR_AND_ASSIGN: b"^=";
     "The `BITWISE_XOR_AND_ASSIGN` token.\n\nRepresent the exclusive bitwise disjunction assignment operator, e.g. `$x ^= $y;`."
 );
+token!(
+    pub BOOL: b"bool";
+    "The `BOOL` token.\n\nRepresent the boolean type, e.g. `(bool) $x`."
+);
+token!(
+    pub BOOLEAN: b"boolean";
+    "The `BOOLEAN` token.\n\nRepresent the boolean type (long form), e.g. `(boolean) $x`."
+);
 token!(
     pub BOOLEAN_AND: b"&&";
     "The `BOOLEAN_AND` token.\n\nRepresent the boolean conjunction operator, e.g. `$x && $y`."
@@ -156,6 +172,10 @@ token!(
     pub CASE: b"case";
     "The `CASE` token.\n\nRepresent a case in a `switch` control structure, e.g. `switch (…) { case …: …; }`."
 );
+token!(
+    pub CASE_SEPARATOR: COLON;
+    "The `CASE_SEPARATOR` token.\n\nRepresent the end of a case label, e.g. `case 42: …`."
+);
 token!(
     pub CATCH: b"catch";
     "The `CATCH` token.\n\nRepresent the `catch` block of a `try`/`catch` control structure, e.g. `try { … } catch (Exception $e) { … }`."
@@ -168,6 +188,10 @@ token!(
     pub CLONE: b"clone";
     "The `CLONE` token.\n\nRepresent the clone operator, e.g. `clone $x`."
 );
+token!(
+    pub CLOSE_TAG: b"?>";
+    "The `CLOSE_TAG` token.\n\nRepresent the closing tag of a PHP section, e.g. `<?php echo 42; ?>`."
+);
 token!(
     pub COALESCE: b"??";
     "The `COALESCE` token.\n\nRepresent the null coalescing operator, e.g. `$x ?? $y`."
@@ -224,6 +248,10 @@ token!(
     pub DO: b"do";
     "The `DO` token.\n\nRepresent the body of a `do`/`while` loop, e.g. `do { … } while (…);`."
 );
+token!(
+    pub DOUBLE: b"double";
+    "The `DOUBLE` token.\n\nRepresent the real type (alternative form), e.g. `(double) $x`."
+);
 token!(
     pub DYNAMIC_CALL: b"->";
     "The `DYNAMIC_CALL` token.\n\nRepresent the dynamic method call operator, e.g. `$object->method()`."
@@ -248,6 +276,10 @@ token!(
     pub EMPTY: b"empty";
     "The `EMPTY` token.\n\nRepresent the emptiness operator, e.g. `empty($x)`."
 );
+token!(
+    pub ENCODING: b"encoding";
+    "The `ENCODING` token.\n\nRepresent the encoding directive of a declaration, e.g. `declare(encoding='UTF-8');`."
+);
 token!(
     pub ENDDECLARE: b"enddeclare";
     "The `ENDDECLARE` token.\n\nRepresent the end of a `declare` block, e.g. `declare: … enddeclare`."
@@ -272,6 +304,14 @@ token!(
     pub ENDWHILE: b"endwhile";
     "The `ENDWHILE` token.\n\nRepresent the end of a `while` block, e.g. `while(…): … endwhile`."
 );
+token!(
+    pub ENUM: b"enum";
+    "The `ENUM` token.\n\nRepresent the enumeration declaration operator, e.g. `enum Suit { … }`."
+);
+token!(
+    pub ENUM_BACKING_TYPE: COLON;
+    "The `ENUM_BACKING_TYPE` token.\n\nRepresent the backing type separator of an enumeration, e.g. `enum Suit: string { … }`."
+);
 token!(
     pub EQUAL: b"==";
     "The `EQUAL` token.\n\nRepresent the equality comparison operator, e.g. `$x == $y`."
@@ -288,6 +328,10 @@ token!(
     pub EXTENDS: b"extends";
     "The `EXTENDS` token.\n\nRepresent the inheritance operator, e.g. `class C extends D { … }`."
 );
+token!(
+    pub FALSE: b"false";
+    "The `FALSE` token.\n\nRepresent the false boolean and type, e.g. `false` or `function f(): false { … }`."
+);
 token!(
     pub FINAL: b"final";
     "The `FINAL` token.\n\nRepresent a final entity, e.g. `final class C { … }`."
@@ -296,6 +340,14 @@ token!(
     pub FINALLY: b"finally";
     "The `FINALLY` token.\n\nRepresent the finally block of a `try`/`catch` control structure, e.g. `try { … } catch (…) { … } finally { … }`."
 );
+token!(
+    pub FLOAT: b"float";
+    "The `FLOAT` token.\n\nRepresent the real type, e.g. `(float) $x`."
+);
+token!(
+    pub FN: b"fn";
+    "The `FN` token.\n\nRepresent the arrow function declaration operator, e.g. `fn(…) => …`."
+);
 token!(
     pub FOR: b"for";
     "The `FOR` token.\n\nRepresent a `for` loop, e.g. `for (…; …; …) { … }`."
@@ -304,6 +356,10 @@ token!(
     pub FOREACH: b"foreach";
     "The `FOREACH` token.\n\nRepresent a `foreach` loop, e.g. `foreach ($i as $k => $v) { … }`."
 );
+token!(
+    pub FROM: b"from";
+    "The `FROM` token.\n\nRepresent the second part of the delegated generator operator, e.g. `yield from …;`."
+);
 token!(
     pub FUNCTION: b"function";
     "The `FUNCTION` token.\n\nRepresent the function declaration operator, e.g. `function f(…) { … }`."
@@ -312,6 +368,10 @@ token!(
     pub FUNCTION_OUTPUT: COLON;
     "The `FUNCTION_OUTPUT` token.\n\nRepresent the function return type declaration operator, e.g. `function f(…): … { … }`."
 );
+token!(
+    pub GET: b"get";
+    "The `GET` token.\n\nRepresent the property hook intercepting the reads, e.g. `get => $this->x;`."
+);
 token!(
     pub GLOBAL: b"global";
     "The `GLOBAL` token.\n\nRepresent the global visibility modifier, e.g. `global $x`."
@@ -328,6 +388,10 @@ token!(
     pub GREATER_THAN_OR_EQUAL_TO: b">=";
     "The `GREATER_THAN_OR_EQUAL_TO` token.\n\nRepresent the greater than or equal to comparison operator, e.g. `$x >= $y`."
 );
+token!(
+    pub HALT_COMPILER: b"__halt_compiler";
+    "The `HALT_COMPILER` token.\n\nRepresent the end of the compilation, e.g. `__halt_compiler();`, the rest of the file being raw data."
+);
 token!(
     pub IDENTICAL: b"===";
     "The `IDENTICAL` token.\n\nRepresent the strict equality comparison operator, e.g. `$x === $y`."
@@ -360,6 +424,14 @@ token!(
     pub INSTEADOF: b"insteadof";
     "The `INSTEADOF` token.\n\nRepresent the conflict resolution operator, `use C, D { C::f insteadof D }`."
 );
+token!(
+    pub INT: b"int";
+    "The `INT` token.\n\nRepresent the integer type, e.g. `(int) $x`."
+);
+token!(
+    pub INTEGER: b"integer";
+    "The `INTEGER` token.\n\nRepresent the integer type (long form), e.g. `(integer) $x`."
+);
 token!(
     pub INTERFACE: b"interface";
     "The `INTERFACE` token.\n\nRepresent the interface declaration operator, e.g. `interface I { … }`."
@@ -368,6 +440,14 @@ token!(
     pub ISSET: b"isset";
     "The `ISSET` token.\n\nRepresent the existence operator, e.g. `isset($x)`."
 );
+token!(
+    pub ITERABLE: b"iterable";
+    "The `ITERABLE` token.\n\nRepresent the iterable type, e.g. `function f(iterable $x) { … }`."
+);
+token!(
+    pub LABEL_SEPARATOR: COLON;
+    "The `LABEL_SEPARATOR` token.\n\nRepresent the end of a label declaration, e.g. `foo: …`."
+);
 token!(
     pub LEFT_CURLY_BRACKET: b"{";
     "The `LEFT_CURLY_BRACKET` token.\n\nUsed to open a block, e.g. `if (…) { … }`."
@@ -392,10 +472,50 @@ token!(
     pub LIST: b"list";
     "The `LIST` token.\n\nRepresent the destructuring operator, e.g. `list($x, $y) = $a`."
 );
+token!(
+    pub MAGIC_CLASS: b"__CLASS__";
+    "The `MAGIC_CLASS` token.\n\nRepresent the magic constant holding the name of the current class, i.e. `__CLASS__`."
+);
+token!(
+    pub MAGIC_DIRECTORY: b"__DIR__";
+    "The `MAGIC_DIRECTORY` token.\n\nRepresent the magic constant holding the directory of the current file, i.e. `__DIR__`."
+);
+token!(
+    pub MAGIC_FILE: b"__FILE__";
+    "The `MAGIC_FILE` token.\n\nRepresent the magic constant holding the full path of the current file, i.e. `__FILE__`."
+);
+token!(
+    pub MAGIC_FUNCTION: b"__FUNCTION__";
+    "The `MAGIC_FUNCTION` token.\n\nRepresent the magic constant holding the name of the current function, i.e. `__FUNCTION__`."
+);
+token!(
+    pub MAGIC_LINE: b"__LINE__";
+    "The `MAGIC_LINE` token.\n\nRepresent the magic constant holding the current line number, i.e. `__LINE__`."
+);
+token!(
+    pub MAGIC_METHOD: b"__METHOD__";
+    "The `MAGIC_METHOD` token.\n\nRepresent the magic constant holding the name of the current method, i.e. `__METHOD__`."
+);
+token!(
+    pub MAGIC_NAMESPACE: b"__NAMESPACE__";
+    "The `MAGIC_NAMESPACE` token.\n\nRepresent the magic constant holding the name of the current namespace, i.e. `__NAMESPACE__`."
+);
+token!(
+    pub MAGIC_TRAIT: b"__TRAIT__";
+    "The `MAGIC_TRAIT` token.\n\nRepresent the magic constant holding the name of the current trait, i.e. `__TRAIT__`."
+);
 token!(
     pub MAP: b"=>";
     "The `MAP` token.\n\nRepresent the mapping operator in an array, e.g. `[42 => 'foo']`."
 );
+token!(
+    pub MATCH: b"match";
+    "The `MATCH` token.\n\nRepresent the match expression, e.g. `match ($x) { 1, 2 => …, default => … }`."
+);
+token!(
+    pub MIXED: b"mixed";
+    "The `MIXED` token.\n\nRepresent the mixed type, e.g. `function f(mixed $x) { … }`."
+);
 token!(
     pub MODULO: b"%";
     "The `MODULO` token.\n\nRepresent the modulus operator, e.g. `$x % $y`."
@@ -412,6 +532,10 @@ token!(
     pub MULTIPLY_AND_ASSIGN: b"*=";
     "The `MULTIPLY_AND_ASSIGN` token.\n\nRepresent the multiplication assignment operator, e.g. `$x *= $y;`."
 );
+token!(
+    pub NAMED_ARGUMENT: COLON;
+    "The `NAMED_ARGUMENT` token.\n\nRepresent the named argument operator, e.g. `f(x: 42)`."
+);
 token!(
     pub NAMESPACE: b"namespace";
     "The `NAMESPACE` token.\n\nRepresent the namespace declaration operator or the current namespace name, e.g. `namespace N;`."
@@ -420,6 +544,10 @@ token!(
     pub NAMESPACE_SEPARATOR: b"\\";
     "The `NAMESPACE_SEPARATOR` token.\n\nRepresent the namespace separator, e.g. `A\\B\\C`."
 );
+token!(
+    pub NEVER: b"never";
+    "The `NEVER` token.\n\nRepresent the never type, e.g. `function f(): never { … }`."
+);
 token!(
     pub NEW: b"new";
     "The `NEW` token.\n\nRepresent the instanciation operator, e.g. `new C()`."
@@ -432,14 +560,38 @@ token!(
     pub NOT_IDENTICAL: b"!==";
     "The `NOT_IDENTICAL` token.\n\nRepresent the strict not equal comparison operator, e.g. `$x !== $y`."
 );
+token!(
+    pub NULL: b"null";
+    "The `NULL` token.\n\nRepresent the null value and type, e.g. `null` or `function f(): null { … }`."
+);
 token!(
     pub NULLABLE: QUESTION_MARK;
     "The `NULLABLE` token.\n\nRepresent the nullable operation, e.g. `function f(?int $x) { … }`."
 );
+token!(
+    pub NULLSAFE_DYNAMIC_CALL: b"?->";
+    "The `NULLSAFE_DYNAMIC_CALL` token.\n\nRepresent the nullsafe object operator, e.g. `$object?->property`."
+);
+token!(
+    pub OBJECT: b"object";
+    "The `OBJECT` token.\n\nRepresent the object type, e.g. `(object) $x`."
+);
+token!(
+    pub OPEN_TAG: b"<?php";
+    "The `OPEN_TAG` token.\n\nRepresent the opening tag of a PHP section, e.g. `<?php echo 42;`."
+);
+token!(
+    pub OPEN_TAG_WITH_ECHO: b"<?=";
+    "The `OPEN_TAG_WITH_ECHO` token.\n\nRepresent the opening tag of a PHP section echoing expressions, e.g. `<?= $x ?>`."
+);
 token!(
     pub OR: b"or";
     "The `OR` token.\n\nRepresent the inclusive disjunction operator, used in a logical expression, e.g. `$x or $y`."
 );
+token!(
+    pub PARENT: b"parent";
+    "The `PARENT` token.\n\nRepresent the parent class scope, e.g. `parent::f()`."
+);
 token!(
     pub POW: b"**";
     "The `POW` token.\n\nRepresent the power operator, e.g. `$x ** $y`."
@@ -456,14 +608,34 @@ token!(
     pub PRIVATE: b"private";
     "The `PRIVATE` token.\n\nRepresent the private visibility operator, e.g. `private $x`."
 );
+token!(
+    pub PRIVATE_SET: b"private(set)";
+    "The `PRIVATE_SET` token.\n\nRepresent the private write visibility modifier, e.g. `public private(set) int $x`."
+);
 token!(
     pub PROTECTED: b"protected";
     "The `PROTECTED` token.\n\nRepresent the protected visibility operator, e.g. `protected $x`."
 );
+token!(
+    pub PROTECTED_SET: b"protected(set)";
+    "The `PROTECTED_SET` token.\n\nRepresent the protected write visibility modifier, e.g. `public protected(set) int $x`."
+);
 token!(
     pub PUBLIC: b"public";
     "The `PUBLIC` token.\n\nRepresent the public visibility operator, e.g. `public $x`."
 );
+token!(
+    pub PUBLIC_SET: b"public(set)";
+    "The `PUBLIC_SET` token.\n\nRepresent the public write visibility modifier, e.g. `public public(set) int $x`."
+);
+token!(
+    pub READONLY: b"readonly";
+    "The `READONLY` token.\n\nRepresent the readonly modifier, e.g. `readonly class C { … }` or `public readonly int $x;`."
+);
+token!(
+    pub REAL: b"real";
+    "The `REAL` token.\n\nRepresent the real type (alternative form), e.g. `(real) $x`."
+);
 token!(
     pub REFERENCE: b"&";
     "The `REFERENCE` token.\n\nRepresent the reference operator, e.g. `&$x`."
@@ -481,7 +653,7 @@ token!(
     "The `RETURN` token.\n\nRepresent the return operator, e.g. `return $x;`."
 );
 token!(
-    pub RIGHT_CURLY_BRACKET: b"{";
+    pub RIGHT_CURLY_BRACKET: b"}";
     "The `RIGHT_CURLY_BRACKET` token.\n\nUsed to close a block, e.g. `if (…) { … }`."
 );
 token!(
@@ -492,10 +664,18 @@ token!(
     pub RIGHT_SQUARE_BRACKET: b"]";
     "The `RIGHT_SQUARE_BRACKET` token.\n\nUsed to close an array construction or an array access for instance, e.g. `[2, 4, 6, 9][0]`."
 );
+token!(
+    pub SELF: b"self";
+    "The `SELF` token.\n\nRepresent the current class scope, e.g. `self::f()`."
+);
 token!(
     pub SEMICOLON: b";";
     "The `SEMICOLON` token.\n\nRepresent the end of an instruction, e.g. `$x = …;`."
 );
+token!(
+    pub SET: b"set";
+    "The `SET` token.\n\nRepresent the property hook intercepting the writes, e.g. `set ($value) { … }`."
+);
 token!(
     pub STATIC: b"static";
     "The `STATIC` token.\n\nRepresent the stack declaration operator, e.g. `static $x`."
@@ -504,6 +684,14 @@ token!(
     pub STATIC_CALL: b"::";
     "The `STATIC_CALL` token.\n\nRepresent the static method call operator, e.g. `class::method()`."
 );
+token!(
+    pub STRICT_TYPES: b"strict_types";
+    "The `STRICT_TYPES` token.\n\nRepresent the strict types directive of a declaration, e.g. `declare(strict_types=1);`."
+);
+token!(
+    pub STRING: b"string";
+    "The `STRING` token.\n\nRepresent the string type, e.g. `(string) $x`."
+);
 token!(
     pub SUBTRACT: b"-";
     "The `SUBTRACT` token.\n\nRepresent the subtraction operator, e.g. `$x - $y`."
@@ -528,10 +716,18 @@ token!(
     pub THROW: b"throw";
     "The `THROW` token.\n\nRepresent the throw exception operator, e.g. `throw $e;`."
 );
+token!(
+    pub TICKS: b"ticks";
+    "The `TICKS` token.\n\nRepresent the ticks directive of a declaration, e.g. `declare(ticks=1);`."
+);
 token!(
     pub TRAIT: b"trait";
     "The `TRAIT` token.\n\nRepresent the trait declaration operator, e.g. `trait T { … }`."
 );
+token!(
+    pub TRUE: b"true";
+    "The `TRUE` token.\n\nRepresent the true boolean and type, e.g. `true` or `function f(): true { … }`."
+);
 token!(
     pub TRY: b"try";
     "The `TRY` token.\n\nRepresent the `try` block of a `try`/`catch` control structure, e.g. `try { … } catch (Exception $e) { … }`."
@@ -552,6 +748,10 @@ token!(
     pub VARIABLE: b"$";
     "The `VARIABLE` token.\n\nRepresent the variable declaration operator, e.g. `$foo`."
 );
+token!(
+    pub VOID: b"void";
+    "The `VOID` token.\n\nRepresent the void type, e.g. `function f(): void { … }`."
+);
 token!(
     pub WHILE: b"while";
     "The `WHILE` token.\n\nRepresent a `while` loop, e.g. `while (…) { … }`."
@@ -571,7 +771,7 @@ token!(
 
 token!(
     COLON: b":";
-    "The `COLON` private token.\n\nSee `FUNCTION_OUTPUT` and `TERNARY_ELSE`."
+    "The `COLON` private token.\n\nSee `ALTERNATIVE_SYNTAX`, `CASE_SEPARATOR`, `ENUM_BACKING_TYPE`, `FUNCTION_OUTPUT`, `LABEL_SEPARATOR`, `NAMED_ARGUMENT` and `TERNARY_ELSE`."
 );
 token!(
     QUESTION_MARK: b"?";
@@ -612,11 +812,13 @@ named!(
       | keyword!(EXTENDS)
       | keyword!(FINALLY)
       | keyword!(FINAL)
+      | keyword!(FN)
       | keyword!(FOREACH)
       | keyword!(FOR)
       | keyword!(FUNCTION)
       | keyword!(GLOBAL)
       | keyword!(GOTO)
+      | keyword!(HALT_COMPILER)
       | keyword!(IF)
       | keyword!(IMPLEMENTS)
       | keyword!(INCLUDE_ONCE)
@@ -626,6 +828,15 @@ named!(
       | keyword!(INTERFACE)
       | keyword!(ISSET)
       | keyword!(LIST)
+      | keyword!(MAGIC_CLASS)
+      | keyword!(MAGIC_DIRECTORY)
+      | keyword!(MAGIC_FILE)
+      | keyword!(MAGIC_FUNCTION)
+      | keyword!(MAGIC_LINE)
+      | keyword!(MAGIC_METHOD)
+      | keyword!(MAGIC_NAMESPACE)
+      | keyword!(MAGIC_TRAIT)
+      | keyword!(MATCH)
       | keyword!(NAMESPACE)
       | keyword!(NEW)
       | keyword!(OR)
@@ -647,9 +858,9 @@ named!(
       | keyword!(WHILE)
       | keyword!(XOR)
       | chain!(
-            keyword!("yield") ~
+            keyword!(YIELD) ~
             whitespace ~
-            keyword!("from"),
+            keyword!(FROM),
             || { YIELD_FROM }
         )
       | keyword!(YIELD)
@@ -670,7 +881,6 @@ mod tests {
         ($test_case_name:ident: ($string:expr, $expect:expr)) => (
             #[test]
             fn $test_case_name() {
-                use std::ascii::AsciiExt;
                 use std::str;
 
                 let output     = Result::Done(&b""[..], $expect);
@@ -713,11 +923,13 @@ mod tests {
     test_keyword!(case_keyword_extends:      (b"extends", super::EXTENDS));
     test_keyword!(case_keyword_final:        (b"final", super::FINAL));
     test_keyword!(case_keyword_finally:      (b"finally", super::FINALLY));
+    test_keyword!(case_keyword_fn:           (b"fn", super::FN));
     test_keyword!(case_keyword_for:          (b"for", super::FOR));
     test_keyword!(case_keyword_foreach:      (b"foreach", super::FOREACH));
     test_keyword!(case_keyword_function:     (b"function", super::FUNCTION));
     test_keyword!(case_keyword_global:       (b"global", super::GLOBAL));
     test_keyword!(case_keyword_goto:         (b"goto", super::GOTO));
+    test_keyword!(case_keyword_halt_compiler: (b"__halt_compiler", super::HALT_COMPILER));
     test_keyword!(case_keyword_if:           (b"if", super::IF));
     test_keyword!(case_keyword_implements:   (b"implements", super::IMPLEMENTS));
     test_keyword!(case_keyword_include:      (b"include", super::INCLUDE));
@@ -727,6 +939,15 @@ mod tests {
     test_keyword!(case_keyword_interface:    (b"interface", super::INTERFACE));
     test_keyword!(case_keyword_isset:        (b"isset", super::ISSET));
     test_keyword!(case_keyword_list:         (b"list", super::LIST));
+    test_keyword!(case_keyword_magic_class:  (b"__CLASS__", super::MAGIC_CLASS));
+    test_keyword!(case_keyword_magic_directory: (b"__DIR__", super::MAGIC_DIRECTORY));
+    test_keyword!(case_keyword_magic_file:   (b"__FILE__", super::MAGIC_FILE));
+    test_keyword!(case_keyword_magic_function: (b"__FUNCTION__", super::MAGIC_FUNCTION));
+    test_keyword!(case_keyword_magic_line:   (b"__LINE__", super::MAGIC_LINE));
+    test_keyword!(case_keyword_magic_method: (b"__METHOD__", super::MAGIC_METHOD));
+    test_keyword!(case_keyword_magic_namespace: (b"__NAMESPACE__", super::MAGIC_NAMESPACE));
+    test_keyword!(case_keyword_magic_trait:  (b"__TRAIT__", super::MAGIC_TRAIT));
+    test_keyword!(case_keyword_match:        (b"match", super::MATCH));
     test_keyword!(case_keyword_namespace:    (b"namespace", super::NAMESPACE));
     test_keyword!(case_keyword_new:          (b"new", super::NEW));
     test_keyword!(case_keyword_or:           (b"or", super::OR));
@@ -0,0 +1,298 @@
+// Tagua VM
+//
+//
+// New BSD License
+//
+// Copyright © 2016-2016, Ivan Enderlin.
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//     * Redistributions of source code must retain the above copyright
+//       notice, this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright
+//       notice, this list of conditions and the following disclaimer in the
+//       documentation and/or other materials provided with the distribution.
+//     * Neither the name of the Hoa nor the names of its contributors may be
+//       used to endorse or promote products derived from this software without
+//       specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE
+// ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDERS AND CONTRIBUTORS BE
+// LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR
+// CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF
+// SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS
+// INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN
+// CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+
+//! Collection of the comments skipped by the parser.
+//!
+//! Comments are skipped between tokens, so they are absent from the AST.
+//! Tools like formatters or documentation generators need them though. The
+//! comments can be collected while parsing a datum: they are recorded on the
+//! current thread, so that the rules do not have to carry them.
+//!
+//! # Examples
+//!
+//! ```
+//! use tagua_parser::rules::expressions::expression;
+//! use tagua_parser::trivia::{self, Comment, CommentKind};
+//!
+//! let input               = b"$a = /* one */ [1, // two\n2]";
+//! let (_result, comments) = trivia::collect(&input[..], expression);
+//!
+//! assert_eq!(
+//!     comments,
+//!     vec![
+//!         Comment {
+//!             kind   : CommentKind::Delimited,
+//!             content: &b" one "[..],
+//!             span   : 5..14
+//!         },
+//!         Comment {
+//!             kind   : CommentKind::SingleLine,
+//!             content: &b" two\n"[..],
+//!             span   : 19..26
+//!         }
+//!     ]
+//! );
+//! ```
+
+use std::cell::{
+    Cell,
+    RefCell
+};
+use std::mem;
+use std::ops::Range;
+
+/// The kind of a comment.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum CommentKind {
+    /// A comment starting with `//` or `#`, and running until the end of
+    /// the line.
+    SingleLine,
+
+    /// A comment delimited by `/*` and `*/`, including documentation
+    /// comments starting with `/**`.
+    Delimited,
+
+    /// The shebang line starting a script, e.g. `#!/usr/bin/env php`. Its
+    /// content excludes `#!` and the newline.
+    Shebang
+}
+
+/// A comment found in the parsed datum.
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Comment<'a> {
+    /// The kind of the comment.
+    pub kind: CommentKind,
+
+    /// The content of the comment, without its delimiters.
+    pub content: &'a [u8],
+
+    /// The byte range of the whole comment, delimiters included, in the
+    /// parsed datum.
+    pub span: Range<usize>
+}
+
+thread_local!(
+    static IS_COLLECTING: Cell<bool> = const { Cell::new(false) }
+);
+
+thread_local!(
+    static COMMENTS: RefCell<Vec<(usize, usize, usize, usize, CommentKind)>> = const { RefCell::new(Vec::new()) }
+);
+
+/// Run a parser on a datum and collect all the comments it has skipped,
+/// sorted by position.
+///
+/// Because the parser backtracks, a comment can be skipped several times;
+/// it is collected once.
+pub fn collect<'a, F, T>(input: &'a [u8], parser: F) -> (T, Vec<Comment<'a>>)
+    where F: FnOnce(&'a [u8]) -> T
+{
+    let collection  = CollectionGuard::new();
+    let output      = parser(input);
+    let mut records = collection.records();
+    let start       = input.as_ptr() as usize;
+    let end         = start + input.len();
+
+    records.sort_by_key(|a| a.0);
+    records.dedup();
+
+    let mut comments: Vec<Comment<'a>> = Vec::new();
+
+    for (comment_start, content_start, content_length, comment_end, kind) in records {
+        if comment_start < start || comment_end > end {
+            continue;
+        }
+
+        // Skip a comment found inside another one, like `// /* */`.
+        if let Some(last) = comments.last() {
+            if comment_start - start < last.span.end {
+                continue;
+            }
+        }
+
+        let content_start = content_start - start;
+
+        comments.push(
+            Comment {
+                kind   : kind,
+                content: &input[content_start..content_start + content_length],
+                span   : comment_start - start..comment_end - start
+            }
+        );
+    }
+
+    (output, comments)
+}
+
+/// Collect the comments on the current thread, and stop collecting them
+/// when dropped, even if the parser panics.
+struct CollectionGuard;
+
+impl CollectionGuard {
+    fn new() -> CollectionGuard {
+        COMMENTS.with(|comments| comments.borrow_mut().clear());
+        IS_COLLECTING.with(|is_collecting| is_collecting.set(true));
+
+        CollectionGuard
+    }
+
+    /// Stop collecting, and return the records collected so far.
+    fn records(self) -> Vec<(usize, usize, usize, usize, CommentKind)> {
+        COMMENTS.with(|comments| mem::take(&mut *comments.borrow_mut()))
+    }
+}
+
+impl Drop for CollectionGuard {
+    fn drop(&mut self) {
+        IS_COLLECTING.with(|is_collecting| is_collecting.set(false));
+        COMMENTS.with(|comments| comments.borrow_mut().clear());
+    }
+}
+
+/// Record a comment if the comments are being collected on the current
+/// thread. The comment spans from `input` to `rest`, and its content starts
+/// at `content`.
+pub fn record(input: &[u8], content: &[u8], rest: &[u8], kind: CommentKind) {
+    if !IS_COLLECTING.with(|is_collecting| is_collecting.get()) {
+        return;
+    }
+
+    COMMENTS.with(
+        |comments| {
+            comments.borrow_mut().push((
+                input.as_ptr() as usize,
+                content.as_ptr() as usize,
+                content.len(),
+                rest.as_ptr() as usize,
+                kind
+            ))
+        }
+    );
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::panic;
+    use super::{
+        COMMENTS,
+        Comment,
+        CommentKind,
+        IS_COLLECTING,
+        collect
+    };
+    use super::super::internal::Result;
+    use super::super::rules::comments::comment;
+    use super::super::rules::statements::statement;
+
+    #[test]
+    fn case_collect_nothing() {
+        let input = b"$a = 1;";
+        let (_, comments) = collect(&input[..], statement);
+
+        assert_eq!(comments, vec![]);
+    }
+
+    #[test]
+    fn case_collect_comments_once_despite_backtracking() {
+        let input = b"if /* a */ ($a) # b\n{ /** c */ }";
+        let (_, comments) = collect(&input[..], statement);
+
+        assert_eq!(
+            comments,
+            vec![
+                Comment {
+                    kind   : CommentKind::Delimited,
+                    content: &b" a "[..],
+                    span   : 3..10
+                },
+                Comment {
+                    kind   : CommentKind::SingleLine,
+                    content: &b" b\n"[..],
+                    span   : 16..20
+                },
+                Comment {
+                    kind   : CommentKind::Delimited,
+                    content: &b"* c "[..],
+                    span   : 22..30
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn case_collect_comment_inside_a_comment() {
+        let input = b"// a /* b */\n";
+        let (_, comments) = collect(&input[..], comment);
+
+        assert_eq!(
+            comments,
+            vec![
+                Comment {
+                    kind   : CommentKind::SingleLine,
+                    content: &b" a /* b */\n"[..],
+                    span   : 0..13
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn case_comments_are_not_recorded_outside_collect() {
+        let input = b"/* a */";
+
+        assert_eq!(comment(input), Result::Done(&b""[..], &b" a "[..]));
+        assert!(COMMENTS.with(|comments| comments.borrow().is_empty()));
+    }
+
+    #[test]
+    fn case_collect_stops_collecting_on_panic() {
+        let input  = b"/* foo */";
+        let result = panic::catch_unwind(
+            || {
+                collect(
+                    &input[..],
+                    |input| {
+                        comment(input);
+
+                        panic!("The parser panics.");
+                    }
+                )
+            }
+        );
+
+        assert!(result.is_err());
+        assert!(!IS_COLLECTING.with(|is_collecting| is_collecting.get()));
+        assert!(COMMENTS.with(|comments| comments.borrow().is_empty()));
+    }
+}
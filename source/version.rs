@@ -0,0 +1,125 @@
+// Tagua VM
+//
+//
+// New BSD License
+//
+// Copyright © 2016-2016, Ivan Enderlin.
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//     * Redistributions of source code must retain the above copyright
+//       notice, this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright
+//       notice, this list of conditions and the following disclaimer in the
+//       documentation and/or other materials provided with the distribution.
+//     * Neither the name of the Hoa nor the names of its contributors may be
+//       used to endorse or promote products derived from this software without
+//       specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE
+// ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDERS AND CONTRIBUTORS BE
+// LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR
+// CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF
+// SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS
+// INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN
+// CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+
+//! Versions of the PHP language.
+//!
+//! Some syntax is only valid from a specific version of PHP. The parser
+//! targets the latest version by default. The targeted version is attached
+//! to the current thread, so that the rules do not have to carry it.
+//!
+//! # Examples
+//!
+//! ```
+//! use tagua_parser::version::{self, Version};
+//!
+//! assert_eq!(version::target(), version::LATEST);
+//!
+//! version::set_target(Version::Php74);
+//! assert_eq!(version::target(), Version::Php74);
+//! ```
+
+use std::cell::Cell;
+
+/// A version of the PHP language.
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub enum Version {
+    /// PHP 7.0.
+    Php70,
+    /// PHP 7.1.
+    Php71,
+    /// PHP 7.2.
+    Php72,
+    /// PHP 7.3.
+    Php73,
+    /// PHP 7.4.
+    Php74,
+    /// PHP 8.0.
+    Php80,
+    /// PHP 8.1.
+    Php81,
+    /// PHP 8.2.
+    Php82,
+    /// PHP 8.3.
+    Php83,
+    /// PHP 8.4.
+    Php84
+}
+
+/// The latest supported version, targeted by default.
+pub const LATEST: Version = Version::Php84;
+
+thread_local!(
+    static TARGET: Cell<Version> = const { Cell::new(LATEST) }
+);
+
+/// Get the version targeted by the parser on the current thread.
+pub fn target() -> Version {
+    TARGET.with(|target| target.get())
+}
+
+/// Set the version targeted by the parser on the current thread.
+pub fn set_target(version: Version) {
+    TARGET.with(|target| target.set(version));
+}
+
+/// Check whether the targeted version is at least the given version.
+pub fn is_targeting(version: Version) -> bool {
+    target() >= version
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        LATEST,
+        Version,
+        is_targeting,
+        set_target,
+        target
+    };
+
+    #[test]
+    fn case_target_defaults_to_the_latest_version() {
+        assert_eq!(target(), LATEST);
+    }
+
+    #[test]
+    fn case_set_target() {
+        set_target(Version::Php73);
+
+        assert_eq!(target(), Version::Php73);
+        assert!(is_targeting(Version::Php70));
+        assert!(is_targeting(Version::Php73));
+        assert!(!is_targeting(Version::Php74));
+
+        set_target(LATEST);
+    }
+}
@@ -0,0 +1,1729 @@
+// Tagua VM
+//
+//
+// New BSD License
+//
+// Copyright © 2016-2016, Ivan Enderlin.
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//     * Redistributions of source code must retain the above copyright
+//       notice, this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright
+//       notice, this list of conditions and the following disclaimer in the
+//       documentation and/or other materials provided with the distribution.
+//     * Neither the name of the Hoa nor the names of its contributors may be
+//       used to endorse or promote products derived from this software without
+//       specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE
+// ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDERS AND CONTRIBUTORS BE
+// LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR
+// CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF
+// SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS
+// INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN
+// CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+
+//! Traversal of the AST.
+//!
+//! The `Visit` and `VisitMut` traits visit every node of the AST. By
+//! default, each method walks through the children of its node, so that a
+//! visitor only overrides the methods of the nodes it is interested in, and
+//! does not break when a new node is added.
+//!
+//! # Examples
+//!
+//! ```
+//! use tagua_parser::ast::Variable;
+//! use tagua_parser::parse;
+//! use tagua_parser::visit::{self, Visit};
+//!
+//! struct Variables<'ast> {
+//!     names: Vec<&'ast [u8]>
+//! }
+//!
+//! impl<'ast> Visit<'ast> for Variables<'ast> {
+//!     fn visit_variable(&mut self, variable: &'ast Variable<'ast>) {
+//!         if let Variable::Name(name) = *variable {
+//!             self.names.push(name);
+//!         }
+//!
+//!         visit::walk_variable(self, variable);
+//!     }
+//! }
+//!
+//! let program       = parse(b"<?php if ($foo) { $bar = [$baz]; }").unwrap();
+//! let mut variables = Variables { names: vec![] };
+//!
+//! for statement in &program.statements {
+//!     variables.visit_statement(statement);
+//! }
+//!
+//! assert_eq!(variables.names, vec![&b"foo"[..], &b"bar"[..], &b"baz"[..]]);
+//! ```
+
+use super::ast::{
+    Argument,
+    Attribute,
+    Catch,
+    ClassMember,
+    ConstantExpression,
+    Expression,
+    Literal,
+    MatchArm,
+    Method,
+    Name,
+    Parameter,
+    PropertyHook,
+    PropertyHookBody,
+    Statement,
+    StringPart,
+    SwitchCase,
+    TraitAdaptation,
+    Type,
+    Use,
+    Variable
+};
+use super::span::Spanned;
+
+/// A visitor of the AST, by reference.
+///
+/// Each method visits a node, and walks through its children by default,
+/// see the `walk_*` functions. Override a method to act on a node, and call
+/// the associated `walk_*` function to continue through its children.
+pub trait Visit<'ast> {
+    /// Visit an argument.
+    fn visit_argument(&mut self, argument: &'ast Argument<'ast>) {
+        walk_argument(self, argument);
+    }
+
+    /// Visit an attribute.
+    fn visit_attribute(&mut self, attribute: &'ast Attribute<'ast>) {
+        walk_attribute(self, attribute);
+    }
+
+    /// Visit a catch clause.
+    fn visit_catch(&mut self, catch: &'ast Catch<'ast>) {
+        walk_catch(self, catch);
+    }
+
+    /// Visit a class member.
+    fn visit_class_member(&mut self, member: &'ast ClassMember<'ast>) {
+        walk_class_member(self, member);
+    }
+
+    /// Visit a constant expression.
+    fn visit_constant_expression(&mut self, constant: &'ast ConstantExpression<'ast>) {
+        walk_constant_expression(self, constant);
+    }
+
+    /// Visit an expression.
+    fn visit_expression(&mut self, expression: &'ast Expression<'ast>) {
+        walk_expression(self, expression);
+    }
+
+    /// Visit a literal, which has no children.
+    fn visit_literal(&mut self, _literal: &'ast Literal) {}
+
+    /// Visit a match arm.
+    fn visit_match_arm(&mut self, arm: &'ast MatchArm<'ast>) {
+        walk_match_arm(self, arm);
+    }
+
+    /// Visit a method.
+    fn visit_method(&mut self, method: &'ast Method<'ast>) {
+        walk_method(self, method);
+    }
+
+    /// Visit a name, which has no children.
+    fn visit_name(&mut self, _name: &'ast Name<'ast>) {}
+
+    /// Visit a parameter.
+    fn visit_parameter(&mut self, parameter: &'ast Parameter<'ast>) {
+        walk_parameter(self, parameter);
+    }
+
+    /// Visit a property hook.
+    fn visit_property_hook(&mut self, hook: &'ast PropertyHook<'ast>) {
+        walk_property_hook(self, hook);
+    }
+
+    /// Visit a statement.
+    fn visit_statement(&mut self, statement: &'ast Statement<'ast>) {
+        walk_statement(self, statement);
+    }
+
+    /// Visit a string part.
+    fn visit_string_part(&mut self, part: &'ast StringPart<'ast>) {
+        walk_string_part(self, part);
+    }
+
+    /// Visit a switch case.
+    fn visit_switch_case(&mut self, case: &'ast SwitchCase<'ast>) {
+        walk_switch_case(self, case);
+    }
+
+    /// Visit a trait adaptation.
+    fn visit_trait_adaptation(&mut self, adaptation: &'ast TraitAdaptation<'ast>) {
+        walk_trait_adaptation(self, adaptation);
+    }
+
+    /// Visit a type.
+    fn visit_type(&mut self, ty: &'ast Type<'ast>) {
+        walk_type(self, ty);
+    }
+
+    /// Visit a use declaration.
+    fn visit_use(&mut self, use_: &'ast Use<'ast>) {
+        walk_use(self, use_);
+    }
+
+    /// Visit a variable.
+    fn visit_variable(&mut self, variable: &'ast Variable<'ast>) {
+        walk_variable(self, variable);
+    }
+}
+
+/// A visitor of the AST, by mutable reference, see `Visit`.
+pub trait VisitMut<'a> {
+    /// Visit an argument.
+    fn visit_argument_mut(&mut self, argument: &mut Argument<'a>) {
+        walk_argument_mut(self, argument);
+    }
+
+    /// Visit an attribute.
+    fn visit_attribute_mut(&mut self, attribute: &mut Attribute<'a>) {
+        walk_attribute_mut(self, attribute);
+    }
+
+    /// Visit a catch clause.
+    fn visit_catch_mut(&mut self, catch: &mut Catch<'a>) {
+        walk_catch_mut(self, catch);
+    }
+
+    /// Visit a class member.
+    fn visit_class_member_mut(&mut self, member: &mut ClassMember<'a>) {
+        walk_class_member_mut(self, member);
+    }
+
+    /// Visit a constant expression.
+    fn visit_constant_expression_mut(&mut self, constant: &mut ConstantExpression<'a>) {
+        walk_constant_expression_mut(self, constant);
+    }
+
+    /// Visit an expression.
+    fn visit_expression_mut(&mut self, expression: &mut Expression<'a>) {
+        walk_expression_mut(self, expression);
+    }
+
+    /// Visit a literal, which has no children.
+    fn visit_literal_mut(&mut self, _literal: &mut Literal) {}
+
+    /// Visit a match arm.
+    fn visit_match_arm_mut(&mut self, arm: &mut MatchArm<'a>) {
+        walk_match_arm_mut(self, arm);
+    }
+
+    /// Visit a method.
+    fn visit_method_mut(&mut self, method: &mut Method<'a>) {
+        walk_method_mut(self, method);
+    }
+
+    /// Visit a name, which has no children.
+    fn visit_name_mut(&mut self, _name: &mut Name<'a>) {}
+
+    /// Visit a parameter.
+    fn visit_parameter_mut(&mut self, parameter: &mut Parameter<'a>) {
+        walk_parameter_mut(self, parameter);
+    }
+
+    /// Visit a property hook.
+    fn visit_property_hook_mut(&mut self, hook: &mut PropertyHook<'a>) {
+        walk_property_hook_mut(self, hook);
+    }
+
+    /// Visit a statement.
+    fn visit_statement_mut(&mut self, statement: &mut Statement<'a>) {
+        walk_statement_mut(self, statement);
+    }
+
+    /// Visit a string part.
+    fn visit_string_part_mut(&mut self, part: &mut StringPart<'a>) {
+        walk_string_part_mut(self, part);
+    }
+
+    /// Visit a switch case.
+    fn visit_switch_case_mut(&mut self, case: &mut SwitchCase<'a>) {
+        walk_switch_case_mut(self, case);
+    }
+
+    /// Visit a trait adaptation.
+    fn visit_trait_adaptation_mut(&mut self, adaptation: &mut TraitAdaptation<'a>) {
+        walk_trait_adaptation_mut(self, adaptation);
+    }
+
+    /// Visit a type.
+    fn visit_type_mut(&mut self, ty: &mut Type<'a>) {
+        walk_type_mut(self, ty);
+    }
+
+    /// Visit a use declaration.
+    fn visit_use_mut(&mut self, use_: &mut Use<'a>) {
+        walk_use_mut(self, use_);
+    }
+
+    /// Visit a variable.
+    fn visit_variable_mut(&mut self, variable: &mut Variable<'a>) {
+        walk_variable_mut(self, variable);
+    }
+}
+
+/// Walk through an expression.
+pub fn walk_expression<'ast, V: Visit<'ast> + ?Sized>(visitor: &mut V, expression: &'ast Expression<'ast>) {
+    match *expression {
+        Expression::AnonymousClass { ref arguments, ref extends, ref implements, ref members } => {
+            for argument in arguments {
+                visitor.visit_argument(argument);
+            }
+
+            if let Some(ref extends) = *extends {
+                visitor.visit_name(extends);
+            }
+
+            for name in implements {
+                visitor.visit_name(name);
+            }
+
+            for member in members {
+                visitor.visit_class_member(member);
+            }
+        },
+
+        Expression::Array(ref items) => {
+            for (key, value) in items {
+                if let Some(ref key) = *key {
+                    visitor.visit_expression(key);
+                }
+
+                visitor.visit_expression(value);
+            }
+        },
+
+        Expression::ArrayAccess { ref array, ref index } => {
+            visitor.visit_expression(array);
+
+            if let Some(ref index) = *index {
+                visitor.visit_expression(index);
+            }
+        },
+
+        Expression::ArrowFunction { ref parameters, ref return_type, ref body, .. } => {
+            for parameter in parameters {
+                visitor.visit_parameter(parameter);
+            }
+
+            if let Some(ref return_type) = *return_type {
+                visitor.visit_type(return_type);
+            }
+
+            visitor.visit_expression(body);
+        },
+
+        Expression::Assignment { ref variable, ref value } => {
+            visitor.visit_expression(variable);
+            visitor.visit_expression(value);
+        },
+
+        Expression::Call { ref callee, ref arguments } |
+        Expression::New { class: ref callee, ref arguments } => {
+            visitor.visit_expression(callee);
+
+            for argument in arguments {
+                visitor.visit_argument(argument);
+            }
+        },
+
+        Expression::CallableCreation(ref operand) |
+        Expression::Cast { ref operand, .. } |
+        Expression::ClassNameConstant(ref operand) |
+        Expression::Clone(ref operand) |
+        Expression::Empty(ref operand) |
+        Expression::Eval(ref operand) |
+        Expression::Include { path: ref operand, .. } |
+        Expression::Negation(ref operand) |
+        Expression::PostDecrement(ref operand) |
+        Expression::PostIncrement(ref operand) |
+        Expression::PreDecrement(ref operand) |
+        Expression::PreIncrement(ref operand) |
+        Expression::Print(ref operand) |
+        Expression::Reference(ref operand) |
+        Expression::Throw(ref operand) |
+        Expression::YieldFrom(ref operand) => {
+            visitor.visit_expression(operand);
+        },
+
+        Expression::Closure { ref parameters, ref captures, ref return_type, ref body, .. } => {
+            for parameter in parameters {
+                visitor.visit_parameter(parameter);
+            }
+
+            for capture in captures {
+                visitor.visit_expression(capture);
+            }
+
+            if let Some(ref return_type) = *return_type {
+                visitor.visit_type(return_type);
+            }
+
+            walk_statements(visitor, body);
+        },
+
+        Expression::DynamicClassConstant { class: ref left, name: ref right } |
+        Expression::InstanceOf { subject: ref left, class: ref right } |
+        Expression::NullsafePropertyFetch { object: ref left, property: ref right } |
+        Expression::Power { base: ref left, exponent: ref right } |
+        Expression::PropertyFetch { object: ref left, property: ref right } |
+        Expression::StaticFetch { class: ref left, member: ref right } => {
+            visitor.visit_expression(left);
+            visitor.visit_expression(right);
+        },
+
+        Expression::Echo(ref expressions) |
+        Expression::Isset(ref expressions) |
+        Expression::Unset(ref expressions) => {
+            for expression in expressions {
+                visitor.visit_expression(expression);
+            }
+        },
+
+        Expression::Exit(ref operand) => {
+            if let Some(ref operand) = *operand {
+                visitor.visit_expression(operand);
+            }
+        },
+
+        Expression::Heredoc { ref parts, .. } |
+        Expression::InterpolatedString(ref parts) |
+        Expression::ShellExec(ref parts) => {
+            for part in parts {
+                visitor.visit_string_part(part);
+            }
+        },
+
+        Expression::List(ref items) |
+        Expression::ShortList(ref items) => {
+            for item in items {
+                if let Some((ref key, ref value)) = *item {
+                    if let Some(ref key) = *key {
+                        visitor.visit_expression(key);
+                    }
+
+                    visitor.visit_expression(value);
+                }
+            }
+        },
+
+        Expression::Literal(ref literal) => {
+            visitor.visit_literal(literal);
+        },
+
+        Expression::Match { ref subject, ref arms } => {
+            visitor.visit_expression(subject);
+
+            for arm in arms {
+                visitor.visit_match_arm(arm);
+            }
+        },
+
+        Expression::MethodCall { ref object, ref method, ref arguments } |
+        Expression::NullsafeMethodCall { ref object, ref method, ref arguments } |
+        Expression::StaticMethodCall { class: ref object, ref method, ref arguments } => {
+            visitor.visit_expression(object);
+            visitor.visit_expression(method);
+
+            for argument in arguments {
+                visitor.visit_argument(argument);
+            }
+        },
+
+        Expression::Name(ref name) => {
+            visitor.visit_name(name);
+        },
+
+        Expression::Variable(ref variable) => {
+            visitor.visit_variable(variable);
+        },
+
+        Expression::Yield { ref key, ref value } => {
+            if let Some(ref key) = *key {
+                visitor.visit_expression(key);
+            }
+
+            if let Some(ref value) = *value {
+                visitor.visit_expression(value);
+            }
+        },
+
+        Expression::MagicConstant(_) |
+        Expression::RelativeScope(_) => ()
+    }
+}
+
+/// Walk through a statement.
+pub fn walk_statement<'ast, V: Visit<'ast> + ?Sized>(visitor: &mut V, statement: &'ast Statement<'ast>) {
+    match *statement {
+        Statement::Block(ref statements) => {
+            walk_statements(visitor, statements);
+        },
+
+        Statement::Class { ref attributes, ref extends, ref implements, ref members, .. } => {
+            for attribute in attributes {
+                visitor.visit_attribute(attribute);
+            }
+
+            if let Some(ref extends) = *extends {
+                visitor.visit_name(extends);
+            }
+
+            for name in implements {
+                visitor.visit_name(name);
+            }
+
+            for member in members {
+                visitor.visit_class_member(member);
+            }
+        },
+
+        Statement::Constant(ref constants) => {
+            for (_, value) in constants {
+                visitor.visit_constant_expression(value);
+            }
+        },
+
+        Statement::Declare { ref body, .. } => {
+            if let Some(ref body) = *body {
+                visitor.visit_statement(body);
+            }
+        },
+
+        Statement::DoWhile { ref body, ref condition } |
+        Statement::While { ref body, ref condition } => {
+            visitor.visit_expression(condition);
+            visitor.visit_statement(body);
+        },
+
+        Statement::Echo(ref expressions) => {
+            for expression in expressions {
+                visitor.visit_expression(expression);
+            }
+        },
+
+        Statement::Enum { ref attributes, ref backing_type, ref implements, ref members, .. } => {
+            for attribute in attributes {
+                visitor.visit_attribute(attribute);
+            }
+
+            if let Some(ref backing_type) = *backing_type {
+                visitor.visit_type(backing_type);
+            }
+
+            for name in implements {
+                visitor.visit_name(name);
+            }
+
+            for member in members {
+                visitor.visit_class_member(member);
+            }
+        },
+
+        Statement::Expression(ref expression) => {
+            visitor.visit_expression(expression);
+        },
+
+        Statement::For { ref init, ref condition, ref step, ref body } => {
+            for expression in init {
+                visitor.visit_expression(expression);
+            }
+
+            for expression in condition {
+                visitor.visit_expression(expression);
+            }
+
+            for expression in step {
+                visitor.visit_expression(expression);
+            }
+
+            visitor.visit_statement(body);
+        },
+
+        Statement::Foreach { ref subject, ref key, ref value, ref body, .. } => {
+            visitor.visit_expression(subject);
+
+            if let Some(ref key) = *key {
+                visitor.visit_expression(key);
+            }
+
+            visitor.visit_expression(value);
+            visitor.visit_statement(body);
+        },
+
+        Statement::Function { ref attributes, ref parameters, ref return_type, ref body, .. } => {
+            for attribute in attributes {
+                visitor.visit_attribute(attribute);
+            }
+
+            for parameter in parameters {
+                visitor.visit_parameter(parameter);
+            }
+
+            if let Some(ref return_type) = *return_type {
+                visitor.visit_type(return_type);
+            }
+
+            walk_statements(visitor, body);
+        },
+
+        Statement::Global(ref variables) => {
+            for variable in variables {
+                visitor.visit_variable(variable);
+            }
+        },
+
+        Statement::If { ref condition, ref then, ref elseifs, ref otherwise } => {
+            visitor.visit_expression(condition);
+            visitor.visit_statement(then);
+
+            for (condition, body) in elseifs {
+                visitor.visit_expression(condition);
+                visitor.visit_statement(body);
+            }
+
+            if let Some(ref otherwise) = *otherwise {
+                visitor.visit_statement(otherwise);
+            }
+        },
+
+        Statement::Interface { ref attributes, ref extends, ref members, .. } => {
+            for attribute in attributes {
+                visitor.visit_attribute(attribute);
+            }
+
+            for name in extends {
+                visitor.visit_name(name);
+            }
+
+            for member in members {
+                visitor.visit_class_member(member);
+            }
+        },
+
+        Statement::Namespace { ref name, ref body } => {
+            if let Some(ref name) = *name {
+                visitor.visit_name(name);
+            }
+
+            if let Some(ref body) = *body {
+                walk_statements(visitor, body);
+            }
+        },
+
+        Statement::Return(ref value) => {
+            if let Some(ref value) = *value {
+                visitor.visit_expression(value);
+            }
+        },
+
+        Statement::StaticVariables(ref variables) => {
+            for (variable, value) in variables {
+                visitor.visit_variable(variable);
+
+                if let Some(ref value) = *value {
+                    visitor.visit_expression(value);
+                }
+            }
+        },
+
+        Statement::Switch { ref subject, ref cases } => {
+            visitor.visit_expression(subject);
+
+            for case in cases {
+                visitor.visit_switch_case(case);
+            }
+        },
+
+        Statement::Trait { ref attributes, ref members, .. } => {
+            for attribute in attributes {
+                visitor.visit_attribute(attribute);
+            }
+
+            for member in members {
+                visitor.visit_class_member(member);
+            }
+        },
+
+        Statement::Try { ref body, ref catches, ref finally } => {
+            walk_statements(visitor, body);
+
+            for catch in catches {
+                visitor.visit_catch(catch);
+            }
+
+            if let Some(ref finally) = *finally {
+                walk_statements(visitor, finally);
+            }
+        },
+
+        Statement::Use(ref uses) => {
+            for use_ in uses {
+                visitor.visit_use(use_);
+            }
+        },
+
+        Statement::Break(_) |
+        Statement::Continue(_) |
+        Statement::Empty |
+        Statement::Error(_) |
+        Statement::Goto(_) |
+        Statement::HaltCompiler { .. } |
+        Statement::InlineHtml(_) |
+        Statement::Label(_) => ()
+    }
+}
+
+/// Walk through statements.
+pub fn walk_statements<'ast, V: Visit<'ast> + ?Sized>(visitor: &mut V, statements: &'ast [Spanned<Statement<'ast>>]) {
+    for statement in statements {
+        visitor.visit_statement(statement);
+    }
+}
+
+/// Walk through a class member.
+pub fn walk_class_member<'ast, V: Visit<'ast> + ?Sized>(visitor: &mut V, member: &'ast ClassMember<'ast>) {
+    match *member {
+        ClassMember::Case { ref attributes, ref value, .. } => {
+            for attribute in attributes {
+                visitor.visit_attribute(attribute);
+            }
+
+            if let Some(ref value) = *value {
+                visitor.visit_constant_expression(value);
+            }
+        },
+
+        ClassMember::Constant { ref attributes, ref ty, ref constants, .. } => {
+            for attribute in attributes {
+                visitor.visit_attribute(attribute);
+            }
+
+            if let Some(ref ty) = *ty {
+                visitor.visit_type(ty);
+            }
+
+            for (_, value) in constants {
+                visitor.visit_constant_expression(value);
+            }
+        },
+
+        ClassMember::Method(ref method) => {
+            visitor.visit_method(method);
+        },
+
+        ClassMember::Property { ref attributes, ref ty, ref properties, ref hooks, .. } => {
+            for attribute in attributes {
+                visitor.visit_attribute(attribute);
+            }
+
+            if let Some(ref ty) = *ty {
+                visitor.visit_type(ty);
+            }
+
+            for (variable, value) in properties {
+                visitor.visit_variable(variable);
+
+                if let Some(ref value) = *value {
+                    visitor.visit_constant_expression(value);
+                }
+            }
+
+            for hook in hooks {
+                visitor.visit_property_hook(hook);
+            }
+        },
+
+        ClassMember::TraitUse { ref traits, ref adaptations } => {
+            for name in traits {
+                visitor.visit_name(name);
+            }
+
+            for adaptation in adaptations {
+                visitor.visit_trait_adaptation(adaptation);
+            }
+        }
+    }
+}
+
+/// Walk through a method.
+pub fn walk_method<'ast, V: Visit<'ast> + ?Sized>(visitor: &mut V, method: &'ast Method<'ast>) {
+    for attribute in &method.attributes {
+        visitor.visit_attribute(attribute);
+    }
+
+    for parameter in &method.parameters {
+        visitor.visit_parameter(parameter);
+    }
+
+    if let Some(ref return_type) = method.return_type {
+        visitor.visit_type(return_type);
+    }
+
+    if let Some(ref body) = method.body {
+        walk_statements(visitor, body);
+    }
+}
+
+/// Walk through a parameter.
+pub fn walk_parameter<'ast, V: Visit<'ast> + ?Sized>(visitor: &mut V, parameter: &'ast Parameter<'ast>) {
+    for attribute in &parameter.attributes {
+        visitor.visit_attribute(attribute);
+    }
+
+    if let Some(ref ty) = parameter.ty {
+        visitor.visit_type(ty);
+    }
+
+    visitor.visit_variable(&parameter.name);
+
+    if let Some(ref value) = parameter.value {
+        visitor.visit_constant_expression(value);
+    }
+
+    for hook in &parameter.hooks {
+        visitor.visit_property_hook(hook);
+    }
+}
+
+/// Walk through a property hook.
+pub fn walk_property_hook<'ast, V: Visit<'ast> + ?Sized>(visitor: &mut V, hook: &'ast PropertyHook<'ast>) {
+    for attribute in &hook.attributes {
+        visitor.visit_attribute(attribute);
+    }
+
+    for parameter in &hook.parameters {
+        visitor.visit_parameter(parameter);
+    }
+
+    match hook.body {
+        PropertyHookBody::Block(ref statements) => walk_statements(visitor, statements),
+        PropertyHookBody::Expression(ref expression) => visitor.visit_expression(expression)
+    }
+}
+
+/// Walk through a trait adaptation.
+pub fn walk_trait_adaptation<'ast, V: Visit<'ast> + ?Sized>(visitor: &mut V, adaptation: &'ast TraitAdaptation<'ast>) {
+    match *adaptation {
+        TraitAdaptation::Alias { ref trait_name, .. } => {
+            if let Some(ref trait_name) = *trait_name {
+                visitor.visit_name(trait_name);
+            }
+        },
+
+        TraitAdaptation::Insteadof { ref trait_name, ref excluded, .. } => {
+            visitor.visit_name(trait_name);
+
+            for name in excluded {
+                visitor.visit_name(name);
+            }
+        }
+    }
+}
+
+/// Walk through an argument.
+pub fn walk_argument<'ast, V: Visit<'ast> + ?Sized>(visitor: &mut V, argument: &'ast Argument<'ast>) {
+    visitor.visit_expression(&argument.value);
+}
+
+/// Walk through an attribute.
+pub fn walk_attribute<'ast, V: Visit<'ast> + ?Sized>(visitor: &mut V, attribute: &'ast Attribute<'ast>) {
+    visitor.visit_name(&attribute.name);
+
+    for argument in &attribute.arguments {
+        visitor.visit_argument(argument);
+    }
+}
+
+/// Walk through a catch clause.
+pub fn walk_catch<'ast, V: Visit<'ast> + ?Sized>(visitor: &mut V, catch: &'ast Catch<'ast>) {
+    for name in &catch.types {
+        visitor.visit_name(name);
+    }
+
+    if let Some(ref variable) = catch.variable {
+        visitor.visit_variable(variable);
+    }
+
+    walk_statements(visitor, &catch.body);
+}
+
+/// Walk through a constant expression.
+pub fn walk_constant_expression<'ast, V: Visit<'ast> + ?Sized>(visitor: &mut V, constant: &'ast ConstantExpression<'ast>) {
+    visitor.visit_expression(&constant.0);
+}
+
+/// Walk through a match arm.
+pub fn walk_match_arm<'ast, V: Visit<'ast> + ?Sized>(visitor: &mut V, arm: &'ast MatchArm<'ast>) {
+    if let Some(ref conditions) = arm.conditions {
+        for condition in conditions {
+            visitor.visit_expression(condition);
+        }
+    }
+
+    visitor.visit_expression(&arm.body);
+}
+
+/// Walk through a string part.
+pub fn walk_string_part<'ast, V: Visit<'ast> + ?Sized>(visitor: &mut V, part: &'ast StringPart<'ast>) {
+    if let StringPart::Expression(ref expression) = *part {
+        visitor.visit_expression(expression);
+    }
+}
+
+/// Walk through a switch case.
+pub fn walk_switch_case<'ast, V: Visit<'ast> + ?Sized>(visitor: &mut V, case: &'ast SwitchCase<'ast>) {
+    if let Some(ref condition) = case.condition {
+        visitor.visit_expression(condition);
+    }
+
+    walk_statements(visitor, &case.body);
+}
+
+/// Walk through a type.
+pub fn walk_type<'ast, V: Visit<'ast> + ?Sized>(visitor: &mut V, ty: &'ast Type<'ast>) {
+    match *ty {
+        Type::Intersection(ref types) |
+        Type::Union(ref types) => {
+            for ty in types {
+                visitor.visit_type(ty);
+            }
+        },
+
+        Type::Name(ref name) => {
+            visitor.visit_name(name);
+        },
+
+        Type::Nullable(ref ty) => {
+            visitor.visit_type(ty);
+        },
+
+        _ => ()
+    }
+}
+
+/// Walk through a use declaration.
+pub fn walk_use<'ast, V: Visit<'ast> + ?Sized>(visitor: &mut V, use_: &'ast Use<'ast>) {
+    visitor.visit_name(&use_.name);
+}
+
+/// Walk through a variable.
+pub fn walk_variable<'ast, V: Visit<'ast> + ?Sized>(visitor: &mut V, variable: &'ast Variable<'ast>) {
+    if let Variable::Dynamic(ref expression) = *variable {
+        visitor.visit_expression(expression);
+    }
+}
+
+/// Walk through an expression, by mutable reference.
+pub fn walk_expression_mut<'a, V: VisitMut<'a> + ?Sized>(visitor: &mut V, expression: &mut Expression<'a>) {
+    match *expression {
+        Expression::AnonymousClass { ref mut arguments, ref mut extends, ref mut implements, ref mut members } => {
+            for argument in arguments {
+                visitor.visit_argument_mut(argument);
+            }
+
+            if let Some(ref mut extends) = *extends {
+                visitor.visit_name_mut(extends);
+            }
+
+            for name in implements {
+                visitor.visit_name_mut(name);
+            }
+
+            for member in members {
+                visitor.visit_class_member_mut(member);
+            }
+        },
+
+        Expression::Array(ref mut items) => {
+            for &mut (ref mut key, ref mut value) in items {
+                if let Some(ref mut key) = *key {
+                    visitor.visit_expression_mut(key);
+                }
+
+                visitor.visit_expression_mut(value);
+            }
+        },
+
+        Expression::ArrayAccess { ref mut array, ref mut index } => {
+            visitor.visit_expression_mut(array);
+
+            if let Some(ref mut index) = *index {
+                visitor.visit_expression_mut(index);
+            }
+        },
+
+        Expression::ArrowFunction { ref mut parameters, ref mut return_type, ref mut body, .. } => {
+            for parameter in parameters {
+                visitor.visit_parameter_mut(parameter);
+            }
+
+            if let Some(ref mut return_type) = *return_type {
+                visitor.visit_type_mut(return_type);
+            }
+
+            visitor.visit_expression_mut(body);
+        },
+
+        Expression::Assignment { ref mut variable, ref mut value } => {
+            visitor.visit_expression_mut(variable);
+            visitor.visit_expression_mut(value);
+        },
+
+        Expression::Call { ref mut callee, ref mut arguments } |
+        Expression::New { class: ref mut callee, ref mut arguments } => {
+            visitor.visit_expression_mut(callee);
+
+            for argument in arguments {
+                visitor.visit_argument_mut(argument);
+            }
+        },
+
+        Expression::CallableCreation(ref mut operand) |
+        Expression::Cast { ref mut operand, .. } |
+        Expression::ClassNameConstant(ref mut operand) |
+        Expression::Clone(ref mut operand) |
+        Expression::Empty(ref mut operand) |
+        Expression::Eval(ref mut operand) |
+        Expression::Include { path: ref mut operand, .. } |
+        Expression::Negation(ref mut operand) |
+        Expression::PostDecrement(ref mut operand) |
+        Expression::PostIncrement(ref mut operand) |
+        Expression::PreDecrement(ref mut operand) |
+        Expression::PreIncrement(ref mut operand) |
+        Expression::Print(ref mut operand) |
+        Expression::Reference(ref mut operand) |
+        Expression::Throw(ref mut operand) |
+        Expression::YieldFrom(ref mut operand) => {
+            visitor.visit_expression_mut(operand);
+        },
+
+        Expression::Closure { ref mut parameters, ref mut captures, ref mut return_type, ref mut body, .. } => {
+            for parameter in parameters {
+                visitor.visit_parameter_mut(parameter);
+            }
+
+            for capture in captures {
+                visitor.visit_expression_mut(capture);
+            }
+
+            if let Some(ref mut return_type) = *return_type {
+                visitor.visit_type_mut(return_type);
+            }
+
+            walk_statements_mut(visitor, body);
+        },
+
+        Expression::DynamicClassConstant { class: ref mut left, name: ref mut right } |
+        Expression::InstanceOf { subject: ref mut left, class: ref mut right } |
+        Expression::NullsafePropertyFetch { object: ref mut left, property: ref mut right } |
+        Expression::Power { base: ref mut left, exponent: ref mut right } |
+        Expression::PropertyFetch { object: ref mut left, property: ref mut right } |
+        Expression::StaticFetch { class: ref mut left, member: ref mut right } => {
+            visitor.visit_expression_mut(left);
+            visitor.visit_expression_mut(right);
+        },
+
+        Expression::Echo(ref mut expressions) |
+        Expression::Isset(ref mut expressions) |
+        Expression::Unset(ref mut expressions) => {
+            for expression in expressions {
+                visitor.visit_expression_mut(expression);
+            }
+        },
+
+        Expression::Exit(ref mut operand) => {
+            if let Some(ref mut operand) = *operand {
+                visitor.visit_expression_mut(operand);
+            }
+        },
+
+        Expression::Heredoc { ref mut parts, .. } |
+        Expression::InterpolatedString(ref mut parts) |
+        Expression::ShellExec(ref mut parts) => {
+            for part in parts {
+                visitor.visit_string_part_mut(part);
+            }
+        },
+
+        Expression::List(ref mut items) |
+        Expression::ShortList(ref mut items) => {
+            for item in items {
+                if let Some((ref mut key, ref mut value)) = *item {
+                    if let Some(ref mut key) = *key {
+                        visitor.visit_expression_mut(key);
+                    }
+
+                    visitor.visit_expression_mut(value);
+                }
+            }
+        },
+
+        Expression::Literal(ref mut literal) => {
+            visitor.visit_literal_mut(literal);
+        },
+
+        Expression::Match { ref mut subject, ref mut arms } => {
+            visitor.visit_expression_mut(subject);
+
+            for arm in arms {
+                visitor.visit_match_arm_mut(arm);
+            }
+        },
+
+        Expression::MethodCall { ref mut object, ref mut method, ref mut arguments } |
+        Expression::NullsafeMethodCall { ref mut object, ref mut method, ref mut arguments } |
+        Expression::StaticMethodCall { class: ref mut object, ref mut method, ref mut arguments } => {
+            visitor.visit_expression_mut(object);
+            visitor.visit_expression_mut(method);
+
+            for argument in arguments {
+                visitor.visit_argument_mut(argument);
+            }
+        },
+
+        Expression::Name(ref mut name) => {
+            visitor.visit_name_mut(name);
+        },
+
+        Expression::Variable(ref mut variable) => {
+            visitor.visit_variable_mut(variable);
+        },
+
+        Expression::Yield { ref mut key, ref mut value } => {
+            if let Some(ref mut key) = *key {
+                visitor.visit_expression_mut(key);
+            }
+
+            if let Some(ref mut value) = *value {
+                visitor.visit_expression_mut(value);
+            }
+        },
+
+        Expression::MagicConstant(_) |
+        Expression::RelativeScope(_) => ()
+    }
+}
+
+/// Walk through a statement, by mutable reference.
+pub fn walk_statement_mut<'a, V: VisitMut<'a> + ?Sized>(visitor: &mut V, statement: &mut Statement<'a>) {
+    match *statement {
+        Statement::Block(ref mut statements) => {
+            walk_statements_mut(visitor, statements);
+        },
+
+        Statement::Class { ref mut attributes, ref mut extends, ref mut implements, ref mut members, .. } => {
+            for attribute in attributes {
+                visitor.visit_attribute_mut(attribute);
+            }
+
+            if let Some(ref mut extends) = *extends {
+                visitor.visit_name_mut(extends);
+            }
+
+            for name in implements {
+                visitor.visit_name_mut(name);
+            }
+
+            for member in members {
+                visitor.visit_class_member_mut(member);
+            }
+        },
+
+        Statement::Constant(ref mut constants) => {
+            for &mut (_, ref mut value) in constants {
+                visitor.visit_constant_expression_mut(value);
+            }
+        },
+
+        Statement::Declare { ref mut body, .. } => {
+            if let Some(ref mut body) = *body {
+                visitor.visit_statement_mut(body);
+            }
+        },
+
+        Statement::DoWhile { ref mut body, ref mut condition } |
+        Statement::While { ref mut body, ref mut condition } => {
+            visitor.visit_expression_mut(condition);
+            visitor.visit_statement_mut(body);
+        },
+
+        Statement::Echo(ref mut expressions) => {
+            for expression in expressions {
+                visitor.visit_expression_mut(expression);
+            }
+        },
+
+        Statement::Enum { ref mut attributes, ref mut backing_type, ref mut implements, ref mut members, .. } => {
+            for attribute in attributes {
+                visitor.visit_attribute_mut(attribute);
+            }
+
+            if let Some(ref mut backing_type) = *backing_type {
+                visitor.visit_type_mut(backing_type);
+            }
+
+            for name in implements {
+                visitor.visit_name_mut(name);
+            }
+
+            for member in members {
+                visitor.visit_class_member_mut(member);
+            }
+        },
+
+        Statement::Expression(ref mut expression) => {
+            visitor.visit_expression_mut(expression);
+        },
+
+        Statement::For { ref mut init, ref mut condition, ref mut step, ref mut body } => {
+            for expression in init {
+                visitor.visit_expression_mut(expression);
+            }
+
+            for expression in condition {
+                visitor.visit_expression_mut(expression);
+            }
+
+            for expression in step {
+                visitor.visit_expression_mut(expression);
+            }
+
+            visitor.visit_statement_mut(body);
+        },
+
+        Statement::Foreach { ref mut subject, ref mut key, ref mut value, ref mut body, .. } => {
+            visitor.visit_expression_mut(subject);
+
+            if let Some(ref mut key) = *key {
+                visitor.visit_expression_mut(key);
+            }
+
+            visitor.visit_expression_mut(value);
+            visitor.visit_statement_mut(body);
+        },
+
+        Statement::Function { ref mut attributes, ref mut parameters, ref mut return_type, ref mut body, .. } => {
+            for attribute in attributes {
+                visitor.visit_attribute_mut(attribute);
+            }
+
+            for parameter in parameters {
+                visitor.visit_parameter_mut(parameter);
+            }
+
+            if let Some(ref mut return_type) = *return_type {
+                visitor.visit_type_mut(return_type);
+            }
+
+            walk_statements_mut(visitor, body);
+        },
+
+        Statement::Global(ref mut variables) => {
+            for variable in variables {
+                visitor.visit_variable_mut(variable);
+            }
+        },
+
+        Statement::If { ref mut condition, ref mut then, ref mut elseifs, ref mut otherwise } => {
+            visitor.visit_expression_mut(condition);
+            visitor.visit_statement_mut(then);
+
+            for &mut (ref mut condition, ref mut body) in elseifs {
+                visitor.visit_expression_mut(condition);
+                visitor.visit_statement_mut(body);
+            }
+
+            if let Some(ref mut otherwise) = *otherwise {
+                visitor.visit_statement_mut(otherwise);
+            }
+        },
+
+        Statement::Interface { ref mut attributes, ref mut extends, ref mut members, .. } => {
+            for attribute in attributes {
+                visitor.visit_attribute_mut(attribute);
+            }
+
+            for name in extends {
+                visitor.visit_name_mut(name);
+            }
+
+            for member in members {
+                visitor.visit_class_member_mut(member);
+            }
+        },
+
+        Statement::Namespace { ref mut name, ref mut body } => {
+            if let Some(ref mut name) = *name {
+                visitor.visit_name_mut(name);
+            }
+
+            if let Some(ref mut body) = *body {
+                walk_statements_mut(visitor, body);
+            }
+        },
+
+        Statement::Return(ref mut value) => {
+            if let Some(ref mut value) = *value {
+                visitor.visit_expression_mut(value);
+            }
+        },
+
+        Statement::StaticVariables(ref mut variables) => {
+            for &mut (ref mut variable, ref mut value) in variables {
+                visitor.visit_variable_mut(variable);
+
+                if let Some(ref mut value) = *value {
+                    visitor.visit_expression_mut(value);
+                }
+            }
+        },
+
+        Statement::Switch { ref mut subject, ref mut cases } => {
+            visitor.visit_expression_mut(subject);
+
+            for case in cases {
+                visitor.visit_switch_case_mut(case);
+            }
+        },
+
+        Statement::Trait { ref mut attributes, ref mut members, .. } => {
+            for attribute in attributes {
+                visitor.visit_attribute_mut(attribute);
+            }
+
+            for member in members {
+                visitor.visit_class_member_mut(member);
+            }
+        },
+
+        Statement::Try { ref mut body, ref mut catches, ref mut finally } => {
+            walk_statements_mut(visitor, body);
+
+            for catch in catches {
+                visitor.visit_catch_mut(catch);
+            }
+
+            if let Some(ref mut finally) = *finally {
+                walk_statements_mut(visitor, finally);
+            }
+        },
+
+        Statement::Use(ref mut uses) => {
+            for use_ in uses {
+                visitor.visit_use_mut(use_);
+            }
+        },
+
+        Statement::Break(_) |
+        Statement::Continue(_) |
+        Statement::Empty |
+        Statement::Error(_) |
+        Statement::Goto(_) |
+        Statement::HaltCompiler { .. } |
+        Statement::InlineHtml(_) |
+        Statement::Label(_) => ()
+    }
+}
+
+/// Walk through statements, by mutable reference.
+pub fn walk_statements_mut<'a, V: VisitMut<'a> + ?Sized>(visitor: &mut V, statements: &mut [Spanned<Statement<'a>>]) {
+    for statement in statements {
+        visitor.visit_statement_mut(statement);
+    }
+}
+
+/// Walk through a class member, by mutable reference.
+pub fn walk_class_member_mut<'a, V: VisitMut<'a> + ?Sized>(visitor: &mut V, member: &mut ClassMember<'a>) {
+    match *member {
+        ClassMember::Case { ref mut attributes, ref mut value, .. } => {
+            for attribute in attributes {
+                visitor.visit_attribute_mut(attribute);
+            }
+
+            if let Some(ref mut value) = *value {
+                visitor.visit_constant_expression_mut(value);
+            }
+        },
+
+        ClassMember::Constant { ref mut attributes, ref mut ty, ref mut constants, .. } => {
+            for attribute in attributes {
+                visitor.visit_attribute_mut(attribute);
+            }
+
+            if let Some(ref mut ty) = *ty {
+                visitor.visit_type_mut(ty);
+            }
+
+            for &mut (_, ref mut value) in constants {
+                visitor.visit_constant_expression_mut(value);
+            }
+        },
+
+        ClassMember::Method(ref mut method) => {
+            visitor.visit_method_mut(method);
+        },
+
+        ClassMember::Property { ref mut attributes, ref mut ty, ref mut properties, ref mut hooks, .. } => {
+            for attribute in attributes {
+                visitor.visit_attribute_mut(attribute);
+            }
+
+            if let Some(ref mut ty) = *ty {
+                visitor.visit_type_mut(ty);
+            }
+
+            for &mut (ref mut variable, ref mut value) in properties {
+                visitor.visit_variable_mut(variable);
+
+                if let Some(ref mut value) = *value {
+                    visitor.visit_constant_expression_mut(value);
+                }
+            }
+
+            for hook in hooks {
+                visitor.visit_property_hook_mut(hook);
+            }
+        },
+
+        ClassMember::TraitUse { ref mut traits, ref mut adaptations } => {
+            for name in traits {
+                visitor.visit_name_mut(name);
+            }
+
+            for adaptation in adaptations {
+                visitor.visit_trait_adaptation_mut(adaptation);
+            }
+        }
+    }
+}
+
+/// Walk through a method, by mutable reference.
+pub fn walk_method_mut<'a, V: VisitMut<'a> + ?Sized>(visitor: &mut V, method: &mut Method<'a>) {
+    for attribute in &mut method.attributes {
+        visitor.visit_attribute_mut(attribute);
+    }
+
+    for parameter in &mut method.parameters {
+        visitor.visit_parameter_mut(parameter);
+    }
+
+    if let Some(ref mut return_type) = method.return_type {
+        visitor.visit_type_mut(return_type);
+    }
+
+    if let Some(ref mut body) = method.body {
+        walk_statements_mut(visitor, body);
+    }
+}
+
+/// Walk through a parameter, by mutable reference.
+pub fn walk_parameter_mut<'a, V: VisitMut<'a> + ?Sized>(visitor: &mut V, parameter: &mut Parameter<'a>) {
+    for attribute in &mut parameter.attributes {
+        visitor.visit_attribute_mut(attribute);
+    }
+
+    if let Some(ref mut ty) = parameter.ty {
+        visitor.visit_type_mut(ty);
+    }
+
+    visitor.visit_variable_mut(&mut parameter.name);
+
+    if let Some(ref mut value) = parameter.value {
+        visitor.visit_constant_expression_mut(value);
+    }
+
+    for hook in &mut parameter.hooks {
+        visitor.visit_property_hook_mut(hook);
+    }
+}
+
+/// Walk through a property hook, by mutable reference.
+pub fn walk_property_hook_mut<'a, V: VisitMut<'a> + ?Sized>(visitor: &mut V, hook: &mut PropertyHook<'a>) {
+    for attribute in &mut hook.attributes {
+        visitor.visit_attribute_mut(attribute);
+    }
+
+    for parameter in &mut hook.parameters {
+        visitor.visit_parameter_mut(parameter);
+    }
+
+    match hook.body {
+        PropertyHookBody::Block(ref mut statements) => walk_statements_mut(visitor, statements),
+        PropertyHookBody::Expression(ref mut expression) => visitor.visit_expression_mut(expression)
+    }
+}
+
+/// Walk through a trait adaptation, by mutable reference.
+pub fn walk_trait_adaptation_mut<'a, V: VisitMut<'a> + ?Sized>(visitor: &mut V, adaptation: &mut TraitAdaptation<'a>) {
+    match *adaptation {
+        TraitAdaptation::Alias { ref mut trait_name, .. } => {
+            if let Some(ref mut trait_name) = *trait_name {
+                visitor.visit_name_mut(trait_name);
+            }
+        },
+
+        TraitAdaptation::Insteadof { ref mut trait_name, ref mut excluded, .. } => {
+            visitor.visit_name_mut(trait_name);
+
+            for name in excluded {
+                visitor.visit_name_mut(name);
+            }
+        }
+    }
+}
+
+/// Walk through an argument, by mutable reference.
+pub fn walk_argument_mut<'a, V: VisitMut<'a> + ?Sized>(visitor: &mut V, argument: &mut Argument<'a>) {
+    visitor.visit_expression_mut(&mut argument.value);
+}
+
+/// Walk through an attribute, by mutable reference.
+pub fn walk_attribute_mut<'a, V: VisitMut<'a> + ?Sized>(visitor: &mut V, attribute: &mut Attribute<'a>) {
+    visitor.visit_name_mut(&mut attribute.name);
+
+    for argument in &mut attribute.arguments {
+        visitor.visit_argument_mut(argument);
+    }
+}
+
+/// Walk through a catch clause, by mutable reference.
+pub fn walk_catch_mut<'a, V: VisitMut<'a> + ?Sized>(visitor: &mut V, catch: &mut Catch<'a>) {
+    for name in &mut catch.types {
+        visitor.visit_name_mut(name);
+    }
+
+    if let Some(ref mut variable) = catch.variable {
+        visitor.visit_variable_mut(variable);
+    }
+
+    walk_statements_mut(visitor, &mut catch.body);
+}
+
+/// Walk through a constant expression, by mutable reference.
+pub fn walk_constant_expression_mut<'a, V: VisitMut<'a> + ?Sized>(visitor: &mut V, constant: &mut ConstantExpression<'a>) {
+    visitor.visit_expression_mut(&mut constant.0);
+}
+
+/// Walk through a match arm, by mutable reference.
+pub fn walk_match_arm_mut<'a, V: VisitMut<'a> + ?Sized>(visitor: &mut V, arm: &mut MatchArm<'a>) {
+    if let Some(ref mut conditions) = arm.conditions {
+        for condition in conditions {
+            visitor.visit_expression_mut(condition);
+        }
+    }
+
+    visitor.visit_expression_mut(&mut arm.body);
+}
+
+/// Walk through a string part, by mutable reference.
+pub fn walk_string_part_mut<'a, V: VisitMut<'a> + ?Sized>(visitor: &mut V, part: &mut StringPart<'a>) {
+    if let StringPart::Expression(ref mut expression) = *part {
+        visitor.visit_expression_mut(expression);
+    }
+}
+
+/// Walk through a switch case, by mutable reference.
+pub fn walk_switch_case_mut<'a, V: VisitMut<'a> + ?Sized>(visitor: &mut V, case: &mut SwitchCase<'a>) {
+    if let Some(ref mut condition) = case.condition {
+        visitor.visit_expression_mut(condition);
+    }
+
+    walk_statements_mut(visitor, &mut case.body);
+}
+
+/// Walk through a type, by mutable reference.
+pub fn walk_type_mut<'a, V: VisitMut<'a> + ?Sized>(visitor: &mut V, ty: &mut Type<'a>) {
+    match *ty {
+        Type::Intersection(ref mut types) |
+        Type::Union(ref mut types) => {
+            for ty in types {
+                visitor.visit_type_mut(ty);
+            }
+        },
+
+        Type::Name(ref mut name) => {
+            visitor.visit_name_mut(name);
+        },
+
+        Type::Nullable(ref mut ty) => {
+            visitor.visit_type_mut(ty);
+        },
+
+        _ => ()
+    }
+}
+
+/// Walk through a use declaration, by mutable reference.
+pub fn walk_use_mut<'a, V: VisitMut<'a> + ?Sized>(visitor: &mut V, use_: &mut Use<'a>) {
+    visitor.visit_name_mut(&mut use_.name);
+}
+
+/// Walk through a variable, by mutable reference.
+pub fn walk_variable_mut<'a, V: VisitMut<'a> + ?Sized>(visitor: &mut V, variable: &mut Variable<'a>) {
+    if let Variable::Dynamic(ref mut expression) = *variable {
+        visitor.visit_expression_mut(expression);
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        Visit,
+        VisitMut,
+        walk_expression,
+        walk_variable_mut
+    };
+    use super::super::ast::{
+        Expression,
+        Name,
+        Statement,
+        Variable
+    };
+    use super::super::internal::Result;
+    use super::super::rules::script::script;
+    use super::super::span::Spanned;
+
+    struct Counter {
+        expressions: usize,
+        names      : usize,
+        variables  : usize
+    }
+
+    impl<'ast> Visit<'ast> for Counter {
+        fn visit_expression(&mut self, expression: &'ast Expression<'ast>) {
+            self.expressions += 1;
+
+            walk_expression(self, expression);
+        }
+
+        fn visit_name(&mut self, _name: &'ast Name<'ast>) {
+            self.names += 1;
+        }
+
+        fn visit_variable(&mut self, _variable: &'ast Variable<'ast>) {
+            self.variables += 1;
+        }
+    }
+
+    fn count(input: &[u8]) -> (usize, usize, usize) {
+        let statements = match script(input) {
+            Result::Done(_, statements) => statements,
+            _ => panic!("The script is invalid.")
+        };
+        let mut counter = Counter {
+            expressions: 0,
+            names      : 0,
+            variables  : 0
+        };
+
+        for statement in &statements {
+            counter.visit_statement(statement);
+        }
+
+        (counter.expressions, counter.names, counter.variables)
+    }
+
+    #[test]
+    fn case_visit_nested_statements_and_expressions() {
+        let input = b"<?php while ($a) { foreach ($b as $k => $v) { f($k, [$v]); } }";
+
+        // Expressions: `$a`, `$b`, `$k`, `$v`, `f(…)`, `f`, `$k`, `[$v]`, `$v`.
+        assert_eq!(count(input), (9, 1, 6));
+    }
+
+    #[test]
+    fn case_visit_declarations() {
+        let input = b"<?php namespace N; #[A(1)] class C extends P implements I { use T; const X = 1; public ?Foo $p = 2; function m(int|Bar $q) { return $q; } }";
+
+        // Names: `N`, `A`, `P`, `I`, `T`, `Foo`, `Bar`.
+        assert_eq!(count(input), (4, 7, 3));
+    }
+
+    #[test]
+    fn case_visit_without_walking() {
+        struct Statements {
+            count: usize
+        }
+
+        impl<'ast> Visit<'ast> for Statements {
+            fn visit_statement(&mut self, _statement: &'ast Statement<'ast>) {
+                self.count += 1;
+            }
+        }
+
+        let statements = match script(b"<?php { {} } {}") {
+            Result::Done(_, statements) => statements,
+            _ => panic!("The script is invalid.")
+        };
+        let mut visitor = Statements { count: 0 };
+
+        for statement in &statements {
+            visitor.visit_statement(statement);
+        }
+
+        assert_eq!(visitor.count, 2);
+    }
+
+    #[test]
+    fn case_visit_mut() {
+        struct Renamer;
+
+        impl<'a> VisitMut<'a> for Renamer {
+            fn visit_variable_mut(&mut self, variable: &mut Variable<'a>) {
+                if let Variable::Name(_) = *variable {
+                    *variable = Variable::Name(&b"renamed"[..]);
+                }
+
+                walk_variable_mut(self, variable);
+            }
+        }
+
+        let mut statements = match script(b"<?php $a = fn () => $b;") {
+            Result::Done(_, statements) => statements,
+            _ => panic!("The script is invalid.")
+        };
+
+        for statement in &mut statements {
+            Renamer.visit_statement_mut(statement);
+        }
+
+        match statements[0].node {
+            Statement::Expression(Spanned { node: Expression::Assignment { ref variable, ref value }, .. }) => {
+                assert_eq!(**variable, Spanned::from(Expression::Variable(Variable::Name(&b"renamed"[..]))));
+
+                match value.node {
+                    Expression::ArrowFunction { ref body, .. } => {
+                        assert_eq!(**body, Spanned::from(Expression::Variable(Variable::Name(&b"renamed"[..]))));
+                    },
+
+                    _ => panic!("An arrow function was expected.")
+                }
+            },
+
+            _ => panic!("An assignment was expected.")
+        }
+    }
+}